@@ -4,6 +4,7 @@ pub(crate) mod address;
 pub mod cashaddr;
 mod constants;
 mod encoding;
+pub mod silent_payment;
 pub mod xyzpub;
 
 use alloc::string::{String, ToString};
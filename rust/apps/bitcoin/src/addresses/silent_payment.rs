@@ -0,0 +1,252 @@
+// BIP-352 silent payment addresses and the sender side output derivation
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::primitives::iter::{ByteIterExt, Fe32IterExt};
+use bech32::{Bech32m, Fe32, Hrp};
+use bitcoin::consensus::serialize;
+use bitcoin::key::TweakedPublicKey;
+use bitcoin::secp256k1::{PublicKey, Scalar, Secp256k1, SecretKey, XOnlyPublicKey};
+use bitcoin::{OutPoint, ScriptBuf};
+
+use crate::errors::{BitcoinError, Result};
+use crate::multi_sig::musig::tagged_hash;
+use crate::network::Network;
+
+const SP_HRP: &str = "sp";
+const SP_HRP_TEST: &str = "tsp";
+const SP_VERSION_0: u8 = 0;
+const SP_PAYLOAD_LEN: usize = 66;
+
+#[derive(Debug, Clone)]
+pub struct SilentPaymentAddress {
+    pub scan_key: PublicKey,
+    pub spend_key: PublicKey,
+    pub network: Network,
+}
+
+impl SilentPaymentAddress {
+    pub fn new(scan_key: PublicKey, spend_key: PublicKey, network: Network) -> Self {
+        Self {
+            scan_key,
+            spend_key,
+            network,
+        }
+    }
+
+    // PSBT_OUT_SP_V0_INFO is the scan key followed by the spend key
+    pub fn from_keys_bytes(keys: &[u8], network: Network) -> Result<Self> {
+        if keys.len() != SP_PAYLOAD_LEN {
+            return Err(BitcoinError::SilentPaymentError(
+                "invalid silent payment keys length".to_string(),
+            ));
+        }
+        let scan_key = PublicKey::from_slice(&keys[..33])
+            .map_err(|e| BitcoinError::SilentPaymentError(e.to_string()))?;
+        let spend_key = PublicKey::from_slice(&keys[33..])
+            .map_err(|e| BitcoinError::SilentPaymentError(e.to_string()))?;
+        Ok(Self::new(scan_key, spend_key, network))
+    }
+}
+
+impl FromStr for SilentPaymentAddress {
+    type Err = BitcoinError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut checked = CheckedHrpstring::new::<Bech32m>(s)
+            .map_err(|e| BitcoinError::SilentPaymentError(e.to_string()))?;
+        let network = match checked.hrp().to_lowercase().as_str() {
+            SP_HRP => Network::Bitcoin,
+            SP_HRP_TEST => Network::BitcoinTestnet,
+            hrp => {
+                return Err(BitcoinError::SilentPaymentError(format!(
+                    "unknown silent payment hrp {}",
+                    hrp
+                )))
+            }
+        };
+        let version = checked
+            .remove_witness_version()
+            .ok_or(BitcoinError::SilentPaymentError(
+                "missing silent payment version".to_string(),
+            ))?
+            .to_u8();
+        let data = checked.byte_iter().collect::<Vec<u8>>();
+        // later versions are forward compatible and only append data, v31 is reserved
+        let valid = match version {
+            SP_VERSION_0 => data.len() == SP_PAYLOAD_LEN,
+            31 => false,
+            _ => data.len() >= SP_PAYLOAD_LEN,
+        };
+        if !valid {
+            return Err(BitcoinError::SilentPaymentError(
+                "invalid silent payment address".to_string(),
+            ));
+        }
+        Self::from_keys_bytes(&data[..SP_PAYLOAD_LEN], network)
+    }
+}
+
+impl fmt::Display for SilentPaymentAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hrp = match self.network {
+            Network::BitcoinTestnet => Hrp::parse_unchecked(SP_HRP_TEST),
+            _ => Hrp::parse_unchecked(SP_HRP),
+        };
+        let data = [self.scan_key.serialize(), self.spend_key.serialize()].concat();
+        let chars = data
+            .iter()
+            .copied()
+            .bytes_to_fes()
+            .with_checksum::<Bech32m>(&hrp)
+            .with_witness_version(Fe32::Q)
+            .chars();
+        for c in chars {
+            fmt::Write::write_char(f, c)?;
+        }
+        Ok(())
+    }
+}
+
+// `input_keys` are the private keys of every eligible input, taproot keys already tweaked
+// and negated to match their even y output key. Outputs sharing a scan key get increasing k
+// in the order they are given.
+pub fn calculate_silent_payment_scripts(
+    input_keys: &[SecretKey],
+    outpoints: &[OutPoint],
+    recipients: &[(PublicKey, PublicKey)],
+) -> Result<Vec<ScriptBuf>> {
+    let secp = Secp256k1::new();
+    let invalid = |reason: &str| BitcoinError::SilentPaymentError(reason.to_string());
+    let (first, rest) = input_keys
+        .split_first()
+        .ok_or(invalid("no eligible inputs"))?;
+    let input_key = rest.iter().try_fold(*first, |acc, key| {
+        acc.add_tweak(&Scalar::from(*key))
+            .map_err(|_| invalid("sum of input keys is zero"))
+    })?;
+    let smallest_outpoint = outpoints
+        .iter()
+        .map(serialize)
+        .min()
+        .ok_or(invalid("no inputs"))?;
+    let input_hash = tagged_hash(
+        "BIP0352/Inputs",
+        &[&smallest_outpoint, &input_key.public_key(&secp).serialize()],
+    );
+    let input_hash =
+        Scalar::from_be_bytes(input_hash).map_err(|_| invalid("invalid input hash"))?;
+    let ecdh_key = input_key
+        .mul_tweak(&input_hash)
+        .map_err(|_| invalid("invalid input hash"))?;
+
+    let mut counters: Vec<(PublicKey, u32)> = Vec::new();
+    recipients
+        .iter()
+        .map(|(scan_key, spend_key)| {
+            let k = match counters.iter_mut().find(|(key, _)| key == scan_key) {
+                Some((_, k)) => {
+                    *k += 1;
+                    *k
+                }
+                None => {
+                    counters.push((*scan_key, 0));
+                    0
+                }
+            };
+            let shared_secret = scan_key
+                .mul_tweak(&secp, &Scalar::from(ecdh_key))
+                .map_err(|_| invalid("invalid scan key"))?;
+            let t_k = tagged_hash(
+                "BIP0352/SharedSecret",
+                &[&shared_secret.serialize(), &k.to_be_bytes()],
+            );
+            let t_k = Scalar::from_be_bytes(t_k).map_err(|_| invalid("invalid shared secret"))?;
+            let output_key = spend_key
+                .add_exp_tweak(&secp, &t_k)
+                .map_err(|_| invalid("invalid spend key"))?;
+            let (x_only, _): (XOnlyPublicKey, _) = output_key.x_only_public_key();
+            Ok(ScriptBuf::new_p2tr_tweaked(
+                TweakedPublicKey::dangerous_assume_tweaked(x_only),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::Txid;
+
+    const ADDRESS: &str = "sp1qqgste7k9hx0qftg6qmwlkqtwuy6cycyavzmzj85c6qdfhjdpdjtdgqjuexzk6murw56suy3e0rd2cgqvycxttddwsvgxe2usfpxumr70xc9pkqwv";
+
+    #[test]
+    fn test_parse_address() {
+        let address = SilentPaymentAddress::from_str(ADDRESS).unwrap();
+        assert_eq!(
+            "0220bcfac5b99e04ad1a06ddfb016ee13582609d60b6291e98d01a9bc9a16c96d4",
+            address.scan_key.to_string()
+        );
+        assert_eq!(
+            "025cc9856d6f8375350e123978daac200c260cb5b5ae83106cab90484dcd8fcf36",
+            address.spend_key.to_string()
+        );
+        assert!(matches!(address.network, Network::Bitcoin));
+        assert_eq!(ADDRESS, address.to_string());
+
+        let testnet =
+            SilentPaymentAddress::new(address.scan_key, address.spend_key, Network::BitcoinTestnet);
+        assert!(testnet.to_string().starts_with("tsp1q"));
+        let decoded = SilentPaymentAddress::from_str(&testnet.to_string()).unwrap();
+        assert!(matches!(decoded.network, Network::BitcoinTestnet));
+        assert_eq!(address.spend_key, decoded.spend_key);
+
+        // a segwit address is not a silent payment address
+        assert!(SilentPaymentAddress::from_str(
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        )
+        .is_err());
+        assert!(SilentPaymentAddress::from_str(&ADDRESS.replace("pkqwv", "pkqww")).is_err());
+    }
+
+    // BIP-352 test vector "Simple send: two inputs"
+    #[test]
+    fn test_calculate_silent_payment_scripts() {
+        let address = SilentPaymentAddress::from_str(ADDRESS).unwrap();
+        let input_keys = [
+            SecretKey::from_str("eadc78165ff1f8ea94ad7cfdc54990738a4c53f6e0507b42154201b8e5dff3b1")
+                .unwrap(),
+            SecretKey::from_str("93f5ed907ad5b2bdbbdcb5d9116ebc0a4e1f92f910d5260237fa45a9408aad16")
+                .unwrap(),
+        ];
+        let outpoints = [
+            OutPoint {
+                txid: Txid::from_str(
+                    "f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16",
+                )
+                .unwrap(),
+                vout: 0,
+            },
+            OutPoint {
+                txid: Txid::from_str(
+                    "a1075db55d416d3ca199f55b6084e2115b9345e16c5cf302fc80e9d5fbf5d48d",
+                )
+                .unwrap(),
+                vout: 0,
+            },
+        ];
+        let scripts = calculate_silent_payment_scripts(
+            &input_keys,
+            &outpoints,
+            &[(address.scan_key, address.spend_key)],
+        )
+        .unwrap();
+        assert_eq!(
+            "51203e9fce73d4e77a4809908e3c3a2e54ee147b9312dc5044a193d1fc85de46e3c1",
+            hex::encode(scripts[0].as_bytes())
+        );
+    }
+}
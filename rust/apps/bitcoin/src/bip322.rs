@@ -0,0 +1,655 @@
+// BIP-322 generic signed messages.
+// The message is committed to by a virtual `to_spend` transaction paying the signing
+// address, and the proof is a virtual `to_sign` transaction spending it. The simple
+// format only carries the witness of `to_sign`, the full format the whole transaction.
+// Requests come in as the `to_sign` transaction wrapped in a PSBT, its input carries the
+// `to_spend` output and the key origin, the message is a global proprietary field.
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use bitcoin::absolute::LockTime;
+use bitcoin::address::AddressData as Payload;
+use bitcoin::bip32::{ChildNumber, DerivationPath, Fingerprint};
+use bitcoin::consensus::{deserialize, serialize};
+use bitcoin::hashes::{hash160, sha256, Hash, HashEngine};
+use bitcoin::key::TapTweak;
+use bitcoin::opcodes::all::OP_RETURN;
+use bitcoin::opcodes::OP_0;
+use bitcoin::secp256k1::{Keypair, Message, Secp256k1, SecretKey, XOnlyPublicKey};
+use bitcoin::sighash::{EcdsaSighashType, Prevouts, SighashCache};
+use bitcoin::transaction::Version;
+use bitcoin::{
+    ecdsa, taproot, Amount, CompressedPublicKey, OutPoint, Psbt, PublicKey, ScriptBuf, Sequence,
+    TapSighashType, Transaction, TxIn, TxOut, Txid, WitnessVersion,
+};
+use bitcoin::{WPubkeyHash, Witness};
+
+use crate::addresses::address::Address;
+use crate::errors::{BitcoinError, Result};
+use crate::network::Network;
+
+const MESSAGE_TAG: &[u8] = b"BIP0322-signed-message";
+const PSBT_PROPRIETARY_PREFIX: &[u8] = b"bip322";
+const PSBT_MESSAGE_SUBTYPE: u8 = 0x00;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bip322Format {
+    // base64 of the consensus encoded witness stack
+    Simple,
+    // base64 of the consensus encoded `to_sign` transaction
+    Full,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Bip322ScriptType {
+    P2wpkh,
+    P2shP2wpkh,
+    P2tr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bip322Request {
+    pub message: Vec<u8>,
+    pub path: String,
+    pub script_pubkey: ScriptBuf,
+}
+
+impl Bip322Request {
+    pub fn get_address(&self) -> Result<String> {
+        let derivation_path = DerivationPath::from_str(&self.path)
+            .map_err(|e| BitcoinError::Bip322Error(e.to_string()))?;
+        let network = match derivation_path.as_ref().get(1) {
+            Some(ChildNumber::Hardened { index: 0 }) => Network::Bitcoin,
+            Some(ChildNumber::Hardened { index: 1 }) => Network::BitcoinTestnet,
+            _ => {
+                return Err(BitcoinError::Bip322Error(format!(
+                    "unsupported derivation path {}",
+                    self.path
+                )))
+            }
+        };
+        Ok(Address::from_script(&self.script_pubkey, network)?.to_string())
+    }
+}
+
+pub fn message_hash(message: &[u8]) -> [u8; 32] {
+    let tag = sha256::Hash::hash(MESSAGE_TAG);
+    let mut engine = sha256::Hash::engine();
+    engine.input(tag.as_ref());
+    engine.input(tag.as_ref());
+    engine.input(message);
+    sha256::Hash::from_engine(engine).to_byte_array()
+}
+
+pub fn sign_message(
+    message: &[u8],
+    seed: &[u8],
+    path: &String,
+    format: Bip322Format,
+) -> Result<Vec<u8>> {
+    let script_type = get_script_type_by_path(path)?;
+    let private_key = keystore::algorithms::secp256k1::get_private_key_by_seed(seed, path)
+        .map_err(|e| BitcoinError::GetKeyError(e.to_string()))?;
+    sign_message_by_private_key(message, &private_key, script_type, format)
+}
+
+pub fn verify_message(address: &str, message: &[u8], signature: &[u8]) -> Result<()> {
+    let script_pubkey = Address::from_str(address)?.script_pubkey();
+    let to_spend = create_to_spend(&script_pubkey, message);
+    // a full proof is a whole transaction, anything else is tried as a simple witness
+    let to_sign = match deserialize::<Transaction>(signature) {
+        Ok(tx) => {
+            check_to_sign(&tx, &to_spend)?;
+            tx
+        }
+        Err(_) => {
+            let witness = deserialize::<Witness>(signature).map_err(|_| {
+                BitcoinError::Bip322Error(
+                    "signature is neither a witness nor a transaction".to_string(),
+                )
+            })?;
+            let mut tx = create_to_sign(&to_spend);
+            tx.input[0].witness = witness;
+            tx
+        }
+    };
+    verify_to_sign(&to_sign, &to_spend.output[0])
+}
+
+pub fn is_bip322_psbt(psbt: &[u8]) -> bool {
+    Psbt::deserialize(psbt)
+        .map(|psbt| get_psbt_message(&psbt).is_some())
+        .unwrap_or(false)
+}
+
+pub fn parse_psbt(psbt: &[u8], mfp: Fingerprint) -> Result<Bip322Request> {
+    let psbt = Psbt::deserialize(psbt).map_err(|e| BitcoinError::InvalidPsbt(e.to_string()))?;
+    parse_request(&psbt, mfp)
+}
+
+// the proof goes back as the finalized `to_sign` input, the host picks the format it needs
+pub fn sign_psbt(psbt: &[u8], seed: &[u8], mfp: Fingerprint) -> Result<Vec<u8>> {
+    let mut psbt = Psbt::deserialize(psbt).map_err(|e| BitcoinError::InvalidPsbt(e.to_string()))?;
+    let request = parse_request(&psbt, mfp)?;
+    let script_type = get_script_type_by_path(&request.path)?;
+    let private_key = keystore::algorithms::secp256k1::get_private_key_by_seed(seed, &request.path)
+        .map_err(|e| BitcoinError::GetKeyError(e.to_string()))?;
+    let (to_spend, to_sign) = sign_to_sign(&request.message, &private_key, script_type)?;
+    if to_spend.output[0].script_pubkey != request.script_pubkey {
+        return Err(BitcoinError::Bip322Error(
+            "the signing key does not control the address".to_string(),
+        ));
+    }
+    verify_to_sign(&to_sign, &to_spend.output[0])?;
+    let input = &mut psbt.inputs[0];
+    input.final_script_witness = Some(to_sign.input[0].witness.clone());
+    if !to_sign.input[0].script_sig.is_empty() {
+        input.final_script_sig = Some(to_sign.input[0].script_sig.clone());
+    }
+    Ok(psbt.serialize())
+}
+
+fn get_psbt_message(psbt: &Psbt) -> Option<&Vec<u8>> {
+    psbt.proprietary
+        .iter()
+        .find(|(key, _)| {
+            key.prefix == PSBT_PROPRIETARY_PREFIX && key.subtype == PSBT_MESSAGE_SUBTYPE
+        })
+        .map(|(_, message)| message)
+}
+
+// the message shown to the user is only trusted once it rebuilds the spent `to_spend`
+fn parse_request(psbt: &Psbt, mfp: Fingerprint) -> Result<Bip322Request> {
+    let message = get_psbt_message(psbt)
+        .ok_or(BitcoinError::Bip322Error("message is missing".to_string()))?
+        .clone();
+    let input = match psbt.inputs.as_slice() {
+        [input] => input,
+        _ => {
+            return Err(BitcoinError::Bip322Error(
+                "to_sign must only spend the to_spend output".to_string(),
+            ))
+        }
+    };
+    let prevout = input
+        .witness_utxo
+        .as_ref()
+        .ok_or(BitcoinError::Bip322Error(
+            "to_spend output is missing".to_string(),
+        ))?;
+    let to_spend = create_to_spend(&prevout.script_pubkey, &message);
+    if *prevout != to_spend.output[0] || psbt.unsigned_tx != create_to_sign(&to_spend) {
+        return Err(BitcoinError::Bip322Error(
+            "to_sign does not commit to the message".to_string(),
+        ));
+    }
+    let path = input
+        .bip32_derivation
+        .values()
+        .chain(input.tap_key_origins.values().map(|(_, origin)| origin))
+        .find(|(fingerprint, _)| *fingerprint == mfp)
+        .map(|(_, path)| format!("m/{}", path))
+        .ok_or(BitcoinError::Bip322Error(
+            "the message is not signed by this wallet".to_string(),
+        ))?;
+    Ok(Bip322Request {
+        message,
+        path,
+        script_pubkey: prevout.script_pubkey.clone(),
+    })
+}
+
+fn get_script_type_by_path(path: &String) -> Result<Bip322ScriptType> {
+    let derivation_path =
+        DerivationPath::from_str(path).map_err(|e| BitcoinError::Bip322Error(e.to_string()))?;
+    match derivation_path.as_ref().first() {
+        Some(ChildNumber::Hardened { index: 84 }) => Ok(Bip322ScriptType::P2wpkh),
+        Some(ChildNumber::Hardened { index: 49 }) => Ok(Bip322ScriptType::P2shP2wpkh),
+        Some(ChildNumber::Hardened { index: 86 }) => Ok(Bip322ScriptType::P2tr),
+        _ => Err(BitcoinError::Bip322Error(format!(
+            "unsupported derivation path {}",
+            path
+        ))),
+    }
+}
+
+fn sign_message_by_private_key(
+    message: &[u8],
+    private_key: &SecretKey,
+    script_type: Bip322ScriptType,
+    format: Bip322Format,
+) -> Result<Vec<u8>> {
+    // the nested segwit proof needs its scriptSig, which only the full format can carry
+    if format == Bip322Format::Simple && script_type == Bip322ScriptType::P2shP2wpkh {
+        return Err(BitcoinError::Bip322Error(
+            "nested segwit addresses can only be signed in the full format".to_string(),
+        ));
+    }
+    let (_, to_sign) = sign_to_sign(message, private_key, script_type)?;
+    match format {
+        Bip322Format::Simple => Ok(serialize(&to_sign.input[0].witness)),
+        Bip322Format::Full => Ok(serialize(&to_sign)),
+    }
+}
+
+// returns the `to_spend` transaction and the signed `to_sign` spending it
+fn sign_to_sign(
+    message: &[u8],
+    private_key: &SecretKey,
+    script_type: Bip322ScriptType,
+) -> Result<(Transaction, Transaction)> {
+    let secp = Secp256k1::new();
+    let public_key = CompressedPublicKey(private_key.public_key(&secp));
+    let (script_pubkey, script_sig) = match script_type {
+        Bip322ScriptType::P2wpkh => (ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash()), None),
+        Bip322ScriptType::P2shP2wpkh => {
+            let redeem_script = ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash());
+            let script_sig = ScriptBuf::builder()
+                .push_slice(<&bitcoin::script::PushBytes>::try_from(
+                    redeem_script.as_bytes(),
+                )?)
+                .into_script();
+            (
+                ScriptBuf::new_p2sh(&redeem_script.script_hash()),
+                Some(script_sig),
+            )
+        }
+        Bip322ScriptType::P2tr => {
+            let (internal_key, _) = public_key.0.x_only_public_key();
+            (ScriptBuf::new_p2tr(&secp, internal_key, None), None)
+        }
+    };
+    let to_spend = create_to_spend(&script_pubkey, message);
+    let mut to_sign = create_to_sign(&to_spend);
+    let witness = match script_type {
+        Bip322ScriptType::P2wpkh | Bip322ScriptType::P2shP2wpkh => {
+            let sighash = SighashCache::new(&to_sign)
+                .p2wpkh_signature_hash(
+                    0,
+                    &ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash()),
+                    Amount::ZERO,
+                    EcdsaSighashType::All,
+                )
+                .map_err(|e| BitcoinError::SignFailure(e.to_string()))?;
+            let signature = ecdsa::Signature::sighash_all(
+                secp.sign_ecdsa(&Message::from_digest(sighash.to_byte_array()), private_key),
+            );
+            Witness::p2wpkh(&signature, &public_key.0)
+        }
+        Bip322ScriptType::P2tr => {
+            let sighash = SighashCache::new(&to_sign)
+                .taproot_key_spend_signature_hash(
+                    0,
+                    &Prevouts::All(&to_spend.output),
+                    TapSighashType::Default,
+                )
+                .map_err(|e| BitcoinError::SignFailure(e.to_string()))?;
+            let keypair = Keypair::from_secret_key(&secp, private_key).tap_tweak(&secp, None);
+            let signature = taproot::Signature {
+                signature: secp.sign_schnorr_no_aux_rand(
+                    &Message::from_digest(sighash.to_byte_array()),
+                    &keypair.to_inner(),
+                ),
+                sighash_type: TapSighashType::Default,
+            };
+            Witness::p2tr_key_spend(&signature)
+        }
+    };
+    to_sign.input[0].witness = witness;
+    if let Some(script_sig) = script_sig {
+        to_sign.input[0].script_sig = script_sig;
+    }
+    Ok((to_spend, to_sign))
+}
+
+fn create_to_spend(script_pubkey: &ScriptBuf, message: &[u8]) -> Transaction {
+    let script_sig = ScriptBuf::builder()
+        .push_opcode(OP_0)
+        .push_slice(message_hash(message))
+        .into_script();
+    Transaction {
+        version: Version(0),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint {
+                txid: Txid::all_zeros(),
+                vout: 0xFFFFFFFF,
+            },
+            script_sig,
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: script_pubkey.clone(),
+        }],
+    }
+}
+
+fn create_to_sign(to_spend: &Transaction) -> Transaction {
+    Transaction {
+        version: Version(0),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint {
+                txid: to_spend.compute_txid(),
+                vout: 0,
+            },
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: ScriptBuf::builder().push_opcode(OP_RETURN).into_script(),
+        }],
+    }
+}
+
+// proofs of funds with additional inputs are not supported
+fn check_to_sign(to_sign: &Transaction, to_spend: &Transaction) -> Result<()> {
+    let expected = create_to_sign(to_spend);
+    if to_sign.input.len() != 1
+        || to_sign.input[0].previous_output != expected.input[0].previous_output
+    {
+        return Err(BitcoinError::Bip322Error(
+            "to_sign must only spend the to_spend output".to_string(),
+        ));
+    }
+    if to_sign.output != expected.output {
+        return Err(BitcoinError::Bip322Error(
+            "to_sign must have a single empty OP_RETURN output".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn verify_to_sign(to_sign: &Transaction, prevout: &TxOut) -> Result<()> {
+    let secp = Secp256k1::verification_only();
+    let input = &to_sign.input[0];
+    let witness = &input.witness;
+    let invalid = || BitcoinError::Bip322Error("invalid signature".to_string());
+    let address = Address::from_script(&prevout.script_pubkey, crate::network::Network::Bitcoin)
+        .map_err(|_| BitcoinError::Bip322Error("unsupported address type".to_string()))?;
+    let (witness_program, redeem_script) = match address.payload {
+        Payload::Segwit { witness_program } => {
+            if !input.script_sig.is_empty() {
+                return Err(invalid());
+            }
+            (witness_program, None)
+        }
+        Payload::P2sh { script_hash } => {
+            // only P2SH-P2WPKH, the scriptSig is a single push of the redeem script
+            let redeem_script = match input
+                .script_sig
+                .instructions()
+                .collect::<Vec<_>>()
+                .as_slice()
+            {
+                [Ok(bitcoin::script::Instruction::PushBytes(bytes))] => {
+                    ScriptBuf::from_bytes(bytes.as_bytes().to_vec())
+                }
+                _ => return Err(invalid()),
+            };
+            if redeem_script.script_hash() != script_hash || !redeem_script.is_p2wpkh() {
+                return Err(BitcoinError::Bip322Error(
+                    "unsupported address type".to_string(),
+                ));
+            }
+            let witness_program =
+                bitcoin::WitnessProgram::new(WitnessVersion::V0, &redeem_script.as_bytes()[2..])?;
+            (witness_program, Some(redeem_script))
+        }
+        _ => {
+            return Err(BitcoinError::Bip322Error(
+                "unsupported address type".to_string(),
+            ))
+        }
+    };
+
+    if witness_program.is_p2wpkh() {
+        if witness.len() != 2 {
+            return Err(invalid());
+        }
+        let signature = ecdsa::Signature::from_slice(&witness[0]).map_err(|_| invalid())?;
+        let public_key = PublicKey::from_slice(&witness[1]).map_err(|_| invalid())?;
+        let wpubkey_hash = WPubkeyHash::from_byte_array(
+            hash160::Hash::hash(&public_key.to_bytes()).to_byte_array(),
+        );
+        if !public_key.compressed
+            || wpubkey_hash.as_byte_array()[..] != witness_program.program().as_bytes()[..]
+        {
+            return Err(invalid());
+        }
+        let script_code = redeem_script.unwrap_or(ScriptBuf::new_p2wpkh(&wpubkey_hash));
+        let sighash = SighashCache::new(to_sign)
+            .p2wpkh_signature_hash(0, &script_code, prevout.value, signature.sighash_type)
+            .map_err(|_| invalid())?;
+        secp.verify_ecdsa(
+            &Message::from_digest(sighash.to_byte_array()),
+            &signature.signature,
+            &public_key.inner,
+        )
+        .map_err(|_| invalid())
+    } else if witness_program.is_p2tr() {
+        if witness.len() != 1 {
+            return Err(invalid());
+        }
+        let signature = taproot::Signature::from_slice(&witness[0]).map_err(|_| invalid())?;
+        let output_key = XOnlyPublicKey::from_slice(witness_program.program().as_bytes())
+            .map_err(|_| invalid())?;
+        let sighash = SighashCache::new(to_sign)
+            .taproot_key_spend_signature_hash(
+                0,
+                &Prevouts::All(&[prevout.clone()]),
+                signature.sighash_type,
+            )
+            .map_err(|_| invalid())?;
+        secp.verify_schnorr(
+            &signature.signature,
+            &Message::from_digest(sighash.to_byte_array()),
+            &output_key,
+        )
+        .map_err(|_| invalid())
+    } else {
+        Err(BitcoinError::Bip322Error(
+            "unsupported address type".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::psbt::raw::ProprietaryKey;
+    use bitcoin::PrivateKey;
+
+    // BIP-322 test vectors
+    const ADDRESS: &str = "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l";
+    const TAPROOT_ADDRESS: &str = "bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3";
+    const PRIVATE_KEY: &str = "L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k";
+
+    fn private_key() -> SecretKey {
+        PrivateKey::from_wif(PRIVATE_KEY).unwrap().inner
+    }
+
+    fn decode(signature: &str) -> Vec<u8> {
+        base64::decode(signature).unwrap()
+    }
+
+    #[test]
+    fn test_message_hash() {
+        assert_eq!(
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1",
+            hex::encode(message_hash(b""))
+        );
+        assert_eq!(
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a",
+            hex::encode(message_hash(b"Hello World"))
+        );
+    }
+
+    #[test]
+    fn test_virtual_transactions() {
+        let script_pubkey = Address::from_str(ADDRESS).unwrap().script_pubkey();
+        let to_spend = create_to_spend(&script_pubkey, b"");
+        assert_eq!(
+            "c5680aa69bb8d860bf82d4e9cd3504b55dde018de765a91bb566283c545a99a7",
+            to_spend.compute_txid().to_string()
+        );
+        assert_eq!(
+            "1e9654e951a5ba44c8604c4de6c67fd78a27e81dcadcfe1edf638ba3aaebaed6",
+            create_to_sign(&to_spend).compute_txid().to_string()
+        );
+        let to_spend = create_to_spend(&script_pubkey, b"Hello World");
+        assert_eq!(
+            "b79d196740ad5217771c1098fc4a4b51e0535c32236c71f1ea4d61a2d603352b",
+            to_spend.compute_txid().to_string()
+        );
+        assert_eq!(
+            "88737ae86f2077145f93cc4b153ae9a1cb8d56afa511988c149c5c8c9d93bddf",
+            create_to_sign(&to_spend).compute_txid().to_string()
+        );
+    }
+
+    #[test]
+    fn test_sign_p2wpkh_simple() {
+        let signature = sign_message_by_private_key(
+            b"Hello World",
+            &private_key(),
+            Bip322ScriptType::P2wpkh,
+            Bip322Format::Simple,
+        )
+        .unwrap();
+        assert_eq!(
+            "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=",
+            base64::encode(&signature)
+        );
+    }
+
+    #[test]
+    fn test_verify_vectors() {
+        let hello = decode("AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=");
+        let empty = decode("AkcwRAIgM2gBAQqvZX15ZiysmKmQpDrG83avLIT492QBzLnQIxYCIBaTpOaD20qRlEylyxFSeEA2ba9YOixpX8z46TSDtS40ASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=");
+        assert!(verify_message(ADDRESS, b"Hello World", &hello).is_ok());
+        assert!(verify_message(ADDRESS, b"", &empty).is_ok());
+        assert!(verify_message(ADDRESS, b"", &hello).is_err());
+        assert!(verify_message(ADDRESS, b"Hello World", &empty).is_err());
+
+        // taproot key path signature with an explicit SIGHASH_ALL
+        let taproot = decode("AUHd69PrJQEv+oKTfZ8l+WROBHuy9HKrbFCJu7U1iK2iiEy1vMU5EfMtjc+VSHM7aU0SDbak5IUZRVno2P5mjSafAQ==");
+        assert!(verify_message(TAPROOT_ADDRESS, b"Hello World", &taproot).is_ok());
+        assert!(verify_message(TAPROOT_ADDRESS, b"", &taproot).is_err());
+        assert!(verify_message(ADDRESS, b"Hello World", &taproot).is_err());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let cases = [
+            (
+                "m/84'/0'/0'/0/0",
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            ),
+            ("m/49'/0'/0'/0/0", "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf"),
+            (
+                "m/86'/0'/0'/0/0",
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+            ),
+        ];
+        for (path, address) in cases {
+            let path = path.to_string();
+            let full = sign_message(b"Hello World", &seed, &path, Bip322Format::Full).unwrap();
+            assert!(verify_message(address, b"Hello World", &full).is_ok());
+            assert!(verify_message(address, b"Hello World!", &full).is_err());
+            if path.starts_with("m/49'") {
+                assert!(sign_message(b"Hello World", &seed, &path, Bip322Format::Simple).is_err());
+                continue;
+            }
+            let simple = sign_message(b"Hello World", &seed, &path, Bip322Format::Simple).unwrap();
+            assert!(verify_message(address, b"Hello World", &simple).is_ok());
+            assert!(verify_message(address, b"Hello World!", &simple).is_err());
+        }
+        assert!(sign_message(
+            b"Hello World",
+            &seed,
+            &"m/44'/0'/0'/0/0".to_string(),
+            Bip322Format::Simple
+        )
+        .is_err());
+    }
+
+    fn request_psbt(seed: &[u8], path: &str, message: &[u8]) -> Psbt {
+        let path = path.to_string();
+        let private_key =
+            keystore::algorithms::secp256k1::get_private_key_by_seed(seed, &path).unwrap();
+        let script_type = get_script_type_by_path(&path).unwrap();
+        let (to_spend, _) = sign_to_sign(message, &private_key, script_type).unwrap();
+        let mut psbt = Psbt::from_unsigned_tx(create_to_sign(&to_spend)).unwrap();
+        psbt.inputs[0].witness_utxo = Some(to_spend.output[0].clone());
+        psbt.inputs[0].bip32_derivation.insert(
+            private_key.public_key(&Secp256k1::new()),
+            (
+                Fingerprint::from_str("73c5da0a").unwrap(),
+                DerivationPath::from_str(&path).unwrap(),
+            ),
+        );
+        psbt.proprietary.insert(
+            ProprietaryKey {
+                prefix: PSBT_PROPRIETARY_PREFIX.to_vec(),
+                subtype: PSBT_MESSAGE_SUBTYPE,
+                key: vec![],
+            },
+            message.to_vec(),
+        );
+        psbt
+    }
+
+    #[test]
+    fn test_sign_psbt() {
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let mfp = Fingerprint::from_str("73c5da0a").unwrap();
+        let cases = [
+            (
+                "m/84'/0'/0'/0/0",
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            ),
+            ("m/49'/0'/0'/0/0", "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf"),
+            (
+                "m/86'/0'/0'/0/0",
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+            ),
+        ];
+        for (path, address) in cases {
+            let psbt = request_psbt(&seed, path, b"Hello World").serialize();
+            assert!(is_bip322_psbt(&psbt));
+            let request = parse_psbt(&psbt, mfp).unwrap();
+            assert_eq!(b"Hello World".to_vec(), request.message);
+            assert_eq!(path, request.path);
+            assert_eq!(address, request.get_address().unwrap());
+
+            let signed = Psbt::deserialize(&sign_psbt(&psbt, &seed, mfp).unwrap()).unwrap();
+            let mut to_sign = signed.unsigned_tx.clone();
+            to_sign.input[0].witness = signed.inputs[0].final_script_witness.clone().unwrap();
+            to_sign.input[0].script_sig = signed.inputs[0]
+                .final_script_sig
+                .clone()
+                .unwrap_or_default();
+            assert!(verify_message(address, b"Hello World", &serialize(&to_sign)).is_ok());
+        }
+
+        // the shown message has to be the one committed to by `to_spend`
+        let mut psbt = request_psbt(&seed, "m/84'/0'/0'/0/0", b"Hello World");
+        let message = psbt.proprietary.values_mut().next().unwrap();
+        *message = b"Hello World!".to_vec();
+        assert!(parse_psbt(&psbt.serialize(), mfp).is_err());
+
+        let psbt = request_psbt(&seed, "m/84'/0'/0'/0/0", b"Hello World").serialize();
+        assert!(parse_psbt(&psbt, Fingerprint::from_str("12345678").unwrap()).is_err());
+        let mut psbt = request_psbt(&seed, "m/84'/0'/0'/0/0", b"Hello World");
+        psbt.proprietary.clear();
+        assert!(!is_bip322_psbt(&psbt.serialize()));
+    }
+}
@@ -81,6 +81,18 @@ pub enum BitcoinError {
     DerivePublicKeyError(String),
     #[error("wallet type error: {0}")]
     WalletTypeError(String),
+    #[error("wallet policy error: {0}")]
+    WalletPolicyError(String),
+    #[error("musig2 error: {0}")]
+    MuSig2Error(String),
+    #[error("bip322 error: {0}")]
+    Bip322Error(String),
+    #[error("silent payment error: {0}")]
+    SilentPaymentError(String),
+    #[error("invalid sighash type: {0}")]
+    InvalidSighashType(String),
+    #[error("bsms error: {0}")]
+    BsmsError(String),
 }
 
 impl From<io::Error> for BitcoinError {
@@ -25,6 +25,8 @@ use hex;
 pub use transactions::legacy::sign_legacy_tx;
 pub use transactions::parsed_tx;
 pub use transactions::psbt::parsed_psbt;
+pub use transactions::psbt::psbt_v2::VersionedPsbt;
+pub use transactions::tx_policy;
 use ur_registry::pb::protoc;
 
 use crate::errors::{BitcoinError, Result};
@@ -34,10 +36,12 @@ use crate::transactions::psbt::wrapped_psbt::WrappedPsbt;
 use crate::transactions::tx_checker::TxChecker;
 
 pub mod addresses;
+pub mod bip322;
 pub mod errors;
 mod macros;
 pub mod multi_sig;
 pub mod network;
+pub mod ordinals;
 mod transactions;
 pub struct PsbtSignStatus {
     pub sign_status: Option<String>,
@@ -45,13 +49,29 @@ pub struct PsbtSignStatus {
 }
 
 pub fn sign_psbt(psbt_hex: Vec<u8>, seed: &[u8], mfp: Fingerprint) -> Result<Vec<u8>> {
-    sign_psbt_no_serialize(psbt_hex, seed, mfp).map(|v| v.serialize())
+    sign_versioned_psbt(psbt_hex, seed, mfp, false).and_then(|v| v.serialize())
 }
 
 pub fn sign_psbt_no_serialize(psbt_hex: Vec<u8>, seed: &[u8], mfp: Fingerprint) -> Result<Psbt> {
-    let psbt = deserialize_psbt(psbt_hex)?;
-    let mut wpsbt = WrappedPsbt { psbt };
-    wpsbt.sign(seed, mfp)
+    sign_versioned_psbt(psbt_hex, seed, mfp, false).map(|v| v.psbt)
+}
+
+// the signed psbt keeps the version it came in, serialize it with `VersionedPsbt::serialize`.
+// SIGHASH_NONE inputs are refused unless the device allows blind signing
+pub fn sign_versioned_psbt(
+    psbt_hex: Vec<u8>,
+    seed: &[u8],
+    mfp: Fingerprint,
+    allow_sighash_none: bool,
+) -> Result<VersionedPsbt> {
+    let mut versioned_psbt = VersionedPsbt::deserialize(&psbt_hex)?;
+    let unsigned = versioned_psbt.psbt.clone();
+    let mut wpsbt = WrappedPsbt {
+        psbt: unsigned.clone(),
+    };
+    versioned_psbt.psbt = wpsbt.sign_with_sighash_none(seed, mfp, allow_sighash_none)?;
+    versioned_psbt.update_modifiable(&unsigned);
+    Ok(versioned_psbt)
 }
 
 pub fn parse_psbt_hex_sign_status(psbt: &[u8]) -> Result<PsbtSignStatus> {
@@ -124,7 +144,7 @@ pub fn check_raw_tx(raw_tx: protoc::Payload, context: keystone::ParseContext) ->
 }
 
 fn deserialize_psbt(psbt_hex: Vec<u8>) -> Result<Psbt> {
-    Psbt::deserialize(&psbt_hex).map_err(|e| BitcoinError::InvalidPsbt(format!("{}", e)))
+    VersionedPsbt::deserialize(&psbt_hex).map(|v| v.psbt)
 }
 
 #[cfg(test)]
@@ -159,6 +179,9 @@ mod test {
                 sign_status: Some("Unsigned".to_string()),
                 is_multisig: false,
                 need_sign: true,
+                policy_warnings: Vec::new(),
+                inscriptions_to_fee: Vec::new(),
+                sighash_warnings: Vec::new(),
             }
         };
     }
@@ -175,6 +198,8 @@ mod test {
                 need_sign: true,
                 sign_status: (0, 1),
                 ecdsa_sighash_type: 0x1,
+                sighash_warning: None,
+                inscriptions: Vec::new(),
             }
         };
     }
@@ -187,6 +212,7 @@ mod test {
                 value: $value,
                 path: Some($path.to_string()),
                 is_external: false,
+                inscriptions: Vec::new(),
             }
         };
     }
@@ -210,6 +236,8 @@ mod test {
                 fee_sat: $fee_sat.to_string(),
                 network: $network.to_string(),
                 sign_status: Some("Unsigned".to_string()),
+                inscriptions_to_fee: Vec::new(),
+                runes: Vec::new(),
             }
         };
     }
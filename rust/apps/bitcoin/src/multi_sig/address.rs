@@ -1,11 +1,16 @@
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
-use bitcoin::{opcodes, script, PublicKey, ScriptBuf};
+use bitcoin::script::Instruction;
+use bitcoin::secp256k1::XOnlyPublicKey;
+use bitcoin::taproot::{LeafVersion, TapLeafHash, TapNodeHash};
+use bitcoin::{opcodes, script, PublicKey, Script, ScriptBuf};
+use core::str::FromStr;
 use itertools::Itertools;
 
 use crate::addresses::address::Address;
 use crate::addresses::xyzpub::Version;
 use crate::addresses::{derive_public_key, xyzpub};
+use crate::multi_sig::musig::KeyAggContext;
 use crate::multi_sig::wallet::MultiSigWalletConfig;
 use crate::multi_sig::{MultiSigFormat, Network};
 use crate::{network, BitcoinError};
@@ -27,6 +32,15 @@ pub fn create_multi_sig_address_for_wallet(
 
     let ordered_pub_keys = pub_keys.iter().sorted().collect::<Vec<_>>();
 
+    if format.is_taproot() {
+        return calculate_taproot_multi_address(
+            &ordered_pub_keys,
+            wallet.threshold,
+            format,
+            wallet.get_network(),
+        );
+    }
+
     let p2ms = crate_p2ms_script(&ordered_pub_keys, wallet.threshold);
 
     calculate_multi_address(&p2ms, format, wallet.get_network())
@@ -44,6 +58,11 @@ pub fn calculate_multi_address(
             ScriptBuf::new_p2sh(&p2wsh.script_hash())
         }
         MultiSigFormat::P2wsh => ScriptBuf::new_p2wsh(&p2ms.wscript_hash()),
+        MultiSigFormat::P2tr | MultiSigFormat::P2trMusig2 => {
+            return Err(BitcoinError::MultiSigWalletAddressCalError(
+                "taproot multisig does not use a p2ms script".to_string(),
+            ))
+        }
     };
 
     let network = if *network == Network::TestNet {
@@ -54,6 +73,52 @@ pub fn calculate_multi_address(
     Ok(Address::from_script(script.as_script(), network)?.to_string())
 }
 
+// BIP-341 suggested NUMS point H, nobody knows its private key so the key path is unspendable
+pub const TAPROOT_NUMS_KEY: &str =
+    "50929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0";
+
+// tr(NUMS, sortedmulti_a(...)) or tr(musig(...), sortedmulti_a(...)), ordered_pub_keys are sorted
+// the same way as sortedmulti, and that order is also used for the MuSig2 key aggregation
+pub fn calculate_taproot_multi_address(
+    ordered_pub_keys: &Vec<&PublicKey>,
+    threshold: u32,
+    format: MultiSigFormat,
+    network: &Network,
+) -> Result<String, BitcoinError> {
+    let (internal_key, merkle_root) =
+        calculate_taproot_multi_spend_info(ordered_pub_keys, threshold, format)?;
+    let network = if *network == Network::TestNet {
+        network::Network::BitcoinTestnet
+    } else {
+        network::Network::Bitcoin
+    };
+    Ok(Address::p2tr(&internal_key, Some(merkle_root), network)?.to_string())
+}
+
+pub fn calculate_taproot_multi_spend_info(
+    ordered_pub_keys: &Vec<&PublicKey>,
+    threshold: u32,
+    format: MultiSigFormat,
+) -> Result<(XOnlyPublicKey, TapNodeHash), BitcoinError> {
+    let internal_key = match format {
+        MultiSigFormat::P2tr => XOnlyPublicKey::from_str(TAPROOT_NUMS_KEY)
+            .map_err(|e| BitcoinError::MultiSigWalletAddressCalError(e.to_string()))?,
+        MultiSigFormat::P2trMusig2 => {
+            KeyAggContext::new(ordered_pub_keys.iter().map(|k| k.inner).collect())?
+                .x_only_public_key()
+        }
+        _ => {
+            return Err(BitcoinError::MultiSigWalletAddressCalError(format!(
+                "{} is not a taproot format",
+                format.get_multi_sig_format_string()
+            )))
+        }
+    };
+    let multi_a = crate_multi_a_script(ordered_pub_keys, threshold);
+    let leaf_hash = TapLeafHash::from_script(&multi_a, LeafVersion::TapScript);
+    Ok((internal_key, TapNodeHash::from(leaf_hash)))
+}
+
 fn derive_pub_key(xpub: &String, change: u32, account: u32) -> Result<PublicKey, BitcoinError> {
     Ok(derive_public_key(
         xpub,
@@ -73,6 +138,65 @@ fn crate_p2ms_script(pub_keys: &Vec<&PublicKey>, threshold: u32) -> ScriptBuf {
         .into_script()
 }
 
+// <pk1> OP_CHECKSIG <pk2> OP_CHECKSIGADD ... <pkn> OP_CHECKSIGADD <k> OP_NUMEQUAL
+pub fn crate_multi_a_script(pub_keys: &Vec<&PublicKey>, threshold: u32) -> ScriptBuf {
+    let builder =
+        pub_keys
+            .iter()
+            .enumerate()
+            .fold(script::Builder::new(), |builder, (index, key)| {
+                let builder = builder.push_x_only_key(&XOnlyPublicKey::from(key.inner));
+                if index == 0 {
+                    builder.push_opcode(opcodes::all::OP_CHECKSIG)
+                } else {
+                    builder.push_opcode(opcodes::all::OP_CHECKSIGADD)
+                }
+            });
+
+    builder
+        .push_int(threshold as i64)
+        .push_opcode(opcodes::all::OP_NUMEQUAL)
+        .into_script()
+}
+
+// the reverse of crate_multi_a_script, returns the threshold and the keys in script order
+pub fn parse_multi_a_script(script: &Script) -> Option<(u32, Vec<XOnlyPublicKey>)> {
+    let instructions = script.instructions().collect::<Result<Vec<_>, _>>().ok()?;
+    let (last, rest) = instructions.split_last()?;
+    if !matches!(last, Instruction::Op(op) if *op == opcodes::all::OP_NUMEQUAL) {
+        return None;
+    }
+    let (threshold, rest) = rest.split_last()?;
+    let threshold = match threshold {
+        //OP_PUSHNUM_1 to OP_PUSHNUM_16
+        Instruction::Op(op) if op.to_u8() >= 0x51 && op.to_u8() <= 0x60 => {
+            (op.to_u8() - 0x50) as u32
+        }
+        _ => return None,
+    };
+    if rest.is_empty() || rest.len() % 2 != 0 {
+        return None;
+    }
+    let mut keys = Vec::new();
+    for (index, pair) in rest.chunks(2).enumerate() {
+        let expected = if index == 0 {
+            opcodes::all::OP_CHECKSIG
+        } else {
+            opcodes::all::OP_CHECKSIGADD
+        };
+        match (&pair[0], &pair[1]) {
+            (Instruction::PushBytes(key), Instruction::Op(op)) if *op == expected => {
+                keys.push(XOnlyPublicKey::from_slice(key.as_bytes()).ok()?)
+            }
+            _ => return None,
+        }
+    }
+    if threshold as usize > keys.len() {
+        return None;
+    }
+    Some((threshold, keys))
+}
+
 #[cfg(test)]
 mod tests {
     extern crate std;
@@ -162,5 +286,51 @@ mod tests {
             let address = create_multi_sig_address_for_wallet(&config, 0, 0).unwrap();
             assert_eq!("3A3vK8133WTMePMpPDmZSqSqK3gobohtG8", address);
         }
+
+        // P2TR, tr(NUMS, sortedmulti_a(...))
+        {
+            let config = r#"# Keystone Multisig setup file
+            #
+            Name: taproot
+            Policy: 2 of 3
+            Derivation: m/48'/0'/0'/3'
+            Format: P2TR
+
+            73C5DA0A: xpub6DkFAXWQ2dHxr7LX1ByDVebj6u3C5KSKTVXWkiVKb3tdYfh9t7FhXzvUVSxNSikoVTRb2bGjvYoW8PqYBReMeswi3megtqDwRCeVs3vxMeH
+            44C757A9: xpub6FA1aifiRQ2ifzw9kpmEDuVz9xbkm4U5QXKy7cbqbvfzjh4NFqG2WKAGaFihmbkTz1vNifpDnmDurfvQJqgQM9TUjvqgxpGNhLvuvZJfJKq
+            A261AFC4: xpub6DbSZPadfy9Tbh4aM7VtmWC1xGi3Yg2n35eUELZM1wPECyjAzSRWKn9EhqKwjqeFHBq2DYnz6hzcagx7NGDXqoxEQRzG2hVNvhwR3w9oAVZ
+            "#;
+
+            let config = parse_wallet_config(config, "73C5DA0A").unwrap();
+            assert_eq!("2ac9f17b", config.verify_code);
+            let address = create_multi_sig_address_for_wallet(&config, 0, 0).unwrap();
+            assert_eq!(
+                "bc1pw70xqlpe2xm805dmtlt8397el94n96ryear93x7kfnn5nqsmjueqeye0ac",
+                address
+            );
+        }
+
+        // P2TR-MUSIG2, tr(musig(...), sortedmulti_a(...))
+        {
+            let config = r#"# Keystone Multisig setup file
+            #
+            Name: taproot
+            Policy: 2 of 3
+            Derivation: m/48'/0'/0'/3'
+            Format: P2TR-MUSIG2
+
+            73C5DA0A: xpub6DkFAXWQ2dHxr7LX1ByDVebj6u3C5KSKTVXWkiVKb3tdYfh9t7FhXzvUVSxNSikoVTRb2bGjvYoW8PqYBReMeswi3megtqDwRCeVs3vxMeH
+            44C757A9: xpub6FA1aifiRQ2ifzw9kpmEDuVz9xbkm4U5QXKy7cbqbvfzjh4NFqG2WKAGaFihmbkTz1vNifpDnmDurfvQJqgQM9TUjvqgxpGNhLvuvZJfJKq
+            A261AFC4: xpub6DbSZPadfy9Tbh4aM7VtmWC1xGi3Yg2n35eUELZM1wPECyjAzSRWKn9EhqKwjqeFHBq2DYnz6hzcagx7NGDXqoxEQRzG2hVNvhwR3w9oAVZ
+            "#;
+
+            let config = parse_wallet_config(config, "73C5DA0A").unwrap();
+            assert_eq!("e49fb314", config.verify_code);
+            let address = create_multi_sig_address_for_wallet(&config, 0, 0).unwrap();
+            assert_eq!(
+                "bc1p2x82nspspwvef8uznhrg4lcfgkhqw2yghfltpqjrkutfuwt8y5gsn6qelg",
+                address
+            );
+        }
     }
 }
@@ -0,0 +1,352 @@
+// BIP-129 Bitcoin Secure Multisig Setup, signer side.
+// Round 1: the signer hands out a key record signed by the key itself.
+// Round 2: the coordinator returns a descriptor record, the signer checks it and registers the wallet.
+// Both records are encrypted with the TOKEN of the session unless the TOKEN is "00".
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::Aes256;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use bitcoin::bip32::Xpub;
+use bitcoin::secp256k1::Secp256k1;
+use bitcoin::sign_message::{signed_msg_hash, MessageSignature};
+use core::str::FromStr;
+use cryptoxide::hashing::sha256;
+use cryptoxide::hmac::Hmac;
+use cryptoxide::mac::Mac;
+use cryptoxide::pbkdf2::pbkdf2;
+use cryptoxide::sha2::{Sha256, Sha512};
+use keystore::algorithms::secp256k1::{
+    get_extended_public_key_by_seed, get_master_fingerprint_by_seed,
+};
+
+use crate::multi_sig::address::create_multi_sig_address_for_wallet;
+use crate::multi_sig::coordinator::descriptor_checksum;
+use crate::multi_sig::wallet::{parse_wallet_config, BsmsWallet, MultiSigWalletConfig};
+use crate::{sign_msg, BitcoinError};
+
+pub const BSMS_VERSION: &str = "BSMS 1.0";
+const NO_ENCRYPTION_TOKEN: &str = "00";
+const KDF_PASSWORD: &[u8] = b"No SPOF";
+const KDF_ITERATIONS: u32 = 2048;
+const MAX_DESCRIPTION_LENGTH: usize = 80;
+const NO_PATH_RESTRICTIONS: &str = "No path restrictions";
+const RECEIVE_AND_CHANGE_PATHS: &str = "/0/*,/1/*";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BsmsDescriptorRecord {
+    pub descriptor: String,
+    pub path_restrictions: String,
+    pub first_address: String,
+}
+
+fn bsms_error(reason: &str) -> BitcoinError {
+    BitcoinError::BsmsError(reason.to_string())
+}
+
+// "00" means no encryption, otherwise 8 bytes (STANDARD) or 16 bytes (EXTENDED) in hex
+fn parse_token(token: &str) -> Result<Option<Vec<u8>>, BitcoinError> {
+    if token == NO_ENCRYPTION_TOKEN {
+        return Ok(None);
+    }
+    match hex::decode(token) {
+        Ok(bytes) if bytes.len() == 8 || bytes.len() == 16 => Ok(Some(bytes)),
+        _ => Err(bsms_error("invalid token")),
+    }
+}
+
+// ENCRYPTION_KEY = PBKDF2-HMAC-SHA512("No SPOF", TOKEN, 2048), the TOKEN bytes are the salt here
+// and the prefix of the MAC data below
+fn derive_encryption_key(token: &[u8]) -> [u8; 32] {
+    let mut hmac = Hmac::new(Sha512::new(), KDF_PASSWORD);
+    let mut key = [0u8; 32];
+    pbkdf2(&mut hmac, token, KDF_ITERATIONS, &mut key);
+    key
+}
+
+// MAC = HMAC-SHA256(SHA256(ENCRYPTION_KEY), TOKEN || DATA), its first 16 bytes are the IV
+fn calculate_mac(key: &[u8; 32], token: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut hmac = Hmac::new(Sha256::new(), &sha256(key));
+    hmac.input(token);
+    hmac.input(data);
+    let mut mac = [0u8; 32];
+    hmac.raw_result(&mut mac);
+    mac
+}
+
+fn aes_256_ctr(key: &[u8; 32], iv: &[u8], data: &[u8]) -> Vec<u8> {
+    let cipher = Aes256::new(GenericArray::from_slice(key));
+    let mut counter = [0u8; 16];
+    counter.copy_from_slice(&iv[..16]);
+    let mut counter = u128::from_be_bytes(counter);
+    let mut output = Vec::with_capacity(data.len());
+    for chunk in data.chunks(16) {
+        let mut block = GenericArray::clone_from_slice(&counter.to_be_bytes());
+        cipher.encrypt_block(&mut block);
+        output.extend(chunk.iter().zip(block.iter()).map(|(a, b)| a ^ b));
+        counter = counter.wrapping_add(1);
+    }
+    output
+}
+
+pub fn encrypt_record(token: &str, data: &str) -> Result<String, BitcoinError> {
+    let token = match parse_token(token)? {
+        Some(token) => token,
+        None => return Ok(data.to_string()),
+    };
+    let key = derive_encryption_key(&token);
+    let mac = calculate_mac(&key, &token, data.as_bytes());
+    let mut output = mac.to_vec();
+    output.extend(aes_256_ctr(&key, &mac, data.as_bytes()));
+    Ok(hex::encode(output))
+}
+
+pub fn decrypt_record(token: &str, record: &str) -> Result<String, BitcoinError> {
+    let token = match parse_token(token)? {
+        Some(token) => token,
+        None => return Ok(record.to_string()),
+    };
+    let bytes = hex::decode(record.trim()).map_err(|_| bsms_error("invalid encrypted record"))?;
+    if bytes.len() <= 32 {
+        return Err(bsms_error("invalid encrypted record"));
+    }
+    let (mac, ciphertext) = bytes.split_at(32);
+    let key = derive_encryption_key(&token);
+    let data = aes_256_ctr(&key, mac, ciphertext);
+    if calculate_mac(&key, &token, &data) != mac {
+        return Err(bsms_error("the record was not encrypted with this token"));
+    }
+    String::from_utf8(data).map_err(|_| bsms_error("invalid record"))
+}
+
+fn record_lines(record: &str, count: usize) -> Result<Vec<&str>, BitcoinError> {
+    let lines = record
+        .trim()
+        .lines()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>();
+    if lines.len() != count || lines[0] != BSMS_VERSION {
+        return Err(bsms_error("invalid record"));
+    }
+    Ok(lines)
+}
+
+// round 1, the signature is a legacy signed message of the first four lines by KEY
+pub fn create_key_record(
+    seed: &[u8],
+    path: &str,
+    token: &str,
+    description: &str,
+) -> Result<String, BitcoinError> {
+    parse_token(token)?;
+    if description.len() > MAX_DESCRIPTION_LENGTH || description.contains('\n') {
+        return Err(bsms_error("invalid description"));
+    }
+    let path = path.replace('h', "'");
+    let mfp = get_master_fingerprint_by_seed(seed)
+        .map_err(|e| BitcoinError::KeystoreError(e.to_string()))?;
+    let xpub = get_extended_public_key_by_seed(seed, &path)
+        .map_err(|e| BitcoinError::KeystoreError(e.to_string()))?;
+    let origin = path.strip_prefix("m").unwrap_or(&path);
+    let message = format!(
+        "{}\n{}\n[{}{}]{}\n{}",
+        BSMS_VERSION, token, mfp, origin, xpub, description
+    );
+    let signature = sign_msg(&message, seed, &path)?;
+    let record = format!("{}\n{}", message, base64::encode(&signature));
+    encrypt_record(token, &record)
+}
+
+// checks the key record signature of another signer, used when this device is the coordinator
+pub fn parse_key_record(token: &str, content: &str) -> Result<BsmsWallet, BitcoinError> {
+    let record = decrypt_record(token, content)?;
+    let lines = record_lines(&record, 5)?;
+    if lines[1] != token {
+        return Err(bsms_error("token of the key record does not match"));
+    }
+    let (origin, key) = lines[2]
+        .strip_prefix('[')
+        .and_then(|v| v.split_once(']'))
+        .ok_or(bsms_error("invalid key origin"))?;
+    let (xfp, path) = origin
+        .split_once('/')
+        .ok_or(bsms_error("invalid key origin"))?;
+    let xpub = Xpub::from_str(key).map_err(|_| bsms_error("invalid key"))?;
+
+    let signature = base64::decode(lines[4])
+        .ok()
+        .and_then(|v| MessageSignature::from_slice(&v).ok())
+        .ok_or(bsms_error("invalid signature"))?;
+    let public_key = signature
+        .recover_pubkey(&Secp256k1::new(), signed_msg_hash(&lines[..4].join("\n")))
+        .map_err(|_| bsms_error("invalid signature"))?;
+    if public_key.inner != xpub.public_key {
+        return Err(bsms_error("key record is not signed by its key"));
+    }
+
+    Ok(BsmsWallet {
+        bsms_version: BSMS_VERSION.to_string(),
+        xfp: xfp.to_string(),
+        derivation_path: format!("m/{}", path),
+        extended_pubkey: key.to_string(),
+    })
+}
+
+pub fn parse_descriptor_record(
+    token: &str,
+    content: &str,
+) -> Result<BsmsDescriptorRecord, BitcoinError> {
+    let record = decrypt_record(token, content)?;
+    let lines = record_lines(&record, 4)?;
+    Ok(BsmsDescriptorRecord {
+        descriptor: lines[1].to_string(),
+        path_restrictions: lines[2].to_string(),
+        first_address: lines[3].to_string(),
+    })
+}
+
+// the descriptor template writes "/**" for the receive and change branches, spelled out here as
+// "/<0;1>/*" (BIP-389) so that the descriptor parser only ever sees explicit key derivations
+fn expand_descriptor(descriptor: &str) -> Result<String, BitcoinError> {
+    let (body, checksum) = descriptor
+        .split_once('#')
+        .ok_or(bsms_error("descriptor checksum is missing"))?;
+    if descriptor_checksum(body)? != checksum {
+        return Err(bsms_error("invalid descriptor checksum"));
+    }
+    let body = body.replace("/**", "/<0;1>/*");
+    Ok(format!("{}#{}", body, descriptor_checksum(&body)?))
+}
+
+// round 2, the wallet is accepted only when our key is in it and the first address matches.
+// Like every other wallet file, the seed check of `strict_verify_wallet_config` runs once the
+// password is entered to register it
+pub fn import_descriptor_record(
+    token: &str,
+    content: &str,
+    xfp: &str,
+) -> Result<MultiSigWalletConfig, BitcoinError> {
+    let record = parse_descriptor_record(token, content)?;
+    if record.path_restrictions != RECEIVE_AND_CHANGE_PATHS
+        && record.path_restrictions != NO_PATH_RESTRICTIONS
+    {
+        return Err(bsms_error("unsupported path restrictions"));
+    }
+    let mut wallet = parse_wallet_config(&expand_descriptor(&record.descriptor)?, xfp)?;
+    if create_multi_sig_address_for_wallet(&wallet, 0, 0)? != record.first_address {
+        return Err(bsms_error("first address does not match the descriptor"));
+    }
+    wallet.creator = String::from("BSMS");
+    Ok(wallet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multi_sig::wallet::strict_verify_wallet_config;
+
+    const SEED: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+
+    fn descriptor() -> String {
+        let body = "sh(wsh(sortedmulti(2,[73c5da0a/48'/0'/0'/1']xpub6DkFAXWQ2dHxnMKoSBogHrw1rgNJKR4umdbnNVNTYeCGcduxWnNUHgGptqEQWPKRmeW4Zn4FHSbLMBKEWYaMDYu47Ytg6DdFnPNt8hwn5mE/**,[5271c071/48'/0'/0'/1'/5/6/7]xpub6LfFMiP3hcgrKeTrho9MgKj2zdKGPsd6ufJzrsQNaHSFZ7uj8e1vnSwibBVQ33VfXYJM5zn9G7E9VrMkFPVcdRtH3Brg9ndHLJs8v2QtwHa/**,[c2202a77/48'/0'/0'/1'/110/8/9]xpub6LZnaHgbbxyZpChT4w9V5NC91qaZC9rrPoebgH3qGjZmcDKvPjLivfZSKLu5R1PjEpboNsznNwtqBifixCuKTfPxDZVNVN9mnjfTBpafqQf/**)))";
+        format!("{}#{}", body, descriptor_checksum(body).unwrap())
+    }
+
+    #[test]
+    fn test_encrypt_record() {
+        let token = "a54044308ceac9b7";
+        let encrypted = encrypt_record(token, "BSMS 1.0\nhello").unwrap();
+        assert_ne!("BSMS 1.0\nhello", encrypted);
+        assert_eq!(
+            "BSMS 1.0\nhello",
+            decrypt_record(token, &encrypted).unwrap()
+        );
+        assert!(decrypt_record("a54044308ceac9b8", &encrypted).is_err());
+        assert!(encrypt_record("a540", "BSMS 1.0").is_err());
+        assert_eq!("BSMS 1.0", encrypt_record("00", "BSMS 1.0").unwrap());
+    }
+
+    #[test]
+    fn test_encryption_vectors() {
+        let token = hex::decode("a54044308ceac9b7").unwrap();
+        let key = derive_encryption_key(&token);
+        assert_eq!(
+            "7673ffd9efd70336a5442eda0b31457f7b6cdf7b42fe17f274434df55efa9839",
+            hex::encode(key)
+        );
+        let data = "BSMS 1.0\na54044308ceac9b7\n[73c5da0a/48'/0'/0'/1']\nKeystone";
+        assert_eq!(
+            "26bbe6de11001fa1857dcf6d22c10bac74bd13b498bf938ff35a774044e9f9c9",
+            hex::encode(calculate_mac(&key, &token, data.as_bytes()))
+        );
+        assert_eq!(
+            "26bbe6de11001fa1857dcf6d22c10bac74bd13b498bf938ff35a774044e9f9c9ff87f1e77667cf1fe002b0311d9f9a3c0750c4bbec96b59d98e1529f9b02e586b40033cada34d1af6bc142bf6de54d3364d04485c1cc8562f750",
+            encrypt_record("a54044308ceac9b7", data).unwrap()
+        );
+
+        // EXTENDED token
+        let token = "1f4bb2ac6e8b96d9a9e2aafd6a0ab05d";
+        assert_eq!(
+            "afd028911ccadac86d722b2efc92f0eb12524ced8c373b168143868dc8f7a338",
+            hex::encode(derive_encryption_key(&hex::decode(token).unwrap()))
+        );
+        let encrypted = "756102bc803891a45fce537c2930ece153c74e3095b376228cec9a734ca76122716c7273c0b74d5e3160cd2677ed";
+        assert_eq!(encrypted, encrypt_record(token, "BSMS 1.0\nhello").unwrap());
+        assert_eq!("BSMS 1.0\nhello", decrypt_record(token, encrypted).unwrap());
+    }
+
+    #[test]
+    fn test_key_record() {
+        let seed = hex::decode(SEED).unwrap();
+        let record = create_key_record(&seed, "m/48'/0'/0'/1'", "00", "Keystone").unwrap();
+        let lines = record.lines().collect::<Vec<_>>();
+        assert_eq!(5, lines.len());
+        assert_eq!("[73c5da0a/48'/0'/0'/1']xpub6DkFAXWQ2dHxnMKoSBogHrw1rgNJKR4umdbnNVNTYeCGcduxWnNUHgGptqEQWPKRmeW4Zn4FHSbLMBKEWYaMDYu47Ytg6DdFnPNt8hwn5mE", lines[2]);
+
+        let key = parse_key_record("00", &record).unwrap();
+        assert_eq!("73c5da0a", key.xfp);
+        assert_eq!("m/48'/0'/0'/1'", key.derivation_path);
+
+        let tampered = record.replace("Keystone", "Attacker");
+        assert!(parse_key_record("00", &tampered).is_err());
+
+        let token = "1f4bb2ac6e8b96d9a9e2aafd6a0ab05d";
+        let record = create_key_record(&seed, "m/48'/0'/0'/1'", token, "Keystone").unwrap();
+        assert!(record.chars().all(|c| c.is_ascii_hexdigit()));
+        assert!(parse_key_record(token, &record).is_ok());
+        assert!(parse_key_record("00", &record).is_err());
+    }
+
+    #[test]
+    fn test_import_descriptor_record() {
+        let seed = hex::decode(SEED).unwrap();
+        let token = "a54044308ceac9b7";
+        // the template shorthand is not a valid key derivation on its own
+        assert!(parse_wallet_config(&descriptor(), "73c5da0a").is_err());
+        let expanded = expand_descriptor(&descriptor()).unwrap();
+        assert!(expanded.contains("/<0;1>/*,[5271c071/48'/0'/0'/1'/5/6/7]"));
+        let wallet = parse_wallet_config(&expanded, "73c5da0a").unwrap();
+        let first_address = create_multi_sig_address_for_wallet(&wallet, 0, 0).unwrap();
+
+        let record = format!("BSMS 1.0\n{}\n/0/*,/1/*\n{}", descriptor(), first_address);
+        let encrypted = encrypt_record(token, &record).unwrap();
+        let wallet = import_descriptor_record(token, &encrypted, "73c5da0a").unwrap();
+        assert_eq!("BSMS", wallet.creator);
+        assert_eq!("P2WSH-P2SH", wallet.format);
+        assert_eq!(2, wallet.threshold);
+        assert_eq!(3, wallet.total);
+        // what the registration checks with the seed
+        assert!(strict_verify_wallet_config(&seed, &wallet, "73c5da0a").is_ok());
+
+        // a coordinator that lies about the first address
+        let record = format!(
+            "BSMS 1.0\n{}\n/0/*,/1/*\n3Ptx8HzhMmAjYMKiPpA2QqmPBGuWqnXH9L",
+            descriptor()
+        );
+        let encrypted = encrypt_record(token, &record).unwrap();
+        assert!(import_descriptor_record(token, &encrypted, "73c5da0a").is_err());
+
+        let record = format!("BSMS 1.0\n{}\n/0/*\n{}", descriptor(), first_address);
+        assert!(import_descriptor_record("00", &record, "73c5da0a").is_err());
+    }
+}
@@ -0,0 +1,520 @@
+// Wallet files of other coordinators: Caravan's JSON wallet config, Specter's wallet JSON
+// and plain output descriptors (Sparrow, Bitcoin Core). They all describe the same
+// sortedmulti wallets as the Coldcard text format and end up in a MultiSigWalletConfig.
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde_json::{json, Value};
+
+use crate::addresses::xyzpub;
+use crate::addresses::xyzpub::Version;
+use crate::multi_sig::address::TAPROOT_NUMS_KEY;
+use crate::multi_sig::wallet::{
+    detect_wallet_network, is_valid_multi_sig_policy, process_xpub_and_xfp, MultiSigWalletConfig,
+};
+use crate::multi_sig::{MultiSigFormat, Network};
+use crate::BitcoinError;
+
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WalletConfigFormat {
+    Coldcard,
+    Caravan,
+    Specter,
+    Descriptor,
+}
+
+impl WalletConfigFormat {
+    pub fn detect(content: &str) -> Self {
+        let content = content.trim();
+        if content.starts_with('{') {
+            match serde_json::from_str::<Value>(content) {
+                Ok(value) if value.get("descriptor").is_some() => WalletConfigFormat::Specter,
+                _ => WalletConfigFormat::Caravan,
+            }
+        } else if find_descriptor(content).is_some() {
+            WalletConfigFormat::Descriptor
+        } else {
+            WalletConfigFormat::Coldcard
+        }
+    }
+}
+
+fn parse_error(reason: &str) -> BitcoinError {
+    BitcoinError::MultiSigWalletParseError(reason.to_string())
+}
+
+fn polymod(c: u64, value: u64) -> u64 {
+    let c0 = c >> 35;
+    let mut c = ((c & 0x7ffffffff) << 5) ^ value;
+    const GENERATORS: [u64; 5] = [
+        0xf5dee51989,
+        0xa9fdca3312,
+        0x1bab10e32d,
+        0x3706b1677a,
+        0x644d626ffd,
+    ];
+    for (i, generator) in GENERATORS.iter().enumerate() {
+        if (c0 >> i) & 1 == 1 {
+            c ^= generator;
+        }
+    }
+    c
+}
+
+// BIP-380 descriptor checksum
+pub fn descriptor_checksum(descriptor: &str) -> Result<String, BitcoinError> {
+    let mut c = 1u64;
+    let mut class = 0u64;
+    let mut class_count = 0;
+    for ch in descriptor.chars() {
+        let position = INPUT_CHARSET
+            .find(ch)
+            .ok_or(parse_error("invalid character in descriptor"))? as u64;
+        c = polymod(c, position & 31);
+        class = class * 3 + (position >> 5);
+        class_count += 1;
+        if class_count == 3 {
+            c = polymod(c, class);
+            class = 0;
+            class_count = 0;
+        }
+    }
+    if class_count > 0 {
+        c = polymod(c, class);
+    }
+    for _ in 0..8 {
+        c = polymod(c, 0);
+    }
+    c ^= 1;
+    Ok((0..8)
+        .map(|i| CHECKSUM_CHARSET[((c >> (5 * (7 - i))) & 31) as usize] as char)
+        .collect())
+}
+
+// the first line that is not a comment, Sparrow puts a "# Receive and change descriptor" header on top
+fn find_descriptor(content: &str) -> Option<&str> {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter(|line| {
+            line.starts_with("sh(") || line.starts_with("wsh(") || line.starts_with("tr(")
+        })
+}
+
+fn strip_wrapper<'a>(value: &'a str, prefix: &str, suffix: &str) -> Option<&'a str> {
+    value.strip_prefix(prefix)?.strip_suffix(suffix)
+}
+
+fn parse_descriptor_key(
+    wallet: &mut MultiSigWalletConfig,
+    key: &str,
+) -> Result<String, BitcoinError> {
+    let (origin, key) = key
+        .strip_prefix('[')
+        .and_then(|v| v.split_once(']'))
+        .ok_or(parse_error("descriptor key has no origin"))?;
+    let (xfp, path) = origin.split_once('/').unwrap_or((origin, ""));
+    // only the receive branch or both receive and change branches (BIP-389) can be registered
+    let (xpub, children) = key
+        .split_once('/')
+        .ok_or(parse_error("descriptor key has no receive derivation"))?;
+    if !matches!(children, "0/*" | "<0;1>/*") {
+        return Err(parse_error("unsupported descriptor key derivation"));
+    }
+    process_xpub_and_xfp(wallet, xfp, xpub)?;
+    let derivation = if path.is_empty() {
+        "m".to_string()
+    } else {
+        format!("m/{}", path)
+    };
+    Ok(derivation.replace(['h', 'H'], "'"))
+}
+
+fn parse_descriptor(
+    wallet: &mut MultiSigWalletConfig,
+    descriptor: &str,
+) -> Result<(), BitcoinError> {
+    let (body, checksum) = descriptor
+        .split_once('#')
+        .ok_or(parse_error("descriptor checksum is missing"))?;
+    if descriptor_checksum(body)? != checksum {
+        return Err(parse_error("invalid descriptor checksum"));
+    }
+    let body = body.replace(' ', "");
+    let tr_prefix = format!("tr({},sortedmulti_a(", TAPROOT_NUMS_KEY);
+    let (format, multi) = if let Some(multi) = strip_wrapper(&body, "sh(wsh(sortedmulti(", ")))") {
+        (MultiSigFormat::P2wshP2sh, multi)
+    } else if let Some(multi) = strip_wrapper(&body, "wsh(sortedmulti(", "))") {
+        (MultiSigFormat::P2wsh, multi)
+    } else if let Some(multi) = strip_wrapper(&body, "sh(sortedmulti(", "))") {
+        (MultiSigFormat::P2sh, multi)
+    } else if let Some(multi) = strip_wrapper(&body, &tr_prefix, "))") {
+        (MultiSigFormat::P2tr, multi)
+    } else {
+        return Err(parse_error("only sortedmulti descriptors are supported"));
+    };
+
+    let mut items = multi.split(',');
+    let threshold = items
+        .next()
+        .and_then(|v| v.parse::<u32>().ok())
+        .ok_or(parse_error("parse threshold error"))?;
+    let derivations = items
+        .map(|key| parse_descriptor_key(wallet, key))
+        .collect::<Result<Vec<_>, _>>()?;
+    set_policy(wallet, threshold, derivations.len() as u32)?;
+    wallet.format = format.get_multi_sig_format_string();
+    set_derivations(wallet, derivations);
+    Ok(())
+}
+
+fn set_policy(
+    wallet: &mut MultiSigWalletConfig,
+    threshold: u32,
+    total: u32,
+) -> Result<(), BitcoinError> {
+    if !is_valid_multi_sig_policy(total, threshold) {
+        return Err(parse_error("this is not a valid policy"));
+    }
+    wallet.threshold = threshold;
+    wallet.total = total;
+    Ok(())
+}
+
+// a single derivation is shared by all cosigners, like the Coldcard format does
+fn set_derivations(wallet: &mut MultiSigWalletConfig, derivations: Vec<String>) {
+    wallet.derivations = match derivations.first() {
+        Some(first) if derivations.iter().all(|v| v == first) => vec![first.clone()],
+        _ => derivations,
+    };
+}
+
+fn default_wallet_name(wallet: &MultiSigWalletConfig) -> String {
+    format!("{}-of-{} {}", wallet.threshold, wallet.total, wallet.format)
+}
+
+pub fn parse_descriptor_wallet_config(content: &str) -> Result<MultiSigWalletConfig, BitcoinError> {
+    let mut wallet = MultiSigWalletConfig::default();
+    let descriptor = find_descriptor(content).ok_or(parse_error("no descriptor found"))?;
+    if content.contains("Sparrow") {
+        wallet.creator = String::from("Sparrow");
+    }
+    parse_descriptor(&mut wallet, descriptor)?;
+    wallet.name = default_wallet_name(&wallet);
+    detect_wallet_network(&mut wallet)?;
+    wallet.config_text = content.to_string();
+    Ok(wallet)
+}
+
+// {"label": "...", "blockheight": 0, "descriptor": "wsh(sortedmulti(...))#checksum", "devices": [...]}
+pub fn parse_specter_wallet_config(content: &str) -> Result<MultiSigWalletConfig, BitcoinError> {
+    let value: Value = serde_json::from_str(content)
+        .map_err(|e| parse_error(&format!("invalid specter wallet, {}", e)))?;
+    let descriptor = value
+        .get("descriptor")
+        .and_then(Value::as_str)
+        .ok_or(parse_error("missing field descriptor"))?;
+    let mut wallet = MultiSigWalletConfig::default();
+    wallet.creator = String::from("Specter");
+    parse_descriptor(&mut wallet, descriptor.trim())?;
+    wallet.name = match value.get("label").and_then(Value::as_str) {
+        Some(label) => label.to_string(),
+        None => default_wallet_name(&wallet),
+    };
+    detect_wallet_network(&mut wallet)?;
+    wallet.config_text = content.to_string();
+    Ok(wallet)
+}
+
+// {"name": "...", "addressType": "P2WSH", "network": "mainnet",
+//  "quorum": {"requiredSigners": 2, "totalSigners": 3},
+//  "extendedPublicKeys": [{"name": "...", "bip32Path": "m/48'/0'/0'/2'", "xpub": "...", "xfp": "..."}]}
+pub fn parse_caravan_wallet_config(content: &str) -> Result<MultiSigWalletConfig, BitcoinError> {
+    let value: Value = serde_json::from_str(content)
+        .map_err(|e| parse_error(&format!("invalid caravan wallet, {}", e)))?;
+    let field = |value: &Value, name: &str| {
+        value
+            .get(name)
+            .and_then(Value::as_str)
+            .map(|v| v.to_string())
+            .ok_or(parse_error(&format!("missing field {}", name)))
+    };
+    let mut wallet = MultiSigWalletConfig::default();
+    wallet.creator = String::from("Caravan");
+    wallet.name = field(&value, "name")?;
+
+    let format = MultiSigFormat::from(&field(&value, "addressType")?)?;
+    if format.is_taproot() {
+        return Err(BitcoinError::MultiSigWalletFormatError(
+            "caravan does not support taproot wallets".to_string(),
+        ));
+    }
+    wallet.format = format.get_multi_sig_format_string();
+
+    let quorum = value
+        .get("quorum")
+        .ok_or(parse_error("missing field quorum"))?;
+    let number = |name: &str| {
+        quorum
+            .get(name)
+            .and_then(Value::as_u64)
+            .map(|v| v as u32)
+            .ok_or(parse_error(&format!("missing field {}", name)))
+    };
+    set_policy(
+        &mut wallet,
+        number("requiredSigners")?,
+        number("totalSigners")?,
+    )?;
+
+    let keys = value
+        .get("extendedPublicKeys")
+        .and_then(Value::as_array)
+        .ok_or(parse_error("missing field extendedPublicKeys"))?;
+    let mut derivations = vec![];
+    for key in keys {
+        process_xpub_and_xfp(&mut wallet, &field(key, "xfp")?, &field(key, "xpub")?)?;
+        derivations.push(field(key, "bip32Path")?.replace(['h', 'H'], "'"));
+    }
+    set_derivations(&mut wallet, derivations);
+
+    detect_wallet_network(&mut wallet)?;
+    if let Some(network) = value.get("network").and_then(Value::as_str) {
+        if !network.eq_ignore_ascii_case(&wallet.network.to_string()) {
+            return Err(parse_error("xpub networks inconsistent"));
+        }
+    }
+    wallet.config_text = content.to_string();
+    Ok(wallet)
+}
+
+fn get_plain_xpub(xpub: &str, network: &Network) -> Result<String, BitcoinError> {
+    match network {
+        Network::MainNet => xyzpub::convert_version(xpub, &Version::Xpub),
+        Network::TestNet => xyzpub::convert_version(xpub, &Version::Tpub),
+    }
+}
+
+fn get_derivation(config: &MultiSigWalletConfig, index: usize) -> Result<String, BitcoinError> {
+    config
+        .get_derivation_by_index(index)
+        .ok_or(parse_error("num of derivations is not right"))
+}
+
+// `children` is appended to every key, "0/*" for a receive descriptor or "<0;1>/*" (BIP-389)
+pub fn export_descriptor(
+    config: &MultiSigWalletConfig,
+    children: &str,
+) -> Result<String, BitcoinError> {
+    let keys = config
+        .xpub_items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            Ok(format!(
+                "[{}/{}]{}/{}",
+                item.xfp.to_lowercase(),
+                get_derivation(config, index)?.replace('\'', "h"),
+                get_plain_xpub(&item.xpub, config.get_network())?,
+                children
+            ))
+        })
+        .collect::<Result<Vec<_>, BitcoinError>>()?
+        .join(",");
+    let format = MultiSigFormat::from(&config.format)?;
+    let multi = match format.is_taproot() {
+        true => format!("sortedmulti_a({},{})", config.threshold, keys),
+        false => format!("sortedmulti({},{})", config.threshold, keys),
+    };
+    let body = match format {
+        MultiSigFormat::P2sh => format!("sh({})", multi),
+        MultiSigFormat::P2wshP2sh => format!("sh(wsh({}))", multi),
+        MultiSigFormat::P2wsh => format!("wsh({})", multi),
+        MultiSigFormat::P2tr => format!("tr({},{})", TAPROOT_NUMS_KEY, multi),
+        MultiSigFormat::P2trMusig2 => {
+            return Err(BitcoinError::MultiSigWalletFormatError(
+                "not support exporting musig2 wallets as descriptor".to_string(),
+            ))
+        }
+    };
+    let checksum = descriptor_checksum(&body)?;
+    Ok(format!("{}#{}", body, checksum))
+}
+
+pub fn export_specter_wallet_config(config: &MultiSigWalletConfig) -> Result<String, BitcoinError> {
+    let devices = config
+        .xpub_items
+        .iter()
+        .map(|item| json!({"type": "other", "label": item.xfp.to_lowercase()}))
+        .collect::<Vec<_>>();
+    let value = json!({
+        "label": config.name,
+        "blockheight": 0,
+        "descriptor": export_descriptor(config, "0/*")?,
+        "devices": devices,
+    });
+    Ok(value.to_string())
+}
+
+pub fn export_caravan_wallet_config(config: &MultiSigWalletConfig) -> Result<String, BitcoinError> {
+    let format = MultiSigFormat::from(&config.format)?;
+    if format.is_taproot() {
+        return Err(BitcoinError::MultiSigWalletFormatError(
+            "caravan does not support taproot wallets".to_string(),
+        ));
+    }
+    let address_type = match format {
+        MultiSigFormat::P2wshP2sh => "P2SH-P2WSH".to_string(),
+        _ => format.get_multi_sig_format_string(),
+    };
+    let keys = config
+        .xpub_items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            Ok(json!({
+                "name": format!("Cosigner {}", index + 1),
+                "bip32Path": format!("m/{}", get_derivation(config, index)?),
+                "xpub": get_plain_xpub(&item.xpub, config.get_network())?,
+                "xfp": item.xfp.to_lowercase(),
+                "method": "text",
+            }))
+        })
+        .collect::<Result<Vec<_>, BitcoinError>>()?;
+    let value = json!({
+        "name": config.name,
+        "addressType": address_type,
+        "network": config.get_network().to_string(),
+        "client": {"type": "public"},
+        "quorum": {
+            "requiredSigners": config.threshold,
+            "totalSigners": config.total,
+        },
+        "extendedPublicKeys": keys,
+        "startingAddressIndex": 0,
+    });
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multi_sig::wallet::{export_wallet_config, parse_wallet_config};
+
+    const COLDCARD_CONFIG: &str = r#"# Coldcard Multisig setup file (created on 5271C071)
+#
+Name: CC-2-of-3
+Policy: 2 of 3
+Derivation: m/48'/0'/0'/2'
+Format: P2WSH
+
+748CC6AA: xpub6F6iZVTmc3KMgAUkV9JRNaouxYYwChRswPN1ut7nTfecn6VPRYLXFgXar1gvPUX27QH1zaVECqVEUoA2qMULZu5TjyKrjcWcLTQ6LkhrZAj
+C2202A77: xpub6EiTGcKqBQy2uTat1QQPhYQWt8LGmZStNqKDoikedkB72sUqgF9fXLUYEyPthqLSb6VP4akUAsy19MV5LL8SvqdzvcABYUpKw45jA1KZMhm
+5271C071: xpub6EWksRHwPbDmXWkjQeA6wbCmXZeDPXieMob9hhbtJjmrmk647bWkh7om5rk2eoeDKcKG6NmD8nT7UZAFxXQMjTnhENTwTEovQw3MDQ8jJ16"#;
+
+    #[test]
+    fn test_descriptor_checksum() {
+        assert_eq!("89f8spxm", descriptor_checksum("raw(deadbeef)").unwrap());
+        assert!(descriptor_checksum("raw(deadbeef)\u{e9}").is_err());
+    }
+
+    #[test]
+    fn test_parse_descriptor_wallet_config() {
+        let content = r#"# Receive and change descriptor (BIP389):
+wsh(sortedmulti(2,[748cc6aa/48h/0h/0h/2h]xpub6F6iZVTmc3KMgAUkV9JRNaouxYYwChRswPN1ut7nTfecn6VPRYLXFgXar1gvPUX27QH1zaVECqVEUoA2qMULZu5TjyKrjcWcLTQ6LkhrZAj/<0;1>/*,[c2202a77/48h/0h/0h/2h]xpub6EiTGcKqBQy2uTat1QQPhYQWt8LGmZStNqKDoikedkB72sUqgF9fXLUYEyPthqLSb6VP4akUAsy19MV5LL8SvqdzvcABYUpKw45jA1KZMhm/<0;1>/*,[5271c071/48h/0h/0h/2h]xpub6EWksRHwPbDmXWkjQeA6wbCmXZeDPXieMob9hhbtJjmrmk647bWkh7om5rk2eoeDKcKG6NmD8nT7UZAFxXQMjTnhENTwTEovQw3MDQ8jJ16/<0;1>/*))"#;
+        let body = content.lines().nth(1).unwrap();
+        let content = format!("{}#{}", content, descriptor_checksum(body).unwrap());
+        assert_eq!(
+            WalletConfigFormat::Descriptor,
+            WalletConfigFormat::detect(&content)
+        );
+
+        let config = parse_wallet_config(&content, "748CC6AA").unwrap();
+        assert_eq!(2, config.threshold);
+        assert_eq!(3, config.total);
+        assert_eq!("P2WSH", config.format);
+        assert_eq!(vec!["m/48'/0'/0'/2'".to_string()], config.derivations);
+        assert_eq!("c2202a77", config.xpub_items[1].xfp);
+        assert_eq!("d4637859", config.verify_code);
+
+        // a wrong checksum is rejected
+        let tampered = content.replace("sortedmulti(2,", "sortedmulti(1,");
+        assert!(parse_wallet_config(&tampered, "748CC6AA").is_err());
+
+        // every key needs the receive or the receive and change derivation
+        for children in ["/<0;1>/*", "/0/*", "/1/*", "/*", ""] {
+            let body = content.lines().nth(1).unwrap().split('#').next().unwrap();
+            let body = body.replacen("/<0;1>/*", children, 1);
+            let descriptor = format!("{}#{}", body, descriptor_checksum(&body).unwrap());
+            let result = parse_wallet_config(&descriptor, "748CC6AA");
+            assert_eq!(matches!(children, "/<0;1>/*" | "/0/*"), result.is_ok());
+        }
+    }
+
+    #[test]
+    fn test_export_and_import_all_formats() {
+        let config = parse_wallet_config(COLDCARD_CONFIG, "748CC6AA").unwrap();
+        assert_eq!("d4637859", config.verify_code);
+
+        for format in [
+            WalletConfigFormat::Coldcard,
+            WalletConfigFormat::Caravan,
+            WalletConfigFormat::Specter,
+            WalletConfigFormat::Descriptor,
+        ] {
+            let content = export_wallet_config(&config, "748CC6AA", format).unwrap();
+            assert_eq!(format, WalletConfigFormat::detect(&content));
+            let imported = parse_wallet_config(&content, "748CC6AA").unwrap();
+            assert_eq!(config.verify_code, imported.verify_code);
+            assert_eq!(config.threshold, imported.threshold);
+            assert_eq!(config.total, imported.total);
+            assert_eq!(config.format, imported.format);
+            assert_eq!(config.derivations, imported.derivations);
+            assert_eq!(Network::MainNet, imported.network);
+            if format != WalletConfigFormat::Descriptor {
+                assert_eq!("CC-2-of-3", imported.name);
+            }
+        }
+
+        let descriptor = export_descriptor(&config, "<0;1>/*").unwrap();
+        assert!(descriptor.starts_with("wsh(sortedmulti(2,[748cc6aa/48h/0h/0h/2h]xpub6F6iZVTm"));
+        let (body, checksum) = descriptor.split_once('#').unwrap();
+        assert_eq!(descriptor_checksum(body).unwrap(), checksum);
+    }
+
+    #[test]
+    fn test_parse_caravan_wallet_config() {
+        let content = r#"{
+            "name": "My Caravan Wallet",
+            "addressType": "P2SH-P2WSH",
+            "network": "mainnet",
+            "client": {"type": "public"},
+            "quorum": {"requiredSigners": 2, "totalSigners": 3},
+            "extendedPublicKeys": [
+                {"name": "a", "bip32Path": "m/48'/0'/0'/1'/12/32/5", "xpub": "xpub6KMfgiWkVW33LfMbZoGjk6M3CvdZtrzkn38RP2SjbGGU9E85JTXDaX6Jn6bXVqnmq2EnRzWTZxeF3AZ1ZLcssM4DT9GY5RSuJBt1GRx3xm2", "xfp": "748cc6aa"},
+                {"name": "b", "bip32Path": "m/48'/0'/0'/1'/5/6/7", "xpub": "xpub6LfFMiP3hcgrKeTrho9MgKj2zdKGPsd6ufJzrsQNaHSFZ7uj8e1vnSwibBVQ33VfXYJM5zn9G7E9VrMkFPVcdRtH3Brg9ndHLJs8v2QtwHa", "xfp": "5271c071"},
+                {"name": "c", "bip32Path": "m/48'/0'/0'/1'/110/8/9", "xpub": "xpub6LZnaHgbbxyZpChT4w9V5NC91qaZC9rrPoebgH3qGjZmcDKvPjLivfZSKLu5R1PjEpboNsznNwtqBifixCuKTfPxDZVNVN9mnjfTBpafqQf", "xfp": "c2202a77"}
+            ],
+            "startingAddressIndex": 0
+        }"#;
+        assert_eq!(
+            WalletConfigFormat::Caravan,
+            WalletConfigFormat::detect(content)
+        );
+        let config = parse_wallet_config(content, "748cc6aa").unwrap();
+        assert_eq!("Caravan", config.creator);
+        assert_eq!("My Caravan Wallet", config.name);
+        assert_eq!("P2WSH-P2SH", config.format);
+        assert_eq!(3, config.derivations.len());
+        assert_eq!("m/48'/0'/0'/1'/5/6/7", config.derivations[1]);
+        // same wallet as the multi path coldcard file in the wallet tests
+        assert_eq!("7cb85a16", config.verify_code);
+
+        let content = content.replace("\"mainnet\"", "\"testnet\"");
+        assert!(parse_wallet_config(&content, "748cc6aa").is_err());
+    }
+}
@@ -1,4 +1,8 @@
 pub mod address;
+pub mod bsms;
+pub mod coordinator;
+pub mod musig;
+pub mod policy;
 pub mod wallet;
 
 use crate::addresses::xyzpub::{convert_version, Version, VERSION_XPUB};
@@ -67,12 +71,18 @@ pub enum MultiSigType {
     P2shTest,
     P2wshP2shTest,
     P2wshTest,
+    P2tr,
+    P2trTest,
 }
 
 pub enum MultiSigFormat {
     P2sh,
     P2wshP2sh,
     P2wsh,
+    // tr(NUMS, sortedmulti_a(...)), script path only
+    P2tr,
+    // tr(musig(...), sortedmulti_a(...)), the key path is a BIP-327 aggregate of all cosigners
+    P2trMusig2,
 }
 
 impl MultiSigFormat {
@@ -82,6 +92,8 @@ impl MultiSigFormat {
             "P2WSH-P2SH" => Ok(MultiSigFormat::P2wshP2sh),
             "P2SH-P2WSH" => Ok(MultiSigFormat::P2wshP2sh),
             "P2WSH" => Ok(MultiSigFormat::P2wsh),
+            "P2TR" => Ok(MultiSigFormat::P2tr),
+            "P2TR-MUSIG2" => Ok(MultiSigFormat::P2trMusig2),
             _ => Err(BitcoinError::MultiSigWalletFormatError(format!(
                 "not support this format {}",
                 format_str
@@ -94,8 +106,14 @@ impl MultiSigFormat {
             MultiSigFormat::P2sh => "P2SH".to_string(),
             MultiSigFormat::P2wshP2sh => "P2WSH-P2SH".to_string(),
             MultiSigFormat::P2wsh => "P2WSH".to_string(),
+            MultiSigFormat::P2tr => "P2TR".to_string(),
+            MultiSigFormat::P2trMusig2 => "P2TR-MUSIG2".to_string(),
         }
     }
+
+    pub fn is_taproot(&self) -> bool {
+        matches!(self, MultiSigFormat::P2tr | MultiSigFormat::P2trMusig2)
+    }
 }
 
 //For MULTI_P2SH, the mainnet and testnet share the same path.
@@ -107,6 +125,9 @@ const MULTI_P2WSH_P2SH_PATH_TEST: &str = "m/48'/1'/0'/1'";
 const MULTI_P2WSH_PATH: &str = "m/48'/0'/0'/2'";
 const MULTI_P2WSH_PATH_TEST: &str = "m/48'/1'/0'/2'";
 
+const MULTI_P2TR_PATH: &str = "m/48'/0'/0'/3'";
+const MULTI_P2TR_PATH_TEST: &str = "m/48'/1'/0'/3'";
+
 #[derive(Debug)]
 pub struct MultiSigXPubInfo {
     pub path: String,
@@ -145,6 +166,7 @@ pub fn extract_xpub_info_from_str(
         MultiSigFormat::P2sh => ("p2sh", "p2sh_deriv"),
         MultiSigFormat::P2wshP2sh => ("p2sh_p2wsh", "p2sh_p2wsh_deriv"),
         MultiSigFormat::P2wsh => ("p2wsh", "p2wsh_deriv"),
+        MultiSigFormat::P2tr | MultiSigFormat::P2trMusig2 => ("p2tr", "p2tr_deriv"),
     };
 
     let path = json_data
@@ -206,6 +228,7 @@ pub fn extract_xpub_info_from_crypto_account(
             ScriptExpression::WitnessScriptHash,
         ],
         MultiSigType::P2wsh | MultiSigType::P2wshTest => vec![ScriptExpression::WitnessScriptHash],
+        MultiSigType::P2tr | MultiSigType::P2trTest => vec![ScriptExpression::Taproot],
     };
 
     let output_descriptors = crypto_account.get_output_descriptors();
@@ -255,6 +278,11 @@ pub fn export_xpub_by_crypto_account(
                     &network,
                 )?)
             }
+            MULTI_P2TR_PATH | MULTI_P2TR_PATH_TEST => outputs.push(generate_multi_sig_p2tr_output(
+                master_fingerprint,
+                extended_public_keys[index],
+                &network,
+            )?),
             _ => {
                 return Err(URError::UrEncodeError(format!(
                     "not supported path:{}",
@@ -410,6 +438,28 @@ fn generate_multi_sig_p2wsh_output(
     ))
 }
 
+fn generate_multi_sig_p2tr_output(
+    master_fingerprint: &[u8; 4],
+    extended_public_key: &str,
+    network: &Network,
+) -> URResult<CryptoOutput> {
+    let script_expressions = vec![ScriptExpression::Taproot];
+    Ok(CryptoOutput::new(
+        script_expressions,
+        None,
+        Some(generate_multi_sig_crypto_hd_key(
+            master_fingerprint,
+            extended_public_key,
+            if *network == Network::MainNet {
+                MultiSigType::P2tr
+            } else {
+                MultiSigType::P2trTest
+            },
+        )?),
+        None,
+    ))
+}
+
 fn generate_multi_sig_crypto_hd_key(
     master_fingerprint: &[u8; 4],
     extended_public_key: &str,
@@ -465,6 +515,24 @@ fn generate_multi_sig_crypto_hd_key(
                 get_path_component(Some(2), true)?,
             ]
         }
+        MultiSigType::P2tr => {
+            network_inner = NetworkInner::MainNet;
+            vec![
+                get_path_component(Some(48), true)?,
+                get_path_component(Some(0), true)?,
+                get_path_component(Some(0), true)?,
+                get_path_component(Some(3), true)?,
+            ]
+        }
+        MultiSigType::P2trTest => {
+            network_inner = NetworkInner::TestNet;
+            vec![
+                get_path_component(Some(48), true)?,
+                get_path_component(Some(1), true)?,
+                get_path_component(Some(0), true)?,
+                get_path_component(Some(3), true)?,
+            ]
+        }
     };
 
     let coin_info = CryptoCoinInfo::new(Some(CoinType::Bitcoin), Some(network_inner));
@@ -496,12 +564,14 @@ fn get_path_component(index: Option<u32>, hardened: bool) -> URResult<PathCompon
 
 #[allow(unused)]
 fn is_valid_multi_path(path: &str) -> bool {
-    const VALID_PATHS: [&str; 5] = [
+    const VALID_PATHS: [&str; 7] = [
         MULTI_P2SH_PATH,
         MULTI_P2WSH_P2SH_PATH,
         MULTI_P2WSH_P2SH_PATH_TEST,
         MULTI_P2WSH_PATH,
         MULTI_P2WSH_PATH_TEST,
+        MULTI_P2TR_PATH,
+        MULTI_P2TR_PATH_TEST,
     ];
     VALID_PATHS
         .iter()
@@ -0,0 +1,663 @@
+// BIP-327 MuSig2 for taproot multisig key path spends.
+// The device has no session storage between two QR scans, so it only implements the
+// DeterministicSign variant: it is always the last signer to contribute its nonce and
+// derives that nonce from the other participants' nonces and the message.
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use bitcoin::hashes::{sha256, Hash, HashEngine};
+use bitcoin::secp256k1::{PublicKey, Scalar, Secp256k1, SecretKey, XOnlyPublicKey};
+use bitcoin::taproot::{TapNodeHash, TapTweakHash};
+use itertools::Itertools;
+
+use crate::BitcoinError;
+
+pub const PUB_NONCE_LEN: usize = 66;
+pub const PARTIAL_SIG_LEN: usize = 32;
+
+// secp256k1 curve order
+const CURVE_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+const ZERO: [u8; 32] = [0; 32];
+const ONE: [u8; 32] = {
+    let mut one = [0; 32];
+    one[31] = 1;
+    one
+};
+
+#[derive(Debug, Clone)]
+pub struct KeyAggContext {
+    pubkeys: Vec<PublicKey>,
+    q: PublicKey,
+    // gacc is always 1 or -1
+    gacc_negated: bool,
+    tacc: [u8; 32],
+}
+
+impl KeyAggContext {
+    pub fn new(pubkeys: Vec<PublicKey>) -> Result<Self, BitcoinError> {
+        if pubkeys.is_empty() {
+            return Err(BitcoinError::MuSig2Error("no participant keys".to_string()));
+        }
+        let secp = Secp256k1::verification_only();
+        let points = pubkeys
+            .iter()
+            .filter_map(|pk| point_mul(&secp, pk, &key_agg_coeff(&pubkeys, pk)))
+            .collect::<Vec<_>>();
+        let q = PublicKey::combine_keys(&points.iter().collect::<Vec<_>>())
+            .map_err(|_| BitcoinError::MuSig2Error("aggregated key is infinity".to_string()))?;
+        Ok(Self {
+            pubkeys,
+            q,
+            gacc_negated: false,
+            tacc: ZERO,
+        })
+    }
+
+    pub fn apply_tweak(&mut self, tweak: &[u8; 32], is_xonly: bool) -> Result<(), BitcoinError> {
+        let secp = Secp256k1::new();
+        let t = Scalar::from_be_bytes(*tweak)
+            .map_err(|_| BitcoinError::MuSig2Error("tweak out of range".to_string()))?;
+        let negate = is_xonly && !has_even_y(&self.q);
+        let g_q = if negate { self.q.negate(&secp) } else { self.q };
+        self.q = g_q
+            .add_exp_tweak(&secp, &t)
+            .map_err(|_| BitcoinError::MuSig2Error("tweaked key is infinity".to_string()))?;
+        self.gacc_negated ^= negate;
+        let g_tacc = if negate {
+            scalar_neg(&self.tacc)
+        } else {
+            self.tacc
+        };
+        self.tacc = scalar_add(tweak, &g_tacc);
+        Ok(())
+    }
+
+    // the BIP-341 tweak that turns the aggregated key into the taproot output key
+    pub fn apply_taproot_tweak(
+        &mut self,
+        merkle_root: Option<TapNodeHash>,
+    ) -> Result<(), BitcoinError> {
+        let tweak = TapTweakHash::from_key_and_tweak(self.x_only_public_key(), merkle_root);
+        self.apply_tweak(&tweak.to_byte_array(), true)
+    }
+
+    pub fn pubkeys(&self) -> &Vec<PublicKey> {
+        &self.pubkeys
+    }
+
+    pub fn aggregated_public_key(&self) -> PublicKey {
+        self.q
+    }
+
+    pub fn x_only_public_key(&self) -> XOnlyPublicKey {
+        self.q.x_only_public_key().0
+    }
+}
+
+pub fn key_sort(pubkeys: &[PublicKey]) -> Vec<PublicKey> {
+    pubkeys
+        .iter()
+        .cloned()
+        .sorted_by_key(|pk| pk.serialize())
+        .collect()
+}
+
+pub fn nonce_agg(pubnonces: &[[u8; PUB_NONCE_LEN]]) -> Result<[u8; PUB_NONCE_LEN], BitcoinError> {
+    let mut aggnonce = [0u8; PUB_NONCE_LEN];
+    for j in 0..2 {
+        let points = pubnonces
+            .iter()
+            .map(|nonce| {
+                PublicKey::from_slice(&nonce[j * 33..(j + 1) * 33])
+                    .map_err(|_| BitcoinError::MuSig2Error("invalid public nonce".to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // an infinity sum is encoded as 33 zero bytes
+        if let Ok(r) = PublicKey::combine_keys(&points.iter().collect::<Vec<_>>()) {
+            aggnonce[j * 33..(j + 1) * 33].copy_from_slice(&r.serialize());
+        }
+    }
+    Ok(aggnonce)
+}
+
+// returns our public nonce and partial signature, aggothernonce is NonceAgg of every other
+// participant's public nonce
+pub fn deterministic_sign(
+    sk: &SecretKey,
+    aggothernonce: &[u8; PUB_NONCE_LEN],
+    ctx: &KeyAggContext,
+    msg: &[u8],
+) -> Result<([u8; PUB_NONCE_LEN], [u8; PARTIAL_SIG_LEN]), BitcoinError> {
+    let secp = Secp256k1::new();
+    let aggpk = ctx.x_only_public_key().serialize();
+    let mut k = [ZERO; 2];
+    for (i, k_i) in k.iter_mut().enumerate() {
+        *k_i = reduce(tagged_hash(
+            "MuSig/deterministic/nonce",
+            &[
+                &sk.secret_bytes(),
+                aggothernonce,
+                &aggpk,
+                &(msg.len() as u64).to_be_bytes(),
+                msg,
+                &[i as u8],
+            ],
+        ));
+    }
+    let mut pubnonce = [0u8; PUB_NONCE_LEN];
+    for (i, k_i) in k.iter().enumerate() {
+        let k_i = SecretKey::from_slice(k_i)
+            .map_err(|_| BitcoinError::MuSig2Error("invalid secret nonce".to_string()))?;
+        pubnonce[i * 33..(i + 1) * 33]
+            .copy_from_slice(&PublicKey::from_secret_key(&secp, &k_i).serialize());
+    }
+    let aggnonce = nonce_agg(&[pubnonce, *aggothernonce])?;
+    let psig = sign(&k, sk, ctx, &aggnonce, msg)?;
+    // BIP-327 recommends checking our own partial signature before handing it out
+    partial_sig_verify(
+        &psig,
+        &pubnonce,
+        &PublicKey::from_secret_key(&secp, sk),
+        ctx,
+        &aggnonce,
+        msg,
+    )?;
+    Ok((pubnonce, psig))
+}
+
+pub fn partial_sig_verify(
+    psig: &[u8; PARTIAL_SIG_LEN],
+    pubnonce: &[u8; PUB_NONCE_LEN],
+    pk: &PublicKey,
+    ctx: &KeyAggContext,
+    aggnonce: &[u8; PUB_NONCE_LEN],
+    msg: &[u8],
+) -> Result<(), BitcoinError> {
+    let secp = Secp256k1::new();
+    let s = SecretKey::from_slice(psig)
+        .map_err(|_| BitcoinError::MuSig2Error("partial signature out of range".to_string()))?;
+    if !ctx.pubkeys.contains(pk) {
+        return Err(BitcoinError::MuSig2Error(
+            "signing key is not a participant".to_string(),
+        ));
+    }
+    let (b, r, e) = session_values(ctx, aggnonce, msg)?;
+    let r1 = PublicKey::from_slice(&pubnonce[..33])
+        .map_err(|_| BitcoinError::MuSig2Error("invalid public nonce".to_string()))?;
+    let r2 = PublicKey::from_slice(&pubnonce[33..])
+        .map_err(|_| BitcoinError::MuSig2Error("invalid public nonce".to_string()))?;
+    let a = key_agg_coeff(&ctx.pubkeys, pk);
+    let ea = scalar_mul(&e, &a);
+    let ea = if has_even_y(&ctx.q) ^ ctx.gacc_negated {
+        ea
+    } else {
+        scalar_neg(&ea)
+    };
+    let re = [Some(r1), point_mul(&secp, &r2, &b)]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    let re = PublicKey::combine_keys(&re.iter().collect::<Vec<_>>())
+        .map_err(|_| BitcoinError::MuSig2Error("invalid public nonce".to_string()))?;
+    let re = if has_even_y(&r) { re } else { re.negate(&secp) };
+    let points = [Some(re), point_mul(&secp, pk, &ea)]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    let expected = PublicKey::combine_keys(&points.iter().collect::<Vec<_>>()).ok();
+    if expected != Some(PublicKey::from_secret_key(&secp, &s)) {
+        return Err(BitcoinError::MuSig2Error(
+            "invalid partial signature".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn partial_sig_agg(
+    psigs: &[[u8; PARTIAL_SIG_LEN]],
+    aggnonce: &[u8; PUB_NONCE_LEN],
+    ctx: &KeyAggContext,
+    msg: &[u8],
+) -> Result<[u8; 64], BitcoinError> {
+    let (_, r, e) = session_values(ctx, aggnonce, msg)?;
+    let mut s = ZERO;
+    for psig in psigs {
+        if Scalar::from_be_bytes(*psig).is_err() {
+            return Err(BitcoinError::MuSig2Error(
+                "partial signature out of range".to_string(),
+            ));
+        }
+        s = scalar_add(&s, psig);
+    }
+    let g_tacc = if has_even_y(&ctx.q) {
+        ctx.tacc
+    } else {
+        scalar_neg(&ctx.tacc)
+    };
+    s = scalar_add(&s, &scalar_mul(&e, &g_tacc));
+
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&r.x_only_public_key().0.serialize());
+    signature[32..].copy_from_slice(&s);
+    Ok(signature)
+}
+
+fn sign(
+    k: &[[u8; 32]; 2],
+    sk: &SecretKey,
+    ctx: &KeyAggContext,
+    aggnonce: &[u8; PUB_NONCE_LEN],
+    msg: &[u8],
+) -> Result<[u8; PARTIAL_SIG_LEN], BitcoinError> {
+    let secp = Secp256k1::new();
+    let (b, r, e) = session_values(ctx, aggnonce, msg)?;
+    let (k1, k2) = if has_even_y(&r) {
+        (k[0], k[1])
+    } else {
+        (scalar_neg(&k[0]), scalar_neg(&k[1]))
+    };
+    let pk = PublicKey::from_secret_key(&secp, sk);
+    if !ctx.pubkeys.contains(&pk) {
+        return Err(BitcoinError::MuSig2Error(
+            "signing key is not a participant".to_string(),
+        ));
+    }
+    let a = key_agg_coeff(&ctx.pubkeys, &pk);
+    let d = if has_even_y(&ctx.q) ^ ctx.gacc_negated {
+        sk.secret_bytes()
+    } else {
+        sk.negate().secret_bytes()
+    };
+    let s = scalar_add(
+        &scalar_add(&k1, &scalar_mul(&b, &k2)),
+        &scalar_mul(&e, &scalar_mul(&a, &d)),
+    );
+    Ok(s)
+}
+
+// (b, R, e) of the signing session
+fn session_values(
+    ctx: &KeyAggContext,
+    aggnonce: &[u8; PUB_NONCE_LEN],
+    msg: &[u8],
+) -> Result<([u8; 32], PublicKey, [u8; 32]), BitcoinError> {
+    let secp = Secp256k1::new();
+    let aggpk = ctx.x_only_public_key().serialize();
+    let b = reduce(tagged_hash("MuSig/noncecoef", &[aggnonce, &aggpk, msg]));
+    let r1 = parse_point_ext(&aggnonce[..33])?;
+    let r2 = parse_point_ext(&aggnonce[33..])?;
+    let b_r2 = r2.and_then(|r2| point_mul(&secp, &r2, &b));
+    let points = [r1, b_r2].into_iter().flatten().collect::<Vec<_>>();
+    // an infinity nonce is replaced by the generator
+    let r = PublicKey::combine_keys(&points.iter().collect::<Vec<_>>()).unwrap_or_else(|_| {
+        PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&ONE).unwrap())
+    });
+    let e = reduce(tagged_hash(
+        "BIP0340/challenge",
+        &[&r.x_only_public_key().0.serialize(), &aggpk, msg],
+    ));
+    Ok((b, r, e))
+}
+
+fn key_agg_coeff(pubkeys: &[PublicKey], pk: &PublicKey) -> [u8; 32] {
+    // the first key that differs from the first one gets a coefficient of 1
+    let second_key = pubkeys.iter().find(|key| **key != pubkeys[0]);
+    if second_key == Some(pk) {
+        return ONE;
+    }
+    let serialized = pubkeys
+        .iter()
+        .map(|key| key.serialize())
+        .collect::<Vec<_>>();
+    let list = serialized
+        .iter()
+        .map(|key| key.as_slice())
+        .collect::<Vec<_>>();
+    let l = tagged_hash("KeyAgg list", &list);
+    reduce(tagged_hash("KeyAgg coefficient", &[&l, &pk.serialize()]))
+}
+
+fn parse_point_ext(bytes: &[u8]) -> Result<Option<PublicKey>, BitcoinError> {
+    if bytes.iter().all(|b| *b == 0) {
+        return Ok(None);
+    }
+    PublicKey::from_slice(bytes)
+        .map(Some)
+        .map_err(|_| BitcoinError::MuSig2Error("invalid aggregated nonce".to_string()))
+}
+
+fn has_even_y(point: &PublicKey) -> bool {
+    point.serialize()[0] == 0x02
+}
+
+fn point_mul<C: bitcoin::secp256k1::Verification>(
+    secp: &Secp256k1<C>,
+    point: &PublicKey,
+    k: &[u8; 32],
+) -> Option<PublicKey> {
+    let k = Scalar::from_be_bytes(*k).ok()?;
+    point.mul_tweak(secp, &k).ok()
+}
+
+pub(crate) fn tagged_hash(tag: &str, data: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = sha256::Hash::hash(tag.as_bytes());
+    let mut engine = sha256::Hash::engine();
+    engine.input(tag_hash.as_ref());
+    engine.input(tag_hash.as_ref());
+    for d in data {
+        engine.input(d);
+    }
+    sha256::Hash::from_engine(engine).to_byte_array()
+}
+
+fn reduce(mut value: [u8; 32]) -> [u8; 32] {
+    if Scalar::from_be_bytes(value).is_ok() {
+        return value;
+    }
+    // value is below 2^256 < 2n, a single subtraction is enough
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let diff = value[i] as i16 - CURVE_ORDER[i] as i16 - borrow;
+        borrow = (diff < 0) as i16;
+        value[i] = (diff + (borrow << 8)) as u8;
+    }
+    value
+}
+
+fn scalar_add(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    match (SecretKey::from_slice(a), Scalar::from_be_bytes(*b)) {
+        (Ok(a), Ok(b)) => a.add_tweak(&b).map(|r| r.secret_bytes()).unwrap_or(ZERO),
+        // a is zero
+        _ => *b,
+    }
+}
+
+fn scalar_mul(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    match (SecretKey::from_slice(a), Scalar::from_be_bytes(*b)) {
+        (Ok(a), Ok(b)) => a.mul_tweak(&b).map(|r| r.secret_bytes()).unwrap_or(ZERO),
+        _ => ZERO,
+    }
+}
+
+fn scalar_neg(a: &[u8; 32]) -> [u8; 32] {
+    SecretKey::from_slice(a)
+        .map(|a| a.negate().secret_bytes())
+        .unwrap_or(ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::secp256k1::{schnorr, Message};
+    use core::str::FromStr;
+
+    const X1: &str = "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9";
+    const X2: &str = "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659";
+    const X3: &str = "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66";
+
+    fn keys(indices: &[usize]) -> Vec<PublicKey> {
+        let all = [X1, X2, X3].map(|k| PublicKey::from_str(k).unwrap());
+        indices.iter().map(|i| all[*i]).collect()
+    }
+
+    #[test]
+    fn test_key_agg_vectors() {
+        let cases = [
+            (
+                vec![0, 1, 2],
+                "90539eede565f5d054f32cc0c220126889ed1e5d193baf15aef344fe59d4610c",
+            ),
+            (
+                vec![2, 1, 0],
+                "6204de8b083426dc6eaf9502d27024d53fc826bf7d2012148a0575435df54b2b",
+            ),
+            (
+                vec![0, 0, 0],
+                "b436e3bad62b8cd409969a224731c193d051162d8c5ae8b109306127da3aa935",
+            ),
+            (
+                vec![0, 0, 1, 1],
+                "69bc22bfa5d106306e48a20679de1d7389386124d07571d0d872686028c26a3e",
+            ),
+        ];
+        for (indices, expected) in cases {
+            let ctx = KeyAggContext::new(keys(&indices)).unwrap();
+            assert_eq!(expected, ctx.x_only_public_key().to_string());
+        }
+        assert_eq!(keys(&[2, 0, 1]), key_sort(&keys(&[0, 1, 2])));
+    }
+
+    // BIP-327 sign_verify_vectors.json
+    const SK: &str = "7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671";
+    const SECNONCE: &str = "508B81A611F100A6B2B6B29656590898AF488BCF2E1F55CF22E5CFB84421FE61FA27FD49B1D50085B481285E1CA205D55C82CC1B31FF5CD54A489829355901F7";
+    const SIGN_PUBKEYS: [&str; 3] = [
+        "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+        "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA661",
+    ];
+    const SIGN_PUBNONCES: [&str; 4] = [
+        "0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+        "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F817980279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        "032DE2662628C90B03F5E720284EB52FF7D71F4284F627B68A853D78C78E1FFE9303E4C5524E83FFE1493B9077CF1CA6BEB2090C93D930321071AD40B2F44E599046",
+        "0237C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0387BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+    ];
+    const SIGN_AGGNONCE: &str = "028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9";
+
+    fn sign_msgs() -> [Vec<u8>; 3] {
+        [
+            hex::decode("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF")
+                .unwrap(),
+            Vec::new(),
+            [0x26u8; 38].to_vec(),
+        ]
+    }
+
+    fn pubnonce(index: usize) -> [u8; PUB_NONCE_LEN] {
+        hex::decode(SIGN_PUBNONCES[index])
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    // (key indices, nonce indices, msg index, signer index, expected partial signature)
+    fn sign_cases() -> [(Vec<usize>, Vec<usize>, usize, usize, &'static str); 6] {
+        [
+            (
+                vec![0, 1, 2],
+                vec![0, 1, 2],
+                0,
+                0,
+                "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+            ),
+            (
+                vec![1, 0, 2],
+                vec![1, 0, 2],
+                0,
+                1,
+                "9FF2F7AAA856150CC8819254218D3ADEEB0535269051897724F9DB3789513A52",
+            ),
+            (
+                vec![1, 2, 0],
+                vec![1, 2, 0],
+                0,
+                2,
+                "FA23C359F6FAC4E7796BB93BC9F0532A95468C539BA20FF86D7C76ED92227900",
+            ),
+            // the aggregated nonce is infinity
+            (
+                vec![0, 1],
+                vec![0, 3],
+                0,
+                0,
+                "AE386064B26105404798F75DE2EB9AF5EDA5387B064B83D049CB7C5E08879531",
+            ),
+            // empty message
+            (
+                vec![0, 1, 2],
+                vec![0, 1, 2],
+                1,
+                0,
+                "D7D63FFD644CCDA4E62BC2BC0B1D02DD32A1DC3030E155195810231D1037D82D",
+            ),
+            // 38 byte message
+            (
+                vec![0, 1, 2],
+                vec![0, 1, 2],
+                2,
+                0,
+                "E184351828DA5094A97C79CABDAAA0BFB87608C32E8829A4DF5340A6F243B78C",
+            ),
+        ]
+    }
+
+    fn sign_pubkeys(indices: &[usize]) -> Vec<PublicKey> {
+        indices
+            .iter()
+            .map(|i| PublicKey::from_str(SIGN_PUBKEYS[*i]).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_sign_vectors() {
+        let sk = SecretKey::from_str(SK).unwrap();
+        let secnonce = hex::decode(SECNONCE).unwrap();
+        let k = [
+            secnonce[..32].try_into().unwrap(),
+            secnonce[32..].try_into().unwrap(),
+        ];
+        let msgs = sign_msgs();
+        for (key_indices, nonce_indices, msg_index, _, expected) in sign_cases() {
+            let ctx = KeyAggContext::new(sign_pubkeys(&key_indices)).unwrap();
+            let nonces = nonce_indices
+                .iter()
+                .map(|i| pubnonce(*i))
+                .collect::<Vec<_>>();
+            let aggnonce = nonce_agg(&nonces).unwrap();
+            if nonce_indices == [0, 1, 2] {
+                assert_eq!(SIGN_AGGNONCE, hex::encode_upper(aggnonce));
+            }
+            let psig = sign(&k, &sk, &ctx, &aggnonce, &msgs[msg_index]).unwrap();
+            assert_eq!(expected, hex::encode_upper(psig));
+        }
+    }
+
+    #[test]
+    fn test_partial_sig_verify_vectors() {
+        let msgs = sign_msgs();
+        for (key_indices, nonce_indices, msg_index, signer, expected) in sign_cases() {
+            let pubkeys = sign_pubkeys(&key_indices);
+            let ctx = KeyAggContext::new(pubkeys.clone()).unwrap();
+            let nonces = nonce_indices
+                .iter()
+                .map(|i| pubnonce(*i))
+                .collect::<Vec<_>>();
+            let aggnonce = nonce_agg(&nonces).unwrap();
+            let psig = hex::decode(expected).unwrap().try_into().unwrap();
+            partial_sig_verify(
+                &psig,
+                &nonces[signer],
+                &pubkeys[signer],
+                &ctx,
+                &aggnonce,
+                &msgs[msg_index],
+            )
+            .unwrap();
+        }
+
+        let pubkeys = sign_pubkeys(&[0, 1, 2]);
+        let ctx = KeyAggContext::new(pubkeys.clone()).unwrap();
+        let nonces = [pubnonce(0), pubnonce(1), pubnonce(2)];
+        let aggnonce = nonce_agg(&nonces).unwrap();
+        let verify = |psig: &str, signer: usize, nonce: &[u8; PUB_NONCE_LEN]| {
+            let psig = hex::decode(psig).unwrap().try_into().unwrap();
+            partial_sig_verify(&psig, nonce, &pubkeys[signer], &ctx, &aggnonce, &msgs[0])
+        };
+        // the negation of the valid signature
+        assert!(verify(
+            "FED54434AD4CFE953FC527DC6A5E5BE8F6234907B7C187559557CE87A0541C46",
+            0,
+            &nonces[0]
+        )
+        .is_err());
+        // wrong signer
+        assert!(verify(
+            "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+            1,
+            &nonces[1]
+        )
+        .is_err());
+        // the signature exceeds the group size
+        assert!(verify(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+            0,
+            &nonces[0]
+        )
+        .is_err());
+        // the first nonce point is not on the curve
+        let mut invalid_nonce = nonces[0];
+        invalid_nonce[..33].copy_from_slice(
+            &hex::decode("020000000000000000000000000000000000000000000000000000000000000009")
+                .unwrap(),
+        );
+        assert!(verify(
+            "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+            0,
+            &invalid_nonce
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_sign_and_aggregate() {
+        let secp = Secp256k1::new();
+        let sks =
+            [[0x11u8; 32], [0x22u8; 32], [0x33u8; 32]].map(|k| SecretKey::from_slice(&k).unwrap());
+        let pubkeys = key_sort(
+            &sks.iter()
+                .map(|sk| PublicKey::from_secret_key(&secp, sk))
+                .collect::<Vec<_>>(),
+        );
+        let mut ctx = KeyAggContext::new(pubkeys).unwrap();
+        ctx.apply_taproot_tweak(None).unwrap();
+        let msg = [0x42u8; 32];
+
+        // the first two signers sign deterministically too, each one seeing the nonces before it
+        // as the "other" nonces is not how a real session goes but exercises the same math
+        let other_nonce = |sk: &SecretKey| {
+            let k1 = SecretKey::from_slice(&tagged_hash("n1", &[&sk.secret_bytes()])).unwrap();
+            let k2 = SecretKey::from_slice(&tagged_hash("n2", &[&sk.secret_bytes()])).unwrap();
+            let mut pubnonce = [0u8; PUB_NONCE_LEN];
+            pubnonce[..33].copy_from_slice(&PublicKey::from_secret_key(&secp, &k1).serialize());
+            pubnonce[33..].copy_from_slice(&PublicKey::from_secret_key(&secp, &k2).serialize());
+            ([k1.secret_bytes(), k2.secret_bytes()], pubnonce)
+        };
+        let (k_a, nonce_a) = other_nonce(&sks[0]);
+        let (k_b, nonce_b) = other_nonce(&sks[1]);
+        let aggothernonce = nonce_agg(&[nonce_a, nonce_b]).unwrap();
+        let (nonce_c, psig_c) = deterministic_sign(&sks[2], &aggothernonce, &ctx, &msg).unwrap();
+
+        let aggnonce = nonce_agg(&[nonce_a, nonce_b, nonce_c]).unwrap();
+        let psig_a = sign(&k_a, &sks[0], &ctx, &aggnonce, &msg).unwrap();
+        let psig_b = sign(&k_b, &sks[1], &ctx, &aggnonce, &msg).unwrap();
+        let signature = partial_sig_agg(&[psig_a, psig_b, psig_c], &aggnonce, &ctx, &msg).unwrap();
+
+        secp.verify_schnorr(
+            &schnorr::Signature::from_slice(&signature).unwrap(),
+            &Message::from_digest(msg),
+            &ctx.x_only_public_key(),
+        )
+        .unwrap();
+
+        // a wrong partial signature does not aggregate into a valid signature
+        let signature = partial_sig_agg(&[psig_a, psig_a, psig_c], &aggnonce, &ctx, &msg).unwrap();
+        assert!(secp
+            .verify_schnorr(
+                &schnorr::Signature::from_slice(&signature).unwrap(),
+                &Message::from_digest(msg),
+                &ctx.x_only_public_key(),
+            )
+            .is_err());
+    }
+}
@@ -0,0 +1,512 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use bitcoin::bip32::{ChildNumber, DerivationPath, Fingerprint};
+use bitcoin::{NetworkKind, ScriptBuf};
+use core::str::FromStr;
+use cryptoxide::hashing::sha256;
+use cryptoxide::hmac::Hmac;
+use cryptoxide::mac::Mac;
+use cryptoxide::sha2::Sha256;
+use keystore::algorithms::crypto::hmac_sha512;
+use keystore::algorithms::secp256k1::get_extended_public_key_by_seed;
+use miniscript::descriptor::{Descriptor, DescriptorPublicKey, Wildcard};
+use serde_json::Value;
+
+use crate::addresses::address::Address;
+use crate::multi_sig::Network;
+use crate::{network, BitcoinError};
+
+// BIP-388 wallet policies: a descriptor template with @i/** key placeholders plus the key information
+// vector, e.g. wsh(or_d(multi(2,@0/**,@1/**,@2/**),and_v(v:pk(@3/**),older(52560))))
+pub const MAX_WALLET_POLICY_KEYS: usize = 16;
+pub const MAX_WALLET_POLICY_NAME_LENGTH: usize = 64;
+// stored as the wallet format so registered policies can be told apart from multisig configs
+pub const WALLET_POLICY_FORMAT: &str = "POLICY";
+
+// SLIP-21 label of the key that authenticates registered policies kept in untrusted storage
+const WALLET_POLICY_HMAC_LABEL: &[u8] = b"Keystone-Wallet policy";
+
+#[derive(Debug, Clone, PartialEq)]
+struct KeyPlaceholder {
+    key_index: usize,
+    receive: u32,
+    change: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletPolicy {
+    pub name: String,
+    pub descriptor_template: String,
+    pub keys_info: Vec<String>,
+    pub network: Network,
+    pub verify_code: String,
+    placeholders: Vec<KeyPlaceholder>,
+    key_origins: Vec<(Fingerprint, DerivationPath)>,
+    // receive and change descriptors
+    descriptors: Vec<Descriptor<DescriptorPublicKey>>,
+}
+
+impl WalletPolicy {
+    pub fn new(
+        name: &str,
+        descriptor_template: &str,
+        keys_info: Vec<String>,
+    ) -> Result<Self, BitcoinError> {
+        if name.is_empty()
+            || name.len() > MAX_WALLET_POLICY_NAME_LENGTH
+            || !name.chars().all(|c| c.is_ascii_graphic() || c == ' ')
+        {
+            return Err(policy_error("invalid wallet policy name"));
+        }
+        if keys_info.is_empty() || keys_info.len() > MAX_WALLET_POLICY_KEYS {
+            return Err(policy_error("invalid number of keys"));
+        }
+
+        let mut key_origins = Vec::with_capacity(keys_info.len());
+        let mut network = None;
+        for (i, key_info) in keys_info.iter().enumerate() {
+            if keys_info[..i].contains(key_info) {
+                return Err(policy_error(&format!("duplicate key @{}", i)));
+            }
+            let key = DescriptorPublicKey::from_str(key_info)
+                .map_err(|e| policy_error(&format!("invalid key @{}, {}", i, e)))?;
+            let xkey = match &key {
+                DescriptorPublicKey::XPub(xkey)
+                    if xkey.derivation_path.is_empty() && xkey.wildcard == Wildcard::None =>
+                {
+                    xkey
+                }
+                _ => {
+                    return Err(policy_error(&format!(
+                        "key @{} must be an extended public key without derivation",
+                        i
+                    )))
+                }
+            };
+            let key_network = match xkey.xkey.network {
+                NetworkKind::Main => Network::MainNet,
+                NetworkKind::Test => Network::TestNet,
+            };
+            if *network.get_or_insert(key_network) != key_network {
+                return Err(policy_error("keys are from different networks"));
+            }
+            key_origins.push((
+                key.master_fingerprint(),
+                key.full_derivation_path().unwrap_or_default(),
+            ));
+        }
+
+        let (descriptor, placeholders) = expand_template(descriptor_template, &keys_info)?;
+        let descriptor = Descriptor::<DescriptorPublicKey>::from_str(&descriptor)
+            .map_err(|e| policy_error(&format!("invalid descriptor template, {}", e)))?;
+        if let Descriptor::Bare(_) = descriptor {
+            return Err(policy_error("bare scripts are not supported"));
+        }
+        descriptor
+            .sanity_check()
+            .map_err(|e| policy_error(&format!("insane descriptor template, {}", e)))?;
+        let descriptors = descriptor
+            .into_single_descriptors()
+            .map_err(|e| policy_error(&e.to_string()))?;
+        if descriptors.len() != 2 {
+            return Err(policy_error(
+                "descriptor template must have receive and change paths",
+            ));
+        }
+
+        let network = network.unwrap_or(Network::MainNet);
+        Ok(WalletPolicy {
+            name: name.to_string(),
+            descriptor_template: descriptor_template.to_string(),
+            verify_code: calculate_wallet_policy_verify_code(descriptor_template, &keys_info),
+            keys_info,
+            network,
+            placeholders,
+            key_origins,
+            descriptors,
+        })
+    }
+
+    pub fn get_network(&self) -> &Network {
+        &self.network
+    }
+
+    pub fn get_key_origins(&self) -> &[(Fingerprint, DerivationPath)] {
+        &self.key_origins
+    }
+
+    pub fn contains_fingerprint(&self, xfp: &str) -> bool {
+        self.key_origins
+            .iter()
+            .any(|(fingerprint, _)| fingerprint.to_string().eq_ignore_ascii_case(xfp))
+    }
+
+    pub fn derive_script_pubkey(
+        &self,
+        is_change: bool,
+        index: u32,
+    ) -> Result<ScriptBuf, BitcoinError> {
+        let descriptor = &self.descriptors[is_change as usize];
+        let descriptor = descriptor
+            .at_derivation_index(index)
+            .map_err(|e| BitcoinError::MultiSigWalletAddressCalError(e.to_string()))?;
+        Ok(descriptor.script_pubkey())
+    }
+
+    pub fn derive_address(&self, is_change: bool, index: u32) -> Result<String, BitcoinError> {
+        let script = self.derive_script_pubkey(is_change, index)?;
+        let network = match self.network {
+            Network::MainNet => network::Network::Bitcoin,
+            Network::TestNet => network::Network::BitcoinTestnet,
+        };
+        Ok(Address::from_script(script.as_script(), network)?.to_string())
+    }
+
+    // find the placeholder a full key path is derived from, returns (is_change, address index)
+    pub fn match_key_path(
+        &self,
+        fingerprint: &Fingerprint,
+        path: &DerivationPath,
+    ) -> Option<(bool, u32)> {
+        let children: &[ChildNumber] = path.as_ref();
+        self.placeholders.iter().find_map(|placeholder| {
+            let (key_fingerprint, origin) = &self.key_origins[placeholder.key_index];
+            let origin: &[ChildNumber] = origin.as_ref();
+            if key_fingerprint != fingerprint
+                || children.len() != origin.len() + 2
+                || !children.starts_with(origin)
+            {
+                return None;
+            }
+            match (children[origin.len()], children[origin.len() + 1]) {
+                (ChildNumber::Normal { index: step }, ChildNumber::Normal { index }) => {
+                    if step == placeholder.receive {
+                        Some((false, index))
+                    } else if step == placeholder.change {
+                        Some((true, index))
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            }
+        })
+    }
+
+    // the name is part of the id because it is what the user approved at registration
+    pub fn id(&self) -> [u8; 32] {
+        let mut data = Vec::new();
+        let fields = [self.name.as_bytes(), self.descriptor_template.as_bytes()]
+            .into_iter()
+            .chain(self.keys_info.iter().map(|v| v.as_bytes()));
+        for field in fields {
+            data.extend_from_slice(&(field.len() as u32).to_be_bytes());
+            data.extend_from_slice(field);
+        }
+        sha256(&data)
+    }
+}
+
+// replace every @i/** or @i/<M;N>/* placeholder with the key it points to
+fn expand_template(
+    template: &str,
+    keys_info: &[String],
+) -> Result<(String, Vec<KeyPlaceholder>), BitcoinError> {
+    let mut expanded = String::new();
+    let mut placeholders: Vec<KeyPlaceholder> = Vec::new();
+    let mut next_key = 0;
+    let mut rest = template;
+    while let Some(position) = rest.find('@') {
+        expanded.push_str(&rest[..position]);
+        rest = &rest[position + 1..];
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let key_index = usize::from_str(&rest[..digits])
+            .map_err(|_e| policy_error("invalid key placeholder"))?;
+        rest = &rest[digits..];
+        let (receive, change) = if let Some(tail) = rest.strip_prefix("/**") {
+            rest = tail;
+            (0, 1)
+        } else if let Some(tail) = rest.strip_prefix("/<") {
+            let end = tail
+                .find(">/*")
+                .ok_or(policy_error("invalid key placeholder derivation"))?;
+            let (receive, change) = tail[..end]
+                .split_once(';')
+                .ok_or(policy_error("invalid key placeholder derivation"))?;
+            rest = &tail[end + 3..];
+            (parse_unhardened(receive)?, parse_unhardened(change)?)
+        } else {
+            return Err(policy_error(
+                "key placeholder must end with /** or /<M;N>/*",
+            ));
+        };
+        if rest.starts_with('\'') || rest.starts_with('h') {
+            return Err(policy_error("hardened wildcard is not allowed"));
+        }
+        if receive >= change {
+            return Err(policy_error("invalid key placeholder derivation"));
+        }
+        // keys are numbered in order of first appearance and must all be used
+        if key_index > next_key || key_index >= keys_info.len() {
+            return Err(policy_error(&format!(
+                "unexpected key placeholder @{}",
+                key_index
+            )));
+        }
+        if key_index == next_key {
+            next_key += 1;
+        }
+        let reused = placeholders.iter().any(|p| {
+            p.key_index == key_index
+                && [p.receive, p.change]
+                    .iter()
+                    .any(|step| *step == receive || *step == change)
+        });
+        if reused {
+            return Err(policy_error(&format!(
+                "key @{} derivation is reused",
+                key_index
+            )));
+        }
+        expanded.push_str(&format!(
+            "{}/<{};{}>/*",
+            keys_info[key_index], receive, change
+        ));
+        placeholders.push(KeyPlaceholder {
+            key_index,
+            receive,
+            change,
+        });
+    }
+    expanded.push_str(rest);
+    if next_key != keys_info.len() {
+        return Err(policy_error("not all keys are used in descriptor template"));
+    }
+    Ok((expanded, placeholders))
+}
+
+fn parse_unhardened(value: &str) -> Result<u32, BitcoinError> {
+    match u32::from_str(value) {
+        Ok(index) if index < 0x8000_0000 => Ok(index),
+        _ => Err(policy_error("invalid key placeholder derivation")),
+    }
+}
+
+fn policy_error(reason: &str) -> BitcoinError {
+    BitcoinError::WalletPolicyError(reason.to_string())
+}
+
+// {"name": "...", "descriptor_template": "...", "keys_info": ["[xfp/path]xpub", ...]}
+pub fn parse_wallet_policy(content: &str) -> Result<WalletPolicy, BitcoinError> {
+    let value: Value = serde_json::from_str(content)
+        .map_err(|e| policy_error(&format!("invalid wallet policy, {}", e)))?;
+    let field = |name: &str| {
+        value
+            .get(name)
+            .and_then(Value::as_str)
+            .ok_or(policy_error(&format!("missing field {}", name)))
+    };
+    let keys_info = value
+        .get("keys_info")
+        .and_then(Value::as_array)
+        .ok_or(policy_error("missing field keys_info"))?
+        .iter()
+        .map(|v| {
+            v.as_str()
+                .map(|v| v.to_string())
+                .ok_or(policy_error("invalid keys_info"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    WalletPolicy::new(field("name")?, field("descriptor_template")?, keys_info)
+}
+
+pub fn calculate_wallet_policy_verify_code(template: &str, keys_info: &[String]) -> String {
+    let data = format!("{} {}", template, keys_info.join(" "));
+    hex::encode(sha256(data.as_bytes()))[0..8].to_string()
+}
+
+pub fn calculate_wallet_policy_hmac(seed: &[u8], policy: &WalletPolicy) -> String {
+    let root = hmac_sha512(b"Symmetric key seed", seed);
+    let mut label = Vec::from([0u8]);
+    label.extend_from_slice(WALLET_POLICY_HMAC_LABEL);
+    let node = hmac_sha512(&root[0..32], &label);
+
+    let mut hmac = Hmac::new(Sha256::new(), &node[32..64]);
+    hmac.input(&policy.id());
+    let mut output = [0u8; 32];
+    hmac.raw_result(&mut output);
+    hex::encode(output)
+}
+
+// check that the policy has one of our keys and that the key really comes from this seed
+pub fn strict_verify_wallet_policy(
+    seed: &[u8],
+    policy: &WalletPolicy,
+    xfp: &str,
+) -> Result<(), BitcoinError> {
+    if !policy.contains_fingerprint(xfp) {
+        return Err(BitcoinError::MultiSigWalletNotMyWallet);
+    }
+    for (key_info, (fingerprint, path)) in policy.keys_info.iter().zip(policy.key_origins.iter()) {
+        if !fingerprint.to_string().eq_ignore_ascii_case(xfp) {
+            continue;
+        }
+        let xpub = get_extended_public_key_by_seed(seed, &format!("m/{}", path))
+            .map_err(|e| policy_error(&format!("Unable to generate xpub, {}", e.to_string())))?;
+        if !key_info.ends_with(&xpub.to_string()) {
+            return Err(policy_error(&format!(
+                "extended public key not match, xfp: {}",
+                xfp
+            )));
+        }
+    }
+    Ok(())
+}
+
+// returns the policy and the hmac to persist along with it
+pub fn register_wallet_policy(
+    seed: &[u8],
+    content: &str,
+    xfp: &str,
+) -> Result<(WalletPolicy, String), BitcoinError> {
+    let policy = parse_wallet_policy(content)?;
+    strict_verify_wallet_policy(seed, &policy, xfp)?;
+    let hmac = calculate_wallet_policy_hmac(seed, &policy);
+    Ok((policy, hmac))
+}
+
+// load a registered policy back from untrusted storage
+pub fn load_wallet_policy(
+    seed: &[u8],
+    content: &str,
+    hmac: &str,
+) -> Result<WalletPolicy, BitcoinError> {
+    let policy = parse_wallet_policy(content)?;
+    let expected = calculate_wallet_policy_hmac(seed, &policy);
+    let matched = expected.len() == hmac.len()
+        && expected
+            .bytes()
+            .zip(hmac.to_ascii_lowercase().bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0;
+    if !matched {
+        return Err(policy_error("wallet policy hmac mismatch"));
+    }
+    Ok(policy)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use alloc::vec;
+
+    const TEMPLATE: &str = "wsh(or_d(multi(2,@0/**,@1/**,@2/**),and_v(v:pk(@3/**),older(52560))))";
+
+    fn keys_info() -> Vec<String> {
+        vec![
+            "[73c5da0a/48'/0'/0'/2']xpub6DkFAXWQ2dHxq2vatrt9qyA3bXYU4ToWQwCHbf5XB2mSTexcHZCeKS1VZYcPoBd5X8yVcbXFHJR9R8UCVpt82VX1VhR28mCyxUFL4r6KFrf".to_string(),
+            "[44c757a9/48'/0'/0'/2']xpub6FA1aifiRQ2if4K56CoceBjqAhGkpPCAzEZzu8dTtjSxszDMrrPkKnG4Vj2LnMFdpgQdrh6eWXRuXA3seVXDJYYkVJSdynVEAT3GD4Wooup".to_string(),
+            "[a261afc4/48'/0'/0'/2']xpub6DbSZPadfy9TYs2ULjLKpeFv3NBg4AR5m4AEiss8bPffAPhVnxB4qZtq9jA8AJYKY2ez4rswbFsMB7ggqQNqKFGuwTVrEAJFqxQfEx98NyH".to_string(),
+            "[83b787a5/48'/0'/0'/2']xpub6F1kySS5M21e5Hx7V9bM1ouaARYTDdzEWDVmZwLxtFbwutdiW83FP6fwKBNugNTbXf27ZcX4DGj6hYmQxwSXyAQCZxVXJ7Rz8P4YFoBBjtB".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_wallet_policy_address() {
+        let policy = WalletPolicy::new("Vault", TEMPLATE, keys_info()).unwrap();
+        assert_eq!(Network::MainNet, policy.network);
+        assert_eq!(
+            "bc1q3u9pj35c3dfsj7cwked746ytmcm4ygs9cny49enwdxe0y420eeqq6hrhl6",
+            policy.derive_address(false, 0).unwrap()
+        );
+        assert_eq!(
+            "bc1qsnnpt5k744s25f90q73v8fge5ah5ap3q9dse4xg3e24lh39zw50qk09trr",
+            policy.derive_address(false, 1).unwrap()
+        );
+        assert_eq!(
+            "bc1qcyel6lkw3k53llg9w2ef765qkvglm98h0wwrae6vhc692j68q56qwjgzhg",
+            policy.derive_address(true, 0).unwrap()
+        );
+        assert_eq!(
+            "bc1qwzmq4rs8z9eqm6sktprrrpt76qv8ecuxgthkrnm7ndea3qad20lsc6q0wk",
+            policy.derive_address(true, 1).unwrap()
+        );
+
+        let fingerprint = Fingerprint::from_str("73c5da0a").unwrap();
+        let path = DerivationPath::from_str("m/48'/0'/0'/2'/1/7").unwrap();
+        assert_eq!(Some((true, 7)), policy.match_key_path(&fingerprint, &path));
+        let path = DerivationPath::from_str("m/48'/0'/0'/2'/2/7").unwrap();
+        assert_eq!(None, policy.match_key_path(&fingerprint, &path));
+        let path = DerivationPath::from_str("m/84'/0'/0'/0/7").unwrap();
+        assert_eq!(None, policy.match_key_path(&fingerprint, &path));
+    }
+
+    #[test]
+    fn test_invalid_wallet_policy() {
+        // keys must appear in order
+        let template = "wsh(or_d(multi(2,@1/**,@0/**,@2/**),and_v(v:pk(@3/**),older(52560))))";
+        assert!(WalletPolicy::new("Vault", template, keys_info()).is_err());
+        // every key must be used
+        let template = "wsh(multi(2,@0/**,@1/**,@2/**))";
+        assert!(WalletPolicy::new("Vault", template, keys_info()).is_err());
+        // the same derivation of a key must not be reused
+        let template = "wsh(or_d(multi(2,@0/**,@1/**,@2/**),and_v(v:pk(@0/<1;2>/*),older(52560))))";
+        let mut keys = keys_info();
+        keys.pop();
+        assert!(WalletPolicy::new("Vault", template, keys.clone()).is_err());
+        let template = "wsh(or_d(multi(2,@0/**,@1/**,@2/**),and_v(v:pk(@0/<2;3>/*),older(52560))))";
+        assert!(WalletPolicy::new("Vault", template, keys).is_ok());
+        // hardened wildcard and raw keys are not allowed
+        let template =
+            "wsh(or_d(multi(2,@0/<0;1>/*',@1/**,@2/**),and_v(v:pk(@3/**),older(52560))))";
+        assert!(WalletPolicy::new("Vault", template, keys_info()).is_err());
+        let template = "wsh(or_d(multi(2,@0,@1/**,@2/**),and_v(v:pk(@3/**),older(52560))))";
+        assert!(WalletPolicy::new("Vault", template, keys_info()).is_err());
+        // duplicate keys
+        let mut keys = keys_info();
+        keys[3] = keys[0].clone();
+        assert!(WalletPolicy::new("Vault", TEMPLATE, keys).is_err());
+        assert!(WalletPolicy::new("", TEMPLATE, keys_info()).is_err());
+    }
+
+    #[test]
+    fn test_register_wallet_policy() {
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let content = serde_json::json!({
+            "name": "Vault",
+            "descriptor_template": TEMPLATE,
+            "keys_info": keys_info(),
+        })
+        .to_string();
+        let (policy, hmac) = register_wallet_policy(&seed, &content, "73c5da0a").unwrap();
+        assert_eq!("01b0055e", policy.verify_code);
+        assert_eq!(
+            "465858f1cfe2db4dab72483aa94b49594967f009ec3d3cb49173866cd1705b6f",
+            hmac
+        );
+        assert_eq!(policy, load_wallet_policy(&seed, &content, &hmac).unwrap());
+
+        let renamed = content.replace("Vault", "Vault2");
+        assert!(load_wallet_policy(&seed, &renamed, &hmac).is_err());
+        assert_eq!(
+            Err(BitcoinError::MultiSigWalletNotMyWallet),
+            register_wallet_policy(&seed, &content, "12345678").map(|_| ())
+        );
+
+        // our fingerprint with a key that is not derived from the seed
+        let mut keys = keys_info();
+        keys[1] = keys[1].replace("44c757a9", "73c5da0a");
+        let content = serde_json::json!({
+            "name": "Vault",
+            "descriptor_template": TEMPLATE,
+            "keys_info": keys,
+        })
+        .to_string();
+        assert!(register_wallet_policy(&seed, &content, "73c5da0a").is_err());
+    }
+}
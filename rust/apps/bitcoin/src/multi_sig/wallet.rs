@@ -12,9 +12,15 @@ use crate::addresses::xyzpub;
 use crate::addresses::xyzpub::Version;
 use crate::BitcoinError;
 
+use crate::multi_sig::coordinator::{
+    export_caravan_wallet_config, export_descriptor, export_specter_wallet_config,
+    parse_caravan_wallet_config, parse_descriptor_wallet_config, parse_specter_wallet_config,
+    WalletConfigFormat,
+};
 use crate::multi_sig::{
-    convert_xpub, MultiSigFormat, MultiSigXPubInfo, Network, MULTI_P2SH_PATH,
-    MULTI_P2WSH_P2SH_PATH, MULTI_P2WSH_P2SH_PATH_TEST, MULTI_P2WSH_PATH, MULTI_P2WSH_PATH_TEST,
+    convert_xpub, MultiSigFormat, MultiSigXPubInfo, Network, MULTI_P2SH_PATH, MULTI_P2TR_PATH,
+    MULTI_P2TR_PATH_TEST, MULTI_P2WSH_P2SH_PATH, MULTI_P2WSH_P2SH_PATH_TEST, MULTI_P2WSH_PATH,
+    MULTI_P2WSH_PATH_TEST,
 };
 
 const CHANGE_XPUB_PREFIX_BY_PATH: bool = true;
@@ -75,6 +81,8 @@ impl MultiSigWalletConfig {
             ("P2WSH-P2SH", Network::TestNet) => MULTI_P2WSH_P2SH_PATH_TEST,
             ("P2WSH", Network::MainNet) => MULTI_P2WSH_PATH,
             ("P2WSH", Network::TestNet) => MULTI_P2WSH_PATH_TEST,
+            ("P2TR" | "P2TR-MUSIG2", Network::MainNet) => MULTI_P2TR_PATH,
+            ("P2TR" | "P2TR-MUSIG2", Network::TestNet) => MULTI_P2TR_PATH_TEST,
             _ => {
                 return Err(BitcoinError::MultiSigWalletParseError(format!(
                     "not support format {}",
@@ -159,7 +167,7 @@ pub fn create_wallet(
     network: Network,
     xfp: &str,
 ) -> Result<MultiSigWalletConfig, BitcoinError> {
-    if !is_valid_multi_sig_policy(threshold, total) {
+    if !is_valid_multi_sig_policy(total, threshold) {
         return Err(BitcoinError::MultiSigWalletCrateError(
             "not a valid policy".to_string(),
         ));
@@ -200,8 +208,6 @@ pub fn create_wallet(
 fn _parse_plain_wallet_config(content: &str) -> Result<MultiSigWalletConfig, BitcoinError> {
     let mut wallet = MultiSigWalletConfig::default();
 
-    let mut is_first = true;
-
     for line in content.lines() {
         if line.trim().starts_with("#") {
             if line.contains("Coldcard") {
@@ -228,6 +234,14 @@ fn _parse_plain_wallet_config(content: &str) -> Result<MultiSigWalletConfig, Bit
         }
     }
 
+    detect_wallet_network(&mut wallet)?;
+
+    wallet.config_text = content.to_string();
+    Ok(wallet)
+}
+
+pub(crate) fn detect_wallet_network(wallet: &mut MultiSigWalletConfig) -> Result<(), BitcoinError> {
+    let mut is_first = true;
     for (_, xpub_item) in wallet.xpub_items.iter().enumerate() {
         let this_network = detect_network(&xpub_item.xpub);
         if this_network == Network::TestNet {
@@ -246,13 +260,20 @@ fn _parse_plain_wallet_config(content: &str) -> Result<MultiSigWalletConfig, Bit
             }
         }
     }
+    Ok(())
+}
 
-    wallet.config_text = content.to_string();
-    Ok(wallet)
+fn _parse_wallet_config(content: &str) -> Result<MultiSigWalletConfig, BitcoinError> {
+    match WalletConfigFormat::detect(content) {
+        WalletConfigFormat::Coldcard => _parse_plain_wallet_config(content),
+        WalletConfigFormat::Caravan => parse_caravan_wallet_config(content),
+        WalletConfigFormat::Specter => parse_specter_wallet_config(content),
+        WalletConfigFormat::Descriptor => parse_descriptor_wallet_config(content),
+    }
 }
 
 pub fn parse_wallet_config(content: &str, xfp: &str) -> Result<MultiSigWalletConfig, BitcoinError> {
-    let mut wallet = _parse_plain_wallet_config(content)?;
+    let mut wallet = _parse_wallet_config(content)?;
     verify_wallet_config(&wallet, xfp)?;
     calculate_wallet_verify_code(&mut wallet)?;
     Ok(wallet)
@@ -266,6 +287,19 @@ pub fn export_wallet_by_ur(
     Ok(Bytes::new(config_data.into_bytes()))
 }
 
+pub fn export_wallet_config(
+    config: &MultiSigWalletConfig,
+    xfp: &str,
+    format: WalletConfigFormat,
+) -> Result<String, BitcoinError> {
+    match format {
+        WalletConfigFormat::Coldcard => generate_config_data(config, xfp),
+        WalletConfigFormat::Caravan => export_caravan_wallet_config(config),
+        WalletConfigFormat::Specter => export_specter_wallet_config(config),
+        WalletConfigFormat::Descriptor => export_descriptor(config, "<0;1>/*"),
+    }
+}
+
 pub fn generate_config_data(
     config: &MultiSigWalletConfig,
     xfp: &str,
@@ -340,7 +374,7 @@ pub fn is_valid_wallet_config(bytes: &Bytes) -> bool {
     if let Ok(d) = String::from_utf8(bytes.get_bytes())
         .map_err(|e| BitcoinError::MultiSigWalletImportXpubError(e.to_string()))
     {
-        if let Ok(_) = _parse_plain_wallet_config(&d) {
+        if let Ok(_) = _parse_wallet_config(&d) {
             return true;
         }
     }
@@ -373,7 +407,7 @@ fn parse_and_set_policy(
     Ok(())
 }
 
-fn process_xpub_and_xfp(
+pub(crate) fn process_xpub_and_xfp(
     wallet: &mut MultiSigWalletConfig,
     label: &str,
     value: &str,
@@ -406,8 +440,8 @@ fn process_xpub_and_xfp(
     }
 }
 
-fn is_valid_multi_sig_policy(total: u32, threshold: u32) -> bool {
-    total <= 15 && total >= 2 && threshold <= total || threshold >= 1
+pub(crate) fn is_valid_multi_sig_policy(total: u32, threshold: u32) -> bool {
+    (2..=15).contains(&total) && (1..=total).contains(&threshold)
 }
 
 fn is_valid_xfp(xfp: &str) -> bool {
@@ -502,15 +536,25 @@ pub fn calculate_multi_sig_verify_code(
         .sorted()
         .join(" ");
 
+    // both taproot formats share a derivation path, the key path type tells them apart
+    let suffix = match format {
+        MultiSigFormat::P2trMusig2 => "musig2",
+        _ => "",
+    };
+
     let path = match (format, network) {
         (MultiSigFormat::P2sh, _) => MULTI_P2SH_PATH,
         (MultiSigFormat::P2wshP2sh, Network::MainNet) => MULTI_P2WSH_P2SH_PATH,
         (MultiSigFormat::P2wshP2sh, Network::TestNet) => MULTI_P2WSH_P2SH_PATH_TEST,
         (MultiSigFormat::P2wsh, Network::MainNet) => MULTI_P2WSH_PATH,
         (MultiSigFormat::P2wsh, Network::TestNet) => MULTI_P2WSH_PATH_TEST,
+        (MultiSigFormat::P2tr | MultiSigFormat::P2trMusig2, Network::MainNet) => MULTI_P2TR_PATH,
+        (MultiSigFormat::P2tr | MultiSigFormat::P2trMusig2, Network::TestNet) => {
+            MULTI_P2TR_PATH_TEST
+        }
     };
 
-    let data = format!("{}{}of{}{}", join_xpubs, threshold, total, path,);
+    let data = format!("{}{}of{}{}{}", join_xpubs, threshold, total, path, suffix);
 
     Ok(hex::encode(sha256(data.as_bytes()))[0..8].to_string())
 }
@@ -717,6 +761,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_valid_multi_sig_policy() {
+        assert!(is_valid_multi_sig_policy(3, 2));
+        assert!(is_valid_multi_sig_policy(15, 15));
+        assert!(!is_valid_multi_sig_policy(3, 0));
+        assert!(!is_valid_multi_sig_policy(3, 4));
+        assert!(!is_valid_multi_sig_policy(1, 1));
+        assert!(!is_valid_multi_sig_policy(16, 2));
+    }
+
     #[test]
     fn test_strict_verify_wallet_config() {
         let config = r#"/*
@@ -0,0 +1,195 @@
+// Inscription envelopes: OP_FALSE OP_IF "ord" <tag> <value> ... OP_0 <body> ... OP_ENDIF
+// inside a taproot script path leaf.
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use bitcoin::blockdata::script::Instruction;
+use bitcoin::hashes::Hash;
+use bitcoin::opcodes::all::{OP_ENDIF, OP_IF, OP_PUSHNUM_1, OP_PUSHNUM_16, OP_PUSHNUM_NEG1};
+use bitcoin::{Script, Txid};
+
+const PROTOCOL_ID: &[u8] = b"ord";
+const BODY_TAG: &[u8] = &[];
+const CONTENT_TYPE_TAG: &[u8] = &[1];
+const POINTER_TAG: &[u8] = &[2];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct InscriptionId {
+    pub txid: Txid,
+    pub index: u32,
+}
+
+impl InscriptionId {
+    pub fn new(txid: Txid, index: u32) -> Self {
+        Self { txid, index }
+    }
+
+    // txid in its internal byte order followed by the little endian index
+    pub fn from_slice(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 36 {
+            return None;
+        }
+        let txid = Txid::from_slice(&bytes[..32]).ok()?;
+        let mut index = [0u8; 4];
+        index.copy_from_slice(&bytes[32..]);
+        Some(Self::new(txid, u32::from_le_bytes(index)))
+    }
+}
+
+impl fmt::Display for InscriptionId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}i{}", self.txid, self.index)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inscription {
+    pub content_type: Option<String>,
+    // offset into the output sats of the reveal transaction
+    pub pointer: Option<u64>,
+    pub body: Vec<u8>,
+}
+
+pub fn parse_inscriptions(script: &Script) -> Vec<Inscription> {
+    let mut inscriptions = Vec::new();
+    let mut instructions = script.instructions();
+    // the envelope header is OP_FALSE OP_IF "ord"
+    let mut header: [Option<Instruction>; 3] = [None, None, None];
+    while let Some(Ok(instruction)) = instructions.next() {
+        header.rotate_left(1);
+        header[2] = Some(instruction);
+        let is_envelope = matches!(header[0], Some(Instruction::PushBytes(v)) if v.is_empty())
+            && matches!(header[1], Some(Instruction::Op(op)) if op == OP_IF)
+            && matches!(header[2], Some(Instruction::PushBytes(v)) if v.as_bytes() == PROTOCOL_ID);
+        if !is_envelope {
+            continue;
+        }
+        header = [None, None, None];
+        let mut pushes = Vec::new();
+        let mut valid = false;
+        while let Some(Ok(instruction)) = instructions.next() {
+            match instruction {
+                Instruction::Op(op) if op == OP_ENDIF => {
+                    valid = true;
+                    break;
+                }
+                Instruction::Op(op) if op == OP_PUSHNUM_NEG1 => pushes.push(Vec::from([0x81])),
+                Instruction::Op(op)
+                    if op.to_u8() >= OP_PUSHNUM_1.to_u8()
+                        && op.to_u8() <= OP_PUSHNUM_16.to_u8() =>
+                {
+                    pushes.push(Vec::from([op.to_u8() - OP_PUSHNUM_1.to_u8() + 1]))
+                }
+                Instruction::PushBytes(v) => pushes.push(v.as_bytes().to_vec()),
+                Instruction::Op(_) => break,
+            }
+        }
+        if valid {
+            inscriptions.push(Inscription::from_payload(pushes));
+        }
+    }
+    inscriptions
+}
+
+impl Inscription {
+    fn from_payload(pushes: Vec<Vec<u8>>) -> Self {
+        let mut content_type = None;
+        let mut pointer = None;
+        let mut body = Vec::new();
+        let mut pushes = pushes.into_iter();
+        while let Some(tag) = pushes.next() {
+            if tag.as_slice() == BODY_TAG {
+                pushes.by_ref().for_each(|v| body.extend(v));
+                break;
+            }
+            let value = match pushes.next() {
+                Some(value) => value,
+                None => break,
+            };
+            match tag.as_slice() {
+                CONTENT_TYPE_TAG if content_type.is_none() => {
+                    content_type = String::from_utf8(value).ok();
+                }
+                // little endian with trailing zeros trimmed, larger values are ignored
+                POINTER_TAG if pointer.is_none() && value.len() <= 8 => {
+                    let mut bytes = [0u8; 8];
+                    bytes[..value.len()].copy_from_slice(&value);
+                    pointer = Some(u64::from_le_bytes(bytes));
+                }
+                _ => {}
+            }
+        }
+        Self {
+            content_type,
+            pointer,
+            body,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use bitcoin::opcodes::all::{OP_CHECKSIG, OP_PUSHBYTES_0};
+    use bitcoin::script::Builder;
+    use core::str::FromStr;
+
+    #[test]
+    fn test_parse_inscriptions() {
+        let script = Builder::new()
+            .push_slice([0x11u8; 32])
+            .push_opcode(OP_CHECKSIG)
+            .push_opcode(OP_PUSHBYTES_0)
+            .push_opcode(OP_IF)
+            .push_slice(b"ord")
+            .push_slice([1u8])
+            .push_slice(b"text/plain;charset=utf-8")
+            .push_slice([2u8])
+            .push_slice([0x10u8, 0x27])
+            .push_opcode(OP_PUSHBYTES_0)
+            .push_slice(b"Hello, ")
+            .push_slice(b"world!")
+            .push_opcode(OP_ENDIF)
+            .into_script();
+        let inscriptions = parse_inscriptions(&script);
+        assert_eq!(1, inscriptions.len());
+        assert_eq!(
+            Some("text/plain;charset=utf-8".to_string()),
+            inscriptions[0].content_type
+        );
+        assert_eq!(Some(10000), inscriptions[0].pointer);
+        assert_eq!(b"Hello, world!".to_vec(), inscriptions[0].body);
+
+        // an envelope that is never closed is not an inscription
+        let script = Builder::new()
+            .push_opcode(OP_PUSHBYTES_0)
+            .push_opcode(OP_IF)
+            .push_slice(b"ord")
+            .push_opcode(OP_PUSHBYTES_0)
+            .push_slice(b"body")
+            .into_script();
+        assert!(parse_inscriptions(&script).is_empty());
+        let script = Builder::new()
+            .push_slice([0x11u8; 32])
+            .push_opcode(OP_CHECKSIG)
+            .into_script();
+        assert!(parse_inscriptions(&script).is_empty());
+    }
+
+    #[test]
+    fn test_inscription_id() {
+        let txid =
+            Txid::from_str("6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799")
+                .unwrap();
+        let mut bytes = txid.to_byte_array().to_vec();
+        bytes.extend(3u32.to_le_bytes());
+        let id = InscriptionId::from_slice(&bytes).unwrap();
+        assert_eq!(
+            "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i3",
+            id.to_string()
+        );
+        assert!(InscriptionId::from_slice(&bytes[1..]).is_none());
+    }
+}
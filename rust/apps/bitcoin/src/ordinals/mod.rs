@@ -0,0 +1,4 @@
+// Ordinal theory artifacts the signer has to be aware of, sats of an input are assigned to the
+// outputs first in first out and whatever is left over goes to the miner as fee.
+pub mod inscription;
+pub mod runestone;
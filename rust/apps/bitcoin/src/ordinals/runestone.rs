@@ -0,0 +1,467 @@
+// Runestones are the first OP_RETURN OP_13 output, the data pushes are concatenated into a
+// sequence of LEB128 integers. A runestone that can not be deciphered is a cenotaph and burns
+// every rune in the inputs.
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use bitcoin::blockdata::script::Instruction;
+use bitcoin::opcodes::all::{OP_PUSHNUM_13, OP_RETURN};
+use bitcoin::Transaction;
+
+const TAG_BODY: u128 = 0;
+const TAG_FLAGS: u128 = 2;
+const TAG_RUNE: u128 = 4;
+const TAG_PREMINE: u128 = 6;
+const TAG_CAP: u128 = 8;
+const TAG_AMOUNT: u128 = 10;
+const TAG_HEIGHT_START: u128 = 12;
+const TAG_HEIGHT_END: u128 = 14;
+const TAG_OFFSET_START: u128 = 16;
+const TAG_OFFSET_END: u128 = 18;
+const TAG_MINT: u128 = 20;
+const TAG_POINTER: u128 = 22;
+const TAG_DIVISIBILITY: u128 = 1;
+const TAG_SPACERS: u128 = 3;
+const TAG_SYMBOL: u128 = 5;
+
+const FLAG_ETCHING: u128 = 1 << 0;
+const FLAG_TERMS: u128 = 1 << 1;
+const FLAG_TURBO: u128 = 1 << 2;
+
+const MAX_DIVISIBILITY: u128 = 38;
+const MAX_SPACERS: u128 = 0b00000111_11111111_11111111_11111111;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RuneId {
+    pub block: u64,
+    pub tx: u32,
+}
+
+impl RuneId {
+    pub fn new(block: u64, tx: u32) -> Option<Self> {
+        if block == 0 && tx > 0 {
+            return None;
+        }
+        Some(Self { block, tx })
+    }
+
+    // edicts are sorted and delta encoded, the tx resets whenever the block moves on
+    fn next(self, block: u128, tx: u128) -> Option<Self> {
+        let block = u64::try_from(block).ok()?;
+        let tx = u32::try_from(tx).ok()?;
+        let next_block = self.block.checked_add(block)?;
+        let next_tx = if block == 0 {
+            self.tx.checked_add(tx)?
+        } else {
+            tx
+        };
+        Self::new(next_block, next_tx)
+    }
+}
+
+impl fmt::Display for RuneId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.block, self.tx)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edict {
+    pub id: RuneId,
+    pub amount: u128,
+    // equal to the number of outputs when the amount is split between all of them
+    pub output: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Terms {
+    pub cap: Option<u128>,
+    pub amount: Option<u128>,
+    pub height: (Option<u64>, Option<u64>),
+    pub offset: (Option<u64>, Option<u64>),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Etching {
+    // a reserved name is assigned when the etching is mined
+    pub rune: Option<u128>,
+    pub spacers: Option<u32>,
+    pub divisibility: Option<u8>,
+    pub symbol: Option<char>,
+    pub premine: Option<u128>,
+    pub terms: Option<Terms>,
+    pub turbo: bool,
+}
+
+impl Etching {
+    pub fn spaced_rune(&self) -> Option<String> {
+        self.rune
+            .map(|rune| spaced_rune_name(rune, self.spacers.unwrap_or(0)))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Runestone {
+    pub edicts: Vec<Edict>,
+    pub etching: Option<Etching>,
+    pub mint: Option<RuneId>,
+    pub pointer: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Artifact {
+    Runestone(Runestone),
+    Cenotaph(String),
+}
+
+pub fn rune_name(rune: u128) -> String {
+    if rune == u128::MAX {
+        return "BCGDENLQRQWDSLRUGSNLBTMFIJAV".to_string();
+    }
+    let mut n = rune + 1;
+    let mut name = Vec::new();
+    while n > 0 {
+        name.push(b'A' + ((n - 1) % 26) as u8);
+        n = (n - 1) / 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+pub fn spaced_rune_name(rune: u128, spacers: u32) -> String {
+    let name = rune_name(rune);
+    let mut spaced = String::new();
+    for (i, c) in name.chars().enumerate() {
+        spaced.push(c);
+        if i + 1 < name.len() && spacers & (1 << i) != 0 {
+            spaced.push('•');
+        }
+    }
+    spaced
+}
+
+pub fn decipher(tx: &Transaction) -> Option<Artifact> {
+    let payload = match find_payload(tx)? {
+        Ok(payload) => payload,
+        Err(flaw) => return Some(Artifact::Cenotaph(flaw.to_string())),
+    };
+    let integers = match decode_integers(&payload) {
+        Ok(integers) => integers,
+        Err(flaw) => return Some(Artifact::Cenotaph(flaw.to_string())),
+    };
+    match Runestone::from_integers(tx, &integers) {
+        Ok(runestone) => Some(Artifact::Runestone(runestone)),
+        Err(flaw) => Some(Artifact::Cenotaph(flaw.to_string())),
+    }
+}
+
+fn find_payload(tx: &Transaction) -> Option<core::result::Result<Vec<u8>, &'static str>> {
+    tx.output.iter().find_map(|output| {
+        let mut instructions = output.script_pubkey.instructions();
+        if !matches!(instructions.next(), Some(Ok(Instruction::Op(op))) if op == OP_RETURN) {
+            return None;
+        }
+        if !matches!(instructions.next(), Some(Ok(Instruction::Op(op))) if op == OP_PUSHNUM_13) {
+            return None;
+        }
+        let mut payload = Vec::new();
+        for instruction in instructions {
+            match instruction {
+                Ok(Instruction::PushBytes(v)) => payload.extend_from_slice(v.as_bytes()),
+                Ok(Instruction::Op(_)) => return Some(Err("non pushdata opcode in runestone")),
+                Err(_) => return Some(Err("invalid script in runestone")),
+            }
+        }
+        Some(Ok(payload))
+    })
+}
+
+fn decode_integers(payload: &[u8]) -> core::result::Result<Vec<u128>, &'static str> {
+    let mut integers = Vec::new();
+    let mut n = 0u128;
+    let mut length = 0;
+    for byte in payload {
+        if length > 18 {
+            return Err("overlong varint in runestone");
+        }
+        let value = u128::from(byte & 0x7f);
+        if length == 18 && value & 0x7c != 0 {
+            return Err("varint overflow in runestone");
+        }
+        n |= value << (7 * length);
+        length += 1;
+        if byte & 0x80 == 0 {
+            integers.push(n);
+            n = 0;
+            length = 0;
+        }
+    }
+    if length > 0 {
+        return Err("truncated varint in runestone");
+    }
+    Ok(integers)
+}
+
+impl Runestone {
+    fn from_integers(
+        tx: &Transaction,
+        integers: &[u128],
+    ) -> core::result::Result<Self, &'static str> {
+        let mut fields: BTreeMap<u128, VecDeque<u128>> = BTreeMap::new();
+        let mut edicts = Vec::new();
+        let mut i = 0;
+        while i < integers.len() {
+            let tag = integers[i];
+            if tag == TAG_BODY {
+                let mut id = RuneId::default();
+                for chunk in integers[i + 1..].chunks(4) {
+                    if chunk.len() != 4 {
+                        return Err("trailing integers in runestone");
+                    }
+                    id = id
+                        .next(chunk[0], chunk[1])
+                        .ok_or("invalid rune id in edict")?;
+                    let output = u32::try_from(chunk[3])
+                        .ok()
+                        .filter(|v| *v as usize <= tx.output.len())
+                        .ok_or("edict output out of range")?;
+                    edicts.push(Edict {
+                        id,
+                        amount: chunk[2],
+                        output,
+                    });
+                }
+                break;
+            }
+            let value = *integers.get(i + 1).ok_or("truncated field in runestone")?;
+            fields.entry(tag).or_default().push_back(value);
+            i += 2;
+        }
+
+        let mut take = |tag: u128| fields.get_mut(&tag).and_then(|v| v.pop_front());
+        let mut flags = take(TAG_FLAGS).unwrap_or(0);
+        let mut take_flag = |flag: u128| {
+            let set = flags & flag != 0;
+            flags &= !flag;
+            set
+        };
+        let is_etching = take_flag(FLAG_ETCHING);
+        let has_terms = take_flag(FLAG_TERMS);
+        let turbo = take_flag(FLAG_TURBO);
+        if flags != 0 {
+            return Err("unrecognized flag in runestone");
+        }
+
+        let to_u64 = |v: Option<u128>| v.and_then(|v| u64::try_from(v).ok());
+        let etching = if is_etching {
+            Some(Etching {
+                rune: take(TAG_RUNE),
+                spacers: take(TAG_SPACERS)
+                    .filter(|v| *v <= MAX_SPACERS)
+                    .map(|v| v as u32),
+                divisibility: take(TAG_DIVISIBILITY)
+                    .filter(|v| *v <= MAX_DIVISIBILITY)
+                    .map(|v| v as u8),
+                symbol: take(TAG_SYMBOL)
+                    .and_then(|v| u32::try_from(v).ok())
+                    .and_then(char::from_u32),
+                premine: take(TAG_PREMINE),
+                terms: if has_terms {
+                    Some(Terms {
+                        cap: take(TAG_CAP),
+                        amount: take(TAG_AMOUNT),
+                        height: (to_u64(take(TAG_HEIGHT_START)), to_u64(take(TAG_HEIGHT_END))),
+                        offset: (to_u64(take(TAG_OFFSET_START)), to_u64(take(TAG_OFFSET_END))),
+                    })
+                } else {
+                    None
+                },
+                turbo,
+            })
+        } else {
+            None
+        };
+
+        let mint = match (take(TAG_MINT), take(TAG_MINT)) {
+            (None, None) => None,
+            (Some(block), Some(tx)) => Some(
+                u64::try_from(block)
+                    .ok()
+                    .zip(u32::try_from(tx).ok())
+                    .and_then(|(block, tx)| RuneId::new(block, tx))
+                    .ok_or("invalid mint in runestone")?,
+            ),
+            _ => return Err("invalid mint in runestone"),
+        };
+        let pointer = match take(TAG_POINTER) {
+            None => None,
+            Some(pointer) => Some(
+                u32::try_from(pointer)
+                    .ok()
+                    .filter(|v| (*v as usize) < tx.output.len())
+                    .ok_or("pointer out of range in runestone")?,
+            ),
+        };
+
+        // odd tags can be ignored safely, an unknown even tag may change the meaning
+        if fields
+            .iter()
+            .any(|(tag, values)| tag % 2 == 0 && !values.is_empty())
+        {
+            return Err("unrecognized even tag in runestone");
+        }
+        Ok(Runestone {
+            edicts,
+            etching,
+            mint,
+            pointer,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::absolute::LockTime;
+    use bitcoin::script::{Builder, PushBytesBuf};
+    use bitcoin::transaction::Version;
+    use bitcoin::{Amount, ScriptBuf, TxOut};
+
+    fn encode(integers: &[u128]) -> Vec<u8> {
+        let mut payload = Vec::new();
+        for n in integers {
+            let mut n = *n;
+            while n >> 7 > 0 {
+                payload.push((n as u8 & 0x7f) | 0x80);
+                n >>= 7;
+            }
+            payload.push(n as u8);
+        }
+        payload
+    }
+
+    fn transaction(integers: &[u128]) -> Transaction {
+        let script = Builder::new()
+            .push_opcode(OP_RETURN)
+            .push_opcode(OP_PUSHNUM_13)
+            .push_slice(PushBytesBuf::try_from(encode(integers)).unwrap())
+            .into_script();
+        let output = |script_pubkey: ScriptBuf| TxOut {
+            value: Amount::from_sat(546),
+            script_pubkey,
+        };
+        Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: Vec::new(),
+            output: [
+                output(script),
+                output(
+                    ScriptBuf::from_hex("0014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e2").unwrap(),
+                ),
+            ]
+            .to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_rune_name() {
+        assert_eq!("A", rune_name(0));
+        assert_eq!("Z", rune_name(25));
+        assert_eq!("AA", rune_name(26));
+        assert_eq!("A•B", spaced_rune_name(27, 1));
+        assert_eq!("BCGDENLQRQWDSLRUGSNLBTMFIJAV", rune_name(u128::MAX));
+    }
+
+    #[test]
+    fn test_decipher_edicts() {
+        let tx = transaction(&[TAG_POINTER, 1, TAG_BODY, 840000, 3, 1000, 1, 0, 2, 500, 2]);
+        assert_eq!(
+            Some(Artifact::Runestone(Runestone {
+                edicts: [
+                    Edict {
+                        id: RuneId::new(840000, 3).unwrap(),
+                        amount: 1000,
+                        output: 1,
+                    },
+                    Edict {
+                        id: RuneId::new(840000, 5).unwrap(),
+                        amount: 500,
+                        output: 2,
+                    },
+                ]
+                .to_vec(),
+                etching: None,
+                mint: None,
+                pointer: Some(1),
+            })),
+            decipher(&tx)
+        );
+    }
+
+    #[test]
+    fn test_decipher_etching_and_mint() {
+        let tx = transaction(&[
+            TAG_FLAGS,
+            FLAG_ETCHING | FLAG_TERMS,
+            TAG_RUNE,
+            27,
+            TAG_SPACERS,
+            1,
+            TAG_DIVISIBILITY,
+            2,
+            TAG_SYMBOL,
+            'R' as u128,
+            TAG_PREMINE,
+            100,
+            TAG_CAP,
+            10,
+            TAG_AMOUNT,
+            5,
+            TAG_MINT,
+            1,
+            TAG_MINT,
+            0,
+        ]);
+        let runestone = match decipher(&tx) {
+            Some(Artifact::Runestone(runestone)) => runestone,
+            _ => panic!("not a runestone"),
+        };
+        let etching = runestone.etching.unwrap();
+        assert_eq!(Some("A•B".to_string()), etching.spaced_rune());
+        assert_eq!(Some(2), etching.divisibility);
+        assert_eq!(Some('R'), etching.symbol);
+        assert_eq!(Some(100), etching.premine);
+        assert_eq!(Some(10), etching.terms.clone().unwrap().cap);
+        assert_eq!(Some(RuneId::new(1, 0).unwrap()), runestone.mint);
+    }
+
+    #[test]
+    fn test_decipher_cenotaph() {
+        // unknown even tag
+        assert!(matches!(
+            decipher(&transaction(&[24, 1])),
+            Some(Artifact::Cenotaph(_))
+        ));
+        // edict to an output that does not exist
+        assert!(matches!(
+            decipher(&transaction(&[TAG_BODY, 1, 0, 10, 3])),
+            Some(Artifact::Cenotaph(_))
+        ));
+        // field without a value
+        assert!(matches!(
+            decipher(&transaction(&[TAG_POINTER])),
+            Some(Artifact::Cenotaph(_))
+        ));
+        // odd tags are ignored
+        assert!(matches!(
+            decipher(&transaction(&[25, 1])),
+            Some(Artifact::Runestone(_))
+        ));
+        let mut tx = transaction(&[]);
+        tx.output.remove(0);
+        assert_eq!(None, decipher(&tx));
+    }
+}
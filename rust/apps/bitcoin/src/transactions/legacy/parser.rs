@@ -47,6 +47,8 @@ impl TxData {
             sign_status: (0, 1),
             need_sign: true,
             ecdsa_sighash_type: 0x01,
+            sighash_warning: None,
+            inscriptions: Vec::new(),
         })
     }
     fn parse_raw_tx_output(&self, output: &TxOut) -> Result<ParsedOutput> {
@@ -66,6 +68,7 @@ impl TxData {
             value: output.value,
             path: Some(output.change_address_path.to_string()),
             is_external,
+            inscriptions: Vec::new(),
         })
     }
 }
@@ -3,3 +3,4 @@ pub mod parsed_tx;
 pub mod psbt;
 mod script_type;
 pub mod tx_checker;
+pub mod tx_policy;
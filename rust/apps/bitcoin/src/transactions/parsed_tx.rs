@@ -1,6 +1,8 @@
 use crate::errors::{BitcoinError, Result};
+use crate::multi_sig::policy::WalletPolicy;
 use crate::multi_sig::wallet::MultiSigWalletConfig;
 use crate::network::{Network, NetworkT};
+use crate::transactions::tx_policy::{TxPolicy, TxPolicyWarning};
 use alloc::collections::BTreeMap;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
@@ -24,6 +26,10 @@ pub struct ParsedInput {
     pub is_external: bool,
     pub need_sign: bool,
     pub ecdsa_sighash_type: u8,
+    // what a non default sighash type lets others change
+    pub sighash_warning: Option<String>,
+    // inscriptions carried by the sats of this input
+    pub inscriptions: Vec<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -33,6 +39,8 @@ pub struct ParsedOutput {
     pub value: u64,
     pub path: Option<String>,
     pub is_external: bool,
+    // inscriptions that end up in this output
+    pub inscriptions: Vec<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -48,6 +56,12 @@ pub struct OverviewTx {
     pub is_multisig: bool,
     pub sign_status: Option<String>,
     pub need_sign: bool,
+    // fee and change findings that do not block signing
+    pub policy_warnings: Vec<TxPolicyWarning>,
+    // inscriptions that are spent to the miner as fee
+    pub inscriptions_to_fee: Vec<String>,
+    // inputs whose signature leaves part of the transaction open
+    pub sighash_warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -62,6 +76,9 @@ pub struct DetailTx {
     pub fee_sat: String,
     pub network: String,
     pub sign_status: Option<String>,
+    // inscriptions that are spent to the miner as fee
+    pub inscriptions_to_fee: Vec<String>,
+    pub runes: Vec<String>,
 }
 
 pub struct ParseContext {
@@ -69,6 +86,11 @@ pub struct ParseContext {
     pub extended_public_keys: BTreeMap<DerivationPath, Xpub>,
     pub verify_code: Option<String>,
     pub multisig_wallet_config: Option<MultiSigWalletConfig>,
+    pub wallet_policy: Option<WalletPolicy>,
+    // falls back to the default policy when not set
+    pub tx_policy: Option<TxPolicy>,
+    // SIGHASH_NONE inputs only pass the checks when blind signing is enabled
+    pub allow_sighash_none: bool,
 }
 
 impl ParseContext {
@@ -77,14 +99,28 @@ impl ParseContext {
         extended_public_keys: BTreeMap<DerivationPath, Xpub>,
         verify_code: Option<String>,
         multisig_wallet_config: Option<MultiSigWalletConfig>,
+        tx_policy: Option<TxPolicy>,
     ) -> Self {
         ParseContext {
             master_fingerprint,
             extended_public_keys,
             verify_code,
             multisig_wallet_config,
+            wallet_policy: None,
+            tx_policy,
+            allow_sighash_none: false,
         }
     }
+
+    pub fn with_wallet_policy(mut self, wallet_policy: WalletPolicy) -> Self {
+        self.wallet_policy = Some(wallet_policy);
+        self
+    }
+
+    pub fn with_allow_sighash_none(mut self, allow_sighash_none: bool) -> Self {
+        self.allow_sighash_none = allow_sighash_none;
+        self
+    }
 }
 
 pub const DIVIDER: f64 = 100_000_000 as f64;
@@ -190,6 +226,15 @@ pub trait TxParser {
             .collect::<Vec<String>>();
         overview_to.sort();
         overview_to.dedup();
+        let sighash_warnings = inputs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, v)| {
+                v.sighash_warning
+                    .as_ref()
+                    .map(|warning| format!("Input #{}: {}", index, warning))
+            })
+            .collect::<Vec<String>>();
         let overview = OverviewTx {
             sign_status: Self::get_sign_status_text(&inputs),
             total_output_amount: Self::format_amount(overview_amount, network),
@@ -202,6 +247,9 @@ pub trait TxParser {
             fee_larger_than_amount: fee > overview_amount,
             is_multisig: inputs.iter().any(|v| v.is_multisig),
             need_sign: Self::is_need_sign(&inputs),
+            policy_warnings: Vec::new(),
+            inscriptions_to_fee: Vec::new(),
+            sighash_warnings,
         };
         let detail = DetailTx {
             sign_status: Self::get_sign_status_text(&inputs),
@@ -214,6 +262,8 @@ pub trait TxParser {
             total_output_sat: Self::format_sat(total_output_value),
             fee_sat: Self::format_sat(fee),
             network: network.normalize(),
+            inscriptions_to_fee: Vec::new(),
+            runes: Vec::new(),
         };
         Ok(ParsedTx { overview, detail })
     }
@@ -1,2 +1,6 @@
+mod ordinals;
 pub mod parsed_psbt;
+pub mod psbt_v2;
+mod sighash;
+mod silent_payment;
 pub mod wrapped_psbt;
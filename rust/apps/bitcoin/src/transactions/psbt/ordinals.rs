@@ -0,0 +1,371 @@
+// Inscriptions already owned by an input are announced by the wallet with a proprietary field
+// per inscription, key data is the binary inscription id and the value is the offset of the
+// inscribed sat inside the input as u64 little endian. Inscriptions revealed by this transaction
+// are read from the taproot leaf scripts.
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use bitcoin::psbt::Input;
+
+use crate::errors::{BitcoinError, Result};
+use crate::ordinals::inscription::{parse_inscriptions, Inscription, InscriptionId};
+use crate::ordinals::runestone::{decipher, Artifact, RuneId};
+use crate::transactions::psbt::wrapped_psbt::WrappedPsbt;
+
+const ORD_PROPRIETARY_PREFIX: &[u8] = b"ord";
+const ORD_INSCRIPTION_SUBTYPE: u8 = 0x00;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InscriptionLocations {
+    pub inputs: Vec<Vec<String>>,
+    pub outputs: Vec<Vec<String>>,
+    pub fee: Vec<String>,
+}
+
+impl WrappedPsbt {
+    pub fn locate_inscriptions(&self, input_values: &[u64]) -> Result<InscriptionLocations> {
+        let unsigned_tx = &self.psbt.unsigned_tx;
+        let overflow = || BitcoinError::InvalidTransaction("value overflow".to_string());
+        // the sat range [start, end) of every output
+        let mut output_ranges = Vec::new();
+        let mut total_output_value = 0u64;
+        for output in unsigned_tx.output.iter() {
+            let start = total_output_value;
+            total_output_value = total_output_value
+                .checked_add(output.value.to_sat())
+                .ok_or_else(overflow)?;
+            output_ranges.push((start, total_output_value));
+        }
+        let txid = unsigned_tx.compute_txid();
+        let mut locations = InscriptionLocations {
+            inputs: Vec::new(),
+            outputs: unsigned_tx.output.iter().map(|_| Vec::new()).collect(),
+            fee: Vec::new(),
+        };
+        let mut sats = Vec::new();
+        let mut input_offset = 0u64;
+        let mut revealed = 0u32;
+        for (index, input) in self.psbt.inputs.iter().enumerate() {
+            let value = *input_values.get(index).ok_or(BitcoinError::InvalidInput)?;
+            let mut carried = Vec::new();
+            for (key, offset) in input.proprietary.iter() {
+                if key.prefix != ORD_PROPRIETARY_PREFIX || key.subtype != ORD_INSCRIPTION_SUBTYPE {
+                    continue;
+                }
+                let id =
+                    InscriptionId::from_slice(&key.key).ok_or(BitcoinError::InvalidTransaction(
+                        format!("invalid inscription id in input #{}", index),
+                    ))?;
+                let offset = <[u8; 8]>::try_from(offset.as_slice())
+                    .map(u64::from_le_bytes)
+                    .ok()
+                    .filter(|v| *v < value)
+                    .ok_or(BitcoinError::InvalidTransaction(format!(
+                        "inscription {} is outside of input #{}",
+                        id, index
+                    )))?;
+                carried.push(id.to_string());
+                let position = input_offset.checked_add(offset).ok_or_else(overflow)?;
+                sats.push((position, id.to_string()));
+            }
+            // a new inscription is bound to the first sat of its input unless it points elsewhere
+            for inscription in Self::get_revealed_inscriptions(input) {
+                let id = InscriptionId::new(txid, revealed);
+                revealed = revealed.checked_add(1).ok_or_else(overflow)?;
+                let position = inscription
+                    .pointer
+                    .filter(|v| *v < total_output_value)
+                    .unwrap_or(input_offset);
+                sats.push((position, id.to_string()));
+            }
+            locations.inputs.push(carried);
+            input_offset = input_offset.checked_add(value).ok_or_else(overflow)?;
+        }
+
+        for (position, id) in sats {
+            let output = output_ranges
+                .iter()
+                .position(|(start, end)| position >= *start && position < *end);
+            match output {
+                Some(output) => locations.outputs[output].push(id),
+                None => locations.fee.push(id),
+            }
+        }
+        Ok(locations)
+    }
+
+    fn get_revealed_inscriptions(input: &Input) -> Vec<Inscription> {
+        match &input.final_script_witness {
+            Some(witness) => witness
+                .tapscript()
+                .map(parse_inscriptions)
+                .unwrap_or_default(),
+            None => input
+                .tap_scripts
+                .values()
+                .flat_map(|(script, _)| parse_inscriptions(script))
+                .collect(),
+        }
+    }
+
+    pub fn parse_runestone(&self) -> Vec<String> {
+        let outputs = self.psbt.unsigned_tx.output.len() as u32;
+        let runestone = match decipher(&self.psbt.unsigned_tx) {
+            None => return Vec::new(),
+            Some(Artifact::Cenotaph(flaw)) => {
+                return Vec::from([format!(
+                    "Cenotaph, all runes in the inputs will be burned ({})",
+                    flaw
+                )])
+            }
+            Some(Artifact::Runestone(runestone)) => runestone,
+        };
+        let mut rows = Vec::new();
+        // only the rune etched here has a known divisibility, others are shown in base units
+        let divisibility = runestone
+            .etching
+            .as_ref()
+            .map(|v| v.divisibility.unwrap_or(0));
+        if let Some(etching) = &runestone.etching {
+            let rune = etching
+                .spaced_rune()
+                .unwrap_or("a reserved rune".to_string());
+            match etching.symbol {
+                Some(symbol) => rows.push(format!("Etch {} ({})", rune, symbol)),
+                None => rows.push(format!("Etch {}", rune)),
+            }
+            if let Some(premine) = etching.premine {
+                rows.push(format!(
+                    "Premine {}",
+                    format_rune_amount(premine, divisibility.unwrap_or(0))
+                ));
+            }
+            if let Some(terms) = &etching.terms {
+                rows.push(format!(
+                    "Open mint {} per mint, cap {}",
+                    format_rune_amount(terms.amount.unwrap_or(0), divisibility.unwrap_or(0)),
+                    terms.cap.unwrap_or(0)
+                ));
+            }
+        }
+        if let Some(mint) = runestone.mint {
+            rows.push(format!("Mint rune {}", mint));
+        }
+        for edict in runestone.edicts.iter() {
+            let (rune, divisibility) = match divisibility {
+                Some(divisibility) if edict.id == RuneId::default() => {
+                    ("the etched rune".to_string(), Some(divisibility))
+                }
+                _ => (format!("rune {}", edict.id), None),
+            };
+            let amount = match (edict.amount, divisibility) {
+                (0, _) => "all".to_string(),
+                (amount, Some(divisibility)) => format_rune_amount(amount, divisibility),
+                (amount, None) => format!("{} base units", amount),
+            };
+            let output = if edict.output == outputs {
+                "every output".to_string()
+            } else {
+                format!("output #{}", edict.output)
+            };
+            rows.push(format!("Transfer {} of {} to {}", amount, rune, output));
+        }
+        if let Some(pointer) = runestone.pointer {
+            rows.push(format!("Unallocated runes go to output #{}", pointer));
+        }
+        rows
+    }
+}
+
+// the decimal point is placed by the divisibility, trailing zeros are dropped like ord does
+fn format_rune_amount(amount: u128, divisibility: u8) -> String {
+    let unit = 10u128.pow(divisibility as u32);
+    let (whole, fraction) = (amount / unit, amount % unit);
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let fraction = format!("{:0width$}", fraction, width = divisibility as usize);
+    format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::absolute::LockTime;
+    use bitcoin::hashes::Hash;
+    use bitcoin::opcodes::all::{
+        OP_CHECKSIG, OP_ENDIF, OP_IF, OP_PUSHBYTES_0, OP_PUSHNUM_13, OP_RETURN,
+    };
+    use bitcoin::psbt::{raw::ProprietaryKey, Psbt};
+    use bitcoin::script::Builder;
+    use bitcoin::taproot::{ControlBlock, LeafVersion};
+    use bitcoin::transaction::Version;
+    use bitcoin::{Amount, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid, Witness};
+    use core::str::FromStr;
+
+    const INSCRIPTION_TXID: &str =
+        "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799";
+
+    fn inscription_key(index: u32) -> ProprietaryKey {
+        let mut key = Txid::from_str(INSCRIPTION_TXID)
+            .unwrap()
+            .to_byte_array()
+            .to_vec();
+        key.extend(index.to_le_bytes());
+        ProprietaryKey {
+            prefix: ORD_PROPRIETARY_PREFIX.to_vec(),
+            subtype: ORD_INSCRIPTION_SUBTYPE,
+            key,
+        }
+    }
+
+    fn wrapped(outputs: &[u64], op_return: Option<ScriptBuf>) -> WrappedPsbt {
+        let tx_in = |vout: u32| TxIn {
+            previous_output: OutPoint {
+                txid: Txid::from_str(INSCRIPTION_TXID).unwrap(),
+                vout,
+            },
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::new(),
+        };
+        let mut output = outputs
+            .iter()
+            .map(|value| TxOut {
+                value: Amount::from_sat(*value),
+                script_pubkey: ScriptBuf::from_hex(
+                    "5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+                )
+                .unwrap(),
+            })
+            .collect::<Vec<_>>();
+        if let Some(script_pubkey) = op_return {
+            output.push(TxOut {
+                value: Amount::ZERO,
+                script_pubkey,
+            });
+        }
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: [tx_in(0), tx_in(1)].to_vec(),
+            output,
+        };
+        WrappedPsbt {
+            psbt: Psbt::from_unsigned_tx(tx).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_locate_inscriptions() {
+        // 546 + 10000 sats in, the inscribed sat at offset 100 of the second input is spent to fee
+        let mut psbt = wrapped(&[546, 9000], None);
+        psbt.psbt.inputs[0]
+            .proprietary
+            .insert(inscription_key(0), 0u64.to_le_bytes().to_vec());
+        psbt.psbt.inputs[1]
+            .proprietary
+            .insert(inscription_key(1), 100u64.to_le_bytes().to_vec());
+        let locations = psbt.locate_inscriptions(&[546, 10000]).unwrap();
+        let id = |index: u32| format!("{}i{}", INSCRIPTION_TXID, index);
+        assert_eq!(
+            Vec::from([Vec::from([id(0)]), Vec::from([id(1)])]),
+            locations.inputs
+        );
+        assert_eq!(
+            Vec::from([Vec::from([id(0)]), Vec::from([id(1)])]),
+            locations.outputs
+        );
+        assert!(locations.fee.is_empty());
+
+        let mut psbt = wrapped(&[546, 9000], None);
+        psbt.psbt.inputs[1]
+            .proprietary
+            .insert(inscription_key(1), 9600u64.to_le_bytes().to_vec());
+        let locations = psbt.locate_inscriptions(&[546, 10000]).unwrap();
+        assert_eq!(Vec::from([id(1)]), locations.fee);
+
+        psbt.psbt.inputs[1]
+            .proprietary
+            .insert(inscription_key(1), 10000u64.to_le_bytes().to_vec());
+        assert!(psbt.locate_inscriptions(&[546, 10000]).is_err());
+        // sat positions past u64::MAX are rejected instead of wrapping around
+        let psbt = wrapped(&[u64::MAX, 1], None);
+        assert!(psbt.locate_inscriptions(&[546, 10000]).is_err());
+    }
+
+    #[test]
+    fn test_locate_revealed_inscription() {
+        let mut psbt = wrapped(&[546, 9000], None);
+        let script = Builder::new()
+            .push_slice([0x11u8; 32])
+            .push_opcode(OP_CHECKSIG)
+            .push_opcode(OP_PUSHBYTES_0)
+            .push_opcode(OP_IF)
+            .push_slice(b"ord")
+            .push_slice([1u8])
+            .push_slice(b"text/plain")
+            .push_opcode(OP_PUSHBYTES_0)
+            .push_slice(b"hello")
+            .push_opcode(OP_ENDIF)
+            .into_script();
+        let control_block = ControlBlock::decode(
+            &hex::decode("c150929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0")
+                .unwrap(),
+        )
+        .unwrap();
+        psbt.psbt.inputs[1]
+            .tap_scripts
+            .insert(control_block, (script, LeafVersion::TapScript));
+        let locations = psbt.locate_inscriptions(&[546, 10000]).unwrap();
+        let id = format!("{}i0", psbt.psbt.unsigned_tx.compute_txid());
+        // the first sat of the second input is the first sat of the second output
+        assert_eq!(Vec::from([Vec::new(), Vec::from([id])]), locations.outputs);
+    }
+
+    #[test]
+    fn test_parse_runestone() {
+        let payload = [22u8, 1, 0, 0xc0, 0xa2, 0x33, 3, 0xe8, 0x07, 1];
+        let script = Builder::new()
+            .push_opcode(OP_RETURN)
+            .push_opcode(OP_PUSHNUM_13)
+            .push_slice(payload)
+            .into_script();
+        let psbt = wrapped(&[546, 9000], Some(script));
+        assert_eq!(
+            Vec::from([
+                "Transfer 1000 base units of rune 840000:3 to output #1".to_string(),
+                "Unallocated runes go to output #1".to_string(),
+            ]),
+            psbt.parse_runestone()
+        );
+
+        let script = Builder::new()
+            .push_opcode(OP_RETURN)
+            .push_opcode(OP_PUSHNUM_13)
+            .push_slice([0u8, 1, 0, 10, 9])
+            .into_script();
+        let psbt = wrapped(&[546, 9000], Some(script));
+        assert_eq!(
+            Vec::from([
+                "Cenotaph, all runes in the inputs will be burned (edict output out of range)"
+                    .to_string()
+            ]),
+            psbt.parse_runestone()
+        );
+        assert!(wrapped(&[546, 9000], None).parse_runestone().is_empty());
+    }
+
+    #[test]
+    fn test_format_rune_amount() {
+        assert_eq!("1000", format_rune_amount(1000, 0));
+        assert_eq!("10", format_rune_amount(1000, 2));
+        assert_eq!("10.5", format_rune_amount(1050, 2));
+        assert_eq!("0.0001", format_rune_amount(1, 4));
+        assert_eq!(
+            "3.40282366920938463463374607431768211455",
+            format_rune_amount(u128::MAX, 38)
+        );
+    }
+}
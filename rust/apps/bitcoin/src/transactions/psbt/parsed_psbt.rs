@@ -11,14 +11,14 @@ impl TxParser for WrappedPsbt {
     fn parse(&self, context: Option<&ParseContext>) -> Result<ParsedTx> {
         let network = self.determine_network()?;
         let context = context.ok_or(BitcoinError::InvalidParseContext(format!("empty context")))?;
-        let inputs = self
+        let mut inputs = self
             .psbt
             .inputs
             .iter()
             .enumerate()
             .map(|(i, input)| self.parse_input(input, i, context, &network))
             .collect::<Result<Vec<ParsedInput>>>()?;
-        let outputs = self
+        let mut outputs = self
             .psbt
             .outputs
             .iter()
@@ -26,10 +26,25 @@ impl TxParser for WrappedPsbt {
             .map(|(i, output)| self.parse_output(output, i, context, &network))
             .collect::<Result<Vec<ParsedOutput>>>()?;
 
-        match self.identify_fractal_bitcoin_tx() {
+        let input_values = inputs.iter().map(|v| v.value).collect::<Vec<u64>>();
+        let locations = self.locate_inscriptions(&input_values)?;
+        for (input, inscriptions) in inputs.iter_mut().zip(locations.inputs) {
+            input.inscriptions = inscriptions;
+        }
+        for (output, inscriptions) in outputs.iter_mut().zip(locations.outputs) {
+            output.inscriptions = inscriptions;
+        }
+
+        let mut parsed_tx = match self.identify_fractal_bitcoin_tx() {
             Some(custom_net) => self.normalize(inputs, outputs, &custom_net),
             None => self.normalize(inputs, outputs, &network),
-        }
+        }?;
+        parsed_tx.overview.inscriptions_to_fee = locations.fee.clone();
+        parsed_tx.detail.inscriptions_to_fee = locations.fee;
+        parsed_tx.detail.runes = self.parse_runestone();
+        parsed_tx.overview.policy_warnings =
+            parsed_tx.check_policy(context, &context.tx_policy.clone().unwrap_or_default());
+        Ok(parsed_tx)
     }
 
     fn determine_network(&self) -> Result<Network> {
@@ -105,12 +120,13 @@ mod tests {
         keys.insert(path, extended_pubkey);
 
         let result = wpsbt
-            .parse(Some(&ParseContext {
+            .parse(Some(&ParseContext::new(
                 master_fingerprint,
-                extended_public_keys: keys,
-                verify_code: None,
-                multisig_wallet_config: None,
-            }))
+                keys,
+                None,
+                None,
+                None,
+            )))
             .unwrap();
         assert_eq!("0.00005992 tBTC", result.detail.total_input_amount);
         assert_eq!("0.00004 tBTC", result.detail.total_output_amount);
@@ -150,12 +166,13 @@ mod tests {
         keys.insert(path, extended_pubkey);
 
         let result = wpsbt
-            .parse(Some(&ParseContext {
+            .parse(Some(&ParseContext::new(
                 master_fingerprint,
-                extended_public_keys: keys,
-                verify_code: None,
-                multisig_wallet_config: None,
-            }))
+                keys,
+                None,
+                None,
+                None,
+            )))
             .unwrap();
         assert_eq!("0.00005992 tFB", result.detail.total_input_amount);
         assert_eq!("0.00004 tFB", result.detail.total_output_amount);
@@ -195,12 +212,13 @@ mod tests {
         keys.insert(path, extended_pubkey);
 
         let result = wpsbt
-            .parse(Some(&ParseContext {
+            .parse(Some(&ParseContext::new(
                 master_fingerprint,
-                extended_public_keys: keys,
-                verify_code: None,
-                multisig_wallet_config: None,
-            }))
+                keys,
+                None,
+                None,
+                None,
+            )))
             .unwrap();
 
         assert_eq!("0.000142 BTC", result.detail.total_input_amount);
@@ -221,12 +239,13 @@ mod tests {
         keys.insert(path, extended_pubkey);
 
         let result = wpsbt
-            .parse(Some(&ParseContext {
+            .parse(Some(&ParseContext::new(
                 master_fingerprint,
-                extended_public_keys: keys,
-                verify_code: None,
-                multisig_wallet_config: None,
-            }))
+                keys,
+                None,
+                None,
+                None,
+            )))
             .unwrap();
 
         assert_eq!("0.00006588 tBTC", result.detail.total_input_amount);
@@ -267,12 +286,13 @@ mod tests {
         keys.insert(path, extended_pubkey);
 
         let result = wpsbt
-            .parse(Some(&ParseContext {
+            .parse(Some(&ParseContext::new(
                 master_fingerprint,
-                extended_public_keys: keys,
-                verify_code: None,
-                multisig_wallet_config: None,
-            }))
+                keys,
+                None,
+                None,
+                None,
+            )))
             .unwrap();
 
         println!("result is {:?}", result);
@@ -316,12 +336,13 @@ mod tests {
         keys.insert(path, extended_pubkey);
 
         let result = wpsbt
-            .parse(Some(&ParseContext {
+            .parse(Some(&ParseContext::new(
                 master_fingerprint,
-                extended_public_keys: keys,
-                verify_code: None,
-                multisig_wallet_config: None,
-            }))
+                keys,
+                None,
+                None,
+                None,
+            )))
             .unwrap();
         assert_eq!("0.0005289 BTC", result.detail.total_input_amount);
         assert_eq!("0.00052516 BTC", result.detail.total_output_amount);
@@ -0,0 +1,511 @@
+// BIP-370 PSBT version 2.
+// rust-bitcoin only understands version 0, so a v2 psbt is rebuilt as v0 with the unsigned
+// transaction assembled from the per input/output fields. The v2 only fields are kept aside
+// untouched and merged back when the signed psbt is serialized again.
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use bitcoin::absolute::LockTime;
+use bitcoin::consensus::encode::{deserialize_partial, serialize, VarInt};
+use bitcoin::hashes::Hash;
+use bitcoin::psbt::{Input, Psbt};
+use bitcoin::sighash::EcdsaSighashType;
+use bitcoin::transaction::Version;
+use bitcoin::{Amount, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid, Witness};
+
+use crate::errors::{BitcoinError, Result};
+use crate::transactions::psbt::wrapped_psbt::PSBT_IN_MUSIG2_PARTIAL_SIG;
+
+const PSBT_MAGIC: &[u8] = b"psbt\xff";
+
+const PSBT_GLOBAL_UNSIGNED_TX: u8 = 0x00;
+const PSBT_GLOBAL_TX_VERSION: u8 = 0x02;
+const PSBT_GLOBAL_FALLBACK_LOCKTIME: u8 = 0x03;
+const PSBT_GLOBAL_INPUT_COUNT: u8 = 0x04;
+const PSBT_GLOBAL_OUTPUT_COUNT: u8 = 0x05;
+const PSBT_GLOBAL_TX_MODIFIABLE: u8 = 0x06;
+const PSBT_GLOBAL_VERSION: u8 = 0xfb;
+
+const PSBT_IN_PREVIOUS_TXID: u8 = 0x0e;
+const PSBT_IN_OUTPUT_INDEX: u8 = 0x0f;
+const PSBT_IN_SEQUENCE: u8 = 0x10;
+const PSBT_IN_REQUIRED_TIME_LOCKTIME: u8 = 0x11;
+const PSBT_IN_REQUIRED_HEIGHT_LOCKTIME: u8 = 0x12;
+
+const PSBT_OUT_AMOUNT: u8 = 0x03;
+const PSBT_OUT_SCRIPT: u8 = 0x04;
+
+// PSBT_GLOBAL_TX_MODIFIABLE bits
+const INPUTS_MODIFIABLE: u8 = 0x01;
+const OUTPUTS_MODIFIABLE: u8 = 0x02;
+const HAS_SIGHASH_SINGLE: u8 = 0x04;
+
+const LOCKTIME_THRESHOLD: u32 = 500_000_000;
+
+// a raw key-value pair, the key still starts with its type
+type Pair = (Vec<u8>, Vec<u8>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PsbtV2Fields {
+    global: Vec<Pair>,
+    inputs: Vec<Vec<Pair>>,
+    outputs: Vec<Vec<Pair>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VersionedPsbt {
+    pub psbt: Psbt,
+    pub v2_fields: Option<PsbtV2Fields>,
+}
+
+impl VersionedPsbt {
+    pub fn deserialize(bytes: &[u8]) -> Result<Self> {
+        match PsbtV2Fields::split(bytes)? {
+            Some((v0, v2_fields)) => Ok(Self {
+                psbt: Psbt::deserialize(&v0)
+                    .map_err(|e| BitcoinError::InvalidPsbt(format!("{}", e)))?,
+                v2_fields: Some(v2_fields),
+            }),
+            None => Ok(Self {
+                psbt: Psbt::deserialize(bytes)
+                    .map_err(|e| BitcoinError::InvalidPsbt(format!("{}", e)))?,
+                v2_fields: None,
+            }),
+        }
+    }
+
+    pub fn is_v2(&self) -> bool {
+        self.v2_fields.is_some()
+    }
+
+    // serializes in the version it was received in
+    pub fn serialize(&self) -> Result<Vec<u8>> {
+        match &self.v2_fields {
+            Some(v2_fields) => v2_fields.merge(&self.psbt.serialize()),
+            None => Ok(self.psbt.serialize()),
+        }
+    }
+
+    // BIP-370 signer rules, signatures added since `unsigned` restrict what can still change
+    pub fn update_modifiable(&mut self, unsigned: &Psbt) {
+        let v2_fields = match &mut self.v2_fields {
+            Some(v) => v,
+            None => return,
+        };
+        let mut flags = match v2_fields.get_global(PSBT_GLOBAL_TX_MODIFIABLE) {
+            Some([flags]) => *flags,
+            _ => return,
+        };
+        for (signed, unsigned) in self.psbt.inputs.iter().zip(unsigned.inputs.iter()) {
+            if signature_count(signed) <= signature_count(unsigned) {
+                continue;
+            }
+            // taproot Default behaves as ALL
+            let sighash_type = signed
+                .sighash_type
+                .map(|v| v.to_u32() as u8)
+                .unwrap_or(EcdsaSighashType::All as u8);
+            if sighash_type & 0x80 == 0 {
+                flags &= !INPUTS_MODIFIABLE;
+            }
+            match sighash_type & 0x1f {
+                0x02 => {}
+                0x03 => {
+                    flags |= HAS_SIGHASH_SINGLE;
+                    flags &= !OUTPUTS_MODIFIABLE;
+                }
+                _ => flags &= !OUTPUTS_MODIFIABLE,
+            }
+        }
+        v2_fields.set_global(PSBT_GLOBAL_TX_MODIFIABLE, vec![flags]);
+    }
+}
+
+impl PsbtV2Fields {
+    // returns the equivalent v0 psbt and the v2 only fields, or None for a v0 psbt
+    fn split(bytes: &[u8]) -> Result<Option<(Vec<u8>, Self)>> {
+        let mut reader = MapReader::new(bytes)?;
+        let global = reader.read_map()?;
+        let version = match find(&global, PSBT_GLOBAL_VERSION) {
+            Some(v) => read_u32(v)?,
+            None => 0,
+        };
+        match version {
+            0 => return Ok(None),
+            2 => {}
+            v => {
+                return Err(BitcoinError::InvalidPsbt(format!(
+                    "unsupported psbt version {}",
+                    v
+                )))
+            }
+        }
+        if find(&global, PSBT_GLOBAL_UNSIGNED_TX).is_some() {
+            return Err(BitcoinError::InvalidPsbt(
+                "psbt v2 must not contain an unsigned transaction".to_string(),
+            ));
+        }
+        let tx_version = read_u32(required(&global, PSBT_GLOBAL_TX_VERSION)?)? as i32;
+        let fallback_locktime = find(&global, PSBT_GLOBAL_FALLBACK_LOCKTIME)
+            .map(read_u32)
+            .transpose()?;
+        let input_count = read_count(required(&global, PSBT_GLOBAL_INPUT_COUNT)?)?;
+        let output_count = read_count(required(&global, PSBT_GLOBAL_OUTPUT_COUNT)?)?;
+
+        let mut inputs = Vec::new();
+        let mut tx_inputs = Vec::new();
+        let mut time_locktimes = Vec::new();
+        let mut height_locktimes = Vec::new();
+        let mut require_time = false;
+        let mut require_height = false;
+        for _ in 0..input_count {
+            let input = reader.read_map()?;
+            let txid = Txid::from_slice(required(&input, PSBT_IN_PREVIOUS_TXID)?)
+                .map_err(|e| BitcoinError::InvalidPsbt(e.to_string()))?;
+            let vout = read_u32(required(&input, PSBT_IN_OUTPUT_INDEX)?)?;
+            let sequence = find(&input, PSBT_IN_SEQUENCE)
+                .map(read_u32)
+                .transpose()?
+                .unwrap_or(0xffffffff);
+            let time = find(&input, PSBT_IN_REQUIRED_TIME_LOCKTIME)
+                .map(read_u32)
+                .transpose()?;
+            let height = find(&input, PSBT_IN_REQUIRED_HEIGHT_LOCKTIME)
+                .map(read_u32)
+                .transpose()?;
+            if let Some(time) = time {
+                if time < LOCKTIME_THRESHOLD {
+                    return Err(BitcoinError::InvalidPsbt(
+                        "invalid required time locktime".to_string(),
+                    ));
+                }
+                time_locktimes.push(time);
+            }
+            if let Some(height) = height {
+                if height == 0 || height >= LOCKTIME_THRESHOLD {
+                    return Err(BitcoinError::InvalidPsbt(
+                        "invalid required height locktime".to_string(),
+                    ));
+                }
+                height_locktimes.push(height);
+            }
+            require_time |= time.is_some() && height.is_none();
+            require_height |= height.is_some() && time.is_none();
+            tx_inputs.push(TxIn {
+                previous_output: OutPoint { txid, vout },
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence(sequence),
+                witness: Witness::new(),
+            });
+            inputs.push(input);
+        }
+
+        let mut outputs = Vec::new();
+        let mut tx_outputs = Vec::new();
+        for _ in 0..output_count {
+            let output = reader.read_map()?;
+            let amount = read_u64(required(&output, PSBT_OUT_AMOUNT)?)?;
+            let script = required(&output, PSBT_OUT_SCRIPT)?.to_vec();
+            tx_outputs.push(TxOut {
+                value: Amount::from_sat(amount),
+                script_pubkey: ScriptBuf::from_bytes(script),
+            });
+            outputs.push(output);
+        }
+        if !reader.is_empty() {
+            return Err(BitcoinError::InvalidPsbt(
+                "psbt has trailing data".to_string(),
+            ));
+        }
+
+        // BIP-370 locktime determination, height wins unless an input can only do time
+        let lock_time = if require_time && require_height {
+            return Err(BitcoinError::InvalidPsbt(
+                "inputs require both a time and a height locktime".to_string(),
+            ));
+        } else if require_time {
+            time_locktimes.iter().max().copied()
+        } else if !height_locktimes.is_empty() {
+            height_locktimes.iter().max().copied()
+        } else {
+            time_locktimes.iter().max().copied()
+        }
+        .or(fallback_locktime)
+        .unwrap_or(0);
+
+        let unsigned_tx = Transaction {
+            version: Version(tx_version),
+            lock_time: LockTime::from_consensus(lock_time),
+            input: tx_inputs,
+            output: tx_outputs,
+        };
+
+        let (mut global, global_v2) = partition(
+            global,
+            &[
+                PSBT_GLOBAL_TX_VERSION,
+                PSBT_GLOBAL_FALLBACK_LOCKTIME,
+                PSBT_GLOBAL_INPUT_COUNT,
+                PSBT_GLOBAL_OUTPUT_COUNT,
+                PSBT_GLOBAL_TX_MODIFIABLE,
+                PSBT_GLOBAL_VERSION,
+            ],
+        );
+        let mut v0 = PSBT_MAGIC.to_vec();
+        global.push((vec![PSBT_GLOBAL_UNSIGNED_TX], serialize(&unsigned_tx)));
+        write_map(&mut v0, global);
+        let mut v2_fields = Self {
+            global: global_v2,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        };
+        for input in inputs {
+            let (input, input_v2) = partition(
+                input,
+                &[
+                    PSBT_IN_PREVIOUS_TXID,
+                    PSBT_IN_OUTPUT_INDEX,
+                    PSBT_IN_SEQUENCE,
+                    PSBT_IN_REQUIRED_TIME_LOCKTIME,
+                    PSBT_IN_REQUIRED_HEIGHT_LOCKTIME,
+                ],
+            );
+            write_map(&mut v0, input);
+            v2_fields.inputs.push(input_v2);
+        }
+        for output in outputs {
+            let (output, output_v2) = partition(output, &[PSBT_OUT_AMOUNT, PSBT_OUT_SCRIPT]);
+            write_map(&mut v0, output);
+            v2_fields.outputs.push(output_v2);
+        }
+        Ok(Some((v0, v2_fields)))
+    }
+
+    // turns a serialized v0 psbt back into v2
+    fn merge(&self, v0: &[u8]) -> Result<Vec<u8>> {
+        let mut reader = MapReader::new(v0)?;
+        let mut result = PSBT_MAGIC.to_vec();
+        let mut global = reader
+            .read_map()?
+            .into_iter()
+            .filter(|(key, _)| key[0] != PSBT_GLOBAL_UNSIGNED_TX && key[0] != PSBT_GLOBAL_VERSION)
+            .collect::<Vec<_>>();
+        global.extend(self.global.iter().cloned());
+        write_map(&mut result, global);
+        for fields in self.inputs.iter().chain(self.outputs.iter()) {
+            let mut map = reader.read_map()?;
+            map.extend(fields.iter().cloned());
+            write_map(&mut result, map);
+        }
+        if !reader.is_empty() {
+            return Err(BitcoinError::InvalidPsbt(
+                "psbt inputs or outputs changed while signing".to_string(),
+            ));
+        }
+        Ok(result)
+    }
+
+    fn get_global(&self, key_type: u8) -> Option<&[u8]> {
+        find(&self.global, key_type)
+    }
+
+    fn set_global(&mut self, key_type: u8, value: Vec<u8>) {
+        self.global.retain(|(key, _)| key.as_slice() != [key_type]);
+        self.global.push((vec![key_type], value));
+    }
+}
+
+// musig2 partial signatures are only known to the psbt library as unknown fields
+fn signature_count(input: &Input) -> usize {
+    input.partial_sigs.len()
+        + input.tap_script_sigs.len()
+        + input.tap_key_sig.map_or(0, |_| 1)
+        + input.final_script_witness.as_ref().map_or(0, |_| 1)
+        + input
+            .unknown
+            .keys()
+            .filter(|key| key.type_value == PSBT_IN_MUSIG2_PARTIAL_SIG)
+            .count()
+}
+
+struct MapReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> MapReader<'a> {
+    fn new(data: &'a [u8]) -> Result<Self> {
+        if !data.starts_with(PSBT_MAGIC) {
+            return Err(BitcoinError::InvalidPsbt("invalid magic".to_string()));
+        }
+        Ok(Self {
+            data,
+            offset: PSBT_MAGIC.len(),
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.offset >= self.data.len()
+    }
+
+    fn read_bytes(&mut self) -> Result<&'a [u8]> {
+        let (VarInt(len), consumed) = deserialize_partial::<VarInt>(&self.data[self.offset..])
+            .map_err(|e| BitcoinError::InvalidPsbt(e.to_string()))?;
+        let start = self.offset + consumed;
+        let end = start
+            .checked_add(len as usize)
+            .filter(|end| *end <= self.data.len())
+            .ok_or(BitcoinError::InvalidPsbt(
+                "unexpected end of data".to_string(),
+            ))?;
+        self.offset = end;
+        Ok(&self.data[start..end])
+    }
+
+    fn read_map(&mut self) -> Result<Vec<Pair>> {
+        let mut map: Vec<Pair> = Vec::new();
+        loop {
+            let key = self.read_bytes()?;
+            if key.is_empty() {
+                return Ok(map);
+            }
+            let value = self.read_bytes()?;
+            if map.iter().any(|(k, _)| k.as_slice() == key) {
+                return Err(BitcoinError::InvalidPsbt("duplicate key".to_string()));
+            }
+            map.push((key.to_vec(), value.to_vec()));
+        }
+    }
+}
+
+// keys are written sorted so the output does not depend on the order fields were merged in
+fn write_map(buf: &mut Vec<u8>, mut map: Vec<Pair>) {
+    map.sort();
+    for (key, value) in map {
+        buf.extend(serialize(&VarInt(key.len() as u64)));
+        buf.extend(key);
+        buf.extend(serialize(&VarInt(value.len() as u64)));
+        buf.extend(value);
+    }
+    buf.push(0x00);
+}
+
+// v2 field keys have no key data, so they are exactly one byte long
+fn find(map: &[Pair], key_type: u8) -> Option<&[u8]> {
+    map.iter()
+        .find(|(key, _)| key.as_slice() == [key_type])
+        .map(|(_, value)| value.as_slice())
+}
+
+fn required(map: &[Pair], key_type: u8) -> Result<&[u8]> {
+    find(map, key_type).ok_or(BitcoinError::InvalidPsbt(format!(
+        "psbt v2 is missing field {:#04x}",
+        key_type
+    )))
+}
+
+fn partition(map: Vec<Pair>, key_types: &[u8]) -> (Vec<Pair>, Vec<Pair>) {
+    map.into_iter()
+        .partition(|(key, _)| !(key.len() == 1 && key_types.contains(&key[0])))
+}
+
+fn read_u32(value: &[u8]) -> Result<u32> {
+    <[u8; 4]>::try_from(value)
+        .map(u32::from_le_bytes)
+        .map_err(|_| BitcoinError::InvalidPsbt("invalid u32 field".to_string()))
+}
+
+fn read_u64(value: &[u8]) -> Result<u64> {
+    <[u8; 8]>::try_from(value)
+        .map(u64::from_le_bytes)
+        .map_err(|_| BitcoinError::InvalidPsbt("invalid u64 field".to_string()))
+}
+
+fn read_count(value: &[u8]) -> Result<u64> {
+    match deserialize_partial::<VarInt>(value) {
+        Ok((VarInt(count), consumed)) if consumed == value.len() => Ok(count),
+        _ => Err(BitcoinError::InvalidPsbt("invalid count field".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+    use hex::FromHex;
+
+    const PSBT_V0: &str = "70736274ff01005202000000016d41e6873468f85aff76d7709a93b47180ea0784edaac748228d2c474396ca550000000000fdffffff01a00f0000000000001600146623828c1f87be7841a9b1cc360d38ae0a8b6ed0000000000001011f6817000000000000160014d0c4a3ef09e997b6e99e397e518fe3e41a118ca1220602e7ab2537b5d49e970309aae06e9e49f36ce1c9febbd44ec8e0d1cca0b4f9c3191873c5da0a54000080010000800000008000000000000000000000";
+    // the same psbt as v2 with inputs and outputs modifiable
+    const PSBT_V2: &str = "70736274ff010204020000000103040000000001040101010501010106010301fb04020000000001011f6817000000000000160014d0c4a3ef09e997b6e99e397e518fe3e41a118ca1220602e7ab2537b5d49e970309aae06e9e49f36ce1c9febbd44ec8e0d1cca0b4f9c3191873c5da0a5400008001000080000000800000000000000000010e206d41e6873468f85aff76d7709a93b47180ea0784edaac748228d2c474396ca55010f0400000000011004fdffffff00010308a00f00000000000001041600146623828c1f87be7841a9b1cc360d38ae0a8b6ed000";
+
+    #[test]
+    fn test_deserialize_v2() {
+        let v0 = VersionedPsbt::deserialize(&Vec::from_hex(PSBT_V0).unwrap()).unwrap();
+        assert!(!v0.is_v2());
+        let v2 = VersionedPsbt::deserialize(&Vec::from_hex(PSBT_V2).unwrap()).unwrap();
+        assert!(v2.is_v2());
+        assert_eq!(v0.psbt, v2.psbt);
+        assert_eq!(PSBT_V2, hex::encode(v2.serialize().unwrap()));
+        assert_eq!(PSBT_V0, hex::encode(v0.serialize().unwrap()));
+    }
+
+    #[test]
+    fn test_sign_v2() {
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let mfp = bitcoin::bip32::Fingerprint::from_str("73c5da0a").unwrap();
+        let signed = crate::sign_psbt(Vec::from_hex(PSBT_V2).unwrap(), &seed, mfp).unwrap();
+        // same signature as the v0 psbt, and SIGHASH_ALL locks the inputs and outputs
+        assert_eq!("70736274ff010204020000000103040000000001040101010501010106010001fb04020000000001011f6817000000000000160014d0c4a3ef09e997b6e99e397e518fe3e41a118ca1220202e7ab2537b5d49e970309aae06e9e49f36ce1c9febbd44ec8e0d1cca0b4f9c319483045022100e2b9a7963bed429203bbd73e5ea000bfe58e3fc46ef8c1939e8cf8d1cf8460810220587ba791fc2a42445db70e2b3373493a19e6d5c47a2af0447d811ff479721b0001220602e7ab2537b5d49e970309aae06e9e49f36ce1c9febbd44ec8e0d1cca0b4f9c3191873c5da0a5400008001000080000000800000000000000000010e206d41e6873468f85aff76d7709a93b47180ea0784edaac748228d2c474396ca55010f0400000000011004fdffffff00010308a00f00000000000001041600146623828c1f87be7841a9b1cc360d38ae0a8b6ed000", hex::encode(signed));
+    }
+
+    #[test]
+    fn test_musig2_partial_sig_locks_modifiable() {
+        let mut v2 = VersionedPsbt::deserialize(&Vec::from_hex(PSBT_V2).unwrap()).unwrap();
+        let unsigned = v2.psbt.clone();
+        v2.psbt.inputs[0].unknown.insert(
+            bitcoin::psbt::raw::Key {
+                type_value: PSBT_IN_MUSIG2_PARTIAL_SIG,
+                key: vec![0x02; 66],
+            },
+            vec![0x01; 32],
+        );
+        v2.update_modifiable(&unsigned);
+        assert_eq!(
+            Some([0x00].as_slice()),
+            v2.v2_fields.unwrap().get_global(PSBT_GLOBAL_TX_MODIFIABLE)
+        );
+    }
+
+    #[test]
+    fn test_locktime() {
+        let mut bytes = Vec::from_hex(PSBT_V2).unwrap();
+        let v2 = VersionedPsbt::deserialize(&bytes).unwrap();
+        let mut fields = v2.v2_fields.unwrap();
+        fields.inputs[0].push((vec![PSBT_IN_REQUIRED_HEIGHT_LOCKTIME], vec![0x10, 0, 0, 0]));
+        fields
+            .global
+            .retain(|(key, _)| key[0] != PSBT_GLOBAL_FALLBACK_LOCKTIME);
+        bytes = fields.merge(&v2.psbt.serialize()).unwrap();
+        let psbt = VersionedPsbt::deserialize(&bytes).unwrap().psbt;
+        assert_eq!(LockTime::from_consensus(16), psbt.unsigned_tx.lock_time);
+    }
+
+    #[test]
+    fn test_invalid_v2() {
+        // no input count
+        let bytes = Vec::from_hex(PSBT_V2).unwrap();
+        let v2 = VersionedPsbt::deserialize(&bytes).unwrap();
+        let mut fields = v2.v2_fields.clone().unwrap();
+        fields
+            .global
+            .retain(|(key, _)| key[0] != PSBT_GLOBAL_INPUT_COUNT);
+        assert!(VersionedPsbt::deserialize(&fields.merge(&v2.psbt.serialize()).unwrap()).is_err());
+        // no previous txid
+        let mut fields = v2.v2_fields.unwrap();
+        fields.inputs[0].retain(|(key, _)| key[0] != PSBT_IN_PREVIOUS_TXID);
+        assert!(VersionedPsbt::deserialize(&fields.merge(&v2.psbt.serialize()).unwrap()).is_err());
+        // version 1 does not exist
+        let bytes = Vec::from_hex(PSBT_V2.replace("01fb0402000000", "01fb0401000000")).unwrap();
+        assert!(VersionedPsbt::deserialize(&bytes).is_err());
+    }
+}
@@ -0,0 +1,349 @@
+// Inputs are signed with the sighash type requested in the PSBT. Anything other than ALL (or
+// DEFAULT for taproot) gives up part of the transaction, which is what marketplace listings rely
+// on, so every such input gets an explanation and SIGHASH_NONE is only signed when the device
+// allows blind signing, never because the request says so.
+use alloc::format;
+use alloc::string::String;
+
+use bitcoin::bip32::Fingerprint;
+use bitcoin::psbt::{Input, PsbtSighashType};
+use bitcoin::{EcdsaSighashType, TapSighashType};
+
+use crate::errors::{BitcoinError, Result};
+use crate::transactions::psbt::wrapped_psbt::WrappedPsbt;
+
+const SIGHASH_ANYONECANPAY: u32 = 0x80;
+const SIGHASH_BASE_MASK: u32 = 0x1f;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SighashBase {
+    All,
+    None,
+    Single,
+}
+
+impl WrappedPsbt {
+    // what the signature of this input leaves open, None for ALL and DEFAULT
+    pub fn get_sighash_warning(&self, input: &Input) -> Option<String> {
+        let sighash_type = input.sighash_type?;
+        let (base, anyone_can_pay) = self.get_sighash_parts(input, sighash_type).ok()?;
+        let (name, explanation) = match (base, anyone_can_pay) {
+            (SighashBase::All, false) => return None,
+            (SighashBase::All, true) => (
+                "SIGHASH_ALL|ANYONECANPAY",
+                "this signature only commits to this input and all outputs, others can add inputs",
+            ),
+            (SighashBase::None, false) => (
+                "SIGHASH_NONE",
+                "this signature commits to all inputs but no outputs, anyone can change where the funds go",
+            ),
+            (SighashBase::None, true) => (
+                "SIGHASH_NONE|ANYONECANPAY",
+                "this signature only commits to this input and no outputs, anyone can spend it anywhere",
+            ),
+            (SighashBase::Single, false) => (
+                "SIGHASH_SINGLE",
+                "this signature commits to all inputs and only the output with the same index, others can add outputs",
+            ),
+            (SighashBase::Single, true) => (
+                "SIGHASH_SINGLE|ANYONECANPAY",
+                "this signature only commits to this input and the output with the same index, others can add inputs and outputs",
+            ),
+        };
+        Some(format!("{}: {}", name, explanation))
+    }
+
+    // the ecdsa flavour of the requested type, taproot DEFAULT counts as ALL
+    pub fn get_ecdsa_sighash_type(&self, input: &Input) -> Result<EcdsaSighashType> {
+        let sighash_type = match input.sighash_type {
+            Some(sighash_type) => sighash_type,
+            None => return Ok(EcdsaSighashType::All),
+        };
+        let (base, anyone_can_pay) = self.get_sighash_parts(input, sighash_type)?;
+        Ok(match (base, anyone_can_pay) {
+            (SighashBase::All, false) => EcdsaSighashType::All,
+            (SighashBase::All, true) => EcdsaSighashType::AllPlusAnyoneCanPay,
+            (SighashBase::None, false) => EcdsaSighashType::None,
+            (SighashBase::None, true) => EcdsaSighashType::NonePlusAnyoneCanPay,
+            (SighashBase::Single, false) => EcdsaSighashType::Single,
+            (SighashBase::Single, true) => EcdsaSighashType::SinglePlusAnyoneCanPay,
+        })
+    }
+
+    // the sighash type of every input we sign must be valid for its script and NONE needs the
+    // blind signing setting
+    pub fn check_sighash_types(&self, mfp: Fingerprint, allow_sighash_none: bool) -> Result<()> {
+        for (index, input) in self.psbt.inputs.iter().enumerate() {
+            let is_mine = input.bip32_derivation.values().any(|(fp, _)| *fp == mfp)
+                || input
+                    .tap_key_origins
+                    .values()
+                    .any(|(_, (fp, _))| *fp == mfp);
+            if is_mine {
+                self.check_input_sighash(input, index, allow_sighash_none)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn check_input_sighash(
+        &self,
+        input: &Input,
+        index: usize,
+        allow_sighash_none: bool,
+    ) -> Result<()> {
+        let sighash_type = match input.sighash_type {
+            Some(sighash_type) => sighash_type,
+            None => return Ok(()),
+        };
+        let (base, _) = self.get_sighash_parts(input, sighash_type)?;
+        match base {
+            SighashBase::None if !allow_sighash_none => {
+                Err(BitcoinError::InvalidSighashType(format!(
+                    "input #{} uses SIGHASH_NONE which needs blind signing to be enabled",
+                    index
+                )))
+            }
+            // a legacy signature for a missing output signs the constant one and could be
+            // replayed on any transaction spending this input
+            SighashBase::Single if index >= self.psbt.unsigned_tx.output.len() => {
+                Err(BitcoinError::InvalidSighashType(format!(
+                    "input #{} uses SIGHASH_SINGLE without a matching output",
+                    index
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn get_sighash_parts(
+        &self,
+        input: &Input,
+        sighash_type: PsbtSighashType,
+    ) -> Result<(SighashBase, bool)> {
+        let invalid = || {
+            BitcoinError::InvalidSighashType(format!(
+                "unsupported sighash type {:#x}",
+                sighash_type.to_u32()
+            ))
+        };
+        let is_taproot = input
+            .witness_utxo
+            .as_ref()
+            .map_or(false, |v| v.script_pubkey.is_p2tr());
+        if is_taproot {
+            sighash_type.taproot_hash_ty().map_err(|_| invalid())?;
+            if sighash_type.to_u32() == TapSighashType::Default as u32 {
+                return Ok((SighashBase::All, false));
+            }
+        } else {
+            sighash_type.ecdsa_hash_ty().map_err(|_| invalid())?;
+        }
+        let value = sighash_type.to_u32();
+        let base = match value & SIGHASH_BASE_MASK {
+            v if v == EcdsaSighashType::All as u32 => SighashBase::All,
+            v if v == EcdsaSighashType::None as u32 => SighashBase::None,
+            v if v == EcdsaSighashType::Single as u32 => SighashBase::Single,
+            _ => return Err(invalid()),
+        };
+        Ok((base, value & SIGHASH_ANYONECANPAY != 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use bitcoin::absolute::LockTime;
+    use bitcoin::hashes::Hash;
+    use bitcoin::psbt::Psbt;
+    use bitcoin::transaction::Version;
+    use bitcoin::{Amount, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness};
+    use core::str::FromStr;
+
+    fn wrapped(script_pubkey: &str, outputs: usize) -> WrappedPsbt {
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: [0u32, 1]
+                .iter()
+                .map(|vout| TxIn {
+                    previous_output: OutPoint {
+                        txid: bitcoin::Txid::from_str(
+                            "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799",
+                        )
+                        .unwrap(),
+                        vout: *vout,
+                    },
+                    script_sig: ScriptBuf::new(),
+                    sequence: Sequence::MAX,
+                    witness: Witness::new(),
+                })
+                .collect(),
+            output: (0..outputs)
+                .map(|_| TxOut {
+                    value: Amount::from_sat(10000),
+                    script_pubkey: ScriptBuf::from_hex(script_pubkey).unwrap(),
+                })
+                .collect(),
+        };
+        let mut psbt = Psbt::from_unsigned_tx(tx).unwrap();
+        for input in psbt.inputs.iter_mut() {
+            input.witness_utxo = Some(TxOut {
+                value: Amount::from_sat(20000),
+                script_pubkey: ScriptBuf::from_hex(script_pubkey).unwrap(),
+            });
+        }
+        WrappedPsbt { psbt }
+    }
+
+    const P2WPKH: &str = "0014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e2";
+    const P2TR: &str = "5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c";
+
+    #[test]
+    fn test_sighash_warning() {
+        let psbt = wrapped(P2WPKH, 2);
+        let mut input = psbt.psbt.inputs[0].clone();
+        assert_eq!(None, psbt.get_sighash_warning(&input));
+        input.sighash_type = Some(EcdsaSighashType::All.into());
+        assert_eq!(None, psbt.get_sighash_warning(&input));
+        input.sighash_type = Some(EcdsaSighashType::SinglePlusAnyoneCanPay.into());
+        assert_eq!(
+            Some("SIGHASH_SINGLE|ANYONECANPAY: this signature only commits to this input and the output with the same index, others can add inputs and outputs".to_string()),
+            psbt.get_sighash_warning(&input)
+        );
+        assert!(psbt.check_input_sighash(&input, 0, false).is_ok());
+        assert_eq!(
+            EcdsaSighashType::SinglePlusAnyoneCanPay,
+            psbt.get_ecdsa_sighash_type(&input).unwrap()
+        );
+
+        input.sighash_type = Some(EcdsaSighashType::None.into());
+        assert!(psbt
+            .get_sighash_warning(&input)
+            .unwrap()
+            .starts_with("SIGHASH_NONE: this signature commits to all inputs but no outputs"));
+        assert_eq!(
+            Err(BitcoinError::InvalidSighashType(
+                "input #0 uses SIGHASH_NONE which needs blind signing to be enabled".to_string()
+            )),
+            psbt.check_input_sighash(&input, 0, false)
+        );
+        assert!(psbt.check_input_sighash(&input, 0, true).is_ok());
+
+        // taproot DEFAULT is not valid for an ecdsa input
+        input.sighash_type = Some(TapSighashType::Default.into());
+        assert!(psbt.check_input_sighash(&input, 0, true).is_err());
+        assert!(psbt.get_ecdsa_sighash_type(&input).is_err());
+    }
+
+    #[test]
+    fn test_taproot_sighash() {
+        let psbt = wrapped(P2TR, 1);
+        let mut input = psbt.psbt.inputs[1].clone();
+        input.sighash_type = Some(TapSighashType::Default.into());
+        assert_eq!(None, psbt.get_sighash_warning(&input));
+        assert!(psbt.check_input_sighash(&input, 1, false).is_ok());
+        assert_eq!(
+            EcdsaSighashType::All,
+            psbt.get_ecdsa_sighash_type(&input).unwrap()
+        );
+        input.sighash_type = Some(TapSighashType::AllPlusAnyoneCanPay.into());
+        assert!(psbt
+            .get_sighash_warning(&input)
+            .unwrap()
+            .starts_with("SIGHASH_ALL|ANYONECANPAY"));
+        // there is no output #1 to commit to
+        input.sighash_type = Some(TapSighashType::Single.into());
+        assert!(psbt.check_input_sighash(&input, 1, false).is_err());
+        assert!(psbt.check_input_sighash(&input, 0, false).is_ok());
+        input.sighash_type = Some(PsbtSighashType::from_u32(0x04));
+        assert!(psbt.check_input_sighash(&input, 0, false).is_err());
+    }
+
+    #[test]
+    fn test_sign_single_anyone_can_pay() {
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let mfp = Fingerprint::from_str("73c5da0a").unwrap();
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+
+        let mut psbt = wrapped(P2WPKH, 2);
+        let public_key = bitcoin::secp256k1::PublicKey::from_str(
+            "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c",
+        )
+        .unwrap();
+        let path = bitcoin::bip32::DerivationPath::from_str("m/84'/0'/0'/0/0").unwrap();
+        for input in psbt.psbt.inputs.iter_mut() {
+            input
+                .bip32_derivation
+                .insert(public_key, (mfp, path.clone()));
+        }
+        psbt.psbt.inputs[0].sighash_type = Some(EcdsaSighashType::SinglePlusAnyoneCanPay.into());
+        let signed = psbt.sign(&seed, mfp).unwrap();
+        let signature = signed.inputs[0]
+            .partial_sigs
+            .values()
+            .next()
+            .unwrap()
+            .clone();
+        assert_eq!(
+            EcdsaSighashType::SinglePlusAnyoneCanPay,
+            signature.sighash_type
+        );
+        let sighash = bitcoin::sighash::SighashCache::new(&signed.unsigned_tx)
+            .p2wpkh_signature_hash(
+                0,
+                &ScriptBuf::from_hex(P2WPKH).unwrap(),
+                Amount::from_sat(20000),
+                EcdsaSighashType::SinglePlusAnyoneCanPay,
+            )
+            .unwrap();
+        let message = bitcoin::secp256k1::Message::from_digest(sighash.to_byte_array());
+        assert!(secp
+            .verify_ecdsa(&message, &signature.signature, &public_key)
+            .is_ok());
+        let signature = signed.inputs[1].partial_sigs.values().next().unwrap();
+        assert_eq!(EcdsaSighashType::All, signature.sighash_type);
+
+        let mut psbt = wrapped(P2TR, 2);
+        let internal_key = bitcoin::secp256k1::XOnlyPublicKey::from_str(
+            "cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115",
+        )
+        .unwrap();
+        let path = bitcoin::bip32::DerivationPath::from_str("m/86'/0'/0'/0/0").unwrap();
+        for input in psbt.psbt.inputs.iter_mut() {
+            input.tap_internal_key = Some(internal_key);
+            input
+                .tap_key_origins
+                .insert(internal_key, (Vec::new(), (mfp, path.clone())));
+        }
+        psbt.psbt.inputs[0].sighash_type = Some(TapSighashType::SinglePlusAnyoneCanPay.into());
+        let signed = psbt.sign(&seed, mfp).unwrap();
+        assert_eq!(
+            TapSighashType::SinglePlusAnyoneCanPay,
+            signed.inputs[0].tap_key_sig.unwrap().sighash_type
+        );
+        assert_eq!(
+            TapSighashType::Default,
+            signed.inputs[1].tap_key_sig.unwrap().sighash_type
+        );
+
+        // NONE is refused at signing time as well unless the device allows it
+        let mut psbt = wrapped(P2TR, 2);
+        psbt.psbt.inputs[0].tap_internal_key = Some(internal_key);
+        psbt.psbt.inputs[0]
+            .tap_key_origins
+            .insert(internal_key, (Vec::new(), (mfp, path)));
+        psbt.psbt.inputs[0].sighash_type = Some(TapSighashType::None.into());
+        assert!(matches!(
+            psbt.sign(&seed, mfp),
+            Err(BitcoinError::InvalidSighashType(_))
+        ));
+        let signed = psbt.sign_with_sighash_none(&seed, mfp, true).unwrap();
+        assert_eq!(
+            TapSighashType::None,
+            signed.inputs[0].tap_key_sig.unwrap().sighash_type
+        );
+    }
+}
@@ -0,0 +1,341 @@
+// BIP-375 silent payment outputs. The coordinator computes the output scripts, the device
+// recomputes them from its own input keys and refuses to sign when they differ. Shares from
+// other signers would need their DLEQ proofs checked, so every eligible input must be ours.
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use bitcoin::bip32::{DerivationPath, Fingerprint};
+use bitcoin::key::{Keypair, TapTweak};
+use bitcoin::psbt::{Input, Output};
+use bitcoin::secp256k1::{Parity, PublicKey, Secp256k1, SecretKey, XOnlyPublicKey};
+use bitcoin::{
+    CompressedPublicKey, EcdsaSighashType, ScriptBuf, TapNodeHash, TapSighashType, WitnessVersion,
+};
+
+use crate::addresses::silent_payment::{calculate_silent_payment_scripts, SilentPaymentAddress};
+use crate::errors::{BitcoinError, Result};
+use crate::multi_sig::address::TAPROOT_NUMS_KEY;
+use crate::network;
+use crate::transactions::parsed_tx::TxParser;
+use crate::transactions::psbt::wrapped_psbt::WrappedPsbt;
+
+const PSBT_OUT_SP_V0_INFO: u8 = 0x09;
+
+impl WrappedPsbt {
+    pub fn get_silent_payment_address(
+        &self,
+        output: &Output,
+        network: &network::Network,
+    ) -> Result<Option<SilentPaymentAddress>> {
+        output
+            .unknown
+            .iter()
+            .find(|(key, _)| key.type_value == PSBT_OUT_SP_V0_INFO && key.key.is_empty())
+            .map(|(_, value)| SilentPaymentAddress::from_keys_bytes(value, network.clone()))
+            .transpose()
+    }
+
+    // runs without the seed when the psbt is checked, the outputs themselves can only be
+    // recomputed with the input private keys in `verify_silent_payment_outputs`
+    pub fn check_silent_payment_outputs(&self, mfp: Fingerprint) -> Result<()> {
+        if self.get_silent_payment_recipients()?.is_empty() {
+            return Ok(());
+        }
+        for (index, input) in self.psbt.inputs.iter().enumerate() {
+            // the outputs commit to every input, nothing may be added or changed later
+            let sighash_all = match input.sighash_type {
+                None => true,
+                Some(v) => {
+                    matches!(v.ecdsa_hash_ty(), Ok(EcdsaSighashType::All))
+                        || matches!(v.taproot_hash_ty(), Ok(TapSighashType::Default))
+                }
+            };
+            if !sighash_all {
+                return Err(BitcoinError::SilentPaymentError(format!(
+                    "input #{} must be signed with SIGHASH_ALL",
+                    index
+                )));
+            }
+            self.get_silent_payment_input(input, index, mfp)?;
+        }
+        Ok(())
+    }
+
+    pub fn verify_silent_payment_outputs(&self, seed: &[u8], mfp: Fingerprint) -> Result<()> {
+        let recipients = self.get_silent_payment_recipients()?;
+        if recipients.is_empty() {
+            return Ok(());
+        }
+        self.check_silent_payment_outputs(mfp)?;
+        let mut input_keys = Vec::new();
+        for (index, input) in self.psbt.inputs.iter().enumerate() {
+            if let Some(eligible) = self.get_silent_payment_input(input, index, mfp)? {
+                input_keys.push(eligible.get_private_key(seed, index)?);
+            }
+        }
+        let outpoints = self
+            .psbt
+            .unsigned_tx
+            .input
+            .iter()
+            .map(|v| v.previous_output)
+            .collect::<Vec<_>>();
+        let scripts = calculate_silent_payment_scripts(
+            &input_keys,
+            &outpoints,
+            &recipients.iter().map(|(_, v)| *v).collect::<Vec<_>>(),
+        )?;
+        for ((index, _), script) in recipients.into_iter().zip(scripts.iter()) {
+            let tx_out = self
+                .psbt
+                .unsigned_tx
+                .output
+                .get(index)
+                .ok_or(BitcoinError::InvalidOutput)?;
+            if tx_out.script_pubkey != *script {
+                return Err(BitcoinError::SilentPaymentError(format!(
+                    "output #{} does not pay the silent payment address",
+                    index
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    // the scan and spend keys of every silent payment output with the output index
+    fn get_silent_payment_recipients(&self) -> Result<Vec<(usize, (PublicKey, PublicKey))>> {
+        let has_silent_payment = self.psbt.outputs.iter().any(|output| {
+            output
+                .unknown
+                .keys()
+                .any(|key| key.type_value == PSBT_OUT_SP_V0_INFO)
+        });
+        if !has_silent_payment {
+            return Ok(Vec::new());
+        }
+        let network = self.determine_network()?;
+        let mut recipients = Vec::new();
+        for (index, output) in self.psbt.outputs.iter().enumerate() {
+            if let Some(address) = self.get_silent_payment_address(output, &network)? {
+                recipients.push((index, (address.scan_key, address.spend_key)));
+            }
+        }
+        Ok(recipients)
+    }
+
+    // the key origin of an input that contributes to the shared secret, None for inputs that
+    // do not take part in BIP-352
+    fn get_silent_payment_input<'a>(
+        &self,
+        input: &'a Input,
+        index: usize,
+        mfp: Fingerprint,
+    ) -> Result<Option<EligibleInput<'a>>> {
+        let secp = Secp256k1::verification_only();
+        let script_pubkey = self
+            .get_input_script_pubkey(input, index)
+            .ok_or(BitcoinError::InvalidInput)?;
+
+        if let Some(version) = script_pubkey.witness_version() {
+            if version > WitnessVersion::V1 {
+                return Err(BitcoinError::SilentPaymentError(format!(
+                    "input #{} spends an unknown segwit version",
+                    index
+                )));
+            }
+        }
+        if script_pubkey.is_p2tr() {
+            let internal_key = match input.tap_internal_key {
+                Some(key) => key,
+                None => return Err(not_mine(index)),
+            };
+            // a NUMS internal key can only be spent by script path, which is not eligible
+            if XOnlyPublicKey::from_str(TAPROOT_NUMS_KEY).ok() == Some(internal_key) {
+                return Ok(None);
+            }
+            let path = match input.tap_key_origins.get(&internal_key) {
+                Some((_, (fingerprint, path))) if *fingerprint == mfp => path,
+                _ => return Err(not_mine(index)),
+            };
+            let (output_key, _) = internal_key.tap_tweak(&secp, input.tap_merkle_root);
+            if ScriptBuf::new_p2tr_tweaked(output_key) != script_pubkey {
+                return Err(not_mine(index));
+            }
+            return Ok(Some(EligibleInput::Taproot {
+                internal_key,
+                merkle_root: input.tap_merkle_root,
+                path,
+            }));
+        }
+
+        let redeem_script = input.redeem_script.as_ref();
+        let eligible = script_pubkey.is_p2wpkh()
+            || script_pubkey.is_p2pkh()
+            || (script_pubkey.is_p2sh() && redeem_script.map_or(false, |v| v.is_p2wpkh()));
+        if !eligible {
+            return Ok(None);
+        }
+        for (public_key, (fingerprint, path)) in input.bip32_derivation.iter() {
+            if *fingerprint != mfp {
+                continue;
+            }
+            let compressed = CompressedPublicKey(*public_key);
+            let matched = match redeem_script {
+                Some(redeem_script) if script_pubkey.is_p2sh() => {
+                    *redeem_script == ScriptBuf::new_p2wpkh(&compressed.wpubkey_hash())
+                        && script_pubkey == ScriptBuf::new_p2sh(&redeem_script.script_hash())
+                }
+                _ => {
+                    script_pubkey == ScriptBuf::new_p2wpkh(&compressed.wpubkey_hash())
+                        || script_pubkey == ScriptBuf::new_p2pkh(&compressed.pubkey_hash())
+                }
+            };
+            if matched {
+                return Ok(Some(EligibleInput::Ecdsa {
+                    public_key: *public_key,
+                    path,
+                }));
+            }
+        }
+        Err(not_mine(index))
+    }
+}
+
+enum EligibleInput<'a> {
+    Taproot {
+        internal_key: XOnlyPublicKey,
+        merkle_root: Option<TapNodeHash>,
+        path: &'a DerivationPath,
+    },
+    Ecdsa {
+        public_key: PublicKey,
+        path: &'a DerivationPath,
+    },
+}
+
+impl EligibleInput<'_> {
+    fn get_private_key(&self, seed: &[u8], index: usize) -> Result<SecretKey> {
+        let secp = Secp256k1::new();
+        let get_private_key = |path: &DerivationPath| {
+            keystore::algorithms::secp256k1::get_private_key_by_seed(seed, &path.to_string())
+                .map_err(|e| BitcoinError::GetKeyError(e.to_string()))
+        };
+        match self {
+            EligibleInput::Taproot {
+                internal_key,
+                merkle_root,
+                path,
+            } => {
+                let keypair = Keypair::from_secret_key(&secp, &get_private_key(path)?);
+                if keypair.x_only_public_key().0 != *internal_key {
+                    return Err(not_mine(index));
+                }
+                let tweaked = keypair.tap_tweak(&secp, *merkle_root).to_inner();
+                let private_key = tweaked.secret_key();
+                Ok(match tweaked.x_only_public_key().1 {
+                    Parity::Even => private_key,
+                    Parity::Odd => private_key.negate(),
+                })
+            }
+            EligibleInput::Ecdsa { public_key, path } => {
+                let private_key = get_private_key(path)?;
+                if PublicKey::from_secret_key(&secp, &private_key) != *public_key {
+                    return Err(not_mine(index));
+                }
+                Ok(private_key)
+            }
+        }
+    }
+}
+
+fn not_mine(index: usize) -> BitcoinError {
+    BitcoinError::SilentPaymentError(format!(
+        "input #{} does not belong to this wallet, silent payments need every eligible input",
+        index
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::psbt::Psbt;
+    use hex::FromHex;
+
+    // a p2wpkh and a p2tr input of the test seed paying twice to the same silent payment address
+    const PSBT: &str = "70736274ff0100b2020000000222222222222222222222222222222222222222222222222222222222222222220100000000fdffffff33333333333333333333333333333333333333333333333333333333333333330000000000fdffffff02307500000000000022512006e84a6f086362be67d8f4f6651c9f5f376e3e1534d24730cea9e9d6e29a9c00204e000000000000225120e299cf1bdc4286e8f63b2a3b5e306f27b7df82327b6bc70695381dfedb97dc0a000000000001011f409c000000000000160014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e222060330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c1873c5da0a54000080000000800000008000000000000000000001012b204e000000000000225120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c2116cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115190073c5da0a5600008000000080000000800000000000000000011720cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115000109420220bcfac5b99e04ad1a06ddfb016ee13582609d60b6291e98d01a9bc9a16c96d4025cc9856d6f8375350e123978daac200c260cb5b5ae83106cab90484dcd8fcf36000109420220bcfac5b99e04ad1a06ddfb016ee13582609d60b6291e98d01a9bc9a16c96d4025cc9856d6f8375350e123978daac200c260cb5b5ae83106cab90484dcd8fcf3600";
+    // the second output reuses k = 0, the recipient would never find it
+    const PSBT_REUSED_OUTPUT: &str = "70736274ff0100b2020000000222222222222222222222222222222222222222222222222222222222222222220100000000fdffffff33333333333333333333333333333333333333333333333333333333333333330000000000fdffffff02307500000000000022512006e84a6f086362be67d8f4f6651c9f5f376e3e1534d24730cea9e9d6e29a9c00204e00000000000022512006e84a6f086362be67d8f4f6651c9f5f376e3e1534d24730cea9e9d6e29a9c00000000000001011f409c000000000000160014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e222060330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c1873c5da0a54000080000000800000008000000000000000000001012b204e000000000000225120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c2116cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115190073c5da0a5600008000000080000000800000000000000000011720cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115000109420220bcfac5b99e04ad1a06ddfb016ee13582609d60b6291e98d01a9bc9a16c96d4025cc9856d6f8375350e123978daac200c260cb5b5ae83106cab90484dcd8fcf36000109420220bcfac5b99e04ad1a06ddfb016ee13582609d60b6291e98d01a9bc9a16c96d4025cc9856d6f8375350e123978daac200c260cb5b5ae83106cab90484dcd8fcf3600";
+    const ADDRESS: &str = "sp1qqgste7k9hx0qftg6qmwlkqtwuy6cycyavzmzj85c6qdfhjdpdjtdgqjuexzk6murw56suy3e0rd2cgqvycxttddwsvgxe2usfpxumr70xc9pkqwv";
+
+    fn seed() -> Vec<u8> {
+        hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap()
+    }
+
+    fn wrapped(psbt_hex: &str) -> WrappedPsbt {
+        WrappedPsbt {
+            psbt: Psbt::deserialize(&Vec::from_hex(psbt_hex).unwrap()).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_sign_silent_payment() {
+        let mfp = Fingerprint::from_str("73c5da0a").unwrap();
+        let mut psbt = wrapped(PSBT);
+        let output = &psbt.psbt.outputs[0];
+        assert_eq!(
+            ADDRESS,
+            psbt.get_silent_payment_address(output, &psbt.determine_network().unwrap())
+                .unwrap()
+                .unwrap()
+                .to_string()
+        );
+        let signed = psbt.sign(&seed(), mfp).unwrap();
+        assert_eq!(1, signed.inputs[0].partial_sigs.len());
+        assert!(signed.inputs[1].tap_key_sig.is_some());
+
+        let mut psbt = wrapped(PSBT_REUSED_OUTPUT);
+        assert_eq!(
+            Err(BitcoinError::SilentPaymentError(
+                "output #1 does not pay the silent payment address".to_string()
+            )),
+            psbt.sign(&seed(), mfp)
+        );
+        // an eligible input from another wallet
+        let mut psbt = wrapped(PSBT);
+        assert!(psbt
+            .sign(&seed(), Fingerprint::from_str("12345678").unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_check_silent_payment_outputs() {
+        let mfp = Fingerprint::from_str("73c5da0a").unwrap();
+        assert!(wrapped(PSBT).check_silent_payment_outputs(mfp).is_ok());
+        assert!(wrapped(PSBT)
+            .check_silent_payment_outputs(Fingerprint::from_str("12345678").unwrap())
+            .is_err());
+        // the output scripts need the input private keys, only signing catches a bad one
+        let psbt = wrapped(PSBT_REUSED_OUTPUT);
+        assert!(psbt.check_silent_payment_outputs(mfp).is_ok());
+        assert!(psbt.verify_silent_payment_outputs(&seed(), mfp).is_err());
+    }
+
+    #[test]
+    fn test_parse_silent_payment_output() {
+        let psbt = wrapped(PSBT);
+        let network = psbt.determine_network().unwrap();
+        let context = crate::transactions::parsed_tx::ParseContext::new(
+            Fingerprint::from_str("73c5da0a").unwrap(),
+            Default::default(),
+            None,
+            None,
+            None,
+        );
+        let output = psbt
+            .parse_output(&psbt.psbt.outputs[1], 1, &context, &network)
+            .unwrap();
+        assert_eq!(ADDRESS, output.address);
+        assert_eq!(20000, output.value);
+    }
+}
@@ -12,20 +12,34 @@ use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::ops::Index;
 use core::str::Chars;
+use core::str::FromStr;
 use keystore::algorithms::secp256k1::derive_public_key;
 
-use crate::multi_sig::address::calculate_multi_address;
+use crate::multi_sig::address::{
+    calculate_multi_address, calculate_taproot_multi_spend_info, parse_multi_a_script,
+    TAPROOT_NUMS_KEY,
+};
+use crate::multi_sig::musig::{deterministic_sign, nonce_agg, KeyAggContext, PUB_NONCE_LEN};
+use crate::multi_sig::policy::WalletPolicy;
 use crate::multi_sig::wallet::calculate_multi_sig_verify_code;
 use crate::multi_sig::MultiSigFormat;
 use bitcoin::bip32::{ChildNumber, DerivationPath, Fingerprint, KeySource, Xpub};
-use bitcoin::psbt::{GetKey, KeyRequest, Psbt};
+use bitcoin::hashes::Hash;
+use bitcoin::psbt::{raw, GetKey, KeyRequest, Psbt};
 use bitcoin::psbt::{Input, Output};
 use bitcoin::secp256k1::{Secp256k1, Signing, XOnlyPublicKey};
-use bitcoin::taproot::TapLeafHash;
+use bitcoin::sighash::{Prevouts, SighashCache};
+use bitcoin::taproot::{LeafVersion, TapLeafHash};
+use bitcoin::TapSighashType;
 use bitcoin::{secp256k1, NetworkKind};
 use bitcoin::{Network, PrivateKey};
 use bitcoin::{PublicKey, ScriptBuf, TxOut};
 
+// BIP-373 MuSig2 input fields
+const PSBT_IN_MUSIG2_PARTICIPANT_PUBKEYS: u8 = 0x1a;
+const PSBT_IN_MUSIG2_PUB_NONCE: u8 = 0x1b;
+pub(crate) const PSBT_IN_MUSIG2_PARTIAL_SIG: u8 = 0x1c;
+
 pub struct WrappedPsbt {
     pub(crate) psbt: Psbt,
 }
@@ -75,16 +89,196 @@ impl GetKey for Keystore {
 
 impl WrappedPsbt {
     pub fn sign(&mut self, seed: &[u8], mfp: Fingerprint) -> Result<Psbt> {
+        self.sign_with_sighash_none(seed, mfp, false)
+    }
+
+    // SIGHASH_NONE inputs are only signed when the device settings allow it
+    pub fn sign_with_sighash_none(
+        &mut self,
+        seed: &[u8],
+        mfp: Fingerprint,
+        allow_sighash_none: bool,
+    ) -> Result<Psbt> {
         let k = Keystore {
             mfp,
             seed: seed.to_vec(),
         };
+        self.check_sighash_types(mfp, allow_sighash_none)?;
+        self.verify_silent_payment_outputs(seed, mfp)?;
         self.psbt
             .sign(&k, &secp256k1::Secp256k1::new())
             .map_err(|_| BitcoinError::SignFailure(format!("unknown error")))?;
+        self.sign_musig2_key_path(seed, mfp)?;
         Ok(self.psbt.clone())
     }
 
+    // the device contributes its nonce and partial signature in one go with BIP-327
+    // DeterministicSign, so every other participant must have published its nonce already
+    fn sign_musig2_key_path(&mut self, seed: &[u8], mfp: Fingerprint) -> Result<()> {
+        let secp = secp256k1::Secp256k1::new();
+        let prevouts = self
+            .psbt
+            .inputs
+            .iter()
+            .map(|input| input.witness_utxo.clone())
+            .collect::<Option<Vec<_>>>();
+        for index in 0..self.psbt.inputs.len() {
+            let input = &self.psbt.inputs[index];
+            let (aggregate_key, participants) = match Self::get_musig2_participants(input)? {
+                Some(v) => v,
+                None => continue,
+            };
+            let my_key = input
+                .tap_key_origins
+                .iter()
+                .filter(|(_, (_, (fingerprint, _)))| *fingerprint == mfp)
+                .find_map(|(x_only_pubkey, (_, (_, path)))| {
+                    participants
+                        .iter()
+                        .find(|pk| pk.x_only_public_key().0 == *x_only_pubkey)
+                        .map(|pk| (*pk, path.clone()))
+                });
+            let (my_key, path) = match my_key {
+                Some(v) => v,
+                None => continue,
+            };
+            let nonces = Self::get_musig2_fields(input, PSBT_IN_MUSIG2_PUB_NONCE, &aggregate_key);
+            if input.tap_key_sig.is_some() || nonces.contains_key(&my_key) {
+                continue;
+            }
+
+            let private_key =
+                keystore::algorithms::secp256k1::get_private_key_by_seed(seed, &path.to_string())
+                    .map_err(|e| BitcoinError::GetKeyError(e.to_string()))?;
+            if secp256k1::PublicKey::from_secret_key(&secp, &private_key) != my_key {
+                return Err(BitcoinError::MuSig2Error(format!(
+                    "input #{} participant key does not match its derivation",
+                    index
+                )));
+            }
+            let mut key_agg_context = KeyAggContext::new(participants.clone())?;
+            if key_agg_context.aggregated_public_key() != aggregate_key {
+                return Err(BitcoinError::MuSig2Error(format!(
+                    "input #{} aggregate key does not match its participants",
+                    index
+                )));
+            }
+            key_agg_context.apply_taproot_tweak(input.tap_merkle_root)?;
+
+            let other_nonces = participants
+                .iter()
+                .filter(|pk| **pk != my_key)
+                .map(|pk| {
+                    nonces
+                        .get(pk)
+                        .and_then(|nonce| <[u8; PUB_NONCE_LEN]>::try_from(nonce.as_slice()).ok())
+                        .ok_or(BitcoinError::MuSig2Error(format!(
+                            "input #{} has no public nonce from {}, the other participants must provide their nonces first",
+                            index, pk
+                        )))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let prevouts = prevouts.as_ref().ok_or(BitcoinError::SignFailure(
+                "taproot signing requires the witness utxo of every input".to_string(),
+            ))?;
+            let sighash_type = input
+                .sighash_type
+                .map(|v| v.taproot_hash_ty())
+                .transpose()
+                .map_err(|e| BitcoinError::SignFailure(e.to_string()))?
+                .unwrap_or(TapSighashType::Default);
+            let sighash = SighashCache::new(&self.psbt.unsigned_tx)
+                .taproot_key_spend_signature_hash(
+                    index,
+                    &Prevouts::All(prevouts.as_slice()),
+                    sighash_type,
+                )
+                .map_err(|e| BitcoinError::SignFailure(e.to_string()))?;
+
+            let (pub_nonce, partial_sig) = deterministic_sign(
+                &private_key,
+                &nonce_agg(&other_nonces)?,
+                &key_agg_context,
+                &sighash.to_byte_array(),
+            )?;
+
+            let key = [my_key.serialize(), aggregate_key.serialize()].concat();
+            let input = &mut self.psbt.inputs[index];
+            input.unknown.insert(
+                raw::Key {
+                    type_value: PSBT_IN_MUSIG2_PUB_NONCE,
+                    key: key.clone(),
+                },
+                pub_nonce.to_vec(),
+            );
+            input.unknown.insert(
+                raw::Key {
+                    type_value: PSBT_IN_MUSIG2_PARTIAL_SIG,
+                    key,
+                },
+                partial_sig.to_vec(),
+            );
+        }
+        Ok(())
+    }
+
+    // BIP-373 participants of a key path MuSig2 session: (plain aggregate key, participant keys)
+    fn get_musig2_participants(
+        input: &Input,
+    ) -> Result<Option<(secp256k1::PublicKey, Vec<secp256k1::PublicKey>)>> {
+        let internal_key = match input.tap_internal_key {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        for (key, value) in input.unknown.iter() {
+            if key.type_value != PSBT_IN_MUSIG2_PARTICIPANT_PUBKEYS {
+                continue;
+            }
+            let aggregate_key = secp256k1::PublicKey::from_slice(&key.key)
+                .map_err(|e| BitcoinError::InvalidPsbt(e.to_string()))?;
+            // musig() inside a leaf script is not supported
+            if aggregate_key.x_only_public_key().0 != internal_key {
+                continue;
+            }
+            if value.is_empty() || value.len() % 33 != 0 {
+                return Err(BitcoinError::InvalidPsbt(
+                    "invalid musig2 participant pubkeys".to_string(),
+                ));
+            }
+            let participants = value
+                .chunks(33)
+                .map(secp256k1::PublicKey::from_slice)
+                .collect::<core::result::Result<Vec<_>, _>>()
+                .map_err(|e| BitcoinError::InvalidPsbt(e.to_string()))?;
+            return Ok(Some((aggregate_key, participants)));
+        }
+        Ok(None)
+    }
+
+    // key path pub nonces or partial signatures by participant
+    fn get_musig2_fields<'a>(
+        input: &'a Input,
+        type_value: u8,
+        aggregate_key: &secp256k1::PublicKey,
+    ) -> BTreeMap<secp256k1::PublicKey, &'a Vec<u8>> {
+        let aggregate_key = aggregate_key.serialize();
+        input
+            .unknown
+            .iter()
+            .filter(|(key, _)| {
+                key.type_value == type_value
+                    && key.key.len() == 66
+                    && key.key[33..] == aggregate_key[..]
+            })
+            .filter_map(|(key, value)| {
+                secp256k1::PublicKey::from_slice(&key.key[..33])
+                    .ok()
+                    .map(|pk| (pk, value))
+            })
+            .collect()
+    }
+
     pub fn parse_input(
         &self,
         input: &Input,
@@ -134,11 +328,9 @@ impl WrappedPsbt {
             is_multisig,
             is_external: path.clone().map_or(false, |v| v.1),
             need_sign,
-            ecdsa_sighash_type: input
-                .sighash_type
-                .map(|v| v.ecdsa_hash_ty().unwrap_or(bitcoin::EcdsaSighashType::All))
-                .unwrap_or(bitcoin::EcdsaSighashType::All)
-                .to_u32() as u8,
+            ecdsa_sighash_type: self.get_ecdsa_sighash_type(input)?.to_u32() as u8,
+            sighash_warning: self.get_sighash_warning(input),
+            inscriptions: Vec::new(),
         })
     }
 
@@ -177,12 +369,13 @@ impl WrappedPsbt {
         self.check_my_input_script(input, index)?;
         self.check_my_input_signature(input, index, context)?;
         self.check_my_input_value_tampered(input, index)?;
+        self.check_input_sighash(input, index, context.allow_sighash_none)?;
         self.check_my_wallet_type(input, context)?;
         Ok(true)
     }
 
     fn get_my_input_verify_code(&self, input: &Input) -> Option<String> {
-        return if input.bip32_derivation.len() > 1 {
+        return if input.bip32_derivation.len() > 1 || self.is_taproot_multi_sig_input(input) {
             match self.get_multi_sig_input_verify_code(input) {
                 Ok(verify_code) => Some(verify_code),
                 Err(_) => None,
@@ -193,6 +386,18 @@ impl WrappedPsbt {
     }
 
     fn check_my_wallet_type(&self, input: &Input, context: &ParseContext) -> Result<()> {
+        // the input script has already been matched against the policy
+        if let Some(policy) = &context.wallet_policy {
+            return match &context.verify_code {
+                Some(verify_code) if !verify_code.eq(&policy.verify_code) => {
+                    Err(BitcoinError::WalletTypeError(format!(
+                        "wallet type mismatch wallet verify code is {} policy verify code is {}",
+                        verify_code, policy.verify_code
+                    )))
+                }
+                _ => Ok(()),
+            };
+        }
         let input_verify_code = self.get_my_input_verify_code(input);
         match &context.verify_code {
             //single sig
@@ -240,7 +445,7 @@ impl WrappedPsbt {
         index: usize,
         context: &ParseContext,
     ) -> Result<()> {
-        if input.bip32_derivation.len() > 1 {
+        if input.bip32_derivation.len() > 1 || self.is_taproot_multi_sig_input(input) {
             // we consider this situation to be OK currently.
 
             // for key in input.bip32_derivation.keys() {
@@ -384,6 +589,11 @@ impl WrappedPsbt {
 
     //return: (sigs, required_sigs)
     fn get_input_sign_status(&self, input: &Input) -> (u32, u32) {
+        if self.is_taproot_multi_sig_input(input) {
+            if let Some(status) = self.get_taproot_multi_sig_sign_status(input) {
+                return status;
+            }
+        }
         //this might be a multisig input;
         if input.bip32_derivation.len() > 1 {
             let result = self.get_multi_sig_script_and_format(input);
@@ -407,8 +617,61 @@ impl WrappedPsbt {
         )
     }
 
+    fn get_taproot_multi_sig_sign_status(&self, input: &Input) -> Option<(u32, u32)> {
+        // key path MuSig2 needs a partial signature from every participant
+        if let Ok(Some((aggregate_key, participants))) = Self::get_musig2_participants(input) {
+            let total = participants.len() as u32;
+            if input.tap_key_sig.is_some() {
+                return Some((total, total));
+            }
+            let partial_sigs =
+                Self::get_musig2_fields(input, PSBT_IN_MUSIG2_PARTIAL_SIG, &aggregate_key);
+            return Some((partial_sigs.len() as u32, total));
+        }
+        let (script, threshold, _) = self.get_taproot_multi_sig_script(input)?;
+        if input.tap_key_sig.is_some() {
+            return Some((threshold, threshold));
+        }
+        let leaf_hash = TapLeafHash::from_script(script, LeafVersion::TapScript);
+        let sigs = input
+            .tap_script_sigs
+            .keys()
+            .filter(|(_, v)| *v == leaf_hash)
+            .count();
+        Some((sigs as u32, threshold))
+    }
+
+    fn is_taproot_multi_sig_input_signed_by_me(
+        &self,
+        input: &Input,
+        context: &ParseContext,
+    ) -> bool {
+        let my_keys = input
+            .tap_key_origins
+            .iter()
+            .filter(|(_, (_, (fingerprint, _)))| *fingerprint == context.master_fingerprint)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        if let Ok(Some((aggregate_key, _))) = Self::get_musig2_participants(input) {
+            return Self::get_musig2_fields(input, PSBT_IN_MUSIG2_PARTIAL_SIG, &aggregate_key)
+                .keys()
+                .any(|pk| my_keys.contains(&pk.x_only_public_key().0));
+        }
+        input
+            .tap_script_sigs
+            .keys()
+            .any(|(pk, _)| my_keys.contains(pk))
+    }
+
     // assume this is my input because we checked it before;
     fn need_sign_input(&self, input: &Input, context: &ParseContext) -> Result<bool> {
+        if self.is_taproot_multi_sig_input(input) {
+            let (cur, req) = self.get_input_sign_status(input);
+            if cur >= req {
+                return Ok(false);
+            }
+            return Ok(!self.is_taproot_multi_sig_input_signed_by_me(input, context));
+        }
         if input.bip32_derivation.len() > 1 {
             let (cur, req) = self.get_input_sign_status(input);
             //already collected needed signatures
@@ -463,6 +726,68 @@ impl WrappedPsbt {
         Ok((required_sigs, total))
     }
 
+    // (script, threshold, keys) of the sortedmulti_a leaf
+    fn get_taproot_multi_sig_script<'a>(
+        &self,
+        input: &'a Input,
+    ) -> Option<(&'a ScriptBuf, u32, Vec<XOnlyPublicKey>)> {
+        input
+            .tap_scripts
+            .values()
+            .filter(|(_, leaf_version)| *leaf_version == LeafVersion::TapScript)
+            .find_map(|(script, _)| {
+                parse_multi_a_script(script).map(|(threshold, keys)| (script, threshold, keys))
+            })
+    }
+
+    // the internal key and the merkle root must be exactly what the cosigner keys produce,
+    // otherwise a coordinator could slip in a key path it controls
+    fn get_taproot_multi_sig_threshold_total_and_format(
+        &self,
+        input: &Input,
+        public_keys: &Vec<secp256k1::PublicKey>,
+    ) -> Result<(u8, u8, MultiSigFormat)> {
+        let (_, threshold, script_keys) =
+            self.get_taproot_multi_sig_script(input)
+                .ok_or(BitcoinError::MultiSigInputError(
+                    "have no multi_a leaf script".to_string(),
+                ))?;
+        if script_keys.len() != public_keys.len() {
+            return Err(BitcoinError::MultiSigInputError(
+                "multi_a leaf script does not match tap_key_origins".to_string(),
+            ));
+        }
+        let nums_key = XOnlyPublicKey::from_str(TAPROOT_NUMS_KEY)
+            .map_err(|e| BitcoinError::MultiSigInputError(e.to_string()))?;
+        let is_musig = input.tap_internal_key != Some(nums_key);
+        let format = || {
+            if is_musig {
+                MultiSigFormat::P2trMusig2
+            } else {
+                MultiSigFormat::P2tr
+            }
+        };
+
+        let ordered_pub_keys = public_keys
+            .iter()
+            .map(|key| PublicKey::new(*key))
+            .sorted()
+            .collect::<Vec<_>>();
+        let (internal_key, merkle_root) = calculate_taproot_multi_spend_info(
+            &ordered_pub_keys.iter().collect(),
+            threshold,
+            format(),
+        )?;
+        if input.tap_internal_key != Some(internal_key)
+            || input.tap_merkle_root != Some(merkle_root)
+        {
+            return Err(BitcoinError::MultiSigInputError(
+                "taproot output does not match the cosigner keys".to_string(),
+            ));
+        }
+        Ok((threshold as u8, script_keys.len() as u8, format()))
+    }
+
     fn get_xpub_from_psbt_by_fingerprint(
         &self,
         fp: &Fingerprint,
@@ -485,27 +810,51 @@ impl WrappedPsbt {
             ));
         }
 
-        let mut xpubs = vec![];
-        for key in input.bip32_derivation.keys() {
-            let (fingerprint, path) = input
+        let is_taproot = self.is_taproot_multi_sig_input(input);
+        let key_sources = if is_taproot {
+            input
+                .tap_key_origins
+                .iter()
+                .filter(|(_, (leaf_hashes, _))| !leaf_hashes.is_empty())
+                .map(|(key, (_, source))| (key.serialize().to_vec(), source))
+                .collect::<Vec<_>>()
+        } else {
+            input
                 .bip32_derivation
-                .get(key)
-                .ok_or(BitcoinError::InvalidInput)?;
+                .iter()
+                .map(|(key, source)| (key.serialize().to_vec(), source))
+                .collect::<Vec<_>>()
+        };
+
+        let mut xpubs = vec![];
+        let mut public_keys = vec![];
+        for (key, (fingerprint, path)) in key_sources.iter() {
             let (xpub, derivation_path) = self.get_xpub_from_psbt_by_fingerprint(fingerprint)?;
             let public_key = derive_public_key_by_path(xpub, derivation_path, path)?;
-            if public_key == *key {
+            let derived_key = if is_taproot {
+                public_key.x_only_public_key().0.serialize().to_vec()
+            } else {
+                public_key.serialize().to_vec()
+            };
+            if derived_key == *key {
                 xpubs.push(xpub.to_string());
+                public_keys.push(public_key);
             }
         }
 
-        if xpubs.is_empty() || xpubs.len() != input.bip32_derivation.len() {
+        if xpubs.is_empty() || xpubs.len() != key_sources.len() {
             return Err(BitcoinError::MultiSigInputError(
                 "xpub does not match bip32_derivation".to_string(),
             ));
         }
 
-        let (script, format) = self.get_multi_sig_script_and_format(input)?;
-        let (threshold, total) = self.get_multi_sig_input_threshold_and_total(script)?;
+        let (threshold, total, format) = if is_taproot {
+            self.get_taproot_multi_sig_threshold_total_and_format(input, &public_keys)?
+        } else {
+            let (script, format) = self.get_multi_sig_script_and_format(input)?;
+            let (threshold, total) = self.get_multi_sig_input_threshold_and_total(script)?;
+            (threshold, total, format)
+        };
 
         let network = if let Some((xpub, _)) = self.psbt.xpub.first_key_value() {
             match xpub.network {
@@ -643,11 +992,15 @@ impl WrappedPsbt {
             .ok_or(BitcoinError::InvalidOutput)?;
         let path = self.get_my_output_path(output, index, context)?;
         Ok(ParsedOutput {
-            address: self.calculate_address_for_output(tx_out, network)?,
+            address: match self.get_silent_payment_address(output, network)? {
+                Some(address) => address.to_string(),
+                None => self.calculate_address_for_output(tx_out, network)?,
+            },
             amount: Self::format_amount(tx_out.value.to_sat(), network),
             value: tx_out.value.to_sat(),
             path: path.clone().map(|v| v.0),
             is_external: path.clone().map_or(false, |v| v.1),
+            inscriptions: Vec::new(),
         })
     }
 
@@ -668,17 +1021,38 @@ impl WrappedPsbt {
         index: usize,
         context: &ParseContext,
     ) -> Result<Option<(String, bool)>> {
-        if context.multisig_wallet_config.is_some() {
-            if self.is_taproot_input(input) {
-                return Err(BitcoinError::InvalidPsbt(format!(
-                    "multisig with taproot is not supported"
-                )));
-            }
+        if let Some(policy) = &context.wallet_policy {
+            let script_pubkey = self.get_input_script_pubkey(input, index);
+            let key_sources = input
+                .bip32_derivation
+                .values()
+                .chain(input.tap_key_origins.values().map(|(_, source)| source));
+            return self.get_my_key_path_for_policy(
+                key_sources,
+                script_pubkey.as_ref(),
+                index,
+                "input",
+                context,
+                policy,
+            );
         }
         if self.is_taproot_input(input) {
+            if context.multisig_wallet_config.is_some() {
+                return self.get_my_key_path(
+                    Self::get_taproot_script_key_sources(&input.tap_key_origins),
+                    index,
+                    "input",
+                    context,
+                );
+            }
             self.get_my_key_path_for_taproot(&input.tap_key_origins, index, "input", context)
         } else {
-            self.get_my_key_path(&input.bip32_derivation, index, "input", context)
+            self.get_my_key_path(
+                input.bip32_derivation.values().collect(),
+                index,
+                "input",
+                context,
+            )
         }
     }
 
@@ -688,16 +1062,49 @@ impl WrappedPsbt {
         index: usize,
         context: &ParseContext,
     ) -> Result<Option<(String, bool)>> {
-        let path = self.get_my_key_path(&output.bip32_derivation, index, "output", context)?;
+        if let Some(policy) = &context.wallet_policy {
+            let script_pubkey = self
+                .psbt
+                .unsigned_tx
+                .output
+                .get(index)
+                .map(|v| v.script_pubkey.clone());
+            let key_sources = output
+                .bip32_derivation
+                .values()
+                .chain(output.tap_key_origins.values().map(|(_, source)| source));
+            return self.get_my_key_path_for_policy(
+                key_sources,
+                script_pubkey.as_ref(),
+                index,
+                "output",
+                context,
+                policy,
+            );
+        }
+        let path = self.get_my_key_path(
+            output.bip32_derivation.values().collect(),
+            index,
+            "output",
+            context,
+        )?;
         if path.is_some() {
             return Ok(path);
         }
+        if context.multisig_wallet_config.is_some() {
+            return self.get_my_key_path(
+                Self::get_taproot_script_key_sources(&output.tap_key_origins),
+                index,
+                "output",
+                context,
+            );
+        }
         self.get_my_key_path_for_taproot(&output.tap_key_origins, index, "output", context)
     }
 
     pub fn get_my_key_path(
         &self,
-        bip32_derivation: &BTreeMap<secp256k1::PublicKey, KeySource>,
+        key_sources: Vec<&KeySource>,
         index: usize,
         purpose: &str,
         context: &ParseContext,
@@ -705,7 +1112,7 @@ impl WrappedPsbt {
         if let Some(config) = &context.multisig_wallet_config {
             let total = config.total;
             // not my key
-            if bip32_derivation.keys().len() as u32 != total {
+            if key_sources.len() as u32 != total {
                 return Ok(None);
             }
 
@@ -715,8 +1122,8 @@ impl WrappedPsbt {
                 .map(|v| v.xfp.clone())
                 .sorted()
                 .fold("".to_string(), |acc, cur| format!("{}{}", acc, cur));
-            let xfps = bip32_derivation
-                .values()
+            let xfps = key_sources
+                .iter()
                 .map(|(fp, _)| fp.to_string())
                 .sorted()
                 .fold("".to_string(), |acc, cur| format!("{}{}", acc, cur));
@@ -726,10 +1133,7 @@ impl WrappedPsbt {
             }
         }
         //it's a singlesig or it's my multisig input
-        for key in bip32_derivation.keys() {
-            let (fingerprint, path) = bip32_derivation
-                .get(key)
-                .ok_or(BitcoinError::InvalidInput)?;
+        for (fingerprint, path) in key_sources {
             if fingerprint.eq(&context.master_fingerprint) {
                 let child = path.to_string();
                 match &context.multisig_wallet_config {
@@ -771,6 +1175,50 @@ impl WrappedPsbt {
         Ok(None)
     }
 
+    // a key path that fits the policy is only ours when the script derived from the policy at that
+    // path is exactly the one being spent or paid to
+    fn get_my_key_path_for_policy<'a>(
+        &self,
+        key_sources: impl Iterator<Item = &'a KeySource>,
+        script_pubkey: Option<&ScriptBuf>,
+        index: usize,
+        purpose: &str,
+        context: &ParseContext,
+        policy: &WalletPolicy,
+    ) -> Result<Option<(String, bool)>> {
+        for (fingerprint, path) in key_sources {
+            if !fingerprint.eq(&context.master_fingerprint) {
+                continue;
+            }
+            let (is_change, address_index) = match policy.match_key_path(fingerprint, path) {
+                Some(v) => v,
+                None => continue,
+            };
+            let expected = policy.derive_script_pubkey(is_change, address_index)?;
+            if script_pubkey != Some(&expected) {
+                return Err(BitcoinError::InvalidTransaction(format!(
+                    "invalid {} #{}, script does not match wallet policy {}",
+                    purpose, index, policy.name
+                )));
+            }
+            return Ok(Some((path.to_string().to_uppercase(), !is_change)));
+        }
+        Ok(None)
+    }
+
+    pub(crate) fn get_input_script_pubkey(&self, input: &Input, index: usize) -> Option<ScriptBuf> {
+        if let Some(utxo) = &input.witness_utxo {
+            return Some(utxo.script_pubkey.clone());
+        }
+        let tx_in = self.psbt.unsigned_tx.input.get(index)?;
+        input
+            .non_witness_utxo
+            .as_ref()?
+            .output
+            .get(tx_in.previous_output.vout as usize)
+            .map(|v| v.script_pubkey.clone())
+    }
+
     fn judge_external_key(child: String, parent: String) -> bool {
         let sub_path = &child[parent.len()..];
         fn judge(v: &mut Chars) -> bool {
@@ -825,6 +1273,23 @@ impl WrappedPsbt {
         None
     }
 
+    // the keys of a taproot multisig are the ones used in leaf scripts, a MuSig2 aggregate key
+    // may be listed as well but only for the key path
+    fn get_taproot_script_key_sources(
+        tap_key_origins: &BTreeMap<XOnlyPublicKey, (Vec<TapLeafHash>, KeySource)>,
+    ) -> Vec<&KeySource> {
+        tap_key_origins
+            .values()
+            .filter(|(leaf_hashes, _)| !leaf_hashes.is_empty())
+            .map(|(_, source)| source)
+            .collect()
+    }
+
+    fn is_taproot_multi_sig_input(&self, input: &Input) -> bool {
+        self.is_taproot_input(input)
+            && Self::get_taproot_script_key_sources(&input.tap_key_origins).len() > 1
+    }
+
     fn is_taproot_input(&self, input: &Input) -> bool {
         if let Some(witness_utxo) = &input.witness_utxo {
             return witness_utxo.script_pubkey.is_p2tr();
@@ -949,12 +1414,13 @@ mod tests {
             let mut keys = BTreeMap::new();
             keys.insert(path, extended_pubkey);
 
-            let reust = wpsbt.check(Left(&ParseContext {
-                master_fingerprint: master_fingerprint.clone(),
-                extended_public_keys: keys.clone(),
-                verify_code: None,
-                multisig_wallet_config: None,
-            }));
+            let reust = wpsbt.check(Left(&ParseContext::new(
+                master_fingerprint.clone(),
+                keys.clone(),
+                None,
+                None,
+                None,
+            )));
             assert_eq!(Ok(()), reust);
         }
 
@@ -970,12 +1436,13 @@ mod tests {
             let mut keys = BTreeMap::new();
             keys.insert(path, extended_pubkey);
 
-            let reust = wpsbt.check(Left(&ParseContext {
-                master_fingerprint: master_fingerprint.clone(),
-                extended_public_keys: keys.clone(),
-                verify_code: None,
-                multisig_wallet_config: None,
-            }));
+            let reust = wpsbt.check(Left(&ParseContext::new(
+                master_fingerprint.clone(),
+                keys.clone(),
+                None,
+                None,
+                None,
+            )));
             assert_eq!(true, reust.is_err());
         }
 
@@ -991,12 +1458,13 @@ mod tests {
             let mut keys = BTreeMap::new();
             keys.insert(path, extended_pubkey);
 
-            let reust = wpsbt.check(Left(&ParseContext {
-                master_fingerprint: master_fingerprint.clone(),
-                extended_public_keys: keys.clone(),
-                verify_code: Some("03669e02".to_string()),
-                multisig_wallet_config: None,
-            }));
+            let reust = wpsbt.check(Left(&ParseContext::new(
+                master_fingerprint.clone(),
+                keys.clone(),
+                Some("03669e02".to_string()),
+                None,
+                None,
+            )));
             assert_eq!(true, reust.is_err());
         }
 
@@ -1012,12 +1480,13 @@ mod tests {
             let mut keys = BTreeMap::new();
             keys.insert(path, extended_pubkey);
 
-            let reust = wpsbt.check(Left(&ParseContext {
-                master_fingerprint: master_fingerprint.clone(),
-                extended_public_keys: keys.clone(),
-                verify_code: Some("03669e02".to_string()),
-                multisig_wallet_config: None,
-            }));
+            let reust = wpsbt.check(Left(&ParseContext::new(
+                master_fingerprint.clone(),
+                keys.clone(),
+                Some("03669e02".to_string()),
+                None,
+                None,
+            )));
             assert_eq!(Ok(()), reust);
         }
 
@@ -1033,13 +1502,182 @@ mod tests {
             let mut keys = BTreeMap::new();
             keys.insert(path, extended_pubkey);
 
-            let reust = wpsbt.check(Left(&ParseContext {
-                master_fingerprint: master_fingerprint.clone(),
-                extended_public_keys: keys.clone(),
-                verify_code: Some("12345678".to_string()),
-                multisig_wallet_config: None,
-            }));
+            let reust = wpsbt.check(Left(&ParseContext::new(
+                master_fingerprint.clone(),
+                keys.clone(),
+                Some("12345678".to_string()),
+                None,
+                None,
+            )));
             assert_eq!(true, reust.is_err());
         }
     }
+
+    #[test]
+    fn test_check_psbt_sighash_none() {
+        let psbt_hex = "70736274ff01005e02000000013aee4d6b51da574900e56d173041115bd1e1d01d4697a845784cf716a10c98060000000000ffffffff0100190000000000002251202258f2d4637b2ca3fd27614868b33dee1a242b42582d5474f51730005fa99ce8000000000001012bbc1900000000000022512022f3956cc27a6a9b0e0003a0afc113b04f31b95d5cad222a65476e8440371bd1010304000000002116b68df382cad577d8304d5a8e640c3cb42d77c10016ab754caa4d6e68b6cb296d190073c5da0a5600008001000080000000800000000002000000011720b68df382cad577d8304d5a8e640c3cb42d77c10016ab754caa4d6e68b6cb296d011820c913dc9a8009a074e7bbc493b9d8b7e741ba137f725f99d44fbce99300b2bb0a0000";
+        let mut psbt = Psbt::deserialize(&Vec::from_hex(psbt_hex).unwrap()).unwrap();
+        psbt.inputs[0].sighash_type = Some(TapSighashType::None.into());
+        let wpsbt = WrappedPsbt { psbt };
+        let master_fingerprint = Fingerprint::from_str("73c5da0a").unwrap();
+        let extended_pubkey = Xpub::from_str("tpubDDfvzhdVV4unsoKt5aE6dcsNsfeWbTgmLZPi8LQDYU2xixrYemMfWJ3BaVneH3u7DBQePdTwhpybaKRU95pi6PMUtLPBJLVQRpzEnjfjZzX").unwrap();
+        let path = DerivationPath::from_str("m/86'/1'/0'").unwrap();
+        let mut keys = BTreeMap::new();
+        keys.insert(path, extended_pubkey);
+        let context = |allow_sighash_none: bool| {
+            ParseContext::new(master_fingerprint, keys.clone(), None, None, None)
+                .with_allow_sighash_none(allow_sighash_none)
+        };
+
+        assert_eq!(
+            Err(BitcoinError::InvalidSighashType(
+                "input #0 uses SIGHASH_NONE which needs blind signing to be enabled".to_string()
+            )),
+            wpsbt.check(Left(&context(false)))
+        );
+        assert_eq!(Ok(()), wpsbt.check(Left(&context(true))));
+    }
+
+    #[test]
+    fn test_check_psbt_with_wallet_policy() {
+        let policy = WalletPolicy::new(
+            "Vault",
+            "wsh(or_d(multi(2,@0/**,@1/**,@2/**),and_v(v:pk(@3/**),older(52560))))",
+            vec![
+                "[73c5da0a/48'/0'/0'/2']xpub6DkFAXWQ2dHxq2vatrt9qyA3bXYU4ToWQwCHbf5XB2mSTexcHZCeKS1VZYcPoBd5X8yVcbXFHJR9R8UCVpt82VX1VhR28mCyxUFL4r6KFrf".to_string(),
+                "[44c757a9/48'/0'/0'/2']xpub6FA1aifiRQ2if4K56CoceBjqAhGkpPCAzEZzu8dTtjSxszDMrrPkKnG4Vj2LnMFdpgQdrh6eWXRuXA3seVXDJYYkVJSdynVEAT3GD4Wooup".to_string(),
+                "[a261afc4/48'/0'/0'/2']xpub6DbSZPadfy9TYs2ULjLKpeFv3NBg4AR5m4AEiss8bPffAPhVnxB4qZtq9jA8AJYKY2ez4rswbFsMB7ggqQNqKFGuwTVrEAJFqxQfEx98NyH".to_string(),
+                "[83b787a5/48'/0'/0'/2']xpub6F1kySS5M21e5Hx7V9bM1ouaARYTDdzEWDVmZwLxtFbwutdiW83FP6fwKBNugNTbXf27ZcX4DGj6hYmQxwSXyAQCZxVXJ7Rz8P4YFoBBjtB".to_string(),
+            ],
+        )
+        .unwrap();
+        let master_fingerprint = Fingerprint::from_str("73c5da0a").unwrap();
+        let context = ParseContext::new(
+            master_fingerprint,
+            BTreeMap::new(),
+            Some(policy.verify_code.clone()),
+            None,
+            None,
+        )
+        .with_wallet_policy(policy);
+
+        // spend from receive address #0 and send change to change address #1
+        {
+            let psbt_hex = "70736274ff01007d020000000111111111111111111111111111111111111111111111111111111111111111110000000000fdffffff0250c3000000000000160014adfce54f529b2154e3c361bbe3f7d41db0635717409c00000000000022002070b60a8e0711720dea16584631857ed0187ce38642ef61cf7e9b73d883ad53ff000000000001012ba0860100000000002200208f0a1946988b53097b0eb65beae88bde37522205c4c952e66e69b2f2554fce40010594522103dc1953c2756c7c58d4f48ca1bbba767f414fd236bf4d662b67721ac626c514e021022f703aa6b76c5fc6056120ef0785a3b4c9f0dfe434dbe94187e82d3363e478f021035b923fe1e6d14ff3ac7d1b3d8223fe6322c19c50bc65f769e5f1fcb13c44562f53ae736421037f804ef398647e7553546b9c1ff38465e678f1b381a42a3fd34cc1ecba6cf3adad0350cd00b2682206022f703aa6b76c5fc6056120ef0785a3b4c9f0dfe434dbe94187e82d3363e478f01c44c757a93000008000000080000000800200008000000000000000002206035b923fe1e6d14ff3ac7d1b3d8223fe6322c19c50bc65f769e5f1fcb13c44562f1ca261afc43000008000000080000000800200008000000000000000002206037f804ef398647e7553546b9c1ff38465e678f1b381a42a3fd34cc1ecba6cf3ad1c83b787a5300000800000008000000080020000800000000000000000220603dc1953c2756c7c58d4f48ca1bbba767f414fd236bf4d662b67721ac626c514e01c73c5da0a3000008000000080000000800200008000000000000000000000010194522103c029b8119aa3871074f41905df6911d6c594e6cc643999db7f14fc5ac9756922210320c2de462e2052145861e657b4258362684673355d504488a8acd1f215d80eec2103b334a8d92c4b6721480766539681f60646b49e2458929aaa0a0e6600f7e28a5953ae736421030c9115f88827420766bd143fd8aa942710f08f69faa3a44c4654c8144b8ceb1bad0350cd00b2682202030c9115f88827420766bd143fd8aa942710f08f69faa3a44c4654c8144b8ceb1b1c83b787a530000080000000800000008002000080010000000100000022020320c2de462e2052145861e657b4258362684673355d504488a8acd1f215d80eec1c44c757a9300000800000008000000080020000800100000001000000220203b334a8d92c4b6721480766539681f60646b49e2458929aaa0a0e6600f7e28a591ca261afc4300000800000008000000080020000800100000001000000220203c029b8119aa3871074f41905df6911d6c594e6cc643999db7f14fc5ac97569221c73c5da0a30000080000000800000008002000080010000000100000000";
+            let psbt = Psbt::deserialize(&Vec::from_hex(psbt_hex).unwrap()).unwrap();
+            let wpsbt = WrappedPsbt { psbt };
+            assert_eq!(Ok(()), wpsbt.check(Left(&context)));
+
+            let result = wpsbt.parse(Some(&context)).unwrap();
+            let input = result.detail.from.get(0).unwrap();
+            assert_eq!(
+                "bc1q3u9pj35c3dfsj7cwked746ytmcm4ygs9cny49enwdxe0y420eeqq6hrhl6",
+                input.address.clone().unwrap()
+            );
+            assert_eq!(true, input.path.is_some());
+            assert_eq!(true, input.is_external);
+            assert_eq!(true, input.need_sign);
+            let external = result.detail.to.get(0).unwrap();
+            assert_eq!(true, external.path.is_none());
+            let change = result.detail.to.get(1).unwrap();
+            assert_eq!(
+                "bc1qwzmq4rs8z9eqm6sktprrrpt76qv8ecuxgthkrnm7ndea3qad20lsc6q0wk",
+                change.address
+            );
+            assert_eq!(true, change.path.is_some());
+            assert_eq!(false, change.is_external);
+            assert_eq!("0.0005 BTC", result.overview.total_output_amount);
+        }
+
+        // change output claims change address #1 but pays to change address #0
+        {
+            let psbt_hex = "70736274ff01007d020000000111111111111111111111111111111111111111111111111111111111111111110000000000fdffffff0250c3000000000000160014adfce54f529b2154e3c361bbe3f7d41db0635717409c000000000000220020c133fd7ece8da91ffd0572b29f6a80b311fd94f77b9c3ee74cbe34554b470534000000000001012ba0860100000000002200208f0a1946988b53097b0eb65beae88bde37522205c4c952e66e69b2f2554fce40010594522103dc1953c2756c7c58d4f48ca1bbba767f414fd236bf4d662b67721ac626c514e021022f703aa6b76c5fc6056120ef0785a3b4c9f0dfe434dbe94187e82d3363e478f021035b923fe1e6d14ff3ac7d1b3d8223fe6322c19c50bc65f769e5f1fcb13c44562f53ae736421037f804ef398647e7553546b9c1ff38465e678f1b381a42a3fd34cc1ecba6cf3adad0350cd00b2682206022f703aa6b76c5fc6056120ef0785a3b4c9f0dfe434dbe94187e82d3363e478f01c44c757a93000008000000080000000800200008000000000000000002206035b923fe1e6d14ff3ac7d1b3d8223fe6322c19c50bc65f769e5f1fcb13c44562f1ca261afc43000008000000080000000800200008000000000000000002206037f804ef398647e7553546b9c1ff38465e678f1b381a42a3fd34cc1ecba6cf3ad1c83b787a5300000800000008000000080020000800000000000000000220603dc1953c2756c7c58d4f48ca1bbba767f414fd236bf4d662b67721ac626c514e01c73c5da0a3000008000000080000000800200008000000000000000000000010194522103c029b8119aa3871074f41905df6911d6c594e6cc643999db7f14fc5ac9756922210320c2de462e2052145861e657b4258362684673355d504488a8acd1f215d80eec2103b334a8d92c4b6721480766539681f60646b49e2458929aaa0a0e6600f7e28a5953ae736421030c9115f88827420766bd143fd8aa942710f08f69faa3a44c4654c8144b8ceb1bad0350cd00b2682202030c9115f88827420766bd143fd8aa942710f08f69faa3a44c4654c8144b8ceb1b1c83b787a530000080000000800000008002000080010000000100000022020320c2de462e2052145861e657b4258362684673355d504488a8acd1f215d80eec1c44c757a9300000800000008000000080020000800100000001000000220203b334a8d92c4b6721480766539681f60646b49e2458929aaa0a0e6600f7e28a591ca261afc4300000800000008000000080020000800100000001000000220203c029b8119aa3871074f41905df6911d6c594e6cc643999db7f14fc5ac97569221c73c5da0a30000080000000800000008002000080010000000100000000";
+            let psbt = Psbt::deserialize(&Vec::from_hex(psbt_hex).unwrap()).unwrap();
+            let wpsbt = WrappedPsbt { psbt };
+            assert_eq!(true, wpsbt.check(Left(&context)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_sign_musig2_key_path() {
+        let config = r#"# Keystone Multisig setup file
+            #
+            Name: taproot
+            Policy: 2 of 3
+            Derivation: m/48'/0'/0'/3'
+            Format: P2TR-MUSIG2
+
+            73C5DA0A: xpub6DkFAXWQ2dHxr7LX1ByDVebj6u3C5KSKTVXWkiVKb3tdYfh9t7FhXzvUVSxNSikoVTRb2bGjvYoW8PqYBReMeswi3megtqDwRCeVs3vxMeH
+            44C757A9: xpub6FA1aifiRQ2ifzw9kpmEDuVz9xbkm4U5QXKy7cbqbvfzjh4NFqG2WKAGaFihmbkTz1vNifpDnmDurfvQJqgQM9TUjvqgxpGNhLvuvZJfJKq
+            A261AFC4: xpub6DbSZPadfy9Tbh4aM7VtmWC1xGi3Yg2n35eUELZM1wPECyjAzSRWKn9EhqKwjqeFHBq2DYnz6hzcagx7NGDXqoxEQRzG2hVNvhwR3w9oAVZ
+            "#;
+        let config = crate::multi_sig::wallet::parse_wallet_config(config, "73C5DA0A").unwrap();
+        let master_fingerprint = Fingerprint::from_str("73c5da0a").unwrap();
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let context = ParseContext::new(
+            master_fingerprint,
+            BTreeMap::new(),
+            Some(config.verify_code.clone()),
+            Some(config),
+            None,
+        );
+
+        // both cosigners have published their nonces
+        {
+            let psbt_hex = "70736274ff01007d020000000111111111111111111111111111111111111111111111111111111111111111110000000000fdffffff0250c3000000000000160014adfce54f529b2154e3c361bbe3f7d41db063571768bf0000000000002251205004d3b9da163e4cb75692060694b45224698af0392b1b3667d628a45f6fcac6000000004f010488b21e041cf297168000000327b615ed8af0de5eb93f3be49dbcbdd534f54ca288f256be62bc205fc3c331f80396315337b6b29f1de58cc11b8a0c549ff3186e202feb29bc5261091be4f99b211473c5da0a300000800000008000000080030000804f010488b21e04dcbf1083800000031e40df384d87b4ff81346efa6ed8a2bcc796f7399a41759986092169c74c540b0250ebd26b7a2823d988535321953bbb26a8d274723f1c90958f95b710364d1f561444c757a9300000800000008000000080030000804f010488b21e04084c0c568000000326e949e6d8b460db691ccad752311a7802700b037ac1dc3fbf8a6c0f3c28a98d03efaa6dc0aeb6eca5886fae4ad9260e939a0c9611dd471319f85acacf5fb7e2cb14a261afc4300000800000008000000080030000800001012ba086010000000000225120518ea9c0300b99949f829dc68aff0945ae072888ba7eb08243b7169e396725112215c1a9f6835318a0b97c5735c124294ce05e23e50482b5a424a008378490214421f36920025128c0bcadea78ad4d7f490e480959aee9ccb55fd200190d8e5e86e5016e05ac2078884ed3b3620bff1d682c471a29fcc2f14de0023e8fb369b12912e13bb38133ba207b80e1d4a9c7cc7cbb4a2a7fcd181f1e7fe06f2c9984fa3324fea48b7c1462ffba529cc02116025128c0bcadea78ad4d7f490e480959aee9ccb55fd200190d8e5e86e5016e053d01778698328df9030443ccbc656242b2183de8f5de3c2d7bf8acf3b873488791ce73c5da0a300000800000008000000080030000800000000000000000211678884ed3b3620bff1d682c471a29fcc2f14de0023e8fb369b12912e13bb381333d01778698328df9030443ccbc656242b2183de8f5de3c2d7bf8acf3b873488791cea261afc430000080000000800000008003000080000000000000000021167b80e1d4a9c7cc7cbb4a2a7fcd181f1e7fe06f2c9984fa3324fea48b7c1462ff3d01778698328df9030443ccbc656242b2183de8f5de3c2d7bf8acf3b873488791ce44c757a9300000800000008000000080030000800000000000000000011720a9f6835318a0b97c5735c124294ce05e23e50482b5a424a008378490214421f3011820778698328df9030443ccbc656242b2183de8f5de3c2d7bf8acf3b873488791ce221a03a9f6835318a0b97c5735c124294ce05e23e50482b5a424a008378490214421f36303025128c0bcadea78ad4d7f490e480959aee9ccb55fd200190d8e5e86e5016e050378884ed3b3620bff1d682c471a29fcc2f14de0023e8fb369b12912e13bb38133037b80e1d4a9c7cc7cbb4a2a7fcd181f1e7fe06f2c9984fa3324fea48b7c1462ff431b037b80e1d4a9c7cc7cbb4a2a7fcd181f1e7fe06f2c9984fa3324fea48b7c1462ff03a9f6835318a0b97c5735c124294ce05e23e50482b5a424a008378490214421f342028929e8c68a0ea94b3b6fe298aee8d6fb1d2336ae5f5823b01cc7a5333e10e29d03bb1ec99b64efac6f01869f0adf63ddb2b744ccc8845c3fb2907e167ac7b9ecb0431b0378884ed3b3620bff1d682c471a29fcc2f14de0023e8fb369b12912e13bb3813303a9f6835318a0b97c5735c124294ce05e23e50482b5a424a008378490214421f342038dc202935d956f67e2718c461c568baf69f262ea78991f87c74181bacf49b18d03a7792c9329f1d83db32cbbf28d631b385a2c7265e044bfca747c1953091dfd4e0000010520e9d454db513f9196f8399a630ad5bc9f34f3b2c4a946b24de0fcd99df4af036201066b00c068208cae20be007cfb4a7b5c062128d33cf42d03a6b9720c2c4b1d9dc6b519c55d65ac20c5019ebae67139d520958aacae2b6ec8b0c8be92d0e5d37d6c5a8aa7a2f0a502ba20f715fabd9e7e4be6318a1ebdbef02945ea1ca50af0016d706380121fe45f603dba529c21078cae20be007cfb4a7b5c062128d33cf42d03a6b9720c2c4b1d9dc6b519c55d653d012aae4a74444d405ef2e83088cf4ea9a9240b28489c1878c33e54653a75ac2b44a261afc43000008000000080000000800300008001000000000000002107c5019ebae67139d520958aacae2b6ec8b0c8be92d0e5d37d6c5a8aa7a2f0a5023d012aae4a74444d405ef2e83088cf4ea9a9240b28489c1878c33e54653a75ac2b4444c757a93000008000000080000000800300008001000000000000002107f715fabd9e7e4be6318a1ebdbef02945ea1ca50af0016d706380121fe45f603d3d012aae4a74444d405ef2e83088cf4ea9a9240b28489c1878c33e54653a75ac2b4473c5da0a30000080000000800000008003000080010000000000000000";
+            let psbt = Psbt::deserialize(&Vec::from_hex(psbt_hex).unwrap()).unwrap();
+            let mut wpsbt = WrappedPsbt { psbt };
+            assert_eq!(Ok(()), wpsbt.check(Left(&context)));
+
+            let result = wpsbt.parse(Some(&context)).unwrap();
+            let input = result.detail.from.get(0).unwrap();
+            assert_eq!(
+                "bc1p2x82nspspwvef8uznhrg4lcfgkhqw2yghfltpqjrkutfuwt8y5gsn6qelg",
+                input.address.clone().unwrap()
+            );
+            assert_eq!(Some("48'/0'/0'/3'/0/0".to_string()), input.path);
+            assert_eq!(true, input.need_sign);
+            assert_eq!((0, 3), input.sign_status);
+            let change = result.detail.to.get(1).unwrap();
+            assert_eq!(Some("48'/0'/0'/3'/1/0".to_string()), change.path);
+            assert_eq!(false, change.is_external);
+
+            let signed = wpsbt.sign(&seed, master_fingerprint).unwrap();
+            let key = Vec::from_hex("03025128c0bcadea78ad4d7f490e480959aee9ccb55fd200190d8e5e86e5016e0503a9f6835318a0b97c5735c124294ce05e23e50482b5a424a008378490214421f3").unwrap();
+            let pub_nonce = signed.inputs[0]
+                .unknown
+                .get(&raw::Key {
+                    type_value: PSBT_IN_MUSIG2_PUB_NONCE,
+                    key: key.clone(),
+                })
+                .unwrap();
+            assert_eq!("02c64859a0925215ad27be88ec71b0d8d844826d429764ee407c426b7b612804dd03479e3a0f177f70f1bccb9bf055d342bf8386dc174fc0723a151567bd82d6afe7", hex::encode(pub_nonce));
+            let partial_sig = signed.inputs[0]
+                .unknown
+                .get(&raw::Key {
+                    type_value: PSBT_IN_MUSIG2_PARTIAL_SIG,
+                    key,
+                })
+                .unwrap();
+            assert_eq!(
+                "7c7c27d18634ac9071457ecba813e92a12d5af3329dd3d6084c04e90db42c44d",
+                hex::encode(partial_sig)
+            );
+
+            let wpsbt = WrappedPsbt { psbt: signed };
+            let result = wpsbt.parse(Some(&context)).unwrap();
+            let input = result.detail.from.get(0).unwrap();
+            assert_eq!(false, input.need_sign);
+            assert_eq!((1, 3), input.sign_status);
+        }
+
+        // the device can only sign after every other participant has provided a nonce
+        {
+            let psbt_hex = "70736274ff01007d020000000111111111111111111111111111111111111111111111111111111111111111110000000000fdffffff0250c3000000000000160014adfce54f529b2154e3c361bbe3f7d41db063571768bf0000000000002251205004d3b9da163e4cb75692060694b45224698af0392b1b3667d628a45f6fcac6000000004f010488b21e041cf297168000000327b615ed8af0de5eb93f3be49dbcbdd534f54ca288f256be62bc205fc3c331f80396315337b6b29f1de58cc11b8a0c549ff3186e202feb29bc5261091be4f99b211473c5da0a300000800000008000000080030000804f010488b21e04dcbf1083800000031e40df384d87b4ff81346efa6ed8a2bcc796f7399a41759986092169c74c540b0250ebd26b7a2823d988535321953bbb26a8d274723f1c90958f95b710364d1f561444c757a9300000800000008000000080030000804f010488b21e04084c0c568000000326e949e6d8b460db691ccad752311a7802700b037ac1dc3fbf8a6c0f3c28a98d03efaa6dc0aeb6eca5886fae4ad9260e939a0c9611dd471319f85acacf5fb7e2cb14a261afc4300000800000008000000080030000800001012ba086010000000000225120518ea9c0300b99949f829dc68aff0945ae072888ba7eb08243b7169e396725112215c1a9f6835318a0b97c5735c124294ce05e23e50482b5a424a008378490214421f36920025128c0bcadea78ad4d7f490e480959aee9ccb55fd200190d8e5e86e5016e05ac2078884ed3b3620bff1d682c471a29fcc2f14de0023e8fb369b12912e13bb38133ba207b80e1d4a9c7cc7cbb4a2a7fcd181f1e7fe06f2c9984fa3324fea48b7c1462ffba529cc02116025128c0bcadea78ad4d7f490e480959aee9ccb55fd200190d8e5e86e5016e053d01778698328df9030443ccbc656242b2183de8f5de3c2d7bf8acf3b873488791ce73c5da0a300000800000008000000080030000800000000000000000211678884ed3b3620bff1d682c471a29fcc2f14de0023e8fb369b12912e13bb381333d01778698328df9030443ccbc656242b2183de8f5de3c2d7bf8acf3b873488791cea261afc430000080000000800000008003000080000000000000000021167b80e1d4a9c7cc7cbb4a2a7fcd181f1e7fe06f2c9984fa3324fea48b7c1462ff3d01778698328df9030443ccbc656242b2183de8f5de3c2d7bf8acf3b873488791ce44c757a9300000800000008000000080030000800000000000000000011720a9f6835318a0b97c5735c124294ce05e23e50482b5a424a008378490214421f3011820778698328df9030443ccbc656242b2183de8f5de3c2d7bf8acf3b873488791ce221a03a9f6835318a0b97c5735c124294ce05e23e50482b5a424a008378490214421f36303025128c0bcadea78ad4d7f490e480959aee9ccb55fd200190d8e5e86e5016e050378884ed3b3620bff1d682c471a29fcc2f14de0023e8fb369b12912e13bb38133037b80e1d4a9c7cc7cbb4a2a7fcd181f1e7fe06f2c9984fa3324fea48b7c1462ff0000010520e9d454db513f9196f8399a630ad5bc9f34f3b2c4a946b24de0fcd99df4af036201066b00c068208cae20be007cfb4a7b5c062128d33cf42d03a6b9720c2c4b1d9dc6b519c55d65ac20c5019ebae67139d520958aacae2b6ec8b0c8be92d0e5d37d6c5a8aa7a2f0a502ba20f715fabd9e7e4be6318a1ebdbef02945ea1ca50af0016d706380121fe45f603dba529c21078cae20be007cfb4a7b5c062128d33cf42d03a6b9720c2c4b1d9dc6b519c55d653d012aae4a74444d405ef2e83088cf4ea9a9240b28489c1878c33e54653a75ac2b44a261afc43000008000000080000000800300008001000000000000002107c5019ebae67139d520958aacae2b6ec8b0c8be92d0e5d37d6c5a8aa7a2f0a5023d012aae4a74444d405ef2e83088cf4ea9a9240b28489c1878c33e54653a75ac2b4444c757a93000008000000080000000800300008001000000000000002107f715fabd9e7e4be6318a1ebdbef02945ea1ca50af0016d706380121fe45f603d3d012aae4a74444d405ef2e83088cf4ea9a9240b28489c1878c33e54653a75ac2b4473c5da0a30000080000000800000008003000080010000000000000000";
+            let psbt = Psbt::deserialize(&Vec::from_hex(psbt_hex).unwrap()).unwrap();
+            let mut wpsbt = WrappedPsbt { psbt };
+            assert_eq!(Ok(()), wpsbt.check(Left(&context)));
+            assert!(wpsbt.sign(&seed, master_fingerprint).is_err());
+        }
+    }
 }
@@ -19,7 +19,8 @@ pub mod psbt {
             match context {
                 Left(context) => {
                     self.check_inputs(context)?;
-                    self.check_outputs(context)
+                    self.check_outputs(context)?;
+                    self.check_silent_payment_outputs(context.master_fingerprint)
                 }
                 _ => Err(BitcoinError::InvalidParseContext(
                     "mismatched context".to_string(),
@@ -0,0 +1,503 @@
+// Sanity checks of a parsed transaction that never block signing. Each finding is returned as a
+// warning so the UI can show it next to the transaction and let the user decide.
+use alloc::vec::Vec;
+use bitcoin::bip32::{ChildNumber, DerivationPath};
+use bitcoin::secp256k1::Secp256k1;
+use bitcoin::{CompressedPublicKey, PublicKey, ScriptBuf};
+use core::fmt;
+use core::str::FromStr;
+
+use crate::multi_sig::address::create_multi_sig_address_for_wallet;
+use crate::multi_sig::MultiSigFormat;
+use crate::transactions::parsed_tx::{ParseContext, ParsedInput, ParsedTx};
+
+// bitcoind refuses to broadcast transactions paying more than 0.1 BTC in fee by default
+pub const DEFAULT_MAX_FEE: u64 = 10_000_000;
+pub const DEFAULT_MAX_FEE_RATE: u64 = 1_000;
+pub const DEFAULT_CHANGE_GAP_LIMIT: u32 = 1_000;
+pub const DEFAULT_DUST_LIMIT: u64 = 546;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxPolicy {
+    // sats
+    pub max_fee: u64,
+    // sat/vB over the estimated virtual size
+    pub max_fee_rate: u64,
+    // how far a change index may be ahead of the highest index we spend from
+    pub change_gap_limit: u32,
+    // only used for outputs whose script is unknown, others use the standard dust threshold
+    pub dust_limit: u64,
+}
+
+impl Default for TxPolicy {
+    fn default() -> Self {
+        TxPolicy {
+            max_fee: DEFAULT_MAX_FEE,
+            max_fee_rate: DEFAULT_MAX_FEE_RATE,
+            change_gap_limit: DEFAULT_CHANGE_GAP_LIMIT,
+            dust_limit: DEFAULT_DUST_LIMIT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxPolicyWarning {
+    AbsurdFee { fee: u64, max_fee: u64 },
+    HighFeeRate { fee_rate: u64, max_fee_rate: u64 },
+    // the output claims to be our change but its script is not derived from our account
+    ChangeNotDerived { output: usize },
+    // the output claims to be change of the multisig wallet but is not one of its addresses
+    ChangeNotInMultiSig { output: usize },
+    ChangeIndexGap { output: usize, index: u32, gap: u32 },
+    DustOutput { output: usize, value: u64 },
+}
+
+impl TxPolicyWarning {
+    pub fn title(&self) -> &'static str {
+        match self {
+            TxPolicyWarning::AbsurdFee { .. } => "High Fee",
+            TxPolicyWarning::HighFeeRate { .. } => "High Fee Rate",
+            TxPolicyWarning::ChangeNotDerived { .. }
+            | TxPolicyWarning::ChangeNotInMultiSig { .. } => "Unverified Change",
+            TxPolicyWarning::ChangeIndexGap { .. } => "Change Index",
+            TxPolicyWarning::DustOutput { .. } => "Dust Output",
+        }
+    }
+}
+
+impl fmt::Display for TxPolicyWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TxPolicyWarning::AbsurdFee { fee, max_fee } => {
+                write!(
+                    f,
+                    "Fee of {} sats is above the limit of {} sats",
+                    fee, max_fee
+                )
+            }
+            TxPolicyWarning::HighFeeRate {
+                fee_rate,
+                max_fee_rate,
+            } => write!(
+                f,
+                "Fee rate of about {} sat/vB is above the limit of {} sat/vB",
+                fee_rate, max_fee_rate
+            ),
+            TxPolicyWarning::ChangeNotDerived { output } => write!(
+                f,
+                "Output #{} is marked as change but does not belong to this account",
+                output
+            ),
+            TxPolicyWarning::ChangeNotInMultiSig { output } => write!(
+                f,
+                "Output #{} is marked as change but is not an address of this multisig wallet",
+                output
+            ),
+            TxPolicyWarning::ChangeIndexGap { output, index, gap } => write!(
+                f,
+                "Change output #{} uses index {}, {} addresses beyond the inputs",
+                output, index, gap
+            ),
+            TxPolicyWarning::DustOutput { output, value } => write!(
+                f,
+                "Output #{} of {} sats is dust and may not be relayed",
+                output, value
+            ),
+        }
+    }
+}
+
+// account path, chain and index of an address path, e.g. 84'/0'/0' 1 5
+fn split_address_path(path: &str) -> Option<(DerivationPath, u32, u32)> {
+    let path = path.trim_start_matches(['m', 'M']).trim_start_matches('/');
+    let mut parts = path.split('/').collect::<Vec<_>>();
+    let index = parts.pop()?.parse::<u32>().ok()?;
+    let chain = parts.pop()?.parse::<u32>().ok()?;
+    let account = DerivationPath::from_str(&parts.join("/")).ok()?;
+    Some((account, chain, index))
+}
+
+fn parse_script_pubkey(address: &str) -> Option<ScriptBuf> {
+    bitcoin::Address::from_str(address)
+        .ok()
+        .map(|address| address.assume_checked().script_pubkey())
+}
+
+fn derive_single_sig_script(
+    context: &ParseContext,
+    account: &DerivationPath,
+    chain: u32,
+    index: u32,
+) -> Option<ScriptBuf> {
+    let xpub = context.extended_public_keys.get(account)?;
+    let secp = Secp256k1::new();
+    let child = xpub
+        .derive_pub(
+            &secp,
+            &[
+                ChildNumber::from_normal_idx(chain).ok()?,
+                ChildNumber::from_normal_idx(index).ok()?,
+            ],
+        )
+        .ok()?;
+    let key = CompressedPublicKey(child.public_key);
+    match account.as_ref().first()? {
+        ChildNumber::Hardened { index: 44 } => Some(ScriptBuf::new_p2pkh(
+            &PublicKey::new(child.public_key).pubkey_hash(),
+        )),
+        ChildNumber::Hardened { index: 49 } => Some(ScriptBuf::new_p2sh(
+            &ScriptBuf::new_p2wpkh(&key.wpubkey_hash()).script_hash(),
+        )),
+        ChildNumber::Hardened { index: 84 } => Some(ScriptBuf::new_p2wpkh(&key.wpubkey_hash())),
+        ChildNumber::Hardened { index: 86 } => Some(ScriptBuf::new_p2tr(
+            &secp,
+            child.public_key.x_only_public_key().0,
+            None,
+        )),
+        _ => None,
+    }
+}
+
+fn compact_size_len(len: u64) -> u64 {
+    if len < 0xfd {
+        1
+    } else {
+        3
+    }
+}
+
+// weight units of an input, signatures are counted at their largest size,
+// multisig inputs can only be estimated when the wallet config is known
+fn estimate_input_weight(input: &ParsedInput, context: &ParseContext) -> Option<u64> {
+    // outpoint, sequence and the script sig length
+    const BASE: u64 = 41;
+    let script = input.address.as_deref().and_then(parse_script_pubkey);
+    let script = match script {
+        Some(script) => script,
+        // unknown inputs are counted as p2wpkh
+        None => return Some(BASE * 4 + 108),
+    };
+    if !input.is_multisig {
+        return Some(if script.is_p2pkh() {
+            (BASE + 107) * 4
+        } else if script.is_p2sh() {
+            (BASE + 23) * 4 + 108
+        } else if script.is_p2tr() {
+            BASE * 4 + 66
+        } else {
+            BASE * 4 + 108
+        });
+    }
+
+    let config = context.multisig_wallet_config.as_ref()?;
+    let (threshold, total) = (config.threshold as u64, config.total as u64);
+    if script.is_p2tr() {
+        // sortedmulti_a leaf, an empty push for every missing signature and a depth 0 control block
+        let leaf = 35 * total + 1;
+        return Some(
+            BASE * 4
+                + 1
+                + threshold * 65
+                + (total - threshold.min(total))
+                + compact_size_len(leaf)
+                + leaf
+                + 34,
+        );
+    }
+    let redeem_script = 3 + 34 * total;
+    let witness = 1 + 1 + threshold * 73 + compact_size_len(redeem_script) + redeem_script;
+    Some(match MultiSigFormat::from(&config.format).ok() {
+        Some(MultiSigFormat::P2sh) => (BASE + 2 + threshold * 73 + 2 + redeem_script) * 4,
+        _ if script.is_p2sh() => (BASE + 35) * 4 + witness,
+        _ => BASE * 4 + witness,
+    })
+}
+
+fn estimate_output_weight(address: &str) -> u64 {
+    let script_len = parse_script_pubkey(address).map_or(34, |script| script.len() as u64);
+    (8 + compact_size_len(script_len) + script_len) * 4
+}
+
+fn estimate_vsize(tx: &ParsedTx, context: &ParseContext) -> Option<u64> {
+    let inputs = &tx.detail.from;
+    let outputs = &tx.detail.to;
+    // version, lock time and the input and output counts, plus the segwit marker and flag
+    let mut weight =
+        (8 + compact_size_len(inputs.len() as u64) + compact_size_len(outputs.len() as u64)) * 4
+            + 2;
+    weight += inputs
+        .iter()
+        .map(|input| estimate_input_weight(input, context))
+        .sum::<Option<u64>>()?;
+    weight += outputs
+        .iter()
+        .map(|output| estimate_output_weight(&output.address))
+        .sum::<u64>();
+    Some((weight + 3) / 4)
+}
+
+impl ParsedTx {
+    pub fn check_policy(&self, context: &ParseContext, policy: &TxPolicy) -> Vec<TxPolicyWarning> {
+        let mut warnings = Vec::new();
+        let inputs = &self.detail.from;
+        let outputs = &self.detail.to;
+
+        // with ANYONECANPAY the fee is decided by whoever completes the transaction
+        if !inputs.iter().any(|v| v.ecdsa_sighash_type & 0x80 > 0) {
+            let fee = inputs
+                .iter()
+                .map(|v| v.value)
+                .sum::<u64>()
+                .saturating_sub(outputs.iter().map(|v| v.value).sum::<u64>());
+            if fee > policy.max_fee {
+                warnings.push(TxPolicyWarning::AbsurdFee {
+                    fee,
+                    max_fee: policy.max_fee,
+                });
+            }
+            if let Some(vsize) = estimate_vsize(self, context) {
+                let fee_rate = fee / vsize.max(1);
+                if fee_rate > policy.max_fee_rate {
+                    warnings.push(TxPolicyWarning::HighFeeRate {
+                        fee_rate,
+                        max_fee_rate: policy.max_fee_rate,
+                    });
+                }
+            }
+        }
+
+        let highest_input_index = inputs
+            .iter()
+            .filter_map(|v| v.path.as_deref().and_then(split_address_path))
+            .map(|(_, _, index)| index)
+            .max()
+            .unwrap_or(0);
+        for (i, output) in outputs.iter().enumerate() {
+            if let Some(warning) = self.check_change(context, policy, i, highest_input_index) {
+                warnings.push(warning);
+            }
+            if output.address.starts_with("OP_RETURN") {
+                continue;
+            }
+            let dust_limit = parse_script_pubkey(&output.address)
+                .map_or(policy.dust_limit, |script| {
+                    script.minimal_non_dust().to_sat()
+                });
+            if output.value < dust_limit {
+                warnings.push(TxPolicyWarning::DustOutput {
+                    output: i,
+                    value: output.value,
+                });
+            }
+        }
+        warnings
+    }
+
+    fn check_change(
+        &self,
+        context: &ParseContext,
+        policy: &TxPolicy,
+        output_index: usize,
+        highest_input_index: u32,
+    ) -> Option<TxPolicyWarning> {
+        let output = &self.detail.to[output_index];
+        let path = output.path.as_deref().filter(|v| !v.is_empty())?;
+        if output.is_external {
+            return None;
+        }
+        let (account, chain, index) = split_address_path(path)?;
+        // scripts of wallet policy outputs are already matched while parsing
+        if context.wallet_policy.is_none() {
+            if let Some(config) = &context.multisig_wallet_config {
+                let address = create_multi_sig_address_for_wallet(config, chain, index).ok();
+                if address.as_ref() != Some(&output.address) {
+                    return Some(TxPolicyWarning::ChangeNotInMultiSig {
+                        output: output_index,
+                    });
+                }
+            } else if let Some(expected) = derive_single_sig_script(context, &account, chain, index)
+            {
+                if parse_script_pubkey(&output.address) != Some(expected) {
+                    return Some(TxPolicyWarning::ChangeNotDerived {
+                        output: output_index,
+                    });
+                }
+            }
+        }
+        let gap = index.saturating_sub(highest_input_index);
+        if gap > policy.change_gap_limit {
+            return Some(TxPolicyWarning::ChangeIndexGap {
+                output: output_index,
+                index,
+                gap,
+            });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::collections::BTreeMap;
+    use alloc::string::ToString;
+    use bitcoin::bip32::{Fingerprint, Xpub};
+    use bitcoin::psbt::Psbt;
+    use hex::FromHex;
+
+    use super::*;
+    use crate::parsed_tx::TxParser;
+    use crate::transactions::psbt::wrapped_psbt::WrappedPsbt;
+
+    // spends 5992 sats from 84'/1'/0'/0/0 to a single external p2wpkh output of 4000 sats
+    const PSBT_HEX: &str = "70736274ff01005202000000016d41e6873468f85aff76d7709a93b47180ea0784edaac748228d2c474396ca550000000000fdffffff01a00f0000000000001600146623828c1f87be7841a9b1cc360d38ae0a8b6ed0000000000001011f6817000000000000160014d0c4a3ef09e997b6e99e397e518fe3e41a118ca1220602e7ab2537b5d49e970309aae06e9e49f36ce1c9febbd44ec8e0d1cca0b4f9c3191873c5da0a54000080010000800000008000000000000000000000";
+
+    fn prepare() -> (ParsedTx, ParseContext) {
+        let psbt = Psbt::deserialize(&Vec::from_hex(PSBT_HEX).unwrap()).unwrap();
+        let extended_pubkey = Xpub::from_str("xpub6Bm9M1SxZdzL3TxdNV8897FgtTLBgehR1wVNnMyJ5VLRK5n3tFqXxrCVnVQj4zooN4eFSkf6Sma84reWc5ZCXMxPbLXQs3BcaBdTd4YQa3B").unwrap();
+        let mut keys = BTreeMap::new();
+        keys.insert(
+            DerivationPath::from_str("m/84'/1'/0'").unwrap(),
+            extended_pubkey,
+        );
+        let context = ParseContext::new(
+            Fingerprint::from_str("73c5da0a").unwrap(),
+            keys,
+            None,
+            None,
+            None,
+        );
+        let parsed_tx = WrappedPsbt { psbt }.parse(Some(&context)).unwrap();
+        (parsed_tx, context)
+    }
+
+    #[test]
+    fn test_default_policy() {
+        let (parsed_tx, context) = prepare();
+        assert!(parsed_tx.overview.policy_warnings.is_empty());
+        assert!(parsed_tx
+            .check_policy(&context, &TxPolicy::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_fee_policy() {
+        let (parsed_tx, context) = prepare();
+        let policy = TxPolicy {
+            max_fee: 1000,
+            max_fee_rate: 10,
+            ..Default::default()
+        };
+        // 1992 sats over an estimated 110 vB
+        assert_eq!(
+            vec![
+                TxPolicyWarning::AbsurdFee {
+                    fee: 1992,
+                    max_fee: 1000
+                },
+                TxPolicyWarning::HighFeeRate {
+                    fee_rate: 18,
+                    max_fee_rate: 10
+                }
+            ],
+            parsed_tx.check_policy(&context, &policy)
+        );
+
+        let context = ParseContext::new(
+            context.master_fingerprint,
+            context.extended_public_keys,
+            None,
+            None,
+            Some(policy),
+        );
+        let parsed_tx = WrappedPsbt {
+            psbt: Psbt::deserialize(&Vec::from_hex(PSBT_HEX).unwrap()).unwrap(),
+        }
+        .parse(Some(&context))
+        .unwrap();
+        assert_eq!(2, parsed_tx.overview.policy_warnings.len());
+        assert_eq!(
+            "Fee of 1992 sats is above the limit of 1000 sats",
+            parsed_tx.overview.policy_warnings[0].to_string()
+        );
+    }
+
+    #[test]
+    fn test_fee_rate_unknown_multisig() {
+        let (mut parsed_tx, context) = prepare();
+        // without a wallet config the size of a multisig input is unknown
+        parsed_tx.detail.from[0].is_multisig = true;
+        let policy = TxPolicy {
+            max_fee: 1000,
+            max_fee_rate: 10,
+            ..Default::default()
+        };
+        assert_eq!(
+            vec![TxPolicyWarning::AbsurdFee {
+                fee: 1992,
+                max_fee: 1000
+            }],
+            parsed_tx.check_policy(&context, &policy)
+        );
+    }
+
+    #[test]
+    fn test_change_policy() {
+        let (mut parsed_tx, context) = prepare();
+        let input_address = parsed_tx.detail.from[0].address.clone().unwrap();
+        let output = &mut parsed_tx.detail.to[0];
+        output.address = input_address;
+        output.path = Some("84'/1'/0'/0/0".to_string());
+        assert!(parsed_tx
+            .check_policy(&context, &TxPolicy::default())
+            .is_empty());
+
+        // the script of 84'/1'/0'/0/0 claimed as change address #0
+        parsed_tx.detail.to[0].path = Some("84'/1'/0'/1/0".to_string());
+        assert_eq!(
+            vec![TxPolicyWarning::ChangeNotDerived { output: 0 }],
+            parsed_tx.check_policy(&context, &TxPolicy::default())
+        );
+
+        // unknown accounts can not be verified, only the index is checked
+        parsed_tx.detail.to[0].path = Some("84'/1'/5'/1/2000".to_string());
+        assert_eq!(
+            vec![TxPolicyWarning::ChangeIndexGap {
+                output: 0,
+                index: 2000,
+                gap: 2000
+            }],
+            parsed_tx.check_policy(&context, &TxPolicy::default())
+        );
+    }
+
+    #[test]
+    fn test_dust_policy() {
+        let (mut parsed_tx, context) = prepare();
+        parsed_tx.detail.to[0].value = 200;
+        let policy = TxPolicy {
+            max_fee: u64::MAX,
+            max_fee_rate: u64::MAX,
+            ..Default::default()
+        };
+        // p2wpkh outputs are dust below 294 sats
+        assert_eq!(
+            vec![TxPolicyWarning::DustOutput {
+                output: 0,
+                value: 200
+            }],
+            parsed_tx.check_policy(&context, &policy)
+        );
+        parsed_tx.detail.to[0].value = 294;
+        assert!(parsed_tx.check_policy(&context, &policy).is_empty());
+    }
+
+    #[test]
+    fn test_split_address_path() {
+        let (account, chain, index) = split_address_path("M/84'/0'/0'/1/5").unwrap();
+        assert_eq!(DerivationPath::from_str("m/84'/0'/0'").unwrap(), account);
+        assert_eq!((1, 5), (chain, index));
+        assert!(split_address_path("84'/0'/0'/1'/5").is_none());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::errors::{EthereumError, Result};
+use crate::selector_registry::parse_contract_data_by_selector;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use alloc::{format, vec};
@@ -10,7 +11,9 @@ use {hex, serde_json};
 impl_public_struct!(ContractData {
     contract_name: String,
     method_name: String,
-    params: Vec<ContractMethodParam>
+    params: Vec<ContractMethodParam>,
+    // true when the method was resolved from the offline selector registry instead of a provided ABI
+    is_signature_inferred: bool
 });
 
 impl_public_struct!(ContractMethodParam {
@@ -20,6 +23,9 @@ impl_public_struct!(ContractMethodParam {
 });
 
 pub fn parse_contract_data(input: Vec<u8>, contract_str: String) -> Result<ContractData> {
+    if contract_str.is_empty() {
+        return parse_contract_data_by_selector(input);
+    }
     let contract_json: Value = serde_json::from_str(contract_str.as_str())
         .map_err(|_e| EthereumError::InvalidContractABI)?;
     #[allow(unused_assignments)] //stupid compiler
@@ -43,7 +49,12 @@ pub fn parse_contract_data(input: Vec<u8>, contract_str: String) -> Result<Contr
             let method_name = function.name.clone();
             let params = _parse_by_function(signature, data, function.clone());
             if let Some(Ok(_params)) = params {
-                return Ok(ContractData::new(contract_name, method_name, _params));
+                return Ok(ContractData::new(
+                    contract_name,
+                    method_name,
+                    _params,
+                    false,
+                ));
             } else if let Some(Err(_e)) = params {
                 return Err(_e);
             }
@@ -71,7 +82,12 @@ pub fn parse_method_data(
     let (signature, data) = input.split_at(4);
     let params = _parse_by_function(signature, data, function.clone());
     match params {
-        Some(Ok(_params)) => Ok(ContractData::new(contract_name, method_name, _params)),
+        Some(Ok(_params)) => Ok(ContractData::new(
+            contract_name,
+            method_name,
+            _params,
+            false,
+        )),
         Some(Err(e)) => Err(e),
         None => Err(EthereumError::DecodeContractDataError(format!(
             "input method selector [{}] not match function ({}) signature [{}]",
@@ -82,7 +98,7 @@ pub fn parse_method_data(
     }
 }
 
-fn _parse_by_function(
+pub(crate) fn _parse_by_function(
     signature: &[u8],
     data: &[u8],
     function: Function,
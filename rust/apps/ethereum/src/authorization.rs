@@ -0,0 +1,212 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use bitcoin::secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+use bitcoin::secp256k1::{Message, Secp256k1};
+use ethereum_types::{H160, U256};
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+
+use crate::address::{checksum_address, generate_address};
+use crate::crypto::keccak256;
+use crate::errors::{EthereumError, Result};
+
+// EIP-7702 authorization payloads are signed as keccak256(MAGIC || rlp([chain_id, address, nonce]))
+pub const AUTHORIZATION_MAGIC: u8 = 0x05;
+
+#[derive(Clone)]
+pub struct Authorization {
+    pub chain_id: U256,
+    pub address: H160,
+    pub nonce: U256,
+}
+
+impl Authorization {
+    pub fn decode_raw(bytes: &[u8]) -> Result<Authorization> {
+        // sign_data should starts with 0x05
+        match bytes.first() {
+            Some(&AUTHORIZATION_MAGIC) => Ok(rlp::decode(&bytes[1..])?),
+            _ => Err(EthereumError::InvalidTransaction),
+        }
+    }
+
+    pub fn signing_data(&self) -> Vec<u8> {
+        let mut data = Vec::from([AUTHORIZATION_MAGIC]);
+        data.extend_from_slice(&rlp::encode(self));
+        data
+    }
+}
+
+impl Decodable for Authorization {
+    fn decode(rlp: &Rlp) -> core::result::Result<Self, DecoderError> {
+        if rlp.item_count()? != 3 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            address: rlp.val_at(1)?,
+            nonce: rlp.val_at(2)?,
+        })
+    }
+}
+
+impl Encodable for Authorization {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(3);
+        s.append(&self.chain_id);
+        s.append(&self.address);
+        s.append(&self.nonce);
+    }
+}
+
+#[derive(Clone)]
+pub struct SignedAuthorization {
+    pub authorization: Authorization,
+    pub y_parity: u8,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl SignedAuthorization {
+    // the authority is never sent along with the tuple, it has to be recovered from the signature
+    pub fn recover_authority(&self) -> Option<String> {
+        let hash = keccak256(&self.authorization.signing_data());
+        let message = Message::from_digest_slice(&hash).ok()?;
+        let mut rs = [0u8; 64];
+        self.r.to_big_endian(&mut rs[..32]);
+        self.s.to_big_endian(&mut rs[32..]);
+        let rec_id = RecoveryId::from_i32(self.y_parity as i32).ok()?;
+        let signature = RecoverableSignature::from_compact(&rs, rec_id).ok()?;
+        let key = Secp256k1::verification_only()
+            .recover_ecdsa(&message, &signature)
+            .ok()?;
+        generate_address(key).ok()
+    }
+}
+
+impl Decodable for SignedAuthorization {
+    fn decode(rlp: &Rlp) -> core::result::Result<Self, DecoderError> {
+        if rlp.item_count()? != 6 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        let y_parity: u8 = rlp.val_at(3)?;
+        if y_parity > 1 {
+            return Err(DecoderError::Custom("invalid authorization y_parity"));
+        }
+        Ok(Self {
+            authorization: Authorization {
+                chain_id: rlp.val_at(0)?,
+                address: rlp.val_at(1)?,
+                nonce: rlp.val_at(2)?,
+            },
+            y_parity,
+            r: rlp.val_at(4)?,
+            s: rlp.val_at(5)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ParsedAuthorization {
+    pub chain_id: String,
+    pub nonce: String,
+    // the contract whose code the authority account will execute after this authorization
+    pub delegate: String,
+    // None when the signature of the authorization tuple can not be recovered
+    pub authority: Option<String>,
+    // chain_id 0 makes the authorization replayable on every chain
+    pub is_any_chain: bool,
+    // the authorization is bound to another chain than the one it is signed for
+    pub is_chain_mismatch: bool,
+    // delegating to the zero address clears the existing delegation
+    pub is_revocation: bool,
+}
+
+impl ParsedAuthorization {
+    pub fn from_authorization(
+        authorization: Authorization,
+        authority: Option<String>,
+        chain_id: Option<u64>,
+    ) -> Result<Self> {
+        let is_chain_mismatch = match chain_id {
+            Some(chain_id) => {
+                !authorization.chain_id.is_zero() && authorization.chain_id != U256::from(chain_id)
+            }
+            None => false,
+        };
+        Ok(Self {
+            chain_id: authorization.chain_id.to_string(),
+            nonce: authorization.nonce.to_string(),
+            delegate: checksum_address(&hex::encode(authorization.address))?,
+            authority,
+            is_any_chain: authorization.chain_id.is_zero(),
+            is_chain_mismatch,
+            is_revocation: authorization.address.is_zero(),
+        })
+    }
+
+    pub fn from_signed_authorization(value: SignedAuthorization, chain_id: u64) -> Result<Self> {
+        let authority = value.recover_authority();
+        Self::from_authorization(value.authorization, authority, Some(chain_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate std;
+
+    #[test]
+    fn test_decode_authorization() {
+        let sign_data = hex::decode("05d7019463c0c19a282a1b52b07dd5a65b58948a07dae32b07").unwrap();
+        let authorization = Authorization::decode_raw(&sign_data).unwrap();
+        assert_eq!(U256::from(1), authorization.chain_id);
+        assert_eq!(U256::from(7), authorization.nonce);
+        assert_eq!(sign_data, authorization.signing_data());
+
+        let parsed =
+            ParsedAuthorization::from_authorization(authorization.clone(), None, Some(1)).unwrap();
+        assert_eq!(
+            "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B",
+            parsed.delegate
+        );
+        assert!(!parsed.is_any_chain);
+        assert!(!parsed.is_chain_mismatch);
+        assert!(!parsed.is_revocation);
+
+        let parsed =
+            ParsedAuthorization::from_authorization(authorization, None, Some(56)).unwrap();
+        assert!(parsed.is_chain_mismatch);
+
+        let sign_data = hex::decode("d7809463c0c19a282a1b52b07dd5a65b58948a07dae32b07").unwrap();
+        let authorization: Authorization = rlp::decode(&sign_data).unwrap();
+        let parsed =
+            ParsedAuthorization::from_authorization(authorization, None, Some(56)).unwrap();
+        assert!(parsed.is_any_chain);
+        assert!(!parsed.is_chain_mismatch);
+
+        let sign_data = hex::decode("02d7019463c0c19a282a1b52b07dd5a65b58948a07dae32b07").unwrap();
+        assert!(Authorization::decode_raw(&sign_data).is_err());
+    }
+
+    #[test]
+    fn test_recover_authority() {
+        let tuple = hex::decode("f85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b0780a0b8b42b7bbdad2be7d50ec724a780bd7ad57a5794f8f795da12123438e59de9aba040c1d675546cccb0b3ca5e603b28800ad11b1ed4548f85ac7f8bf35a089e1220").unwrap();
+        let authorization: SignedAuthorization = rlp::decode(&tuple).unwrap();
+        let parsed = ParsedAuthorization::from_signed_authorization(authorization, 1).unwrap();
+        assert_eq!(
+            Some("0x9858EfFD232B4033E47d90003D41EC34EcaEda94".to_string()),
+            parsed.authority
+        );
+        assert_eq!("1", parsed.chain_id);
+        assert_eq!("7", parsed.nonce);
+        assert!(!parsed.is_chain_mismatch);
+    }
+
+    #[test]
+    fn test_reject_invalid_y_parity() {
+        // the y_parity of the tuple above replaced by 27
+        let tuple = hex::decode("f85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b071ba0b8b42b7bbdad2be7d50ec724a780bd7ad57a5794f8f795da12123438e59de9aba040c1d675546cccb0b3ca5e603b28800ad11b1ed4548f85ac7f8bf35a089e1220").unwrap();
+        assert!(rlp::decode::<SignedAuthorization>(&tuple).is_err());
+    }
+}
@@ -1,9 +1,378 @@
-use crate::erc20::parse_erc20_approval;
+use crate::erc20::{parse_erc20_approval, ParsedErc20Approval};
+use crate::normalizer::normalize_value;
+use crate::selector_registry::{lookup, parse_named_tokens_by_selector};
+use alloc::boxed::Box;
 use alloc::format;
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use ethabi::Token;
+use ethereum_types::U256;
 
-use crate::{errors::EthereumError, structs::ParsedEthereumTransaction};
+use crate::{
+    errors::EthereumError,
+    structs::{EthereumTransactionType, ParsedEthereumTransaction},
+};
+
+pub trait BatchTxRule {
+    fn name(&self) -> &'static str;
+    fn check(&self, txs: &[ParsedEthereumTransaction]) -> Result<(), EthereumError>;
+}
+
+// A batch is accepted when every invariant holds and at least one shape recognises it.
+// Invariants apply to any batch, shapes describe what the batch is meant to do (swap, bridge...).
+pub struct BatchTxPolicy {
+    invariants: Vec<Box<dyn BatchTxRule>>,
+    shapes: Vec<Box<dyn BatchTxRule>>,
+}
+
+impl BatchTxPolicy {
+    pub fn new() -> Self {
+        Self {
+            invariants: Vec::new(),
+            shapes: Vec::new(),
+        }
+    }
+
+    pub fn register_invariant(mut self, rule: Box<dyn BatchTxRule>) -> Self {
+        self.invariants.push(rule);
+        self
+    }
+
+    pub fn register_shape(mut self, rule: Box<dyn BatchTxRule>) -> Self {
+        self.shapes.push(rule);
+        self
+    }
+
+    // returns the name of the shape that matched
+    pub fn check(&self, txs: &[ParsedEthereumTransaction]) -> Result<&'static str, EthereumError> {
+        if txs.is_empty() {
+            return Err(EthereumError::InvalidBatchTransaction(
+                "empty batch".to_string(),
+            ));
+        }
+        for rule in self.invariants.iter() {
+            rule.check(txs)?;
+        }
+        let mut errors = Vec::new();
+        for shape in self.shapes.iter() {
+            match shape.check(txs) {
+                Ok(_) => return Ok(shape.name()),
+                Err(e) => errors.push(format!("{}: {}", shape.name(), e)),
+            }
+        }
+        Err(EthereumError::InvalidBatchTransaction(format!(
+            "no matching batch shape, {}",
+            errors.join("; ")
+        )))
+    }
+}
+
+impl Default for BatchTxPolicy {
+    fn default() -> Self {
+        Self::new()
+            .register_invariant(Box::new(TransactionTypeRule))
+            .register_invariant(Box::new(SameSenderRule))
+            .register_invariant(Box::new(ContiguousNonceRule))
+            .register_invariant(Box::new(ApprovalSpenderRule))
+            .register_invariant(Box::new(ApprovalAmountRule))
+            .register_invariant(Box::new(SubCallAmountRule))
+            .register_shape(Box::new(SwapRule))
+    }
+}
+
+// blob and set code transactions carry payloads none of the rules look at, so a batch only
+// accepts plain calls
+pub struct TransactionTypeRule;
+
+impl BatchTxRule for TransactionTypeRule {
+    fn name(&self) -> &'static str {
+        "transaction_type"
+    }
+
+    fn check(&self, txs: &[ParsedEthereumTransaction]) -> Result<(), EthereumError> {
+        for tx in txs {
+            let plain = matches!(
+                tx.tx_type,
+                EthereumTransactionType::Legacy
+                    | EthereumTransactionType::AccessList
+                    | EthereumTransactionType::FeeMarket
+            );
+            if !plain || !tx.authorization_list.is_empty() || !tx.blob_versioned_hashes.is_empty() {
+                return Err(EthereumError::InvalidBatchTransaction(format!(
+                    "unsupported transaction type in batch: {}",
+                    tx.tx_type.get_type_name()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct SameSenderRule;
+
+impl BatchTxRule for SameSenderRule {
+    fn name(&self) -> &'static str {
+        "same_sender"
+    }
+
+    fn check(&self, txs: &[ParsedEthereumTransaction]) -> Result<(), EthereumError> {
+        let first = &txs[0];
+        for tx in txs.iter().skip(1) {
+            if tx.chain_id != first.chain_id {
+                return Err(EthereumError::InvalidBatchTransaction(format!(
+                    "chain id mismatch: {} != {}",
+                    tx.chain_id, first.chain_id
+                )));
+            }
+            if !tx.from.eq_ignore_ascii_case(&first.from) {
+                return Err(EthereumError::InvalidBatchTransaction(format!(
+                    "from address mismatch: {} != {}",
+                    tx.from, first.from
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct ContiguousNonceRule;
+
+impl BatchTxRule for ContiguousNonceRule {
+    fn name(&self) -> &'static str {
+        "contiguous_nonce"
+    }
+
+    fn check(&self, txs: &[ParsedEthereumTransaction]) -> Result<(), EthereumError> {
+        for pair in txs.windows(2) {
+            if pair[0].nonce.checked_add(1) != Some(pair[1].nonce) {
+                return Err(EthereumError::InvalidBatchTransaction(format!(
+                    "nonce {} does not follow {}",
+                    pair[1].nonce, pair[0].nonce
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+// an approval is only expected when a later transaction of the batch spends it
+pub struct ApprovalSpenderRule;
+
+impl BatchTxRule for ApprovalSpenderRule {
+    fn name(&self) -> &'static str {
+        "approval_spender"
+    }
+
+    fn check(&self, txs: &[ParsedEthereumTransaction]) -> Result<(), EthereumError> {
+        for (index, tx) in txs.iter().enumerate() {
+            let approval = match parse_approval(tx) {
+                Some(approval) => approval,
+                None => continue,
+            };
+            // revoking is always harmless
+            if approval.value == "0" {
+                continue;
+            }
+            let spent = txs[index + 1..]
+                .iter()
+                .any(|later| later.to.eq_ignore_ascii_case(&approval.spender));
+            if !spent {
+                return Err(EthereumError::InvalidBatchTransaction(format!(
+                    "approved spender {} is not called by a later transaction",
+                    approval.spender
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+// approvals must not move ETH and a revoke must be followed by a new approval of the same token and spender
+pub struct ApprovalAmountRule;
+
+impl BatchTxRule for ApprovalAmountRule {
+    fn name(&self) -> &'static str {
+        "approval_amount"
+    }
+
+    fn check(&self, txs: &[ParsedEthereumTransaction]) -> Result<(), EthereumError> {
+        for (index, tx) in txs.iter().enumerate() {
+            let approval = match parse_approval(tx) {
+                Some(approval) => approval,
+                None => continue,
+            };
+            if !is_zero_value(&tx.value) {
+                return Err(EthereumError::InvalidBatchTransaction(format!(
+                    "approval transaction carries value: {}",
+                    tx.value
+                )));
+            }
+            if approval.value != "0" {
+                continue;
+            }
+            let reapproved = txs[index + 1..].iter().any(|later| {
+                later.to.eq_ignore_ascii_case(&tx.to)
+                    && parse_approval(later).map_or(false, |a| {
+                        a.value != "0" && a.spender.eq_ignore_ascii_case(&approval.spender)
+                    })
+            });
+            if !reapproved {
+                return Err(EthereumError::InvalidBatchTransaction(format!(
+                    "revoke of {} is not followed by a new approval",
+                    approval.spender
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+// the amount a decoded call moves must match what is shown for it: a native asset call has to
+// carry exactly that value and a token call can not pull more than the batch approved
+pub struct SubCallAmountRule;
+
+impl BatchTxRule for SubCallAmountRule {
+    fn name(&self) -> &'static str {
+        "sub_call_amount"
+    }
+
+    fn check(&self, txs: &[ParsedEthereumTransaction]) -> Result<(), EthereumError> {
+        for (index, tx) in txs.iter().enumerate() {
+            let (asset, amount) = match decode_asset_amount(tx)? {
+                Some(v) => v,
+                None => continue,
+            };
+            if asset == NATIVE_ASSET {
+                if normalize_value(amount) != tx.value {
+                    return Err(EthereumError::InvalidBatchTransaction(format!(
+                        "decoded amount {} does not match the transaction value {}",
+                        normalize_value(amount),
+                        tx.value
+                    )));
+                }
+                continue;
+            }
+            if !is_zero_value(&tx.value) {
+                return Err(EthereumError::InvalidBatchTransaction(format!(
+                    "token call carries value: {}",
+                    tx.value
+                )));
+            }
+            // the latest approval of this batch is the allowance the call spends
+            let approval = txs[..index]
+                .iter()
+                .rev()
+                .filter(|earlier| earlier.to.eq_ignore_ascii_case(&asset))
+                .filter_map(parse_approval)
+                .find(|approval| approval.spender.eq_ignore_ascii_case(&tx.to));
+            if let Some(approval) = approval {
+                let approved = U256::from_dec_str(&approval.value).map_err(|_| {
+                    EthereumError::InvalidBatchTransaction(format!(
+                        "invalid approval amount: {}",
+                        approval.value
+                    ))
+                })?;
+                if amount > approved {
+                    return Err(EthereumError::InvalidBatchTransaction(format!(
+                        "call spends {} of {} but only {} is approved",
+                        amount, asset, approved
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct SwapRule;
+
+impl BatchTxRule for SwapRule {
+    fn name(&self) -> &'static str {
+        "swap"
+    }
+
+    fn check(&self, txs: &[ParsedEthereumTransaction]) -> Result<(), EthereumError> {
+        rule_swap(txs.to_vec())
+    }
+}
+
+fn parse_approval(tx: &ParsedEthereumTransaction) -> Option<ParsedErc20Approval> {
+    parse_erc20_approval(&tx.input, 0).ok()
+}
+
+// routers use the zero address for the native asset
+const NATIVE_ASSET: &str = "0x0000000000000000000000000000000000000000";
+
+// token swaps whose spent amount has to be found, a batch calling one that can not be decoded fails
+const TOKEN_SWAP_METHODS: &[&str] = &[
+    "swapExactTokensForETH",
+    "swapExactTokensForTokens",
+    "swapTokensForExactTokens",
+    "exactInputSingle",
+    "exactInput",
+    "exactOutputSingle",
+];
+
+fn is_token_swap(input: &[u8]) -> bool {
+    input.len() >= 4
+        && lookup(&input[..4]).iter().any(|signature| {
+            signature
+                .split_once('(')
+                .map_or(false, |(name, _)| TOKEN_SWAP_METHODS.contains(&name))
+        })
+}
+
+// only calls that name both the asset and the amount can be checked, tuple components are
+// searched as well and a swap path starts with the asset being spent
+fn decode_asset_amount(
+    tx: &ParsedEthereumTransaction,
+) -> Result<Option<(String, U256)>, EthereumError> {
+    let input = match hex::decode(&tx.input) {
+        Ok(input) => input,
+        Err(_) => return Ok(None),
+    };
+    let asset_amount = parse_named_tokens_by_selector(&input)
+        .ok()
+        .and_then(|(_, tokens)| find_asset_amount(&tokens));
+    match asset_amount {
+        Some((asset, amount)) => Ok(Some((format!("0x{}", hex::encode(asset)), amount))),
+        None if is_token_swap(&input) => Err(EthereumError::InvalidBatchTransaction(format!(
+            "can not decode the amount spent by swap call to {}",
+            tx.to
+        ))),
+        None => Ok(None),
+    }
+}
+
+fn find_asset_amount(tokens: &[(String, Token)]) -> Option<([u8; 20], U256)> {
+    let find = |names: &[&str]| {
+        tokens
+            .iter()
+            .find(|(name, _)| names.contains(&name.as_str()))
+            .map(|(_, token)| token)
+    };
+    let asset = match find(&["asset", "token", "tokenIn", "path"])? {
+        Token::Address(address) => *address.as_fixed_bytes(),
+        Token::Array(path) => match path.first()? {
+            Token::Address(address) => *address.as_fixed_bytes(),
+            _ => return None,
+        },
+        Token::Bytes(path) if path.len() >= 20 => {
+            let mut address = [0u8; 20];
+            address.copy_from_slice(&path[..20]);
+            address
+        }
+        _ => return None,
+    };
+    match find(&["amount", "amountIn", "amountInMax", "amountInMaximum"])? {
+        Token::Uint(amount) => Some((asset, *amount)),
+        _ => None,
+    }
+}
+
+fn is_zero_value(value: &str) -> bool {
+    value.chars().all(|c| c == '0' || c == '.')
+}
 
 pub fn rule_swap(txs: Vec<ParsedEthereumTransaction>) -> Result<(), EthereumError> {
     //
@@ -42,3 +411,204 @@ pub fn rule_swap(txs: Vec<ParsedEthereumTransaction>) -> Result<(), EthereumErro
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::EthereumTransactionType;
+
+    extern crate std;
+
+    const TOKEN: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+    const ROUTER: &str = "0x1111111254eeb25477b68fb85ed929f73a960582";
+
+    fn tx(nonce: u32, to: &str, input: &str) -> ParsedEthereumTransaction {
+        ParsedEthereumTransaction {
+            tx_type: EthereumTransactionType::FeeMarket,
+            nonce,
+            chain_id: 1,
+            from: "0x9858EfFD232B4033E47d90003D41EC34EcaEda94".to_string(),
+            to: to.to_string(),
+            value: "0".to_string(),
+            input: input.to_string(),
+            gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            max_fee: None,
+            max_priority: None,
+            gas_limit: "21000".to_string(),
+            max_txn_fee: "0".to_string(),
+            access_list: Vec::new(),
+            authorization_list: Vec::new(),
+            max_fee_per_blob_gas: None,
+            max_blob_fee: None,
+            blob_versioned_hashes: Vec::new(),
+        }
+    }
+
+    fn approve(spender: &str, amount: u64) -> String {
+        format!(
+            "095ea7b3{:0>64}{:0>64x}",
+            spender.trim_start_matches("0x"),
+            amount
+        )
+    }
+
+    fn supply(asset: &str, amount: u64) -> String {
+        format!(
+            "617ba037{:0>64}{:0>64x}{:0>64}{:0>64}",
+            asset.trim_start_matches("0x"),
+            amount,
+            "9858effd232b4033e47d90003d41ec34ecaeda94",
+            0
+        )
+    }
+
+    fn exact_input_single(token_in: &str, amount_in: u64) -> String {
+        format!(
+            "414bf389{:0>64}{:0>64}{:0>64x}{:0>64}{:0>64x}{:0>64x}{:0>64x}{:0>64x}",
+            token_in.trim_start_matches("0x"),
+            "c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            3000,
+            "9858effd232b4033e47d90003d41ec34ecaeda94",
+            1717512748,
+            amount_in,
+            0,
+            0
+        )
+    }
+
+    #[test]
+    fn test_swap_batch() {
+        let policy = BatchTxPolicy::default();
+        let txs = [
+            tx(5, TOKEN, &approve(ROUTER, 0)),
+            tx(6, TOKEN, &approve(ROUTER, 1000000)),
+            tx(7, ROUTER, "12aa3caf"),
+        ];
+        assert_eq!("swap", policy.check(&txs).unwrap());
+        assert_eq!("swap", policy.check(&txs[1..]).unwrap());
+    }
+
+    #[test]
+    fn test_invariants() {
+        let policy = BatchTxPolicy::default();
+
+        let txs = [
+            tx(5, TOKEN, &approve(ROUTER, 1000000)),
+            tx(7, ROUTER, "12aa3caf"),
+        ];
+        assert!(policy.check(&txs).is_err());
+
+        let mut other_chain = tx(6, ROUTER, "12aa3caf");
+        other_chain.chain_id = 56;
+        let txs = [tx(5, TOKEN, &approve(ROUTER, 1000000)), other_chain];
+        assert!(policy.check(&txs).is_err());
+
+        // the approved spender never gets called
+        let txs = [
+            tx(5, TOKEN, &approve(ROUTER, 1000000)),
+            tx(6, TOKEN, "12aa3caf"),
+        ];
+        assert!(policy.check(&txs).is_err());
+
+        // a revoke that is not followed by a new approval
+        let txs = [
+            tx(5, TOKEN, &approve(ROUTER, 0)),
+            tx(6, TOKEN, "12aa3caf"),
+            tx(7, ROUTER, "12aa3caf"),
+        ];
+        assert!(policy.check(&txs).is_err());
+    }
+
+    #[test]
+    fn test_sub_call_amount() {
+        let policy = BatchTxPolicy::default();
+        let txs = [
+            tx(5, TOKEN, &approve(ROUTER, 1000000)),
+            tx(6, ROUTER, &supply(TOKEN, 1000000)),
+        ];
+        assert_eq!("swap", policy.check(&txs).unwrap());
+
+        // pulls more than the approval shown on the first page
+        let txs = [
+            tx(5, TOKEN, &approve(ROUTER, 1000000)),
+            tx(6, ROUTER, &supply(TOKEN, 2000000)),
+        ];
+        assert!(policy.check(&txs).is_err());
+
+        // the decoded native amount differs from the value shown
+        let mut native = tx(5, ROUTER, &supply(NATIVE_ASSET, 1000000000000000000));
+        native.value = "0.5".to_string();
+        assert!(policy.check(&[native.clone()]).is_err());
+        native.value = "1".to_string();
+        assert_eq!("swap", policy.check(&[native]).unwrap());
+    }
+
+    #[test]
+    fn test_sub_call_amount_in_tuple() {
+        let policy = BatchTxPolicy::default();
+        let txs = [
+            tx(5, TOKEN, &approve(ROUTER, 1000000)),
+            tx(6, ROUTER, &exact_input_single(TOKEN, 1000000)),
+        ];
+        assert_eq!("swap", policy.check(&txs).unwrap());
+
+        let txs = [
+            tx(5, TOKEN, &approve(ROUTER, 1000000)),
+            tx(6, ROUTER, &exact_input_single(TOKEN, 2000000)),
+        ];
+        assert!(policy.check(&txs).is_err());
+
+        // a known swap selector whose arguments can not be decoded
+        let txs = [
+            tx(5, TOKEN, &approve(ROUTER, 1000000)),
+            tx(6, ROUTER, &exact_input_single(TOKEN, 1000000)[..72]),
+        ];
+        assert!(policy.check(&txs).is_err());
+    }
+
+    #[test]
+    fn test_transaction_type() {
+        let policy = BatchTxPolicy::default();
+        let mut set_code = tx(6, ROUTER, "12aa3caf");
+        set_code.tx_type = EthereumTransactionType::SetCode;
+        let txs = [tx(5, TOKEN, &approve(ROUTER, 1000000)), set_code];
+        assert!(policy.check(&txs).is_err());
+
+        let mut blob = tx(6, ROUTER, "12aa3caf");
+        blob.blob_versioned_hashes.push(format!("0x01{:0>62}", 0));
+        let txs = [tx(5, TOKEN, &approve(ROUTER, 1000000)), blob];
+        assert!(policy.check(&txs).is_err());
+
+        let mut legacy = tx(6, ROUTER, "12aa3caf");
+        legacy.tx_type = EthereumTransactionType::Legacy;
+        let txs = [tx(5, TOKEN, &approve(ROUTER, 1000000)), legacy];
+        assert_eq!("swap", policy.check(&txs).unwrap());
+    }
+
+    struct AnyRule;
+
+    impl BatchTxRule for AnyRule {
+        fn name(&self) -> &'static str {
+            "any"
+        }
+
+        fn check(&self, _txs: &[ParsedEthereumTransaction]) -> Result<(), EthereumError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_register_shape() {
+        let txs = [
+            tx(5, TOKEN, &approve(ROUTER, 1000000)),
+            tx(6, ROUTER, "12aa3caf"),
+            tx(7, ROUTER, "12aa3caf"),
+            tx(8, ROUTER, "12aa3caf"),
+        ];
+        assert!(BatchTxPolicy::default().check(&txs).is_err());
+        let policy = BatchTxPolicy::default().register_shape(Box::new(AnyRule));
+        assert_eq!("any", policy.check(&txs).unwrap());
+    }
+}
@@ -1,11 +1,15 @@
+use crate::errors::{EthereumError, Result};
 use crate::normalizer::{normalize_price, normalize_value};
-use crate::structs::TransactionAction;
+use crate::structs::{
+    checked_fee, checked_u32, checked_u64, decode_trailing_access_list, AccessList,
+    ParsedAccessListItem, TransactionAction,
+};
 use crate::traits::BaseTransaction;
 use crate::{impl_base_transaction, Bytes};
 use alloc::format;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
-use core::ops::Mul;
 use ethereum_types::U256;
 
 use rlp::{Decodable, DecoderError, Rlp};
@@ -19,12 +23,11 @@ pub struct EIP1559Transaction {
     pub action: TransactionAction,
     pub value: U256,
     pub input: Bytes,
-    // we do not care access_list currently
-    // pub access_list: AccessList,
+    pub access_list: AccessList,
 }
 
 impl EIP1559Transaction {
-    pub fn decode_raw(bytes: &[u8]) -> Result<EIP1559Transaction, DecoderError> {
+    pub fn decode_raw(bytes: &[u8]) -> core::result::Result<EIP1559Transaction, DecoderError> {
         rlp::decode(bytes)
     }
 }
@@ -32,7 +35,7 @@ impl EIP1559Transaction {
 impl_base_transaction!(EIP1559Transaction);
 
 impl Decodable for EIP1559Transaction {
-    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+    fn decode(rlp: &Rlp) -> core::result::Result<Self, DecoderError> {
         Ok(Self {
             chain_id: rlp.val_at(0)?,
             nonce: rlp.val_at(1)?,
@@ -42,6 +45,7 @@ impl Decodable for EIP1559Transaction {
             action: rlp.val_at(5)?,
             value: rlp.val_at(6)?,
             input: rlp.val_at(7)?,
+            access_list: decode_trailing_access_list(rlp, 8)?,
         })
     }
 }
@@ -58,22 +62,34 @@ pub struct ParsedEIP1559Transaction {
     pub(crate) max_txn_fee: String,
     pub(crate) max_fee: String,
     pub(crate) max_priority: String,
+    pub(crate) access_list: Vec<ParsedAccessListItem>,
 }
 
-impl From<EIP1559Transaction> for ParsedEIP1559Transaction {
-    fn from(value: EIP1559Transaction) -> Self {
-        Self {
+impl TryFrom<EIP1559Transaction> for ParsedEIP1559Transaction {
+    type Error = EthereumError;
+
+    fn try_from(value: EIP1559Transaction) -> Result<Self> {
+        let max_fee = checked_fee(value.max_fee_per_gas, value.gas_limit)?;
+        Ok(Self {
             chain_id: value.chain_id,
-            nonce: value.nonce.as_u32(),
-            max_priority_fee_per_gas: normalize_price(value.max_priority_fee_per_gas.as_u64()),
-            max_fee_per_gas: normalize_price(value.max_fee_per_gas.as_u64()),
+            nonce: checked_u32(value.nonce)?,
+            max_priority_fee_per_gas: normalize_price(checked_u64(value.max_priority_fee_per_gas)?),
+            max_fee_per_gas: normalize_price(checked_u64(value.max_fee_per_gas)?),
             gas_limit: value.gas_limit.to_string(),
             to: format!("0x{}", hex::encode(value.get_to())),
             value: normalize_value(value.value),
             input: hex::encode(value.input),
-            max_txn_fee: normalize_value(value.max_fee_per_gas.mul(value.gas_limit)),
-            max_priority: normalize_value(value.max_priority_fee_per_gas.mul(value.gas_limit)),
-            max_fee: normalize_value(value.max_fee_per_gas.mul(value.gas_limit)),
-        }
+            max_txn_fee: normalize_value(max_fee),
+            max_priority: normalize_value(checked_fee(
+                value.max_priority_fee_per_gas,
+                value.gas_limit,
+            )?),
+            max_fee: normalize_value(max_fee),
+            access_list: value
+                .access_list
+                .into_iter()
+                .map(ParsedAccessListItem::from)
+                .collect(),
+        })
     }
 }
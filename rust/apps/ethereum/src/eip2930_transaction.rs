@@ -0,0 +1,83 @@
+use crate::errors::{EthereumError, Result};
+use crate::normalizer::{normalize_price, normalize_value};
+use crate::structs::{
+    checked_fee, checked_u32, checked_u64, decode_trailing_access_list, AccessList,
+    ParsedAccessListItem, TransactionAction,
+};
+use crate::traits::BaseTransaction;
+use crate::{impl_base_transaction, Bytes};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use ethereum_types::U256;
+
+use rlp::{Decodable, DecoderError, Rlp};
+
+pub struct EIP2930Transaction {
+    pub chain_id: u64,
+    pub nonce: U256,
+    pub gas_price: U256,
+    pub gas_limit: U256,
+    pub action: TransactionAction,
+    pub value: U256,
+    pub input: Bytes,
+    pub access_list: AccessList,
+}
+
+impl EIP2930Transaction {
+    pub fn decode_raw(bytes: &[u8]) -> core::result::Result<EIP2930Transaction, DecoderError> {
+        rlp::decode(bytes)
+    }
+}
+
+impl_base_transaction!(EIP2930Transaction);
+
+impl Decodable for EIP2930Transaction {
+    fn decode(rlp: &Rlp) -> core::result::Result<Self, DecoderError> {
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            nonce: rlp.val_at(1)?,
+            gas_price: rlp.val_at(2)?,
+            gas_limit: rlp.val_at(3)?,
+            action: rlp.val_at(4)?,
+            value: rlp.val_at(5)?,
+            input: rlp.val_at(6)?,
+            access_list: decode_trailing_access_list(rlp, 7)?,
+        })
+    }
+}
+
+pub struct ParsedEIP2930Transaction {
+    pub(crate) chain_id: u64,
+    pub(crate) nonce: u32,
+    pub(crate) gas_price: String,
+    pub(crate) gas_limit: String,
+    pub(crate) to: String,
+    pub(crate) value: String,
+    pub(crate) input: String,
+    pub(crate) max_txn_fee: String,
+    pub(crate) access_list: Vec<ParsedAccessListItem>,
+}
+
+impl TryFrom<EIP2930Transaction> for ParsedEIP2930Transaction {
+    type Error = EthereumError;
+
+    fn try_from(value: EIP2930Transaction) -> Result<Self> {
+        Ok(Self {
+            chain_id: value.chain_id,
+            nonce: checked_u32(value.nonce)?,
+            gas_price: normalize_price(checked_u64(value.gas_price)?),
+            gas_limit: value.gas_limit.to_string(),
+            to: format!("0x{}", hex::encode(value.get_to())),
+            value: normalize_value(value.value),
+            input: hex::encode(value.input),
+            max_txn_fee: normalize_value(checked_fee(value.gas_price, value.gas_limit)?),
+            access_list: value
+                .access_list
+                .into_iter()
+                .map(ParsedAccessListItem::from)
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,144 @@
+use crate::errors::{EthereumError, Result};
+use crate::normalizer::{normalize_price, normalize_value};
+use crate::structs::{
+    checked_fee, checked_u32, checked_u64, AccessList, ParsedAccessListItem, TransactionAction,
+};
+use crate::traits::BaseTransaction;
+use crate::{impl_base_transaction, Bytes};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use ethereum_types::{H256, U256};
+
+use rlp::{Decodable, DecoderError, Rlp};
+
+// every blob consumes a fixed amount of blob gas, see EIP-4844 GAS_PER_BLOB
+pub const GAS_PER_BLOB: u64 = 131072;
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+// only the signed payload is supported here, the network wrapper with blobs,
+// commitments and proofs is never part of the signing hash
+pub struct BlobTransaction {
+    pub chain_id: u64,
+    pub nonce: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: U256,
+    pub action: TransactionAction,
+    pub value: U256,
+    pub input: Bytes,
+    pub access_list: AccessList,
+    pub max_fee_per_blob_gas: U256,
+    pub blob_versioned_hashes: Vec<H256>,
+}
+
+impl BlobTransaction {
+    pub fn decode_raw(bytes: &[u8]) -> core::result::Result<BlobTransaction, DecoderError> {
+        rlp::decode(bytes)
+    }
+
+    pub fn max_blob_fee(&self) -> Result<U256> {
+        let blob_gas = U256::from(GAS_PER_BLOB)
+            .checked_mul(U256::from(self.blob_versioned_hashes.len()))
+            .ok_or(EthereumError::InvalidTransaction)?;
+        checked_fee(self.max_fee_per_blob_gas, blob_gas)
+    }
+}
+
+impl_base_transaction!(BlobTransaction);
+
+impl Decodable for BlobTransaction {
+    fn decode(rlp: &Rlp) -> core::result::Result<Self, DecoderError> {
+        if rlp.item_count()? != 11 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        let action: TransactionAction = rlp.val_at(5)?;
+        // blob transactions can not be used to create contracts
+        if let TransactionAction::Create = action {
+            return Err(DecoderError::Custom("blob transaction without destination"));
+        }
+        let blob_versioned_hashes: Vec<H256> = rlp.list_at(10)?;
+        if blob_versioned_hashes.is_empty() {
+            return Err(DecoderError::Custom("empty blob versioned hashes"));
+        }
+        if blob_versioned_hashes
+            .iter()
+            .any(|hash| hash.as_bytes()[0] != VERSIONED_HASH_VERSION_KZG)
+        {
+            return Err(DecoderError::Custom("invalid blob versioned hash"));
+        }
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            nonce: rlp.val_at(1)?,
+            max_priority_fee_per_gas: rlp.val_at(2)?,
+            max_fee_per_gas: rlp.val_at(3)?,
+            gas_limit: rlp.val_at(4)?,
+            action,
+            value: rlp.val_at(6)?,
+            input: rlp.val_at(7)?,
+            access_list: rlp.list_at(8)?,
+            max_fee_per_blob_gas: rlp.val_at(9)?,
+            blob_versioned_hashes,
+        })
+    }
+}
+
+pub struct ParsedBlobTransaction {
+    pub(crate) chain_id: u64,
+    pub(crate) nonce: u32,
+    pub(crate) max_priority_fee_per_gas: String,
+    pub(crate) max_fee_per_gas: String,
+    pub(crate) max_fee_per_blob_gas: String,
+    pub(crate) gas_limit: String,
+    pub(crate) to: String,
+    pub(crate) value: String,
+    pub(crate) input: String,
+    pub(crate) max_txn_fee: String,
+    pub(crate) max_fee: String,
+    pub(crate) max_priority: String,
+    pub(crate) max_blob_fee: String,
+    pub(crate) access_list: Vec<ParsedAccessListItem>,
+    pub(crate) blob_versioned_hashes: Vec<String>,
+}
+
+impl TryFrom<BlobTransaction> for ParsedBlobTransaction {
+    type Error = EthereumError;
+
+    fn try_from(value: BlobTransaction) -> Result<Self> {
+        let max_fee = checked_fee(value.max_fee_per_gas, value.gas_limit)?;
+        let max_blob_fee = value.max_blob_fee()?;
+        // the worst case fee pays both the execution gas and the blob gas
+        let max_txn_fee = max_fee
+            .checked_add(max_blob_fee)
+            .ok_or(EthereumError::InvalidTransaction)?;
+        Ok(Self {
+            chain_id: value.chain_id,
+            nonce: checked_u32(value.nonce)?,
+            max_priority_fee_per_gas: normalize_price(checked_u64(value.max_priority_fee_per_gas)?),
+            max_fee_per_gas: normalize_price(checked_u64(value.max_fee_per_gas)?),
+            max_fee_per_blob_gas: normalize_price(checked_u64(value.max_fee_per_blob_gas)?),
+            gas_limit: value.gas_limit.to_string(),
+            to: format!("0x{}", hex::encode(value.get_to())),
+            value: normalize_value(value.value),
+            input: hex::encode(value.input),
+            max_txn_fee: normalize_value(max_txn_fee),
+            max_priority: normalize_value(checked_fee(
+                value.max_priority_fee_per_gas,
+                value.gas_limit,
+            )?),
+            max_fee: normalize_value(max_fee),
+            max_blob_fee: normalize_value(max_blob_fee),
+            access_list: value
+                .access_list
+                .into_iter()
+                .map(ParsedAccessListItem::from)
+                .collect(),
+            blob_versioned_hashes: value
+                .blob_versioned_hashes
+                .iter()
+                .map(|hash| format!("0x{}", hex::encode(hash)))
+                .collect(),
+        })
+    }
+}
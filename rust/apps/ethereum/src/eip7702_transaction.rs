@@ -0,0 +1,117 @@
+use crate::authorization::{ParsedAuthorization, SignedAuthorization};
+use crate::errors::{EthereumError, Result};
+use crate::normalizer::{normalize_price, normalize_value};
+use crate::structs::{
+    checked_fee, checked_u32, checked_u64, AccessList, ParsedAccessListItem, TransactionAction,
+};
+use crate::traits::BaseTransaction;
+use crate::{impl_base_transaction, Bytes};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use ethereum_types::U256;
+
+use rlp::{Decodable, DecoderError, Rlp};
+
+pub struct SetCodeTransaction {
+    pub chain_id: u64,
+    pub nonce: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: U256,
+    pub action: TransactionAction,
+    pub value: U256,
+    pub input: Bytes,
+    pub access_list: AccessList,
+    pub authorization_list: Vec<SignedAuthorization>,
+}
+
+impl SetCodeTransaction {
+    pub fn decode_raw(bytes: &[u8]) -> core::result::Result<SetCodeTransaction, DecoderError> {
+        rlp::decode(bytes)
+    }
+}
+
+impl_base_transaction!(SetCodeTransaction);
+
+impl Decodable for SetCodeTransaction {
+    fn decode(rlp: &Rlp) -> core::result::Result<Self, DecoderError> {
+        if rlp.item_count()? != 10 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        let action: TransactionAction = rlp.val_at(5)?;
+        // set code transactions can not be used to create contracts
+        if let TransactionAction::Create = action {
+            return Err(DecoderError::Custom(
+                "set code transaction without destination",
+            ));
+        }
+        let authorization_list: Vec<SignedAuthorization> = rlp.list_at(9)?;
+        if authorization_list.is_empty() {
+            return Err(DecoderError::Custom("empty authorization list"));
+        }
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            nonce: rlp.val_at(1)?,
+            max_priority_fee_per_gas: rlp.val_at(2)?,
+            max_fee_per_gas: rlp.val_at(3)?,
+            gas_limit: rlp.val_at(4)?,
+            action,
+            value: rlp.val_at(6)?,
+            input: rlp.val_at(7)?,
+            access_list: rlp.list_at(8)?,
+            authorization_list,
+        })
+    }
+}
+
+pub struct ParsedSetCodeTransaction {
+    pub(crate) chain_id: u64,
+    pub(crate) nonce: u32,
+    pub(crate) max_priority_fee_per_gas: String,
+    pub(crate) max_fee_per_gas: String,
+    pub(crate) gas_limit: String,
+    pub(crate) to: String,
+    pub(crate) value: String,
+    pub(crate) input: String,
+    pub(crate) max_txn_fee: String,
+    pub(crate) max_fee: String,
+    pub(crate) max_priority: String,
+    pub(crate) access_list: Vec<ParsedAccessListItem>,
+    pub(crate) authorization_list: Vec<ParsedAuthorization>,
+}
+
+impl TryFrom<SetCodeTransaction> for ParsedSetCodeTransaction {
+    type Error = EthereumError;
+
+    fn try_from(value: SetCodeTransaction) -> Result<Self> {
+        let max_fee = checked_fee(value.max_fee_per_gas, value.gas_limit)?;
+        Ok(Self {
+            chain_id: value.chain_id,
+            nonce: checked_u32(value.nonce)?,
+            max_priority_fee_per_gas: normalize_price(checked_u64(value.max_priority_fee_per_gas)?),
+            max_fee_per_gas: normalize_price(checked_u64(value.max_fee_per_gas)?),
+            gas_limit: value.gas_limit.to_string(),
+            to: format!("0x{}", hex::encode(value.get_to())),
+            value: normalize_value(value.value),
+            input: hex::encode(value.input),
+            max_txn_fee: normalize_value(max_fee),
+            max_priority: normalize_value(checked_fee(
+                value.max_priority_fee_per_gas,
+                value.gas_limit,
+            )?),
+            max_fee: normalize_value(max_fee),
+            access_list: value
+                .access_list
+                .into_iter()
+                .map(ParsedAccessListItem::from)
+                .collect(),
+            authorization_list: value
+                .authorization_list
+                .into_iter()
+                .map(|v| ParsedAuthorization::from_signed_authorization(v, value.chain_id))
+                .collect::<Result<Vec<ParsedAuthorization>>>()?,
+        })
+    }
+}
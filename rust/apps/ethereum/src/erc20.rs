@@ -1,4 +1,5 @@
 extern crate alloc;
+use crate::selector_registry::{ERC20_APPROVE_SELECTOR, ERC20_TRANSFER_SELECTOR};
 use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
@@ -17,8 +18,7 @@ pub struct ParsedErc20Approval {
 }
 
 pub fn encode_erc20_transfer_calldata(to: H160, amount: U256) -> String {
-    // transfer(address recipient, uint256 amount) function signature is 0xa9059cbb
-    let mut calldata = "a9059cbb".to_string();
+    let mut calldata = hex::encode(ERC20_TRANSFER_SELECTOR);
     calldata.push_str(&format!("{:0>64}", hex::encode(to)));
     // convert value to hex and pad it to 64 bytes
     let amount_hex = format!("{:x}", amount);
@@ -70,7 +70,7 @@ pub fn parse_erc20_approval(
     }
 
     let method_id = &input[0..8];
-    if method_id != "095ea7b3" {
+    if method_id != hex::encode(ERC20_APPROVE_SELECTOR) {
         return Err("Invalid method id");
     }
 
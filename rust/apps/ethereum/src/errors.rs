@@ -35,6 +35,8 @@ pub enum EthereumError {
     InvalidSwapTransaction(String),
     #[error("Invalid SwapkitMemo")]
     InvalidSwapkitMemo,
+    #[error("Invalid Batch Transaction, {0}")]
+    InvalidBatchTransaction(String),
 }
 
 impl From<DecoderError> for EthereumError {
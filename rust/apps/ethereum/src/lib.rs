@@ -10,25 +10,35 @@ pub use ethereum_types::{H160, U256};
 
 pub use legacy_transaction::*;
 
+use crate::authorization::{Authorization, ParsedAuthorization, AUTHORIZATION_MAGIC};
 use crate::crypto::keccak256;
 use crate::eip1559_transaction::{EIP1559Transaction, ParsedEIP1559Transaction};
+use crate::eip2930_transaction::{EIP2930Transaction, ParsedEIP2930Transaction};
+use crate::eip4844_transaction::{BlobTransaction, ParsedBlobTransaction};
 use crate::eip712::eip712::{Eip712, TypedData as Eip712TypedData};
+use crate::eip7702_transaction::{ParsedSetCodeTransaction, SetCodeTransaction};
 use crate::errors::{EthereumError, Result};
 use crate::structs::{EthereumSignature, ParsedEthereumTransaction, PersonalMessage, TypedData};
 
 pub mod abi;
 pub mod address;
+pub mod authorization;
 pub mod batch_tx_rules;
 mod crypto;
 mod eip1559_transaction;
+mod eip2930_transaction;
+mod eip4844_transaction;
 pub mod eip712;
+mod eip7702_transaction;
 pub mod erc20;
 pub mod errors;
 mod legacy_transaction;
 mod normalizer;
+pub mod selector_registry;
 pub mod structs;
 pub mod swap;
 mod traits;
+pub mod typed_data_summary;
 pub type Bytes = Vec<u8>;
 
 pub fn parse_legacy_tx(tx_hex: &[u8], from_key: PublicKey) -> Result<ParsedEthereumTransaction> {
@@ -43,11 +53,50 @@ pub fn parse_fee_market_tx(
     from_key: PublicKey,
 ) -> Result<ParsedEthereumTransaction> {
     ParsedEthereumTransaction::from_eip1559(
-        ParsedEIP1559Transaction::from(EIP1559Transaction::decode_raw(tx_hex)?),
+        ParsedEIP1559Transaction::try_from(EIP1559Transaction::decode_raw(tx_hex)?)?,
         from_key,
     )
 }
 
+pub fn parse_access_list_tx(
+    tx_hex: &[u8],
+    from_key: PublicKey,
+) -> Result<ParsedEthereumTransaction> {
+    ParsedEthereumTransaction::from_eip2930(
+        ParsedEIP2930Transaction::try_from(EIP2930Transaction::decode_raw(tx_hex)?)?,
+        from_key,
+    )
+}
+
+pub fn parse_blob_tx(tx_hex: &[u8], from_key: PublicKey) -> Result<ParsedEthereumTransaction> {
+    ParsedEthereumTransaction::from_eip4844(
+        ParsedBlobTransaction::try_from(BlobTransaction::decode_raw(tx_hex)?)?,
+        from_key,
+    )
+}
+
+pub fn parse_set_code_tx(tx_hex: &[u8], from_key: PublicKey) -> Result<ParsedEthereumTransaction> {
+    ParsedEthereumTransaction::from_eip7702(
+        ParsedSetCodeTransaction::try_from(SetCodeTransaction::decode_raw(tx_hex)?)?,
+        from_key,
+    )
+}
+
+// a standalone EIP-7702 authorization is always signed by the requested key itself,
+// chain_id is the chain of the sign request when the wallet tells it
+pub fn parse_authorization(
+    sign_data: &[u8],
+    from_key: PublicKey,
+    chain_id: Option<u64>,
+) -> Result<ParsedAuthorization> {
+    let authorization = Authorization::decode_raw(sign_data)?;
+    ParsedAuthorization::from_authorization(
+        authorization,
+        Some(address::generate_address(from_key)?),
+        chain_id,
+    )
+}
+
 pub fn parse_personal_message(tx_hex: Vec<u8>, from_key: PublicKey) -> Result<PersonalMessage> {
     let raw_messge = hex::encode(tx_hex.clone());
     let utf8_message = match String::from_utf8(tx_hex) {
@@ -114,8 +163,52 @@ pub fn sign_fee_markey_tx(
     path: &String,
 ) -> Result<EthereumSignature> {
     // sign_data should starts with 0x02
+    sign_typed_tx(0x02, sign_data, seed, path)
+}
+
+pub fn sign_access_list_tx(
+    sign_data: Vec<u8>,
+    seed: &[u8],
+    path: &String,
+) -> Result<EthereumSignature> {
+    // sign_data should starts with 0x01
+    sign_typed_tx(0x01, sign_data, seed, path)
+}
+
+pub fn sign_blob_tx(sign_data: Vec<u8>, seed: &[u8], path: &String) -> Result<EthereumSignature> {
+    // sign_data should starts with 0x03
+    sign_typed_tx(0x03, sign_data, seed, path)
+}
+
+pub fn sign_set_code_tx(
+    sign_data: Vec<u8>,
+    seed: &[u8],
+    path: &String,
+) -> Result<EthereumSignature> {
+    // sign_data should starts with 0x04
+    sign_typed_tx(0x04, sign_data, seed, path)
+}
+
+pub fn sign_authorization(
+    sign_data: Vec<u8>,
+    seed: &[u8],
+    path: &String,
+) -> Result<EthereumSignature> {
+    // make sure the payload is a well formed authorization tuple before signing it
+    Authorization::decode_raw(&sign_data)?;
+    sign_typed_tx(AUTHORIZATION_MAGIC, sign_data, seed, path)
+}
+
+// EIP-2718 typed transactions and EIP-7702 authorizations sign keccak256(type || rlp(payload))
+// and use y_parity as v
+fn sign_typed_tx(
+    tx_type: u8,
+    sign_data: Vec<u8>,
+    seed: &[u8],
+    path: &String,
+) -> Result<EthereumSignature> {
     let first_byte = *sign_data.first().ok_or(EthereumError::InvalidTransaction)?;
-    if first_byte != 0x02 {
+    if first_byte != tx_type {
         return Err(EthereumError::InvalidTransaction);
     }
 
@@ -181,9 +274,11 @@ mod tests {
 
     use crate::alloc::string::ToString;
     use crate::eip712::eip712::{Eip712, TypedData as Eip712TypedData};
+    use crate::structs::EthereumTransactionType;
     use crate::{
-        parse_fee_market_tx, parse_personal_message, parse_typed_data_message,
-        sign_personal_message, sign_typed_data_message,
+        parse_access_list_tx, parse_authorization, parse_blob_tx, parse_fee_market_tx,
+        parse_personal_message, parse_set_code_tx, parse_typed_data_message, sign_access_list_tx,
+        sign_authorization, sign_personal_message, sign_typed_data_message,
     };
 
     #[test]
@@ -235,9 +330,160 @@ mod tests {
         assert_eq!("0.0000158472", result.max_priority.unwrap());
         assert_eq!("158472", result.gas_limit);
         assert_eq!("0.002483224658432064", result.max_txn_fee);
+        assert_eq!(EthereumTransactionType::FeeMarket, result.tx_type);
+        assert!(result.access_list.is_empty());
         assert_eq!(result.input, "3593564c000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000064996e5f00000000000000000000000000000000000000000000000000000000000000020b000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000002386f26fc1000000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000002386f26fc10000000000000000000000000000000000000000000000000000f84605ccc515414000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002bc02aaa39b223fe8d0a0e5c4f27ead9083c756cc20001f46b175474e89094c44da98b954eedeac495271d0f000000000000000000000000000000000000000000");
     }
 
+    #[test]
+    fn test_parse_access_list_tx() {
+        let sign_data = hex::decode("f88601058504a817c80082ea60943fc91a3afd70395cd496c647d5a6cc9d4b2b7fad872386f26fc1000080f85bf859943fc91a3afd70395cd496c647d5a6cc9d4b2b7fadf842a00000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000001").unwrap();
+        let path = "m/44'/60'/0'/0/0".to_string();
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let pubkey = get_public_key_by_seed(&seed, &path).unwrap();
+        let result = parse_access_list_tx(&sign_data, pubkey).unwrap();
+        assert_eq!(EthereumTransactionType::AccessList, result.tx_type);
+        assert_eq!(5, result.nonce);
+        assert_eq!(1, result.chain_id);
+        assert_eq!("0x9858EfFD232B4033E47d90003D41EC34EcaEda94", result.from);
+        assert_eq!("0x3fc91a3afd70395cd496c647d5a6cc9d4b2b7fad", result.to);
+        assert_eq!("0.01", result.value);
+        assert_eq!("20 Gwei", result.gas_price.unwrap());
+        assert_eq!("60000", result.gas_limit);
+        assert_eq!("0.0012", result.max_txn_fee);
+        assert_eq!(None, result.max_fee_per_gas);
+        assert_eq!(1, result.access_list.len());
+        assert_eq!(
+            "0x3fc91a3afd70395cd496c647d5a6cc9d4b2b7fad",
+            result.access_list[0].address
+        );
+        assert_eq!(
+            result.access_list[0].storage_keys,
+            [
+                "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "0x0000000000000000000000000000000000000000000000000000000000000001",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sign_access_list_tx_rejects_other_types() {
+        let sign_data = hex::decode("02f88601058504a817c80082ea60943fc91a3afd70395cd496c647d5a6cc9d4b2b7fad872386f26fc1000080f85bf859943fc91a3afd70395cd496c647d5a6cc9d4b2b7fadf842a00000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000001").unwrap();
+        let path = "m/44'/60'/0'/0/0".to_string();
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        assert!(sign_access_list_tx(sign_data, &seed, &path).is_err());
+    }
+
+    #[test]
+    fn test_parse_access_list_tx_payload_length() {
+        let path = "m/44'/60'/0'/0/0".to_string();
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let pubkey = get_public_key_by_seed(&seed, &path).unwrap();
+        // the trailing access list omitted
+        let sign_data = hex::decode(
+            "e901058504a817c80082ea60943fc91a3afd70395cd496c647d5a6cc9d4b2b7fad872386f26fc1000080",
+        )
+        .unwrap();
+        let result = parse_access_list_tx(&sign_data, pubkey).unwrap();
+        assert!(result.access_list.is_empty());
+        // an extra field after the access list
+        let sign_data = hex::decode("f88701058504a817c80082ea60943fc91a3afd70395cd496c647d5a6cc9d4b2b7fad872386f26fc1000080f85bf859943fc91a3afd70395cd496c647d5a6cc9d4b2b7fadf842a00000000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000000180").unwrap();
+        assert!(parse_access_list_tx(&sign_data, pubkey).is_err());
+        // a nonce that does not fit u32
+        let sign_data = hex::decode("f201890100000000000000008504a817c80082ea60943fc91a3afd70395cd496c647d5a6cc9d4b2b7fad872386f26fc1000080").unwrap();
+        assert!(parse_access_list_tx(&sign_data, pubkey).is_err());
+    }
+
+    #[test]
+    fn test_parse_blob_tx() {
+        let sign_data = hex::decode("f8710103843b9aca008506fc23ac00825208943fc91a3afd70395cd496c647d5a6cc9d4b2b7fad8080c08477359400f842a001abababababababababababababababababababababababababababababababa001cdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcd").unwrap();
+        let path = "m/44'/60'/0'/0/0".to_string();
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let pubkey = get_public_key_by_seed(&seed, &path).unwrap();
+        let result = parse_blob_tx(&sign_data, pubkey).unwrap();
+        assert_eq!(EthereumTransactionType::Blob, result.tx_type);
+        assert_eq!(3, result.nonce);
+        assert_eq!("30 Gwei", result.max_fee_per_gas.unwrap());
+        assert_eq!("2 Gwei", result.max_fee_per_blob_gas.unwrap());
+        assert_eq!("0.00063", result.max_fee.unwrap());
+        assert_eq!("0.000524288", result.max_blob_fee.unwrap());
+        assert_eq!("0.001154288", result.max_txn_fee);
+        assert_eq!(2, result.blob_versioned_hashes.len());
+        assert_eq!(
+            "0x01abababababababababababababababababababababababababababababab",
+            result.blob_versioned_hashes[0]
+        );
+    }
+
+    #[test]
+    fn test_parse_blob_tx_fee_overflow() {
+        // max_fee_per_blob_gas is 2^64 wei
+        let sign_data = hex::decode("f8760103843b9aca008506fc23ac00825208943fc91a3afd70395cd496c647d5a6cc9d4b2b7fad8080c089010000000000000000f842a001abababababababababababababababababababababababababababababababa001cdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcd").unwrap();
+        let path = "m/44'/60'/0'/0/0".to_string();
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let pubkey = get_public_key_by_seed(&seed, &path).unwrap();
+        assert!(parse_blob_tx(&sign_data, pubkey).is_err());
+    }
+
+    #[test]
+    fn test_parse_set_code_tx() {
+        let sign_data = hex::decode("f8870108843b9aca008504a817c800830186a0949858effd232b4033e47d90003d41ec34ecaeda948080c0f85cf85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b0780a0b8b42b7bbdad2be7d50ec724a780bd7ad57a5794f8f795da12123438e59de9aba040c1d675546cccb0b3ca5e603b28800ad11b1ed4548f85ac7f8bf35a089e1220").unwrap();
+        let path = "m/44'/60'/0'/0/0".to_string();
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let pubkey = get_public_key_by_seed(&seed, &path).unwrap();
+        let result = parse_set_code_tx(&sign_data, pubkey).unwrap();
+        assert_eq!(EthereumTransactionType::SetCode, result.tx_type);
+        assert_eq!(8, result.nonce);
+        assert_eq!("0x9858effd232b4033e47d90003d41ec34ecaeda94", result.to);
+        assert_eq!("0.002", result.max_txn_fee);
+        assert_eq!("0.0001", result.max_priority.unwrap());
+        assert_eq!(1, result.authorization_list.len());
+        let authorization = &result.authorization_list[0];
+        assert_eq!(
+            "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B",
+            authorization.delegate
+        );
+        assert_eq!(
+            Some("0x9858EfFD232B4033E47d90003D41EC34EcaEda94".to_string()),
+            authorization.authority
+        );
+        assert_eq!("7", authorization.nonce);
+    }
+
+    #[test]
+    fn test_parse_set_code_tx_fee_overflow() {
+        // max_fee_per_gas is 2^64 wei
+        let sign_data = hex::decode("f88b0108843b9aca0089010000000000000000830186a0949858effd232b4033e47d90003d41ec34ecaeda948080c0f85cf85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b0780a0b8b42b7bbdad2be7d50ec724a780bd7ad57a5794f8f795da12123438e59de9aba040c1d675546cccb0b3ca5e603b28800ad11b1ed4548f85ac7f8bf35a089e1220").unwrap();
+        let path = "m/44'/60'/0'/0/0".to_string();
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let pubkey = get_public_key_by_seed(&seed, &path).unwrap();
+        assert!(parse_set_code_tx(&sign_data, pubkey).is_err());
+    }
+
+    #[test]
+    fn test_parse_and_sign_authorization() {
+        let sign_data = hex::decode("05d7019463c0c19a282a1b52b07dd5a65b58948a07dae32b07").unwrap();
+        let path = "m/44'/60'/0'/0/0".to_string();
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let pubkey = get_public_key_by_seed(&seed, &path).unwrap();
+        let result = parse_authorization(&sign_data, pubkey, Some(1)).unwrap();
+        assert_eq!("1", result.chain_id);
+        assert_eq!(
+            "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B",
+            result.delegate
+        );
+        assert_eq!(
+            Some("0x9858EfFD232B4033E47d90003D41EC34EcaEda94".to_string()),
+            result.authority
+        );
+
+        let signature = sign_authorization(sign_data, &seed, &path).unwrap();
+        assert_eq!(
+            "b8b42b7bbdad2be7d50ec724a780bd7ad57a5794f8f795da12123438e59de9ab40c1d675546cccb0b3ca5e603b28800ad11b1ed4548f85ac7f8bf35a089e122000",
+            hex::encode(signature.serialize())
+        );
+    }
+
     #[test]
     fn test_parse_typed_data() {
         let sign_data = "7b227479706573223a7b22454950373132446f6d61696e223a5b7b226e616d65223a226e616d65222c2274797065223a22737472696e67227d2c7b226e616d65223a2276657273696f6e222c2274797065223a22737472696e67227d2c7b226e616d65223a22636861696e4964222c2274797065223a2275696e74323536227d2c7b226e616d65223a22766572696679696e67436f6e7472616374222c2274797065223a2261646472657373227d5d2c224f72646572436f6d706f6e656e7473223a5b7b226e616d65223a226f666665726572222c2274797065223a2261646472657373227d2c7b226e616d65223a227a6f6e65222c2274797065223a2261646472657373227d2c7b226e616d65223a226f66666572222c2274797065223a224f666665724974656d5b5d227d2c7b226e616d65223a22737461727454696d65222c2274797065223a2275696e74323536227d2c7b226e616d65223a22656e6454696d65222c2274797065223a2275696e74323536227d2c7b226e616d65223a227a6f6e6548617368222c2274797065223a2262797465733332227d2c7b226e616d65223a2273616c74222c2274797065223a2275696e74323536227d2c7b226e616d65223a22636f6e647569744b6579222c2274797065223a2262797465733332227d2c7b226e616d65223a22636f756e746572222c2274797065223a2275696e74323536227d5d2c224f666665724974656d223a5b7b226e616d65223a22746f6b656e222c2274797065223a2261646472657373227d5d2c22436f6e73696465726174696f6e4974656d223a5b7b226e616d65223a22746f6b656e222c2274797065223a2261646472657373227d2c7b226e616d65223a226964656e7469666965724f724372697465726961222c2274797065223a2275696e74323536227d2c7b226e616d65223a227374617274416d6f756e74222c2274797065223a2275696e74323536227d2c7b226e616d65223a22656e64416d6f756e74222c2274797065223a2275696e74323536227d2c7b226e616d65223a22726563697069656e74222c2274797065223a2261646472657373227d5d7d2c227072696d61727954797065223a224f72646572436f6d706f6e656e7473222c22646f6d61696e223a7b226e616d65223a22536561706f7274222c2276657273696f6e223a22312e31222c22636861696e4964223a2231222c22766572696679696e67436f6e7472616374223a22307830303030303030303030366333383532636245663365303845386446323839313639456445353831227d2c226d657373616765223a7b226f666665726572223a22307866333946643665353161616438384636463463653661423838323732373963666646623932323636222c226f66666572223a5b7b22746f6b656e223a22307841363034303630383930393233466634303065386336663532393034363141383341454441436563227d5d2c22737461727454696d65223a2231363538363435353931222c22656e6454696d65223a2231363539323530333836222c227a6f6e65223a22307830303443303035303030303061443130344437444264303065336165304135433030353630433030222c227a6f6e6548617368223a22307830303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030222c2273616c74223a223136313738323038383937313336363138222c22636f6e647569744b6579223a22307830303030303037623032323330303931613765643031323330303732663730303661303034643630613864346537316435393962383130343235306630303030222c22746f74616c4f726967696e616c436f6e73696465726174696f6e4974656d73223a2232222c22636f756e746572223a2230227d7d";
@@ -0,0 +1,264 @@
+use crate::abi::{ContractData, _parse_by_function};
+use crate::errors::{EthereumError, Result};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use ethabi::{Function, Token};
+use serde_json::{json, Value};
+
+// bump whenever entries are added or removed so the version can be shown alongside decoded data
+pub const SELECTOR_REGISTRY_VERSION: u32 = 2;
+
+pub const ERC20_TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+pub const ERC20_APPROVE_SELECTOR: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+
+// selector -> human readable signature, must stay sorted by selector for the binary search.
+// parameter names are only used for display, the selector is derived from the types.
+const SELECTORS: &[([u8; 4], &str)] = &[
+    ([0x02, 0x75, 0x1c, 0xec], "removeLiquidityETH(address token,uint256 liquidity,uint256 amountTokenMin,uint256 amountETHMin,address to,uint256 deadline)"),
+    (ERC20_APPROVE_SELECTOR, "approve(address spender,uint256 amount)"),
+    ([0x18, 0xcb, 0xaf, 0xe5], "swapExactTokensForETH(uint256 amountIn,uint256 amountOutMin,address[] path,address to,uint256 deadline)"),
+    ([0x23, 0xb8, 0x72, 0xdd], "transferFrom(address from,address to,uint256 amount)"),
+    ([0x24, 0x85, 0x6b, 0xc3], "execute(bytes commands,bytes[] inputs)"),
+    ([0x2e, 0x1a, 0x7d, 0x4d], "withdraw(uint256 wad)"),
+    ([0x2e, 0xb2, 0xc2, 0xd6], "safeBatchTransferFrom(address from,address to,uint256[] ids,uint256[] amounts,bytes data)"),
+    ([0x35, 0x93, 0x56, 0x4c], "execute(bytes commands,bytes[] inputs,uint256 deadline)"),
+    ([0x38, 0xed, 0x17, 0x39], "swapExactTokensForTokens(uint256 amountIn,uint256 amountOutMin,address[] path,address to,uint256 deadline)"),
+    ([0x39, 0x50, 0x93, 0x51], "increaseAllowance(address spender,uint256 addedValue)"),
+    ([0x40, 0xc1, 0x0f, 0x19], "mint(address to,uint256 amount)"),
+    ([0x41, 0x4b, 0xf3, 0x89], "exactInputSingle((address tokenIn,address tokenOut,uint24 fee,address recipient,uint256 deadline,uint256 amountIn,uint256 amountOutMinimum,uint160 sqrtPriceLimitX96) params)"),
+    ([0x42, 0x84, 0x2e, 0x0e], "safeTransferFrom(address from,address to,uint256 tokenId)"),
+    ([0x42, 0x96, 0x6c, 0x68], "burn(uint256 amount)"),
+    ([0x44, 0xbc, 0x93, 0x7b], "depositWithExpiry(address vault,address asset,uint256 amount,string memo,uint256 expiration)"),
+    ([0x57, 0x3a, 0xde, 0x81], "repay(address asset,uint256 amount,uint256 interestRateMode,address onBehalfOf)"),
+    ([0x5a, 0xe4, 0x01, 0xdc], "multicall(uint256 deadline,bytes[] data)"),
+    ([0x5c, 0x19, 0xa9, 0x5c], "delegate(address delegatee)"),
+    ([0x61, 0x7b, 0xa0, 0x37], "supply(address asset,uint256 amount,address onBehalfOf,uint16 referralCode)"),
+    ([0x69, 0x32, 0x8d, 0xec], "withdraw(address asset,uint256 amount,address to)"),
+    ([0x6a, 0x76, 0x12, 0x02], "execTransaction(address to,uint256 value,bytes data,uint8 operation,uint256 safeTxGas,uint256 baseGas,uint256 gasPrice,address gasToken,address refundReceiver,bytes signatures)"),
+    ([0x7f, 0xf3, 0x6a, 0xb5], "swapExactETHForTokens(uint256 amountOutMin,address[] path,address to,uint256 deadline)"),
+    ([0x88, 0x03, 0xdb, 0xee], "swapTokensForExactTokens(uint256 amountOut,uint256 amountInMax,address[] path,address to,uint256 deadline)"),
+    ([0xa1, 0x90, 0x3e, 0xab], "submit(address referral)"),
+    ([0xa2, 0x2c, 0xb4, 0x65], "setApprovalForAll(address operator,bool approved)"),
+    ([0xa4, 0x15, 0xbc, 0xad], "borrow(address asset,uint256 amount,uint256 interestRateMode,uint16 referralCode,address onBehalfOf)"),
+    ([0xa4, 0x57, 0xc2, 0xd7], "decreaseAllowance(address spender,uint256 subtractedValue)"),
+    (ERC20_TRANSFER_SELECTOR, "transfer(address to,uint256 amount)"),
+    ([0xac, 0x96, 0x50, 0xd8], "multicall(bytes[] data)"),
+    ([0xb8, 0x8d, 0x4f, 0xde], "safeTransferFrom(address from,address to,uint256 tokenId,bytes data)"),
+    ([0xba, 0xa2, 0xab, 0xde], "removeLiquidity(address tokenA,address tokenB,uint256 liquidity,uint256 amountAMin,uint256 amountBMin,address to,uint256 deadline)"),
+    ([0xc0, 0x4b, 0x8d, 0x59], "exactInput((bytes path,address recipient,uint256 deadline,uint256 amountIn,uint256 amountOutMinimum) params)"),
+    ([0xd0, 0xe3, 0x0d, 0xb0], "deposit()"),
+    ([0xd5, 0x05, 0xac, 0xcf], "permit(address owner,address spender,uint256 value,uint256 deadline,uint8 v,bytes32 r,bytes32 s)"),
+    ([0xdb, 0x3e, 0x21, 0x98], "exactOutputSingle((address tokenIn,address tokenOut,uint24 fee,address recipient,uint256 deadline,uint256 amountOut,uint256 amountInMaximum,uint160 sqrtPriceLimitX96) params)"),
+    ([0xe8, 0xe3, 0x37, 0x00], "addLiquidity(address tokenA,address tokenB,uint256 amountADesired,uint256 amountBDesired,uint256 amountAMin,uint256 amountBMin,address to,uint256 deadline)"),
+    ([0xf2, 0x42, 0x43, 0x2a], "safeTransferFrom(address from,address to,uint256 id,uint256 amount,bytes data)"),
+    ([0xf3, 0x05, 0xd7, 0x19], "addLiquidityETH(address token,uint256 amountTokenDesired,uint256 amountTokenMin,uint256 amountETHMin,address to,uint256 deadline)"),
+    ([0xfb, 0x3b, 0xdb, 0x41], "swapETHForExactTokens(uint256 amountOut,address[] path,address to,uint256 deadline)"),
+];
+
+// a selector is only 4 bytes, unrelated functions may collide so every candidate is returned
+pub fn lookup(selector: &[u8]) -> Vec<&'static str> {
+    let start = SELECTORS.partition_point(|(s, _)| s.as_slice() < selector);
+    SELECTORS[start..]
+        .iter()
+        .take_while(|(s, _)| s.as_slice() == selector)
+        .map(|(_, signature)| *signature)
+        .collect()
+}
+
+// decode calldata without an ABI, the result is only a best guess since any contract
+// is free to implement a function with the same selector but different semantics
+pub fn parse_contract_data_by_selector(input: Vec<u8>) -> Result<ContractData> {
+    if input.len() < 4 {
+        return Err(EthereumError::DecodeContractDataError(format!(
+            "invalid input data: {}",
+            hex::encode(input)
+        )));
+    }
+    let (selector, data) = input.split_at(4);
+    let mut result = Err(EthereumError::DecodeContractDataError(format!(
+        "unknown method selector [{}]",
+        hex::encode(selector)
+    )));
+    for signature in lookup(selector) {
+        let function = signature_to_function(signature)?;
+        let method_name = function.name.clone();
+        match _parse_by_function(selector, data, function) {
+            Some(Ok(params)) => {
+                return Ok(ContractData::new(String::new(), method_name, params, true))
+            }
+            Some(Err(e)) => result = Err(e),
+            None => {}
+        }
+    }
+    result
+}
+
+// same as parse_contract_data_by_selector but keeps the raw tokens and flattens tuple components,
+// so a caller can find an argument by name wherever it sits, e.g. amountIn of exactInputSingle
+pub fn parse_named_tokens_by_selector(input: &[u8]) -> Result<(String, Vec<(String, Token)>)> {
+    if input.len() < 4 {
+        return Err(EthereumError::DecodeContractDataError(format!(
+            "invalid input data: {}",
+            hex::encode(input)
+        )));
+    }
+    let (selector, data) = input.split_at(4);
+    let mut result = Err(EthereumError::DecodeContractDataError(format!(
+        "unknown method selector [{}]",
+        hex::encode(selector)
+    )));
+    for signature in lookup(selector) {
+        let (name, inputs) = signature_to_inputs(signature)?;
+        let function = signature_to_function(signature)?;
+        match function.decode_input(data) {
+            Ok(tokens) => {
+                let mut named = Vec::new();
+                flatten_tokens(&inputs, tokens, &mut named);
+                return Ok((name.to_string(), named));
+            }
+            Err(_e) => {
+                result = Err(EthereumError::DecodeContractDataError(String::from(
+                    "invalid input data",
+                )))
+            }
+        }
+    }
+    result
+}
+
+fn flatten_tokens(inputs: &[Value], tokens: Vec<Token>, named: &mut Vec<(String, Token)>) {
+    for (input, token) in inputs.iter().zip(tokens) {
+        match (input["components"].as_array(), token) {
+            (Some(components), Token::Tuple(tokens)) => flatten_tokens(components, tokens, named),
+            (_, token) => named.push((input["name"].as_str().unwrap_or("").to_string(), token)),
+        }
+    }
+}
+
+fn signature_to_inputs(signature: &str) -> Result<(&str, Vec<Value>)> {
+    let invalid = || EthereumError::InvalidContractABI;
+    let (name, params) = signature.split_once('(').ok_or_else(invalid)?;
+    let params = params.strip_suffix(')').ok_or_else(invalid)?;
+    Ok((name, parse_params(params).ok_or_else(invalid)?))
+}
+
+fn signature_to_function(signature: &str) -> Result<Function> {
+    let invalid = || EthereumError::InvalidContractABI;
+    let (name, inputs) = signature_to_inputs(signature)?;
+    let function = json!({
+        "type": "function",
+        "name": name,
+        "inputs": inputs,
+        "outputs": [],
+        "stateMutability": "nonpayable",
+    });
+    serde_json::from_value(function).map_err(|_e| invalid())
+}
+
+fn parse_params(params: &str) -> Option<Vec<Value>> {
+    split_top_level(params)
+        .into_iter()
+        .map(parse_param)
+        .collect()
+}
+
+fn parse_param(param: &str) -> Option<Value> {
+    let param = param.trim();
+    if param.starts_with('(') {
+        // tuple, everything up to the last parenthesis are the components
+        let end = param.rfind(')')?;
+        let rest = &param[end + 1..];
+        let (suffix, name) = rest.split_once(' ').unwrap_or((rest, ""));
+        return Some(json!({
+            "name": name,
+            "type": format!("tuple{}", suffix),
+            "components": parse_params(&param[1..end])?,
+        }));
+    }
+    let (kind, name) = param.split_once(' ').unwrap_or((param, ""));
+    Some(json!({ "name": name, "type": kind }))
+}
+
+fn split_top_level(params: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    if params.is_empty() {
+        return parts;
+    }
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in params.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&params[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&params[start..]);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate std;
+
+    #[test]
+    fn test_registry_selectors() {
+        for (selector, signature) in SELECTORS {
+            let function = signature_to_function(signature).unwrap();
+            assert_eq!(*selector, function.short_signature(), "{}", signature);
+        }
+        assert!(SELECTORS.windows(2).all(|w| w[0].0 <= w[1].0));
+    }
+
+    #[test]
+    fn test_parse_contract_data_by_selector() {
+        let input = hex::decode("095ea7b30000000000000000000000001111111254eeb25477b68fb85ed929f73a960582ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").unwrap();
+        let result = parse_contract_data_by_selector(input).unwrap();
+        assert_eq!("approve", result.get_method_name());
+        assert!(result.get_is_signature_inferred());
+        assert_eq!("spender", result.get_params()[0].get_name());
+        assert_eq!(
+            "0x1111111254eeb25477b68fb85ed929f73a960582",
+            result.get_params()[0].get_value()
+        );
+        assert_eq!("address", result.get_params()[0].get_param_type());
+        assert_eq!(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+            result.get_params()[1].get_value()
+        );
+
+        let result = parse_contract_data_by_selector(hex::decode("d0e30db0").unwrap()).unwrap();
+        assert_eq!("deposit", result.get_method_name());
+        assert!(result.get_params().is_empty());
+
+        let input = hex::decode("12345678").unwrap();
+        assert!(parse_contract_data_by_selector(input).is_err());
+    }
+
+    #[test]
+    fn test_parse_tuple_by_selector() {
+        let input = hex::decode("414bf389000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb480000000000000000000000000000000000000000000000000000000000000bb80000000000000000000000009858effd232b4033e47d90003d41ec34ecaeda9400000000000000000000000000000000000000000000000000000000665f6a2c00000000000000000000000000000000000000000000000000038d7ea4c6800000000000000000000000000000000000000000000000000000000000000f42400000000000000000000000000000000000000000000000000000000000000000").unwrap();
+        let result = parse_contract_data_by_selector(input).unwrap();
+        assert_eq!("exactInputSingle", result.get_method_name());
+        assert_eq!(1, result.get_params().len());
+        assert_eq!(
+            "(address,address,uint24,address,uint256,uint256,uint256,uint160)",
+            result.get_params()[0].get_param_type()
+        );
+
+        let input = hex::decode("414bf389000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb480000000000000000000000000000000000000000000000000000000000000bb80000000000000000000000009858effd232b4033e47d90003d41ec34ecaeda9400000000000000000000000000000000000000000000000000000000665f6a2c00000000000000000000000000000000000000000000000000038d7ea4c6800000000000000000000000000000000000000000000000000000000000000f42400000000000000000000000000000000000000000000000000000000000000000").unwrap();
+        let (name, tokens) = parse_named_tokens_by_selector(&input).unwrap();
+        assert_eq!("exactInputSingle", name);
+        assert_eq!(8, tokens.len());
+        assert_eq!("tokenIn", tokens[0].0);
+        assert_eq!("amountIn", tokens[5].0);
+        assert_eq!(Token::Uint(1000000000000000u64.into()), tokens[5].1);
+    }
+}
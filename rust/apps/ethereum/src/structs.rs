@@ -1,8 +1,13 @@
 use core::ops::Add;
 
+use crate::authorization::ParsedAuthorization;
 use crate::eip1559_transaction::ParsedEIP1559Transaction;
+use crate::eip2930_transaction::ParsedEIP2930Transaction;
+use crate::eip4844_transaction::ParsedBlobTransaction;
 use crate::eip712::eip712::TypedData as Eip712TypedData;
-use crate::errors::Result;
+use crate::eip7702_transaction::ParsedSetCodeTransaction;
+use crate::errors::{EthereumError, Result};
+use crate::typed_data_summary::{summarize_typed_data, TypedDataSummary};
 use crate::{address::generate_address, eip712::eip712::Eip712};
 use crate::{Bytes, ParsedLegacyTransaction};
 use alloc::string::{String, ToString};
@@ -12,7 +17,7 @@ use alloc::vec::Vec;
 use bitcoin::secp256k1::PublicKey;
 use cryptoxide::hashing::keccak256;
 use ethabi::{encode, Address, Token};
-use ethereum_types::{H160, U256};
+use ethereum_types::{H160, H256, U256};
 use hex;
 use rlp::{Decodable, DecoderError, Encodable, Rlp};
 use serde_json::{from_str, Value};
@@ -50,8 +55,103 @@ impl Encodable for TransactionAction {
     }
 }
 
+#[derive(Clone)]
+pub struct AccessListItem {
+    pub address: H160,
+    pub storage_keys: Vec<H256>,
+}
+
+pub type AccessList = Vec<AccessListItem>;
+
+impl Decodable for AccessListItem {
+    fn decode(rlp: &Rlp) -> core::result::Result<Self, DecoderError> {
+        if rlp.item_count()? != 2 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+        Ok(Self {
+            address: rlp.val_at(0)?,
+            storage_keys: rlp.list_at(1)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParsedAccessListItem {
+    pub address: String,
+    pub storage_keys: Vec<String>,
+}
+
+impl From<AccessListItem> for ParsedAccessListItem {
+    fn from(value: AccessListItem) -> Self {
+        Self {
+            address: format!("0x{}", hex::encode(value.address)),
+            storage_keys: value
+                .storage_keys
+                .iter()
+                .map(|key| format!("0x{}", hex::encode(key)))
+                .collect(),
+        }
+    }
+}
+
+// some wallets omit the access list entirely when it is empty, that is only
+// unambiguous when it is the last field of the payload
+pub(crate) fn decode_trailing_access_list(
+    rlp: &Rlp,
+    index: usize,
+) -> core::result::Result<AccessList, DecoderError> {
+    match rlp.item_count()? {
+        count if count == index + 1 => rlp.list_at(index),
+        count if count == index => Ok(vec![]),
+        _ => Err(DecoderError::RlpIncorrectListLen),
+    }
+}
+
+// decoded quantities are arbitrary U256 values, they must not be truncated for display
+pub(crate) fn checked_u32(value: U256) -> Result<u32> {
+    if value > U256::from(u32::MAX) {
+        return Err(EthereumError::InvalidTransaction);
+    }
+    Ok(value.as_u32())
+}
+
+pub(crate) fn checked_u64(value: U256) -> Result<u64> {
+    if value > U256::from(u64::MAX) {
+        return Err(EthereumError::InvalidTransaction);
+    }
+    Ok(value.as_u64())
+}
+
+pub(crate) fn checked_fee(price: U256, gas_limit: U256) -> Result<U256> {
+    price
+        .checked_mul(gas_limit)
+        .ok_or(EthereumError::InvalidTransaction)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EthereumTransactionType {
+    Legacy,
+    AccessList,
+    FeeMarket,
+    Blob,
+    SetCode,
+}
+
+impl EthereumTransactionType {
+    pub fn get_type_name(&self) -> String {
+        match self {
+            EthereumTransactionType::Legacy => "Legacy".to_string(),
+            EthereumTransactionType::AccessList => "AccessList".to_string(),
+            EthereumTransactionType::FeeMarket => "FeeMarket".to_string(),
+            EthereumTransactionType::Blob => "Blob".to_string(),
+            EthereumTransactionType::SetCode => "SetCode".to_string(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ParsedEthereumTransaction {
+    pub tx_type: EthereumTransactionType,
     pub nonce: u32,
     pub chain_id: u64,
     pub from: String,
@@ -67,11 +167,19 @@ pub struct ParsedEthereumTransaction {
 
     pub gas_limit: String,
     pub max_txn_fee: String,
+
+    pub access_list: Vec<ParsedAccessListItem>,
+    pub authorization_list: Vec<ParsedAuthorization>,
+
+    pub max_fee_per_blob_gas: Option<String>,
+    pub max_blob_fee: Option<String>,
+    pub blob_versioned_hashes: Vec<String>,
 }
 
 impl ParsedEthereumTransaction {
     pub(crate) fn from_legacy(tx: ParsedLegacyTransaction, from: PublicKey) -> Result<Self> {
         Ok(Self {
+            tx_type: EthereumTransactionType::Legacy,
             nonce: tx.nonce,
             gas_limit: tx.gas_limit,
             gas_price: Some(tx.gas_price),
@@ -86,11 +194,42 @@ impl ParsedEthereumTransaction {
             max_priority_fee_per_gas: None,
             max_fee: None,
             max_priority: None,
+            access_list: vec![],
+            authorization_list: vec![],
+            max_fee_per_blob_gas: None,
+            max_blob_fee: None,
+            blob_versioned_hashes: vec![],
+        })
+    }
+
+    pub(crate) fn from_eip2930(tx: ParsedEIP2930Transaction, from: PublicKey) -> Result<Self> {
+        Ok(Self {
+            tx_type: EthereumTransactionType::AccessList,
+            nonce: tx.nonce,
+            gas_limit: tx.gas_limit,
+            gas_price: Some(tx.gas_price),
+            from: generate_address(from)?,
+            to: tx.to,
+            value: tx.value,
+            chain_id: tx.chain_id,
+            input: tx.input,
+            max_txn_fee: tx.max_txn_fee,
+
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            max_fee: None,
+            max_priority: None,
+            access_list: tx.access_list,
+            authorization_list: vec![],
+            max_fee_per_blob_gas: None,
+            max_blob_fee: None,
+            blob_versioned_hashes: vec![],
         })
     }
 
     pub(crate) fn from_eip1559(tx: ParsedEIP1559Transaction, from: PublicKey) -> Result<Self> {
         Ok(Self {
+            tx_type: EthereumTransactionType::FeeMarket,
             nonce: tx.nonce,
             gas_limit: tx.gas_limit,
             from: generate_address(from)?,
@@ -104,6 +243,59 @@ impl ParsedEthereumTransaction {
             max_fee: Some(tx.max_fee),
             max_priority: Some(tx.max_priority),
             gas_price: None,
+            access_list: tx.access_list,
+            authorization_list: vec![],
+            max_fee_per_blob_gas: None,
+            max_blob_fee: None,
+            blob_versioned_hashes: vec![],
+        })
+    }
+
+    pub(crate) fn from_eip7702(tx: ParsedSetCodeTransaction, from: PublicKey) -> Result<Self> {
+        Ok(Self {
+            tx_type: EthereumTransactionType::SetCode,
+            nonce: tx.nonce,
+            gas_limit: tx.gas_limit,
+            from: generate_address(from)?,
+            to: tx.to,
+            value: tx.value,
+            chain_id: tx.chain_id,
+            input: tx.input,
+            max_fee_per_gas: Some(tx.max_fee_per_gas),
+            max_priority_fee_per_gas: Some(tx.max_priority_fee_per_gas),
+            max_txn_fee: tx.max_txn_fee,
+            max_fee: Some(tx.max_fee),
+            max_priority: Some(tx.max_priority),
+            gas_price: None,
+            access_list: tx.access_list,
+            authorization_list: tx.authorization_list,
+            max_fee_per_blob_gas: None,
+            max_blob_fee: None,
+            blob_versioned_hashes: vec![],
+        })
+    }
+
+    pub(crate) fn from_eip4844(tx: ParsedBlobTransaction, from: PublicKey) -> Result<Self> {
+        Ok(Self {
+            tx_type: EthereumTransactionType::Blob,
+            nonce: tx.nonce,
+            gas_limit: tx.gas_limit,
+            from: generate_address(from)?,
+            to: tx.to,
+            value: tx.value,
+            chain_id: tx.chain_id,
+            input: tx.input,
+            max_fee_per_gas: Some(tx.max_fee_per_gas),
+            max_priority_fee_per_gas: Some(tx.max_priority_fee_per_gas),
+            max_txn_fee: tx.max_txn_fee,
+            max_fee: Some(tx.max_fee),
+            max_priority: Some(tx.max_priority),
+            gas_price: None,
+            access_list: tx.access_list,
+            authorization_list: vec![],
+            max_fee_per_blob_gas: Some(tx.max_fee_per_blob_gas),
+            max_blob_fee: Some(tx.max_blob_fee),
+            blob_versioned_hashes: tx.blob_versioned_hashes,
         })
     }
 }
@@ -175,6 +367,10 @@ impl TypedData {
         Self::from(Into::into(data), from)
     }
 
+    pub fn get_risk_summary(&self) -> Option<TypedDataSummary> {
+        summarize_typed_data(self)
+    }
+
     pub fn get_safe_tx_hash(&self) -> String {
         // bytes32 private constant SAFE_TX_TYPEHASH = 0xbb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8;
         // bytes32 safeTxHash = keccak256(
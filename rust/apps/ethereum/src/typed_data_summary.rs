@@ -0,0 +1,490 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use ethereum_types::U256;
+use serde_json::Value;
+
+use crate::structs::TypedData;
+
+// amounts at or above uint160 max can not be spent down in practice, wallets and dApps
+// use both type(uint256).max (EIP-2612) and type(uint160).max (Permit2) for "infinite"
+fn unlimited_threshold() -> U256 {
+    (U256::one() << 160) - U256::one()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypedDataKind {
+    Permit,
+    Permit2Single,
+    Permit2Batch,
+    Permit2TransferFrom,
+    SeaportOrder,
+}
+
+impl TypedDataKind {
+    pub fn get_name(&self) -> String {
+        match self {
+            TypedDataKind::Permit => "Permit".to_string(),
+            TypedDataKind::Permit2Single => "Permit2 PermitSingle".to_string(),
+            TypedDataKind::Permit2Batch => "Permit2 PermitBatch".to_string(),
+            TypedDataKind::Permit2TransferFrom => "Permit2 PermitTransferFrom".to_string(),
+            TypedDataKind::SeaportOrder => "Seaport Order".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TokenAllowance {
+    pub token: String,
+    pub amount: String,
+    pub is_unlimited: bool,
+    // Permit2 allowances outlive the signature, they stay valid until this timestamp
+    pub expiration: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ConsiderationItem {
+    pub recipient: String,
+    pub token: String,
+    pub amount: String,
+    pub is_signer: bool,
+}
+
+// what the signer actually gives away by signing an approval-style message
+#[derive(Clone, Debug)]
+pub struct TypedDataSummary {
+    pub kind: TypedDataKind,
+    pub owner: Option<String>,
+    pub spender: Option<String>,
+    pub tokens: Vec<TokenAllowance>,
+    pub deadline: Option<String>,
+    // only Seaport orders have consideration, anything not paid to the offerer is worth a look
+    pub consideration: Vec<ConsiderationItem>,
+    // set when the message is a known approval type but its fields could not be decoded
+    pub warning: Option<String>,
+}
+
+impl TypedDataSummary {
+    pub fn has_unlimited_amount(&self) -> bool {
+        self.tokens.iter().any(|t| t.is_unlimited)
+    }
+
+    pub fn has_recipient_not_signer(&self) -> bool {
+        self.consideration.iter().any(|c| !c.is_signer)
+    }
+
+    fn undecodable(kind: TypedDataKind) -> Self {
+        let warning = format!(
+            "Could not decode this {} message, check the raw data carefully",
+            kind.get_name()
+        );
+        Self {
+            kind,
+            owner: None,
+            spender: None,
+            tokens: Vec::new(),
+            deadline: None,
+            consideration: Vec::new(),
+            warning: Some(warning),
+        }
+    }
+}
+
+// None only for messages that are not an approval type we know of, a known type
+// that fails to decode still yields a summary so that it is never shown as harmless
+pub fn summarize_typed_data(typed_data: &TypedData) -> Option<TypedDataSummary> {
+    let kind = match typed_data.primary_type.as_str() {
+        "Permit" => TypedDataKind::Permit,
+        "PermitSingle" => TypedDataKind::Permit2Single,
+        "PermitBatch" => TypedDataKind::Permit2Batch,
+        "PermitTransferFrom"
+        | "PermitWitnessTransferFrom"
+        | "PermitBatchTransferFrom"
+        | "PermitBatchWitnessTransferFrom" => TypedDataKind::Permit2TransferFrom,
+        "OrderComponents" => TypedDataKind::SeaportOrder,
+        _ => return None,
+    };
+    let summary = serde_json::from_str::<Value>(&typed_data.message)
+        .ok()
+        .and_then(|message| match kind {
+            TypedDataKind::Permit => summarize_permit(&message, &typed_data.verifying_contract),
+            TypedDataKind::Permit2Single => summarize_permit_single(&message),
+            TypedDataKind::Permit2Batch => summarize_permit_batch(&message),
+            TypedDataKind::Permit2TransferFrom => summarize_permit_transfer_from(&message),
+            TypedDataKind::SeaportOrder => {
+                summarize_seaport_order(&message, &typed_data.verifying_contract, &typed_data.from)
+            }
+        });
+    Some(summary.unwrap_or_else(|| TypedDataSummary::undecodable(kind)))
+}
+
+// EIP-2612, the token itself is the verifying contract of the domain
+fn summarize_permit(message: &Value, verifying_contract: &str) -> Option<TypedDataSummary> {
+    let spender = get_string(message, "spender")?;
+    // DAI predates EIP-2612 and signs a boolean allowance instead of an amount
+    let (owner, amount, deadline) = match message.get("allowed") {
+        Some(allowed) => {
+            let amount = if allowed.as_bool().unwrap_or_default() {
+                U256::MAX
+            } else {
+                U256::zero()
+            };
+            (get_string(message, "holder"), amount, message.get("expiry"))
+        }
+        None => (
+            get_string(message, "owner"),
+            get_u256(message.get("value")?)?,
+            message.get("deadline"),
+        ),
+    };
+    Some(TypedDataSummary {
+        kind: TypedDataKind::Permit,
+        owner,
+        spender: Some(spender),
+        tokens: Vec::from([TokenAllowance {
+            token: verifying_contract.to_string(),
+            amount: amount.to_string(),
+            is_unlimited: amount >= unlimited_threshold(),
+            expiration: None,
+        }]),
+        deadline: deadline.and_then(get_u256).map(|d| d.to_string()),
+        consideration: Vec::new(),
+        warning: None,
+    })
+}
+
+fn summarize_permit_single(message: &Value) -> Option<TypedDataSummary> {
+    Some(TypedDataSummary {
+        kind: TypedDataKind::Permit2Single,
+        owner: None,
+        spender: get_string(message, "spender"),
+        tokens: Vec::from([parse_permit_details(message.get("details")?)?]),
+        deadline: message
+            .get("sigDeadline")
+            .and_then(get_u256)
+            .map(|d| d.to_string()),
+        consideration: Vec::new(),
+        warning: None,
+    })
+}
+
+fn summarize_permit_batch(message: &Value) -> Option<TypedDataSummary> {
+    let tokens = message
+        .get("details")?
+        .as_array()?
+        .iter()
+        .map(parse_permit_details)
+        .collect::<Option<Vec<TokenAllowance>>>()?;
+    Some(TypedDataSummary {
+        kind: TypedDataKind::Permit2Batch,
+        owner: None,
+        spender: get_string(message, "spender"),
+        tokens,
+        deadline: message
+            .get("sigDeadline")
+            .and_then(get_u256)
+            .map(|d| d.to_string()),
+        consideration: Vec::new(),
+        warning: None,
+    })
+}
+
+// signature transfers move the tokens directly, both the single and the batch variant
+// (with or without witness) share the same shape apart from `permitted` being an array
+fn summarize_permit_transfer_from(message: &Value) -> Option<TypedDataSummary> {
+    let permitted = message.get("permitted")?;
+    let tokens = match permitted.as_array() {
+        Some(items) => items
+            .iter()
+            .map(parse_token_permissions)
+            .collect::<Option<Vec<TokenAllowance>>>()?,
+        None => Vec::from([parse_token_permissions(permitted)?]),
+    };
+    Some(TypedDataSummary {
+        kind: TypedDataKind::Permit2TransferFrom,
+        owner: None,
+        spender: get_string(message, "spender"),
+        tokens,
+        deadline: message
+            .get("deadline")
+            .and_then(get_u256)
+            .map(|d| d.to_string()),
+        consideration: Vec::new(),
+        warning: None,
+    })
+}
+
+fn summarize_seaport_order(
+    message: &Value,
+    verifying_contract: &str,
+    signer: &str,
+) -> Option<TypedDataSummary> {
+    let owner = get_string(message, "offerer");
+    // without the signing key the offerer is the best guess of who is signing
+    let signer = match signer.is_empty() {
+        true => owner.clone()?,
+        false => signer.to_lowercase(),
+    };
+    let tokens = message
+        .get("offer")?
+        .as_array()?
+        .iter()
+        .map(|item| {
+            let amount = get_u256(item.get("endAmount")?)?;
+            Some(TokenAllowance {
+                token: get_string(item, "token")?,
+                amount: amount.to_string(),
+                is_unlimited: amount >= unlimited_threshold(),
+                expiration: None,
+            })
+        })
+        .collect::<Option<Vec<TokenAllowance>>>()?;
+    let consideration = message
+        .get("consideration")?
+        .as_array()?
+        .iter()
+        .map(|item| {
+            let recipient = get_string(item, "recipient")?;
+            Some(ConsiderationItem {
+                is_signer: recipient == signer,
+                recipient,
+                token: get_string(item, "token")?,
+                amount: get_u256(item.get("endAmount")?)?.to_string(),
+            })
+        })
+        .collect::<Option<Vec<ConsiderationItem>>>()?;
+    Some(TypedDataSummary {
+        kind: TypedDataKind::SeaportOrder,
+        owner,
+        // the offer items are pulled by the Seaport contract (or its conduit) once the order is filled
+        spender: Some(verifying_contract.to_string()),
+        tokens,
+        deadline: message
+            .get("endTime")
+            .and_then(get_u256)
+            .map(|d| d.to_string()),
+        consideration,
+        warning: None,
+    })
+}
+
+// Permit2 PermitDetails { token, amount, expiration, nonce }
+fn parse_permit_details(details: &Value) -> Option<TokenAllowance> {
+    let amount = get_u256(details.get("amount")?)?;
+    Some(TokenAllowance {
+        token: get_string(details, "token")?,
+        amount: amount.to_string(),
+        is_unlimited: amount >= unlimited_threshold(),
+        expiration: details
+            .get("expiration")
+            .and_then(get_u256)
+            .map(|e| e.to_string()),
+    })
+}
+
+// Permit2 TokenPermissions { token, amount }
+fn parse_token_permissions(permitted: &Value) -> Option<TokenAllowance> {
+    let amount = get_u256(permitted.get("amount")?)?;
+    Some(TokenAllowance {
+        token: get_string(permitted, "token")?,
+        amount: amount.to_string(),
+        is_unlimited: amount >= unlimited_threshold(),
+        expiration: None,
+    })
+}
+
+fn get_string(value: &Value, key: &str) -> Option<String> {
+    value.get(key)?.as_str().map(|s| s.to_lowercase())
+}
+
+// numbers show up as decimal strings, hex strings or plain json numbers depending on the dApp
+fn get_u256(value: &Value) -> Option<U256> {
+    match value {
+        Value::String(s) => match s.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16).ok(),
+            None => U256::from_dec_str(s).ok(),
+        },
+        Value::Number(n) => n.as_u64().map(U256::from),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate std;
+
+    fn typed_data(primary_type: &str, verifying_contract: &str, message: Value) -> TypedData {
+        TypedData {
+            name: "".to_string(),
+            version: "".to_string(),
+            chain_id: "1".to_string(),
+            verifying_contract: verifying_contract.to_string(),
+            salt: "".to_string(),
+            primary_type: primary_type.to_string(),
+            message: message.to_string(),
+            from: "".to_string(),
+            domain_separator: "".to_string(),
+            message_hash: "".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_summarize_permit() {
+        let message = serde_json::json!({
+            "owner": "0x9858EfFD232B4033E47d90003D41EC34EcaEda94",
+            "spender": "0x1111111254EEB25477B68fb85Ed929f73A960582",
+            "value": "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+            "nonce": "0",
+            "deadline": 1718000000
+        });
+        let data = typed_data(
+            "Permit",
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            message,
+        );
+        let summary = summarize_typed_data(&data).unwrap();
+        assert_eq!(TypedDataKind::Permit, summary.kind);
+        assert_eq!(
+            Some("0x1111111254eeb25477b68fb85ed929f73a960582".to_string()),
+            summary.spender
+        );
+        assert_eq!(
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            summary.tokens[0].token
+        );
+        assert!(summary.has_unlimited_amount());
+        assert_eq!(Some("1718000000".to_string()), summary.deadline);
+
+        let message = serde_json::json!({
+            "holder": "0x9858EfFD232B4033E47d90003D41EC34EcaEda94",
+            "spender": "0x1111111254EEB25477B68fb85Ed929f73A960582",
+            "nonce": 1,
+            "expiry": 0,
+            "allowed": true
+        });
+        let data = typed_data(
+            "Permit",
+            "0x6b175474e89094c44da98b954eedeac495271d0f",
+            message,
+        );
+        let summary = summarize_typed_data(&data).unwrap();
+        assert!(summary.has_unlimited_amount());
+        assert_eq!(
+            Some("0x9858effd232b4033e47d90003d41ec34ecaeda94".to_string()),
+            summary.owner
+        );
+    }
+
+    #[test]
+    fn test_summarize_permit2() {
+        let message = serde_json::json!({
+            "details": {
+                "token": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "amount": "1461501637330902918203684832716283019655932542975",
+                "expiration": "1720592000",
+                "nonce": "0"
+            },
+            "spender": "0x3fc91a3afd70395cd496c647d5a6cc9d4b2b7fad",
+            "sigDeadline": "1718001800"
+        });
+        let data = typed_data(
+            "PermitSingle",
+            "0x000000000022d473030f116ddee9f6b43ac78ba3",
+            message,
+        );
+        let summary = summarize_typed_data(&data).unwrap();
+        assert_eq!(TypedDataKind::Permit2Single, summary.kind);
+        assert!(summary.tokens[0].is_unlimited);
+        assert_eq!(Some("1720592000".to_string()), summary.tokens[0].expiration);
+        assert_eq!(Some("1718001800".to_string()), summary.deadline);
+
+        let message = serde_json::json!({
+            "permitted": [
+                { "token": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", "amount": "1000000" },
+                { "token": "0xdac17f958d2ee523a2206206994597c13d831ec7", "amount": "0x0f4240" }
+            ],
+            "spender": "0x3fc91a3afd70395cd496c647d5a6cc9d4b2b7fad",
+            "nonce": "7",
+            "deadline": "1718001800"
+        });
+        let data = typed_data(
+            "PermitBatchTransferFrom",
+            "0x000000000022d473030f116ddee9f6b43ac78ba3",
+            message,
+        );
+        let summary = summarize_typed_data(&data).unwrap();
+        assert_eq!(TypedDataKind::Permit2TransferFrom, summary.kind);
+        assert_eq!(2, summary.tokens.len());
+        assert_eq!("1000000", summary.tokens[1].amount);
+        assert!(!summary.has_unlimited_amount());
+    }
+
+    #[test]
+    fn test_summarize_seaport_order() {
+        let message = serde_json::json!({
+            "offerer": "0x9858effd232b4033e47d90003d41ec34ecaeda94",
+            "zone": "0x0000000000000000000000000000000000000000",
+            "offer": [{
+                "itemType": 2,
+                "token": "0xbc4ca0eda7647a8ab7c2061c2e118a18a936f13d",
+                "identifierOrCriteria": "1234",
+                "startAmount": "1",
+                "endAmount": "1"
+            }],
+            "consideration": [{
+                "itemType": 0,
+                "token": "0x0000000000000000000000000000000000000000",
+                "identifierOrCriteria": "0",
+                "startAmount": "1",
+                "endAmount": "1",
+                "recipient": "0x000000000000000000000000000000000000dead"
+            }],
+            "orderType": 0,
+            "startTime": "1718000000",
+            "endTime": "1720592000",
+            "zoneHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "salt": "0",
+            "conduitKey": "0x0000007b02230091a7ed01230072f7006a004d60a8d4e71d599b8104250f0000",
+            "counter": "0"
+        });
+        let data = typed_data(
+            "OrderComponents",
+            "0x00000000000000adc04c56bf30ac9d3c0aaf14dc",
+            message,
+        );
+        let summary = summarize_typed_data(&data).unwrap();
+        assert_eq!(TypedDataKind::SeaportOrder, summary.kind);
+        assert_eq!(1, summary.consideration.len());
+        assert_eq!(
+            "0x000000000000000000000000000000000000dead",
+            summary.consideration[0].recipient
+        );
+        assert_eq!("1", summary.consideration[0].amount);
+        assert!(summary.has_recipient_not_signer());
+        assert_eq!(Some("1720592000".to_string()), summary.deadline);
+    }
+
+    #[test]
+    fn test_summarize_undecodable_typed_data() {
+        // a permit without a spender must still be flagged
+        let message = serde_json::json!({ "owner": "0x9858effd232b4033e47d90003d41ec34ecaeda94" });
+        let data = typed_data(
+            "Permit",
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            message,
+        );
+        let summary = summarize_typed_data(&data).unwrap();
+        assert_eq!(TypedDataKind::Permit, summary.kind);
+        assert!(summary.warning.is_some());
+        assert!(summary.tokens.is_empty());
+    }
+
+    #[test]
+    fn test_summarize_other_typed_data() {
+        let data = typed_data("Mail", "", serde_json::json!({ "contents": "hello" }));
+        assert!(summarize_typed_data(&data).is_none());
+    }
+}
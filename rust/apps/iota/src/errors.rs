@@ -54,3 +54,9 @@ impl From<hex::FromHexError> for IotaError {
         Self::InvalidData(format!("hex operation failed {}", value))
     }
 }
+
+impl From<serde_json::Error> for IotaError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::InvalidData(format!("serde_json operation failed {}", value))
+    }
+}
@@ -6,5 +6,55 @@ extern crate alloc;
 #[macro_use]
 extern crate std;
 
+use crate::errors::{IotaError, Result};
+use crate::parser::structs::ParsedIotaTx;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use cryptoxide::hashing::blake2b_256;
+
 pub mod address;
 pub mod errors;
+pub mod parser;
+
+pub fn parse_intent(intent: &[u8]) -> Result<ParsedIotaTx> {
+    ParsedIotaTx::build(intent)
+}
+
+// the signature commits to the blake2b hash of the whole intent message, only sign what we can display
+pub fn sign_intent(seed: &[u8], path: &String, intent: &[u8]) -> Result<[u8; 64]> {
+    parse_intent(intent)?;
+    sign_hash(seed, path, &blake2b_256(intent))
+}
+
+pub fn sign_hash(seed: &[u8], path: &String, hash: &[u8]) -> Result<[u8; 64]> {
+    keystore::algorithms::ed25519::slip10_ed25519::sign_message_by_seed(seed, path, hash)
+        .map_err(|e| IotaError::SignFailure(e.to_string()))
+}
+
+pub fn get_public_key(seed: &[u8], path: &String) -> Result<Vec<u8>> {
+    let public_key =
+        keystore::algorithms::ed25519::slip10_ed25519::get_public_key_by_seed(seed, path)?;
+    Ok(public_key.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_sign_intent() {
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let path = "m/44'/4218'/0'/0'/0'".to_string();
+        let intent = hex::decode("00000000000200201ff915a5e9e32fdbe0135535b6c69a00a9809aaf7f7c0275d3239ca79db20d640008002f685900000000020200010101000101020000010000193a4811b7207ac7a861f840552f9c718172400f4c46bdef5935008a7977fb0401a2e3e42930675d9571a467eb5d4b22553c93ccb84e9097972e02c490b4e7a22ab73200000000000020176c4727433105da34209f04ac3f22e192a2573d7948cb2fabde7d13a7f4f149193a4811b7207ac7a861f840552f9c718172400f4c46bdef5935008a7977fb04e80300000000000080841e000000000000").unwrap();
+        let signature = sign_intent(&seed, &path, &intent).unwrap();
+        assert_eq!("b08b5496aff97d88cb2f4a1fac9fb9d3a2e53369991e39a1de98653a79288a27627c73667bda95047c291ccff272dcb4794dc2bcc4bc613ad3ae8cc32bb18b08", hex::encode(signature));
+        assert_eq!(
+            "931c54b678837cf96a49ee1d1122027fabadf0aee97d9f9094187db8be396f63",
+            hex::encode(get_public_key(&seed, &path).unwrap())
+        );
+
+        // refuse to blind sign what can not be parsed
+        assert!(sign_intent(&seed, &path, &intent[..intent.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,632 @@
+pub mod overview;
+mod reader;
+pub mod structs;
+mod transaction;
+
+use crate::errors::{IotaError, Result};
+use crate::parser::overview::{
+    IotaTxOverview, IotaTxOverviewBridge, IotaTxOverviewGeneral, IotaTxOverviewMessage,
+    IotaTxOverviewStake, IotaTxOverviewTransfer, IotaTxOverviewUnstake,
+};
+use crate::parser::reader::Reader;
+use crate::parser::structs::{IotaTxDisplayType, ParsedIotaTx};
+use crate::parser::transaction::{
+    address_to_string, Argument, CallArg, Command, IotaAddress, ObjectArg, ObjectRef,
+    ProgrammableTransaction, TransactionData, TransactionExpiration,
+};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde_json::{json, Value};
+
+pub const NANOS_PER_IOTA: u64 = 1_000_000_000;
+
+// intent prefix is [scope, version, app id], IOTA only uses version 0 and app id 0
+pub const INTENT_SCOPE_TRANSACTION_DATA: u8 = 0;
+pub const INTENT_SCOPE_PERSONAL_MESSAGE: u8 = 3;
+const INTENT_VERSION: u8 = 0;
+const INTENT_APP_ID: u8 = 0;
+
+// the transaction does not state the chain it is meant for
+const NETWORK: &str = "IOTA";
+
+// ISC core contracts of the IOTA EVM chain
+const ISC_PACKAGE_ADDRESS: &str =
+    "0x1b33a3cf7eb5dde04ed7ae571db1763006811ff6b7bb35b3d1c780de153af9dd";
+// request::create_and_send_request(anchor, assets, contract, function, args, ...)
+const ISC_REQUEST_ARGS_INDEX: usize = 4;
+const ISC_AGENT_ID_ETHEREUM: u8 = 3;
+
+const IOTA_SYSTEM_ADDRESS: IotaAddress = {
+    let mut address = [0u8; 32];
+    address[31] = 3;
+    address
+};
+
+impl ParsedIotaTx {
+    pub fn build(intent: &[u8]) -> Result<Self> {
+        if intent.len() < 3 {
+            return Err(IotaError::InvalidData("intent too short".to_string()));
+        }
+        if intent[1] != INTENT_VERSION || intent[2] != INTENT_APP_ID {
+            return Err(IotaError::InvalidData(format!(
+                "unsupported intent {}",
+                hex::encode(&intent[..3])
+            )));
+        }
+        match intent[0] {
+            INTENT_SCOPE_TRANSACTION_DATA => {
+                Self::build_transaction(&TransactionData::from_bytes(&intent[3..])?)
+            }
+            INTENT_SCOPE_PERSONAL_MESSAGE => Self::build_message(&intent[3..]),
+            scope => Err(IotaError::InvalidData(format!(
+                "unsupported intent scope {}",
+                scope
+            ))),
+        }
+    }
+
+    fn build_transaction(tx: &TransactionData) -> Result<Self> {
+        let from = address_to_string(&tx.sender);
+        let pt = &tx.kind;
+        let (display_type, overview) = if let Some(overview) = Self::build_transfer(pt, &from) {
+            (IotaTxDisplayType::Transfer, overview)
+        } else if let Some(overview) = Self::build_stake(pt, &from) {
+            (IotaTxDisplayType::Stake, overview)
+        } else if let Some(overview) = Self::build_unstake(pt, &from) {
+            (IotaTxDisplayType::Unstake, overview)
+        } else if let Some(overview) = Self::build_bridge(pt, &from) {
+            (IotaTxDisplayType::Bridge, overview)
+        } else {
+            (
+                IotaTxDisplayType::General,
+                IotaTxOverview::General(IotaTxOverviewGeneral {
+                    from,
+                    action_list: pt.commands.iter().map(Command::name).collect(),
+                }),
+            )
+        };
+        Ok(Self {
+            display_type,
+            overview,
+            detail: Self::build_detail(tx)?,
+            network: NETWORK.to_string(),
+            max_fee: Some(format_amount(tx.gas_data.budget)),
+        })
+    }
+
+    // IOTA only ever leaves the wallet through coins split from the gas coin or the gas coin itself
+    fn build_transfer(pt: &ProgrammableTransaction, from: &str) -> Option<IotaTxOverview> {
+        let mut recipient: Option<IotaAddress> = None;
+        let mut total: u64 = 0;
+        let mut max = false;
+        for command in pt.commands.iter() {
+            match command {
+                Command::SplitCoins(Argument::GasCoin, _) => {}
+                Command::TransferObjects(objects, to) => {
+                    let to = pt.pure_address(to)?;
+                    if *recipient.get_or_insert(to) != to {
+                        return None;
+                    }
+                    for object in objects.iter() {
+                        match object {
+                            Argument::GasCoin => max = true,
+                            _ => total = total.checked_add(gas_split_amount(pt, object)?)?,
+                        }
+                    }
+                }
+                _ => return None,
+            }
+        }
+        let amount = if max {
+            "Max".to_string()
+        } else {
+            format_amount(total)
+        };
+        Some(IotaTxOverview::Transfer(IotaTxOverviewTransfer {
+            from: from.to_string(),
+            to: address_to_string(&recipient?),
+            amount,
+        }))
+    }
+
+    // 0x3::iota_system::request_add_stake(system_state, coin, validator)
+    fn build_stake(pt: &ProgrammableTransaction, from: &str) -> Option<IotaTxOverview> {
+        let call = single_move_call(pt)?;
+        if !call.is(&IOTA_SYSTEM_ADDRESS, "iota_system", "request_add_stake")
+            || call.arguments.len() != 3
+        {
+            return None;
+        }
+        let amount = gas_split_amount(pt, &call.arguments[1])?;
+        let validator = pt.pure_address(&call.arguments[2])?;
+        Some(IotaTxOverview::Stake(IotaTxOverviewStake {
+            from: from.to_string(),
+            validator: address_to_string(&validator),
+            amount: format_amount(amount),
+        }))
+    }
+
+    // 0x3::iota_system::request_withdraw_stake(system_state, staked_iota)
+    fn build_unstake(pt: &ProgrammableTransaction, from: &str) -> Option<IotaTxOverview> {
+        let call = single_move_call(pt)?;
+        if pt.commands.len() != 1
+            || !call.is(
+                &IOTA_SYSTEM_ADDRESS,
+                "iota_system",
+                "request_withdraw_stake",
+            )
+            || call.arguments.len() != 2
+        {
+            return None;
+        }
+        let staked_iota = pt.object_id(&call.arguments[1])?;
+        Some(IotaTxOverview::Unstake(IotaTxOverviewUnstake {
+            from: from.to_string(),
+            staked_iota: address_to_string(&staked_iota),
+        }))
+    }
+
+    // the L1 to L2 bridge puts the coins into an assets bag and sends it with a request to the
+    // ISC package, the evm recipient is the agent id passed as the argument of the request
+    fn build_bridge(pt: &ProgrammableTransaction, from: &str) -> Option<IotaTxOverview> {
+        let mut request = None;
+        for command in pt.commands.iter() {
+            match command {
+                Command::SplitCoins(Argument::GasCoin, _) => {}
+                Command::MoveCall(call)
+                    if address_to_string(&call.package) == ISC_PACKAGE_ADDRESS =>
+                {
+                    // a single request, its agent id is the only recipient shown
+                    let is_request =
+                        call.module == "request" && call.function == "create_and_send_request";
+                    if is_request && request.replace(call).is_some() {
+                        return None;
+                    }
+                }
+                _ => return None,
+            }
+        }
+        let request = request?;
+        let to = match pt.input(request.arguments.get(ISC_REQUEST_ARGS_INDEX)?)? {
+            CallArg::Pure(bytes) => evm_agent_id(bytes)?,
+            _ => return None,
+        };
+        let amount = pt
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                Command::SplitCoins(Argument::GasCoin, amounts) => Some(amounts),
+                _ => None,
+            })
+            .flatten()
+            .try_fold(0u64, |acc, amount| acc.checked_add(pt.pure_u64(amount)?))?;
+        Some(IotaTxOverview::Bridge(IotaTxOverviewBridge {
+            from: from.to_string(),
+            to,
+            amount: format_amount(amount),
+        }))
+    }
+
+    fn build_message(data: &[u8]) -> Result<Self> {
+        // personal messages are bcs encoded bytes, some dapps send them raw
+        let mut reader = Reader::new(data);
+        let message = match reader.read_vec_u8() {
+            Ok(message) if reader.is_empty() => message,
+            _ => data.to_vec(),
+        };
+        let message = match String::from_utf8(message.clone()) {
+            Ok(text) if !app_utils::is_cjk(&text) => text,
+            _ => format!("0x{}", hex::encode(&message)),
+        };
+        Ok(Self {
+            display_type: IotaTxDisplayType::Message,
+            detail: serde_json::to_string(&json!({ "Message": message }))?,
+            overview: IotaTxOverview::Message(IotaTxOverviewMessage { message }),
+            network: NETWORK.to_string(),
+            max_fee: None,
+        })
+    }
+
+    fn build_detail(tx: &TransactionData) -> Result<String> {
+        let pt = &tx.kind;
+        let expiration = match tx.expiration {
+            TransactionExpiration::None => "None".to_string(),
+            TransactionExpiration::Epoch(epoch) => format!("Epoch {}", epoch),
+        };
+        let detail = json!({
+            "Sender": address_to_string(&tx.sender),
+            "Inputs": pt.inputs.iter().map(input_detail).collect::<Vec<_>>(),
+            "Commands": pt.commands.iter().map(command_detail).collect::<Vec<_>>(),
+            "Gas Owner": address_to_string(&tx.gas_data.owner),
+            "Gas Price": tx.gas_data.price.to_string(),
+            "Gas Budget": format_amount(tx.gas_data.budget),
+            "Gas Payment": tx.gas_data.payment.iter().map(object_detail).collect::<Vec<_>>(),
+            "Expiration": expiration,
+        });
+        Ok(serde_json::to_string(&detail)?)
+    }
+}
+
+pub fn format_amount(value: u64) -> String {
+    let whole = value / NANOS_PER_IOTA;
+    let fraction = value % NANOS_PER_IOTA;
+    if fraction == 0 {
+        return format!("{} IOTA", whole);
+    }
+    let fraction = format!("{:09}", fraction);
+    format!("{}.{} IOTA", whole, fraction.trim_end_matches('0'))
+}
+
+// args is a bcs vector<vector<u8>> holding the single ethereum agent id of the recipient
+fn evm_agent_id(bytes: &[u8]) -> Option<String> {
+    match bytes {
+        [1, 21, ISC_AGENT_ID_ETHEREUM, address @ ..] if address.len() == 20 => {
+            Some(format!("0x{}", hex::encode(address)))
+        }
+        _ => None,
+    }
+}
+
+fn single_move_call(pt: &ProgrammableTransaction) -> Option<&transaction::MoveCall> {
+    let mut call = None;
+    for command in pt.commands.iter() {
+        match command {
+            Command::MoveCall(c) if call.is_none() => call = Some(c),
+            Command::SplitCoins(Argument::GasCoin, _) => {}
+            _ => return None,
+        }
+    }
+    call
+}
+
+// amount of IOTA carried by `argument` when it is a coin split from the gas coin
+fn gas_split_amount(pt: &ProgrammableTransaction, argument: &Argument) -> Option<u64> {
+    let (index, nested) = match argument {
+        Argument::Result(index) => (*index, None),
+        Argument::NestedResult(index, nested) => (*index, Some(*nested)),
+        _ => return None,
+    };
+    match pt.commands.get(index as usize)? {
+        Command::SplitCoins(Argument::GasCoin, amounts) => match nested {
+            Some(nested) => pt.pure_u64(amounts.get(nested as usize)?),
+            None if amounts.len() == 1 => pt.pure_u64(&amounts[0]),
+            None => None,
+        },
+        _ => None,
+    }
+}
+
+fn object_detail(object: &ObjectRef) -> Value {
+    json!({
+        "Object ID": address_to_string(&object.object_id),
+        "Version": object.version.to_string(),
+        "Digest": hex::encode(object.digest),
+    })
+}
+
+fn input_detail(input: &CallArg) -> Value {
+    match input {
+        CallArg::Pure(bytes) => json!({ "Pure": hex::encode(bytes) }),
+        CallArg::Object(ObjectArg::ImmOrOwnedObject(object)) => {
+            json!({ "Object": object_detail(object) })
+        }
+        CallArg::Object(ObjectArg::Receiving(object)) => {
+            json!({ "Receiving Object": object_detail(object) })
+        }
+        CallArg::Object(ObjectArg::SharedObject {
+            id,
+            initial_shared_version,
+            mutable,
+        }) => json!({
+            "Shared Object": {
+                "Object ID": address_to_string(id),
+                "Initial Shared Version": initial_shared_version.to_string(),
+                "Mutable": mutable,
+            }
+        }),
+    }
+}
+
+fn arguments_detail(arguments: &[Argument]) -> Vec<String> {
+    arguments.iter().map(|a| a.to_string()).collect()
+}
+
+fn command_detail(command: &Command) -> Value {
+    match command {
+        Command::MoveCall(call) => json!({
+            "Move Call": {
+                "Function": call.to_string(),
+                "Type Arguments": call.type_arguments.iter().map(|t| t.to_string()).collect::<Vec<_>>(),
+                "Arguments": arguments_detail(&call.arguments),
+            }
+        }),
+        Command::TransferObjects(objects, recipient) => json!({
+            "Transfer Objects": {
+                "Objects": arguments_detail(objects),
+                "Recipient": recipient.to_string(),
+            }
+        }),
+        Command::SplitCoins(coin, amounts) => json!({
+            "Split Coins": {
+                "Coin": coin.to_string(),
+                "Amounts": arguments_detail(amounts),
+            }
+        }),
+        Command::MergeCoins(destination, sources) => json!({
+            "Merge Coins": {
+                "Destination": destination.to_string(),
+                "Sources": arguments_detail(sources),
+            }
+        }),
+        Command::Publish(modules, dependencies) => json!({
+            "Publish": {
+                "Modules": modules.len(),
+                "Dependencies": dependencies.iter().map(address_to_string).collect::<Vec<_>>(),
+            }
+        }),
+        Command::MakeMoveVec(type_tag, elements) => json!({
+            "Make Move Vector": {
+                "Type": type_tag.as_ref().map(|t| t.to_string()),
+                "Elements": arguments_detail(elements),
+            }
+        }),
+        Command::Upgrade(modules, dependencies, package, ticket) => json!({
+            "Upgrade": {
+                "Modules": modules.len(),
+                "Dependencies": dependencies.iter().map(address_to_string).collect::<Vec<_>>(),
+                "Package": address_to_string(package),
+                "Ticket": ticket.to_string(),
+            }
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_intent;
+
+    const SENDER: &str = "0x193a4811b7207ac7a861f840552f9c718172400f4c46bdef5935008a7977fb04";
+    const RECIPIENT: &str = "0x1ff915a5e9e32fdbe0135535b6c69a00a9809aaf7f7c0275d3239ca79db20d64";
+
+    #[test]
+    fn test_parse_transfer() {
+        let intent = hex::decode("00000000000200201ff915a5e9e32fdbe0135535b6c69a00a9809aaf7f7c0275d3239ca79db20d640008002f685900000000020200010101000101020000010000193a4811b7207ac7a861f840552f9c718172400f4c46bdef5935008a7977fb0401a2e3e42930675d9571a467eb5d4b22553c93ccb84e9097972e02c490b4e7a22ab73200000000000020176c4727433105da34209f04ac3f22e192a2573d7948cb2fabde7d13a7f4f149193a4811b7207ac7a861f840552f9c718172400f4c46bdef5935008a7977fb04e80300000000000080841e000000000000").unwrap();
+        let parsed = parse_intent(&intent).unwrap();
+        assert_eq!(IotaTxDisplayType::Transfer, parsed.display_type);
+        assert_eq!(Some("0.002 IOTA".to_string()), parsed.max_fee);
+        match parsed.overview {
+            IotaTxOverview::Transfer(overview) => {
+                assert_eq!(SENDER, overview.from);
+                assert_eq!(RECIPIENT, overview.to);
+                assert_eq!("1.5 IOTA", overview.amount);
+            }
+            _ => panic!("error overview type"),
+        }
+        let detail: Value = serde_json::from_str(&parsed.detail).unwrap();
+        assert_eq!(SENDER, detail["Sender"]);
+        assert_eq!("1000", detail["Gas Price"]);
+        assert_eq!("None", detail["Expiration"]);
+        assert_eq!(
+            json!({"Split Coins": {"Coin": "GasCoin", "Amounts": ["Input(1)"]}}),
+            detail["Commands"][0]
+        );
+        assert_eq!(
+            json!({"Transfer Objects": {"Objects": ["Result(0)"], "Recipient": "Input(0)"}}),
+            detail["Commands"][1]
+        );
+
+        // trailing bytes are not part of what the user reviews
+        let mut intent = intent;
+        intent.push(0);
+        assert!(parse_intent(&intent).is_err());
+    }
+
+    #[test]
+    fn test_parse_transfer_max() {
+        let intent = hex::decode("00000000000100201ff915a5e9e32fdbe0135535b6c69a00a9809aaf7f7c0275d3239ca79db20d6401010100010000193a4811b7207ac7a861f840552f9c718172400f4c46bdef5935008a7977fb0401a2e3e42930675d9571a467eb5d4b22553c93ccb84e9097972e02c490b4e7a22ab73200000000000020176c4727433105da34209f04ac3f22e192a2573d7948cb2fabde7d13a7f4f149193a4811b7207ac7a861f840552f9c718172400f4c46bdef5935008a7977fb04e80300000000000080841e000000000000").unwrap();
+        let parsed = parse_intent(&intent).unwrap();
+        match parsed.overview {
+            IotaTxOverview::Transfer(overview) => {
+                assert_eq!(RECIPIENT, overview.to);
+                assert_eq!("Max", overview.amount);
+            }
+            _ => panic!("error overview type"),
+        }
+    }
+
+    #[test]
+    fn test_parse_stake() {
+        let intent = hex::decode("0000000000030101000000000000000000000000000000000000000000000000000000000000000501000000000000000100080010a5d4e80000000020a2e3e42930675d9571a467eb5d4b22553c93ccb84e9097972e02c490b4e7a2ff020200010101000000000000000000000000000000000000000000000000000000000000000000030b696f74615f73797374656d11726571756573745f6164645f7374616b650003010000020000010200193a4811b7207ac7a861f840552f9c718172400f4c46bdef5935008a7977fb0401a2e3e42930675d9571a467eb5d4b22553c93ccb84e9097972e02c490b4e7a22ab73200000000000020176c4727433105da34209f04ac3f22e192a2573d7948cb2fabde7d13a7f4f149193a4811b7207ac7a861f840552f9c718172400f4c46bdef5935008a7977fb04e803000000000000404b4c000000000000").unwrap();
+        let parsed = parse_intent(&intent).unwrap();
+        assert_eq!(IotaTxDisplayType::Stake, parsed.display_type);
+        match parsed.overview {
+            IotaTxOverview::Stake(overview) => {
+                assert_eq!(SENDER, overview.from);
+                assert_eq!(
+                    "0xa2e3e42930675d9571a467eb5d4b22553c93ccb84e9097972e02c490b4e7a2ff",
+                    overview.validator
+                );
+                assert_eq!("1000 IOTA", overview.amount);
+            }
+            _ => panic!("error overview type"),
+        }
+        let detail: Value = serde_json::from_str(&parsed.detail).unwrap();
+        assert_eq!(
+            json!({"Shared Object": {
+                "Object ID": "0x0000000000000000000000000000000000000000000000000000000000000005",
+                "Initial Shared Version": "1",
+                "Mutable": true,
+            }}),
+            detail["Inputs"][0]
+        );
+        assert_eq!(
+            "0x3::iota_system::request_add_stake",
+            detail["Commands"][1]["Move Call"]["Function"]
+        );
+    }
+
+    #[test]
+    fn test_parse_unstake() {
+        let intent = hex::decode("000000000002010100000000000000000000000000000000000000000000000000000000000000050100000000000000010100d833a8eabc697a0b2e23740aca7be9b0b9e1560a39d2f390cf2534e94429f91ced0c00000000000020190ca0d64215ac63f50dbffa47563404182304e0c10ea30b5e4d671b7173a34c010000000000000000000000000000000000000000000000000000000000000000030b696f74615f73797374656d16726571756573745f77697468647261775f7374616b650002010000010100193a4811b7207ac7a861f840552f9c718172400f4c46bdef5935008a7977fb0401a2e3e42930675d9571a467eb5d4b22553c93ccb84e9097972e02c490b4e7a22ab73200000000000020176c4727433105da34209f04ac3f22e192a2573d7948cb2fabde7d13a7f4f149193a4811b7207ac7a861f840552f9c718172400f4c46bdef5935008a7977fb04e803000000000000404b4c0000000000016400000000000000").unwrap();
+        let parsed = parse_intent(&intent).unwrap();
+        assert_eq!(IotaTxDisplayType::Unstake, parsed.display_type);
+        match parsed.overview {
+            IotaTxOverview::Unstake(overview) => {
+                assert_eq!(
+                    "0xd833a8eabc697a0b2e23740aca7be9b0b9e1560a39d2f390cf2534e94429f91c",
+                    overview.staked_iota
+                );
+            }
+            _ => panic!("error overview type"),
+        }
+        let detail: Value = serde_json::from_str(&parsed.detail).unwrap();
+        assert_eq!("Epoch 100", detail["Expiration"]);
+    }
+
+    #[test]
+    fn test_parse_move_call() {
+        let intent = hex::decode("0000000000020100d833a8eabc697a0b2e23740aca7be9b0b9e1560a39d2f390cf2534e94429f91ced0c00000000000020190ca0d64215ac63f50dbffa47563404182304e0c10ea30b5e4d671b7173a34c00090140420f000000000001000000000000000000000000000000000000000000000000000000000000000002037061790973706c69745f7665630107000000000000000000000000000000000000000000000000000000000000000204696f746104494f54410002010000010100193a4811b7207ac7a861f840552f9c718172400f4c46bdef5935008a7977fb0401a2e3e42930675d9571a467eb5d4b22553c93ccb84e9097972e02c490b4e7a22ab73200000000000020176c4727433105da34209f04ac3f22e192a2573d7948cb2fabde7d13a7f4f149193a4811b7207ac7a861f840552f9c718172400f4c46bdef5935008a7977fb04e80300000000000040420f000000000000").unwrap();
+        let parsed = parse_intent(&intent).unwrap();
+        assert_eq!(IotaTxDisplayType::General, parsed.display_type);
+        match parsed.overview {
+            IotaTxOverview::General(overview) => {
+                assert_eq!(SENDER, overview.from);
+                assert_eq!(
+                    vec!["0x2::pay::split_vec".to_string()],
+                    overview.action_list
+                );
+            }
+            _ => panic!("error overview type"),
+        }
+        let detail: Value = serde_json::from_str(&parsed.detail).unwrap();
+        assert_eq!(
+            json!({"Move Call": {
+                "Function": "0x2::pay::split_vec",
+                "Type Arguments": ["0x2::iota::IOTA"],
+                "Arguments": ["Input(0)", "Input(1)"],
+            }}),
+            detail["Commands"][0]
+        );
+        assert_eq!(
+            "0xd833a8eabc697a0b2e23740aca7be9b0b9e1560a39d2f390cf2534e94429f91c",
+            detail["Inputs"][0]["Object"]["Object ID"]
+        );
+    }
+
+    #[test]
+    fn test_parse_bridge() {
+        let isc_package: IotaAddress = hex::decode(&ISC_PACKAGE_ADDRESS[2..])
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let recipient = hex::decode("0d2e1b4b5a0a8e7c5b1d25c3c9f9a0b2d6c9b86a").unwrap();
+        let move_call = |package: IotaAddress, module: &str, function: &str, arguments| {
+            Command::MoveCall(transaction::MoveCall {
+                package,
+                module: module.to_string(),
+                function: function.to_string(),
+                type_arguments: vec![],
+                arguments,
+            })
+        };
+        let build = |package: IotaAddress, args: Argument| {
+            let mut agent_id = vec![1, 21, ISC_AGENT_ID_ETHEREUM];
+            agent_id.extend_from_slice(&recipient);
+            let mut decoy = vec![1, 21, ISC_AGENT_ID_ETHEREUM];
+            decoy.extend_from_slice(&[0xee; 20]);
+            ProgrammableTransaction {
+                inputs: vec![
+                    CallArg::Pure([0xaa; 32].to_vec()),
+                    CallArg::Pure(1_500_000_000u64.to_le_bytes().to_vec()),
+                    CallArg::Pure(agent_id),
+                    CallArg::Pure(decoy),
+                ],
+                commands: vec![
+                    Command::SplitCoins(Argument::GasCoin, vec![Argument::Input(1)]),
+                    move_call(package, "assets_bag", "new", vec![]),
+                    move_call(
+                        package,
+                        "assets_bag",
+                        "place_coin",
+                        vec![Argument::Result(1), Argument::Result(0)],
+                    ),
+                    move_call(
+                        package,
+                        "request",
+                        "create_and_send_request",
+                        vec![
+                            Argument::Input(0),
+                            Argument::Result(1),
+                            Argument::Input(0),
+                            Argument::Input(0),
+                            args,
+                        ],
+                    ),
+                ],
+            }
+        };
+
+        match ParsedIotaTx::build_bridge(&build(isc_package, Argument::Input(2)), SENDER) {
+            Some(IotaTxOverview::Bridge(overview)) => {
+                assert_eq!(SENDER, overview.from);
+                assert_eq!(format!("0x{}", hex::encode(&recipient)), overview.to);
+                assert_eq!("1.5 IOTA", overview.amount);
+            }
+            _ => panic!("error overview type"),
+        }
+        // the recipient is the agent id the request is sent with
+        match ParsedIotaTx::build_bridge(&build(isc_package, Argument::Input(3)), SENDER) {
+            Some(IotaTxOverview::Bridge(overview)) => {
+                assert_eq!(format!("0x{}", hex::encode([0xee; 20])), overview.to);
+            }
+            _ => panic!("error overview type"),
+        }
+        // look alike modules of another package are not the bridge
+        assert!(
+            ParsedIotaTx::build_bridge(&build([0xbb; 32], Argument::Input(2)), SENDER).is_none()
+        );
+        assert!(
+            ParsedIotaTx::build_bridge(&build(isc_package, Argument::Input(1)), SENDER).is_none()
+        );
+    }
+
+    #[test]
+    fn test_parse_message() {
+        let parsed = parse_intent(&hex::decode("0300000b48656c6c6f2c20494f5441").unwrap()).unwrap();
+        assert_eq!(IotaTxDisplayType::Message, parsed.display_type);
+        assert_eq!(None, parsed.max_fee);
+        match parsed.overview {
+            IotaTxOverview::Message(overview) => assert_eq!("Hello, IOTA", overview.message),
+            _ => panic!("error overview type"),
+        }
+
+        // raw bytes without the bcs length prefix
+        let parsed = parse_intent(&hex::decode("03000048656c6c6f2c20494f5441").unwrap()).unwrap();
+        match parsed.overview {
+            IotaTxOverview::Message(overview) => assert_eq!("Hello, IOTA", overview.message),
+            _ => panic!("error overview type"),
+        }
+
+        let parsed = parse_intent(&hex::decode("03000002ff00").unwrap()).unwrap();
+        match parsed.overview {
+            IotaTxOverview::Message(overview) => assert_eq!("0xff00", overview.message),
+            _ => panic!("error overview type"),
+        }
+    }
+
+    #[test]
+    fn test_parse_unsupported_intent() {
+        assert!(parse_intent(&hex::decode("0100000b48656c6c6f2c20494f5441").unwrap()).is_err());
+        assert!(parse_intent(&hex::decode("0300010b48656c6c6f2c20494f5441").unwrap()).is_err());
+        assert!(parse_intent(&hex::decode("0000").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!("0 IOTA", format_amount(0));
+        assert_eq!("0.000000001 IOTA", format_amount(1));
+        assert_eq!("12.3 IOTA", format_amount(12_300_000_000));
+    }
+}
@@ -0,0 +1,51 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Debug, Clone)]
+pub struct IotaTxOverviewTransfer {
+    pub from: String,
+    pub to: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct IotaTxOverviewStake {
+    pub from: String,
+    pub validator: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct IotaTxOverviewUnstake {
+    pub from: String,
+    pub staked_iota: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct IotaTxOverviewBridge {
+    pub from: String,
+    // evm address on the L2 side
+    pub to: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct IotaTxOverviewGeneral {
+    pub from: String,
+    pub action_list: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IotaTxOverviewMessage {
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum IotaTxOverview {
+    Transfer(IotaTxOverviewTransfer),
+    Stake(IotaTxOverviewStake),
+    Unstake(IotaTxOverviewUnstake),
+    Bridge(IotaTxOverviewBridge),
+    General(IotaTxOverviewGeneral),
+    Message(IotaTxOverviewMessage),
+}
@@ -0,0 +1,147 @@
+use crate::errors::{IotaError, Result};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+// minimal BCS reader, IOTA transactions are plain BCS so there is no need to pull in
+// the whole move type system to decode them
+pub struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offset >= self.data.len()
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .ok_or(IotaError::InvalidLength)?;
+        if end > self.data.len() {
+            return Err(IotaError::UnexpectedEof);
+        }
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            v => Err(IotaError::InvalidData(format!("invalid bool {}", v))),
+        }
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut result = [0u8; N];
+        result.copy_from_slice(self.read_bytes(N)?);
+        Ok(result)
+    }
+
+    // BCS lengths and enum tags are canonical ULEB128 values that fit in a u32
+    pub fn read_uleb128(&mut self) -> Result<usize> {
+        let mut value: u64 = 0;
+        for shift in (0..32).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                if shift > 0 && byte == 0 {
+                    return Err(IotaError::InvalidData(
+                        "non canonical uleb128 value".to_string(),
+                    ));
+                }
+                if value > u32::MAX as u64 {
+                    return Err(IotaError::InvalidLength);
+                }
+                return Ok(value as usize);
+            }
+        }
+        Err(IotaError::InvalidLength)
+    }
+
+    pub fn read_len(&mut self) -> Result<usize> {
+        let len = self.read_uleb128()?;
+        // every element takes at least one byte, refuse lengths the input can not hold
+        if len > self.data.len() - self.offset {
+            return Err(IotaError::UnexpectedEof);
+        }
+        Ok(len)
+    }
+
+    pub fn read_vec_u8(&mut self) -> Result<Vec<u8>> {
+        let len = self.read_len()?;
+        Ok(self.read_bytes(len)?.to_vec())
+    }
+
+    pub fn read_string(&mut self) -> Result<String> {
+        String::from_utf8(self.read_vec_u8()?)
+            .map_err(|_e| IotaError::InvalidData("invalid utf8 string".to_string()))
+    }
+
+    pub fn read_vec<T>(&mut self, mut f: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let len = self.read_len()?;
+        let mut result = Vec::with_capacity(len);
+        for _ in 0..len {
+            result.push(f(self)?);
+        }
+        Ok(result)
+    }
+
+    pub fn read_option<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<Option<T>> {
+        match self.read_u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(f(self)?)),
+            v => Err(IotaError::InvalidData(format!("invalid option tag {}", v))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_uleb128() {
+        assert_eq!(1, Reader::new(&[0x01]).read_uleb128().unwrap());
+        assert_eq!(128, Reader::new(&[0x80, 0x01]).read_uleb128().unwrap());
+        assert_eq!(
+            u32::MAX as usize,
+            Reader::new(&[0xff, 0xff, 0xff, 0xff, 0x0f])
+                .read_uleb128()
+                .unwrap()
+        );
+        assert!(Reader::new(&[0x80, 0x00]).read_uleb128().is_err());
+        assert!(Reader::new(&[0xff, 0xff, 0xff, 0xff, 0x1f])
+            .read_uleb128()
+            .is_err());
+        assert!(Reader::new(&[0x80]).read_uleb128().is_err());
+    }
+
+    #[test]
+    fn test_read_vec() {
+        let mut reader = Reader::new(&[0x02, 0x01, 0x00, 0x02, 0x00]);
+        assert_eq!(vec![1u16, 2], reader.read_vec(|r| r.read_u16()).unwrap());
+        assert!(reader.is_empty());
+        assert!(Reader::new(&[0x05, 0x01]).read_vec_u8().is_err());
+    }
+}
@@ -0,0 +1,39 @@
+use crate::parser::overview::IotaTxOverview;
+
+use alloc::string::String;
+use core::fmt;
+
+#[derive(Clone, Debug)]
+pub struct ParsedIotaTx {
+    pub display_type: IotaTxDisplayType,
+    pub overview: IotaTxOverview,
+    pub detail: String,
+    pub network: String,
+    // gas budget, personal messages do not pay any fee
+    pub max_fee: Option<String>,
+}
+
+// method label on ui
+#[derive(Clone, Debug, PartialEq)]
+pub enum IotaTxDisplayType {
+    Transfer,
+    Stake,
+    Unstake,
+    Bridge,
+    General,
+    Message,
+}
+
+impl fmt::Display for IotaTxDisplayType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            IotaTxDisplayType::Transfer => "Transfer",
+            IotaTxDisplayType::Stake => "Stake",
+            IotaTxDisplayType::Unstake => "Unstake",
+            IotaTxDisplayType::Bridge => "Bridge",
+            IotaTxDisplayType::General => "Programmable Transaction",
+            IotaTxDisplayType::Message => "Message",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,442 @@
+use crate::errors::{IotaError, Result};
+use crate::parser::reader::Reader;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+// nested generics deeper than this are not something a wallet would ever build
+const MAX_TYPE_TAG_DEPTH: usize = 16;
+
+pub type IotaAddress = [u8; 32];
+
+pub fn address_to_string(address: &IotaAddress) -> String {
+    format!("0x{}", hex::encode(address))
+}
+
+// framework packages such as 0x2 and 0x3 are displayed the short way
+pub fn short_address_to_string(address: &IotaAddress) -> String {
+    let hex = hex::encode(address);
+    let trimmed = hex.trim_start_matches('0');
+    if trimmed.is_empty() {
+        "0x0".to_string()
+    } else {
+        format!("0x{}", trimmed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectRef {
+    pub object_id: IotaAddress,
+    pub version: u64,
+    pub digest: [u8; 32],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectArg {
+    ImmOrOwnedObject(ObjectRef),
+    SharedObject {
+        id: IotaAddress,
+        initial_shared_version: u64,
+        mutable: bool,
+    },
+    Receiving(ObjectRef),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallArg {
+    Pure(Vec<u8>),
+    Object(ObjectArg),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Argument {
+    GasCoin,
+    Input(u16),
+    Result(u16),
+    NestedResult(u16, u16),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructTag {
+    pub address: IotaAddress,
+    pub module: String,
+    pub name: String,
+    pub type_params: Vec<TypeTag>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeTag {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Signer,
+    Vector(Box<TypeTag>),
+    Struct(Box<StructTag>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveCall {
+    pub package: IotaAddress,
+    pub module: String,
+    pub function: String,
+    pub type_arguments: Vec<TypeTag>,
+    pub arguments: Vec<Argument>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    MoveCall(MoveCall),
+    TransferObjects(Vec<Argument>, Argument),
+    SplitCoins(Argument, Vec<Argument>),
+    MergeCoins(Argument, Vec<Argument>),
+    Publish(Vec<Vec<u8>>, Vec<IotaAddress>),
+    MakeMoveVec(Option<TypeTag>, Vec<Argument>),
+    Upgrade(Vec<Vec<u8>>, Vec<IotaAddress>, IotaAddress, Argument),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgrammableTransaction {
+    pub inputs: Vec<CallArg>,
+    pub commands: Vec<Command>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasData {
+    pub payment: Vec<ObjectRef>,
+    pub owner: IotaAddress,
+    pub price: u64,
+    pub budget: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactionExpiration {
+    None,
+    Epoch(u64),
+}
+
+// TransactionData::V1 with a programmable transaction kind, the other kinds are system
+// transactions which are never signed by a user
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionData {
+    pub kind: ProgrammableTransaction,
+    pub sender: IotaAddress,
+    pub gas_data: GasData,
+    pub expiration: TransactionExpiration,
+}
+
+impl TransactionData {
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut reader = Reader::new(data);
+        let tx = Self::read(&mut reader)?;
+        if !reader.is_empty() {
+            return Err(IotaError::ParseTxError(
+                "unexpected trailing bytes".to_string(),
+            ));
+        }
+        Ok(tx)
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self> {
+        match reader.read_uleb128()? {
+            0 => {}
+            v => {
+                return Err(IotaError::ParseTxError(format!(
+                    "unsupported transaction data version {}",
+                    v
+                )))
+            }
+        }
+        let kind = match reader.read_uleb128()? {
+            0 => ProgrammableTransaction::read(reader)?,
+            v => {
+                return Err(IotaError::UnsupportedProgram(format!(
+                    "transaction kind {}",
+                    v
+                )))
+            }
+        };
+        Ok(Self {
+            kind,
+            sender: reader.read_array()?,
+            gas_data: GasData::read(reader)?,
+            expiration: TransactionExpiration::read(reader)?,
+        })
+    }
+}
+
+impl ProgrammableTransaction {
+    fn read(reader: &mut Reader) -> Result<Self> {
+        Ok(Self {
+            inputs: reader.read_vec(CallArg::read)?,
+            commands: reader.read_vec(Command::read)?,
+        })
+    }
+
+    pub fn input(&self, argument: &Argument) -> Option<&CallArg> {
+        match argument {
+            Argument::Input(index) => self.inputs.get(*index as usize),
+            _ => None,
+        }
+    }
+
+    pub fn pure_u64(&self, argument: &Argument) -> Option<u64> {
+        match self.input(argument) {
+            Some(CallArg::Pure(bytes)) => {
+                Some(u64::from_le_bytes(bytes.as_slice().try_into().ok()?))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn pure_address(&self, argument: &Argument) -> Option<IotaAddress> {
+        match self.input(argument) {
+            Some(CallArg::Pure(bytes)) => bytes.as_slice().try_into().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn object_id(&self, argument: &Argument) -> Option<IotaAddress> {
+        match self.input(argument) {
+            Some(CallArg::Object(ObjectArg::ImmOrOwnedObject(object)))
+            | Some(CallArg::Object(ObjectArg::Receiving(object))) => Some(object.object_id),
+            Some(CallArg::Object(ObjectArg::SharedObject { id, .. })) => Some(*id),
+            _ => None,
+        }
+    }
+}
+
+impl CallArg {
+    fn read(reader: &mut Reader) -> Result<Self> {
+        match reader.read_uleb128()? {
+            0 => Ok(Self::Pure(reader.read_vec_u8()?)),
+            1 => Ok(Self::Object(ObjectArg::read(reader)?)),
+            v => Err(IotaError::InvalidData(format!("call arg tag {}", v))),
+        }
+    }
+}
+
+impl ObjectArg {
+    fn read(reader: &mut Reader) -> Result<Self> {
+        match reader.read_uleb128()? {
+            0 => Ok(Self::ImmOrOwnedObject(ObjectRef::read(reader)?)),
+            1 => Ok(Self::SharedObject {
+                id: reader.read_array()?,
+                initial_shared_version: reader.read_u64()?,
+                mutable: reader.read_bool()?,
+            }),
+            2 => Ok(Self::Receiving(ObjectRef::read(reader)?)),
+            v => Err(IotaError::InvalidData(format!("object arg tag {}", v))),
+        }
+    }
+}
+
+impl ObjectRef {
+    fn read(reader: &mut Reader) -> Result<Self> {
+        let object_id = reader.read_array()?;
+        let version = reader.read_u64()?;
+        // the digest is serialized as a length prefixed byte array
+        if reader.read_uleb128()? != 32 {
+            return Err(IotaError::InvalidLength);
+        }
+        Ok(Self {
+            object_id,
+            version,
+            digest: reader.read_array()?,
+        })
+    }
+}
+
+impl Argument {
+    fn read(reader: &mut Reader) -> Result<Self> {
+        match reader.read_uleb128()? {
+            0 => Ok(Self::GasCoin),
+            1 => Ok(Self::Input(reader.read_u16()?)),
+            2 => Ok(Self::Result(reader.read_u16()?)),
+            3 => Ok(Self::NestedResult(reader.read_u16()?, reader.read_u16()?)),
+            v => Err(IotaError::InvalidData(format!("argument tag {}", v))),
+        }
+    }
+}
+
+impl fmt::Display for Argument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GasCoin => write!(f, "GasCoin"),
+            Self::Input(index) => write!(f, "Input({})", index),
+            Self::Result(index) => write!(f, "Result({})", index),
+            Self::NestedResult(index, nested) => write!(f, "NestedResult({}, {})", index, nested),
+        }
+    }
+}
+
+impl TypeTag {
+    fn read(reader: &mut Reader) -> Result<Self> {
+        Self::read_with_depth(reader, 0)
+    }
+
+    fn read_with_depth(reader: &mut Reader, depth: usize) -> Result<Self> {
+        if depth > MAX_TYPE_TAG_DEPTH {
+            return Err(IotaError::InvalidData("type tag too deep".to_string()));
+        }
+        match reader.read_uleb128()? {
+            0 => Ok(Self::Bool),
+            1 => Ok(Self::U8),
+            2 => Ok(Self::U64),
+            3 => Ok(Self::U128),
+            4 => Ok(Self::Address),
+            5 => Ok(Self::Signer),
+            6 => Ok(Self::Vector(Box::new(Self::read_with_depth(
+                reader,
+                depth + 1,
+            )?))),
+            7 => Ok(Self::Struct(Box::new(StructTag {
+                address: reader.read_array()?,
+                module: reader.read_string()?,
+                name: reader.read_string()?,
+                type_params: reader.read_vec(|r| Self::read_with_depth(r, depth + 1))?,
+            }))),
+            8 => Ok(Self::U16),
+            9 => Ok(Self::U32),
+            10 => Ok(Self::U256),
+            v => Err(IotaError::InvalidData(format!("type tag {}", v))),
+        }
+    }
+}
+
+impl fmt::Display for TypeTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool => write!(f, "bool"),
+            Self::U8 => write!(f, "u8"),
+            Self::U16 => write!(f, "u16"),
+            Self::U32 => write!(f, "u32"),
+            Self::U64 => write!(f, "u64"),
+            Self::U128 => write!(f, "u128"),
+            Self::U256 => write!(f, "u256"),
+            Self::Address => write!(f, "address"),
+            Self::Signer => write!(f, "signer"),
+            Self::Vector(inner) => write!(f, "vector<{}>", inner),
+            Self::Struct(tag) => {
+                write!(
+                    f,
+                    "{}::{}::{}",
+                    short_address_to_string(&tag.address),
+                    tag.module,
+                    tag.name
+                )?;
+                if !tag.type_params.is_empty() {
+                    let params: Vec<String> =
+                        tag.type_params.iter().map(|t| t.to_string()).collect();
+                    write!(f, "<{}>", params.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl MoveCall {
+    fn read(reader: &mut Reader) -> Result<Self> {
+        Ok(Self {
+            package: reader.read_array()?,
+            module: reader.read_string()?,
+            function: reader.read_string()?,
+            type_arguments: reader.read_vec(TypeTag::read)?,
+            arguments: reader.read_vec(Argument::read)?,
+        })
+    }
+
+    pub fn is(&self, package: &IotaAddress, module: &str, function: &str) -> bool {
+        &self.package == package && self.module == module && self.function == function
+    }
+}
+
+impl fmt::Display for MoveCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::{}::{}",
+            short_address_to_string(&self.package),
+            self.module,
+            self.function
+        )
+    }
+}
+
+impl Command {
+    fn read(reader: &mut Reader) -> Result<Self> {
+        let tag = reader.read_uleb128()?;
+        match tag {
+            0 => Ok(Self::MoveCall(MoveCall::read(reader)?)),
+            1 => Ok(Self::TransferObjects(
+                reader.read_vec(Argument::read)?,
+                Argument::read(reader)?,
+            )),
+            2 => Ok(Self::SplitCoins(
+                Argument::read(reader)?,
+                reader.read_vec(Argument::read)?,
+            )),
+            3 => Ok(Self::MergeCoins(
+                Argument::read(reader)?,
+                reader.read_vec(Argument::read)?,
+            )),
+            4 => Ok(Self::Publish(
+                reader.read_vec(|r| r.read_vec_u8())?,
+                reader.read_vec(|r| r.read_array())?,
+            )),
+            5 => Ok(Self::MakeMoveVec(
+                reader.read_option(TypeTag::read)?,
+                reader.read_vec(Argument::read)?,
+            )),
+            6 => Ok(Self::Upgrade(
+                reader.read_vec(|r| r.read_vec_u8())?,
+                reader.read_vec(|r| r.read_array())?,
+                reader.read_array()?,
+                Argument::read(reader)?,
+            )),
+            v => Err(IotaError::InvalidCommand(
+                u8::try_from(v).unwrap_or(u8::MAX),
+            )),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Self::MoveCall(call) => call.to_string(),
+            Self::TransferObjects(..) => "Transfer Objects".to_string(),
+            Self::SplitCoins(..) => "Split Coins".to_string(),
+            Self::MergeCoins(..) => "Merge Coins".to_string(),
+            Self::Publish(..) => "Publish".to_string(),
+            Self::MakeMoveVec(..) => "Make Move Vector".to_string(),
+            Self::Upgrade(..) => "Upgrade".to_string(),
+        }
+    }
+}
+
+impl GasData {
+    fn read(reader: &mut Reader) -> Result<Self> {
+        Ok(Self {
+            payment: reader.read_vec(ObjectRef::read)?,
+            owner: reader.read_array()?,
+            price: reader.read_u64()?,
+            budget: reader.read_u64()?,
+        })
+    }
+}
+
+impl TransactionExpiration {
+    fn read(reader: &mut Reader) -> Result<Self> {
+        match reader.read_uleb128()? {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Epoch(reader.read_u64()?)),
+            v => Err(IotaError::InvalidData(format!("expiration tag {}", v))),
+        }
+    }
+}
@@ -27,6 +27,12 @@ pub enum SolanaError {
 
     #[error("Could not parse transaction, reason: `{0}`")]
     ParseTxError(String),
+
+    #[error("Invalid address lookup table, reason: `{0}`")]
+    LookupTableError(String),
+
+    #[error("Could not sign message, reason: `{0}`")]
+    MessageError(String),
 }
 
 pub type Result<T> = core::result::Result<T, SolanaError>;
@@ -6,9 +6,11 @@ use crate::errors::SolanaError::ProgramError;
 use crate::errors::{Result, SolanaError};
 use crate::parser::detail::SolanaDetail;
 use crate::resolvers;
+use crate::solana_lib::solana_program::compute_budget::instruction::ComputeBudgetInstruction;
 use crate::solana_lib::solana_program::stake::instruction::StakeInstruction;
 use crate::solana_lib::solana_program::system_instruction::SystemInstruction;
 use crate::solana_lib::solana_program::vote::instruction::VoteInstruction;
+use crate::solana_lib::spl::associated_token_account::instruction::AssociatedTokenAccountInstruction;
 use crate::solana_lib::traits::Dispatch;
 use crate::Read;
 #[derive(Debug, Clone)]
@@ -40,10 +42,14 @@ enum SupportedProgram {
     VoteProgram,
     StakeProgram,
     TokenProgram,
+    Token2022Program,
     TokenSwapProgramV3,
     TokenLendingProgram,
     SquadsProgramV4,
     JupiterProgramV6,
+    ComputeBudgetProgram,
+    MemoProgram,
+    AssociatedTokenAccountProgram,
 }
 
 impl SupportedProgram {
@@ -53,6 +59,7 @@ impl SupportedProgram {
             "Vote111111111111111111111111111111111111111" => Ok(SupportedProgram::VoteProgram),
             "Stake11111111111111111111111111111111111111" => Ok(SupportedProgram::StakeProgram),
             "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA" => Ok(SupportedProgram::TokenProgram),
+            "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb" => Ok(SupportedProgram::Token2022Program),
             "SwapsVeCiPHMUAtzQWZw7RjsKjgCjhwU55QGu4U1Szw" => {
                 Ok(SupportedProgram::TokenSwapProgramV3)
             }
@@ -62,6 +69,15 @@ impl SupportedProgram {
             }
             "SQDS4ep65T869zMMBKyuUq6aD6EgTu8psMjkvj52pCf" => Ok(SupportedProgram::SquadsProgramV4),
             "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4" => Ok(SupportedProgram::JupiterProgramV6),
+            "ComputeBudget111111111111111111111111111111" => {
+                Ok(SupportedProgram::ComputeBudgetProgram)
+            }
+            // memo v2 and the deprecated v1
+            "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"
+            | "Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo" => Ok(SupportedProgram::MemoProgram),
+            "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL" => {
+                Ok(SupportedProgram::AssociatedTokenAccountProgram)
+            }
             x => Err(SolanaError::UnsupportedProgram(x.to_string())),
         }
     }
@@ -94,6 +110,14 @@ impl Instruction {
                     .map_err(|e| ProgramError(e.to_string()))?;
                 resolvers::token::resolve(instruction, accounts)
             }
+            SupportedProgram::Token2022Program => {
+                let instruction =
+                    crate::solana_lib::spl::token_2022::instruction::Token2022Instruction::unpack(
+                        self.data.clone().as_slice(),
+                    )
+                    .map_err(|e| ProgramError(e.to_string()))?;
+                resolvers::token_2022::resolve(instruction, accounts)
+            }
             SupportedProgram::TokenSwapProgramV3 => {
                 let instruction =
                     crate::solana_lib::spl::token_swap::instruction::SwapInstruction::unpack(
@@ -128,6 +152,17 @@ impl Instruction {
                     .map_err(|e| ProgramError(e.to_string()))?;
                 resolvers::jupiter_v6::resolve(instruction, accounts)
             }
+            SupportedProgram::ComputeBudgetProgram => {
+                let instruction = ComputeBudgetInstruction::unpack(self.data.as_slice())
+                    .map_err(|e| ProgramError(e.to_string()))?;
+                resolvers::compute_budget::resolve(instruction)
+            }
+            SupportedProgram::MemoProgram => resolvers::memo::resolve(&self.data, accounts),
+            SupportedProgram::AssociatedTokenAccountProgram => {
+                let instruction = AssociatedTokenAccountInstruction::unpack(self.data.as_slice())
+                    .map_err(|e| ProgramError(e.to_string()))?;
+                resolvers::associated_token_account::resolve(instruction, accounts)
+            }
         }
     }
 
@@ -15,6 +15,8 @@ use alloc::vec::Vec;
 
 pub use address::get_address;
 
+use crate::errors::SolanaError;
+use crate::offchain_message::OffchainMessage;
 use crate::parser::structs::ParsedSolanaTx;
 use crate::read::Read;
 use crate::structs::SolanaMessage;
@@ -24,28 +26,75 @@ mod compact;
 pub mod errors;
 mod instruction;
 pub mod message;
+pub mod offchain_message;
 pub mod parser;
 pub mod read;
 mod resolvers;
+pub mod siws;
 mod solana_lib;
 pub mod structs;
 pub mod utils;
 pub fn parse_message(tx_hex: Vec<u8>, from_key: &String) -> errors::Result<SolanaMessage> {
+    // a message that reads as a transaction would get that transaction signed
+    if validate_tx(&mut tx_hex.clone()) {
+        return Err(SolanaError::MessageError(
+            "message could be parsed as a transaction".to_string(),
+        ));
+    }
     let raw_message = hex::encode(tx_hex.clone());
-    let mut utf8_message =
-        String::from_utf8(tx_hex).map_or_else(|_| "".to_string(), |utf8_msg| utf8_msg);
+    let offchain = if OffchainMessage::is_offchain_message(&tx_hex) {
+        Some(OffchainMessage::parse(&tx_hex)?)
+    } else {
+        None
+    };
+    let mut utf8_message = match &offchain {
+        Some(offchain) => offchain.message.clone(),
+        None => String::from_utf8(tx_hex).map_or_else(|_| "".to_string(), |utf8_msg| utf8_msg),
+    };
     if app_utils::is_cjk(&utf8_message) {
         utf8_message = "".to_string();
     }
-    SolanaMessage::from(raw_message, utf8_message, from_key)
+    SolanaMessage::from(raw_message, utf8_message, from_key, offchain)
 }
 
 pub fn validate_tx(message: &mut Vec<u8>) -> bool {
     message::Message::validate(message)
 }
 
+// the sign request of a v0 message may carry the contents of its address lookup tables after
+// the message, only the message itself is signed
+pub fn split_lookup_tables(
+    data: &[u8],
+) -> errors::Result<(Vec<u8>, Vec<message::AddressLookupTable>)> {
+    let mut raw = data.to_vec();
+    message::Message::read(&mut raw)?;
+    let message = data[..data.len() - raw.len()].to_vec();
+    if raw.is_empty() {
+        return Ok((message, Vec::new()));
+    }
+    Ok((message, message::AddressLookupTable::read_tables(&mut raw)?))
+}
+
 pub fn parse(data: &Vec<u8>) -> errors::Result<ParsedSolanaTx> {
-    ParsedSolanaTx::build(data)
+    let (message, lookup_tables) = split_lookup_tables(data)?;
+    if lookup_tables.is_empty() {
+        return ParsedSolanaTx::build(&message);
+    }
+    ParsedSolanaTx::build_with_lookup_tables(&message, &lookup_tables)
+}
+
+// the checks made when the request was shown are repeated on the data that is actually signed:
+// lookup tables are stripped from a transaction, anything else has to pass as a message of the
+// signing key
+pub fn prepare_sign_data(data: &[u8], hd_path: &String, seed: &[u8]) -> errors::Result<Vec<u8>> {
+    if validate_tx(&mut data.to_vec()) {
+        return Ok(split_lookup_tables(data)?.0);
+    }
+    let pubkey =
+        keystore::algorithms::ed25519::slip10_ed25519::get_public_key_by_seed(seed, hd_path)
+            .map_err(|e| SolanaError::KeystoreError(format!("derive public key failed {:?}", e)))?;
+    parse_message(data.to_vec(), &hex::encode(pubkey))?;
+    Ok(data.to_vec())
 }
 
 pub fn sign(message: Vec<u8>, hd_path: &String, seed: &[u8]) -> errors::Result<[u8; 64]> {
@@ -71,6 +120,60 @@ mod tests {
         assert_eq!("9625b26df39be0a392cd2f0db075a238fe7bd98d181b8705bcc6c1c64f652294c54760af911cca245769489c30c12e44cf5e139ca71f1acc834eea4b63017b00", signature.encode_hex::<String>());
     }
 
+    #[test]
+    fn test_solana_prepare_sign_data() {
+        let hd_path = "m/44'/501'/0'".to_string();
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let pubkey = hex::encode(
+            keystore::algorithms::ed25519::slip10_ed25519::get_public_key_by_seed(&seed, &hd_path)
+                .unwrap(),
+        );
+        let build = |signer: &str| {
+            let mut data = offchain_message::SIGNING_DOMAIN.to_vec();
+            data.push(0);
+            data.extend([0u8; 32]);
+            data.push(0);
+            data.push(1);
+            data.extend(hex::decode(signer).unwrap());
+            data.extend(12u16.to_le_bytes());
+            data.extend(b"Hello, world");
+            data
+        };
+        let data = build(&pubkey);
+        assert_eq!(data, prepare_sign_data(&data, &hd_path, &seed).unwrap());
+
+        // an off-chain message of another signer or with a broken header is refused
+        let other = "0000000000000000000000000000000000000000000000000000000000000001";
+        assert!(prepare_sign_data(&build(other), &hd_path, &seed).is_err());
+        let mut data = build(&pubkey);
+        data[offchain_message::SIGNING_DOMAIN.len()] = 1;
+        assert!(prepare_sign_data(&data, &hd_path, &seed).is_err());
+    }
+
+    #[test]
+    fn test_solana_split_lookup_tables() {
+        let tx_hex =  Vec::from_hex("010002041a93fffb26ce645adeae58f0f414c320bcec30ce12a66bd263a91ec9b3958ff46f345144d352e4190c2dec43e1d3e0296a49bdfc2594eed9d8a5902e22d0af8b00000000000000000000000000000000000000000000000000000000000000000306466fe5211732ffecadba72c39be7bc8ce5bbc5f7126b2c439b3a40000000f70a9d4448ef435c5beab6cbc4211e00ddb4b9ad84886385f8b7ccfb9d9e7ca40303000903d8d600000000000003000502400d0300020200010c020000008096980000000000").unwrap();
+        let (message, tables) = split_lookup_tables(&tx_hex).unwrap();
+        assert_eq!(tx_hex, message);
+        assert!(tables.is_empty());
+
+        // one table holding a single address
+        let mut data = tx_hex.clone();
+        data.push(1);
+        data.extend([7u8; 32]);
+        data.push(1);
+        data.extend([8u8; 32]);
+        let (message, tables) = split_lookup_tables(&data).unwrap();
+        assert_eq!(tx_hex, message);
+        assert_eq!(1, tables.len());
+        assert_eq!(vec![8u8; 32], tables[0].addresses[0].value);
+
+        // trailing bytes that aren't lookup tables
+        let mut data = tx_hex.clone();
+        data.push(1);
+        assert!(split_lookup_tables(&data).is_err());
+    }
+
     #[test]
     fn test_solana_validate() {
         let mut tx_hex =  Vec::from_hex("010002041a93fffb26ce645adeae58f0f414c320bcec30ce12a66bd263a91ec9b3958ff46f345144d352e4190c2dec43e1d3e0296a49bdfc2594eed9d8a5902e22d0af8b00000000000000000000000000000000000000000000000000000000000000000306466fe5211732ffecadba72c39be7bc8ce5bbc5f7126b2c439b3a40000000f70a9d4448ef435c5beab6cbc4211e00ddb4b9ad84886385f8b7ccfb9d9e7ca40303000903d8d600000000000003000502400d0300020200010c020000008096980000000000").unwrap();
@@ -84,6 +187,70 @@ mod tests {
         let sol_sign_request = SolSignRequest::try_from(hex::decode(cbor_hex).unwrap()).unwrap();
         let parsed = parse_message(sol_sign_request.get_sign_data(), &pubkey).unwrap();
         assert_eq!("GWZVzcS2MXfFqHmP782Qx3RzkkQX2KfgchZPLp3AEZrm", parsed.from);
+        assert!(parsed.offchain.is_none());
+        let sign_in = parsed.sign_in.unwrap();
+        assert_eq!("magiceden.io", sign_in.domain);
+        assert_eq!(
+            "GWZVzcS2MXfFqHmP782Qx3RzkkQX2KfgchZPLp3AEZrm",
+            sign_in.address
+        );
+        assert!(sign_in
+            .statement
+            .unwrap()
+            .starts_with("Click Sign or Approve only means"));
+        assert_eq!(Some("https://magiceden.io".to_string()), sign_in.uri);
+        assert_eq!(Some("1".to_string()), sign_in.version);
+        assert_eq!(Some("mainnet".to_string()), sign_in.chain_id);
+        assert_eq!(Some("vpdx3nGfb9".to_string()), sign_in.nonce);
+        assert_eq!(
+            Some("2023-07-27T02:10:49.161Z".to_string()),
+            sign_in.issued_at
+        );
+        assert_eq!(None, sign_in.expiration_time);
+
+        // the sign-in address must be the signer
+        let other = "0000000000000000000000000000000000000000000000000000000000000001".to_string();
+        assert!(parse_message(sol_sign_request.get_sign_data(), &other).is_err());
+    }
+
+    #[test]
+    fn test_solana_parse_offchain_message() {
+        let pubkey = "e671e524ef43ccc5ef0006876f9a2fd66681d5abc5871136b343a3e4b073efde".to_string();
+        let build = |signer: &str| {
+            let mut data = offchain_message::SIGNING_DOMAIN.to_vec();
+            data.push(0);
+            data.extend([0u8; 32]);
+            data.push(0);
+            data.push(1);
+            data.extend(hex::decode(signer).unwrap());
+            data.extend(12u16.to_le_bytes());
+            data.extend(b"Hello, world");
+            data
+        };
+        let parsed = parse_message(build(&pubkey), &pubkey).unwrap();
+        let offchain = parsed.offchain.unwrap();
+        assert_eq!(
+            "11111111111111111111111111111111",
+            offchain.application_domain
+        );
+        assert_eq!("Restricted ASCII", offchain.format.to_string());
+        assert_eq!(
+            vec!["GWZVzcS2MXfFqHmP782Qx3RzkkQX2KfgchZPLp3AEZrm".to_string()],
+            offchain.signers
+        );
+        assert_eq!("Hello, world", parsed.utf8_message);
+        assert!(parsed.sign_in.is_none());
+
+        // the signer must be listed in the message
+        let other = "0000000000000000000000000000000000000000000000000000000000000001";
+        assert!(parse_message(build(other), &pubkey).is_err());
+    }
+
+    #[test]
+    fn test_solana_parse_message_rejects_transaction() {
+        let tx_hex = Vec::from_hex("010002041a93fffb26ce645adeae58f0f414c320bcec30ce12a66bd263a91ec9b3958ff46f345144d352e4190c2dec43e1d3e0296a49bdfc2594eed9d8a5902e22d0af8b00000000000000000000000000000000000000000000000000000000000000000306466fe5211732ffecadba72c39be7bc8ce5bbc5f7126b2c439b3a40000000f70a9d4448ef435c5beab6cbc4211e00ddb4b9ad84886385f8b7ccfb9d9e7ca40303000903d8d600000000000003000502400d0300020200010c020000008096980000000000").unwrap();
+        let pubkey = "e671e524ef43ccc5ef0006876f9a2fd66681d5abc5871136b343a3e4b073efde".to_string();
+        assert!(parse_message(tx_hex, &pubkey).is_err());
     }
 
     #[test]
@@ -67,6 +67,8 @@ pub struct Message {
     pub block_hash: BlockHash,
     pub instructions: Vec<Instruction>,
     pub address_table_lookups: Option<Vec<MessageAddressTableLookup>>,
+    // accounts of the address table lookups once the table contents are supplied
+    pub loaded_addresses: Option<LoadedAddresses>,
 }
 
 impl Read<Message> for Message {
@@ -95,26 +97,17 @@ impl Read<Message> for Message {
             block_hash,
             instructions,
             address_table_lookups,
+            loaded_addresses: None,
         })
     }
 }
 
 impl Message {
     pub fn to_program_details(&self) -> Result<Vec<SolanaDetail>> {
-        let accounts = self.prepare_accounts();
         self.instructions
             .iter()
-            .map(|instruction| {
-                let accounts = instruction
-                    .account_indexes
-                    .iter()
-                    .map(|account_index| {
-                        accounts
-                            .get(*account_index as usize)
-                            .map(|v| v.to_string())
-                            .unwrap_or("Unknown Account".to_string())
-                    })
-                    .collect::<Vec<String>>();
+            .zip(self.to_instruction_accounts())
+            .map(|(instruction, accounts)| {
                 let program_account =
                     base58::encode(&self.accounts[usize::from(instruction.program_index)].value);
                 // parse instruction data
@@ -136,6 +129,94 @@ impl Message {
             .collect::<Result<Vec<SolanaDetail>>>()
     }
 
+    // accounts every instruction is invoked with, in instruction order
+    pub(crate) fn to_instruction_accounts(&self) -> Vec<Vec<String>> {
+        let accounts = self.prepare_accounts();
+        self.instructions
+            .iter()
+            .map(|instruction| {
+                instruction
+                    .account_indexes
+                    .iter()
+                    .map(|account_index| {
+                        accounts
+                            .get(*account_index as usize)
+                            .map(|v| v.to_string())
+                            .unwrap_or("Unknown Account".to_string())
+                    })
+                    .collect::<Vec<String>>()
+            })
+            .collect()
+    }
+
+    // the table contents come with the sign request and can't be proven on device, so they must
+    // at least cover every lookup of the message and must not load an account twice, which the
+    // runtime would reject
+    pub fn resolve_address_lookup_tables(&mut self, tables: &[AddressLookupTable]) -> Result<()> {
+        let lookups = match &self.address_table_lookups {
+            Some(lookups) => lookups,
+            None => return Ok(()),
+        };
+        let mut writable = vec![];
+        let mut readonly = vec![];
+        for lookup in lookups {
+            let table_key = base58::encode(&lookup.account_key.value);
+            let table = tables
+                .iter()
+                .find(|table| table.account_key.value == lookup.account_key.value)
+                .ok_or(SolanaError::LookupTableError(format!(
+                    "missing address lookup table {}",
+                    table_key
+                )))?;
+            let resolve = |indexes: &[u8]| {
+                indexes
+                    .iter()
+                    .map(|index| {
+                        table
+                            .addresses
+                            .get(*index as usize)
+                            .map(|v| base58::encode(&v.value))
+                            .ok_or(SolanaError::LookupTableError(format!(
+                                "index {} is out of address lookup table {}",
+                                index, table_key
+                            )))
+                    })
+                    .collect::<Result<Vec<String>>>()
+            };
+            writable.append(&mut resolve(&lookup.writable_indexes)?);
+            readonly.append(&mut resolve(&lookup.readonly_indexes)?);
+        }
+        let mut accounts: Vec<String> = self
+            .accounts
+            .iter()
+            .map(|v| base58::encode(&v.value))
+            .collect();
+        for account in writable.iter().chain(readonly.iter()) {
+            if accounts.contains(account) {
+                return Err(SolanaError::LookupTableError(format!(
+                    "account {} is loaded more than once",
+                    account
+                )));
+            }
+            accounts.push(account.to_string());
+        }
+        self.loaded_addresses = Some(LoadedAddresses { writable, readonly });
+        Ok(())
+    }
+
+    // accounts of lookup tables are either resolved from the tables carried with the request,
+    // which the device can't prove, or left as `table#index`
+    pub fn is_lookup_table_account(&self, account: &str) -> bool {
+        match &self.loaded_addresses {
+            Some(loaded) => loaded
+                .writable
+                .iter()
+                .chain(loaded.readonly.iter())
+                .any(|v| v == account),
+            None => account.contains('#'),
+        }
+    }
+
     pub fn validate(raw: &mut Vec<u8>) -> bool {
         Self::read(raw).is_ok()
     }
@@ -148,6 +229,11 @@ impl Message {
             .iter()
             .map(|v| bitcoin::base58::encode(&v.value))
             .collect();
+        if let Some(loaded) = &self.loaded_addresses {
+            accounts.append(&mut loaded.writable.clone());
+            accounts.append(&mut loaded.readonly.clone());
+            return accounts;
+        }
         // construct address table lookup account
         let mut writable_lookup_accounts: Vec<String> = vec![];
         let mut readonly_lookup_accounts: Vec<String> = vec![];
@@ -225,3 +311,40 @@ impl Read<MessageAddressTableLookup> for MessageAddressTableLookup {
         })
     }
 }
+
+// contents of an on-chain address lookup table
+#[derive(Clone)]
+pub struct AddressLookupTable {
+    pub account_key: Account,
+    pub addresses: Vec<Account>,
+}
+
+impl Read<AddressLookupTable> for AddressLookupTable {
+    fn read(raw: &mut Vec<u8>) -> Result<AddressLookupTable> {
+        let account_key = Account::read(raw)?;
+        let addresses = Compact::read(raw)?.data;
+        Ok(Self {
+            account_key,
+            addresses,
+        })
+    }
+}
+
+impl AddressLookupTable {
+    // a compact array of tables, each table key followed by a compact array of its addresses
+    pub fn read_tables(raw: &mut Vec<u8>) -> Result<Vec<AddressLookupTable>> {
+        let tables = Compact::read(raw)?.data;
+        if !raw.is_empty() {
+            return Err(SolanaError::InvalidData(
+                "address lookup tables".to_string(),
+            ));
+        }
+        Ok(tables)
+    }
+}
+
+#[derive(Clone)]
+pub struct LoadedAddresses {
+    pub writable: Vec<String>,
+    pub readonly: Vec<String>,
+}
@@ -0,0 +1,178 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use bitcoin::base58;
+
+use crate::errors::{Result, SolanaError};
+
+// off-chain messages start with a byte no transaction or legacy message can start with
+pub const SIGNING_DOMAIN: &[u8] = b"\xffsolana offchain";
+// restricted ascii and limited utf-8 messages must fit a packet so that ledgers can sign them
+const PACKET_DATA_SIZE: usize = 1232;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OffchainMessageFormat {
+    RestrictedAscii,
+    LimitedUtf8,
+    ExtendedUtf8,
+}
+
+impl OffchainMessageFormat {
+    fn from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Self::RestrictedAscii),
+            1 => Ok(Self::LimitedUtf8),
+            2 => Ok(Self::ExtendedUtf8),
+            _ => Err(SolanaError::InvalidData(
+                "off-chain message format".to_string(),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OffchainMessageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RestrictedAscii => write!(f, "Restricted ASCII"),
+            Self::LimitedUtf8 => write!(f, "Limited UTF-8"),
+            Self::ExtendedUtf8 => write!(f, "Extended UTF-8"),
+        }
+    }
+}
+
+// version 0 of the off-chain message signing spec:
+// signing domain | version | application domain | format | signer count | signers | length | body
+#[derive(Clone, Debug)]
+pub struct OffchainMessage {
+    pub version: u8,
+    pub application_domain: String,
+    pub format: OffchainMessageFormat,
+    pub signers: Vec<String>,
+    pub message: String,
+}
+
+impl OffchainMessage {
+    pub fn is_offchain_message(data: &[u8]) -> bool {
+        data.starts_with(SIGNING_DOMAIN)
+    }
+
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let invalid =
+            |field: &str| SolanaError::InvalidData(format!("off-chain message {}", field));
+        let rest = data
+            .strip_prefix(SIGNING_DOMAIN)
+            .ok_or(invalid("signing domain"))?;
+        let (&version, rest) = rest.split_first().ok_or(invalid("version"))?;
+        if version != 0 {
+            return Err(invalid("version"));
+        }
+        let (application_domain, rest) = rest
+            .split_at_checked(32)
+            .ok_or(invalid("application domain"))?;
+        let (&format, rest) = rest.split_first().ok_or(invalid("format"))?;
+        let format = OffchainMessageFormat::from(format)?;
+        let (&signer_count, mut rest) = rest.split_first().ok_or(invalid("signer count"))?;
+        if signer_count == 0 {
+            return Err(invalid("signer count"));
+        }
+        let mut signers = Vec::new();
+        for _ in 0..signer_count {
+            let (signer, next) = rest.split_at_checked(32).ok_or(invalid("signers"))?;
+            signers.push(base58::encode(signer));
+            rest = next;
+        }
+        let (length, body) = rest.split_at_checked(2).ok_or(invalid("message length"))?;
+        let length = u16::from_le_bytes([length[0], length[1]]) as usize;
+        if length == 0 || body.len() != length {
+            return Err(invalid("message length"));
+        }
+        // the body must fit the declared format
+        let fits = match format {
+            OffchainMessageFormat::RestrictedAscii => {
+                data.len() <= PACKET_DATA_SIZE && body.iter().all(|c| (0x20..=0x7e).contains(c))
+            }
+            OffchainMessageFormat::LimitedUtf8 => data.len() <= PACKET_DATA_SIZE,
+            OffchainMessageFormat::ExtendedUtf8 => true,
+        };
+        if !fits {
+            return Err(invalid("body"));
+        }
+        let message = core::str::from_utf8(body)
+            .map_err(|_| invalid("body"))?
+            .to_string();
+        Ok(Self {
+            version,
+            application_domain: base58::encode(application_domain),
+            format,
+            signers,
+            message,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn build(format: u8, signers: &[[u8; 32]], body: &[u8]) -> Vec<u8> {
+        let mut data = SIGNING_DOMAIN.to_vec();
+        data.push(0);
+        data.extend([7u8; 32]);
+        data.push(format);
+        data.push(signers.len() as u8);
+        signers.iter().for_each(|v| data.extend(v));
+        data.extend((body.len() as u16).to_le_bytes());
+        data.extend(body);
+        data
+    }
+
+    #[test]
+    fn test_parse_offchain_message() {
+        let data = build(0, &[[1u8; 32]], b"Hello, Solana!");
+        assert!(OffchainMessage::is_offchain_message(&data));
+        let message = OffchainMessage::parse(&data).unwrap();
+        assert_eq!(0, message.version);
+        assert_eq!(base58::encode(&[7u8; 32]), message.application_domain);
+        assert_eq!(OffchainMessageFormat::RestrictedAscii, message.format);
+        assert_eq!(vec![base58::encode(&[1u8; 32])], message.signers);
+        assert_eq!("Hello, Solana!", message.message);
+
+        let data = build(1, &[[1u8; 32], [2u8; 32]], "你好\nSolana".as_bytes());
+        let message = OffchainMessage::parse(&data).unwrap();
+        assert_eq!(OffchainMessageFormat::LimitedUtf8, message.format);
+        assert_eq!(2, message.signers.len());
+        assert_eq!("你好\nSolana", message.message);
+
+        let body = vec![b'a'; 2000];
+        let message = OffchainMessage::parse(&build(2, &[[1u8; 32]], &body)).unwrap();
+        assert_eq!(OffchainMessageFormat::ExtendedUtf8, message.format);
+        assert_eq!(2000, message.message.len());
+    }
+
+    #[test]
+    fn test_parse_invalid_offchain_message() {
+        // not restricted ascii
+        assert!(OffchainMessage::parse(&build(0, &[[1u8; 32]], b"Hello\nSolana")).is_err());
+        // not utf-8
+        assert!(OffchainMessage::parse(&build(1, &[[1u8; 32]], &[0xff, 0xfe])).is_err());
+        // too long for a limited utf-8 message
+        assert!(OffchainMessage::parse(&build(1, &[[1u8; 32]], &vec![b'a'; 2000])).is_err());
+        // unknown format
+        assert!(OffchainMessage::parse(&build(3, &[[1u8; 32]], b"Hello")).is_err());
+        // no signers
+        assert!(OffchainMessage::parse(&build(0, &[], b"Hello")).is_err());
+        // empty body
+        assert!(OffchainMessage::parse(&build(0, &[[1u8; 32]], b"")).is_err());
+        // unsupported version
+        let mut data = build(0, &[[1u8; 32]], b"Hello");
+        data[SIGNING_DOMAIN.len()] = 1;
+        assert!(OffchainMessage::parse(&data).is_err());
+        // length does not match the body
+        let mut data = build(0, &[[1u8; 32]], b"Hello");
+        data.push(b'!');
+        assert!(OffchainMessage::parse(&data).is_err());
+    }
+}
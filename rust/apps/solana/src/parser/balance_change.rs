@@ -0,0 +1,653 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Add;
+
+use bitcoin::base58;
+
+use crate::errors::{Result, SolanaError};
+use crate::message::Message;
+use crate::parser::detail::{ProgramDetail, SolanaDetail};
+use crate::parser::overview::ProgramOverviewNetworkFee;
+use crate::parser::structs::ParsedSolanaTx;
+use crate::utils;
+
+// token accounts no instruction of the transaction names the mint of
+const UNKNOWN_MINT: &str = "Unknown";
+const SOL_DECIMALS: u8 = 9;
+const BPS_BASE: u128 = 10_000;
+
+// net change of a balance in base units, bounded when it depends on the chain state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceDelta {
+    Exact(i128),
+    // e.g. the minimum out of a swap
+    AtLeast(i128),
+    // e.g. the rent a new account takes from its funding account
+    AtMost(i128),
+    Unknown,
+}
+
+impl Add for BalanceDelta {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Exact(a), Self::Exact(b)) => Self::Exact(a + b),
+            (Self::Exact(a), Self::AtLeast(b))
+            | (Self::AtLeast(a), Self::Exact(b))
+            | (Self::AtLeast(a), Self::AtLeast(b)) => Self::AtLeast(a + b),
+            (Self::Exact(a), Self::AtMost(b))
+            | (Self::AtMost(a), Self::Exact(b))
+            | (Self::AtMost(a), Self::AtMost(b)) => Self::AtMost(a + b),
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceChange {
+    pub account: String,
+    // None for native SOL
+    pub mint: Option<String>,
+    pub symbol: String,
+    // None when the transaction doesn't tell the decimals of the mint
+    pub decimals: Option<u8>,
+    pub delta: BalanceDelta,
+    // the account is also passed to an instruction whose effect can't be previewed
+    pub unknown_effect: bool,
+}
+
+impl BalanceChange {
+    pub fn amount(&self) -> Result<String> {
+        let format_value = |value: i128| {
+            let sign = if value < 0 { "-" } else { "+" };
+            let magnitude = u64::try_from(value.unsigned_abs()).map_err(|_| {
+                SolanaError::ParseTxError(format!("balance change of {} overflows", self.account))
+            })?;
+            let magnitude = match self.decimals {
+                Some(decimals) => {
+                    utils::token_amount_to_human_readable(magnitude, decimals.into()).to_string()
+                }
+                None => magnitude.to_string(),
+            };
+            Ok(format!("{}{} {}", sign, magnitude, self.symbol))
+        };
+        Ok(match self.delta {
+            BalanceDelta::Exact(value) => format_value(value)?,
+            BalanceDelta::AtLeast(value) => format!("at least {}", format_value(value)?),
+            BalanceDelta::AtMost(value) => format!("at most {}", format_value(value)?),
+            BalanceDelta::Unknown => format!("Unknown {}", self.symbol),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownEffect {
+    pub program: String,
+    pub method: String,
+    pub accounts: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BalanceChanges {
+    pub changes: Vec<BalanceChange>,
+    // instructions of programs the preview doesn't model
+    pub unknown_effects: Vec<UnknownEffect>,
+}
+
+// accounts and amounts of a jupiter swap as far as the user accounts are concerned
+struct JupiterSwap<'a> {
+    source: &'a String,
+    destination: &'a String,
+    source_mint: Option<&'a String>,
+    destination_mint: &'a String,
+    source_delta: BalanceDelta,
+    destination_delta: BalanceDelta,
+}
+
+impl<'a> JupiterSwap<'a> {
+    fn from(kind: &'a ProgramDetail) -> Option<Self> {
+        let min_out = |quoted_out_amount: u64, slippage_bps: u16| {
+            let slippage_bps = (slippage_bps as u128).min(BPS_BASE);
+            BalanceDelta::AtLeast(
+                (quoted_out_amount as u128 * (BPS_BASE - slippage_bps) / BPS_BASE) as i128,
+            )
+        };
+        let max_in = |quoted_in_amount: u64, slippage_bps: u16| {
+            let max_in =
+                (quoted_in_amount as u128 * (BPS_BASE + slippage_bps as u128)).div_ceil(BPS_BASE);
+            BalanceDelta::AtLeast(-(max_in as i128))
+        };
+        // account layouts match the ones of the swap overview
+        match kind {
+            ProgramDetail::JupiterV6SharedAccountsRoute(v) => Some(Self {
+                source: v.accounts.get(3)?,
+                destination: v.accounts.get(6)?,
+                source_mint: Some(v.accounts.get(7)?),
+                destination_mint: v.accounts.get(8)?,
+                source_delta: BalanceDelta::Exact(-(v.args.in_amount as i128)),
+                destination_delta: min_out(v.args.quoted_out_amount, v.args.slippage_bps),
+            }),
+            ProgramDetail::JupiterV6SharedAccountsExactOutRoute(v) => Some(Self {
+                source: v.accounts.get(3)?,
+                destination: v.accounts.get(6)?,
+                source_mint: Some(v.accounts.get(7)?),
+                destination_mint: v.accounts.get(8)?,
+                source_delta: max_in(v.args.quoted_in_amount, v.args.slippage_bps),
+                destination_delta: BalanceDelta::AtLeast(v.args.out_amount as i128),
+            }),
+            ProgramDetail::JupiterV6ExactOutRoute(v) => Some(Self {
+                source: v.accounts.get(2)?,
+                destination: v.accounts.get(3)?,
+                source_mint: Some(v.accounts.get(5)?),
+                destination_mint: v.accounts.get(6)?,
+                source_delta: max_in(v.args.quoted_in_amount, v.args.slippage_bps),
+                destination_delta: BalanceDelta::AtLeast(v.args.out_amount as i128),
+            }),
+            ProgramDetail::JupiterV6Route(v) => Some(Self {
+                source: v.accounts.get(2)?,
+                destination: v.accounts.get(3)?,
+                source_mint: None,
+                destination_mint: v.accounts.get(5)?,
+                source_delta: BalanceDelta::Exact(-(v.args.in_amount as i128)),
+                destination_delta: min_out(v.args.quoted_out_amount, v.args.slippage_bps),
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+struct BalanceTable {
+    changes: Vec<BalanceChange>,
+    unknown_effects: Vec<UnknownEffect>,
+    // token account => mint, learned from every instruction naming both
+    token_mints: Vec<(String, String)>,
+    // mint => decimals
+    mint_decimals: Vec<(String, u8)>,
+}
+
+impl BalanceTable {
+    fn learn_mint(&mut self, account: &str, mint: &str, decimals: Option<u8>) {
+        if !self.token_mints.iter().any(|(a, _)| a == account) {
+            self.token_mints
+                .push((account.to_string(), mint.to_string()));
+        }
+        if let Some(decimals) = decimals {
+            if !self.mint_decimals.iter().any(|(m, _)| m == mint) {
+                self.mint_decimals.push((mint.to_string(), decimals));
+            }
+        }
+    }
+
+    fn learn_mints(&mut self, detail: &SolanaDetail) {
+        match &detail.kind {
+            ProgramDetail::TokenTransferChecked(v) => {
+                self.learn_mint(&v.account, &v.mint, Some(v.decimals));
+                self.learn_mint(&v.recipient, &v.mint, Some(v.decimals));
+            }
+            ProgramDetail::Token2022TransferCheckedWithFee(v) => {
+                self.learn_mint(&v.account, &v.mint, Some(v.decimals));
+                self.learn_mint(&v.recipient, &v.mint, Some(v.decimals));
+            }
+            ProgramDetail::TokenBurn(v) => self.learn_mint(&v.account, &v.mint, None),
+            ProgramDetail::TokenBurnChecked(v) => {
+                self.learn_mint(&v.account, &v.mint, Some(v.decimals))
+            }
+            ProgramDetail::TokenMintTo(v) => self.learn_mint(&v.mint_to_account, &v.mint, None),
+            ProgramDetail::TokenMintToChecked(v) => {
+                self.learn_mint(&v.mint_to_account, &v.mint, Some(v.decimals))
+            }
+            ProgramDetail::TokenInitializeAccount(v) => self.learn_mint(&v.account, &v.mint, None),
+            ProgramDetail::TokenInitializeAccount2(v) => self.learn_mint(&v.account, &v.mint, None),
+            ProgramDetail::TokenInitializeAccount3(v) => self.learn_mint(&v.account, &v.mint, None),
+            ProgramDetail::AssociatedTokenAccountCreate(v) => {
+                self.learn_mint(&v.associated_account, &v.mint, None)
+            }
+            kind => {
+                if let Some(swap) = JupiterSwap::from(kind) {
+                    if let Some(mint) = swap.source_mint {
+                        self.learn_mint(swap.source, mint, None);
+                    }
+                    self.learn_mint(swap.destination, swap.destination_mint, None);
+                }
+            }
+        }
+    }
+
+    fn add(&mut self, account: &str, mint: Option<String>, delta: BalanceDelta) {
+        match self
+            .changes
+            .iter_mut()
+            .find(|v| v.account == account && v.mint == mint)
+        {
+            Some(change) => change.delta = change.delta + delta,
+            None => self.changes.push(BalanceChange {
+                account: account.to_string(),
+                mint,
+                symbol: String::new(),
+                decimals: None,
+                delta,
+                unknown_effect: false,
+            }),
+        }
+    }
+
+    fn sol(&mut self, account: &str, delta: BalanceDelta) {
+        self.add(account, None, delta)
+    }
+
+    fn token(&mut self, account: &str, delta: BalanceDelta) {
+        let mint = self
+            .token_mints
+            .iter()
+            .find(|(a, _)| a == account)
+            .map(|(_, mint)| mint.to_string())
+            .unwrap_or(UNKNOWN_MINT.to_string());
+        self.add(account, Some(mint), delta)
+    }
+
+    fn transfer_sol(&mut self, from: &str, to: &str, lamports: u64) {
+        self.sol(from, BalanceDelta::Exact(-(lamports as i128)));
+        self.sol(to, BalanceDelta::Exact(lamports as i128));
+    }
+
+    fn transfer_token(&mut self, from: &str, to: &str, amount: u64) {
+        self.token(from, BalanceDelta::Exact(-(amount as i128)));
+        self.token(to, BalanceDelta::Exact(amount as i128));
+    }
+
+    // every lamport of `from` goes to `to`, how many isn't known before execution
+    fn drain_sol(&mut self, from: &str, to: &str) {
+        self.sol(from, BalanceDelta::AtMost(0));
+        self.sol(to, BalanceDelta::AtLeast(0));
+    }
+
+    fn apply(&mut self, detail: &SolanaDetail, accounts: &[String]) -> Result<()> {
+        let parse_u64 = |value: &str| {
+            value
+                .parse::<u64>()
+                .map_err(|e| SolanaError::ParseTxError(format!("invalid amount {}: {}", value, e)))
+        };
+        match &detail.kind {
+            ProgramDetail::SystemTransfer(v) => self.transfer_sol(&v.from, &v.to, v.lamports),
+            ProgramDetail::SystemTransferWithSeed(v) => {
+                self.transfer_sol(&v.from, &v.to, v.lamports)
+            }
+            ProgramDetail::SystemCreateAccount(v) => {
+                self.transfer_sol(&v.funding_account, &v.new_account, parse_u64(&v.amount)?)
+            }
+            ProgramDetail::SystemCreateAccountWithSeed(v) => {
+                self.transfer_sol(&v.funding_account, &v.new_account, parse_u64(&v.amount)?)
+            }
+            ProgramDetail::SystemWithdrawNonceAccount(v) => {
+                self.transfer_sol(&v.nonce_account, &v.recipient, parse_u64(&v.amount)?)
+            }
+            ProgramDetail::VoteWithdraw(v) => {
+                self.transfer_sol(&v.vote_account, &v.recipient, parse_u64(&v.amount)?)
+            }
+            ProgramDetail::StakeWithdraw(v) => {
+                self.transfer_sol(&v.stake_account, &v.recipient, parse_u64(&v.amount)?)
+            }
+            ProgramDetail::StakeSplit(v) => {
+                self.transfer_sol(&v.stake_account, &v.target_account, parse_u64(&v.amount)?)
+            }
+            ProgramDetail::StakeMerge(v) => {
+                self.drain_sol(&v.source_stake_account, &v.destination_stake_account)
+            }
+            ProgramDetail::TokenTransfer(v) => {
+                self.transfer_token(&v.source_account, &v.recipient, parse_u64(&v.amount)?)
+            }
+            ProgramDetail::TokenTransferChecked(v) => {
+                self.transfer_token(&v.account, &v.recipient, parse_u64(&v.amount)?)
+            }
+            // the fee is withheld from what the recipient gets
+            ProgramDetail::Token2022TransferCheckedWithFee(v) => {
+                let amount = parse_u64(&v.amount)? as i128;
+                let fee = parse_u64(&v.fee)? as i128;
+                self.token(&v.account, BalanceDelta::Exact(-amount));
+                self.token(&v.recipient, BalanceDelta::Exact(amount - fee));
+            }
+            ProgramDetail::TokenBurn(v) => self.token(
+                &v.account,
+                BalanceDelta::Exact(-(parse_u64(&v.amount)? as i128)),
+            ),
+            ProgramDetail::TokenBurnChecked(v) => self.token(
+                &v.account,
+                BalanceDelta::Exact(-(parse_u64(&v.amount)? as i128)),
+            ),
+            ProgramDetail::TokenMintTo(v) => self.token(
+                &v.mint_to_account,
+                BalanceDelta::Exact(parse_u64(&v.amount)? as i128),
+            ),
+            ProgramDetail::TokenMintToChecked(v) => self.token(
+                &v.mint_to_account,
+                BalanceDelta::Exact(parse_u64(&v.amount)? as i128),
+            ),
+            ProgramDetail::TokenCloseAccount(v) => self.drain_sol(&v.account, &v.recipient),
+            // the new account takes its rent from the funding account
+            ProgramDetail::AssociatedTokenAccountCreate(v) => {
+                self.drain_sol(&v.funding_account, &v.associated_account)
+            }
+            // these only change settings or state, never a balance
+            ProgramDetail::ComputeBudgetRequestUnits(_)
+            | ProgramDetail::ComputeBudgetRequestHeapFrame(_)
+            | ProgramDetail::ComputeBudgetSetComputeUnitLimit(_)
+            | ProgramDetail::ComputeBudgetSetComputeUnitPrice(_)
+            | ProgramDetail::ComputeBudgetSetLoadedAccountsDataSizeLimit(_)
+            | ProgramDetail::Memo(_)
+            | ProgramDetail::SystemAssign(_)
+            | ProgramDetail::SystemAssignWithSeed(_)
+            | ProgramDetail::SystemAllocate(_)
+            | ProgramDetail::SystemAllocateWithSeed(_)
+            | ProgramDetail::SystemAdvanceNonceAccount(_)
+            | ProgramDetail::SystemInitializeNonceAccount(_)
+            | ProgramDetail::SystemAuthorizeNonceAccount(_)
+            | ProgramDetail::SystemUpgradeNonceAccount(_)
+            | ProgramDetail::VoteUpdateValidatorIdentity(_)
+            | ProgramDetail::VoteUpdateCommission(_)
+            | ProgramDetail::VoteVoteSwitch(_)
+            | ProgramDetail::VoteAuthorizeChecked(_)
+            | ProgramDetail::VoteUpdateVoteState(_)
+            | ProgramDetail::VoteUpdateVoteStateSwitch(_)
+            | ProgramDetail::VoteAuthorizeWithSeed(_)
+            | ProgramDetail::VoteAuthorizeCheckedWithSeed(_)
+            | ProgramDetail::VoteInitializeAccount(_)
+            | ProgramDetail::VoteAuthorize(_)
+            | ProgramDetail::VoteVote(_)
+            | ProgramDetail::TokenInitializeMint(_)
+            | ProgramDetail::TokenInitializeMint2(_)
+            | ProgramDetail::TokenInitializeAccount(_)
+            | ProgramDetail::TokenInitializeAccount2(_)
+            | ProgramDetail::TokenInitializeAccount3(_)
+            | ProgramDetail::TokenInitializeMultisig(_)
+            | ProgramDetail::TokenInitializeMultisig2(_)
+            | ProgramDetail::TokenApprove(_)
+            | ProgramDetail::TokenApproveChecked(_)
+            | ProgramDetail::TokenRevoke(_)
+            | ProgramDetail::TokenSetAuthority(_)
+            | ProgramDetail::TokenFreezeAccount(_)
+            | ProgramDetail::TokenThawAccount(_)
+            | ProgramDetail::TokenSyncNative(_)
+            | ProgramDetail::Token2022InitializeTransferFeeConfig(_)
+            | ProgramDetail::Token2022SetTransferFee(_)
+            | ProgramDetail::Token2022DefaultAccountState(_)
+            | ProgramDetail::Token2022InterestBearingMint(_)
+            | ProgramDetail::Token2022InitializePermanentDelegate(_)
+            | ProgramDetail::Token2022TransferHook(_)
+            | ProgramDetail::StakeInitialize(_)
+            | ProgramDetail::StakeInitializeChecked(_)
+            | ProgramDetail::StakeAuthorize(_)
+            | ProgramDetail::StakeAuthorizeChecked(_)
+            | ProgramDetail::StakeAuthorizeWithSeed(_)
+            | ProgramDetail::StakeAuthorizeCheckedWithSeed(_)
+            | ProgramDetail::StakeDelegateStake(_)
+            | ProgramDetail::StakeDeactivate(_)
+            | ProgramDetail::StakeDeactivateDelinquent(_)
+            | ProgramDetail::StakeSetLockup(_)
+            | ProgramDetail::StakeSetLockupChecked(_)
+            | ProgramDetail::StakeGetMinimumDelegation(_) => {}
+            kind => match JupiterSwap::from(kind) {
+                Some(swap) => {
+                    self.token(swap.source, swap.source_delta);
+                    self.token(swap.destination, swap.destination_delta);
+                }
+                None => self.unknown_effects.push(UnknownEffect {
+                    program: detail.common.program.to_string(),
+                    method: detail.common.method.to_string(),
+                    accounts: accounts.to_vec(),
+                }),
+            },
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> BalanceChanges {
+        for change in self.changes.iter_mut() {
+            change.unknown_effect = self
+                .unknown_effects
+                .iter()
+                .any(|v| v.accounts.contains(&change.account));
+            let (symbol, decimals) = match &change.mint {
+                None => ("SOL".to_string(), Some(SOL_DECIMALS)),
+                Some(mint) => {
+                    let (symbol, _, decimals) = ParsedSolanaTx::find_token_info(mint);
+                    let decimals = self
+                        .mint_decimals
+                        .iter()
+                        .find(|(m, _)| m == mint)
+                        .map(|(_, decimals)| *decimals)
+                        // the token list only knows the decimals of the tokens it names
+                        .or((symbol != "SPLToken").then_some(decimals));
+                    (symbol, decimals)
+                }
+            };
+            change.symbol = symbol;
+            change.decimals = decimals;
+        }
+        BalanceChanges {
+            changes: self.changes,
+            unknown_effects: self.unknown_effects,
+        }
+    }
+}
+
+impl ParsedSolanaTx {
+    pub(super) fn build_balance_changes(
+        details: &[SolanaDetail],
+        message: &Message,
+        network_fee: &ProgramOverviewNetworkFee,
+    ) -> Result<BalanceChanges> {
+        // the first account of a message always pays the fee
+        let fee_payer = message
+            .accounts
+            .first()
+            .map(|v| base58::encode(&v.value))
+            .ok_or(SolanaError::ParseTxError("empty accounts".to_string()))?;
+        Self::analyze_balance_changes(
+            details,
+            &message.to_instruction_accounts(),
+            &fee_payer,
+            network_fee.total_fee_lamports,
+        )
+    }
+
+    fn analyze_balance_changes(
+        details: &[SolanaDetail],
+        instruction_accounts: &[Vec<String>],
+        fee_payer: &str,
+        fee: u64,
+    ) -> Result<BalanceChanges> {
+        let mut table = BalanceTable::default();
+        details.iter().for_each(|detail| table.learn_mints(detail));
+        table.sol(fee_payer, BalanceDelta::Exact(-(fee as i128)));
+        for (index, detail) in details.iter().enumerate() {
+            let accounts = instruction_accounts
+                .get(index)
+                .map(|v| v.as_slice())
+                .unwrap_or_default();
+            table.apply(detail, accounts)?;
+        }
+        Ok(table.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::detail::{
+        CommonDetail, JupiterV6RouteDetail, JupiterV6SharedAccountsExactOutRouteDetail,
+        ProgramDetailTokenTransfer, ProgramDetailUnknown,
+    };
+    use crate::solana_lib::jupiter_v6::instructions::{RouteArgs, SharedAccountsExactOutRouteArgs};
+    use hex::FromHex;
+
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    fn detail(program: &str, method: &str, kind: ProgramDetail) -> SolanaDetail {
+        SolanaDetail {
+            common: CommonDetail {
+                program: program.to_string(),
+                method: method.to_string(),
+            },
+            kind,
+        }
+    }
+
+    fn accounts(names: &[&str]) -> Vec<String> {
+        names.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn find<'a>(
+        changes: &'a BalanceChanges,
+        account: &str,
+        mint: Option<&str>,
+    ) -> &'a BalanceChange {
+        changes
+            .changes
+            .iter()
+            .find(|v| v.account == account && v.mint.as_deref() == mint)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_balance_delta_add() {
+        use BalanceDelta::*;
+        assert_eq!(Exact(3), Exact(1) + Exact(2));
+        assert_eq!(AtLeast(-1), Exact(-3) + AtLeast(2));
+        assert_eq!(AtMost(5), AtMost(2) + Exact(3));
+        assert_eq!(Unknown, AtLeast(2) + AtMost(3));
+        assert_eq!(Unknown, Exact(2) + Unknown);
+    }
+
+    #[test]
+    fn test_balance_change_amount_overflow() {
+        let change = BalanceChange {
+            account: "account".to_string(),
+            mint: None,
+            symbol: "SOL".to_string(),
+            decimals: Some(SOL_DECIMALS),
+            delta: BalanceDelta::Exact(u64::MAX as i128) + BalanceDelta::Exact(1),
+            unknown_effect: false,
+        };
+        assert!(change.amount().is_err());
+    }
+
+    #[test]
+    fn test_balance_changes_of_system_transfer() {
+        // compute budget instructions and a transfer of 0.01 SOL
+        let data = Vec::from_hex("010002041a93fffb26ce645adeae58f0f414c320bcec30ce12a66bd263a91ec9b3958ff46f345144d352e4190c2dec43e1d3e0296a49bdfc2594eed9d8a5902e22d0af8b00000000000000000000000000000000000000000000000000000000000000000306466fe5211732ffecadba72c39be7bc8ce5bbc5f7126b2c439b3a40000000f70a9d4448ef435c5beab6cbc4211e00ddb4b9ad84886385f8b7ccfb9d9e7ca40303000903d8d600000000000003000502400d0300020200010c020000008096980000000000").unwrap();
+        let parsed = ParsedSolanaTx::build(&data).unwrap();
+        let fee = parsed.overview.network_fee.total_fee_lamports as i128;
+        let changes = parsed.balance_changes;
+        assert!(changes.unknown_effects.is_empty());
+        assert_eq!(2, changes.changes.len());
+        let from = &changes.changes[0];
+        assert_eq!(None, from.mint);
+        assert_eq!(BalanceDelta::Exact(-10_000_000 - fee), from.delta);
+        let to = &changes.changes[1];
+        assert_eq!(BalanceDelta::Exact(10_000_000), to.delta);
+        assert_eq!("+0.01 SOL", to.amount().unwrap());
+        assert!(!to.unknown_effect);
+    }
+
+    #[test]
+    fn test_balance_changes_of_token_transfers_and_swaps() {
+        let details = vec![
+            detail(
+                "Token",
+                "Transfer",
+                ProgramDetail::TokenTransfer(ProgramDetailTokenTransfer {
+                    source_account: "source".to_string(),
+                    recipient: "recipient".to_string(),
+                    owner: "owner".to_string(),
+                    signers: None,
+                    amount: "1500000".to_string(),
+                }),
+            ),
+            detail(
+                "JupiterV6",
+                "SharedAccountsExactOutRoute",
+                ProgramDetail::JupiterV6SharedAccountsExactOutRoute(
+                    JupiterV6SharedAccountsExactOutRouteDetail {
+                        accounts: accounts(&[
+                            "token_program",
+                            "program_authority",
+                            "owner",
+                            "source",
+                            "program_source",
+                            "program_destination",
+                            "destination",
+                            USDC,
+                            "mint_b",
+                        ]),
+                        args: SharedAccountsExactOutRouteArgs {
+                            out_amount: 700,
+                            quoted_in_amount: 1_000_000,
+                            slippage_bps: 50,
+                            ..Default::default()
+                        },
+                    },
+                ),
+            ),
+            detail(
+                "JupiterV6",
+                "Route",
+                ProgramDetail::JupiterV6Route(JupiterV6RouteDetail {
+                    accounts: accounts(&[
+                        "token_program",
+                        "owner",
+                        "other_source",
+                        "destination",
+                        "destination",
+                        "mint_b",
+                    ]),
+                    args: RouteArgs {
+                        in_amount: 42,
+                        quoted_out_amount: 1000,
+                        slippage_bps: 100,
+                        ..Default::default()
+                    },
+                }),
+            ),
+            detail(
+                "Unknown",
+                "",
+                ProgramDetail::Unknown(ProgramDetailUnknown::default()),
+            ),
+        ];
+        let instruction_accounts = vec![
+            vec![],
+            vec![],
+            vec![],
+            accounts(&["destination", "program"]),
+        ];
+        let changes =
+            ParsedSolanaTx::analyze_balance_changes(&details, &instruction_accounts, "owner", 5000)
+                .unwrap();
+
+        let fee = find(&changes, "owner", None);
+        assert_eq!(BalanceDelta::Exact(-5000), fee.delta);
+        assert_eq!("-0.000005 SOL", fee.amount().unwrap());
+
+        // the mint of the transfer is learned from the swap
+        let source = find(&changes, "source", Some(USDC));
+        assert_eq!(BalanceDelta::AtLeast(-1_500_000 - 1_005_000), source.delta);
+        assert_eq!("USDC", source.symbol);
+        assert_eq!("at least -2.505 USDC", source.amount().unwrap());
+        let recipient = find(&changes, "recipient", Some(UNKNOWN_MINT));
+        assert_eq!(BalanceDelta::Exact(1_500_000), recipient.delta);
+        assert_eq!(None, recipient.decimals);
+        assert_eq!("+1500000 SPLToken", recipient.amount().unwrap());
+
+        let other_source = find(&changes, "other_source", Some(UNKNOWN_MINT));
+        assert_eq!(BalanceDelta::Exact(-42), other_source.delta);
+        let destination = find(&changes, "destination", Some("mint_b"));
+        assert_eq!(BalanceDelta::AtLeast(700 + 990), destination.delta);
+        assert!(destination.unknown_effect);
+
+        assert_eq!(1, changes.unknown_effects.len());
+        assert_eq!("Unknown", changes.unknown_effects[0].program);
+    }
+}
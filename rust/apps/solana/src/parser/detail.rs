@@ -26,6 +26,8 @@ pub struct ProgramDetailSystemTransfer {
     pub value: String,
     pub from: String,
     pub to: String,
+    #[serde(skip)]
+    pub lamports: u64,
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
@@ -36,6 +38,8 @@ pub struct ProgramDetailSystemTransferWithSeed {
     pub from_base_pubkey: String,
     pub from_owner: String,
     pub from_seed: String,
+    #[serde(skip)]
+    pub lamports: u64,
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
@@ -448,6 +452,155 @@ pub struct ProgramDetailTokenInitializeMint2 {
     pub decimals: u8,
 }
 
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailToken2022TransferCheckedWithFee {
+    pub account: String,
+    pub mint: String,
+    pub recipient: String,
+    pub owner: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signers: Option<Vec<String>>,
+    pub decimals: u8,
+    pub amount: String,
+    pub fee: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailToken2022InitializeTransferFeeConfig {
+    pub mint: String,
+    pub transfer_fee_config_authority: String,
+    pub withdraw_withheld_authority: String,
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailToken2022SetTransferFee {
+    pub mint: String,
+    pub transfer_fee_config_authority: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signers: Option<Vec<String>>,
+    pub transfer_fee_basis_points: u16,
+    pub maximum_fee: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailToken2022WithdrawWithheldTokens {
+    pub mint: String,
+    pub recipient: String,
+    pub withdraw_withheld_authority: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signers: Option<Vec<String>>,
+    pub source_accounts: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailToken2022HarvestWithheldTokens {
+    pub mint: String,
+    pub source_accounts: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailToken2022ConfidentialTransfer {
+    pub accounts: Vec<String>,
+    // only deposits and withdrawals carry a plain amount
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u8>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailToken2022DefaultAccountState {
+    pub mint: String,
+    pub freeze_authority_pubkey: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signers: Option<Vec<String>>,
+    pub state: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailToken2022InterestBearingMint {
+    pub mint: String,
+    pub rate_authority: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signers: Option<Vec<String>>,
+    pub rate: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailToken2022InitializePermanentDelegate {
+    pub mint: String,
+    pub delegate: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailToken2022TransferHook {
+    pub mint: String,
+    pub authority: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signers: Option<Vec<String>>,
+    pub program_id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailToken2022Extension {
+    pub accounts: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailComputeBudgetRequestUnits {
+    pub units: u32,
+    pub additional_fee: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailComputeBudgetRequestHeapFrame {
+    pub bytes: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailComputeBudgetSetComputeUnitLimit {
+    pub units: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailComputeBudgetSetComputeUnitPrice {
+    pub micro_lamports: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailComputeBudgetSetLoadedAccountsDataSizeLimit {
+    pub bytes: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailMemo {
+    pub memo: String,
+    pub signers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailAssociatedTokenAccountCreate {
+    pub funding_account: String,
+    pub associated_account: String,
+    pub wallet: String,
+    pub mint: String,
+    pub system_program: String,
+    pub token_program: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProgramDetailAssociatedTokenAccountRecoverNested {
+    pub nested_account: String,
+    pub nested_mint: String,
+    pub destination_account: String,
+    pub owner_account: String,
+    pub owner_mint: String,
+    pub wallet: String,
+    pub token_program: String,
+}
+
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct ProgramDetailLendingInitLendingMarket {
     pub lending_market_account: String,
@@ -1152,6 +1305,35 @@ pub enum ProgramDetail {
     TokenInitializeMultisig2(ProgramDetailTokenInitializeMultisig2),
     TokenInitializeMint2(ProgramDetailTokenInitializeMint2),
 
+    // token 2022, instructions shared with the token program use the token variants
+    Token2022TransferCheckedWithFee(ProgramDetailToken2022TransferCheckedWithFee),
+    Token2022InitializeTransferFeeConfig(ProgramDetailToken2022InitializeTransferFeeConfig),
+    Token2022SetTransferFee(ProgramDetailToken2022SetTransferFee),
+    Token2022WithdrawWithheldTokens(ProgramDetailToken2022WithdrawWithheldTokens),
+    Token2022HarvestWithheldTokens(ProgramDetailToken2022HarvestWithheldTokens),
+    Token2022ConfidentialTransfer(ProgramDetailToken2022ConfidentialTransfer),
+    Token2022DefaultAccountState(ProgramDetailToken2022DefaultAccountState),
+    Token2022InterestBearingMint(ProgramDetailToken2022InterestBearingMint),
+    Token2022InitializePermanentDelegate(ProgramDetailToken2022InitializePermanentDelegate),
+    Token2022TransferHook(ProgramDetailToken2022TransferHook),
+    Token2022Extension(ProgramDetailToken2022Extension),
+
+    // compute budget
+    ComputeBudgetRequestUnits(ProgramDetailComputeBudgetRequestUnits),
+    ComputeBudgetRequestHeapFrame(ProgramDetailComputeBudgetRequestHeapFrame),
+    ComputeBudgetSetComputeUnitLimit(ProgramDetailComputeBudgetSetComputeUnitLimit),
+    ComputeBudgetSetComputeUnitPrice(ProgramDetailComputeBudgetSetComputeUnitPrice),
+    ComputeBudgetSetLoadedAccountsDataSizeLimit(
+        ProgramDetailComputeBudgetSetLoadedAccountsDataSizeLimit,
+    ),
+
+    // memo
+    Memo(ProgramDetailMemo),
+
+    // associated token account
+    AssociatedTokenAccountCreate(ProgramDetailAssociatedTokenAccountCreate),
+    AssociatedTokenAccountRecoverNested(ProgramDetailAssociatedTokenAccountRecoverNested),
+
     // token lending
     LendingInitLendingMarket(ProgramDetailLendingInitLendingMarket),
     LendingSetLendingMarketOwner(ProgramDetailLendingSetLendingMarketOwner),
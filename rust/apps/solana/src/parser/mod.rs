@@ -7,34 +7,61 @@ use bitcoin::base58;
 use serde_json::json;
 
 use crate::errors::{Result, SolanaError};
-use crate::message::Message;
+use crate::message::{AddressLookupTable, Message};
 use crate::parser::detail::{
-    CommonDetail, ProgramDetail, ProgramDetailGeneralUnknown, SolanaDetail,
+    CommonDetail, ProgramDetail, ProgramDetailGeneralUnknown, ProgramDetailTokenTransferChecked,
+    SolanaDetail,
 };
 use crate::parser::overview::{
-    JupiterV6SwapOverview, JupiterV6SwapTokenInfoOverview, ProgramOverviewGeneral,
+    JupiterV6SwapOverview, JupiterV6SwapTokenInfoOverview, ProgramOverview, ProgramOverviewGeneral,
     ProgramOverviewInstruction, ProgramOverviewInstructions, ProgramOverviewMultisigCreate,
-    ProgramOverviewProposal, ProgramOverviewSplTokenTransfer, ProgramOverviewTransfer,
-    ProgramOverviewVote, SolanaOverview,
+    ProgramOverviewNetworkFee, ProgramOverviewProposal, ProgramOverviewSplTokenTransfer,
+    ProgramOverviewTransfer, ProgramOverviewVote, SolanaOverview,
 };
 use crate::parser::structs::{ParsedSolanaTx, SolanaTxDisplayType};
 use crate::read::Read;
+use crate::resolvers::{compute_budget, format_amount, token_2022};
 use crate::utils;
 
+pub mod balance_change;
 pub mod detail;
 pub mod overview;
 pub mod structs;
 
+const LAMPORTS_PER_SIGNATURE: u64 = 5000;
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
 impl ParsedSolanaTx {
     pub fn build(data: &Vec<u8>) -> Result<Self> {
         let message = Message::read(data.clone().to_vec().as_mut())?;
+        Self::build_from_message(message)
+    }
+
+    pub fn build_with_lookup_tables(
+        data: &Vec<u8>,
+        lookup_tables: &[AddressLookupTable],
+    ) -> Result<Self> {
+        let mut message = Message::read(data.clone().to_vec().as_mut())?;
+        message.resolve_address_lookup_tables(lookup_tables)?;
+        Self::build_from_message(message)
+    }
+
+    fn build_from_message(message: Message) -> Result<Self> {
         let raw_details = message.to_program_details()?;
         let display_type = Self::detect_display_type(&raw_details);
-        let parsed_overview = Self::build_overview(&display_type, &raw_details)?;
+        let parsed_overview = Self::build_overview(&display_type, &raw_details, &message)?;
         let parsed_detail = Self::build_detail(&display_type, &raw_details, &message)?;
+        let network_fee = Self::build_network_fee(&raw_details, &message)?;
+        let balance_changes = Self::build_balance_changes(&raw_details, &message, &network_fee)?;
         Ok(Self {
             display_type,
-            overview: parsed_overview,
+            overview: SolanaOverview {
+                kind: parsed_overview,
+                network_fee,
+            },
+            balance_changes,
             detail: parsed_detail,
             network: "Solana Mainnet".to_string(),
         })
@@ -81,11 +108,16 @@ impl ParsedSolanaTx {
             return SolanaTxDisplayType::Vote;
         }
 
+        // compute budget instructions only set the fee, they don't make the rest readable
         let instructions: Vec<&SolanaDetail> = details
             .iter()
-            .filter(|d| Self::is_unknown_detail(&d.common))
+            .filter(|d| !Self::is_compute_budget_detail(&d.common))
             .collect::<Vec<&SolanaDetail>>();
-        if instructions.len() == details.len() {
+        if (details.is_empty() || !instructions.is_empty())
+            && instructions
+                .iter()
+                .all(|d| Self::is_unknown_detail(&d.common))
+        {
             return SolanaTxDisplayType::Unknown;
         }
         SolanaTxDisplayType::General
@@ -96,7 +128,10 @@ impl ParsedSolanaTx {
     }
 
     fn is_token_transfer_checked_detail(common: &CommonDetail) -> bool {
-        common.program.eq("Token") && common.method.eq("TransferChecked")
+        (common.program.eq("Token") && common.method.eq("TransferChecked"))
+            || (common.program.eq(token_2022::PROGRAM_NAME)
+                && (common.method.eq("TransferChecked")
+                    || common.method.eq("TransferCheckedWithFee")))
     }
 
     fn is_vote_detail(common: &CommonDetail) -> bool {
@@ -111,6 +146,10 @@ impl ParsedSolanaTx {
         common.program.eq("Instructions") && common.method.eq("")
     }
 
+    fn is_compute_budget_detail(common: &CommonDetail) -> bool {
+        common.program.eq(compute_budget::PROGRAM_NAME)
+    }
+
     fn is_sqauds_v4_detail(common: &CommonDetail) -> bool {
         common.program.eq("SquadsV4")
     }
@@ -156,7 +195,7 @@ impl ParsedSolanaTx {
         details: &[SolanaDetail],
         message: &Message,
     ) -> Result<String> {
-        match display_type {
+        let detail = match display_type {
             SolanaTxDisplayType::Unknown => Self::build_unknown_detail(details, message),
             SolanaTxDisplayType::General => Self::build_genera_detail(details),
             SolanaTxDisplayType::Transfer | SolanaTxDisplayType::Vote => {
@@ -165,16 +204,42 @@ impl ParsedSolanaTx {
             SolanaTxDisplayType::SquadsV4 => Ok(serde_json::to_string(&details)?),
             SolanaTxDisplayType::TokenTransfer => Ok(serde_json::to_string(&details)?),
             SolanaTxDisplayType::JupiterV6 => Ok(serde_json::to_string(&details)?),
-        }
+        }?;
+        Self::append_lookup_table_accounts(detail, message)
+    }
+
+    // accounts resolved from the lookup tables carried with the request are listed next to the
+    // instructions, since the device can't prove the table contents
+    fn append_lookup_table_accounts(detail: String, message: &Message) -> Result<String> {
+        let loaded = match &message.loaded_addresses {
+            Some(loaded) => loaded,
+            None => return Ok(detail),
+        };
+        let accounts = loaded
+            .writable
+            .iter()
+            .chain(loaded.readonly.iter())
+            .collect::<Vec<&String>>();
+        let value = match serde_json::from_str::<serde_json::Value>(&detail)? {
+            serde_json::Value::Object(mut value) => {
+                value.insert("lookup_table_accounts".to_string(), json!(accounts));
+                serde_json::Value::Object(value)
+            }
+            instructions => json!({
+                "instructions": instructions,
+                "lookup_table_accounts": accounts,
+            }),
+        };
+        Ok(value.to_string())
     }
 
-    fn build_transfer_overview(details: &[SolanaDetail]) -> Result<SolanaOverview> {
-        let overview: Option<SolanaOverview> = details
+    fn build_transfer_overview(details: &[SolanaDetail]) -> Result<ProgramOverview> {
+        let overview: Option<ProgramOverview> = details
             .iter()
             .find(|d| Self::is_system_transfer_detail(&d.common))
             .and_then(|detail| {
                 if let ProgramDetail::SystemTransfer(v) = &detail.kind {
-                    Some(SolanaOverview::Transfer(ProgramOverviewTransfer {
+                    Some(ProgramOverview::Transfer(ProgramOverviewTransfer {
                         value: v.value.to_string(),
                         main_action: "SOL Transfer".to_string(),
                         from: v.from.to_string(),
@@ -323,41 +388,104 @@ impl ParsedSolanaTx {
             _ => ("SPLToken".to_string(), "Unknown".to_string(), 0),
         }
     }
-    fn build_token_transfer_checked_overview(details: &[SolanaDetail]) -> Result<SolanaOverview> {
-        let overview: Option<SolanaOverview> = details
+    fn build_token_transfer_checked_overview(
+        details: &[SolanaDetail],
+        message: &Message,
+    ) -> Result<ProgramOverview> {
+        let overview: Option<ProgramOverview> = details
             .iter()
             .find(|d| Self::is_token_transfer_checked_detail(&d.common))
             .and_then(|detail| {
-                if let ProgramDetail::TokenTransferChecked(v) = &detail.kind {
-                    let amount_f64 = v.amount.parse::<f64>().unwrap();
-                    let amount = amount_f64 / 10u64.pow(v.decimals as u32) as f64;
-                    Some(SolanaOverview::SplTokenTransfer(
-                        ProgramOverviewSplTokenTransfer {
-                            source: v.account.to_string(),
-                            destination: v.recipient.to_string(),
-                            authority: v.owner.to_string(),
+                let (v, fee) = match &detail.kind {
+                    ProgramDetail::TokenTransferChecked(v) => (v.clone(), None),
+                    ProgramDetail::Token2022TransferCheckedWithFee(v) => (
+                        ProgramDetailTokenTransferChecked {
+                            account: v.account.clone(),
+                            mint: v.mint.clone(),
+                            recipient: v.recipient.clone(),
+                            owner: v.owner.clone(),
+                            signers: v.signers.clone(),
                             decimals: v.decimals,
-                            amount: format!("{} {}", amount, Self::find_token_info(&v.mint).0),
-                            token_mint_account: v.mint.clone(),
-                            token_symbol: Self::find_token_info(&v.mint).0,
-                            token_name: Self::find_token_info(&v.mint).1,
+                            amount: v.amount.clone(),
                         },
-                    ))
+                        Some(v.fee.clone()),
+                    ),
+                    _ => return None,
+                };
+                let (token_symbol, token_name, _) = Self::find_token_info(&v.mint);
+                let format_amount = |value: &str| {
+                    let value = value.parse::<f64>().unwrap() / 10u64.pow(v.decimals as u32) as f64;
+                    format!("{} {}", value, token_symbol)
+                };
+                let transfer_hook = if detail.common.program == token_2022::PROGRAM_NAME {
+                    Self::find_transfer_hook(details, &v.mint, &v.signers, message)
                 } else {
                     None
-                }
+                };
+                let transfer_hook_verified = matches!(transfer_hook, Some((_, true)));
+                Some(ProgramOverview::SplTokenTransfer(
+                    ProgramOverviewSplTokenTransfer {
+                        source: v.account.to_string(),
+                        destination: v.recipient.to_string(),
+                        authority: v.owner.to_string(),
+                        decimals: v.decimals,
+                        amount: format_amount(&v.amount),
+                        fee: fee.map(|fee| format_amount(&fee)),
+                        transfer_hook: transfer_hook.map(|(program_id, _)| program_id),
+                        transfer_hook_verified,
+                        token_mint_account: v.mint.clone(),
+                        token_symbol: token_symbol.clone(),
+                        token_name,
+                    },
+                ))
             });
         overview.ok_or(SolanaError::ParseTxError(
             "parse spl token transfer failed, empty transfer program".to_string(),
         ))
     }
-    fn build_vote_overview(details: &[SolanaDetail]) -> Result<SolanaOverview> {
-        let overview: Option<SolanaOverview> = details
+
+    // the hook set up in the same transaction is verified, otherwise Token-2022 transfers of a
+    // mint with a transfer hook carry the extra accounts of the hook followed by the hook program
+    // and its validation account, while the extra accounts of a multisig owner all sign the
+    // message, so the program found there is unverified
+    fn find_transfer_hook(
+        details: &[SolanaDetail],
+        mint: &str,
+        extra_accounts: &Option<Vec<String>>,
+        message: &Message,
+    ) -> Option<(String, bool)> {
+        let configured = details.iter().find_map(|d| match &d.kind {
+            ProgramDetail::Token2022TransferHook(v)
+                if v.mint == mint && !v.program_id.is_empty() =>
+            {
+                Some((v.program_id.clone(), true))
+            }
+            _ => None,
+        });
+        if configured.is_some() {
+            return configured;
+        }
+        let extra_accounts = extra_accounts.as_ref()?;
+        let signers = message
+            .accounts
+            .iter()
+            .take(message.header.num_required_signatures as usize)
+            .map(|v| base58::encode(&v.value))
+            .collect::<Vec<String>>();
+        if extra_accounts.len() < 2 || extra_accounts.iter().all(|v| signers.contains(v)) {
+            return None;
+        }
+        extra_accounts
+            .get(extra_accounts.len() - 2)
+            .map(|program_id| (program_id.clone(), false))
+    }
+    fn build_vote_overview(details: &[SolanaDetail]) -> Result<ProgramOverview> {
+        let overview: Option<ProgramOverview> = details
             .iter()
             .find(|d| Self::is_vote_detail(&d.common))
             .and_then(|detail| {
                 if let ProgramDetail::VoteVote(v) = &detail.kind {
-                    Some(SolanaOverview::Vote(ProgramOverviewVote {
+                    Some(ProgramOverview::Vote(ProgramOverviewVote {
                         votes_on: v.slots.to_owned(),
                         main_action: "Vote".to_string(),
                         vote_account: v.vote_account.to_string(),
@@ -371,7 +499,7 @@ impl ParsedSolanaTx {
         ))
     }
 
-    fn build_general_overview(details: &[SolanaDetail]) -> Result<SolanaOverview> {
+    fn build_general_overview(details: &[SolanaDetail]) -> Result<ProgramOverview> {
         let mut overview = Vec::new();
         details.iter().for_each(|d| {
             if d.common.program != SolanaTxDisplayType::Unknown.to_string() {
@@ -381,9 +509,9 @@ impl ParsedSolanaTx {
                 })
             }
         });
-        Ok(SolanaOverview::General(overview))
+        Ok(ProgramOverview::General(overview))
     }
-    fn build_squads_v4_proposal_overview(details: &[SolanaDetail]) -> Result<SolanaOverview> {
+    fn build_squads_v4_proposal_overview(details: &[SolanaDetail]) -> Result<ProgramOverview> {
         let mut proposal_overview_vec: Vec<ProgramOverviewProposal> = Vec::new();
         for d in details {
             let kind = &d.kind;
@@ -456,9 +584,12 @@ impl ParsedSolanaTx {
                 _ => {}
             }
         }
-        return Ok(SolanaOverview::SquadsV4Proposal(proposal_overview_vec));
+        return Ok(ProgramOverview::SquadsV4Proposal(proposal_overview_vec));
     }
-    fn build_squads_v4_multisig_overview(details: &[SolanaDetail]) -> Result<SolanaOverview> {
+    fn build_squads_v4_multisig_overview(
+        details: &[SolanaDetail],
+        message: &Message,
+    ) -> Result<ProgramOverview> {
         let mut transfer_overview_vec: Vec<ProgramOverviewTransfer> = Vec::new();
         let mut total_value = 0f64;
         details.iter().for_each(|d| {
@@ -495,7 +626,7 @@ impl ParsedSolanaTx {
                     .map(|m| m.key.to_string())
                     .collect::<Vec<String>>();
                 let total_value = format!("~{:.3} SOL", total_value);
-                return Ok(SolanaOverview::SquadsV4MultisigCreate(
+                return Ok(ProgramOverview::SquadsV4MultisigCreate(
                     ProgramOverviewMultisigCreate {
                         wallet_name,
                         wallet_desc,
@@ -523,7 +654,7 @@ impl ParsedSolanaTx {
                     .map(|m| m.key.to_string())
                     .collect::<Vec<String>>();
                 let total_value = format!("~{:.3} SOL", total_value);
-                return Ok(SolanaOverview::SquadsV4MultisigCreate(
+                return Ok(ProgramOverview::SquadsV4MultisigCreate(
                     ProgramOverviewMultisigCreate {
                         wallet_name,
                         wallet_desc,
@@ -536,9 +667,12 @@ impl ParsedSolanaTx {
                 ));
             }
         }
-        return Self::build_instructions_overview(details);
+        return Self::build_instructions_overview(details, message);
     }
-    fn build_squads_overview(details: &[SolanaDetail]) -> Result<SolanaOverview> {
+    fn build_squads_overview(
+        details: &[SolanaDetail],
+        message: &Message,
+    ) -> Result<ProgramOverview> {
         if details.iter().any(|d| {
             matches!(
                 d.kind,
@@ -546,7 +680,7 @@ impl ParsedSolanaTx {
                     | ProgramDetail::SquadsV4MultisigCreateV2(_)
             )
         }) {
-            return Self::build_squads_v4_multisig_overview(details);
+            return Self::build_squads_v4_multisig_overview(details, message);
         }
 
         if details.iter().any(|d| {
@@ -563,16 +697,11 @@ impl ParsedSolanaTx {
         }) {
             return Self::build_squads_v4_proposal_overview(details);
         }
-        return Self::build_instructions_overview(details);
-    }
-
-    // util function to check the account is exist in the address lookup table
-    fn is_account_exist_in_lookup_table(account: &str) -> bool {
-        // if account contains #, it means it's a table account we add "Table" to the head
-        account.contains("#")
+        return Self::build_instructions_overview(details, message);
     }
 
     fn genreate_jupiter_swap_overview(
+        message: &Message,
         instruction_name: &str,
         token_a_mint: &str,
         token_b_mint: &str,
@@ -580,7 +709,7 @@ impl ParsedSolanaTx {
         platform_fee_bps: u16,
         in_amount: u64,
         out_amount: u64,
-    ) -> SolanaOverview {
+    ) -> ProgramOverview {
         let program_name = "Jupiter Aggregator v6";
         let program_address = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
         let base_percent = 10000;
@@ -603,7 +732,7 @@ impl ParsedSolanaTx {
                 ),
                 Self::find_token_info(&token_a_mint).0
             ),
-            exist_in_address_lookup_table: Self::is_account_exist_in_lookup_table(&token_a_mint),
+            exist_in_address_lookup_table: message.is_lookup_table_account(&token_a_mint),
         };
         let token_b_overview = JupiterV6SwapTokenInfoOverview {
             token_name: Self::find_token_info(&token_b_mint).1,
@@ -617,9 +746,9 @@ impl ParsedSolanaTx {
                 ),
                 Self::find_token_info(&token_b_mint).0
             ),
-            exist_in_address_lookup_table: Self::is_account_exist_in_lookup_table(&token_b_mint),
+            exist_in_address_lookup_table: message.is_lookup_table_account(&token_b_mint),
         };
-        SolanaOverview::JupiterV6SwapOverview(JupiterV6SwapOverview {
+        ProgramOverview::JupiterV6SwapOverview(JupiterV6SwapOverview {
             program_name: program_name.to_string(),
             program_address: program_address.to_string(),
             instruction_name: instruction_name.to_string(),
@@ -629,7 +758,10 @@ impl ParsedSolanaTx {
             platform_fee_bps,
         })
     }
-    fn build_jupiter_v6_overview(details: &[SolanaDetail]) -> Result<SolanaOverview> {
+    fn build_jupiter_v6_overview(
+        details: &[SolanaDetail],
+        message: &Message,
+    ) -> Result<ProgramOverview> {
         //  we only parse jupiter v6 swap on stage 1
         let jupiter_instruction = details.iter().find(|d| {
             matches!(
@@ -650,6 +782,7 @@ impl ParsedSolanaTx {
                     // index 8 : destination token mint
                     let token_b_mint = v.accounts[8].clone();
                     return Ok(Self::genreate_jupiter_swap_overview(
+                        message,
                         "JupiterV6SharedAccountsRoute",
                         &token_a_mint,
                         &token_b_mint,
@@ -667,6 +800,7 @@ impl ParsedSolanaTx {
                     // index 8 : destination token mint
                     let token_b_mint = v.accounts[8].clone();
                     return Ok(Self::genreate_jupiter_swap_overview(
+                        message,
                         "JupiterV6SharedAccountsExactOutRoute",
                         &token_a_mint,
                         &token_b_mint,
@@ -683,6 +817,7 @@ impl ParsedSolanaTx {
                     // index 6 : destination token mint
                     let token_b_mint = v.accounts[6].clone();
                     return Ok(Self::genreate_jupiter_swap_overview(
+                        message,
                         "JupiterV6ExactOutRoute",
                         &token_a_mint,
                         &token_b_mint,
@@ -699,6 +834,7 @@ impl ParsedSolanaTx {
                     // index 5 destination token mint
                     let token_b_mint = v.accounts[5].clone();
                     return Ok(Self::genreate_jupiter_swap_overview(
+                        message,
                         "JupiterV6Route",
                         &token_a_mint,
                         &token_b_mint,
@@ -711,10 +847,13 @@ impl ParsedSolanaTx {
                 _ => {}
             }
         }
-        return Self::build_instructions_overview(details);
+        return Self::build_instructions_overview(details, message);
     }
 
-    fn build_instructions_overview(details: &[SolanaDetail]) -> Result<SolanaOverview> {
+    fn build_instructions_overview(
+        details: &[SolanaDetail],
+        message: &Message,
+    ) -> Result<ProgramOverview> {
         let mut overview_instructions = Vec::new();
         let mut overview_accounts: Vec<String> = Vec::new();
         details.iter().for_each(|d| {
@@ -732,31 +871,96 @@ impl ParsedSolanaTx {
 
                 overview_instructions.push(ProgramOverviewInstruction {
                     accounts: accounts.to_owned(),
+                    from_lookup_table: accounts
+                        .iter()
+                        .map(|account| message.is_lookup_table_account(account))
+                        .collect(),
                     data,
                     program_address,
                 });
             }
         });
-        Ok(SolanaOverview::Instructions(ProgramOverviewInstructions {
+        Ok(ProgramOverview::Instructions(ProgramOverviewInstructions {
             overview_accounts,
             overview_instructions,
         }))
     }
 
+    // base fee of every signature plus the priority fee of the requested compute units,
+    // instructions without a compute unit limit get the runtime default
+    fn build_network_fee(
+        details: &[SolanaDetail],
+        message: &Message,
+    ) -> Result<ProgramOverviewNetworkFee> {
+        let parse_u64 = |value: &str| {
+            value
+                .parse::<u64>()
+                .map_err(|e| SolanaError::ParseTxError(format!("invalid fee {}: {}", value, e)))
+        };
+        let mut compute_unit_limit = None;
+        let mut compute_unit_price = 0;
+        let mut request_units_fee = None;
+        for detail in details {
+            match &detail.kind {
+                ProgramDetail::ComputeBudgetSetComputeUnitLimit(v) => {
+                    compute_unit_limit = Some(v.units)
+                }
+                ProgramDetail::ComputeBudgetSetComputeUnitPrice(v) => {
+                    compute_unit_price = parse_u64(&v.micro_lamports)?
+                }
+                // deprecated, pays a flat fee instead of a compute unit price
+                ProgramDetail::ComputeBudgetRequestUnits(v) => {
+                    compute_unit_limit = Some(v.units);
+                    request_units_fee = Some(parse_u64(&v.additional_fee)?);
+                }
+                _ => {}
+            }
+        }
+        let compute_unit_limit = compute_unit_limit
+            .unwrap_or_else(|| {
+                let count = details
+                    .iter()
+                    .filter(|d| !Self::is_compute_budget_detail(&d.common))
+                    .count() as u32;
+                count.saturating_mul(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT)
+            })
+            .min(MAX_COMPUTE_UNIT_LIMIT);
+        let overflow = || SolanaError::ParseTxError("network fee overflow".to_string());
+        let priority_fee = match request_units_fee {
+            Some(fee) => fee,
+            None => u64::try_from(
+                (compute_unit_limit as u128 * compute_unit_price as u128)
+                    .div_ceil(MICRO_LAMPORTS_PER_LAMPORT),
+            )
+            .map_err(|_| overflow())?,
+        };
+        let base_fee = LAMPORTS_PER_SIGNATURE * message.header.num_required_signatures as u64;
+        let total_fee = base_fee.checked_add(priority_fee).ok_or_else(overflow)?;
+        Ok(ProgramOverviewNetworkFee {
+            compute_unit_limit,
+            compute_unit_price,
+            base_fee: format_amount(base_fee.to_string())?,
+            priority_fee: format_amount(priority_fee.to_string())?,
+            total_fee: format_amount(total_fee.to_string())?,
+            total_fee_lamports: total_fee,
+        })
+    }
+
     fn build_overview(
         display_type: &SolanaTxDisplayType,
         details: &[SolanaDetail],
-    ) -> Result<SolanaOverview> {
+        message: &Message,
+    ) -> Result<ProgramOverview> {
         match display_type {
             SolanaTxDisplayType::Transfer => Self::build_transfer_overview(details),
             SolanaTxDisplayType::Vote => Self::build_vote_overview(details),
             SolanaTxDisplayType::General => Self::build_general_overview(details),
-            SolanaTxDisplayType::Unknown => Self::build_instructions_overview(details),
-            SolanaTxDisplayType::SquadsV4 => Self::build_squads_overview(details),
+            SolanaTxDisplayType::Unknown => Self::build_instructions_overview(details, message),
+            SolanaTxDisplayType::SquadsV4 => Self::build_squads_overview(details, message),
             SolanaTxDisplayType::TokenTransfer => {
-                Self::build_token_transfer_checked_overview(details)
+                Self::build_token_transfer_checked_overview(details, message)
             }
-            SolanaTxDisplayType::JupiterV6 => Self::build_jupiter_v6_overview(details),
+            SolanaTxDisplayType::JupiterV6 => Self::build_jupiter_v6_overview(details, message),
         }
     }
 }
@@ -774,8 +978,8 @@ mod tests {
         let data = "01000103876c762c4c83532f82966935ba1810659a96237028a2af6688dadecb0155ae071c7d0930a08193e702b0f24ebba96f179e9c186ef1208f98652ee775001744490000000000000000000000000000000000000000000000000000000000000000a7516fe1d3af3457fdc54e60856c0c3c87f4e5be3d10ffbc7a5cce8bf96792a101020200010c020000008813000000000000";
         let transaction = Vec::from_hex(data).unwrap();
         let parsed = ParsedSolanaTx::build(&transaction).unwrap();
-        match parsed.overview {
-            SolanaOverview::Transfer(overview) => {
+        match parsed.overview.kind {
+            ProgramOverview::Transfer(overview) => {
                 assert_eq!("0.000005 SOL".to_string(), overview.value);
                 assert_eq!(
                     "A7dxsCbMy5ktZwQUgsQhVxsoJpx6wPAZYEcccQVjWnkE".to_string(),
@@ -809,8 +1013,8 @@ mod tests {
         let data = "0301070a2877bcb8e7acfa950840ace015aee0f7c34f8da98cb47a0379a0848d1d146732295c04a089afe0f89e2eb1068d40b6dabc909d1c8fbd42a33d38388a9028952a0bfed77df018f67f348da938e5e96b9f66d05a542123fa3f9bebf7332df339cf000000000000000000000000000000000000000000000000000000000000000006a1d8179137542a983437bdfe2a7ab2557f535c8a78722b68a49dc00000000006a1d817a502050b680791e6ce6db88e1e5b7150f61fc6790a4eb4d10000000006a7d51718c774c928566398691d5eb68b5eb8a39b4b6d5c73555b210000000006a7d517192c5c51218cc94c3d4af17f58daee089ba1fd44e3dbd98a0000000006a7d517193584d0feed9bb3431d13206be544281b57b8566cc5375ff40000006dcf56df0c05417d5642726a2159a63bec115672bf8c57a694cf097e91a21c6f5cd38793d98807ac0a75e5553f78d37654a08385ecb7280ce9c7c905843164d305030200010c02000000af613239da0000000301010c08000000c800000000000000030101240100000006a1d8179137542a983437bdfe2a7ab2557f535c8a78722b68a49dc0000000000402010774000000000bfed77df018f67f348da938e5e96b9f66d05a542123fa3f9bebf7332df339cfc384e275e260429599cc2093f115fa55564dee44af077765b86417bd6967b37d00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004060109060805020402000000";
         let transaction = Vec::from_hex(data).unwrap();
         let parsed = ParsedSolanaTx::build(&transaction).unwrap();
-        match parsed.overview {
-            SolanaOverview::Transfer(overview) => {
+        match parsed.overview.kind {
+            ProgramOverview::Transfer(overview) => {
                 assert_eq!("937.262473647 SOL".to_string(), overview.value);
                 assert_eq!(
                     "3iyCqaBWcvHfEpN6NsdzjYpnAxVvioXTpjnPBLUwhva5".to_string(),
@@ -895,8 +1099,8 @@ mod tests {
         let data = "01000305b446cb8fd7c225bf416df87c286710d75711af95222e41216da2177289cbbfa6b68edcd94d93de68614892bd165a94a6647aa040d87b9a042b41a009bdb469cf06a7d51718c774c928566398691d5eb68b5eb8a39b4b6d5c73555b210000000006a7d517192f0aafc6f265e3fb77cc7ada82c529d0be3b136e2d0055200000000761481d357474bb7c4d7624ebd3bdb3d8355e73d11043fc0da35380000000009254bd5e695fabf43f0ead6da730e88cf39bec6991c2c4374bcade97d0a73be7010404010302003d02000000010000000000000060f2790800000000856a887d33af1cd1723388576a7be8fa6d9c9c80c548495a24bf680c908812cf01da7dd66200000000";
         let transaction = Vec::from_hex(data).unwrap();
         let parsed = ParsedSolanaTx::build(&transaction).unwrap();
-        match parsed.overview {
-            SolanaOverview::Vote(overview) => {
+        match parsed.overview.kind {
+            ProgramOverview::Vote(overview) => {
                 assert_eq!("Vote".to_string(), overview.main_action);
                 assert_eq!(vec!["142209632"], overview.votes_on);
                 assert_eq!(
@@ -932,8 +1136,8 @@ mod tests {
         let data = "0200050a06852df21778a462ea79aae81500eae98a935dcca05f8b899ca8b41021a79980acc933a10d87058ad3131361cd345fe95eb7598ad52d972ee559f1ea3f8deb452bb2df65fdf1ad0514f549457e4338bb71e6885354aa5ed87969ef14f5fc736772295dfa0330919867f6f90f2e334d1a56a2203ec3d4086151aab0171ca13c74b626da01ca1cb62be1bbbf9927dd0de251964d351736fd36100bb0e06f728b4100000000000000000000000000000000000000000000000000000000000000008c97258f4e2489f1bb3d1029148e0d830b5a1399daff1084048e7bd8dbe9f8590b7065b1e3d17c45389d527f6b04c3cd58b86c731aa0fdb549b6d1bc03f8294606a7d517192c5c51218cc94c3d4af17f58daee089ba1fd44e3dbd98a0000000006ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a92865a919afcfd4d57cf8f69e11990c98a55e4cc4389ba43c7d32184ca652adb406050200013400000000604d160000000000520000000000000006ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a90902010843000006852df21778a462ea79aae81500eae98a935dcca05f8b899ca8b41021a799800106852df21778a462ea79aae81500eae98a935dcca05f8b899ca8b41021a799800707030100000005087b0012000000536e65616b65722023313830333539303435000000003200000068747470733a2f2f6170692e737465706e2e636f6d2f72756e2f6e66746a736f6e2f3130332f3130363036313531353732319001010100000006852df21778a462ea79aae81500eae98a935dcca05f8b899ca8b41021a799800164010607000400010509080009030104000907010000000000000007090201000000030905080a0a010000000000000000";
         let transaction = Vec::from_hex(data).unwrap();
         let parsed = ParsedSolanaTx::build(&transaction).unwrap();
-        match parsed.overview {
-            SolanaOverview::General(overview) => {
+        match parsed.overview.kind {
+            ProgramOverview::General(overview) => {
                 let overview_1 = overview.get(0).unwrap();
                 let overview_2 = overview.get(1).unwrap();
                 let overview_3 = overview.get(2).unwrap();
@@ -983,18 +1187,14 @@ mod tests {
             "program_account": "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
           },
           {
-            "accounts": [
-              "STEPNq2UGeGSzCyGVr2nMQAzf8xuejwqebd84wcksCK",
-              "DG3Za1KX8Tj1TeZJy2U9nDa8qX3tyZCBYNehNy4fFsnQ",
-              "STEPNq2UGeGSzCyGVr2nMQAzf8xuejwqebd84wcksCK",
-              "CdV4w55UDTvcza5d6V2Y6m7TF9Xmq9MHPUBYMe9WtptL",
-              "11111111111111111111111111111111",
-              "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
-              "SysvarRent111111111111111111111111111111111"
-            ],
-            "data": "",
-            "program": "Unknown",
-            "program_account": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+            "associated_account": "DG3Za1KX8Tj1TeZJy2U9nDa8qX3tyZCBYNehNy4fFsnQ",
+            "funding_account": "STEPNq2UGeGSzCyGVr2nMQAzf8xuejwqebd84wcksCK",
+            "method": "Create",
+            "mint": "CdV4w55UDTvcza5d6V2Y6m7TF9Xmq9MHPUBYMe9WtptL",
+            "program": "AssociatedTokenAccount",
+            "system_program": "11111111111111111111111111111111",
+            "token_program": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            "wallet": "STEPNq2UGeGSzCyGVr2nMQAzf8xuejwqebd84wcksCK"
           },
           {
             "amount": "1",
@@ -1032,8 +1232,8 @@ mod tests {
         let data = "02000407e9940f6435ae992ddbb4ac739ada475fde93bd54c6a9f36a8b60b37fe23ec3fdd8ffff8ad461ca3138f356758b148f2dffa7d055a79356b52727298026189ae82e6df8bd210e5f167971908e8746aa6790aa3bc74ee48a4bbf23236f9effaa65069b8857feab8184fb687f634618c035dac439dc1aeb3b5598a0f0000000000106a7d517192c5c51218cc94c3d4af17f58daee089ba1fd44e3dbd98a00000000000000000000000000000000000000000000000000000000000000000000000006ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a9bff514b7cba346fe333553de5579d50a7da74cf950dd7bdd27327ce9a17c876f04050200013400000000f01d1f0000000000a50000000000000006ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a9060401030004010106030201000903242d84be0000000006030100000109";
         let transaction = Vec::from_hex(data).unwrap();
         let parsed = ParsedSolanaTx::build(&transaction).unwrap();
-        match parsed.overview {
-            SolanaOverview::General(mut overview) => {
+        match parsed.overview.kind {
+            ProgramOverview::General(mut overview) => {
                 let overview_1 = overview.pop().unwrap();
                 let overview_2 = overview.pop().unwrap();
                 let overview_3 = overview.pop().unwrap();
@@ -1094,8 +1294,8 @@ mod tests {
         let data = "0201060908a13fb5c9e7bc18aef6d4ec2e5bca9fb0b8c329c32bdf2baae9125aa3191cd36eeb5c79927943eef87a2828925665d2b3612a070fe5eee74680d8ac0b779ca136a3ae0cda1d97779bcd08c24409fe1c76f84f218aeed3296d8efe2dade261a606a7d517192c5c51218cc94c3d4af17f58daee089ba1fd44e3dbd98a0000000006ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a90b3338a0ab2cc841d5b014bc6a3cf756291874b319c9517d9bbfa9e4e9661ef90000000000000000000000000000000000000000000000000000000000000000054a5350f85dc882d614a55672788a296ddf1eababd0a60678884932f4eef6a08c97258f4e2489f1bb3d1029148e0d830b5a1399daff1084048e7bd8dbe9f859704b00127cf4d5d2ca44446993ee3bab439ce957bde518d1767b108b87a4a7d00307002c416141414141414141414141414141414141414141414141414141414141414141414141414141414141413d08070002010506040300040202012306030108a13fb5c9e7bc18aef6d4ec2e5bca9fb0b8c329c32bdf2baae9125aa3191cd3";
         let transaction = Vec::from_hex(data).unwrap();
         let parsed = ParsedSolanaTx::build(&transaction).unwrap();
-        match parsed.overview {
-            SolanaOverview::General(mut overview) => {
+        match parsed.overview.kind {
+            ProgramOverview::General(mut overview) => {
                 let overview_1 = overview.pop().unwrap();
                 assert_eq!("Token", overview_1.program);
                 assert_eq!("SetAuthority", overview_1.method);
@@ -1106,24 +1306,20 @@ mod tests {
 
         let expected_detail = json!([
           {
-            "accounts": [],
-            "data": "NFw4Tg8NvoG7NVDGdoferkiJmQTGJ6esGoTc6W89Z9HRabSLuLYYjs6qwPmW",
-            "program": "Unknown",
-            "program_account": "Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo"
+            "memo": "AaAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+            "method": "Memo",
+            "program": "Memo",
+            "signers": []
           },
           {
-            "accounts": [
-              "agsWhfJ5PPGjmzMieWY8BR5o1XRVszUBQ5uFz4CtDiJ",
-              "4gHmx6Puk1J9YntAUvnyrXP68SmjCvQocuArQCt5o4p5",
-              "8Tz15moyu4eL48o4Pq5XLyxX5XkkKEsNcgx27ycaPLaU",
-              "kinXdEcpDQeHPEuQnqmUgtYykqKGVFq6CeVX5iAHJq6",
-              "11111111111111111111111111111111",
-              "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
-              "SysvarRent111111111111111111111111111111111"
-            ],
-            "data": "",
-            "program": "Unknown",
-            "program_account": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+            "associated_account": "4gHmx6Puk1J9YntAUvnyrXP68SmjCvQocuArQCt5o4p5",
+            "funding_account": "agsWhfJ5PPGjmzMieWY8BR5o1XRVszUBQ5uFz4CtDiJ",
+            "method": "Create",
+            "mint": "kinXdEcpDQeHPEuQnqmUgtYykqKGVFq6CeVX5iAHJq6",
+            "program": "AssociatedTokenAccount",
+            "system_program": "11111111111111111111111111111111",
+            "token_program": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            "wallet": "8Tz15moyu4eL48o4Pq5XLyxX5XkkKEsNcgx27ycaPLaU"
           },
           {
             "account": "4gHmx6Puk1J9YntAUvnyrXP68SmjCvQocuArQCt5o4p5",
@@ -1144,8 +1340,8 @@ mod tests {
         let data = "0301070faa30697d8ea2d14ce506c401ad5f1bd33476ebcb8a8b5cea89fa0aafb7c04f3925070f12913aa23553bfaf08f0a6f293aadb24dd66711db239c9b0ccca751b05dcc3a6c16cb67f59d67085e174cd7469f3e00b99a63d6c8d95337096f9e8437d8c17a1e64eba64bb7238d33b21461db8824508c879f91199d9eff9309ff63952baf04e4356057aaea057a6d744e1a0dbb99091448d6c2807114f0a016c9f2c39df8b1e991b87277d51b2ee23b63496ff1a54ab30e61eb4c4572e52fb99af9421e636e5095d76cede0e72ff2a024a07652d423fb7f3978a4663a278d13e1c1ba10deb821d34b39060c73598d3dd86ecf853df3b2f38b02991ad2ccfa51306e01600000000000000000000000000000000000000000000000000000000000000003f5877e18f96dea58c638a21d2be860ba96f0e21d1d84c6a94dba44e2be81f0e494500f4fdcbc9ad22814e250c0d6763266f6ca9169e12662f477601991e1a36be49a1eeb81bf889c158fd8b7496ff9141d4aa433eae3948d0d8488f78951b78069b8857feab8184fb687f634618c035dac439dc1aeb3b5598a0f0000000000106a7d517192c5c51218cc94c3d4af17f58daee089ba1fd44e3dbd98a0000000006ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a954c495b382bac905bb70f97bc252b2ee3b796b2836a3287ed5787c70eb2484120608020001340000000030266d0500000000a50000000000000006ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a90e04010c000d01010e03010200090440084e05000000000b0a090a020106030504070e110140084e05000000005d4d3700000000000e02010001050e030100000109";
         let transaction = Vec::from_hex(data).unwrap();
         let parsed = ParsedSolanaTx::build(&transaction).unwrap();
-        match parsed.overview {
-            SolanaOverview::General(overview) => {
+        match parsed.overview.kind {
+            ProgramOverview::General(overview) => {
                 let overview_1 = overview.get(0).unwrap();
                 assert_eq!("System", overview_1.program);
                 assert_eq!("CreateAccount", overview_1.method);
@@ -1249,16 +1445,14 @@ mod tests {
           },
           "instructions": [
             {
-              "accounts": [],
-              "data": "Hg27aP",
-              "program": "Unknown",
-              "program_account": "ComputeBudget111111111111111111111111111111"
+              "method": "SetComputeUnitLimit",
+              "program": "ComputeBudget",
+              "units": 542092
             },
             {
-              "accounts": [],
-              "data": "3Ju5f2HYNk7Z",
-              "program": "Unknown",
-              "program_account": "ComputeBudget111111111111111111111111111111"
+              "method": "SetComputeUnitPrice",
+              "micro_lamports": "99872",
+              "program": "ComputeBudget"
             },
             {
               "accounts": [
@@ -1311,6 +1505,127 @@ mod tests {
         assert_eq!(expect_data, parsed_detail);
     }
 
+    // every table holds 160 addresses, the address at `index` of the table `t` is [t + 1, index, 0..]
+    fn lookup_table_address(t: u8, index: u8) -> [u8; 32] {
+        let mut address = [0u8; 32];
+        address[0] = t + 1;
+        address[1] = index;
+        address
+    }
+
+    fn build_lookup_tables(keys: &[&str]) -> Vec<u8> {
+        let mut raw = vec![keys.len() as u8];
+        for (t, key) in keys.iter().enumerate() {
+            raw.extend(base58::decode(key).unwrap());
+            // compact length 160
+            raw.extend([0xa0, 0x01]);
+            for index in 0..160 {
+                raw.extend(lookup_table_address(t as u8, index));
+            }
+        }
+        raw
+    }
+
+    #[test]
+    fn test_parse_versioned_transaction_with_lookup_tables() {
+        let raw_message = "800100020305919998ccad85d7254856bde658b1926b77276f9c9f93d4713eec50c6f0cd4e0306466fe5211732ffecadba72c39be7bc8ce5bbc5f7126b2c439b3a4000000008239266ec3629985acb2ec387b47c3c3ca057181773835b4f0e99ab4f065e7dc3e93416d06d11ad5352987624986db3240b02ea5cc42ba3c2e9f3a6e9bfe1c203010005028c45080001000903208601000000000002271d1f1e00040508070906191c19141b11120c0f1a0b0a0e13101020070d00180315000d04171619295bbffbf792f60aa2084d230100000000ad88c11900000000030201070000000000000101000000000004fdd750c4799429e7e17c0c7cf0a55a82d70846d1bc97665714af810651e8bb04010e060d010c0f0709209be863b24cb2303c7162670000dfaf3176a1ada32a0305df593d8a935c7dcd06038b8d118a8c0287868cb7d386ca1b4cb4125557bf989073c37d63ae73104247ee1280afa86a8ac75b0b9695938f97949a9192988d019bfddf37b259d3ceada480a9a43ba12f21bfd3d7afd64d35b9f0330f6645d3016f03969a9900";
+        let transaction = Vec::from_hex(raw_message).unwrap();
+        let keys = [
+            "J5taGmJ5wt1pgfbwTjt9g9yifbDfNbdnsPctQtzSH7hm",
+            "3CHw45wdjHwfcnKdZk65dCHnf9tZePfhTDhqkC5NoKzU",
+            "AUJexzjDyphJf8wZvKo83oRSANxmUcvgdfZF3s6Bb37g",
+            "J61ZcWYAsQbdJBs99iuubCDLpAkPh2LGTPzMpRFJLjAv",
+        ];
+        let tables = AddressLookupTable::read_tables(&mut build_lookup_tables(&keys)).unwrap();
+        let parsed = ParsedSolanaTx::build_with_lookup_tables(&transaction, &tables).unwrap();
+        let parsed_detail: Value = serde_json::from_str(parsed.detail.as_str()).unwrap();
+        let accounts = parsed_detail["instructions"][2]["accounts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect::<Vec<String>>();
+        assert!(accounts.iter().all(|v| !v.contains("#")));
+        // J5taGmJ5wt1pgfbwTjt9g9yifbDfNbdnsPctQtzSH7hm#9
+        assert_eq!(base58::encode(&lookup_table_address(0, 9)), accounts[0]);
+        // 3CHw45wdjHwfcnKdZk65dCHnf9tZePfhTDhqkC5NoKzU#134
+        assert_eq!(base58::encode(&lookup_table_address(1, 134)), accounts[1]);
+        assert_eq!("NjordRPSzFs8XQUKMjGrhPcmGo9yfC9HP3VHmh8xZpZ", accounts[3]);
+        // J61ZcWYAsQbdJBs99iuubCDLpAkPh2LGTPzMpRFJLjAv#150
+        assert_eq!(base58::encode(&lookup_table_address(3, 150)), accounts[32]);
+        assert!(parsed_detail["lookup_table_accounts"]
+            .as_array()
+            .unwrap()
+            .contains(&json!(accounts[0])));
+        match parsed.overview.kind {
+            ProgramOverview::Instructions(overview) => {
+                let instruction = &overview.overview_instructions[0];
+                assert_eq!(accounts, instruction.accounts);
+                assert!(instruction.from_lookup_table[0]);
+                assert!(instruction.from_lookup_table[1]);
+                assert!(!instruction.from_lookup_table[3]);
+            }
+            _ => panic!("program overview parse error!"),
+        };
+
+        // a lookup table of the message is missing
+        let tables = AddressLookupTable::read_tables(&mut build_lookup_tables(&keys[..3])).unwrap();
+        assert!(matches!(
+            ParsedSolanaTx::build_with_lookup_tables(&transaction, &tables),
+            Err(SolanaError::LookupTableError(_))
+        ));
+
+        // the message looks up index 155 of AUJexzjDyphJf8wZvKo83oRSANxmUcvgdfZF3s6Bb37g
+        let mut tables = AddressLookupTable::read_tables(&mut build_lookup_tables(&keys)).unwrap();
+        tables[2].addresses.truncate(155);
+        assert!(matches!(
+            ParsedSolanaTx::build_with_lookup_tables(&transaction, &tables),
+            Err(SolanaError::LookupTableError(_))
+        ));
+
+        // a table address can't be one of the static accounts of the message
+        let mut tables = AddressLookupTable::read_tables(&mut build_lookup_tables(&keys)).unwrap();
+        tables[0].addresses[9].value =
+            base58::decode("NjordRPSzFs8XQUKMjGrhPcmGo9yfC9HP3VHmh8xZpZ").unwrap();
+        assert!(matches!(
+            ParsedSolanaTx::build_with_lookup_tables(&transaction, &tables),
+            Err(SolanaError::LookupTableError(_))
+        ));
+
+        // trailing bytes after the tables
+        let mut raw = build_lookup_tables(&keys);
+        raw.push(0);
+        assert!(AddressLookupTable::read_tables(&mut raw).is_err());
+    }
+
+    #[test]
+    fn test_parse_network_fee() {
+        // ComputeBudget.SetComputeUnitLimit + ComputeBudget.SetComputeUnitPrice + Unknown
+        let raw_message = "800100020305919998ccad85d7254856bde658b1926b77276f9c9f93d4713eec50c6f0cd4e0306466fe5211732ffecadba72c39be7bc8ce5bbc5f7126b2c439b3a4000000008239266ec3629985acb2ec387b47c3c3ca057181773835b4f0e99ab4f065e7dc3e93416d06d11ad5352987624986db3240b02ea5cc42ba3c2e9f3a6e9bfe1c203010005028c45080001000903208601000000000002271d1f1e00040508070906191c19141b11120c0f1a0b0a0e13101020070d00180315000d04171619295bbffbf792f60aa2084d230100000000ad88c11900000000030201070000000000000101000000000004fdd750c4799429e7e17c0c7cf0a55a82d70846d1bc97665714af810651e8bb04010e060d010c0f0709209be863b24cb2303c7162670000dfaf3176a1ada32a0305df593d8a935c7dcd06038b8d118a8c0287868cb7d386ca1b4cb4125557bf989073c37d63ae73104247ee1280afa86a8ac75b0b9695938f97949a9192988d019bfddf37b259d3ceada480a9a43ba12f21bfd3d7afd64d35b9f0330f6645d3016f03969a9900";
+        let transaction = Vec::from_hex(raw_message).unwrap();
+        let parsed = ParsedSolanaTx::build(&transaction).unwrap();
+        assert!(matches!(parsed.display_type, SolanaTxDisplayType::Unknown));
+        let fee = parsed.overview.network_fee;
+        assert_eq!(542092, fee.compute_unit_limit);
+        assert_eq!(99872, fee.compute_unit_price);
+        assert_eq!("0.000005 SOL", fee.base_fee);
+        assert_eq!("0.00005414 SOL", fee.priority_fee);
+        assert_eq!("0.00005914 SOL", fee.total_fee);
+
+        // Memo + AToken.CreateAssociatedAccount + Token.SetAuthority, two signers and no budget
+        let data = "0201060908a13fb5c9e7bc18aef6d4ec2e5bca9fb0b8c329c32bdf2baae9125aa3191cd36eeb5c79927943eef87a2828925665d2b3612a070fe5eee74680d8ac0b779ca136a3ae0cda1d97779bcd08c24409fe1c76f84f218aeed3296d8efe2dade261a606a7d517192c5c51218cc94c3d4af17f58daee089ba1fd44e3dbd98a0000000006ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a90b3338a0ab2cc841d5b014bc6a3cf756291874b319c9517d9bbfa9e4e9661ef90000000000000000000000000000000000000000000000000000000000000000054a5350f85dc882d614a55672788a296ddf1eababd0a60678884932f4eef6a08c97258f4e2489f1bb3d1029148e0d830b5a1399daff1084048e7bd8dbe9f859704b00127cf4d5d2ca44446993ee3bab439ce957bde518d1767b108b87a4a7d00307002c416141414141414141414141414141414141414141414141414141414141414141414141414141414141413d08070002010506040300040202012306030108a13fb5c9e7bc18aef6d4ec2e5bca9fb0b8c329c32bdf2baae9125aa3191cd3";
+        let transaction = Vec::from_hex(data).unwrap();
+        let fee = ParsedSolanaTx::build(&transaction)
+            .unwrap()
+            .overview
+            .network_fee;
+        assert_eq!(600000, fee.compute_unit_limit);
+        assert_eq!(0, fee.compute_unit_price);
+        assert_eq!("0.00001 SOL", fee.base_fee);
+        assert_eq!("0 SOL", fee.priority_fee);
+        assert_eq!("0.00001 SOL", fee.total_fee);
+    }
+
     #[test]
     fn test_parse_legacy_transaction() {
         // https://solscan.io/tx/5zgvxQjV6BisU8SfahqasBZGfXy5HJ3YxYseMBG7VbR4iypDdtdymvE1jmEMG7G39bdVBaHhLYUHUejSTtuZEpEj
@@ -1443,8 +1758,8 @@ mod tests {
         let data = "010007096aefb992fa0cd54aea185bf65a7da92aad6bd46da5a67c7675a04e6540d86f7a3d2ce2421048aa748a6cc22b5696032f902cfc0b3dd6bce0d379f76c383bceda0000000000000000000000000000000000000000000000000000000000000000e23a2b23b625e7513991be370a2c20d5c5e276491d36777ef2e5b1227ffe732906a1d8179137542a983437bdfe2a7ab2557f535c8a78722b68a49dc00000000006a1d817a502050b680791e6ce6db88e1e5b7150f61fc6790a4eb4d10000000006a7d51718c774c928566398691d5eb68b5eb8a39b4b6d5c73555b210000000006a7d517192c5c51218cc94c3d4af17f58daee089ba1fd44e3dbd98a0000000006a7d517193584d0feed9bb3431d13206be544281b57b8566cc5375ff4000000aada712c5d14f4e64d913b330ff3e519bc7f2aac580997f0c549620601866915030202000174030000006aefb992fa0cd54aea185bf65a7da92aad6bd46da5a67c7675a04e6540d86f7a18000000000000007374616b653a302e3231363239323431373439393638393500de2a9200000000c80000000000000006a1d8179137542a983437bdfe2a7ab2557f535c8a78722b68a49dc0000000000402010774000000006aefb992fa0cd54aea185bf65a7da92aad6bd46da5a67c7675a04e6540d86f7a6aefb992fa0cd54aea185bf65a7da92aad6bd46da5a67c7675a04e6540d86f7a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004060103060805000402000000";
         let transaction = Vec::from_hex(data).unwrap();
         let parsed = ParsedSolanaTx::build(&transaction).unwrap();
-        match parsed.overview {
-            SolanaOverview::General(overview) => {
+        match parsed.overview.kind {
+            ProgramOverview::General(overview) => {
                 let overview_1 = overview.get(0).unwrap();
                 assert_eq!("System", overview_1.program);
                 assert_eq!("CreateAccountWithSeed", overview_1.method);
@@ -1503,8 +1818,8 @@ mod tests {
         let data = "01000305575949043cea1e1713d06b6b2eba6bb22d303884908e683fcaaa7b0ba6209be859d521dc428449106dabb34dadd3b44cc7795f58be0d4a81aaeaada967b21bd206a1d8179137542a983437bdfe2a7ab2557f535c8a78722b68a49dc00000000006a7d51718c774c928566398691d5eb68b5eb8a39b4b6d5c73555b210000000006a7d517193584d0feed9bb3431d13206be544281b57b8566cc5375ff400000067415e51677a2d98e9f86da5c65fe4c72bdee5cb2755af7a27d13dda710aa26001020501000304000c04000000ed77410300000000";
         let transaction = Vec::from_hex(data).unwrap();
         let parsed = ParsedSolanaTx::build(&transaction).unwrap();
-        match parsed.overview {
-            SolanaOverview::General(overview) => {
+        match parsed.overview.kind {
+            ProgramOverview::General(overview) => {
                 let overview_1 = overview.get(0).unwrap();
                 assert_eq!("Stake", overview_1.program);
                 assert_eq!("Withdraw", overview_1.method);
@@ -1535,8 +1850,8 @@ mod tests {
         let data = "010002044dd6a13d7b9ca64c690638eb9679f4a264a5a93022212ec608b24964dbc5701aff979426efda42a314f5b5477ea3264fddfb5ee1b9f939bff1e90cbea09cde3306a1d8179137542a983437bdfe2a7ab2557f535c8a78722b68a49dc00000000006a7d51718c774c928566398691d5eb68b5eb8a39b4b6d5c73555b2100000000a4eb4d5967097a0ec98783003eba3fde67341ba6d6d55f7d6987494a952466520102030103000405000000";
         let transaction = Vec::from_hex(data).unwrap();
         let parsed = ParsedSolanaTx::build(&transaction).unwrap();
-        match parsed.overview {
-            SolanaOverview::General(overview) => {
+        match parsed.overview.kind {
+            ProgramOverview::General(overview) => {
                 let overview_1 = overview.get(0).unwrap();
                 assert_eq!("Stake", overview_1.program);
                 assert_eq!("Deactivate", overview_1.method);
@@ -1562,8 +1877,8 @@ mod tests {
         let data = "0301080fae0e9965d80b3bb521ed714366a4d461fd58d7b7c97caa15564ba34c3ec5c04d940d487f489c470872533e2d8b55a5ec1ae1fd130cefae0f1bd1527a9b6955c1ab9daad5867d8a4dba28bb9b9bc4146bc81a83e877c01d693d9860e2863df6f5aaf29edc6d0d3544fccda1232277d6032783264c5cfc335600c85f30754adaa9f604b96c15a6018a88598d0c5a310fe2b6333aa48ba916e502be02578ca50384cc51e45da7f68a2906979e692c1e8bc87e51deca9ddfe7e673895a01ef80facf5f4019373457f129bf4cae6a4255518b885bf718157dd4357233dc79268c4cbf069b8857feab8184fb687f634618c035dac439dc1aeb3b5598a0f0000000000106a7d517192c5c51218cc94c3d4af17f58daee089ba1fd44e3dbd98a0000000006a7d51718c774c928566398691d5eb68b5eb8a39b4b6d5c73555b210000000023166cdfc331b06925f390147d4270172c25a5b218580326b09081a9f3bbe90c051e8a28c6a067b32fbb33323ed92334b6adbdc4639b871c8a2e44f47058ef8506ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a900000000000000000000000000000000000000000000000000000000000000000508c2ceb1b5d05c874980ac52cf659740e7e9b9356aaf2a0362673263526c15e83b5d0c7735cf4f76914b1488bc665d32dce3140950851428922cc65fbb565b070c03030200090424eb0700000000000d0200013400000000f01d1f0000000000a50000000000000006ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a90c040107000801010e02090401080e0a03010405060a0b02090c090424eb0700000000000c02030001050c03010000010901";
         let transaction = Vec::from_hex(data).unwrap();
         let parsed = ParsedSolanaTx::build(&transaction).unwrap();
-        match parsed.overview {
-            SolanaOverview::General(overview) => {
+        match parsed.overview.kind {
+            ProgramOverview::General(overview) => {
                 let overview_1 = overview.get(0).unwrap();
                 assert_eq!("Token", overview_1.program);
                 assert_eq!("Approve", overview_1.method);
@@ -1660,8 +1975,8 @@ mod tests {
         let data = "0100060e1a93fffb26ce645adeae58f0f414c320bcec30ce12a66bd263a91ec9b3958ff45d2a5ee5685c17e07cede5bef98300d4170ebbe2d99f064c4bb05ee97b35de7d75119b3175807586e3f4a7e5cd0f890e96a753b10fccc7681e9473a0083270f1bfa153f6e9dc8b683a60c22c944131adc8abfb93befad36efcc572c783e27055f7f1afd0d58a5a0dcfa04274a0c6aaa6e8c38380af1cd2b1c3f881cc9e24ac330b62ba074f722c9d4114f2d8f70a00c66002337b9bf90c873657a6d201db4c80067d7c615ffb9455750b6b889211bcf4c76f687cbb6ba93adc37ecaefbfb41b207154414aa83b02488ade068d5effab202658060008643843ceacd8dc5e1b89f0000000000000000000000000000000000000000000000000000000000000000222829e89767b2043c86d1b51f31364e5adaeb861fd62e7a7f46be4dbbc55ca4cfa6452d2d0f594a1ddd1bead98a7a52fd63925ae3266435dde44a5ab3fdb8f10545e365bef271ad75350367565da40da336dc1c879bb1548a7afcc55aa9391e0b616d4895472c6a02e9ecd3e87951f18401d675dedcbe33e8673e00221522d306ddf6e1d765a193d9cbe146ceeb79ac1cb485ed5f5b37913a8cf5857eff00a91b421195ecaa4f6b65834080c6ac091cd06449b7cb33a5cbe42a86b27d36a469010c0d020507010a03000409080d0b0610f223c68952e1f2b68096980000000000";
         let transaction = Vec::from_hex(data).unwrap();
         let parsed = ParsedSolanaTx::build(&transaction).unwrap();
-        match parsed.overview {
-            SolanaOverview::Unknown(overview) => {
+        match parsed.overview.kind {
+            ProgramOverview::Unknown(overview) => {
                 assert_eq!("This transaction can not be decoded", overview.description);
             }
             _ => println!("program overview parse error!"),
@@ -1722,8 +2037,8 @@ mod tests {
         let data = "01000306507e6e0eedc8bad07d8e1907faf5cd44364b75503f4b2f9a2efd196fabcdbc0d8a35bc824e0b6a9970622a2d84513cb3d0d8d9274d026c09b189a3c991b04e679afd0fdcc13de092bc32eaef523c79183e3c923924634e64b9808b67ce00316f06a7d51718c774c928566398691d5eb68b5eb8a39b4b6d5c73555b210000000006a7d517193584d0feed9bb3431d13206be544281b57b8566cc5375ff400000006a1d8179137542a983437bdfe2a7ab2557f535c8a78722b68a49dc000000000453461b52d93efbb8f738fe6df0fb3d12c5a014432f41011d426e3b01eb4f66b01050501020304000407000000";
         let transaction = Vec::from_hex(data).unwrap();
         let parsed = ParsedSolanaTx::build(&transaction).unwrap();
-        match parsed.overview {
-            SolanaOverview::General(overview) => {
+        match parsed.overview.kind {
+            ProgramOverview::General(overview) => {
                 let overview_1 = overview.get(0).unwrap();
                 assert_eq!("Stake", overview_1.program);
                 assert_eq!("Merge", overview_1.method);
@@ -1734,4 +2049,59 @@ mod tests {
         let expected_detail = json!([{"program":"Stake","method":"Merge","destination_stake_account":"AJWneUm7QuQENJXkn7rDMp1Bvfab3R5vY7LcGuMmTtQv","source_stake_account":"BS1bVhRD2iJrGbiHV61J3SRg8TsGjR2a9TSBeoJBY6Ce","sysvar_clock":"SysvarC1ock11111111111111111111111111111111","sysvar_stake_history":"SysvarStakeHistory1111111111111111111111111","stake_authority_pubkey":"6RDS19fkTJ3BtTx78NswVm2VSPRmwM9tEa6kGuadAPKS"}]);
         assert_eq!(expected_detail, parsed_detail);
     }
+
+    #[test]
+    fn test_parse_token_2022_transfer_with_fee() {
+        // Token2022: TransferCheckedWithFee of 1.5 tokens paying a fee of 0.0015
+        let data = "01000205010101010101010101010101010101010101010101010101010101010101010102020202020202020202020202020202020202020202020202020202020202020303030303030303030303030303030303030303030303030303030303030303040404040404040404040404040404040404040404040404040404040404040406ddf6e1ee758fde18425dbce46ccddab61afc4d83b90d27febdf928d8a18bfc090909090909090909090909090909090909090909090909090909090909090901040401030200131a0160e316000000000006dc05000000000000";
+        let transaction = Vec::from_hex(data).unwrap();
+        let parsed = ParsedSolanaTx::build(&transaction).unwrap();
+        assert_eq!("TokenTransfer", parsed.display_type.to_string());
+        match parsed.overview.kind {
+            ProgramOverview::SplTokenTransfer(overview) => {
+                assert_eq!("1.5 SPLToken", overview.amount);
+                assert_eq!(Some("0.0015 SPLToken".to_string()), overview.fee);
+                assert_eq!(None, overview.transfer_hook);
+                assert!(!overview.transfer_hook_verified);
+                assert_eq!(
+                    "CktRuQ2mttgRGkXJtyksdKHjUdc2C4TgDzyB98oEzy8",
+                    overview.destination
+                );
+            }
+            _ => panic!("program overview parse error!"),
+        };
+        let parsed_detail: Value = serde_json::from_str(parsed.detail.as_str()).unwrap();
+        let expected_detail = json!([{
+            "program": "Token2022",
+            "method": "TransferCheckedWithFee",
+            "account": "8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR",
+            "mint": "GgBaCs3NCBuZN12kCJgAW63ydqohFkHEdfdEXBPzLHq",
+            "recipient": "CktRuQ2mttgRGkXJtyksdKHjUdc2C4TgDzyB98oEzy8",
+            "owner": "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+            "decimals": 6,
+            "amount": "1500000",
+            "fee": "1500"
+        }]);
+        assert_eq!(expected_detail, parsed_detail);
+    }
+
+    #[test]
+    fn test_parse_token_2022_transfer_with_hook() {
+        // Token2022: TransferChecked followed by an extra account, the hook program and its validation account
+        let data = "01000508010101010101010101010101010101010101010101010101010101010101010102020202020202020202020202020202020202020202020202020202020202020303030303030303030303030303030303030303030303030303030303030303040404040404040404040404040404040404040404040404040404040404040405050505050505050505050505050505050505050505050505050505050505050606060606060606060606060606060606060606060606060606060606060606070707070707070707070707070707070707070707070707070707070707070706ddf6e1ee758fde18425dbce46ccddab61afc4d83b90d27febdf928d8a18bfc0909090909090909090909090909090909090909090909090909090909090909010707010302000405060a0c80841e000000000006";
+        let transaction = Vec::from_hex(data).unwrap();
+        let parsed = ParsedSolanaTx::build(&transaction).unwrap();
+        match parsed.overview.kind {
+            ProgramOverview::SplTokenTransfer(overview) => {
+                assert_eq!("2 SPLToken", overview.amount);
+                assert_eq!(None, overview.fee);
+                assert_eq!(
+                    Some("QWmroo4YnnMqYW3cnxWkFdaTxGD3P7vMSzwMHGbUzwF".to_string()),
+                    overview.transfer_hook
+                );
+                assert!(!overview.transfer_hook_verified);
+            }
+            _ => panic!("program overview parse error!"),
+        };
+    }
 }
@@ -16,11 +16,29 @@ pub struct ProgramOverviewSplTokenTransfer {
     pub authority: String,
     pub decimals: u8,
     pub amount: String,
+    // Token-2022 fee withheld from the amount, paid by the recipient
+    pub fee: Option<String>,
+    // program invoked by Token-2022 on every transfer of the mint
+    pub transfer_hook: Option<String>,
+    // false when the hook is only inferred from the extra accounts of the transfer
+    pub transfer_hook_verified: bool,
     pub token_mint_account: String,
     pub token_symbol: String,
     pub token_name: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct ProgramOverviewNetworkFee {
+    pub compute_unit_limit: u32,
+    // micro-lamports paid per compute unit on top of the base fee
+    pub compute_unit_price: u64,
+    pub base_fee: String,
+    pub priority_fee: String,
+    pub total_fee: String,
+    // lamports of `total_fee`, for whatever has to count with it
+    pub total_fee_lamports: u64,
+}
+
 #[derive(Debug, Clone)]
 pub struct ProgramOverviewVote {
     pub votes_on: Vec<String>,
@@ -43,6 +61,8 @@ pub struct ProgramOverviewInstructions {
 #[derive(Debug, Clone)]
 pub struct ProgramOverviewInstruction {
     pub accounts: Vec<String>,
+    // for every account, whether it was loaded from an address lookup table
+    pub from_lookup_table: Vec<bool>,
     pub data: String,
     pub program_address: String,
 }
@@ -100,7 +120,14 @@ pub struct ProgramOverviewProposal {
 }
 
 #[derive(Debug, Clone)]
-pub enum SolanaOverview {
+pub struct SolanaOverview {
+    pub kind: ProgramOverview,
+    // base and priority fee, set for every display type
+    pub network_fee: ProgramOverviewNetworkFee,
+}
+
+#[derive(Debug, Clone)]
+pub enum ProgramOverview {
     Transfer(ProgramOverviewTransfer),
     Vote(ProgramOverviewVote),
     General(Vec<ProgramOverviewGeneral>),
@@ -1,11 +1,13 @@
 use alloc::string::{String, ToString};
 
+use crate::parser::balance_change::BalanceChanges;
 use crate::parser::overview::SolanaOverview;
 
 #[derive(Clone, Debug)]
 pub struct ParsedSolanaTx {
     pub display_type: SolanaTxDisplayType,
     pub overview: SolanaOverview,
+    pub balance_changes: BalanceChanges,
     pub detail: String,
     pub network: String,
 }
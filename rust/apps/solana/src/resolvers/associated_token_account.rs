@@ -0,0 +1,74 @@
+use crate::errors::{Result, SolanaError};
+use crate::parser::detail::{
+    CommonDetail, ProgramDetail, ProgramDetailAssociatedTokenAccountCreate,
+    ProgramDetailAssociatedTokenAccountRecoverNested, SolanaDetail,
+};
+use crate::solana_lib::spl::associated_token_account::instruction::AssociatedTokenAccountInstruction;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+static PROGRAM_NAME: &str = "AssociatedTokenAccount";
+
+fn get_account(accounts: &[String], index: usize, method_name: &str, name: &str) -> Result<String> {
+    accounts
+        .get(index)
+        .map(|v| v.to_string())
+        .ok_or(SolanaError::AccountNotFound(format!(
+            "{}.{}",
+            method_name, name
+        )))
+}
+
+fn detail(method_name: &str, kind: ProgramDetail) -> SolanaDetail {
+    SolanaDetail {
+        common: CommonDetail {
+            program: PROGRAM_NAME.to_string(),
+            method: method_name.to_string(),
+        },
+        kind,
+    }
+}
+
+pub fn resolve(
+    instruction: AssociatedTokenAccountInstruction,
+    accounts: Vec<String>,
+) -> Result<SolanaDetail> {
+    match instruction {
+        AssociatedTokenAccountInstruction::Create => create(accounts, "Create"),
+        AssociatedTokenAccountInstruction::CreateIdempotent => create(accounts, "CreateIdempotent"),
+        AssociatedTokenAccountInstruction::RecoverNested => recover_nested(accounts),
+    }
+}
+
+fn create(accounts: Vec<String>, method_name: &str) -> Result<SolanaDetail> {
+    Ok(detail(
+        method_name,
+        ProgramDetail::AssociatedTokenAccountCreate(ProgramDetailAssociatedTokenAccountCreate {
+            funding_account: get_account(&accounts, 0, method_name, "funding_account")?,
+            associated_account: get_account(&accounts, 1, method_name, "associated_account")?,
+            wallet: get_account(&accounts, 2, method_name, "wallet")?,
+            mint: get_account(&accounts, 3, method_name, "mint")?,
+            system_program: get_account(&accounts, 4, method_name, "system_program")?,
+            token_program: get_account(&accounts, 5, method_name, "token_program")?,
+        }),
+    ))
+}
+
+fn recover_nested(accounts: Vec<String>) -> Result<SolanaDetail> {
+    let method_name = "RecoverNested";
+    Ok(detail(
+        method_name,
+        ProgramDetail::AssociatedTokenAccountRecoverNested(
+            ProgramDetailAssociatedTokenAccountRecoverNested {
+                nested_account: get_account(&accounts, 0, method_name, "nested_account")?,
+                nested_mint: get_account(&accounts, 1, method_name, "nested_mint")?,
+                destination_account: get_account(&accounts, 2, method_name, "destination_account")?,
+                owner_account: get_account(&accounts, 3, method_name, "owner_account")?,
+                owner_mint: get_account(&accounts, 4, method_name, "owner_mint")?,
+                wallet: get_account(&accounts, 5, method_name, "wallet")?,
+                token_program: get_account(&accounts, 6, method_name, "token_program")?,
+            },
+        ),
+    ))
+}
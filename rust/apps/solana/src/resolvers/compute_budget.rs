@@ -0,0 +1,63 @@
+use crate::errors::Result;
+use crate::parser::detail::{
+    CommonDetail, ProgramDetail, ProgramDetailComputeBudgetRequestHeapFrame,
+    ProgramDetailComputeBudgetRequestUnits, ProgramDetailComputeBudgetSetComputeUnitLimit,
+    ProgramDetailComputeBudgetSetComputeUnitPrice,
+    ProgramDetailComputeBudgetSetLoadedAccountsDataSizeLimit, SolanaDetail,
+};
+use crate::solana_lib::solana_program::compute_budget::instruction::ComputeBudgetInstruction;
+use alloc::string::ToString;
+
+pub(crate) static PROGRAM_NAME: &str = "ComputeBudget";
+
+fn detail(method_name: &str, kind: ProgramDetail) -> SolanaDetail {
+    SolanaDetail {
+        common: CommonDetail {
+            program: PROGRAM_NAME.to_string(),
+            method: method_name.to_string(),
+        },
+        kind,
+    }
+}
+
+// compute budget instructions take no accounts
+pub fn resolve(instruction: ComputeBudgetInstruction) -> Result<SolanaDetail> {
+    Ok(match instruction {
+        ComputeBudgetInstruction::RequestUnits {
+            units,
+            additional_fee,
+        } => detail(
+            "RequestUnits",
+            ProgramDetail::ComputeBudgetRequestUnits(ProgramDetailComputeBudgetRequestUnits {
+                units,
+                additional_fee: additional_fee.to_string(),
+            }),
+        ),
+        ComputeBudgetInstruction::RequestHeapFrame(bytes) => detail(
+            "RequestHeapFrame",
+            ProgramDetail::ComputeBudgetRequestHeapFrame(
+                ProgramDetailComputeBudgetRequestHeapFrame { bytes },
+            ),
+        ),
+        ComputeBudgetInstruction::SetComputeUnitLimit(units) => detail(
+            "SetComputeUnitLimit",
+            ProgramDetail::ComputeBudgetSetComputeUnitLimit(
+                ProgramDetailComputeBudgetSetComputeUnitLimit { units },
+            ),
+        ),
+        ComputeBudgetInstruction::SetComputeUnitPrice(micro_lamports) => detail(
+            "SetComputeUnitPrice",
+            ProgramDetail::ComputeBudgetSetComputeUnitPrice(
+                ProgramDetailComputeBudgetSetComputeUnitPrice {
+                    micro_lamports: micro_lamports.to_string(),
+                },
+            ),
+        ),
+        ComputeBudgetInstruction::SetLoadedAccountsDataSizeLimit(bytes) => detail(
+            "SetLoadedAccountsDataSizeLimit",
+            ProgramDetail::ComputeBudgetSetLoadedAccountsDataSizeLimit(
+                ProgramDetailComputeBudgetSetLoadedAccountsDataSizeLimit { bytes },
+            ),
+        ),
+    })
+}
@@ -0,0 +1,23 @@
+use crate::errors::{Result, SolanaError};
+use crate::parser::detail::{CommonDetail, ProgramDetail, ProgramDetailMemo, SolanaDetail};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+static PROGRAM_NAME: &str = "Memo";
+
+// the memo program rejects data that is not valid utf-8, every account must sign
+pub fn resolve(data: &[u8], accounts: Vec<String>) -> Result<SolanaDetail> {
+    let memo = core::str::from_utf8(data)
+        .map_err(|e| SolanaError::ProgramError(format!("invalid memo: {}", e)))?;
+    Ok(SolanaDetail {
+        common: CommonDetail {
+            program: PROGRAM_NAME.to_string(),
+            method: "Memo".to_string(),
+        },
+        kind: ProgramDetail::Memo(ProgramDetailMemo {
+            memo: memo.to_string(),
+            signers: accounts,
+        }),
+    })
+}
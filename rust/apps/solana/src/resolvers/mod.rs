@@ -4,10 +4,14 @@ use core::ops::Div;
 
 use crate::errors::{Result, SolanaError};
 
+pub mod associated_token_account;
+pub mod compute_budget;
+pub mod memo;
 pub mod squads_v4;
 pub mod stake;
 pub mod system;
 pub mod token;
+pub mod token_2022;
 pub mod token_lending;
 pub mod token_swap_v3;
 pub mod vote;
@@ -140,7 +140,12 @@ fn resolve_transfer(accounts: Vec<String>, lamports: u64) -> Result<SolanaDetail
             program: PROGRAM_NAME.to_string(),
             method: method_name,
         },
-        kind: ProgramDetail::SystemTransfer(ProgramDetailSystemTransfer { value, from, to }),
+        kind: ProgramDetail::SystemTransfer(ProgramDetailSystemTransfer {
+            value,
+            from,
+            to,
+            lamports,
+        }),
     })
 }
 
@@ -494,6 +499,7 @@ fn resolve_transfer_with_seed(
             from_base_pubkey,
             from_owner,
             from_seed,
+            lamports,
         }),
     })
 }
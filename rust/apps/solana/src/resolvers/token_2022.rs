@@ -0,0 +1,445 @@
+use crate::errors::{Result, SolanaError};
+use crate::parser::detail::{
+    CommonDetail, ProgramDetail, ProgramDetailToken2022ConfidentialTransfer,
+    ProgramDetailToken2022DefaultAccountState, ProgramDetailToken2022Extension,
+    ProgramDetailToken2022HarvestWithheldTokens, ProgramDetailToken2022InitializePermanentDelegate,
+    ProgramDetailToken2022InitializeTransferFeeConfig, ProgramDetailToken2022InterestBearingMint,
+    ProgramDetailToken2022SetTransferFee, ProgramDetailToken2022TransferCheckedWithFee,
+    ProgramDetailToken2022TransferHook, ProgramDetailToken2022WithdrawWithheldTokens,
+    ProgramDetailTokenSetAuthority, SolanaDetail,
+};
+use crate::resolvers;
+use crate::solana_lib::solana_program::program_option::COption;
+use crate::solana_lib::solana_program::pubkey::Pubkey;
+use crate::solana_lib::spl::token_2022::instruction::{
+    AccountState, AuthorityType, ConfidentialTransferInstruction, Token2022Instruction,
+    TransferFeeInstruction,
+};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+pub(crate) static PROGRAM_NAME: &str = "Token2022";
+
+fn get_account(accounts: &[String], index: usize, method_name: &str, name: &str) -> Result<String> {
+    accounts
+        .get(index)
+        .map(|v| v.to_string())
+        .ok_or(SolanaError::AccountNotFound(format!(
+            "{}.{}",
+            method_name, name
+        )))
+}
+
+// accounts from `point` on are the signers of a multisig authority
+fn get_signers(accounts: &[String], point: usize) -> Option<Vec<String>> {
+    if accounts.len() > point {
+        Some(accounts[point..].to_vec())
+    } else {
+        None
+    }
+}
+
+fn pubkey_or_empty(value: Option<Pubkey>) -> String {
+    value.map(|v| v.to_string()).unwrap_or("".to_string())
+}
+
+fn coption_or_empty(value: COption<Pubkey>) -> String {
+    match value {
+        COption::Some(v) => v.to_string(),
+        COption::None => "".to_string(),
+    }
+}
+
+fn detail(method_name: &str, kind: ProgramDetail) -> SolanaDetail {
+    SolanaDetail {
+        common: CommonDetail {
+            program: PROGRAM_NAME.to_string(),
+            method: method_name.to_string(),
+        },
+        kind,
+    }
+}
+
+pub fn resolve(instruction: Token2022Instruction, accounts: Vec<String>) -> Result<SolanaDetail> {
+    match instruction {
+        // same accounts and data as the token program
+        Token2022Instruction::Token(instruction) => {
+            let mut detail = resolvers::token::resolve(instruction, accounts)?;
+            detail.common.program = PROGRAM_NAME.to_string();
+            Ok(detail)
+        }
+        Token2022Instruction::SetAuthority {
+            authority_type,
+            new_authority,
+        } => set_authority(accounts, authority_type, new_authority),
+        Token2022Instruction::TransferFee(instruction) => transfer_fee(accounts, instruction),
+        Token2022Instruction::ConfidentialTransfer(instruction) => {
+            confidential_transfer(accounts, instruction)
+        }
+        Token2022Instruction::DefaultAccountState { update, state } => {
+            default_account_state(accounts, update, state)
+        }
+        Token2022Instruction::InterestBearingMint {
+            rate_authority,
+            rate,
+            update,
+        } => interest_bearing_mint(accounts, rate_authority, rate, update),
+        Token2022Instruction::InitializePermanentDelegate { delegate } => {
+            initialize_permanent_delegate(accounts, delegate)
+        }
+        Token2022Instruction::TransferHook {
+            authority,
+            program_id,
+            update,
+        } => transfer_hook(accounts, authority, program_id, update),
+        Token2022Instruction::Other { name } => Ok(detail(
+            name,
+            ProgramDetail::Token2022Extension(ProgramDetailToken2022Extension { accounts }),
+        )),
+    }
+}
+
+fn set_authority(
+    accounts: Vec<String>,
+    authority_type: AuthorityType,
+    new_authority: COption<Pubkey>,
+) -> Result<SolanaDetail> {
+    let method_name = "SetAuthority";
+    let account = get_account(&accounts, 0, method_name, "account")?;
+    let old_authority_pubkey = get_account(&accounts, 1, method_name, "old_authority_pubkey")?;
+    let authority_type = match authority_type {
+        AuthorityType::MintTokens => "mint tokens",
+        AuthorityType::FreezeAccount => "freeze account",
+        AuthorityType::AccountOwner => "account owner",
+        AuthorityType::CloseAccount => "close account",
+        AuthorityType::TransferFeeConfig => "transfer fee config",
+        AuthorityType::WithheldWithdraw => "withheld withdraw",
+        AuthorityType::CloseMint => "close mint",
+        AuthorityType::InterestRate => "interest rate",
+        AuthorityType::PermanentDelegate => "permanent delegate",
+        AuthorityType::ConfidentialTransferMint => "confidential transfer mint",
+        AuthorityType::TransferHookProgramId => "transfer hook program id",
+        AuthorityType::ConfidentialTransferFeeConfig => "confidential transfer fee config",
+        AuthorityType::MetadataPointer => "metadata pointer",
+        AuthorityType::GroupPointer => "group pointer",
+        AuthorityType::GroupMemberPointer => "group member pointer",
+        AuthorityType::ScaledUiAmount => "scaled ui amount",
+        AuthorityType::Pause => "pause",
+    }
+    .to_string();
+    Ok(detail(
+        method_name,
+        ProgramDetail::TokenSetAuthority(ProgramDetailTokenSetAuthority {
+            account,
+            old_authority_pubkey,
+            signers: get_signers(&accounts, 2),
+            authority_type,
+            new_authority_pubkey: coption_or_empty(new_authority),
+        }),
+    ))
+}
+
+fn transfer_fee(
+    accounts: Vec<String>,
+    instruction: TransferFeeInstruction,
+) -> Result<SolanaDetail> {
+    match instruction {
+        TransferFeeInstruction::InitializeTransferFeeConfig {
+            transfer_fee_config_authority,
+            withdraw_withheld_authority,
+            transfer_fee_basis_points,
+            maximum_fee,
+        } => {
+            let method_name = "InitializeTransferFeeConfig";
+            Ok(detail(
+                method_name,
+                ProgramDetail::Token2022InitializeTransferFeeConfig(
+                    ProgramDetailToken2022InitializeTransferFeeConfig {
+                        mint: get_account(&accounts, 0, method_name, "mint")?,
+                        transfer_fee_config_authority: coption_or_empty(
+                            transfer_fee_config_authority,
+                        ),
+                        withdraw_withheld_authority: coption_or_empty(withdraw_withheld_authority),
+                        transfer_fee_basis_points,
+                        maximum_fee: maximum_fee.to_string(),
+                    },
+                ),
+            ))
+        }
+        TransferFeeInstruction::TransferCheckedWithFee {
+            amount,
+            decimals,
+            fee,
+        } => {
+            let method_name = "TransferCheckedWithFee";
+            Ok(detail(
+                method_name,
+                ProgramDetail::Token2022TransferCheckedWithFee(
+                    ProgramDetailToken2022TransferCheckedWithFee {
+                        account: get_account(&accounts, 0, method_name, "account")?,
+                        mint: get_account(&accounts, 1, method_name, "mint")?,
+                        recipient: get_account(&accounts, 2, method_name, "recipient")?,
+                        owner: get_account(&accounts, 3, method_name, "owner")?,
+                        signers: get_signers(&accounts, 4),
+                        decimals,
+                        amount: amount.to_string(),
+                        fee: fee.to_string(),
+                    },
+                ),
+            ))
+        }
+        TransferFeeInstruction::WithdrawWithheldTokensFromMint => {
+            let method_name = "WithdrawWithheldTokensFromMint";
+            Ok(detail(
+                method_name,
+                ProgramDetail::Token2022WithdrawWithheldTokens(
+                    ProgramDetailToken2022WithdrawWithheldTokens {
+                        mint: get_account(&accounts, 0, method_name, "mint")?,
+                        recipient: get_account(&accounts, 1, method_name, "recipient")?,
+                        withdraw_withheld_authority: get_account(
+                            &accounts,
+                            2,
+                            method_name,
+                            "withdraw_withheld_authority",
+                        )?,
+                        signers: get_signers(&accounts, 3),
+                        source_accounts: Vec::new(),
+                    },
+                ),
+            ))
+        }
+        TransferFeeInstruction::WithdrawWithheldTokensFromAccounts { num_token_accounts } => {
+            let method_name = "WithdrawWithheldTokensFromAccounts";
+            // the source accounts come after the signers
+            let sources_start = accounts
+                .len()
+                .checked_sub(num_token_accounts as usize)
+                .filter(|v| *v >= 3)
+                .ok_or(SolanaError::AccountNotFound(format!(
+                    "{}.source_accounts",
+                    method_name
+                )))?;
+            Ok(detail(
+                method_name,
+                ProgramDetail::Token2022WithdrawWithheldTokens(
+                    ProgramDetailToken2022WithdrawWithheldTokens {
+                        mint: get_account(&accounts, 0, method_name, "mint")?,
+                        recipient: get_account(&accounts, 1, method_name, "recipient")?,
+                        withdraw_withheld_authority: get_account(
+                            &accounts,
+                            2,
+                            method_name,
+                            "withdraw_withheld_authority",
+                        )?,
+                        signers: get_signers(&accounts[..sources_start], 3),
+                        source_accounts: accounts[sources_start..].to_vec(),
+                    },
+                ),
+            ))
+        }
+        TransferFeeInstruction::HarvestWithheldTokensToMint => {
+            let method_name = "HarvestWithheldTokensToMint";
+            Ok(detail(
+                method_name,
+                ProgramDetail::Token2022HarvestWithheldTokens(
+                    ProgramDetailToken2022HarvestWithheldTokens {
+                        mint: get_account(&accounts, 0, method_name, "mint")?,
+                        source_accounts: accounts.iter().skip(1).cloned().collect(),
+                    },
+                ),
+            ))
+        }
+        TransferFeeInstruction::SetTransferFee {
+            transfer_fee_basis_points,
+            maximum_fee,
+        } => {
+            let method_name = "SetTransferFee";
+            Ok(detail(
+                method_name,
+                ProgramDetail::Token2022SetTransferFee(ProgramDetailToken2022SetTransferFee {
+                    mint: get_account(&accounts, 0, method_name, "mint")?,
+                    transfer_fee_config_authority: get_account(
+                        &accounts,
+                        1,
+                        method_name,
+                        "transfer_fee_config_authority",
+                    )?,
+                    signers: get_signers(&accounts, 2),
+                    transfer_fee_basis_points,
+                    maximum_fee: maximum_fee.to_string(),
+                }),
+            ))
+        }
+    }
+}
+
+fn confidential_transfer(
+    accounts: Vec<String>,
+    instruction: ConfidentialTransferInstruction,
+) -> Result<SolanaDetail> {
+    let (method_name, amount, decimals) = match instruction {
+        ConfidentialTransferInstruction::InitializeMint => {
+            ("InitializeConfidentialTransferMint", None, None)
+        }
+        ConfidentialTransferInstruction::UpdateMint => {
+            ("UpdateConfidentialTransferMint", None, None)
+        }
+        ConfidentialTransferInstruction::ConfigureAccount => {
+            ("ConfigureConfidentialTransferAccount", None, None)
+        }
+        ConfidentialTransferInstruction::ApproveAccount => {
+            ("ApproveConfidentialTransferAccount", None, None)
+        }
+        ConfidentialTransferInstruction::EmptyAccount => {
+            ("EmptyConfidentialTransferAccount", None, None)
+        }
+        ConfidentialTransferInstruction::Deposit { amount, decimals } => (
+            "ConfidentialDeposit",
+            Some(amount.to_string()),
+            Some(decimals),
+        ),
+        ConfidentialTransferInstruction::Withdraw { amount, decimals } => (
+            "ConfidentialWithdraw",
+            Some(amount.to_string()),
+            Some(decimals),
+        ),
+        ConfidentialTransferInstruction::Transfer => ("ConfidentialTransfer", None, None),
+        ConfidentialTransferInstruction::ApplyPendingBalance => ("ApplyPendingBalance", None, None),
+        ConfidentialTransferInstruction::EnableConfidentialCredits => {
+            ("EnableConfidentialCredits", None, None)
+        }
+        ConfidentialTransferInstruction::DisableConfidentialCredits => {
+            ("DisableConfidentialCredits", None, None)
+        }
+        ConfidentialTransferInstruction::EnableNonConfidentialCredits => {
+            ("EnableNonConfidentialCredits", None, None)
+        }
+        ConfidentialTransferInstruction::DisableNonConfidentialCredits => {
+            ("DisableNonConfidentialCredits", None, None)
+        }
+        ConfidentialTransferInstruction::TransferWithFee => {
+            ("ConfidentialTransferWithFee", None, None)
+        }
+        ConfidentialTransferInstruction::ConfigureAccountWithRegistry => {
+            ("ConfigureConfidentialAccountWithRegistry", None, None)
+        }
+    };
+    Ok(detail(
+        method_name,
+        ProgramDetail::Token2022ConfidentialTransfer(ProgramDetailToken2022ConfidentialTransfer {
+            accounts,
+            amount,
+            decimals,
+        }),
+    ))
+}
+
+fn default_account_state(
+    accounts: Vec<String>,
+    update: bool,
+    state: AccountState,
+) -> Result<SolanaDetail> {
+    let method_name = if update {
+        "UpdateDefaultAccountState"
+    } else {
+        "InitializeDefaultAccountState"
+    };
+    let state = match state {
+        AccountState::Uninitialized => "uninitialized",
+        AccountState::Initialized => "initialized",
+        AccountState::Frozen => "frozen",
+    }
+    .to_string();
+    let (freeze_authority_pubkey, signers) = if update {
+        (
+            get_account(&accounts, 1, method_name, "freeze_authority_pubkey")?,
+            get_signers(&accounts, 2),
+        )
+    } else {
+        ("".to_string(), None)
+    };
+    Ok(detail(
+        method_name,
+        ProgramDetail::Token2022DefaultAccountState(ProgramDetailToken2022DefaultAccountState {
+            mint: get_account(&accounts, 0, method_name, "mint")?,
+            freeze_authority_pubkey,
+            signers,
+            state,
+        }),
+    ))
+}
+
+fn interest_bearing_mint(
+    accounts: Vec<String>,
+    rate_authority: Option<Pubkey>,
+    rate: i16,
+    update: bool,
+) -> Result<SolanaDetail> {
+    let method_name = if update {
+        "UpdateInterestRate"
+    } else {
+        "InitializeInterestBearingMint"
+    };
+    let (rate_authority, signers) = if update {
+        (
+            get_account(&accounts, 1, method_name, "rate_authority")?,
+            get_signers(&accounts, 2),
+        )
+    } else {
+        (pubkey_or_empty(rate_authority), None)
+    };
+    Ok(detail(
+        method_name,
+        ProgramDetail::Token2022InterestBearingMint(ProgramDetailToken2022InterestBearingMint {
+            mint: get_account(&accounts, 0, method_name, "mint")?,
+            rate_authority,
+            signers,
+            // basis points
+            rate: format!("{}%", rate as f64 / 100.0),
+        }),
+    ))
+}
+
+fn initialize_permanent_delegate(accounts: Vec<String>, delegate: Pubkey) -> Result<SolanaDetail> {
+    let method_name = "InitializePermanentDelegate";
+    Ok(detail(
+        method_name,
+        ProgramDetail::Token2022InitializePermanentDelegate(
+            ProgramDetailToken2022InitializePermanentDelegate {
+                mint: get_account(&accounts, 0, method_name, "mint")?,
+                delegate: delegate.to_string(),
+            },
+        ),
+    ))
+}
+
+fn transfer_hook(
+    accounts: Vec<String>,
+    authority: Option<Pubkey>,
+    program_id: Option<Pubkey>,
+    update: bool,
+) -> Result<SolanaDetail> {
+    let method_name = if update {
+        "UpdateTransferHook"
+    } else {
+        "InitializeTransferHook"
+    };
+    let (authority, signers) = if update {
+        (
+            get_account(&accounts, 1, method_name, "authority")?,
+            get_signers(&accounts, 2),
+        )
+    } else {
+        (pubkey_or_empty(authority), None)
+    };
+    Ok(detail(
+        method_name,
+        ProgramDetail::Token2022TransferHook(ProgramDetailToken2022TransferHook {
+            mint: get_account(&accounts, 0, method_name, "mint")?,
+            authority,
+            signers,
+            program_id: pubkey_or_empty(program_id),
+        }),
+    ))
+}
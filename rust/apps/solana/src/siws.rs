@@ -0,0 +1,163 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use bitcoin::base58;
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your Solana account:";
+const FIELDS: [&str; 8] = [
+    "URI",
+    "Version",
+    "Chain ID",
+    "Nonce",
+    "Issued At",
+    "Expiration Time",
+    "Not Before",
+    "Request ID",
+];
+
+// Sign-In-With-Solana message, the solana flavour of EIP-4361:
+// <domain> wants you to sign in with your Solana account:
+// <address>
+//
+// [<statement>
+//
+// ]URI: <uri>
+// Version: <version>
+// ...
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SignInWithSolana {
+    pub domain: String,
+    pub address: String,
+    pub statement: Option<String>,
+    pub uri: Option<String>,
+    pub version: Option<String>,
+    pub chain_id: Option<String>,
+    pub nonce: Option<String>,
+    pub issued_at: Option<String>,
+    pub expiration_time: Option<String>,
+    pub not_before: Option<String>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+impl SignInWithSolana {
+    // returns None when the text is not a well-formed sign-in message,
+    // it is shown as a plain message then
+    pub fn parse(message: &str) -> Option<Self> {
+        let mut lines = message.split('\n');
+        let domain = lines.next()?.strip_suffix(HEADER_SUFFIX)?;
+        if domain.is_empty() || domain.contains(char::is_whitespace) {
+            return None;
+        }
+        let address = lines.next()?;
+        if base58::decode(address).ok()?.len() != 32 {
+            return None;
+        }
+        let mut siws = Self {
+            domain: domain.to_string(),
+            address: address.to_string(),
+            ..Default::default()
+        };
+        let mut lines = lines.peekable();
+        if lines.next().is_some_and(|v| !v.is_empty()) {
+            return None;
+        }
+        // the statement is the only free text and is followed by an empty line
+        if let Some(line) = lines.peek() {
+            if !line.is_empty() && !is_field(line) {
+                siws.statement = Some(line.to_string());
+                lines.next();
+                if lines.next().is_some_and(|v| !v.is_empty()) {
+                    return None;
+                }
+            }
+        }
+        while let Some(line) = lines.next() {
+            if line.is_empty() {
+                continue;
+            }
+            if line == "Resources:" {
+                while let Some(resource) = lines.next_if(|v| v.starts_with("- ")) {
+                    siws.resources.push(resource[2..].to_string());
+                }
+                continue;
+            }
+            let (key, value) = line.split_once(": ")?;
+            let field = match key {
+                "URI" => &mut siws.uri,
+                "Version" => &mut siws.version,
+                "Chain ID" => &mut siws.chain_id,
+                "Nonce" => &mut siws.nonce,
+                "Issued At" => &mut siws.issued_at,
+                "Expiration Time" => &mut siws.expiration_time,
+                "Not Before" => &mut siws.not_before,
+                "Request ID" => &mut siws.request_id,
+                _ => return None,
+            };
+            // a repeated field could show one value and mean another
+            if field.replace(value.to_string()).is_some() {
+                return None;
+            }
+        }
+        Some(siws)
+    }
+}
+
+fn is_field(line: &str) -> bool {
+    line == "Resources:"
+        || FIELDS
+            .iter()
+            .any(|key| line.strip_prefix(key).is_some_and(|v| v.starts_with(": ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sign_in_message() {
+        let message = "example.com wants you to sign in with your Solana account:\nGWZVzcS2MXfFqHmP782Qx3RzkkQX2KfgchZPLp3AEZrm\n\nSign in to Example\n\nURI: https://example.com/login\nVersion: 1\nChain ID: mainnet\nNonce: 32891756\nIssued At: 2024-01-01T00:00:00.000Z\nExpiration Time: 2024-01-01T00:10:00.000Z\nResources:\n- https://example.com/terms\n- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq";
+        let siws = SignInWithSolana::parse(message).unwrap();
+        assert_eq!("example.com", siws.domain);
+        assert_eq!("GWZVzcS2MXfFqHmP782Qx3RzkkQX2KfgchZPLp3AEZrm", siws.address);
+        assert_eq!(Some("Sign in to Example".to_string()), siws.statement);
+        assert_eq!(Some("https://example.com/login".to_string()), siws.uri);
+        assert_eq!(Some("1".to_string()), siws.version);
+        assert_eq!(Some("mainnet".to_string()), siws.chain_id);
+        assert_eq!(Some("32891756".to_string()), siws.nonce);
+        assert_eq!(Some("2024-01-01T00:00:00.000Z".to_string()), siws.issued_at);
+        assert_eq!(
+            Some("2024-01-01T00:10:00.000Z".to_string()),
+            siws.expiration_time
+        );
+        assert_eq!(None, siws.not_before);
+        assert_eq!(2, siws.resources.len());
+
+        // only the domain and the address are required
+        let message = "example.com wants you to sign in with your Solana account:\nGWZVzcS2MXfFqHmP782Qx3RzkkQX2KfgchZPLp3AEZrm";
+        let siws = SignInWithSolana::parse(message).unwrap();
+        assert_eq!(None, siws.statement);
+        assert_eq!(None, siws.nonce);
+
+        let message = "example.com wants you to sign in with your Solana account:\nGWZVzcS2MXfFqHmP782Qx3RzkkQX2KfgchZPLp3AEZrm\n\nNonce: 32891756";
+        let siws = SignInWithSolana::parse(message).unwrap();
+        assert_eq!(None, siws.statement);
+        assert_eq!(Some("32891756".to_string()), siws.nonce);
+    }
+
+    #[test]
+    fn test_parse_malformed_sign_in_message() {
+        assert!(SignInWithSolana::parse("Hello, Solana!").is_none());
+        // invalid address
+        assert!(SignInWithSolana::parse(
+            "example.com wants you to sign in with your Solana account:\nnot-an-address"
+        )
+        .is_none());
+        // unknown field
+        assert!(SignInWithSolana::parse("example.com wants you to sign in with your Solana account:\nGWZVzcS2MXfFqHmP782Qx3RzkkQX2KfgchZPLp3AEZrm\n\nURI: https://example.com\nAmount: 100").is_none());
+        // repeated field
+        assert!(SignInWithSolana::parse("example.com wants you to sign in with your Solana account:\nGWZVzcS2MXfFqHmP782Qx3RzkkQX2KfgchZPLp3AEZrm\n\nNonce: 32891756\nNonce: 12345678").is_none());
+        // statement not followed by an empty line
+        assert!(SignInWithSolana::parse("example.com wants you to sign in with your Solana account:\nGWZVzcS2MXfFqHmP782Qx3RzkkQX2KfgchZPLp3AEZrm\n\nSign in\nURI: https://example.com").is_none());
+    }
+}
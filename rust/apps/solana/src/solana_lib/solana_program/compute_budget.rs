@@ -0,0 +1,87 @@
+pub mod instruction {
+    use crate::solana_lib::solana_program::errors::InstructionError;
+
+    /// Compute budget instructions, borsh encoded with a single byte tag.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum ComputeBudgetInstruction {
+        /// Deprecated, requests a transaction-wide compute unit limit and pays
+        /// `additional_fee` lamports on top of the base fee.
+        RequestUnits { units: u32, additional_fee: u32 },
+        /// Requests a specific transaction-wide program heap region size in bytes.
+        RequestHeapFrame(u32),
+        /// Sets a specific compute unit limit the transaction is allowed to consume.
+        SetComputeUnitLimit(u32),
+        /// Sets a compute unit price in "micro-lamports" to pay a higher transaction
+        /// fee for higher transaction prioritization.
+        SetComputeUnitPrice(u64),
+        /// Sets a specific transaction-wide account data size limit, in bytes.
+        SetLoadedAccountsDataSizeLimit(u32),
+    }
+
+    impl ComputeBudgetInstruction {
+        pub fn unpack(input: &[u8]) -> Result<Self, InstructionError> {
+            let (&tag, rest) = input
+                .split_first()
+                .ok_or(InstructionError::InvalidInstructionData)?;
+            Ok(match tag {
+                0 => {
+                    let (units, rest) = Self::unpack_u32(rest)?;
+                    let (additional_fee, _rest) = Self::unpack_u32(rest)?;
+                    Self::RequestUnits {
+                        units,
+                        additional_fee,
+                    }
+                }
+                1 => Self::RequestHeapFrame(Self::unpack_u32(rest)?.0),
+                2 => Self::SetComputeUnitLimit(Self::unpack_u32(rest)?.0),
+                3 => Self::SetComputeUnitPrice(Self::unpack_u64(rest)?.0),
+                4 => Self::SetLoadedAccountsDataSizeLimit(Self::unpack_u32(rest)?.0),
+                _ => return Err(InstructionError::InvalidInstructionData),
+            })
+        }
+
+        fn unpack_u32(input: &[u8]) -> Result<(u32, &[u8]), InstructionError> {
+            let value = input
+                .get(..4)
+                .and_then(|slice| slice.try_into().ok())
+                .map(u32::from_le_bytes)
+                .ok_or(InstructionError::InvalidInstructionData)?;
+            Ok((value, &input[4..]))
+        }
+
+        fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), InstructionError> {
+            let value = input
+                .get(..8)
+                .and_then(|slice| slice.try_into().ok())
+                .map(u64::from_le_bytes)
+                .ok_or(InstructionError::InvalidInstructionData)?;
+            Ok((value, &input[8..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::instruction::ComputeBudgetInstruction;
+
+    #[test]
+    fn test_unpack() {
+        assert_eq!(
+            ComputeBudgetInstruction::SetComputeUnitLimit(542092),
+            ComputeBudgetInstruction::unpack(&[2, 0x8c, 0x45, 0x08, 0x00]).unwrap()
+        );
+        assert_eq!(
+            ComputeBudgetInstruction::SetComputeUnitPrice(100000),
+            ComputeBudgetInstruction::unpack(&[3, 0xa0, 0x86, 0x01, 0, 0, 0, 0, 0]).unwrap()
+        );
+        assert_eq!(
+            ComputeBudgetInstruction::RequestUnits {
+                units: 300000,
+                additional_fee: 1000
+            },
+            ComputeBudgetInstruction::unpack(&[0, 0xe0, 0x93, 0x04, 0, 0xe8, 0x03, 0, 0]).unwrap()
+        );
+        assert!(ComputeBudgetInstruction::unpack(&[2, 0x8c]).is_err());
+        assert!(ComputeBudgetInstruction::unpack(&[5, 0, 0, 0, 0]).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod compute_budget;
 pub mod errors;
 pub mod program_option;
 pub mod program_pack;
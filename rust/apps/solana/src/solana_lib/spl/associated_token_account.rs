@@ -0,0 +1,56 @@
+pub mod instruction {
+    use crate::solana_lib::solana_program::errors::ProgramError;
+    use crate::solana_lib::spl::errors::TokenError;
+
+    /// Instructions supported by the associated token account program.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum AssociatedTokenAccountInstruction {
+        /// Creates an associated token account for the given wallet address and
+        /// token mint, fails if the account already exists.
+        Create,
+        /// Creates an associated token account for the given wallet address and
+        /// token mint, if it doesn't already exist.
+        CreateIdempotent,
+        /// Transfers from and closes a nested associated token account: an
+        /// associated token account owned by an associated token account.
+        RecoverNested,
+    }
+
+    impl AssociatedTokenAccountInstruction {
+        pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+            // the first version of the program takes no instruction data
+            match input.first() {
+                None | Some(0) => Ok(Self::Create),
+                Some(1) => Ok(Self::CreateIdempotent),
+                Some(2) => Ok(Self::RecoverNested),
+                _ => Err(TokenError::InvalidInstruction.into()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::instruction::AssociatedTokenAccountInstruction;
+
+    #[test]
+    fn test_unpack() {
+        assert_eq!(
+            AssociatedTokenAccountInstruction::Create,
+            AssociatedTokenAccountInstruction::unpack(&[]).unwrap()
+        );
+        assert_eq!(
+            AssociatedTokenAccountInstruction::Create,
+            AssociatedTokenAccountInstruction::unpack(&[0]).unwrap()
+        );
+        assert_eq!(
+            AssociatedTokenAccountInstruction::CreateIdempotent,
+            AssociatedTokenAccountInstruction::unpack(&[1]).unwrap()
+        );
+        assert_eq!(
+            AssociatedTokenAccountInstruction::RecoverNested,
+            AssociatedTokenAccountInstruction::unpack(&[2]).unwrap()
+        );
+        assert!(AssociatedTokenAccountInstruction::unpack(&[3]).is_err());
+    }
+}
@@ -1,4 +1,6 @@
+pub mod associated_token_account;
 pub mod errors;
 pub mod token;
+pub mod token_2022;
 pub mod token_lending;
 pub mod token_swap;
@@ -0,0 +1,549 @@
+pub mod instruction {
+    use crate::solana_lib::solana_program::errors::ProgramError;
+    use crate::solana_lib::solana_program::program_option::COption;
+    use crate::solana_lib::solana_program::pubkey::Pubkey;
+    use crate::solana_lib::spl::errors::TokenError;
+    use crate::solana_lib::spl::token::instruction::TokenInstruction;
+
+    /// Specifies the authority type for SetAuthority instructions, Token-2022 adds
+    /// one authority per extension after the four of the classic token program.
+    #[repr(u8)]
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum AuthorityType {
+        MintTokens,
+        FreezeAccount,
+        AccountOwner,
+        CloseAccount,
+        TransferFeeConfig,
+        WithheldWithdraw,
+        CloseMint,
+        InterestRate,
+        PermanentDelegate,
+        ConfidentialTransferMint,
+        TransferHookProgramId,
+        ConfidentialTransferFeeConfig,
+        MetadataPointer,
+        GroupPointer,
+        GroupMemberPointer,
+        ScaledUiAmount,
+        Pause,
+    }
+
+    impl AuthorityType {
+        fn from(index: u8) -> Result<Self, ProgramError> {
+            match index {
+                0 => Ok(AuthorityType::MintTokens),
+                1 => Ok(AuthorityType::FreezeAccount),
+                2 => Ok(AuthorityType::AccountOwner),
+                3 => Ok(AuthorityType::CloseAccount),
+                4 => Ok(AuthorityType::TransferFeeConfig),
+                5 => Ok(AuthorityType::WithheldWithdraw),
+                6 => Ok(AuthorityType::CloseMint),
+                7 => Ok(AuthorityType::InterestRate),
+                8 => Ok(AuthorityType::PermanentDelegate),
+                9 => Ok(AuthorityType::ConfidentialTransferMint),
+                10 => Ok(AuthorityType::TransferHookProgramId),
+                11 => Ok(AuthorityType::ConfidentialTransferFeeConfig),
+                12 => Ok(AuthorityType::MetadataPointer),
+                13 => Ok(AuthorityType::GroupPointer),
+                14 => Ok(AuthorityType::GroupMemberPointer),
+                15 => Ok(AuthorityType::ScaledUiAmount),
+                16 => Ok(AuthorityType::Pause),
+                _ => Err(TokenError::InvalidInstruction.into()),
+            }
+        }
+    }
+
+    /// State a new token account is created in when the mint has the default
+    /// account state extension.
+    #[repr(u8)]
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum AccountState {
+        Uninitialized,
+        Initialized,
+        Frozen,
+    }
+
+    impl AccountState {
+        fn from(index: u8) -> Result<Self, ProgramError> {
+            match index {
+                0 => Ok(AccountState::Uninitialized),
+                1 => Ok(AccountState::Initialized),
+                2 => Ok(AccountState::Frozen),
+                _ => Err(TokenError::InvalidInstruction.into()),
+            }
+        }
+    }
+
+    /// Instructions of the transfer fee extension.
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum TransferFeeInstruction {
+        /// Accounts expected by this instruction:
+        ///
+        ///   0. `[writable]` The mint to initialize.
+        InitializeTransferFeeConfig {
+            transfer_fee_config_authority: COption<Pubkey>,
+            withdraw_withheld_authority: COption<Pubkey>,
+            transfer_fee_basis_points: u16,
+            maximum_fee: u64,
+        },
+        /// Transfer, providing the expected fee so the transfer fails if the fee
+        /// of the mint was changed in the meantime.
+        ///
+        /// Accounts expected by this instruction:
+        ///
+        ///   0. `[writable]` The source account.
+        ///   1. `[]` The token mint.
+        ///   2. `[writable]` The destination account.
+        ///   3. `[signer]` The source account's owner/delegate.
+        ///   4. ..4+M `[signer]` M signer accounts.
+        TransferCheckedWithFee { amount: u64, decimals: u8, fee: u64 },
+        /// Accounts expected by this instruction:
+        ///
+        ///   0. `[writable]` The token mint.
+        ///   1. `[writable]` The fee receiver account.
+        ///   2. `[signer]` The mint's withdraw_withheld_authority.
+        ///   3. ..3+M `[signer]` M signer accounts.
+        WithdrawWithheldTokensFromMint,
+        /// Accounts expected by this instruction:
+        ///
+        ///   0. `[]` The token mint.
+        ///   1. `[writable]` The fee receiver account.
+        ///   2. `[signer]` The mint's withdraw_withheld_authority.
+        ///   3. ..3+M `[signer]` M signer accounts.
+        ///   3+M+1. ..3+M+N `[writable]` The source accounts to withdraw from.
+        WithdrawWithheldTokensFromAccounts { num_token_accounts: u8 },
+        /// Accounts expected by this instruction:
+        ///
+        ///   0. `[writable]` The mint.
+        ///   1. ..1+N `[writable]` The source accounts to harvest from.
+        HarvestWithheldTokensToMint,
+        /// Accounts expected by this instruction:
+        ///
+        ///   0. `[writable]` The mint.
+        ///   1. `[signer]` The mint's fee account owner.
+        ///   2. ..2+M `[signer]` M signer accounts.
+        SetTransferFee {
+            transfer_fee_basis_points: u16,
+            maximum_fee: u64,
+        },
+    }
+
+    /// Instructions of the confidential transfer extension. Amounts moved between
+    /// confidential balances are encrypted, only deposits and withdrawals carry a
+    /// plain amount.
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum ConfidentialTransferInstruction {
+        InitializeMint,
+        UpdateMint,
+        ConfigureAccount,
+        ApproveAccount,
+        EmptyAccount,
+        Deposit { amount: u64, decimals: u8 },
+        Withdraw { amount: u64, decimals: u8 },
+        Transfer,
+        ApplyPendingBalance,
+        EnableConfidentialCredits,
+        DisableConfidentialCredits,
+        EnableNonConfidentialCredits,
+        DisableNonConfidentialCredits,
+        TransferWithFee,
+        ConfigureAccountWithRegistry,
+    }
+
+    /// Instructions supported by the Token-2022 program. Instructions 0 to 20
+    /// share their layout with the classic token program.
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Token2022Instruction {
+        Token(TokenInstruction),
+        /// Same as the classic instruction, with the authority types of the extensions.
+        SetAuthority {
+            authority_type: AuthorityType,
+            new_authority: COption<Pubkey>,
+        },
+        TransferFee(TransferFeeInstruction),
+        ConfidentialTransfer(ConfidentialTransferInstruction),
+        /// Accounts expected by this instruction:
+        ///
+        ///   * Initialize
+        ///   0. `[writable]` The mint.
+        ///
+        ///   * Update
+        ///   0. `[writable]` The mint.
+        ///   1. `[signer]` The mint's freeze authority.
+        ///   2. ..2+M `[signer]` M signer accounts.
+        DefaultAccountState {
+            update: bool,
+            state: AccountState,
+        },
+        /// Accounts expected by this instruction:
+        ///
+        ///   * Initialize
+        ///   0. `[writable]` The mint.
+        ///
+        ///   * UpdateRate
+        ///   0. `[writable]` The mint.
+        ///   1. `[signer]` The mint's rate authority.
+        ///   2. ..2+M `[signer]` M signer accounts.
+        InterestBearingMint {
+            rate_authority: Option<Pubkey>,
+            /// Interest rate in basis points
+            rate: i16,
+            update: bool,
+        },
+        /// Accounts expected by this instruction:
+        ///
+        ///   0. `[writable]` The mint.
+        InitializePermanentDelegate {
+            delegate: Pubkey,
+        },
+        /// Accounts expected by this instruction:
+        ///
+        ///   * Initialize
+        ///   0. `[writable]` The mint.
+        ///
+        ///   * Update
+        ///   0. `[writable]` The mint.
+        ///   1. `[signer]` The transfer hook authority.
+        ///   2. ..2+M `[signer]` M signer accounts.
+        TransferHook {
+            authority: Option<Pubkey>,
+            program_id: Option<Pubkey>,
+            update: bool,
+        },
+        /// Any other instruction or extension, only its name is decoded.
+        Other {
+            name: &'static str,
+        },
+    }
+
+    impl Token2022Instruction {
+        pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+            use TokenError::InvalidInstruction;
+
+            let (&tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+            Ok(match tag {
+                6 => {
+                    let (&authority_type, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                    let (new_authority, _rest) = Self::unpack_pubkey_option(rest)?;
+                    Self::SetAuthority {
+                        authority_type: AuthorityType::from(authority_type)?,
+                        new_authority,
+                    }
+                }
+                0..=20 => Self::Token(TokenInstruction::unpack(input)?),
+                26 => Self::TransferFee(Self::unpack_transfer_fee(rest)?),
+                27 => Self::ConfidentialTransfer(Self::unpack_confidential_transfer(rest)?),
+                28 => {
+                    let (&sub_tag, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                    let &state = rest.first().ok_or(InvalidInstruction)?;
+                    if sub_tag > 1 {
+                        return Err(InvalidInstruction.into());
+                    }
+                    Self::DefaultAccountState {
+                        update: sub_tag == 1,
+                        state: AccountState::from(state)?,
+                    }
+                }
+                33 => {
+                    let (&sub_tag, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                    match sub_tag {
+                        0 => {
+                            let (rate_authority, rest) = Self::unpack_optional_pubkey(rest)?;
+                            let (rate, _rest) = Self::unpack_i16(rest)?;
+                            Self::InterestBearingMint {
+                                rate_authority,
+                                rate,
+                                update: false,
+                            }
+                        }
+                        1 => {
+                            let (rate, _rest) = Self::unpack_i16(rest)?;
+                            Self::InterestBearingMint {
+                                rate_authority: None,
+                                rate,
+                                update: true,
+                            }
+                        }
+                        _ => return Err(InvalidInstruction.into()),
+                    }
+                }
+                35 => {
+                    let (delegate, _rest) = Self::unpack_pubkey(rest)?;
+                    Self::InitializePermanentDelegate { delegate }
+                }
+                36 => {
+                    let (&sub_tag, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                    match sub_tag {
+                        0 => {
+                            let (authority, rest) = Self::unpack_optional_pubkey(rest)?;
+                            let (program_id, _rest) = Self::unpack_optional_pubkey(rest)?;
+                            Self::TransferHook {
+                                authority,
+                                program_id,
+                                update: false,
+                            }
+                        }
+                        1 => {
+                            let (program_id, _rest) = Self::unpack_optional_pubkey(rest)?;
+                            Self::TransferHook {
+                                authority: None,
+                                program_id,
+                                update: true,
+                            }
+                        }
+                        _ => return Err(InvalidInstruction.into()),
+                    }
+                }
+                _ => Self::Other {
+                    name: Self::instruction_name(tag).ok_or(InvalidInstruction)?,
+                },
+            })
+        }
+
+        fn instruction_name(tag: u8) -> Option<&'static str> {
+            Some(match tag {
+                21 => "GetAccountDataSize",
+                22 => "InitializeImmutableOwner",
+                23 => "AmountToUiAmount",
+                24 => "UiAmountToAmount",
+                25 => "InitializeMintCloseAuthority",
+                29 => "Reallocate",
+                30 => "MemoTransfer",
+                31 => "CreateNativeMint",
+                32 => "InitializeNonTransferableMint",
+                34 => "CpiGuard",
+                37 => "ConfidentialTransferFee",
+                38 => "WithdrawExcessLamports",
+                39 => "MetadataPointer",
+                40 => "GroupPointer",
+                41 => "GroupMemberPointer",
+                42 => "ConfidentialMintBurn",
+                43 => "ScaledUiAmount",
+                44 => "Pausable",
+                _ => return None,
+            })
+        }
+
+        fn unpack_transfer_fee(input: &[u8]) -> Result<TransferFeeInstruction, ProgramError> {
+            use TokenError::InvalidInstruction;
+
+            let (&tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+            Ok(match tag {
+                0 => {
+                    let (transfer_fee_config_authority, rest) = Self::unpack_pubkey_option(rest)?;
+                    let (withdraw_withheld_authority, rest) = Self::unpack_pubkey_option(rest)?;
+                    let (transfer_fee_basis_points, rest) = Self::unpack_u16(rest)?;
+                    let (maximum_fee, _rest) = Self::unpack_u64(rest)?;
+                    TransferFeeInstruction::InitializeTransferFeeConfig {
+                        transfer_fee_config_authority,
+                        withdraw_withheld_authority,
+                        transfer_fee_basis_points,
+                        maximum_fee,
+                    }
+                }
+                1 => {
+                    let (amount, rest) = Self::unpack_u64(rest)?;
+                    let (&decimals, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                    let (fee, _rest) = Self::unpack_u64(rest)?;
+                    TransferFeeInstruction::TransferCheckedWithFee {
+                        amount,
+                        decimals,
+                        fee,
+                    }
+                }
+                2 => TransferFeeInstruction::WithdrawWithheldTokensFromMint,
+                3 => {
+                    let &num_token_accounts = rest.first().ok_or(InvalidInstruction)?;
+                    TransferFeeInstruction::WithdrawWithheldTokensFromAccounts {
+                        num_token_accounts,
+                    }
+                }
+                4 => TransferFeeInstruction::HarvestWithheldTokensToMint,
+                5 => {
+                    let (transfer_fee_basis_points, rest) = Self::unpack_u16(rest)?;
+                    let (maximum_fee, _rest) = Self::unpack_u64(rest)?;
+                    TransferFeeInstruction::SetTransferFee {
+                        transfer_fee_basis_points,
+                        maximum_fee,
+                    }
+                }
+                _ => return Err(InvalidInstruction.into()),
+            })
+        }
+
+        fn unpack_confidential_transfer(
+            input: &[u8],
+        ) -> Result<ConfidentialTransferInstruction, ProgramError> {
+            use TokenError::InvalidInstruction;
+
+            let (&tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+            Ok(match tag {
+                0 => ConfidentialTransferInstruction::InitializeMint,
+                1 => ConfidentialTransferInstruction::UpdateMint,
+                2 => ConfidentialTransferInstruction::ConfigureAccount,
+                3 => ConfidentialTransferInstruction::ApproveAccount,
+                4 => ConfidentialTransferInstruction::EmptyAccount,
+                5 | 6 => {
+                    let (amount, rest) = Self::unpack_u64(rest)?;
+                    let &decimals = rest.first().ok_or(InvalidInstruction)?;
+                    if tag == 5 {
+                        ConfidentialTransferInstruction::Deposit { amount, decimals }
+                    } else {
+                        ConfidentialTransferInstruction::Withdraw { amount, decimals }
+                    }
+                }
+                7 => ConfidentialTransferInstruction::Transfer,
+                8 => ConfidentialTransferInstruction::ApplyPendingBalance,
+                9 => ConfidentialTransferInstruction::EnableConfidentialCredits,
+                10 => ConfidentialTransferInstruction::DisableConfidentialCredits,
+                11 => ConfidentialTransferInstruction::EnableNonConfidentialCredits,
+                12 => ConfidentialTransferInstruction::DisableNonConfidentialCredits,
+                13 => ConfidentialTransferInstruction::TransferWithFee,
+                14 => ConfidentialTransferInstruction::ConfigureAccountWithRegistry,
+                _ => return Err(InvalidInstruction.into()),
+            })
+        }
+
+        fn unpack_u16(input: &[u8]) -> Result<(u16, &[u8]), ProgramError> {
+            let value = input
+                .get(..2)
+                .and_then(|slice| slice.try_into().ok())
+                .map(u16::from_le_bytes)
+                .ok_or(TokenError::InvalidInstruction)?;
+            Ok((value, &input[2..]))
+        }
+
+        fn unpack_i16(input: &[u8]) -> Result<(i16, &[u8]), ProgramError> {
+            let (value, rest) = Self::unpack_u16(input)?;
+            Ok((value as i16, rest))
+        }
+
+        fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+            let value = input
+                .get(..8)
+                .and_then(|slice| slice.try_into().ok())
+                .map(u64::from_le_bytes)
+                .ok_or(TokenError::InvalidInstruction)?;
+            Ok((value, &input[8..]))
+        }
+
+        fn unpack_pubkey(input: &[u8]) -> Result<(Pubkey, &[u8]), ProgramError> {
+            if input.len() >= 32 {
+                let (key, rest) = input.split_at(32);
+                Ok((Pubkey::new(key), rest))
+            } else {
+                Err(TokenError::InvalidInstruction.into())
+            }
+        }
+
+        // extensions encode a missing key as 32 zero bytes
+        fn unpack_optional_pubkey(input: &[u8]) -> Result<(Option<Pubkey>, &[u8]), ProgramError> {
+            let is_none = input
+                .get(..32)
+                .map_or(false, |key| key.iter().all(|v| *v == 0));
+            let (key, rest) = Self::unpack_pubkey(input)?;
+            Ok((if is_none { None } else { Some(key) }, rest))
+        }
+
+        fn unpack_pubkey_option(input: &[u8]) -> Result<(COption<Pubkey>, &[u8]), ProgramError> {
+            match input.split_first() {
+                Option::Some((&0, rest)) => Ok((COption::None, rest)),
+                Option::Some((&1, rest)) => {
+                    let (key, rest) = Self::unpack_pubkey(rest)?;
+                    Ok((COption::Some(key), rest))
+                }
+                _ => Err(TokenError::InvalidInstruction.into()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::instruction::*;
+    use crate::solana_lib::solana_program::program_option::COption;
+    use crate::solana_lib::solana_program::pubkey::Pubkey;
+    use crate::solana_lib::spl::token::instruction::TokenInstruction;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_unpack_shared_instructions() {
+        let mut data = vec![12];
+        data.extend_from_slice(&1000u64.to_le_bytes());
+        data.push(6);
+        assert_eq!(
+            Token2022Instruction::Token(TokenInstruction::TransferChecked {
+                amount: 1000,
+                decimals: 6
+            }),
+            Token2022Instruction::unpack(&data).unwrap()
+        );
+
+        let mut data = vec![6, 10, 1];
+        data.extend_from_slice(&[7; 32]);
+        assert_eq!(
+            Token2022Instruction::SetAuthority {
+                authority_type: AuthorityType::TransferHookProgramId,
+                new_authority: COption::Some(Pubkey::new(&[7; 32])),
+            },
+            Token2022Instruction::unpack(&data).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_unpack_extensions() {
+        let mut data = vec![36, 0];
+        data.extend_from_slice(&[0; 32]);
+        data.extend_from_slice(&[5; 32]);
+        assert_eq!(
+            Token2022Instruction::TransferHook {
+                authority: None,
+                program_id: Some(Pubkey::new(&[5; 32])),
+                update: false,
+            },
+            Token2022Instruction::unpack(&data).unwrap()
+        );
+
+        let mut data = vec![33, 1];
+        data.extend_from_slice(&(-250i16).to_le_bytes());
+        assert_eq!(
+            Token2022Instruction::InterestBearingMint {
+                rate_authority: None,
+                rate: -250,
+                update: true,
+            },
+            Token2022Instruction::unpack(&data).unwrap()
+        );
+
+        assert_eq!(
+            Token2022Instruction::DefaultAccountState {
+                update: false,
+                state: AccountState::Frozen,
+            },
+            Token2022Instruction::unpack(&[28, 0, 2]).unwrap()
+        );
+
+        let mut data = vec![27, 5];
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.push(9);
+        assert_eq!(
+            Token2022Instruction::ConfidentialTransfer(ConfidentialTransferInstruction::Deposit {
+                amount: 42,
+                decimals: 9
+            }),
+            Token2022Instruction::unpack(&data).unwrap()
+        );
+
+        assert_eq!(
+            Token2022Instruction::Other {
+                name: "InitializeImmutableOwner"
+            },
+            Token2022Instruction::unpack(&[22]).unwrap()
+        );
+        let invalid: Vec<u8> = vec![45];
+        assert!(Token2022Instruction::unpack(&invalid).is_err());
+        assert!(Token2022Instruction::unpack(&[26, 1, 0]).is_err());
+    }
+}
@@ -1,5 +1,8 @@
-use crate::errors::Result;
+use crate::errors::{Result, SolanaError};
 use crate::get_address;
+use crate::offchain_message::OffchainMessage;
+use crate::siws::SignInWithSolana;
+use alloc::format;
 use alloc::string::String;
 
 #[derive(Clone, Debug)]
@@ -7,14 +10,41 @@ pub struct SolanaMessage {
     pub raw_message: String,
     pub utf8_message: String,
     pub from: String,
+    pub offchain: Option<OffchainMessage>,
+    pub sign_in: Option<SignInWithSolana>,
 }
 
 impl SolanaMessage {
-    pub fn from(raw_message: String, utf8_message: String, from: &String) -> Result<Self> {
+    pub fn from(
+        raw_message: String,
+        utf8_message: String,
+        from: &String,
+        offchain: Option<OffchainMessage>,
+    ) -> Result<Self> {
+        let from = get_address(from)?;
+        if let Some(offchain) = &offchain {
+            if !offchain.signers.contains(&from) {
+                return Err(SolanaError::MessageError(format!(
+                    "{} is not a signer of the off-chain message",
+                    from
+                )));
+            }
+        }
+        let sign_in = SignInWithSolana::parse(&utf8_message);
+        if let Some(sign_in) = &sign_in {
+            if sign_in.address != from {
+                return Err(SolanaError::MessageError(format!(
+                    "sign-in requested for {} but the signer is {}",
+                    sign_in.address, from
+                )));
+            }
+        }
         Ok(Self {
             raw_message,
             utf8_message,
-            from: get_address(from)?,
+            from,
+            offchain,
+            sign_in,
         })
     }
 }
@@ -13,6 +13,8 @@ extern crate alloc;
 
 use vendor::{address::TonAddress, wallet::TonWallet};
 
+pub use vendor::wallet::WalletVersion;
+
 pub mod errors;
 mod jettons;
 mod messages;
@@ -23,23 +25,32 @@ mod utils;
 mod vendor;
 
 pub fn ton_public_key_to_address(pk: Vec<u8>) -> Result<String> {
-    TonWallet::derive_default(vendor::wallet::WalletVersion::V4R2, pk)
+    ton_public_key_to_address_by_version(pk, WalletVersion::V4R2)
+}
+
+pub fn ton_public_key_to_address_by_version(pk: Vec<u8>, version: WalletVersion) -> Result<String> {
+    TonWallet::derive_default(version, pk)
         .map(|v| v.address.to_base64_url_flags(true, false))
         .map_err(|e| errors::TonError::AddressError(e.to_string()))
 }
 
+// the requesting wallet may hold funds in any of the supported wallet contracts
 pub fn ton_compare_address_and_public_key(pk: Vec<u8>, address: String) -> bool {
-    match TonWallet::derive_default(vendor::wallet::WalletVersion::V4R2, pk) {
-        Ok(wallet) => match TonAddress::from_str(&address) {
-            Ok(address) => wallet.address.eq(&address),
-            Err(_e) => false,
-        },
-        Err(_e) => false,
-    }
+    ton_get_wallet_version(pk, &address).is_some()
+}
+
+pub fn ton_get_wallet_version(pk: Vec<u8>, address: &str) -> Option<WalletVersion> {
+    let address = TonAddress::from_str(address).ok()?;
+    WalletVersion::ALL.iter().copied().find(|version| {
+        TonWallet::derive_default(*version, pk.clone())
+            .map(|wallet| wallet.address.eq(&address))
+            .unwrap_or(false)
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::string::ToString;
 
     #[test]
     fn test_generate_address() {
@@ -48,4 +59,22 @@ mod tests {
         let address = super::ton_public_key_to_address(pk).unwrap();
         assert_eq!(address, "UQC4FC01K66rElokeYTPeEnWStITQDxGiK8RhkMXpT88FY5b")
     }
+
+    #[test]
+    fn test_generate_address_by_version() {
+        let pk = hex::decode("15556a2d93ab1471eb34e1d6873fc637e6a4b5a9cb2638148c12dc4bac1651f1")
+            .unwrap();
+        let address =
+            super::ton_public_key_to_address_by_version(pk.clone(), super::WalletVersion::V3R2)
+                .unwrap();
+        assert_eq!(address, "UQBVd_xDMsCZnZnZo8BeU87V-YCRBoymZzkeWrepSgK_7TOM");
+        let address =
+            super::ton_public_key_to_address_by_version(pk.clone(), super::WalletVersion::V5R1)
+                .unwrap();
+        assert_eq!(address, "UQCQLKPrxX3AkdsRM1_BLFyg-p1g_y7b6rU792i6QaPWoxal");
+        assert!(super::ton_compare_address_and_public_key(
+            pk,
+            "UQCQLKPrxX3AkdsRM1_BLFyg-p1g_y7b6rU792i6QaPWoxal".to_string()
+        ));
+    }
 }
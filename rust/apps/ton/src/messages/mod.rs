@@ -7,19 +7,31 @@ use serde::Serialize;
 use self::jetton::JettonMessage;
 use self::nft::{NFTMessage, NFT_TRANSFER};
 use self::traits::ParseCell;
-use crate::vendor::cell::{ArcCell, CellBuilder, TonCellError};
+use crate::vendor::cell::{ArcCell, CellBuilder, CellParser, TonCellError};
 use crate::vendor::message::JETTON_TRANSFER;
+use crate::vendor::wallet::WalletVersion;
 
 pub mod jetton;
 pub mod nft;
 pub mod traits;
 
+// W5 signed requests start with an opcode instead of the wallet id
+pub const W5_EXTERNAL_SIGNED: u32 = 0x7369676e;
+pub const W5_INTERNAL_SIGNED: u32 = 0x73696e74;
+pub const ACTION_SEND_MSG: u32 = 0x0ec3c86d;
+pub const V4_SIMPLE_SEND: u8 = 0x00;
+pub const ACTION_ADD_EXTENSION: u8 = 0x02;
+pub const ACTION_REMOVE_EXTENSION: u8 = 0x03;
+pub const ACTION_SET_SIGNATURE_ALLOWED: u8 = 0x04;
+
 #[derive(Debug, Clone, Serialize)]
 pub struct SigningMessage {
+    pub wallet_version: WalletVersion,
     pub wallet_id: Option<u32>,
     pub timeout: u32,
     pub seq_no: u32,
     pub messages: Vec<TransferMessage>,
+    pub extended_actions: Vec<ExtendedAction>,
 }
 
 impl ParseCell for SigningMessage {
@@ -28,23 +40,133 @@ impl ParseCell for SigningMessage {
         Self: Sized,
     {
         cell.parse(|parser| {
-            let wallet_id = parser.load_u32(32).ok();
+            let prefix = parser.load_u32(32)?;
+            if prefix == W5_EXTERNAL_SIGNED || prefix == W5_INTERNAL_SIGNED {
+                return Self::parse_v5(cell, parser);
+            }
+            let wallet_id = Some(prefix);
             let timeout = parser.load_u32(32)?;
             let seq_no = parser.load_u32(32)?;
-            let _order = parser.load_u8(8).unwrap();
-            let _send_mode = parser.load_u8(8).unwrap();
-            let messages: Result<Vec<TransferMessage>, TonCellError> =
-                cell.references.iter().map(TransferMessage::parse).collect();
+            // every message is preceded by its send mode, V4 adds an op before them
+            let message_count = cell.references.len();
+            let wallet_version = if parser.remaining_bits() == 8 * message_count {
+                WalletVersion::V3R2
+            } else if parser.remaining_bits() == 8 * (message_count + 1) {
+                // plugin deployment and removal ops are not simple transfers
+                let op = parser.load_u8(8)?;
+                if op != V4_SIMPLE_SEND {
+                    return Err(TonCellError::CellParserError(format!(
+                        "unsupported wallet v4 op {:x}",
+                        op
+                    )));
+                }
+                WalletVersion::V4R2
+            } else {
+                return Err(TonCellError::CellParserError(
+                    "unsupported signing message layout".to_string(),
+                ));
+            };
+            let messages = cell
+                .references
+                .iter()
+                .map(|message| {
+                    let _send_mode = parser.load_u8(8)?;
+                    TransferMessage::parse(message)
+                })
+                .collect::<Result<Vec<TransferMessage>, TonCellError>>()?;
             Ok(Self {
+                wallet_version,
                 wallet_id,
                 timeout,
                 seq_no,
-                messages: messages?,
+                messages,
+                extended_actions: Vec::new(),
             })
         })
     }
 }
 
+impl SigningMessage {
+    fn parse_v5(cell: &ArcCell, parser: &mut CellParser) -> Result<Self, TonCellError> {
+        let wallet_id = Some(parser.load_u32(32)?);
+        let timeout = parser.load_u32(32)?;
+        let seq_no = parser.load_u32(32)?;
+        let mut ref_index = 0;
+        let messages = if parser.load_bit()? {
+            ref_index += 1;
+            parse_out_list(cell.reference(0)?)?
+        } else {
+            Vec::new()
+        };
+        let mut extended_actions = Vec::new();
+        if parser.load_bit()? {
+            // the first extended action is stored inline, the following ones are chained by reference
+            extended_actions.push(ExtendedAction::load(parser)?);
+            let mut next = cell.reference(ref_index).ok();
+            while let Some(action) = next {
+                extended_actions.push(action.parse(ExtendedAction::load)?);
+                next = action.reference(0).ok();
+            }
+        }
+        Ok(Self {
+            wallet_version: WalletVersion::V5R1,
+            wallet_id,
+            timeout,
+            seq_no,
+            messages,
+            extended_actions,
+        })
+    }
+}
+
+// the out list is stored last action first, return the actions in execution order
+fn parse_out_list(cell: &ArcCell) -> Result<Vec<TransferMessage>, TonCellError> {
+    let mut messages = Vec::new();
+    let mut current = cell;
+    while current.bit_len() > 0 || !current.references.is_empty() {
+        let message = current.parse(|parser| {
+            let tag = parser.load_u32(32)?;
+            if tag != ACTION_SEND_MSG {
+                return Err(TonCellError::CellParserError(format!(
+                    "unsupported out action {:x}",
+                    tag
+                )));
+            }
+            let _send_mode = parser.load_u8(8)?;
+            TransferMessage::parse(current.reference(1)?)
+        })?;
+        messages.push(message);
+        current = current.reference(0)?;
+    }
+    messages.reverse();
+    Ok(messages)
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub enum ExtendedAction {
+    AddExtension(String),
+    RemoveExtension(String),
+    SetSignatureAllowed(bool),
+}
+
+impl ExtendedAction {
+    fn load(parser: &mut CellParser) -> Result<Self, TonCellError> {
+        match parser.load_u8(8)? {
+            ACTION_ADD_EXTENSION => Ok(Self::AddExtension(
+                parser.load_address()?.to_base64_url_flags(true, false),
+            )),
+            ACTION_REMOVE_EXTENSION => Ok(Self::RemoveExtension(
+                parser.load_address()?.to_base64_url_flags(true, false),
+            )),
+            ACTION_SET_SIGNATURE_ALLOWED => Ok(Self::SetSignatureAllowed(parser.load_bit()?)),
+            tag => Err(TonCellError::CellParserError(format!(
+                "unsupported extended action {:x}",
+                tag
+            ))),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct TransferMessage {
     pub ihr_disabled: bool,
@@ -3,11 +3,12 @@ use crate::jettons;
 use crate::messages::jetton::JettonMessage;
 use crate::messages::nft::NFTMessage;
 use crate::messages::traits::ParseCell;
-use crate::messages::{Operation, SigningMessage};
+use crate::messages::{ExtendedAction, Operation, SigningMessage, TransferMessage};
 use crate::utils::shorten_string;
 use crate::vendor::address::TonAddress;
 use crate::vendor::cell::BagOfCells;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
 use hex;
 use serde::Serialize;
@@ -22,6 +23,18 @@ pub struct TonTransaction {
     pub data_view: Option<String>,
     pub raw_data: String,
     pub contract_data: Option<String>,
+    pub wallet_version: String,
+    pub actions: Vec<TonAction>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct TonAction {
+    pub to: String,
+    pub amount: String,
+    pub action: String,
+    pub comment: Option<String>,
+    pub data_view: Option<String>,
+    pub contract_data: Option<String>,
 }
 
 impl TonTransaction {
@@ -49,12 +62,63 @@ impl TryFrom<&SigningMessage> for TonTransaction {
     type Error = TonError;
 
     fn try_from(signing_message: &SigningMessage) -> Result<Self> {
-        if signing_message.messages.first().is_none() {
-            return Err(TonError::InvalidTransaction(
+        let mut actions = signing_message
+            .messages
+            .iter()
+            .map(TonAction::try_from)
+            .collect::<Result<Vec<TonAction>>>()?;
+        actions.extend(signing_message.extended_actions.iter().map(TonAction::from));
+        // the overview shows the first action, every action is listed in actions
+        let first = actions
+            .first()
+            .cloned()
+            .ok_or(TonError::InvalidTransaction(
                 "transaction does not contain transfer info".to_string(),
-            ));
-        };
-        let message = signing_message.messages[0].clone();
+            ))?;
+        Ok(Self {
+            to: first.to,
+            amount: first.amount,
+            action: first.action,
+            comment: first.comment,
+            data_view: first.data_view,
+            raw_data: "".to_string(),
+            contract_data: first.contract_data,
+            wallet_version: signing_message.wallet_version.name().to_string(),
+            actions,
+        })
+    }
+}
+
+impl From<&ExtendedAction> for TonAction {
+    fn from(action: &ExtendedAction) -> Self {
+        match action {
+            ExtendedAction::AddExtension(address) => Self {
+                to: address.to_string(),
+                action: "Add Extension".to_string(),
+                ..Default::default()
+            },
+            ExtendedAction::RemoveExtension(address) => Self {
+                to: address.to_string(),
+                action: "Remove Extension".to_string(),
+                ..Default::default()
+            },
+            ExtendedAction::SetSignatureAllowed(allowed) => Self {
+                action: if *allowed {
+                    "Enable Signature Auth".to_string()
+                } else {
+                    "Disable Signature Auth".to_string()
+                },
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl TryFrom<&TransferMessage> for TonAction {
+    type Error = TonError;
+
+    fn try_from(message: &TransferMessage) -> Result<Self> {
+        let message = message.clone();
         let to = message.dest_addr.clone();
         let amount = message.value.clone();
         match message.data {
@@ -1,15 +1,32 @@
-use crate::errors::Result;
+use crate::errors::{Result, TonError};
 use crate::structs::{TonProof, TonTransaction};
 use crate::utils::sha256;
 use crate::vendor::cell::BagOfCells;
-use alloc::vec;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use alloc::{format, vec};
 use cryptoxide::ed25519;
 
 pub fn parse_transaction(serial: &[u8]) -> Result<TonTransaction> {
     TonTransaction::parse_hex(serial)
 }
 
+// the signing message layout must belong to the wallet contract of the requesting address
+pub fn check_wallet_version(serial: &[u8], pk: Vec<u8>, address: String) -> Result<()> {
+    let version = crate::ton_get_wallet_version(pk, &address).ok_or(
+        TonError::InvalidTransaction("address does not match the public key".to_string()),
+    )?;
+    let tx = parse_transaction(serial)?;
+    if tx.wallet_version != version.name() {
+        return Err(TonError::InvalidTransaction(format!(
+            "{} signing message for a {} wallet",
+            tx.wallet_version,
+            version.name()
+        )));
+    }
+    Ok(())
+}
+
 pub fn buffer_to_sign(serial: &[u8]) -> Result<Vec<u8>> {
     let boc = BagOfCells::parse(serial)?;
     let root = boc.single_root()?;
@@ -45,6 +62,7 @@ pub fn sign_proof(serial: &[u8], sk: [u8; 32]) -> Result<[u8; 64]> {
 #[cfg(test)]
 mod tests {
     extern crate std;
+    use alloc::string::ToString;
     use base64::{engine::general_purpose::STANDARD, Engine};
     use hex;
     use std::println;
@@ -52,7 +70,7 @@ mod tests {
 
     use crate::{transaction::parse_transaction, vendor::cell::BagOfCells};
 
-    use super::sign_proof;
+    use super::{check_wallet_version, sign_proof};
 
     #[test]
     fn test_parse_transaction() {
@@ -133,6 +151,72 @@ mod tests {
         println!("{:?}", tx);
     }
 
+    #[test]
+    fn test_parse_ton_v5_transfer() {
+        let serial = hex::decode("b5ee9c724101070100a00001217369676e7fffff116657574000000005a001020a0ec3c86d030206020a0ec3c86d030304000001684200562ce65e380bfb69d1a74f1fa21bbbde7ec0cfe6237ca72627714894a09a9b45a02faf0800000000000000000000000000010500120000000048656c6c6f0068420016a46586486116291ca794bd99cc8617b5dfb6212bc0431399cb0fd22bf80a7624a817c80000000000000000000000000000a3ae1562").unwrap();
+        let tx = parse_transaction(&serial).unwrap();
+        assert_eq!("W5", tx.wallet_version);
+        assert_eq!(2, tx.actions.len());
+        // actions are listed in execution order
+        assert_eq!(
+            "UQCsWcy8cBf206NOnj9EN3e8_YGfzEb5TkxO4pEpQTU2ix9w",
+            tx.actions[0].to
+        );
+        assert_eq!("0.1 Ton", tx.actions[0].amount);
+        assert_eq!(Some("Hello".to_string()), tx.actions[0].comment);
+        assert_eq!(
+            "UQAtSMsMkMIsUjlPKXszmQwva79sQleAhiczlh-kV_AU7K6j",
+            tx.actions[1].to
+        );
+        assert_eq!("2.5 Ton", tx.actions[1].amount);
+        assert_eq!(tx.actions[0].to, tx.to);
+        assert_eq!("Ton Transfer", tx.action);
+    }
+
+    #[test]
+    fn test_check_wallet_version() {
+        let serial = hex::decode("b5ee9c724101070100a00001217369676e7fffff116657574000000005a001020a0ec3c86d030206020a0ec3c86d030304000001684200562ce65e380bfb69d1a74f1fa21bbbde7ec0cfe6237ca72627714894a09a9b45a02faf0800000000000000000000000000010500120000000048656c6c6f0068420016a46586486116291ca794bd99cc8617b5dfb6212bc0431399cb0fd22bf80a7624a817c80000000000000000000000000000a3ae1562").unwrap();
+        let pk = hex::decode("15556a2d93ab1471eb34e1d6873fc637e6a4b5a9cb2638148c12dc4bac1651f1")
+            .unwrap();
+        // W5 signing message requested by the W5 wallet of the key
+        assert!(check_wallet_version(
+            &serial,
+            pk.clone(),
+            "UQCQLKPrxX3AkdsRM1_BLFyg-p1g_y7b6rU792i6QaPWoxal".to_string()
+        )
+        .is_ok());
+        // the same message requested by the V4R2 wallet of the key
+        assert!(check_wallet_version(
+            &serial,
+            pk,
+            "UQC4FC01K66rElokeYTPeEnWStITQDxGiK8RhkMXpT88FY5b".to_string()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_ton_v5_extended_action() {
+        let serial = hex::decode("b5ee9c724101010100350000657369676e7fffff11665757400000000640a0016a46586486116291ca794bd99cc8617b5dfb6212bc0431399cb0fd22bf80a7643fce01e6").unwrap();
+        let tx = parse_transaction(&serial).unwrap();
+        assert_eq!("W5", tx.wallet_version);
+        assert_eq!(1, tx.actions.len());
+        assert_eq!("Add Extension", tx.action);
+        assert_eq!("UQAtSMsMkMIsUjlPKXszmQwva79sQleAhiczlh-kV_AU7K6j", tx.to);
+    }
+
+    #[test]
+    fn test_parse_ton_v3_transfer() {
+        let serial = hex::decode("b5ee9c7241010201004600011a29a9a317665757400000000703010068420016a46586486116291ca794bd99cc8617b5dfb6212bc0431399cb0fd22bf80a7624a817c80000000000000000000000000000ba551252").unwrap();
+        let tx = parse_transaction(&serial).unwrap();
+        assert_eq!("V3R2", tx.wallet_version);
+        assert_eq!(1, tx.actions.len());
+        assert_eq!("2.5 Ton", tx.amount);
+
+        let body = "te6cckEBAgEARwABHCmpoxdmOz6lAAAACAADAQBoQgArFnMvHAX9tOjTp4/RDd3vP2Bn8xG+U5MTuKRKUE1NoqHc1lAAAAAAAAAAAAAAAAAAAHBy4G8=";
+        let tx = parse_transaction(&STANDARD.decode(body).unwrap()).unwrap();
+        assert_eq!("V4R2", tx.wallet_version);
+    }
+
     #[test]
     fn test_sign_ton_proof() {
         let serial = hex::decode("746f6e2d70726f6f662d6974656d2d76322f00000000b5232c324308b148e53ca5ecce6430bdaefdb1095e02189cce587e915fc053b015000000746b6170702e746f6e706f6b65722e6f6e6c696e65142b5866000000003735323061653632393534653666666330303030303030303636353765333639").unwrap();
@@ -20,6 +20,7 @@ mod utils;
 
 pub use crate::address::get_address;
 pub use crate::transaction::parser::{DetailTx, OverviewTx, ParsedTx, TxParser};
+pub use crate::transaction::stake::TronVote;
 use crate::transaction::wrapped_tron::WrappedTron;
 use app_utils::keystone;
 use transaction::checker::TxChecker;
@@ -44,6 +45,39 @@ pub fn check_raw_tx(raw_tx: protoc::Payload, context: keystone::ParseContext) ->
     tx_data.check(&context)
 }
 
+// raw_data is the serialized `Transaction.raw_data` protobuf built by a third party wallet
+pub fn sign_raw_data(
+    raw_data: &[u8],
+    hd_path: String,
+    xfp: String,
+    context: keystone::ParseContext,
+    seed: &[u8],
+) -> Result<(String, String)> {
+    let tx = WrappedTron::from_raw_data(raw_data, hd_path, xfp, &context)?;
+    tx.check(&context)?;
+    tx.sign(seed)
+}
+
+pub fn parse_raw_data(
+    raw_data: &[u8],
+    hd_path: String,
+    xfp: String,
+    context: keystone::ParseContext,
+) -> Result<ParsedTx> {
+    let tx_data = WrappedTron::from_raw_data(raw_data, hd_path, xfp, &context)?;
+    tx_data.parse()
+}
+
+pub fn check_raw_data(
+    raw_data: &[u8],
+    hd_path: String,
+    xfp: String,
+    context: keystone::ParseContext,
+) -> Result<()> {
+    let tx_data = WrappedTron::from_raw_data(raw_data, hd_path, xfp, &context)?;
+    tx_data.check(&context)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -64,4 +98,115 @@ mod test {
         let base: Base = parse_protobuf(unzip_data).unwrap();
         base.data.unwrap()
     }
+
+    #[test]
+    fn test_sign_raw_data() {
+        // raw_data of https://tronscan.org/#/transaction/0f89c6365796afa96ae05fab57207a4d8c5cc801b92ac2099c7dd9dcd5a91df0
+        let raw_data = hex::decode("0a02ea0522082c84cb547bae782640b8f3c28bfc305a65080112610a2d747970652e676f6f676c65617069732e636f6d2f70726f746f636f6c2e5472616e73666572436f6e747261637412300a1541bf90ce9c87c42a58699b1a2e3f65e88181436d53121541b0ca665130d03c977823834e3fcbe63af2421fa11801").unwrap();
+        let pubkey_str = "xpub6C3ndD75jvoARyqUBTvrsMZaprs2ZRF84kRTt5r9oxKQXn5oFChRRgrP2J8QhykhKACBLF2HxwAh4wccFqFsuJUBBcwyvkyqfzJU5gfn5pY";
+        let hd_path = "m/44'/195'/0'/0/0".to_string();
+        let xfp = "73c5da0a".to_string();
+
+        let parsed_tx = parse_raw_data(
+            &raw_data,
+            hd_path.clone(),
+            xfp.clone(),
+            prepare_parse_context(pubkey_str),
+        )
+        .unwrap();
+        assert_eq!("TRX Transfer", parsed_tx.overview.method);
+        assert_eq!("0.000001 TRX", parsed_tx.overview.value);
+        assert_eq!(
+            "TTS7Y53sS4rzrDCtZaiuRzqxd16atCe2UR",
+            parsed_tx.overview.from
+        );
+        assert_eq!("TS5zPoC4XEBmHvDNAnnW2gH3MQhcRN6iRm", parsed_tx.overview.to);
+        assert_eq!(
+            Ok(()),
+            check_raw_data(
+                &raw_data,
+                hd_path.clone(),
+                xfp.clone(),
+                prepare_parse_context(pubkey_str)
+            )
+        );
+
+        let seed = hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+        let (raw_hex, tx_id) = sign_raw_data(
+            &raw_data,
+            hd_path,
+            xfp,
+            prepare_parse_context(pubkey_str),
+            &seed,
+        )
+        .unwrap();
+        assert_eq!("0a7c0a02ea0522082c84cb547bae782640b8f3c28bfc305a65080112610a2d747970652e676f6f676c65617069732e636f6d2f70726f746f636f6c2e5472616e73666572436f6e747261637412300a1541bf90ce9c87c42a58699b1a2e3f65e88181436d53121541b0ca665130d03c977823834e3fcbe63af2421fa118011241fe599fc2386cabae4f7a9478d4ab8116513cb849644b997521f9192e4be74fec7efa3449c806bf0307e53d8ad659bf9d18795c87a059acecbce28de58c43631e01", raw_hex);
+        assert_eq!(
+            "0f89c6365796afa96ae05fab57207a4d8c5cc801b92ac2099c7dd9dcd5a91df0",
+            tx_id
+        );
+    }
+
+    #[test]
+    fn test_parse_raw_data_trc20() {
+        let raw_data = hex::decode("0a02e8b32208b0465d7759adf6d640c881858bfc305aae01081f12a9010a31747970652e676f6f676c65617069732e636f6d2f70726f746f636f6c2e54726967676572536d617274436f6e747261637412740a1541bf90ce9c87c42a58699b1a2e3f65e88181436d53121541a614f803b6fd780986a42c78ec9c7f77e6ded13c2244a9059cbb000000000000000000000000b0ca665130d03c977823834e3fcbe63af2421fa100000000000000000000000000000000000000000000000000000000000007c37088f4cd89fc3090018094ebdc03").unwrap();
+        let pubkey_str = "xpub6C3ndD75jvoARyqUBTvrsMZaprs2ZRF84kRTt5r9oxKQXn5oFChRRgrP2J8QhykhKACBLF2HxwAh4wccFqFsuJUBBcwyvkyqfzJU5gfn5pY";
+        let parsed_tx = parse_raw_data(
+            &raw_data,
+            "m/44'/195'/0'/0/0".to_string(),
+            "73c5da0a".to_string(),
+            prepare_parse_context(pubkey_str),
+        )
+        .unwrap();
+        assert_eq!("TRC-20 Transfer", parsed_tx.detail.method);
+        assert_eq!("1987 TRC-20", parsed_tx.detail.value);
+        assert_eq!("TS5zPoC4XEBmHvDNAnnW2gH3MQhcRN6iRm", parsed_tx.detail.to);
+        assert_eq!(
+            "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t",
+            parsed_tx.detail.contract_address
+        );
+        assert_eq!(Some("1000 TRX".to_string()), parsed_tx.detail.fee_limit);
+
+        // an unknown field appended to raw_data would not be covered by what is displayed
+        let mut raw_data = raw_data;
+        raw_data.extend_from_slice(&[0xf8, 0x01, 0x01]);
+        assert!(parse_raw_data(
+            &raw_data,
+            "m/44'/195'/0'/0/0".to_string(),
+            "73c5da0a".to_string(),
+            prepare_parse_context(pubkey_str),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_raw_data_trc20_with_trc10_value() {
+        use crate::pb::protocol::{transaction, TriggerSmartContract};
+        use prost::Message;
+
+        let raw_data = hex::decode("0a02e8b32208b0465d7759adf6d640c881858bfc305aae01081f12a9010a31747970652e676f6f676c65617069732e636f6d2f70726f746f636f6c2e54726967676572536d617274436f6e747261637412740a1541bf90ce9c87c42a58699b1a2e3f65e88181436d53121541a614f803b6fd780986a42c78ec9c7f77e6ded13c2244a9059cbb000000000000000000000000b0ca665130d03c977823834e3fcbe63af2421fa100000000000000000000000000000000000000000000000000000000000007c37088f4cd89fc3090018094ebdc03").unwrap();
+        let pubkey_str = "xpub6C3ndD75jvoARyqUBTvrsMZaprs2ZRF84kRTt5r9oxKQXn5oFChRRgrP2J8QhykhKACBLF2HxwAh4wccFqFsuJUBBcwyvkyqfzJU5gfn5pY";
+        let with_contract = |update: &dyn Fn(&mut TriggerSmartContract)| {
+            let mut raw = transaction::Raw::decode(raw_data.as_slice()).unwrap();
+            let parameter = raw.contract[0].parameter.as_mut().unwrap();
+            let mut contract = TriggerSmartContract::decode(parameter.value.as_slice()).unwrap();
+            update(&mut contract);
+            parameter.value = contract.encode_to_vec();
+            raw.encode_to_vec()
+        };
+
+        // a TRC-10 token value attached to the call is not displayed
+        for raw_data in [
+            with_contract(&|c| c.call_token_value = 1),
+            with_contract(&|c| c.token_id = 1000001),
+        ] {
+            assert!(parse_raw_data(
+                &raw_data,
+                "m/44'/195'/0'/0/0".to_string(),
+                "73c5da0a".to_string(),
+                prepare_parse_context(pubkey_str),
+            )
+            .is_err());
+        }
+    }
 }
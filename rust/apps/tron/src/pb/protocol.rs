@@ -50,7 +50,13 @@ pub mod transaction {
             AccountCreateContract = 0,
             TransferContract = 1,
             TransferAssetContract = 2,
+            VoteWitnessContract = 4,
+            WithdrawBalanceContract = 13,
             TriggerSmartContract = 31,
+            FreezeBalanceV2Contract = 54,
+            UnfreezeBalanceV2Contract = 55,
+            DelegateResourceContract = 57,
+            UnDelegateResourceContract = 58,
         }
         impl ContractType {
             /// String value of the enum field names used in the ProtoBuf definition.
@@ -62,7 +68,13 @@ pub mod transaction {
                     ContractType::AccountCreateContract => "AccountCreateContract",
                     ContractType::TransferContract => "TransferContract",
                     ContractType::TransferAssetContract => "TransferAssetContract",
+                    ContractType::VoteWitnessContract => "VoteWitnessContract",
+                    ContractType::WithdrawBalanceContract => "WithdrawBalanceContract",
                     ContractType::TriggerSmartContract => "TriggerSmartContract",
+                    ContractType::FreezeBalanceV2Contract => "FreezeBalanceV2Contract",
+                    ContractType::UnfreezeBalanceV2Contract => "UnfreezeBalanceV2Contract",
+                    ContractType::DelegateResourceContract => "DelegateResourceContract",
+                    ContractType::UnDelegateResourceContract => "UnDelegateResourceContract",
                 }
             }
             /// Creates an enum from field names used in the ProtoBuf definition.
@@ -71,7 +83,13 @@ pub mod transaction {
                     "AccountCreateContract" => Some(Self::AccountCreateContract),
                     "TransferContract" => Some(Self::TransferContract),
                     "TransferAssetContract" => Some(Self::TransferAssetContract),
+                    "VoteWitnessContract" => Some(Self::VoteWitnessContract),
+                    "WithdrawBalanceContract" => Some(Self::WithdrawBalanceContract),
                     "TriggerSmartContract" => Some(Self::TriggerSmartContract),
+                    "FreezeBalanceV2Contract" => Some(Self::FreezeBalanceV2Contract),
+                    "UnfreezeBalanceV2Contract" => Some(Self::UnfreezeBalanceV2Contract),
+                    "DelegateResourceContract" => Some(Self::DelegateResourceContract),
+                    "UnDelegateResourceContract" => Some(Self::UnDelegateResourceContract),
                     _ => None,
                 }
             }
@@ -144,3 +162,107 @@ pub struct TriggerSmartContract {
     #[prost(int64, tag = "6")]
     pub token_id: i64,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VoteWitnessContract {
+    #[prost(bytes = "vec", tag = "1")]
+    pub owner_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, repeated, tag = "2")]
+    pub votes: ::prost::alloc::vec::Vec<vote_witness_contract::Vote>,
+    #[prost(bool, tag = "3")]
+    pub support: bool,
+}
+/// Nested message and enum types in `VoteWitnessContract`.
+pub mod vote_witness_contract {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Vote {
+        #[prost(bytes = "vec", tag = "1")]
+        pub vote_address: ::prost::alloc::vec::Vec<u8>,
+        #[prost(int64, tag = "2")]
+        pub vote_count: i64,
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WithdrawBalanceContract {
+    #[prost(bytes = "vec", tag = "1")]
+    pub owner_address: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FreezeBalanceV2Contract {
+    #[prost(bytes = "vec", tag = "1")]
+    pub owner_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(int64, tag = "2")]
+    pub frozen_balance: i64,
+    #[prost(enumeration = "ResourceCode", tag = "3")]
+    pub resource: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnfreezeBalanceV2Contract {
+    #[prost(bytes = "vec", tag = "1")]
+    pub owner_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(int64, tag = "2")]
+    pub unfreeze_balance: i64,
+    #[prost(enumeration = "ResourceCode", tag = "3")]
+    pub resource: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DelegateResourceContract {
+    #[prost(bytes = "vec", tag = "1")]
+    pub owner_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration = "ResourceCode", tag = "2")]
+    pub resource: i32,
+    #[prost(int64, tag = "3")]
+    pub balance: i64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub receiver_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bool, tag = "5")]
+    pub lock: bool,
+    #[prost(int64, tag = "6")]
+    pub lock_period: i64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnDelegateResourceContract {
+    #[prost(bytes = "vec", tag = "1")]
+    pub owner_address: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration = "ResourceCode", tag = "2")]
+    pub resource: i32,
+    #[prost(int64, tag = "3")]
+    pub balance: i64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub receiver_address: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ResourceCode {
+    Bandwidth = 0,
+    Energy = 1,
+    TronPower = 2,
+}
+impl ResourceCode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ResourceCode::Bandwidth => "BANDWIDTH",
+            ResourceCode::Energy => "ENERGY",
+            ResourceCode::TronPower => "TRON_POWER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "BANDWIDTH" => Some(Self::Bandwidth),
+            "ENERGY" => Some(Self::Energy),
+            "TRON_POWER" => Some(Self::TronPower),
+            _ => None,
+        }
+    }
+}
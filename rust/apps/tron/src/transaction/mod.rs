@@ -1,4 +1,5 @@
 pub mod checker;
 pub mod parser;
 pub mod signer;
+pub mod stake;
 pub mod wrapped_tron;
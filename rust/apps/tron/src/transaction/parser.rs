@@ -1,6 +1,8 @@
 use crate::errors::Result;
+use crate::transaction::stake::TronVote;
 use crate::transaction::wrapped_tron::{WrappedTron, NETWORK};
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct ParsedTx {
@@ -15,6 +17,7 @@ pub struct OverviewTx {
     pub from: String,
     pub to: String,
     pub network: String,
+    pub resource: Option<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -26,6 +29,10 @@ pub struct DetailTx {
     pub network: String,
     pub token: String,
     pub contract_address: String,
+    pub resource: Option<String>,
+    pub lock_period: Option<String>,
+    pub votes: Vec<TronVote>,
+    pub fee_limit: Option<String>,
 }
 
 pub trait TxParser {
@@ -40,6 +47,7 @@ impl TxParser for WrappedTron {
             from: self.from.to_string(),
             to: self.to.to_string(),
             network: NETWORK.to_string(),
+            resource: self.stake.as_ref().and_then(|s| s.get_resource()),
         };
         let detail = DetailTx {
             value: self.format_amount()?,
@@ -49,6 +57,18 @@ impl TxParser for WrappedTron {
             network: NETWORK.to_string(),
             contract_address: self.contract_address.to_string(),
             token: self.token.to_string(),
+            resource: self.stake.as_ref().and_then(|s| s.get_resource()),
+            lock_period: self
+                .stake
+                .as_ref()
+                .and_then(|s| s.get_lock_period())
+                .map(|p| p.to_string()),
+            votes: self
+                .stake
+                .as_ref()
+                .map(|s| s.get_votes())
+                .unwrap_or_default(),
+            fee_limit: self.format_fee_limit(),
         };
         Ok(ParsedTx { overview, detail })
     }
@@ -0,0 +1,367 @@
+use crate::errors::{Result, TronError};
+use crate::pb::protocol::transaction::contract::ContractType;
+use crate::pb::protocol::transaction::Contract;
+use crate::pb::protocol::{
+    DelegateResourceContract, FreezeBalanceV2Contract, ResourceCode, UnDelegateResourceContract,
+    UnfreezeBalanceV2Contract, VoteWitnessContract, WithdrawBalanceContract,
+};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use bitcoin::base58;
+use prost::Message;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TronVote {
+    pub address: String,
+    pub count: String,
+}
+
+// Stake 2.0, voting and resource delegation contracts, amounts are in sun
+#[derive(Debug, Clone)]
+pub enum StakeContract {
+    FreezeBalanceV2 {
+        owner: String,
+        amount: i64,
+        resource: String,
+    },
+    UnfreezeBalanceV2 {
+        owner: String,
+        amount: i64,
+        resource: String,
+    },
+    DelegateResource {
+        owner: String,
+        receiver: String,
+        amount: i64,
+        resource: String,
+        lock_period: Option<i64>,
+    },
+    UnDelegateResource {
+        owner: String,
+        receiver: String,
+        amount: i64,
+        resource: String,
+    },
+    VoteWitness {
+        owner: String,
+        votes: Vec<TronVote>,
+    },
+    WithdrawBalance {
+        owner: String,
+    },
+}
+
+impl StakeContract {
+    // returns None for contracts that are not staking related
+    pub fn from_contract(contract: &Contract) -> Result<Option<Self>> {
+        let contract_type = match ContractType::from_i32(contract.r#type) {
+            Some(contract_type) => contract_type,
+            None => return Ok(None),
+        };
+        let parameter = contract
+            .parameter
+            .as_ref()
+            .map(|p| p.value.as_slice())
+            .unwrap_or_default();
+        let stake = match contract_type {
+            ContractType::FreezeBalanceV2Contract => {
+                let c = FreezeBalanceV2Contract::decode(parameter).map_err(protobuf_error)?;
+                StakeContract::FreezeBalanceV2 {
+                    owner: encode_address(&c.owner_address)?,
+                    amount: c.frozen_balance,
+                    resource: resource_name(c.resource)?,
+                }
+            }
+            ContractType::UnfreezeBalanceV2Contract => {
+                let c = UnfreezeBalanceV2Contract::decode(parameter).map_err(protobuf_error)?;
+                StakeContract::UnfreezeBalanceV2 {
+                    owner: encode_address(&c.owner_address)?,
+                    amount: c.unfreeze_balance,
+                    resource: resource_name(c.resource)?,
+                }
+            }
+            ContractType::DelegateResourceContract => {
+                let c = DelegateResourceContract::decode(parameter).map_err(protobuf_error)?;
+                StakeContract::DelegateResource {
+                    owner: encode_address(&c.owner_address)?,
+                    receiver: encode_address(&c.receiver_address)?,
+                    amount: c.balance,
+                    resource: resource_name(c.resource)?,
+                    lock_period: c.lock.then_some(c.lock_period),
+                }
+            }
+            ContractType::UnDelegateResourceContract => {
+                let c = UnDelegateResourceContract::decode(parameter).map_err(protobuf_error)?;
+                StakeContract::UnDelegateResource {
+                    owner: encode_address(&c.owner_address)?,
+                    receiver: encode_address(&c.receiver_address)?,
+                    amount: c.balance,
+                    resource: resource_name(c.resource)?,
+                }
+            }
+            ContractType::VoteWitnessContract => {
+                let c = VoteWitnessContract::decode(parameter).map_err(protobuf_error)?;
+                let votes = c
+                    .votes
+                    .iter()
+                    .map(|vote| {
+                        // the chain rejects non-positive counts, never show them as a vote
+                        if vote.vote_count <= 0 {
+                            return Err(TronError::InvalidRawTxCryptoBytes(format!(
+                                "invalid vote count {}",
+                                vote.vote_count
+                            )));
+                        }
+                        Ok(TronVote {
+                            address: encode_address(&vote.vote_address)?,
+                            count: vote.vote_count.to_string(),
+                        })
+                    })
+                    .collect::<Result<Vec<TronVote>>>()?;
+                StakeContract::VoteWitness {
+                    owner: encode_address(&c.owner_address)?,
+                    votes,
+                }
+            }
+            ContractType::WithdrawBalanceContract => {
+                let c = WithdrawBalanceContract::decode(parameter).map_err(protobuf_error)?;
+                StakeContract::WithdrawBalance {
+                    owner: encode_address(&c.owner_address)?,
+                }
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(stake))
+    }
+
+    pub fn get_method(&self) -> String {
+        match self {
+            StakeContract::FreezeBalanceV2 { .. } => "Stake 2.0 Freeze".to_string(),
+            StakeContract::UnfreezeBalanceV2 { .. } => "Stake 2.0 Unfreeze".to_string(),
+            StakeContract::DelegateResource { .. } => "Delegate Resource".to_string(),
+            StakeContract::UnDelegateResource { .. } => "Undelegate Resource".to_string(),
+            StakeContract::VoteWitness { .. } => "Vote Witness".to_string(),
+            StakeContract::WithdrawBalance { .. } => "Withdraw Rewards".to_string(),
+        }
+    }
+
+    pub fn get_owner(&self) -> String {
+        match self {
+            StakeContract::FreezeBalanceV2 { owner, .. }
+            | StakeContract::UnfreezeBalanceV2 { owner, .. }
+            | StakeContract::DelegateResource { owner, .. }
+            | StakeContract::UnDelegateResource { owner, .. }
+            | StakeContract::VoteWitness { owner, .. }
+            | StakeContract::WithdrawBalance { owner } => owner.to_string(),
+        }
+    }
+
+    pub fn get_receiver(&self) -> Option<String> {
+        match self {
+            StakeContract::DelegateResource { receiver, .. }
+            | StakeContract::UnDelegateResource { receiver, .. } => Some(receiver.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn get_amount(&self) -> Option<i64> {
+        match self {
+            StakeContract::FreezeBalanceV2 { amount, .. }
+            | StakeContract::UnfreezeBalanceV2 { amount, .. }
+            | StakeContract::DelegateResource { amount, .. }
+            | StakeContract::UnDelegateResource { amount, .. } => Some(*amount),
+            _ => None,
+        }
+    }
+
+    pub fn get_resource(&self) -> Option<String> {
+        match self {
+            StakeContract::FreezeBalanceV2 { resource, .. }
+            | StakeContract::UnfreezeBalanceV2 { resource, .. }
+            | StakeContract::DelegateResource { resource, .. }
+            | StakeContract::UnDelegateResource { resource, .. } => Some(resource.to_string()),
+            _ => None,
+        }
+    }
+
+    // locked delegations can not be reclaimed before the lock period (in blocks) ends
+    pub fn get_lock_period(&self) -> Option<i64> {
+        match self {
+            StakeContract::DelegateResource { lock_period, .. } => *lock_period,
+            _ => None,
+        }
+    }
+
+    pub fn get_votes(&self) -> Vec<TronVote> {
+        match self {
+            StakeContract::VoteWitness { votes, .. } => votes.to_vec(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn resource_name(code: i32) -> Result<String> {
+    match ResourceCode::from_i32(code) {
+        Some(ResourceCode::Bandwidth) => Ok("Bandwidth".to_string()),
+        Some(ResourceCode::Energy) => Ok("Energy".to_string()),
+        Some(ResourceCode::TronPower) => Ok("TRON Power".to_string()),
+        None => Err(TronError::InvalidRawTxCryptoBytes(format!(
+            "invalid resource code {}",
+            code
+        ))),
+    }
+}
+
+pub(crate) fn encode_address(address: &[u8]) -> Result<String> {
+    if address.len() != 21 {
+        return Err(TronError::InvalidRawTxCryptoBytes(format!(
+            "invalid address {}",
+            hex::encode(address)
+        )));
+    }
+    Ok(base58::encode_check(address))
+}
+
+fn protobuf_error(e: prost::DecodeError) -> TronError {
+    TronError::ProtobufError(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::protocol::vote_witness_contract::Vote;
+    use crate::test::prepare_parse_context;
+    use crate::transaction::checker::TxChecker;
+    use crate::transaction::wrapped_tron::WrappedTron;
+    use crate::utils::base58check_to_u8_slice;
+    use crate::TxParser;
+
+    const OWNER: &str = "TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH";
+    const RECEIVER: &str = "TSeJkUh4Qv67VNFwY8LaAxERygNdy6NQZK";
+
+    fn prepare_tx(
+        message: impl Message,
+        contract_type: ContractType,
+        type_name: &str,
+    ) -> WrappedTron {
+        let pubkey_str = "xpub6D1AabNHCupeiLM65ZR9UStMhJ1vCpyV4XbZdyhMZBiJXALQtmn9p42VTQckoHVn8WNqS7dqnJokZHAHcHGoaQgmv8D45oNUKx6DZMNZBCd";
+        let context = prepare_parse_context(pubkey_str);
+        let tron_tx =
+            WrappedTron::build_transfer_contract(message, contract_type, type_name, None).unwrap();
+        WrappedTron::from_transaction(
+            tron_tx,
+            "m/44'/195'/0'/0/0".to_string(),
+            "73c5da0a".to_string(),
+            &context,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_delegate_resource() {
+        let contract = DelegateResourceContract {
+            owner_address: base58check_to_u8_slice(OWNER.to_string()).unwrap(),
+            resource: ResourceCode::Energy as i32,
+            balance: 1000000000,
+            receiver_address: base58check_to_u8_slice(RECEIVER.to_string()).unwrap(),
+            lock: true,
+            lock_period: 86400,
+        };
+        let tx = prepare_tx(
+            contract,
+            ContractType::DelegateResourceContract,
+            "DelegateResourceContract",
+        );
+        let context = prepare_parse_context("xpub6D1AabNHCupeiLM65ZR9UStMhJ1vCpyV4XbZdyhMZBiJXALQtmn9p42VTQckoHVn8WNqS7dqnJokZHAHcHGoaQgmv8D45oNUKx6DZMNZBCd");
+        assert_eq!(Ok(()), tx.check(&context));
+        let parsed_tx = tx.parse().unwrap();
+        assert_eq!("Delegate Resource", parsed_tx.overview.method);
+        assert_eq!("1000 TRX", parsed_tx.overview.value);
+        assert_eq!(OWNER, parsed_tx.overview.from);
+        assert_eq!(RECEIVER, parsed_tx.overview.to);
+        assert_eq!(Some("Energy".to_string()), parsed_tx.overview.resource);
+        assert_eq!(Some("86400".to_string()), parsed_tx.detail.lock_period);
+        assert!(parsed_tx.detail.votes.is_empty());
+    }
+
+    #[test]
+    fn test_parse_vote_witness() {
+        let contract = VoteWitnessContract {
+            owner_address: base58check_to_u8_slice(OWNER.to_string()).unwrap(),
+            votes: Vec::from([
+                Vote {
+                    vote_address: base58check_to_u8_slice(RECEIVER.to_string()).unwrap(),
+                    vote_count: 100,
+                },
+                Vote {
+                    vote_address: base58check_to_u8_slice(
+                        "TYJPRrdB5APNeRs4R7fYZSwW3TcrTKw2gx".to_string(),
+                    )
+                    .unwrap(),
+                    vote_count: 20,
+                },
+            ]),
+            support: false,
+        };
+        let tx = prepare_tx(
+            contract,
+            ContractType::VoteWitnessContract,
+            "VoteWitnessContract",
+        );
+        let parsed_tx = tx.parse().unwrap();
+        assert_eq!("Vote Witness", parsed_tx.detail.method);
+        assert_eq!("120 Votes", parsed_tx.detail.value);
+        assert_eq!(None, parsed_tx.detail.resource);
+        assert_eq!(2, parsed_tx.detail.votes.len());
+        assert_eq!(RECEIVER, parsed_tx.detail.votes[0].address);
+        assert_eq!("100", parsed_tx.detail.votes[0].count);
+    }
+
+    #[test]
+    fn test_reject_invalid_vote_count() {
+        let contract = VoteWitnessContract {
+            owner_address: base58check_to_u8_slice(OWNER.to_string()).unwrap(),
+            votes: Vec::from([Vote {
+                vote_address: base58check_to_u8_slice(RECEIVER.to_string()).unwrap(),
+                vote_count: -1,
+            }]),
+            support: false,
+        };
+        let tron_tx = WrappedTron::build_transfer_contract(
+            contract,
+            ContractType::VoteWitnessContract,
+            "VoteWitnessContract",
+            None,
+        )
+        .unwrap();
+        let context = prepare_parse_context("xpub6D1AabNHCupeiLM65ZR9UStMhJ1vCpyV4XbZdyhMZBiJXALQtmn9p42VTQckoHVn8WNqS7dqnJokZHAHcHGoaQgmv8D45oNUKx6DZMNZBCd");
+        assert!(WrappedTron::from_transaction(
+            tron_tx,
+            "m/44'/195'/0'/0/0".to_string(),
+            "73c5da0a".to_string(),
+            &context,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_freeze_other_owner() {
+        let contract = FreezeBalanceV2Contract {
+            owner_address: base58check_to_u8_slice(RECEIVER.to_string()).unwrap(),
+            frozen_balance: 5000000,
+            resource: ResourceCode::Bandwidth as i32,
+        };
+        let tx = prepare_tx(
+            contract,
+            ContractType::FreezeBalanceV2Contract,
+            "FreezeBalanceV2Contract",
+        );
+        let parsed_tx = tx.parse().unwrap();
+        assert_eq!("Stake 2.0 Freeze", parsed_tx.overview.method);
+        assert_eq!("5 TRX", parsed_tx.overview.value);
+        assert_eq!(Some("Bandwidth".to_string()), parsed_tx.overview.resource);
+        let context = prepare_parse_context("xpub6D1AabNHCupeiLM65ZR9UStMhJ1vCpyV4XbZdyhMZBiJXALQtmn9p42VTQckoHVn8WNqS7dqnJokZHAHcHGoaQgmv8D45oNUKx6DZMNZBCd");
+        assert_eq!(Err(TronError::NoMyInputs), tx.check(&context));
+    }
+}
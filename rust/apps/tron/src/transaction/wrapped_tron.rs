@@ -3,6 +3,7 @@ use crate::errors::{Result, TronError};
 use crate::pb::protocol::{
     transaction, Transaction, TransferAssetContract, TransferContract, TriggerSmartContract,
 };
+use crate::transaction::stake::{encode_address, StakeContract};
 use crate::utils::base58check_to_u8_slice;
 use alloc::borrow::ToOwned;
 use alloc::string::{String, ToString};
@@ -37,6 +38,7 @@ pub struct WrappedTron {
     pub(crate) value: String,
     pub(crate) token_short_name: Option<String>,
     pub(crate) divider: f64,
+    pub(crate) stake: Option<StakeContract>,
 }
 
 #[macro_export]
@@ -121,7 +123,7 @@ impl WrappedTron {
         }
     }
 
-    fn build_transfer_contract(
+    pub(crate) fn build_transfer_contract(
         message: impl Message,
         contract_type: transaction::contract::ContractType,
         type_name: &str,
@@ -303,6 +305,7 @@ impl WrappedTron {
                     value: tx.value.to_string(),
                     divider,
                     token_short_name,
+                    stake: None,
                 })
             }
             _ => Err(TronError::InvalidRawTxCryptoBytes(
@@ -311,14 +314,166 @@ impl WrappedTron {
         }
     }
 
+    // third party transactions (TronLink, TronWeb...) only provide the serialized raw_data
+    pub fn from_raw_data(
+        raw_data: &[u8],
+        hd_path: String,
+        xfp: String,
+        context: &keystone::ParseContext,
+    ) -> Result<Self> {
+        let raw = transaction::Raw::decode(raw_data)
+            .map_err(|e| TronError::ProtobufError(e.to_string()))?;
+        // unknown fields are dropped while decoding, the hash must cover exactly what is displayed
+        if raw.encode_to_vec() != raw_data {
+            return Err(TronError::InvalidRawTxCryptoBytes(
+                "raw data is not canonically encoded".to_string(),
+            ));
+        }
+        let tron_tx = Transaction {
+            raw_data: Some(raw),
+            signature: vec![],
+        };
+        Self::from_transaction(tron_tx, hd_path, xfp, context)
+    }
+
+    // transactions signed as is, transfers and staking contracts are supported here
+    pub fn from_transaction(
+        tron_tx: Transaction,
+        hd_path: String,
+        xfp: String,
+        context: &keystone::ParseContext,
+    ) -> Result<Self> {
+        let raw_data = tron_tx
+            .raw_data
+            .as_ref()
+            .ok_or(TronError::InvalidRawTxCryptoBytes(
+                "empty raw data".to_string(),
+            ))?;
+        let contract = match raw_data.contract.as_slice() {
+            [contract] => contract,
+            _ => {
+                return Err(TronError::InvalidRawTxCryptoBytes(format!(
+                    "invalid contract count {}",
+                    raw_data.contract.len()
+                )))
+            }
+        };
+        let mut tx = Self {
+            hd_path,
+            extended_pubkey: context.extended_public_key.to_string(),
+            xfp,
+            token: "".to_string(),
+            contract_address: "".to_string(),
+            from: "".to_string(),
+            to: "".to_string(),
+            value: "".to_string(),
+            token_short_name: None,
+            divider: DIVIDER,
+            stake: None,
+            tron_tx: tron_tx.to_owned(),
+        };
+        if let Some(stake) = StakeContract::from_contract(contract)? {
+            tx.from = stake.get_owner();
+            tx.to = stake.get_receiver().unwrap_or_default();
+            tx.value = stake
+                .get_amount()
+                .map(|amount| amount.to_string())
+                .unwrap_or_default();
+            tx.stake = Some(stake);
+            return Ok(tx);
+        }
+        let parameter = contract
+            .parameter
+            .as_ref()
+            .map(|p| p.value.as_slice())
+            .unwrap_or_default();
+        let protobuf_error = |e: prost::DecodeError| TronError::ProtobufError(e.to_string());
+        match transaction::contract::ContractType::from_i32(contract.r#type) {
+            Some(transaction::contract::ContractType::TransferContract) => {
+                let c = TransferContract::decode(parameter).map_err(protobuf_error)?;
+                tx.from = encode_address(&c.owner_address)?;
+                tx.to = encode_address(&c.to_address)?;
+                tx.value = c.amount.to_string();
+            }
+            Some(transaction::contract::ContractType::TransferAssetContract) => {
+                let c = TransferAssetContract::decode(parameter).map_err(protobuf_error)?;
+                let token = String::from_utf8(c.asset_name).map_err(|_e| {
+                    TronError::InvalidRawTxCryptoBytes("invalid token".to_string())
+                })?;
+                tx.from = encode_address(&c.owner_address)?;
+                tx.to = encode_address(&c.to_address)?;
+                tx.value = c.amount.to_string();
+                // the precision of the token is unknown without the companion app, show base units
+                tx.token_short_name = Some(token.to_string());
+                tx.divider = 1_f64;
+                tx.token = token;
+            }
+            Some(transaction::contract::ContractType::TriggerSmartContract) => {
+                let c = TriggerSmartContract::decode(parameter).map_err(protobuf_error)?;
+                // only plain TRC-20 transfers can be displayed, anything else would be blind signing
+                // TRC-10 tokens attached to the call are not shown either
+                if c.call_value != 0
+                    || c.call_token_value != 0
+                    || c.token_id != 0
+                    || c.data.len() != 68
+                    || c.data[..4] != TRC20_TRANSFER_SELECTOR
+                    || c.data[4..16].iter().any(|b| *b != 0)
+                {
+                    return Err(TronError::InvalidRawTxCryptoBytes(
+                        "unsupported smart contract call".to_string(),
+                    ));
+                }
+                let mut to = vec![0x41];
+                to.extend_from_slice(&c.data[16..36]);
+                tx.from = encode_address(&c.owner_address)?;
+                tx.contract_address = encode_address(&c.contract_address)?;
+                tx.to = encode_address(&to)?;
+                tx.value = U256::from_big_endian(&c.data[36..68]).to_string();
+                tx.token_short_name = Some("TRC-20".to_string());
+                tx.divider = 1_f64;
+            }
+            _ => {
+                return Err(TronError::InvalidRawTxCryptoBytes(format!(
+                    "unsupported contract type {}",
+                    contract.r#type
+                )))
+            }
+        }
+        Ok(tx)
+    }
+
     pub fn format_amount(&self) -> Result<String> {
-        let value = f64::from_str(self.value.as_str())?;
+        if let Some(StakeContract::VoteWitness { votes, .. }) = &self.stake {
+            let mut total = 0u64;
+            for vote in votes {
+                let count = vote.count.parse::<u64>().map_err(|_e| {
+                    TronError::InvalidRawTxCryptoBytes(format!("invalid vote count {}", vote.count))
+                })?;
+                total = total
+                    .checked_add(count)
+                    .ok_or(TronError::InvalidRawTxCryptoBytes(
+                        "vote count overflow".to_string(),
+                    ))?;
+            }
+            return Ok(format!("{} Votes", total));
+        }
+        // withdrawing rewards does not state an amount
+        if self.value.is_empty() {
+            return Ok("".to_string());
+        }
         let unit = self.format_unit()?;
+        // base units, avoid the precision loss of the float conversion
+        if self.divider == 1_f64 {
+            return Ok(format!("{} {}", self.value, unit));
+        }
+        let value = f64::from_str(self.value.as_str())?;
         Ok(format!("{} {}", value.div(self.divider), unit))
     }
 
     pub fn format_method(&self) -> Result<String> {
-        if !self.contract_address.is_empty() {
+        if let Some(stake) = &self.stake {
+            Ok(stake.get_method())
+        } else if !self.contract_address.is_empty() {
             Ok("TRC-20 Transfer".to_string())
         } else if !self.token.is_empty() && self.token.to_uppercase() != "TRX" {
             Ok("TRC-10 Transfer".to_string())
@@ -326,6 +481,15 @@ impl WrappedTron {
             Ok("TRX Transfer".to_string())
         }
     }
+    // the energy of smart contract calls is paid up to this limit
+    pub fn format_fee_limit(&self) -> Option<String> {
+        let fee_limit = self.tron_tx.raw_data.as_ref()?.fee_limit;
+        if fee_limit <= 0 {
+            return None;
+        }
+        Some(format!("{} TRX", (fee_limit as f64).div(DIVIDER)))
+    }
+
     pub fn format_unit(&self) -> Result<String> {
         match self.token_short_name.to_owned() {
             Some(name) => Ok(name),
@@ -336,3 +500,4 @@ impl WrappedTron {
 
 pub const DIVIDER: f64 = 1000000_f64;
 pub const NETWORK: &str = "TRON";
+const TRC20_TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
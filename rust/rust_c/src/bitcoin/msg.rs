@@ -10,15 +10,19 @@ use crate::common::{
 };
 use crate::extract_ptr_with_type;
 use alloc::{
-    slice,
+    format, slice,
     string::{String, ToString},
     vec::Vec,
 };
+use app_bitcoin::bip322;
 use base64;
+use bitcoin::bip32::Fingerprint;
 use core::ptr::null_mut;
+use core::str::FromStr;
 use keystore::algorithms::secp256k1;
 use ur_registry::bitcoin::btc_sign_request::{BtcSignRequest, DataType};
 use ur_registry::bitcoin::btc_signature::BtcSignature;
+use ur_registry::crypto_psbt::CryptoPSBT;
 use ur_registry::traits::RegistryItem;
 
 #[no_mangle]
@@ -207,3 +211,89 @@ pub extern "C" fn sign_seed_signer_message(
         }
     }
 }
+
+fn get_master_fingerprint(master_fingerprint: PtrBytes, length: u32) -> Option<Fingerprint> {
+    if length != 4 {
+        return None;
+    }
+    let mfp = unsafe { slice::from_raw_parts(master_fingerprint, 4) };
+    Fingerprint::from_str(hex::encode(mfp).as_str()).ok()
+}
+
+// BIP-322 requests are crypto-psbt URs carrying the `to_sign` transaction
+#[no_mangle]
+pub extern "C" fn btc_check_bip322_msg(
+    ptr: PtrUR,
+    master_fingerprint: PtrBytes,
+    length: u32,
+) -> PtrT<TransactionCheckResult> {
+    let mfp = match get_master_fingerprint(master_fingerprint, length) {
+        Some(v) => v,
+        None => return TransactionCheckResult::from(RustCError::InvalidMasterFingerprint).c_ptr(),
+    };
+    let crypto_psbt = extract_ptr_with_type!(ptr, CryptoPSBT);
+    match bip322::parse_psbt(&crypto_psbt.get_psbt(), mfp) {
+        Ok(_) => TransactionCheckResult::new().c_ptr(),
+        Err(e) => TransactionCheckResult::from(e).c_ptr(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn btc_parse_bip322_msg(
+    ptr: PtrUR,
+    master_fingerprint: PtrBytes,
+    length: u32,
+) -> *mut TransactionParseResult<DisplayBtcMsg> {
+    let mfp = match get_master_fingerprint(master_fingerprint, length) {
+        Some(v) => v,
+        None => return TransactionParseResult::from(RustCError::InvalidMasterFingerprint).c_ptr(),
+    };
+    let crypto_psbt = extract_ptr_with_type!(ptr, CryptoPSBT);
+    let request = match bip322::parse_psbt(&crypto_psbt.get_psbt(), mfp) {
+        Ok(v) => v,
+        Err(e) => return TransactionParseResult::from(e).c_ptr(),
+    };
+    let address = match request.get_address() {
+        Ok(v) => v,
+        Err(e) => return TransactionParseResult::from(e).c_ptr(),
+    };
+    let detail = String::from_utf8(request.message.clone())
+        .unwrap_or_else(|_| format!("0x{}", hex::encode(&request.message)));
+    TransactionParseResult::success(
+        DisplayBtcMsg {
+            detail: convert_c_char(detail),
+            address: convert_c_char(address),
+        }
+        .c_ptr(),
+    )
+    .c_ptr()
+}
+
+// the signed proof is checked against the message before it leaves the device
+#[no_mangle]
+pub extern "C" fn btc_sign_bip322_msg(
+    ptr: PtrUR,
+    seed: PtrBytes,
+    seed_len: u32,
+    master_fingerprint: PtrBytes,
+    master_fingerprint_len: u32,
+) -> *mut UREncodeResult {
+    let mfp = match get_master_fingerprint(master_fingerprint, master_fingerprint_len) {
+        Some(v) => v,
+        None => return UREncodeResult::from(RustCError::InvalidMasterFingerprint).c_ptr(),
+    };
+    let crypto_psbt = extract_ptr_with_type!(ptr, CryptoPSBT);
+    let seed = unsafe { slice::from_raw_parts(seed, seed_len as usize) };
+    match bip322::sign_psbt(&crypto_psbt.get_psbt(), seed, mfp)
+        .map(|v| CryptoPSBT::new(v).try_into())
+    {
+        Ok(Ok(data)) => UREncodeResult::encode(
+            data,
+            CryptoPSBT::get_registry_type().get_type(),
+            FRAGMENT_MAX_LENGTH_DEFAULT,
+        )
+        .c_ptr(),
+        Ok(Err(e)) => UREncodeResult::from(e).c_ptr(),
+        Err(e) => UREncodeResult::from(e).c_ptr(),
+    }
+}
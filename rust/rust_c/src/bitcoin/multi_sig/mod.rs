@@ -6,15 +6,18 @@ use alloc::{format, slice, vec};
 
 use app_bitcoin::errors::BitcoinError;
 use app_bitcoin::multi_sig::address::create_multi_sig_address_for_wallet;
+use app_bitcoin::multi_sig::bsms::{create_key_record, import_descriptor_record};
+use app_bitcoin::multi_sig::policy::{parse_wallet_policy, register_wallet_policy};
 use app_bitcoin::multi_sig::wallet::{
-    export_wallet_by_ur, parse_bsms_wallet_config, parse_wallet_config, strict_verify_wallet_config,
+    export_wallet_by_ur, export_wallet_config, parse_bsms_wallet_config, parse_wallet_config,
+    strict_verify_wallet_config,
 };
 use app_bitcoin::multi_sig::{
     export_xpub_by_crypto_account, extract_xpub_info_from_crypto_account,
 };
 use core::str::FromStr;
 use cty::c_char;
-use structs::{MultiSigFormatType, MultiSigXPubInfoItem};
+use structs::{MultiSigFormatType, MultiSigWalletFileFormat, MultiSigXPubInfoItem};
 
 use cryptoxide::hashing::sha256;
 use hex;
@@ -233,6 +236,37 @@ pub extern "C" fn export_multi_sig_wallet_by_ur(
     }
 }
 
+// exports the wallet as a Coldcard/Caravan/Specter file or a plain descriptor
+#[no_mangle]
+pub extern "C" fn export_multi_sig_wallet_by_file(
+    master_fingerprint: PtrBytes,
+    master_fingerprint_len: u32,
+    config: PtrString,
+    format: MultiSigWalletFileFormat,
+) -> Ptr<SimpleResponse<c_char>> {
+    if master_fingerprint_len != 4 {
+        return SimpleResponse::from(RustCError::InvalidMasterFingerprint).simple_c_ptr();
+    }
+    let master_fingerprint = unsafe { core::slice::from_raw_parts(master_fingerprint, 4) };
+    let master_fingerprint =
+        match bitcoin::bip32::Fingerprint::from_str(hex::encode(master_fingerprint).as_str())
+            .map_err(|_e| RustCError::InvalidMasterFingerprint)
+        {
+            Ok(mfp) => mfp,
+            Err(e) => {
+                return SimpleResponse::from(e).simple_c_ptr();
+            }
+        };
+    let config = recover_c_char(config);
+    let result = parse_wallet_config(&config, &master_fingerprint.to_string()).and_then(|wallet| {
+        export_wallet_config(&wallet, &master_fingerprint.to_string(), format.into())
+    });
+    match result {
+        Ok(content) => SimpleResponse::success(convert_c_char(content)).simple_c_ptr(),
+        Err(e) => SimpleResponse::from(e).simple_c_ptr(),
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn import_multi_sig_wallet_by_ur(
     ur: PtrUR,
@@ -294,6 +328,46 @@ pub extern "C" fn import_multi_sig_wallet_by_file(
     }
 }
 
+// BIP-129 round 1, the key record is encrypted with the token unless the token is "00"
+#[no_mangle]
+pub extern "C" fn export_bsms_key_record(
+    seed: PtrBytes,
+    seed_len: u32,
+    path: PtrString,
+    token: PtrString,
+    description: PtrString,
+) -> Ptr<SimpleResponse<c_char>> {
+    let seed = unsafe { core::slice::from_raw_parts(seed, seed_len as usize) };
+    let path = recover_c_char(path);
+    let token = recover_c_char(token);
+    let description = recover_c_char(description);
+    match create_key_record(seed, &path, &token, &description) {
+        Ok(record) => SimpleResponse::success(convert_c_char(record)).simple_c_ptr(),
+        Err(e) => SimpleResponse::from(e).simple_c_ptr(),
+    }
+}
+
+// BIP-129 round 2, the descriptor record is checked like a wallet file, the seed check happens
+// in `parse_and_verify_multisig_config` when the wallet is registered
+#[no_mangle]
+pub extern "C" fn import_multi_sig_wallet_by_bsms(
+    content: PtrString,
+    token: PtrString,
+    master_fingerprint: PtrBytes,
+    master_fingerprint_len: u32,
+) -> Ptr<Response<MultiSigWallet>> {
+    if master_fingerprint_len != 4 {
+        return Response::from(RustCError::InvalidMasterFingerprint).c_ptr();
+    }
+    let master_fingerprint = unsafe { core::slice::from_raw_parts(master_fingerprint, 4) };
+    let content = recover_c_char(content);
+    let token = recover_c_char(token);
+    match import_descriptor_record(&token, &content, &hex::encode(master_fingerprint)) {
+        Ok(wallet) => Response::success_ptr(MultiSigWallet::from(wallet).c_ptr()).c_ptr(),
+        Err(e) => Response::from(e).c_ptr(),
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn generate_address_for_multisig_wallet_config(
     wallet_config: PtrString,
@@ -385,3 +459,81 @@ pub extern "C" fn parse_and_verify_multisig_config(
         Err(e) => Response::from(e).c_ptr(),
     }
 }
+
+#[no_mangle]
+pub extern "C" fn import_wallet_policy_by_file(
+    content: PtrString,
+    master_fingerprint: PtrBytes,
+    master_fingerprint_len: u32,
+) -> Ptr<Response<MultiSigWallet>> {
+    if master_fingerprint_len != 4 {
+        return Response::from(RustCError::InvalidMasterFingerprint).c_ptr();
+    }
+    let master_fingerprint = unsafe { core::slice::from_raw_parts(master_fingerprint, 4) };
+    let content = recover_c_char(content);
+    match parse_wallet_policy(&content) {
+        Ok(policy) if !policy.contains_fingerprint(&hex::encode(master_fingerprint)) => {
+            Response::from(BitcoinError::MultiSigWalletNotMyWallet).c_ptr()
+        }
+        Ok(policy) => {
+            Response::success_ptr(MultiSigWallet::from_wallet_policy(&policy, content).c_ptr())
+                .c_ptr()
+        }
+        Err(e) => Response::from(e).c_ptr(),
+    }
+}
+
+// returns the hmac that has to be stored together with the policy
+#[no_mangle]
+pub extern "C" fn btc_register_wallet_policy(
+    seed: PtrBytes,
+    seed_len: u32,
+    wallet_policy: PtrString,
+    master_fingerprint: PtrBytes,
+    master_fingerprint_len: u32,
+) -> Ptr<SimpleResponse<c_char>> {
+    if master_fingerprint_len != 4 {
+        return SimpleResponse::from(RustCError::InvalidMasterFingerprint).simple_c_ptr();
+    }
+    let seed = unsafe { core::slice::from_raw_parts(seed, seed_len as usize) };
+    let master_fingerprint = unsafe { core::slice::from_raw_parts(master_fingerprint, 4) };
+    let content = recover_c_char(wallet_policy);
+    match register_wallet_policy(seed, &content, &hex::encode(master_fingerprint)) {
+        Ok((_, hmac)) => SimpleResponse::success(convert_c_char(hmac)).simple_c_ptr(),
+        Err(e) => SimpleResponse::from(e).simple_c_ptr(),
+    }
+}
+
+// like the multisig configs the policy is read from the hash protected wallet storage, the
+// hmac only has to be checked when signing
+#[no_mangle]
+pub extern "C" fn btc_generate_address_for_wallet_policy(
+    wallet_policy: PtrString,
+    is_change: bool,
+    index: u32,
+) -> Ptr<SimpleResponse<c_char>> {
+    let content = recover_c_char(wallet_policy);
+    match parse_wallet_policy(&content).and_then(|policy| policy.derive_address(is_change, index)) {
+        Ok(address) => SimpleResponse::success(convert_c_char(address)).simple_c_ptr(),
+        Err(e) => SimpleResponse::from(e).simple_c_ptr(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn btc_export_wallet_policy_by_ur(wallet_policy: PtrString) -> *mut UREncodeResult {
+    let content = recover_c_char(wallet_policy);
+    if let Err(e) = parse_wallet_policy(&content) {
+        return UREncodeResult::from(e).c_ptr();
+    }
+    Bytes::new(content.into_bytes()).try_into().map_or_else(
+        |e| UREncodeResult::from(e).c_ptr(),
+        |data| {
+            UREncodeResult::encode(
+                data,
+                Bytes::get_registry_type().get_type(),
+                FRAGMENT_MAX_LENGTH_DEFAULT,
+            )
+            .c_ptr()
+        },
+    )
+}
@@ -1,6 +1,6 @@
 use alloc::boxed::Box;
 use alloc::format;
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 
 use crate::common::ffi::VecFFI;
 use crate::common::free::Free;
@@ -9,6 +9,8 @@ use crate::common::ur::UREncodeResult;
 use crate::common::utils::{convert_c_char, recover_c_char};
 use crate::{check_and_free_ptr, free_str_ptr, free_vec, impl_c_ptr, make_free_method};
 use alloc::vec::Vec;
+use app_bitcoin::multi_sig::coordinator::WalletConfigFormat;
+use app_bitcoin::multi_sig::policy::{WalletPolicy, WALLET_POLICY_FORMAT};
 use app_bitcoin::multi_sig::wallet::{BsmsWallet, MultiSigWalletConfig};
 use app_bitcoin::multi_sig::{MultiSigFormat, MultiSigType, MultiSigXPubInfo, Network};
 
@@ -36,6 +38,25 @@ impl From<&NetworkType> for Network {
     }
 }
 
+#[repr(C)]
+pub enum MultiSigWalletFileFormat {
+    Coldcard,
+    Caravan,
+    Specter,
+    Descriptor,
+}
+
+impl From<MultiSigWalletFileFormat> for WalletConfigFormat {
+    fn from(val: MultiSigWalletFileFormat) -> Self {
+        match val {
+            MultiSigWalletFileFormat::Coldcard => WalletConfigFormat::Coldcard,
+            MultiSigWalletFileFormat::Caravan => WalletConfigFormat::Caravan,
+            MultiSigWalletFileFormat::Specter => WalletConfigFormat::Specter,
+            MultiSigWalletFileFormat::Descriptor => WalletConfigFormat::Descriptor,
+        }
+    }
+}
+
 #[repr(C)]
 pub enum MultiSigFormatType {
     P2sh,
@@ -44,6 +65,10 @@ pub enum MultiSigFormatType {
     P2shTest,
     P2wshP2shTest,
     P2wshTest,
+    P2tr,
+    P2trTest,
+    P2trMusig2,
+    P2trMusig2Test,
 }
 
 impl From<MultiSigFormatType> for MultiSigType {
@@ -55,6 +80,10 @@ impl From<MultiSigFormatType> for MultiSigType {
             MultiSigFormatType::P2shTest => MultiSigType::P2shTest,
             MultiSigFormatType::P2wshP2shTest => MultiSigType::P2wshP2shTest,
             MultiSigFormatType::P2wshTest => MultiSigType::P2wshTest,
+            MultiSigFormatType::P2tr => MultiSigType::P2tr,
+            MultiSigFormatType::P2trTest => MultiSigType::P2trTest,
+            MultiSigFormatType::P2trMusig2 => MultiSigType::P2tr,
+            MultiSigFormatType::P2trMusig2Test => MultiSigType::P2trTest,
         }
     }
 }
@@ -68,6 +97,10 @@ impl From<&MultiSigFormatType> for MultiSigType {
             MultiSigFormatType::P2shTest => MultiSigType::P2shTest,
             MultiSigFormatType::P2wshP2shTest => MultiSigType::P2wshP2shTest,
             MultiSigFormatType::P2wshTest => MultiSigType::P2wshTest,
+            MultiSigFormatType::P2tr => MultiSigType::P2tr,
+            MultiSigFormatType::P2trTest => MultiSigType::P2trTest,
+            MultiSigFormatType::P2trMusig2 => MultiSigType::P2tr,
+            MultiSigFormatType::P2trMusig2Test => MultiSigType::P2trTest,
         }
     }
 }
@@ -81,6 +114,10 @@ impl From<MultiSigFormatType> for MultiSigFormat {
             MultiSigFormatType::P2shTest => MultiSigFormat::P2sh,
             MultiSigFormatType::P2wshP2shTest => MultiSigFormat::P2wshP2sh,
             MultiSigFormatType::P2wshTest => MultiSigFormat::P2wsh,
+            MultiSigFormatType::P2tr => MultiSigFormat::P2tr,
+            MultiSigFormatType::P2trTest => MultiSigFormat::P2tr,
+            MultiSigFormatType::P2trMusig2 => MultiSigFormat::P2trMusig2,
+            MultiSigFormatType::P2trMusig2Test => MultiSigFormat::P2trMusig2,
         }
     }
 }
@@ -94,6 +131,10 @@ impl From<&MultiSigFormatType> for MultiSigFormat {
             MultiSigFormatType::P2shTest => MultiSigFormat::P2sh,
             MultiSigFormatType::P2wshP2shTest => MultiSigFormat::P2wshP2sh,
             MultiSigFormatType::P2wshTest => MultiSigFormat::P2wsh,
+            MultiSigFormatType::P2tr => MultiSigFormat::P2tr,
+            MultiSigFormatType::P2trTest => MultiSigFormat::P2tr,
+            MultiSigFormatType::P2trMusig2 => MultiSigFormat::P2trMusig2,
+            MultiSigFormatType::P2trMusig2Test => MultiSigFormat::P2trMusig2,
         }
     }
 }
@@ -239,6 +280,52 @@ impl From<MultiSigWalletConfig> for MultiSigWallet {
     }
 }
 
+impl MultiSigWallet {
+    // the policy has no threshold of its own, the descriptor template is shown instead
+    pub fn from_wallet_policy(value: &WalletPolicy, config_text: String) -> Self {
+        MultiSigWallet {
+            creator: convert_c_char("".to_string()),
+            name: convert_c_char(value.name.clone()),
+            policy: convert_c_char(value.descriptor_template.clone()),
+            threshold: 0,
+            total: value.keys_info.len() as u32,
+            derivations: VecFFI::from(
+                value
+                    .get_key_origins()
+                    .iter()
+                    .map(|(_, path)| convert_c_char(format!("m/{}", path)))
+                    .collect::<Vec<_>>(),
+            )
+            .c_ptr(),
+            format: convert_c_char(WALLET_POLICY_FORMAT.to_string()),
+            xpub_items: VecFFI::from(
+                value
+                    .keys_info
+                    .iter()
+                    .zip(value.get_key_origins())
+                    .map(|(key_info, (fingerprint, _))| MultiSigXPubItem {
+                        xfp: convert_c_char(fingerprint.to_string().to_uppercase()),
+                        xpub: convert_c_char(
+                            key_info
+                                .rsplit(']')
+                                .next()
+                                .unwrap_or(key_info.as_str())
+                                .to_string(),
+                        ),
+                    })
+                    .collect::<Vec<MultiSigXPubItem>>(),
+            )
+            .c_ptr(),
+            verify_code: convert_c_char(value.verify_code.clone()),
+            config_text: convert_c_char(config_text),
+            network: match value.get_network() {
+                Network::MainNet => 0,
+                Network::TestNet => 1,
+            },
+        }
+    }
+}
+
 impl From<&mut MultiSigWallet> for MultiSigWalletConfig {
     fn from(val: &mut MultiSigWallet) -> Self {
         MultiSigWalletConfig {
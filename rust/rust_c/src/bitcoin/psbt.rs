@@ -3,6 +3,7 @@ use alloc::collections::BTreeMap;
 use alloc::slice;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use app_bitcoin::multi_sig::policy::{load_wallet_policy, parse_wallet_policy, WalletPolicy};
 use app_bitcoin::multi_sig::wallet::parse_wallet_config;
 use core::ptr::null_mut;
 use core::str::FromStr;
@@ -17,6 +18,7 @@ use crate::common::ur::{UREncodeResult, FRAGMENT_MAX_LENGTH_DEFAULT, FRAGMENT_UN
 use crate::common::utils::{convert_c_char, recover_c_array, recover_c_char};
 use crate::extract_ptr_with_type;
 use app_bitcoin::parsed_tx::ParseContext;
+use app_bitcoin::tx_policy::TxPolicy;
 use app_bitcoin::{self, parse_psbt_hex_sign_status, parse_psbt_sign_status};
 use bitcoin::bip32::{DerivationPath, Xpub};
 use hex;
@@ -48,7 +50,64 @@ pub extern "C" fn btc_parse_psbt(
         };
         let mfp = core::slice::from_raw_parts(master_fingerprint, 4);
         let public_keys = recover_c_array(public_keys);
-        parse_psbt(mfp, public_keys, psbt, multisig_wallet_config)
+        parse_psbt(mfp, public_keys, psbt, multisig_wallet_config, None)
+    }
+}
+
+// the registered policy is read from the multisig wallet storage, which is covered by the
+// multisig data hash, its hmac is checked again before signing
+#[no_mangle]
+pub extern "C" fn btc_parse_psbt_with_wallet_policy(
+    ptr: PtrUR,
+    master_fingerprint: PtrBytes,
+    length: u32,
+    wallet_policy: PtrString,
+) -> *mut TransactionParseResult<DisplayTx> {
+    if length != 4 {
+        return TransactionParseResult::from(RustCError::InvalidMasterFingerprint).c_ptr();
+    }
+    let crypto_psbt = extract_ptr_with_type!(ptr, CryptoPSBT);
+    let psbt = crypto_psbt.get_psbt();
+
+    unsafe {
+        let wallet_policy = match parse_wallet_policy(&recover_c_char(wallet_policy)) {
+            Ok(policy) => policy,
+            Err(e) => return TransactionParseResult::from(e).c_ptr(),
+        };
+        let mfp = core::slice::from_raw_parts(master_fingerprint, 4);
+        parse_psbt(mfp, &[], psbt, None, Some(wallet_policy))
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn btc_check_psbt_with_wallet_policy(
+    ptr: PtrUR,
+    master_fingerprint: PtrBytes,
+    length: u32,
+    wallet_policy: PtrString,
+    allow_sighash_none: bool,
+) -> PtrT<TransactionCheckResult> {
+    if length != 4 {
+        return TransactionCheckResult::from(RustCError::InvalidMasterFingerprint).c_ptr();
+    }
+    let crypto_psbt = extract_ptr_with_type!(ptr, CryptoPSBT);
+    let psbt = crypto_psbt.get_psbt();
+
+    unsafe {
+        let wallet_policy = match parse_wallet_policy(&recover_c_char(wallet_policy)) {
+            Ok(policy) => policy,
+            Err(e) => return TransactionCheckResult::from(e).c_ptr(),
+        };
+        let mfp = core::slice::from_raw_parts(master_fingerprint, 4);
+        check_psbt(
+            mfp,
+            &[],
+            psbt,
+            None,
+            None,
+            Some(wallet_policy),
+            allow_sighash_none,
+        )
     }
 }
 
@@ -59,6 +118,7 @@ fn btc_sign_psbt_dynamic(
     seed_len: u32,
     master_fingerprint: PtrBytes,
     master_fingerprint_len: u32,
+    allow_sighash_none: bool,
     fragment_length: usize,
 ) -> *mut UREncodeResult {
     if master_fingerprint_len != 4 {
@@ -80,7 +140,9 @@ fn btc_sign_psbt_dynamic(
 
     let seed = unsafe { slice::from_raw_parts(seed, seed_len as usize) };
 
-    let result = app_bitcoin::sign_psbt(psbt, seed, master_fingerprint);
+    let result =
+        app_bitcoin::sign_versioned_psbt(psbt, seed, master_fingerprint, allow_sighash_none)
+            .and_then(|v| v.serialize());
     match result.map(|v| CryptoPSBT::new(v).try_into()) {
         Ok(v) => match v {
             Ok(data) => UREncodeResult::encode(
@@ -102,6 +164,7 @@ pub extern "C" fn btc_sign_psbt(
     seed_len: u32,
     master_fingerprint: PtrBytes,
     master_fingerprint_len: u32,
+    allow_sighash_none: bool,
 ) -> *mut UREncodeResult {
     btc_sign_psbt_dynamic(
         ptr,
@@ -109,6 +172,7 @@ pub extern "C" fn btc_sign_psbt(
         seed_len,
         master_fingerprint,
         master_fingerprint_len,
+        allow_sighash_none,
         FRAGMENT_MAX_LENGTH_DEFAULT,
     )
 }
@@ -120,6 +184,7 @@ pub extern "C" fn btc_sign_psbt_unlimited(
     seed_len: u32,
     master_fingerprint: PtrBytes,
     master_fingerprint_len: u32,
+    allow_sighash_none: bool,
 ) -> *mut UREncodeResult {
     btc_sign_psbt_dynamic(
         ptr,
@@ -127,6 +192,7 @@ pub extern "C" fn btc_sign_psbt_unlimited(
         seed_len,
         master_fingerprint,
         master_fingerprint_len,
+        allow_sighash_none,
         FRAGMENT_UNLIMITED_LENGTH,
     )
 }
@@ -138,6 +204,7 @@ pub extern "C" fn btc_sign_multisig_psbt(
     seed_len: u32,
     master_fingerprint: PtrBytes,
     master_fingerprint_len: u32,
+    allow_sighash_none: bool,
 ) -> *mut MultisigSignResult {
     if master_fingerprint_len != 4 {
         return MultisigSignResult {
@@ -172,14 +239,16 @@ pub extern "C" fn btc_sign_multisig_psbt(
 
     let seed = unsafe { slice::from_raw_parts(seed, seed_len as usize) };
 
-    let result = app_bitcoin::sign_psbt_no_serialize(psbt, seed, master_fingerprint);
-    match result.map(|v| {
-        let buf = v.serialize();
-        let sign_state = parse_psbt_sign_status(v);
-        CryptoPSBT::new(buf.clone())
-            .try_into()
-            .map(|v| (sign_state, v, buf))
-    }) {
+    let result =
+        app_bitcoin::sign_versioned_psbt(psbt, seed, master_fingerprint, allow_sighash_none);
+    match result
+        .and_then(|v| v.serialize().map(|buf| (buf, v.psbt)))
+        .map(|(buf, v)| {
+            let sign_state = parse_psbt_sign_status(v);
+            CryptoPSBT::new(buf.clone())
+                .try_into()
+                .map(|v| (sign_state, v, buf))
+        }) {
         Ok(v) => match v {
             Ok((sign_state, data, psbt_hex)) => {
                 let (ptr, size, _cap) = psbt_hex.into_raw_parts();
@@ -217,6 +286,43 @@ pub extern "C" fn btc_sign_multisig_psbt(
     }
 }
 
+// refuse to sign when the stored policy no longer matches the hmac from its registration
+#[no_mangle]
+pub extern "C" fn btc_sign_multisig_psbt_with_wallet_policy(
+    ptr: PtrUR,
+    seed: PtrBytes,
+    seed_len: u32,
+    master_fingerprint: PtrBytes,
+    master_fingerprint_len: u32,
+    wallet_policy: PtrString,
+    policy_hmac: PtrString,
+    allow_sighash_none: bool,
+) -> *mut MultisigSignResult {
+    let policy_seed = unsafe { slice::from_raw_parts(seed, seed_len as usize) };
+    if let Err(e) = load_wallet_policy(
+        policy_seed,
+        &recover_c_char(wallet_policy),
+        &recover_c_char(policy_hmac),
+    ) {
+        return MultisigSignResult {
+            ur_result: UREncodeResult::from(e).c_ptr(),
+            sign_status: null_mut(),
+            is_completed: false,
+            psbt_hex: null_mut(),
+            psbt_len: 0,
+        }
+        .c_ptr();
+    }
+    btc_sign_multisig_psbt(
+        ptr,
+        seed,
+        seed_len,
+        master_fingerprint,
+        master_fingerprint_len,
+        allow_sighash_none,
+    )
+}
+
 #[no_mangle]
 pub extern "C" fn btc_export_multisig_psbt(ptr: PtrUR) -> *mut MultisigSignResult {
     let crypto_psbt = extract_ptr_with_type!(ptr, CryptoPSBT);
@@ -296,6 +402,7 @@ pub extern "C" fn btc_check_psbt(
     public_keys: PtrT<CSliceFFI<ExtendedPublicKey>>,
     verify_code: PtrString,
     multisig_wallet_config: PtrString,
+    allow_sighash_none: bool,
 ) -> PtrT<TransactionCheckResult> {
     if length != 4 {
         return TransactionCheckResult::from(RustCError::InvalidMasterFingerprint).c_ptr();
@@ -316,7 +423,15 @@ pub extern "C" fn btc_check_psbt(
         };
         let mfp = core::slice::from_raw_parts(master_fingerprint, 4);
         let public_keys = recover_c_array(public_keys);
-        check_psbt(mfp, public_keys, psbt, verify_code, multisig_wallet_config)
+        check_psbt(
+            mfp,
+            public_keys,
+            psbt,
+            verify_code,
+            multisig_wallet_config,
+            None,
+            allow_sighash_none,
+        )
     }
 }
 
@@ -329,6 +444,7 @@ pub extern "C" fn btc_check_psbt_bytes(
     public_keys: PtrT<CSliceFFI<ExtendedPublicKey>>,
     verify_code: PtrString,
     multisig_wallet_config: PtrString,
+    allow_sighash_none: bool,
 ) -> PtrT<TransactionCheckResult> {
     if length != 4 {
         return TransactionCheckResult::from(RustCError::InvalidMasterFingerprint).c_ptr();
@@ -353,7 +469,15 @@ pub extern "C" fn btc_check_psbt_bytes(
         };
         let mfp = core::slice::from_raw_parts(master_fingerprint, 4);
         let public_keys = recover_c_array(public_keys);
-        check_psbt(mfp, public_keys, psbt, verify_code, multisig_wallet_config)
+        check_psbt(
+            mfp,
+            public_keys,
+            psbt,
+            verify_code,
+            multisig_wallet_config,
+            None,
+            allow_sighash_none,
+        )
     }
 }
 
@@ -382,7 +506,7 @@ pub extern "C" fn btc_parse_psbt_bytes(
         };
         let mfp = core::slice::from_raw_parts(master_fingerprint, 4);
         let public_keys = recover_c_array(public_keys);
-        parse_psbt(mfp, public_keys, psbt, multisig_wallet_config)
+        parse_psbt(mfp, public_keys, psbt, multisig_wallet_config, None)
     }
 }
 
@@ -394,6 +518,7 @@ pub extern "C" fn btc_sign_multisig_psbt_bytes(
     seed_len: u32,
     master_fingerprint: PtrBytes,
     master_fingerprint_len: u32,
+    allow_sighash_none: bool,
 ) -> *mut MultisigSignResult {
     if master_fingerprint_len != 4 {
         return MultisigSignResult {
@@ -443,14 +568,16 @@ pub extern "C" fn btc_sign_multisig_psbt_bytes(
 
     let seed = unsafe { slice::from_raw_parts(seed, seed_len as usize) };
 
-    let result = app_bitcoin::sign_psbt_no_serialize(psbt, seed, master_fingerprint);
-    match result.map(|v| {
-        let buf = v.serialize();
-        let sign_state = parse_psbt_sign_status(v);
-        CryptoPSBT::new(buf.clone())
-            .try_into()
-            .map(|v| (sign_state, v, buf))
-    }) {
+    let result =
+        app_bitcoin::sign_versioned_psbt(psbt, seed, master_fingerprint, allow_sighash_none);
+    match result
+        .and_then(|v| v.serialize().map(|buf| (buf, v.psbt)))
+        .map(|(buf, v)| {
+            let sign_state = parse_psbt_sign_status(v);
+            CryptoPSBT::new(buf.clone())
+                .try_into()
+                .map(|v| (sign_state, v, buf))
+        }) {
         Ok(v) => match v {
             Ok((sign_state, data, psbt_hex)) => {
                 let (ptr, size, _cap) = psbt_hex.into_raw_parts();
@@ -488,11 +615,111 @@ pub extern "C" fn btc_sign_multisig_psbt_bytes(
     }
 }
 
+#[no_mangle]
+pub extern "C" fn btc_check_psbt_bytes_with_wallet_policy(
+    psbt_bytes: PtrBytes,
+    psbt_bytes_length: u32,
+    master_fingerprint: PtrBytes,
+    length: u32,
+    wallet_policy: PtrString,
+    allow_sighash_none: bool,
+) -> PtrT<TransactionCheckResult> {
+    if length != 4 {
+        return TransactionCheckResult::from(RustCError::InvalidMasterFingerprint).c_ptr();
+    }
+    unsafe {
+        let psbt = core::slice::from_raw_parts(psbt_bytes, psbt_bytes_length as usize);
+        let psbt = match get_psbt_bytes(psbt) {
+            Ok(psbt) => psbt,
+            Err(e) => return TransactionCheckResult::from(e).c_ptr(),
+        };
+        let wallet_policy = match parse_wallet_policy(&recover_c_char(wallet_policy)) {
+            Ok(policy) => policy,
+            Err(e) => return TransactionCheckResult::from(e).c_ptr(),
+        };
+        let mfp = core::slice::from_raw_parts(master_fingerprint, 4);
+        check_psbt(
+            mfp,
+            &[],
+            psbt,
+            None,
+            None,
+            Some(wallet_policy),
+            allow_sighash_none,
+        )
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn btc_parse_psbt_bytes_with_wallet_policy(
+    psbt_bytes: PtrBytes,
+    psbt_bytes_length: u32,
+    master_fingerprint: PtrBytes,
+    length: u32,
+    wallet_policy: PtrString,
+) -> *mut TransactionParseResult<DisplayTx> {
+    if length != 4 {
+        return TransactionParseResult::from(RustCError::InvalidMasterFingerprint).c_ptr();
+    }
+    unsafe {
+        let psbt = core::slice::from_raw_parts(psbt_bytes, psbt_bytes_length as usize);
+        let psbt = match get_psbt_bytes(psbt) {
+            Ok(psbt) => psbt,
+            Err(e) => return TransactionParseResult::from(e).c_ptr(),
+        };
+        let wallet_policy = match parse_wallet_policy(&recover_c_char(wallet_policy)) {
+            Ok(policy) => policy,
+            Err(e) => return TransactionParseResult::from(e).c_ptr(),
+        };
+        let mfp = core::slice::from_raw_parts(master_fingerprint, 4);
+        parse_psbt(mfp, &[], psbt, None, Some(wallet_policy))
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn btc_sign_multisig_psbt_bytes_with_wallet_policy(
+    psbt_bytes: PtrBytes,
+    psbt_bytes_length: u32,
+    seed: PtrBytes,
+    seed_len: u32,
+    master_fingerprint: PtrBytes,
+    master_fingerprint_len: u32,
+    wallet_policy: PtrString,
+    policy_hmac: PtrString,
+    allow_sighash_none: bool,
+) -> *mut MultisigSignResult {
+    let policy_seed = unsafe { slice::from_raw_parts(seed, seed_len as usize) };
+    if let Err(e) = load_wallet_policy(
+        policy_seed,
+        &recover_c_char(wallet_policy),
+        &recover_c_char(policy_hmac),
+    ) {
+        return MultisigSignResult {
+            ur_result: UREncodeResult::from(e).c_ptr(),
+            sign_status: null_mut(),
+            is_completed: false,
+            psbt_hex: null_mut(),
+            psbt_len: 0,
+        }
+        .c_ptr();
+    }
+    btc_sign_multisig_psbt_bytes(
+        psbt_bytes,
+        psbt_bytes_length,
+        seed,
+        seed_len,
+        master_fingerprint,
+        master_fingerprint_len,
+        allow_sighash_none,
+    )
+}
+
 fn parse_psbt(
     mfp: &[u8],
     public_keys: &[ExtendedPublicKey],
     psbt: Vec<u8>,
     multisig_wallet_config: Option<String>,
+    wallet_policy: Option<WalletPolicy>,
 ) -> *mut TransactionParseResult<DisplayTx> {
     let master_fingerprint = bitcoin::bip32::Fingerprint::from_str(hex::encode(mfp).as_str())
         .map_err(|_e| RustCError::InvalidMasterFingerprint);
@@ -522,7 +749,11 @@ fn parse_psbt(
                 Ok(t) => t,
                 Err(e) => return TransactionParseResult::from(e).c_ptr(),
             };
-            let context = ParseContext::new(fp, keys, None, wallet_config);
+            let mut context =
+                ParseContext::new(fp, keys, None, wallet_config, Some(TxPolicy::default()));
+            if let Some(wallet_policy) = wallet_policy {
+                context = context.with_wallet_policy(wallet_policy);
+            }
             let parsed_psbt = app_bitcoin::parse_psbt(psbt, context);
             match parsed_psbt {
                 Ok(res) => {
@@ -542,6 +773,8 @@ fn check_psbt(
     psbt: Vec<u8>,
     verify_code: Option<String>,
     multisig_wallet_config: Option<String>,
+    wallet_policy: Option<WalletPolicy>,
+    allow_sighash_none: bool,
 ) -> PtrT<TransactionCheckResult> {
     let master_fingerprint = bitcoin::bip32::Fingerprint::from_str(hex::encode(mfp).as_str())
         .map_err(|_e| RustCError::InvalidMasterFingerprint);
@@ -571,7 +804,11 @@ fn check_psbt(
                 Ok(t) => t,
                 Err(e) => return TransactionCheckResult::from(e).c_ptr(),
             };
-            let context = ParseContext::new(fp, keys, verify_code, wallet_config);
+            let mut context = ParseContext::new(fp, keys, verify_code, wallet_config, None)
+                .with_allow_sighash_none(allow_sighash_none);
+            if let Some(wallet_policy) = wallet_policy {
+                context = context.with_wallet_policy(wallet_policy);
+            }
             let check_result = app_bitcoin::check_psbt(psbt, context);
             match check_result {
                 Ok(_) => TransactionCheckResult::new().c_ptr(),
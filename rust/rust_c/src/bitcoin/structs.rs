@@ -1,4 +1,5 @@
 use alloc::boxed::Box;
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::ptr::null_mut;
 
@@ -64,6 +65,9 @@ pub struct DisplayTxOverview {
     fee_larger_than_amount: bool,
     sign_status: PtrString,
     need_sign: bool,
+    policy_warnings: PtrString,
+    inscriptions_to_fee: PtrString,
+    sighash_warnings: PtrString,
 }
 
 impl_c_ptr!(DisplayTxOverview);
@@ -80,6 +84,8 @@ pub struct DisplayTxDetail {
     total_output_sat: PtrString,
     fee_sat: PtrString,
     sign_status: PtrString,
+    inscriptions_to_fee: PtrString,
+    runes: PtrString,
 }
 
 impl_c_ptr!(DisplayTxDetail);
@@ -97,6 +103,8 @@ pub struct DisplayTxDetailInput {
     is_mine: bool,
     path: PtrString,
     is_external: bool,
+    inscriptions: PtrString,
+    sighash_warning: PtrString,
 }
 
 #[repr(C)]
@@ -111,6 +119,16 @@ pub struct DisplayTxDetailOutput {
     is_mine: bool,
     path: PtrString,
     is_external: bool,
+    inscriptions: PtrString,
+}
+
+// one entry per line, null when there is nothing to show
+fn convert_lines(lines: Vec<String>) -> PtrString {
+    if lines.is_empty() {
+        null_mut()
+    } else {
+        convert_c_char(lines.join("\n"))
+    }
 }
 
 impl From<ParsedTx> for DisplayTx {
@@ -158,6 +176,15 @@ impl From<OverviewTx> for DisplayTxOverview {
                 null_mut()
             },
             need_sign: value.need_sign,
+            policy_warnings: convert_lines(
+                value
+                    .policy_warnings
+                    .iter()
+                    .map(|v| format!("{}: {}", v.title(), v))
+                    .collect(),
+            ),
+            inscriptions_to_fee: convert_lines(value.inscriptions_to_fee),
+            sighash_warnings: convert_lines(value.sighash_warnings),
         }
     }
 }
@@ -193,6 +220,8 @@ impl From<DetailTx> for DisplayTxDetail {
             } else {
                 null_mut()
             },
+            inscriptions_to_fee: convert_lines(value.inscriptions_to_fee),
+            runes: convert_lines(value.runes),
         }
     }
 }
@@ -206,6 +235,11 @@ impl From<ParsedInput> for DisplayTxDetailInput {
             is_mine: value.path.is_some(),
             path: value.path.map(convert_c_char).unwrap_or(null_mut()),
             is_external: value.is_external,
+            inscriptions: convert_lines(value.inscriptions),
+            sighash_warning: value
+                .sighash_warning
+                .map(convert_c_char)
+                .unwrap_or(null_mut()),
         }
     }
 }
@@ -218,6 +252,7 @@ impl From<ParsedOutput> for DisplayTxDetailOutput {
             is_mine: value.path.is_some(),
             path: value.path.map(convert_c_char).unwrap_or(null_mut()),
             is_external: value.is_external,
+            inscriptions: convert_lines(value.inscriptions),
         }
     }
 }
@@ -255,6 +290,9 @@ impl Free for DisplayTxOverview {
             let _ = Box::from_raw(self.fee_sat);
             let _ = Box::from_raw(self.network);
         }
+        free_str_ptr!(self.policy_warnings);
+        free_str_ptr!(self.inscriptions_to_fee);
+        free_str_ptr!(self.sighash_warnings);
     }
 }
 
@@ -280,6 +318,8 @@ impl Free for DisplayTxDetail {
             let _ = Box::from_raw(self.total_output_sat);
             let _ = Box::from_raw(self.fee_sat);
         }
+        free_str_ptr!(self.inscriptions_to_fee);
+        free_str_ptr!(self.runes);
     }
 }
 
@@ -298,6 +338,8 @@ impl Free for DisplayTxDetailInput {
             let _ = Box::from_raw(self.amount);
             let _ = Box::from_raw(self.path);
         }
+        free_str_ptr!(self.inscriptions);
+        free_str_ptr!(self.sighash_warning);
     }
 }
 
@@ -316,6 +358,7 @@ impl Free for DisplayTxDetailOutput {
             let _ = Box::from_raw(self.amount);
             let _ = Box::from_raw(self.path);
         }
+        free_str_ptr!(self.inscriptions);
     }
 }
 
@@ -117,6 +117,12 @@ pub enum ErrorCodes {
     BitcoinMultiSigInputError,
     BitcoinDerivePublicKeyError,
     BitcoinWalletTypeError,
+    BitcoinWalletPolicyError,
+    BitcoinMuSig2Error,
+    BitcoinBip322Error,
+    BitcoinSilentPaymentError,
+    BitcoinInvalidSighashType,
+    BitcoinBsmsError,
 
     //Ethereum
     EthereumRlpDecodingError = 200,
@@ -132,6 +138,7 @@ pub enum ErrorCodes {
     EthereumHashTypedDataError,
     EthereumInvalidSwapTransaction,
     EthereumInvalidSwapkitMemo,
+    EthereumInvalidBatchTransaction,
     //Tron
     TronInvalidRawTxCryptoBytes = 300,
     TronInvalidParseContext,
@@ -159,6 +166,8 @@ pub enum ErrorCodes {
     SolanaAccountNotFound,
     SolanaProgramError,
     SolanaParseTxError,
+    SolanaLookupTableError,
+    SolanaMessageError,
 
     // Near
     NearKeystoreError = 700,
@@ -301,6 +310,12 @@ impl From<&BitcoinError> for ErrorCodes {
             BitcoinError::MultiSigInputError(_) => Self::BitcoinMultiSigInputError,
             BitcoinError::DerivePublicKeyError(_) => Self::BitcoinDerivePublicKeyError,
             BitcoinError::WalletTypeError(_) => Self::BitcoinWalletTypeError,
+            BitcoinError::WalletPolicyError(_) => Self::BitcoinWalletPolicyError,
+            BitcoinError::MuSig2Error(_) => Self::BitcoinMuSig2Error,
+            BitcoinError::Bip322Error(_) => Self::BitcoinBip322Error,
+            BitcoinError::SilentPaymentError(_) => Self::BitcoinSilentPaymentError,
+            BitcoinError::InvalidSighashType(_) => Self::BitcoinInvalidSighashType,
+            BitcoinError::BsmsError(_) => Self::BitcoinBsmsError,
         }
     }
 }
@@ -356,6 +371,7 @@ impl From<&EthereumError> for ErrorCodes {
             EthereumError::HashTypedDataError(_) => Self::EthereumHashTypedDataError,
             EthereumError::InvalidSwapTransaction(_) => Self::EthereumInvalidSwapTransaction,
             EthereumError::InvalidSwapkitMemo => Self::EthereumInvalidSwapkitMemo,
+            EthereumError::InvalidBatchTransaction(_) => Self::EthereumInvalidBatchTransaction,
         }
     }
 }
@@ -401,6 +417,8 @@ impl From<&SolanaError> for ErrorCodes {
             SolanaError::AccountNotFound(_) => Self::SolanaAccountNotFound,
             SolanaError::AddressError(_) => Self::SolanaAddressEncodingError,
             SolanaError::ParseTxError(_) => Self::SolanaParseTxError,
+            SolanaError::LookupTableError(_) => Self::SolanaLookupTableError,
+            SolanaError::MessageError(_) => Self::SolanaMessageError,
         }
     }
 }
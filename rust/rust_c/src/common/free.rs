@@ -52,6 +52,21 @@ macro_rules! free_vec {
     };
 }
 
+#[macro_export]
+macro_rules! free_str_vec {
+    ($p: expr) => {
+        if !$p.is_null() {
+            unsafe {
+                let x = alloc::boxed::Box::from_raw($p);
+                let ve = Vec::from_raw_parts(x.data, x.size, x.cap);
+                ve.iter().for_each(|v| {
+                    $crate::free_str_ptr!(*v);
+                })
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! free_ptr_with_type {
     ($x: expr, $name: ident) => {
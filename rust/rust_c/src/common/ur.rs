@@ -223,8 +223,12 @@ pub enum ViewType {
     EthPersonalMessage,
     #[cfg(feature = "ethereum")]
     EthTypedData,
+    #[cfg(feature = "ethereum")]
+    EthAuthorization,
     #[cfg(feature = "tron")]
     TronTx,
+    #[cfg(feature = "tron")]
+    TronRawTx,
     #[cfg(feature = "solana")]
     SolanaTx,
     #[cfg(feature = "solana")]
@@ -359,6 +363,8 @@ pub enum QRCodeType {
     #[cfg(feature = "monero")]
     XmrTxUnsignedRequest,
     URTypeUnKnown,
+    #[cfg(feature = "bitcoin")]
+    Bip322Message,
 }
 
 impl QRCodeType {
@@ -486,6 +492,18 @@ impl URParseResult {
             .._self
         }
     }
+
+    // a BIP-322 `to_sign` psbt is signed as a message, not as a transaction
+    #[cfg(feature = "bitcoin")]
+    fn infer_bip322_message(self) -> Self {
+        match self.t {
+            ViewType::BtcMsg => Self {
+                ur_type: QRCodeType::Bip322Message,
+                ..self
+            },
+            _ => self,
+        }
+    }
 }
 
 impl Free for URParseResult {
@@ -502,6 +520,10 @@ fn free_ur(ur_type: &QRCodeType, data: PtrUR) {
         QRCodeType::CryptoPSBT => {
             free_ptr_with_type!(data, CryptoPSBT);
         }
+        #[cfg(feature = "bitcoin")]
+        QRCodeType::Bip322Message => {
+            free_ptr_with_type!(data, CryptoPSBT);
+        }
         QRCodeType::CryptoMultiAccounts => {
             free_ptr_with_type!(data, CryptoMultiAccounts);
         }
@@ -657,6 +679,17 @@ impl URParseMultiResult {
             .._self
         }
     }
+
+    #[cfg(feature = "bitcoin")]
+    fn infer_bip322_message(self) -> Self {
+        match self.t {
+            ViewType::BtcMsg => Self {
+                ur_type: QRCodeType::Bip322Message,
+                ..self
+            },
+            _ => self,
+        }
+    }
 }
 
 impl Free for URParseMultiResult {
@@ -718,7 +751,7 @@ pub fn decode_ur(ur: String) -> URParseResult {
 
     match ur_type {
         #[cfg(feature = "bitcoin")]
-        QRCodeType::CryptoPSBT => _decode_ur::<CryptoPSBT>(ur, ur_type),
+        QRCodeType::CryptoPSBT => _decode_ur::<CryptoPSBT>(ur, ur_type).infer_bip322_message(),
         #[cfg(feature = "bitcoin")]
         QRCodeType::CryptoAccount => _decode_ur::<CryptoAccount>(ur, ur_type),
         QRCodeType::CryptoMultiAccounts => _decode_ur::<CryptoMultiAccounts>(ur, ur_type),
@@ -782,6 +815,10 @@ pub fn decode_ur(ur: String) -> URParseResult {
         QRCodeType::URTypeUnKnown | QRCodeType::SeedSignerMessage => URParseResult::from(
             URError::NotSupportURTypeError("UnKnown ur type".to_string()),
         ),
+        #[cfg(feature = "bitcoin")]
+        QRCodeType::Bip322Message => URParseResult::from(URError::NotSupportURTypeError(
+            "UnKnown ur type".to_string(),
+        )),
     }
 }
 
@@ -820,7 +857,9 @@ fn receive_ur(ur: String, decoder: &mut KeystoneURDecoder) -> URParseMultiResult
     };
     match ur_type {
         #[cfg(feature = "bitcoin")]
-        QRCodeType::CryptoPSBT => _receive_ur::<CryptoPSBT>(ur, ur_type, decoder),
+        QRCodeType::CryptoPSBT => {
+            _receive_ur::<CryptoPSBT>(ur, ur_type, decoder).infer_bip322_message()
+        }
         #[cfg(feature = "bitcoin")]
         QRCodeType::CryptoAccount => _receive_ur::<CryptoAccount>(ur, ur_type, decoder),
         QRCodeType::CryptoMultiAccounts => _receive_ur::<CryptoMultiAccounts>(ur, ur_type, decoder),
@@ -888,6 +927,10 @@ fn receive_ur(ur: String, decoder: &mut KeystoneURDecoder) -> URParseMultiResult
         QRCodeType::URTypeUnKnown | QRCodeType::SeedSignerMessage => URParseMultiResult::from(
             URError::NotSupportURTypeError("UnKnown ur type".to_string()),
         ),
+        #[cfg(feature = "bitcoin")]
+        QRCodeType::Bip322Message => URParseMultiResult::from(URError::NotSupportURTypeError(
+            "UnKnown ur type".to_string(),
+        )),
     }
 }
 
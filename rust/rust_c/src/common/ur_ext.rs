@@ -66,6 +66,8 @@ use ur_registry::ton::ton_sign_request::{DataType, TonSignRequest};
 use ur_registry::zcash::zcash_pczt::ZcashPczt;
 
 use super::ur::ViewType;
+#[cfg(feature = "tron")]
+use crate::tron::request::TronSignRequest;
 
 pub trait InferViewType {
     fn infer(&self) -> Result<ViewType, URError> {
@@ -75,6 +77,9 @@ pub trait InferViewType {
 
 impl InferViewType for CryptoPSBT {
     fn infer(&self) -> Result<ViewType, URError> {
+        if app_bitcoin::bip322::is_bip322_psbt(&self.get_psbt()) {
+            return Ok(ViewType::BtcMsg);
+        }
         Ok(ViewType::BtcTx)
     }
 }
@@ -104,8 +109,13 @@ impl InferViewType for CryptoAccount {
 impl InferViewType for EthSignRequest {
     fn infer(&self) -> Result<ViewType, URError> {
         match self.get_data_type() {
-            eth_sign_request::DataType::Transaction
-            | eth_sign_request::DataType::TypedTransaction => Ok(ViewType::EthTx),
+            eth_sign_request::DataType::Transaction => Ok(ViewType::EthTx),
+            eth_sign_request::DataType::TypedTransaction => match self.get_sign_data().first() {
+                Some(&app_ethereum::authorization::AUTHORIZATION_MAGIC) => {
+                    Ok(ViewType::EthAuthorization)
+                }
+                _ => Ok(ViewType::EthTx),
+            },
             eth_sign_request::DataType::TypedData => Ok(ViewType::EthTypedData),
             eth_sign_request::DataType::PersonalMessage => Ok(ViewType::EthPersonalMessage),
         }
@@ -270,8 +280,12 @@ impl InferViewType for KeystoneSignRequest {
 impl InferViewType for Bytes {
     fn infer(&self) -> Result<ViewType, URError> {
         match from_slice::<Value>(self.get_bytes().as_slice()) {
-            // XRPTx or WebAuth
+            // XRPTx, WebAuth or a tron raw_data sign request
             Ok(_v) => {
+                #[cfg(feature = "tron")]
+                if TronSignRequest::is_sign_request(&_v) {
+                    return Ok(ViewType::TronRawTx);
+                }
                 if let Some(_type) = _v.pointer("/data/type") {
                     let contract_name: String = from_value(_type.clone())
                         .map_err(|e| URError::UrDecodeError(format!("invalid data, {}", e)))?;
@@ -25,6 +25,31 @@ pub extern "C" fn eth_parse_contract_data(
     }
 }
 
+#[no_mangle]
+pub extern "C" fn eth_parse_contract_data_by_selector(
+    input_data: PtrString,
+) -> Ptr<Response<DisplayContractData>> {
+    let input_data = recover_c_char(input_data);
+    let input_data =
+        hex::decode(input_data.clone()).map_err(|_e| RustCError::InvalidHex(input_data.clone()));
+    match input_data {
+        Ok(_input_data) => {
+            let result =
+                app_ethereum::selector_registry::parse_contract_data_by_selector(_input_data);
+            match result {
+                Ok(v) => Response::success_ptr(DisplayContractData::from(v).c_ptr()).c_ptr(),
+                Err(e) => Response::from(e).c_ptr(),
+            }
+        }
+        Err(e) => Response::from(e).c_ptr(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn eth_get_selector_registry_version() -> u32 {
+    app_ethereum::selector_registry::SELECTOR_REGISTRY_VERSION
+}
+
 #[no_mangle]
 pub extern "C" fn eth_parse_swapkit_contract(
     input_data: PtrString,
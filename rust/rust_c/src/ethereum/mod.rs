@@ -3,12 +3,13 @@ use alloc::vec::Vec;
 use alloc::{format, slice};
 
 use app_ethereum::address::derive_address;
-use app_ethereum::batch_tx_rules::rule_swap;
+use app_ethereum::batch_tx_rules::BatchTxPolicy;
 use app_ethereum::erc20::{parse_erc20, parse_erc20_approval};
 use app_ethereum::errors::EthereumError;
 use app_ethereum::{
-    parse_fee_market_tx, parse_legacy_tx, parse_personal_message, parse_typed_data_message,
-    LegacyTransaction, TransactionSignature,
+    parse_access_list_tx, parse_authorization, parse_blob_tx, parse_fee_market_tx, parse_legacy_tx,
+    parse_personal_message, parse_set_code_tx, parse_typed_data_message, LegacyTransaction,
+    TransactionSignature,
 };
 use cryptoxide::hashing::keccak256;
 
@@ -35,8 +36,8 @@ use crate::common::KEYSTONE;
 use crate::extract_ptr_with_type;
 
 use structs::{
-    DisplayETH, DisplayETHBatchTx, DisplayETHPersonalMessage, DisplayETHTypedData,
-    EthParsedErc20Approval, EthParsedErc20Transaction, TransactionType,
+    DisplayETH, DisplayETHAuthorization, DisplayETHBatchTx, DisplayETHPersonalMessage,
+    DisplayETHTypedData, EthParsedErc20Approval, EthParsedErc20Transaction, TransactionType,
 };
 
 mod abi;
@@ -265,6 +266,42 @@ pub extern "C" fn eth_parse(
                                 Err(e) => TransactionParseResult::from(e).c_ptr(),
                             }
                         }
+                        Some(01) => {
+                            //remove envelop
+                            let payload = &crypto_eth.get_sign_data()[1..];
+                            let tx = parse_access_list_tx(payload, key);
+                            match tx {
+                                Ok(t) => {
+                                    TransactionParseResult::success(DisplayETH::from(t).c_ptr())
+                                        .c_ptr()
+                                }
+                                Err(e) => TransactionParseResult::from(e).c_ptr(),
+                            }
+                        }
+                        Some(03) => {
+                            //remove envelop
+                            let payload = &crypto_eth.get_sign_data()[1..];
+                            let tx = parse_blob_tx(payload, key);
+                            match tx {
+                                Ok(t) => {
+                                    TransactionParseResult::success(DisplayETH::from(t).c_ptr())
+                                        .c_ptr()
+                                }
+                                Err(e) => TransactionParseResult::from(e).c_ptr(),
+                            }
+                        }
+                        Some(04) => {
+                            //remove envelop
+                            let payload = &crypto_eth.get_sign_data()[1..];
+                            let tx = parse_set_code_tx(payload, key);
+                            match tx {
+                                Ok(t) => {
+                                    TransactionParseResult::success(DisplayETH::from(t).c_ptr())
+                                        .c_ptr()
+                                }
+                                Err(e) => TransactionParseResult::from(e).c_ptr(),
+                            }
+                        }
                         Some(x) => TransactionParseResult::from(
                             RustCError::UnsupportedTransaction(format!("ethereum tx type:{}", x)),
                         )
@@ -418,6 +455,16 @@ pub extern "C" fn eth_check_then_parse_batch_tx(
                             Err(e) => return TransactionParseResult::from(e).c_ptr(),
                         }
                     }
+                    Some(01) => {
+                        //remove envelop
+                        let payload = &request.get_sign_data()[1..];
+                        let tx = parse_access_list_tx(payload, key);
+                        match tx {
+                            Ok(t) => result.push(t),
+                            Err(e) => return TransactionParseResult::from(e).c_ptr(),
+                        }
+                    }
+                    // blob and set code transactions are never part of a batch
                     Some(x) => {
                         return TransactionParseResult::from(RustCError::UnsupportedTransaction(
                             format!("ethereum tx type:{}", x),
@@ -439,7 +486,7 @@ pub extern "C" fn eth_check_then_parse_batch_tx(
         }
     }
 
-    match rule_swap(result.clone()) {
+    match BatchTxPolicy::default().check(&result) {
         Ok(_) => {
             let display_result = result
                 .iter()
@@ -480,6 +527,9 @@ pub extern "C" fn eth_sign_batch_tx(
                 Some(0x02) => {
                     app_ethereum::sign_fee_markey_tx(request.get_sign_data().to_vec(), seed, &path)
                 }
+                Some(0x01) => {
+                    app_ethereum::sign_access_list_tx(request.get_sign_data().to_vec(), seed, &path)
+                }
                 Some(x) => {
                     return UREncodeResult::from(RustCError::UnsupportedTransaction(format!(
                         "ethereum tx type: {}",
@@ -556,6 +606,41 @@ pub extern "C" fn eth_parse_typed_data(
     }
 }
 
+#[no_mangle]
+pub extern "C" fn eth_parse_authorization(
+    ptr: PtrUR,
+    xpub: PtrString,
+) -> PtrT<TransactionParseResult<DisplayETHAuthorization>> {
+    let crypto_eth = extract_ptr_with_type!(ptr, EthSignRequest);
+    let xpub = recover_c_char(xpub);
+    let pubkey = try_get_eth_public_key(xpub, &crypto_eth);
+
+    let transaction_type = TransactionType::from(crypto_eth.get_data_type());
+
+    match (pubkey, transaction_type) {
+        (Err(e), _) => TransactionParseResult::from(e).c_ptr(),
+        (Ok(key), ty) => match ty {
+            TransactionType::TypedTransaction => {
+                let chain_id = crypto_eth
+                    .get_chain_id()
+                    .and_then(|id| u64::try_from(id).ok());
+                let authorization = parse_authorization(&crypto_eth.get_sign_data(), key, chain_id);
+                match authorization {
+                    Ok(t) => {
+                        TransactionParseResult::success(DisplayETHAuthorization::from(t).c_ptr())
+                            .c_ptr()
+                    }
+                    Err(e) => TransactionParseResult::from(e).c_ptr(),
+                }
+            }
+            _ => TransactionParseResult::from(RustCError::UnsupportedTransaction(
+                "Legacy or PersonalMessage or TypedData".to_string(),
+            ))
+            .c_ptr(),
+        },
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn eth_sign_tx_dynamic(
     ptr: PtrUR,
@@ -581,6 +666,18 @@ pub extern "C" fn eth_sign_tx_dynamic(
             Some(0x02) => {
                 app_ethereum::sign_fee_markey_tx(crypto_eth.get_sign_data().to_vec(), seed, &path)
             }
+            Some(0x01) => {
+                app_ethereum::sign_access_list_tx(crypto_eth.get_sign_data().to_vec(), seed, &path)
+            }
+            Some(0x03) => {
+                app_ethereum::sign_blob_tx(crypto_eth.get_sign_data().to_vec(), seed, &path)
+            }
+            Some(0x04) => {
+                app_ethereum::sign_set_code_tx(crypto_eth.get_sign_data().to_vec(), seed, &path)
+            }
+            Some(0x05) => {
+                app_ethereum::sign_authorization(crypto_eth.get_sign_data().to_vec(), seed, &path)
+            }
             Some(x) => {
                 return UREncodeResult::from(RustCError::UnsupportedTransaction(format!(
                     "ethereum tx type: {}",
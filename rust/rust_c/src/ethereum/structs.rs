@@ -7,12 +7,18 @@ use crate::common::free::Free;
 use crate::common::structs::{Response, TransactionParseResult};
 use crate::common::types::{Ptr, PtrString, PtrT};
 use crate::common::utils::convert_c_char;
-use crate::{check_and_free_ptr, free_str_ptr, free_vec, impl_c_ptr, make_free_method};
+use crate::{
+    check_and_free_ptr, free_str_ptr, free_str_vec, free_vec, impl_c_ptr, make_free_method,
+};
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use app_ethereum::abi::{ContractData, ContractMethodParam};
+use app_ethereum::authorization::ParsedAuthorization;
 use app_ethereum::erc20::encode_erc20_transfer_calldata;
-use app_ethereum::structs::{ParsedEthereumTransaction, PersonalMessage, TypedData};
+use app_ethereum::structs::{
+    ParsedAccessListItem, ParsedEthereumTransaction, PersonalMessage, TypedData,
+};
+use app_ethereum::typed_data_summary::{ConsiderationItem, TokenAllowance, TypedDataSummary};
 use core::ptr::null_mut;
 use core::str::FromStr;
 use itertools::Itertools;
@@ -81,6 +87,11 @@ impl TryFrom<EthTx> for DisplayETH {
                 to: convert_c_char(contract_address),
                 nonce: convert_c_char(eth_tx.nonce.to_string()),
                 input: convert_c_char(input_data),
+                access_list: null_mut(),
+                authorization_list: null_mut(),
+                max_fee_per_blob_gas: null_mut(),
+                max_blob_fee: null_mut(),
+                blob_versioned_hashes: null_mut(),
             };
             let display_eth = DisplayETH {
                 tx_type: convert_c_char("Legacy".to_string()),
@@ -118,6 +129,11 @@ impl TryFrom<EthTx> for DisplayETH {
                 to: convert_c_char(eth_tx.to),
                 nonce: convert_c_char(eth_tx.nonce.to_string()),
                 input: convert_c_char(eth_tx.memo),
+                access_list: null_mut(),
+                authorization_list: null_mut(),
+                max_fee_per_blob_gas: null_mut(),
+                max_blob_fee: null_mut(),
+                blob_versioned_hashes: null_mut(),
             };
             let display_eth = DisplayETH {
                 tx_type: convert_c_char("Legacy".to_string()),
@@ -176,6 +192,14 @@ pub struct DisplayETHDetail {
     nonce: PtrString,
 
     input: PtrString,
+
+    access_list: PtrT<VecFFI<DisplayETHAccessListItem>>,
+
+    authorization_list: PtrT<VecFFI<DisplayETHAuthorization>>,
+
+    max_fee_per_blob_gas: PtrString,
+    max_blob_fee: PtrString,
+    blob_versioned_hashes: PtrT<VecFFI<PtrString>>,
 }
 
 impl_c_ptr!(DisplayETHDetail);
@@ -194,6 +218,40 @@ impl Free for DisplayETHDetail {
         free_str_ptr!(self.max_priority_price);
         free_str_ptr!(self.nonce);
         free_str_ptr!(self.input);
+        free_vec!(self.access_list);
+        free_vec!(self.authorization_list);
+        free_str_ptr!(self.max_fee_per_blob_gas);
+        free_str_ptr!(self.max_blob_fee);
+        free_str_vec!(self.blob_versioned_hashes);
+    }
+}
+
+#[repr(C)]
+pub struct DisplayETHAccessListItem {
+    address: PtrString,
+    storage_keys: PtrT<VecFFI<PtrString>>,
+}
+
+impl From<ParsedAccessListItem> for DisplayETHAccessListItem {
+    fn from(value: ParsedAccessListItem) -> Self {
+        Self {
+            address: convert_c_char(value.address),
+            storage_keys: VecFFI::from(
+                value
+                    .storage_keys
+                    .into_iter()
+                    .map(convert_c_char)
+                    .collect_vec(),
+            )
+            .c_ptr(),
+        }
+    }
+}
+
+impl Free for DisplayETHAccessListItem {
+    fn free(&self) {
+        free_str_ptr!(self.address);
+        free_str_vec!(self.storage_keys);
     }
 }
 
@@ -212,10 +270,7 @@ impl Free for DisplayETH {
 impl From<ParsedEthereumTransaction> for DisplayETH {
     fn from(value: ParsedEthereumTransaction) -> Self {
         Self {
-            tx_type: match value.max_priority_fee_per_gas {
-                Some(_) => convert_c_char(String::from("FeeMarket")),
-                None => convert_c_char(String::from("Legacy")),
-            },
+            tx_type: convert_c_char(value.tx_type.get_type_name()),
             chain_id: value.chain_id,
             overview: DisplayETHOverview::from(value.clone()).c_ptr(),
             detail: DisplayETHDetail::from(value.clone()).c_ptr(),
@@ -257,10 +312,72 @@ impl From<ParsedEthereumTransaction> for DisplayETHDetail {
             to: convert_c_char(tx.to),
             nonce: convert_c_char(tx.nonce.to_string()),
             input: convert_c_char(tx.input),
+            access_list: VecFFI::from(
+                tx.access_list
+                    .into_iter()
+                    .map(DisplayETHAccessListItem::from)
+                    .collect_vec(),
+            )
+            .c_ptr(),
+            authorization_list: VecFFI::from(
+                tx.authorization_list
+                    .into_iter()
+                    .map(DisplayETHAuthorization::from)
+                    .collect_vec(),
+            )
+            .c_ptr(),
+            max_fee_per_blob_gas: tx
+                .max_fee_per_blob_gas
+                .map(convert_c_char)
+                .unwrap_or(null_mut()),
+            max_blob_fee: tx.max_blob_fee.map(convert_c_char).unwrap_or(null_mut()),
+            blob_versioned_hashes: VecFFI::from(
+                tx.blob_versioned_hashes
+                    .into_iter()
+                    .map(convert_c_char)
+                    .collect_vec(),
+            )
+            .c_ptr(),
+        }
+    }
+}
+
+#[repr(C)]
+pub struct DisplayETHAuthorization {
+    chain_id: PtrString,
+    nonce: PtrString,
+    delegate: PtrString,
+    authority: PtrString,
+    is_any_chain: bool,
+    is_chain_mismatch: bool,
+    is_revocation: bool,
+}
+
+impl From<ParsedAuthorization> for DisplayETHAuthorization {
+    fn from(value: ParsedAuthorization) -> Self {
+        Self {
+            chain_id: convert_c_char(value.chain_id),
+            nonce: convert_c_char(value.nonce),
+            delegate: convert_c_char(value.delegate),
+            authority: value.authority.map(convert_c_char).unwrap_or(null_mut()),
+            is_any_chain: value.is_any_chain,
+            is_chain_mismatch: value.is_chain_mismatch,
+            is_revocation: value.is_revocation,
         }
     }
 }
 
+impl_c_ptr!(DisplayETHAuthorization);
+
+impl Free for DisplayETHAuthorization {
+    fn free(&self) {
+        free_str_ptr!(self.chain_id);
+        free_str_ptr!(self.nonce);
+        free_str_ptr!(self.delegate);
+        free_str_ptr!(self.authority);
+    }
+}
+
 #[repr(C)]
 pub struct DisplayETHPersonalMessage {
     raw_message: PtrString,
@@ -305,6 +422,8 @@ pub struct DisplayETHTypedData {
     domain_hash: PtrString,
     message_hash: PtrString,
     safe_tx_hash: PtrString,
+    // null unless the message is a recognised Permit / Permit2 / Seaport approval
+    risk_summary: PtrT<DisplayETHTypedDataSummary>,
 }
 
 impl From<TypedData> for DisplayETHTypedData {
@@ -318,6 +437,10 @@ impl From<TypedData> for DisplayETHTypedData {
         }
 
         let safe_tx_hash = message.get_safe_tx_hash();
+        let risk_summary = message
+            .get_risk_summary()
+            .map(|v| DisplayETHTypedDataSummary::from(v).c_ptr())
+            .unwrap_or(null_mut());
 
         Self {
             name: to_ptr_string(message.name),
@@ -331,6 +454,7 @@ impl From<TypedData> for DisplayETHTypedData {
             domain_hash: to_ptr_string(message.domain_separator),
             message_hash: to_ptr_string(message.message_hash),
             safe_tx_hash: to_ptr_string(safe_tx_hash),
+            risk_summary,
         }
     }
 }
@@ -350,6 +474,125 @@ impl Free for DisplayETHTypedData {
         free_str_ptr!(self.domain_hash);
         free_str_ptr!(self.message_hash);
         free_str_ptr!(self.safe_tx_hash);
+        if !self.risk_summary.is_null() {
+            unsafe {
+                let x = Box::from_raw(self.risk_summary);
+                x.free();
+            }
+        }
+    }
+}
+
+#[repr(C)]
+pub struct DisplayETHTypedDataSummary {
+    kind: PtrString,
+    owner: PtrString,
+    spender: PtrString,
+    deadline: PtrString,
+    tokens: PtrT<VecFFI<DisplayETHTokenAllowance>>,
+    consideration: PtrT<VecFFI<DisplayETHConsiderationItem>>,
+    has_unlimited_amount: bool,
+    has_recipient_not_signer: bool,
+    warning: PtrString,
+}
+
+impl From<TypedDataSummary> for DisplayETHTypedDataSummary {
+    fn from(value: TypedDataSummary) -> Self {
+        let has_unlimited_amount = value.has_unlimited_amount();
+        let has_recipient_not_signer = value.has_recipient_not_signer();
+        Self {
+            kind: convert_c_char(value.kind.get_name()),
+            owner: value.owner.map(convert_c_char).unwrap_or(null_mut()),
+            spender: value.spender.map(convert_c_char).unwrap_or(null_mut()),
+            deadline: value.deadline.map(convert_c_char).unwrap_or(null_mut()),
+            tokens: VecFFI::from(
+                value
+                    .tokens
+                    .into_iter()
+                    .map(DisplayETHTokenAllowance::from)
+                    .collect_vec(),
+            )
+            .c_ptr(),
+            consideration: VecFFI::from(
+                value
+                    .consideration
+                    .into_iter()
+                    .map(DisplayETHConsiderationItem::from)
+                    .collect_vec(),
+            )
+            .c_ptr(),
+            has_unlimited_amount,
+            has_recipient_not_signer,
+            warning: value.warning.map(convert_c_char).unwrap_or(null_mut()),
+        }
+    }
+}
+
+impl_c_ptr!(DisplayETHTypedDataSummary);
+
+impl Free for DisplayETHTypedDataSummary {
+    fn free(&self) {
+        free_str_ptr!(self.kind);
+        free_str_ptr!(self.owner);
+        free_str_ptr!(self.spender);
+        free_str_ptr!(self.deadline);
+        free_vec!(self.tokens);
+        free_vec!(self.consideration);
+        free_str_ptr!(self.warning);
+    }
+}
+
+#[repr(C)]
+pub struct DisplayETHTokenAllowance {
+    token: PtrString,
+    amount: PtrString,
+    is_unlimited: bool,
+    expiration: PtrString,
+}
+
+impl From<TokenAllowance> for DisplayETHTokenAllowance {
+    fn from(value: TokenAllowance) -> Self {
+        Self {
+            token: convert_c_char(value.token),
+            amount: convert_c_char(value.amount),
+            is_unlimited: value.is_unlimited,
+            expiration: value.expiration.map(convert_c_char).unwrap_or(null_mut()),
+        }
+    }
+}
+
+impl Free for DisplayETHTokenAllowance {
+    fn free(&self) {
+        free_str_ptr!(self.token);
+        free_str_ptr!(self.amount);
+        free_str_ptr!(self.expiration);
+    }
+}
+
+#[repr(C)]
+pub struct DisplayETHConsiderationItem {
+    recipient: PtrString,
+    token: PtrString,
+    amount: PtrString,
+    is_signer: bool,
+}
+
+impl From<ConsiderationItem> for DisplayETHConsiderationItem {
+    fn from(value: ConsiderationItem) -> Self {
+        Self {
+            recipient: convert_c_char(value.recipient),
+            token: convert_c_char(value.token),
+            amount: convert_c_char(value.amount),
+            is_signer: value.is_signer,
+        }
+    }
+}
+
+impl Free for DisplayETHConsiderationItem {
+    fn free(&self) {
+        free_str_ptr!(self.recipient);
+        free_str_ptr!(self.token);
+        free_str_ptr!(self.amount);
     }
 }
 
@@ -376,6 +619,7 @@ pub struct DisplayContractData {
     pub contract_name: PtrString,
     pub method_name: PtrString,
     pub params: PtrT<VecFFI<DisplayContractParam>>,
+    pub is_signature_inferred: bool,
 }
 
 impl_c_ptr!(DisplayContractData);
@@ -393,6 +637,7 @@ impl From<ContractData> for DisplayContractData {
                     .collect_vec(),
             )
             .c_ptr(),
+            is_signature_inferred: value.get_is_signature_inferred(),
         }
     }
 }
@@ -552,6 +797,7 @@ impl_c_ptr!(DisplayETHBatchTx);
 make_free_method!(TransactionParseResult<DisplayETH>);
 make_free_method!(TransactionParseResult<DisplayETHPersonalMessage>);
 make_free_method!(TransactionParseResult<DisplayETHTypedData>);
+make_free_method!(TransactionParseResult<DisplayETHAuthorization>);
 make_free_method!(TransactionParseResult<DisplayETHBatchTx>);
 make_free_method!(Response<DisplayContractData>);
 make_free_method!(TransactionParseResult<EthParsedErc20Transaction>);
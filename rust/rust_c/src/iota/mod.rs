@@ -4,17 +4,14 @@ use crate::common::types::{PtrBytes, PtrString, PtrT, PtrUR};
 use crate::common::ur::{UREncodeResult, FRAGMENT_MAX_LENGTH_DEFAULT};
 use crate::common::utils::{convert_c_char, recover_c_char};
 use crate::extract_ptr_with_type;
-use crate::sui::get_public_key;
 use alloc::vec::Vec;
 use alloc::{format, slice};
 use alloc::{
     string::{String, ToString},
     vec,
 };
-use app_sui::errors::SuiError;
-use app_sui::Intent;
-use bitcoin::bip32::DerivationPath;
-use core::str::FromStr;
+use app_iota::errors::IotaError;
+use app_iota::parser::structs::IotaTxDisplayType;
 use cty::c_char;
 use structs::DisplayIotaIntentData;
 use structs::DisplayIotaSignMessageHash;
@@ -41,25 +38,18 @@ pub extern "C" fn iota_parse_intent(
 ) -> PtrT<TransactionParseResult<DisplayIotaIntentData>> {
     let sign_request = extract_ptr_with_type!(ptr, IotaSignRequest);
     let sign_data = sign_request.get_intent_message();
-    let address = format!(
-        "0x{}",
-        hex::encode(sign_request.get_addresses().unwrap_or(vec![])[0].clone())
-    );
-    let data = app_sui::parse_intent(&sign_data);
-    match data {
-        Ok(data) => match data {
-            Intent::TransactionData(ref transaction_data) => {
-                TransactionParseResult::success(DisplayIotaIntentData::from(data).c_ptr()).c_ptr()
+    match app_iota::parse_intent(&sign_data) {
+        Ok(parsed) => {
+            let is_message = parsed.display_type == IotaTxDisplayType::Message;
+            let mut display = DisplayIotaIntentData::from(parsed);
+            // personal messages do not carry the signer, show the address of the request
+            if is_message {
+                if let Some(address) = sign_request.get_addresses().unwrap_or_default().first() {
+                    display = display.with_address(format!("0x{}", hex::encode(address)));
+                }
             }
-            Intent::PersonalMessage(ref personal_message) => TransactionParseResult::success(
-                DisplayIotaIntentData::from(data)
-                    .with_address(address)
-                    .c_ptr(),
-            )
-            .c_ptr(),
-            _ => TransactionParseResult::from(SuiError::InvalidData("Invalid intent".to_string()))
-                .c_ptr(),
-        },
+            TransactionParseResult::success(display.c_ptr()).c_ptr()
+        }
         Err(e) => TransactionParseResult::from(e).c_ptr(),
     }
 }
@@ -97,17 +87,17 @@ pub extern "C" fn iota_sign_hash(
     let path = match sign_request.get_derivation_paths()[0].get_path() {
         Some(p) => p,
         None => {
-            return UREncodeResult::from(SuiError::SignFailure(
+            return UREncodeResult::from(IotaError::SignFailure(
                 "invalid derivation path".to_string(),
             ))
             .c_ptr()
         }
     };
-    let signature = match app_sui::sign_hash(seed, &path, &hex::decode(hash).unwrap()) {
+    let signature = match app_iota::sign_hash(seed, &path, &hex::decode(hash).unwrap()) {
         Ok(v) => v,
         Err(e) => return UREncodeResult::from(e).c_ptr(),
     };
-    let pub_key = match get_public_key(seed, &path) {
+    let pub_key = match app_iota::get_public_key(seed, &path) {
         Ok(v) => v,
         Err(e) => return UREncodeResult::from(e).c_ptr(),
     };
@@ -140,17 +130,17 @@ pub extern "C" fn iota_sign_intent(
     let path = match sign_request.get_derivation_paths()[0].get_path() {
         Some(p) => p,
         None => {
-            return UREncodeResult::from(SuiError::SignFailure(
+            return UREncodeResult::from(IotaError::SignFailure(
                 "invalid derivation path".to_string(),
             ))
             .c_ptr()
         }
     };
-    let signature = match app_sui::sign_intent(seed, &path, &sign_data.to_vec()) {
+    let signature = match app_iota::sign_intent(seed, &path, &sign_data) {
         Ok(v) => v,
         Err(e) => return UREncodeResult::from(e).c_ptr(),
     };
-    let pub_key = match get_public_key(seed, &path) {
+    let pub_key = match app_iota::get_public_key(seed, &path) {
         Ok(v) => v,
         Err(e) => return UREncodeResult::from(e).c_ptr(),
     };
@@ -1,29 +1,14 @@
-use alloc::format;
-use alloc::string::ToString;
-use alloc::vec::Vec;
-use alloc::{boxed::Box, string::String};
-use app_cardano::structs::{
-    CardanoCertificate, CardanoFrom, CardanoTo, CardanoWithdrawal, ParsedCardanoSignCip8Data,
-    ParsedCardanoSignData, ParsedCardanoTx, VotingProcedure, VotingProposal,
-};
+use alloc::string::{String, ToString};
+use app_iota::parser::overview::IotaTxOverview;
+use app_iota::parser::structs::{IotaTxDisplayType, ParsedIotaTx};
 use core::ptr::null_mut;
-use hex;
-use itertools::Itertools;
 
-use crate::common::ffi::VecFFI;
-use crate::common::free::{free_ptr_string, Free};
+use crate::common::free::Free;
 use crate::common::structs::TransactionParseResult;
-use crate::common::types::{Ptr, PtrString, PtrT};
+use crate::common::types::{PtrString, PtrT};
 use crate::common::utils::convert_c_char;
-use crate::{
-    check_and_free_ptr, free_str_ptr, free_vec, impl_c_ptr, impl_c_ptrs, make_free_method,
-};
+use crate::{free_str_ptr, impl_c_ptr, make_free_method};
 use app_ethereum::address::checksum_address;
-use app_sui::Intent;
-use sui_types::{
-    message::PersonalMessage,
-    transaction::{CallArg, Command, TransactionData, TransactionDataV1, TransactionKind},
-};
 
 #[repr(C)]
 pub struct DisplayIotaSignData {
@@ -44,187 +29,92 @@ pub struct DisplayIotaIntentData {
     method: PtrString,
     message: PtrString,
     to: PtrString,
+    max_fee: PtrString,
+    staked_iota: PtrString,
 }
 
 impl_c_ptr!(DisplayIotaIntentData);
 
 impl DisplayIotaIntentData {
     pub fn with_address(mut self, address: String) -> Self {
+        free_str_ptr!(self.sender);
         self.sender = convert_c_char(address);
         self
     }
 }
 
-impl From<Intent> for DisplayIotaIntentData {
-    fn from(value: Intent) -> Self {
-        match value {
-            Intent::TransactionData(transaction_data) => {
-                let TransactionData::V1(data) = transaction_data.value else {
-                    unreachable!("Only V1 transactions are supported")
-                };
-                let details = serde_json::to_string(&data)
-                    .unwrap_or_else(|_| "Failed to serialize transaction data".to_string());
-
-                let TransactionKind::ProgrammableTransaction(kind_data) = data.kind else {
-                    unreachable!("Only ProgrammableTransaction is supported")
-                };
-
-                let method_string = get_method(&kind_data.commands);
-                let method = match method_string.as_str() {
-                    "stake" => convert_c_char("Stake".to_string()),
-                    "bridge" => convert_c_char("Bridge".to_string()),
-                    _ => null_mut(),
-                };
-                let (amount, recipient, to) = extract_transaction_params(
-                    &kind_data.inputs,
-                    &kind_data.commands,
-                    &method_string,
-                );
-
-                Self {
-                    amount,
-                    recipient,
-                    network: convert_c_char("IOTA Mainnet".to_string()),
-                    sender: convert_c_char(data.sender.to_string()),
-                    details: convert_c_char(details),
-                    transaction_type: convert_c_char("Programmable Transaction".to_string()),
-                    method,
-                    message: null_mut(),
-                    to,
-                }
-            }
-            Intent::PersonalMessage(personal_message) => Self {
-                amount: null_mut(),
-                recipient: null_mut(),
-                network: null_mut(),
-                sender: null_mut(),
-                details: null_mut(),
-                transaction_type: convert_c_char("Message".to_string()),
-                method: null_mut(),
-                message: convert_c_char(
-                    base64::decode(&personal_message.value.message)
-                        .ok()
-                        .and_then(|bytes| String::from_utf8(bytes).ok())
-                        .unwrap_or_else(|| personal_message.value.message.clone()),
-                ),
-                to: null_mut(),
-            },
-            _ => todo!("Other Intent types not implemented"),
+impl Default for DisplayIotaIntentData {
+    fn default() -> Self {
+        Self {
+            amount: null_mut(),
+            network: null_mut(),
+            sender: null_mut(),
+            recipient: null_mut(),
+            details: null_mut(),
+            transaction_type: null_mut(),
+            method: null_mut(),
+            message: null_mut(),
+            to: null_mut(),
+            max_fee: null_mut(),
+            staked_iota: null_mut(),
         }
     }
 }
 
-fn extract_transaction_params(
-    args: &Vec<CallArg>,
-    commands: &Vec<Command>,
-    method_string: &String,
-) -> (PtrString, PtrString, PtrString) {
-    let pure_args = args
-        .iter()
-        .filter_map(|arg| {
-            if let CallArg::Pure(data) = arg {
-                Some(data)
-            } else {
-                None
+impl From<ParsedIotaTx> for DisplayIotaIntentData {
+    fn from(value: ParsedIotaTx) -> Self {
+        let transaction_type = match value.display_type {
+            IotaTxDisplayType::Message => "Message".to_string(),
+            _ => "Programmable Transaction".to_string(),
+        };
+        let method = match value.display_type {
+            IotaTxDisplayType::Stake | IotaTxDisplayType::Unstake | IotaTxDisplayType::Bridge => {
+                convert_c_char(value.display_type.to_string())
             }
-        })
-        .collect::<Vec<_>>();
-
-    let amounts: Vec<u64> = pure_args
-        .iter()
-        .filter_map(|bytes| {
-            if bytes.len() == 8 {
-                Some(u64::from_le_bytes(
-                    bytes.as_slice().try_into().unwrap_or([0; 8]),
-                ))
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    let amount = if amounts.len() >= 2 {
-        let max_amount = amounts.iter().max().unwrap_or(&0);
-        let min_amount = amounts.iter().min().unwrap_or(&0);
-        let net_amount = max_amount - min_amount;
-        convert_c_char(format!("{} IOTA", net_amount as f64 / 1000_000_000.0))
-    } else if amounts.len() == 1 {
-        convert_c_char(format!("{} IOTA", amounts[0] as f64 / 1000_000_000.0))
-    } else {
-        let has_transfer_gas_coin = commands.iter().any(|command| {
-            if let Command::TransferObjects(objects, target) = command {
-                objects.len() == 1
-                    && matches!(objects[0], sui_types::transaction::Argument::GasCoin)
-                    && matches!(target, sui_types::transaction::Argument::Input(0))
-            } else {
-                false
-            }
-        });
-        if has_transfer_gas_coin {
-            convert_c_char("max".to_string())
-        } else {
-            null_mut()
+            _ => null_mut(),
+        };
+        let display = Self {
+            network: convert_c_char(value.network),
+            details: convert_c_char(value.detail),
+            transaction_type: convert_c_char(transaction_type),
+            method,
+            max_fee: value.max_fee.map(convert_c_char).unwrap_or(null_mut()),
+            ..Default::default()
+        };
+        match value.overview {
+            IotaTxOverview::Transfer(overview) => Self {
+                sender: convert_c_char(overview.from),
+                recipient: convert_c_char(overview.to),
+                amount: convert_c_char(overview.amount),
+                ..display
+            },
+            IotaTxOverview::Stake(overview) => Self {
+                sender: convert_c_char(overview.from),
+                recipient: convert_c_char(overview.validator),
+                amount: convert_c_char(overview.amount),
+                ..display
+            },
+            IotaTxOverview::Unstake(overview) => Self {
+                sender: convert_c_char(overview.from),
+                staked_iota: convert_c_char(overview.staked_iota),
+                ..display
+            },
+            IotaTxOverview::Bridge(overview) => Self {
+                sender: convert_c_char(overview.from),
+                to: convert_c_char(checksum_address(&overview.to).unwrap_or(overview.to)),
+                amount: convert_c_char(overview.amount),
+                ..display
+            },
+            IotaTxOverview::General(overview) => Self {
+                sender: convert_c_char(overview.from),
+                ..display
+            },
+            IotaTxOverview::Message(overview) => Self {
+                message: convert_c_char(overview.message),
+                ..display
+            },
         }
-    };
-
-    let (recipient, to) = if method_string.contains("bridge") {
-        let to = pure_args
-            .iter()
-            .find(|bytes| bytes.len() == (20 + 1 + 1 + 1))
-            .map(|bytes| {
-                convert_c_char(
-                    checksum_address(&format!("0x{}", hex::encode(&bytes[3..]))).unwrap(),
-                )
-            })
-            .unwrap_or(null_mut());
-        (null_mut(), to)
-    } else {
-        let recipient = pure_args
-            .iter()
-            .find(|bytes| bytes.len() == 32)
-            .map(|bytes| convert_c_char(format!("0x{}", hex::encode(bytes))))
-            .unwrap_or(null_mut());
-        (recipient, null_mut())
-    };
-
-    (amount, recipient, to)
-}
-
-fn get_method(commands: &Vec<Command>) -> String {
-    let move_calls: Vec<_> = commands
-        .iter()
-        .filter_map(|command| {
-            if let Command::MoveCall(kind_data) = command {
-                Some(kind_data)
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    let has_assets_bag_new = move_calls
-        .iter()
-        .any(|call| call.module.to_string() == "assets_bag" && call.function.to_string() == "new");
-    let has_request_create = move_calls.iter().any(|call| {
-        call.module.to_string() == "request"
-            && call.function.to_string() == "create_and_send_request"
-    });
-
-    if has_assets_bag_new && has_request_create {
-        return "bridge".to_string();
     }
-
-    if move_calls
-        .iter()
-        .any(|call| call.function.to_string().contains("stake"))
-    {
-        return "stake".to_string();
-    }
-
-    move_calls
-        .first()
-        .map(|call| call.function.to_string())
-        .unwrap_or_default()
 }
 
 #[repr(C)]
@@ -270,6 +160,8 @@ impl Free for DisplayIotaIntentData {
         free_str_ptr!(self.amount);
         free_str_ptr!(self.message);
         free_str_ptr!(self.to);
+        free_str_ptr!(self.max_fee);
+        free_str_ptr!(self.staked_iota);
     }
 }
 
@@ -28,7 +28,8 @@ fn build_sign_result(ptr: PtrUR, seed: &[u8]) -> Result<SolSignature, SolanaErro
     if !path.starts_with("m/") {
         path = format!("m/{}", path);
     }
-    let signature = app_solana::sign(sign_request.get_sign_data().to_vec(), &path, seed)?;
+    let sign_data = app_solana::prepare_sign_data(&sign_request.get_sign_data(), &path, seed)?;
+    let signature = app_solana::sign(sign_data, &path, seed)?;
     Ok(SolSignature::new(
         sign_request.get_request_id(),
         signature.to_vec(),
@@ -85,8 +86,8 @@ pub extern "C" fn solana_check(
 pub extern "C" fn solana_parse_tx(ptr: PtrUR) -> PtrT<TransactionParseResult<DisplaySolanaTx>> {
     let solan_sign_reqeust = extract_ptr_with_type!(ptr, SolSignRequest);
     let tx_hex = solan_sign_reqeust.get_sign_data();
-    match app_solana::parse(&tx_hex.to_vec()) {
-        Ok(v) => TransactionParseResult::success(DisplaySolanaTx::from(v).c_ptr()).c_ptr(),
+    match app_solana::parse(&tx_hex.to_vec()).and_then(DisplaySolanaTx::try_from) {
+        Ok(v) => TransactionParseResult::success(v.c_ptr()).c_ptr(),
         Err(e) => TransactionParseResult::from(e).c_ptr(),
     }
 }
@@ -1,10 +1,15 @@
 use alloc::boxed::Box;
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::ptr::null_mut;
 
-use app_solana::parser::overview::{ProgramOverviewGeneral, SolanaOverview};
+use app_solana::errors::SolanaError;
+use app_solana::parser::balance_change::{BalanceChange, BalanceChanges, UnknownEffect};
+use app_solana::parser::overview::{
+    ProgramOverview, ProgramOverviewGeneral, ProgramOverviewNetworkFee,
+};
 use app_solana::parser::structs::{ParsedSolanaTx, SolanaTxDisplayType};
+use app_solana::siws::SignInWithSolana;
 use app_solana::structs::SolanaMessage;
 use itertools::Itertools;
 
@@ -20,6 +25,122 @@ pub struct DisplaySolanaTx {
     pub network: PtrString,
     pub overview: PtrT<DisplaySolanaTxOverview>,
     pub detail: PtrString,
+    pub balance_changes: PtrT<DisplaySolanaTxBalanceChanges>,
+}
+
+#[repr(C)]
+pub struct DisplaySolanaTxBalanceChange {
+    pub account: PtrString,
+    pub token: PtrString,
+    // null for native SOL
+    pub mint: PtrString,
+    pub amount: PtrString,
+    pub unknown_effect: bool,
+}
+
+impl Free for DisplaySolanaTxBalanceChange {
+    fn free(&self) {
+        free_str_ptr!(self.account);
+        free_str_ptr!(self.token);
+        free_str_ptr!(self.mint);
+        free_str_ptr!(self.amount);
+    }
+}
+
+impl DisplaySolanaTxBalanceChange {
+    fn new(value: &BalanceChange, amount: String) -> Self {
+        Self {
+            account: convert_c_char(value.account.to_string()),
+            token: convert_c_char(value.symbol.to_string()),
+            mint: value.mint.clone().map(convert_c_char).unwrap_or(null_mut()),
+            amount: convert_c_char(amount),
+            unknown_effect: value.unknown_effect,
+        }
+    }
+}
+
+#[repr(C)]
+pub struct DisplaySolanaTxUnknownEffect {
+    pub program: PtrString,
+    pub method: PtrString,
+}
+
+impl Free for DisplaySolanaTxUnknownEffect {
+    fn free(&self) {
+        free_str_ptr!(self.program);
+        free_str_ptr!(self.method);
+    }
+}
+
+impl From<&UnknownEffect> for DisplaySolanaTxUnknownEffect {
+    fn from(value: &UnknownEffect) -> Self {
+        Self {
+            program: convert_c_char(value.program.to_string()),
+            method: convert_c_char(value.method.to_string()),
+        }
+    }
+}
+
+// summary page of what the transaction does to every balance it touches
+#[repr(C)]
+pub struct DisplaySolanaTxBalanceChanges {
+    pub changes: PtrT<VecFFI<DisplaySolanaTxBalanceChange>>,
+    pub unknown_effects: PtrT<VecFFI<DisplaySolanaTxUnknownEffect>>,
+}
+
+impl_c_ptr!(DisplaySolanaTxBalanceChanges);
+
+impl Free for DisplaySolanaTxBalanceChanges {
+    fn free(&self) {
+        unsafe {
+            if !self.changes.is_null() {
+                let x = Box::from_raw(self.changes);
+                let ve = Vec::from_raw_parts(x.data, x.size, x.cap);
+                ve.iter().for_each(|v| {
+                    v.free();
+                });
+            }
+            if !self.unknown_effects.is_null() {
+                let x = Box::from_raw(self.unknown_effects);
+                let ve = Vec::from_raw_parts(x.data, x.size, x.cap);
+                ve.iter().for_each(|v| {
+                    v.free();
+                });
+            }
+        }
+    }
+}
+
+impl TryFrom<&BalanceChanges> for DisplaySolanaTxBalanceChanges {
+    type Error = SolanaError;
+
+    fn try_from(value: &BalanceChanges) -> Result<Self, Self::Error> {
+        // every amount is formatted before any string is handed out, so nothing leaks on error
+        let amounts = value
+            .changes
+            .iter()
+            .map(BalanceChange::amount)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            changes: VecFFI::from(
+                value
+                    .changes
+                    .iter()
+                    .zip(amounts)
+                    .map(|(change, amount)| DisplaySolanaTxBalanceChange::new(change, amount))
+                    .collect_vec(),
+            )
+            .c_ptr(),
+            unknown_effects: VecFFI::from(
+                value
+                    .unknown_effects
+                    .iter()
+                    .map(DisplaySolanaTxUnknownEffect::from)
+                    .collect_vec(),
+            )
+            .c_ptr(),
+        })
+    }
 }
 
 #[repr(C)]
@@ -54,6 +175,8 @@ impl_c_ptrs!(DisplaySolanaTxOverviewUnknownInstructions);
 #[repr(C)]
 pub struct Instruction {
     pub accounts: PtrT<VecFFI<PtrString>>,
+    // for every account, whether it was loaded from an address lookup table
+    pub from_lookup_table: PtrT<VecFFI<bool>>,
     pub data: PtrString,
     pub program_address: PtrString,
 }
@@ -77,6 +200,12 @@ impl Free for DisplaySolanaTxOverviewUnknownInstructions {
                     accounts.iter().for_each(|a| {
                         free_str_ptr!(*a);
                     });
+                    let from_lookup_table = Box::from_raw(v.from_lookup_table);
+                    Vec::from_raw_parts(
+                        from_lookup_table.data,
+                        from_lookup_table.size,
+                        from_lookup_table.cap,
+                    );
                     free_str_ptr!(v.data);
                     free_str_ptr!(v.program_address);
                 });
@@ -108,6 +237,10 @@ pub struct DisplaySolanaTxSplTokenTransferOverview {
     pub authority: PtrString,
     pub decimals: u8,
     pub amount: PtrString,
+    // null unless the Token-2022 transfer carries a fee or a transfer hook
+    pub fee: PtrString,
+    pub transfer_hook: PtrString,
+    pub transfer_hook_verified: bool,
     pub token_mint_account: PtrString,
     pub token_symbol: PtrString,
     pub token_name: PtrString,
@@ -119,6 +252,8 @@ impl Free for DisplaySolanaTxSplTokenTransferOverview {
         free_str_ptr!(self.destination);
         free_str_ptr!(self.authority);
         free_str_ptr!(self.amount);
+        free_str_ptr!(self.fee);
+        free_str_ptr!(self.transfer_hook);
         free_str_ptr!(self.token_mint_account);
         free_str_ptr!(self.token_symbol);
         free_str_ptr!(self.token_name);
@@ -175,6 +310,36 @@ impl Free for DisplaySolanaTxOverviewJupiterV6Swap {
     }
 }
 
+#[repr(C)]
+pub struct DisplaySolanaTxOverviewNetworkFee {
+    pub compute_unit_limit: u32,
+    pub compute_unit_price: u64,
+    pub base_fee: PtrString,
+    pub priority_fee: PtrString,
+    pub total_fee: PtrString,
+}
+impl_c_ptrs!(DisplaySolanaTxOverviewNetworkFee);
+
+impl Free for DisplaySolanaTxOverviewNetworkFee {
+    fn free(&self) {
+        free_str_ptr!(self.base_fee);
+        free_str_ptr!(self.priority_fee);
+        free_str_ptr!(self.total_fee);
+    }
+}
+
+impl From<&ProgramOverviewNetworkFee> for DisplaySolanaTxOverviewNetworkFee {
+    fn from(value: &ProgramOverviewNetworkFee) -> Self {
+        Self {
+            compute_unit_limit: value.compute_unit_limit,
+            compute_unit_price: value.compute_unit_price,
+            base_fee: convert_c_char(value.base_fee.to_string()),
+            priority_fee: convert_c_char(value.priority_fee.to_string()),
+            total_fee: convert_c_char(value.total_fee.to_string()),
+        }
+    }
+}
+
 #[repr(C)]
 pub struct DisplaySolanaTxOverview {
     // `Transfer`, `Vote`, `General`, `Unknown`
@@ -201,6 +366,9 @@ pub struct DisplaySolanaTxOverview {
 
     // jupiter_v6 swap
     pub jupiter_v6_swap: PtrT<DisplaySolanaTxOverviewJupiterV6Swap>,
+
+    // base and priority fee, set for every display type
+    pub network_fee: PtrT<DisplaySolanaTxOverviewNetworkFee>,
 }
 
 #[repr(C)]
@@ -290,12 +458,19 @@ impl Default for DisplaySolanaTxOverview {
             squads_proposal: null_mut(),
             spl_token_transfer: null_mut(),
             jupiter_v6_swap: null_mut(),
+            network_fee: null_mut(),
         }
     }
 }
 
 impl Free for DisplaySolanaTx {
     fn free(&self) {
+        if !self.balance_changes.is_null() {
+            unsafe {
+                let x = Box::from_raw(self.balance_changes);
+                x.free();
+            }
+        }
         check_and_free_ptr!(self.overview);
         free_str_ptr!(self.network);
         free_str_ptr!(self.detail);
@@ -346,26 +521,43 @@ impl Free for DisplaySolanaTxOverview {
                 let x = Box::from_raw(self.spl_token_transfer);
                 x.free();
             }
+            if !self.network_fee.is_null() {
+                let x = Box::from_raw(self.network_fee);
+                x.free();
+            }
         }
     }
 }
 
-impl From<ParsedSolanaTx> for DisplaySolanaTx {
-    fn from(value: ParsedSolanaTx) -> Self {
-        DisplaySolanaTx {
+impl TryFrom<ParsedSolanaTx> for DisplaySolanaTx {
+    type Error = SolanaError;
+
+    fn try_from(value: ParsedSolanaTx) -> Result<Self, Self::Error> {
+        let balance_changes = DisplaySolanaTxBalanceChanges::try_from(&value.balance_changes)?;
+        Ok(DisplaySolanaTx {
             network: convert_c_char(value.network.to_string()),
             overview: DisplaySolanaTxOverview::from(&value).c_ptr(),
+            balance_changes: balance_changes.c_ptr(),
             detail: convert_c_char(value.detail),
-        }
+        })
     }
 }
 
 impl From<&ParsedSolanaTx> for DisplaySolanaTxOverview {
     fn from(value: &ParsedSolanaTx) -> Self {
+        let mut overview = Self::from_display_type(value);
+        overview.network_fee =
+            DisplaySolanaTxOverviewNetworkFee::from(&value.overview.network_fee).c_ptr();
+        overview
+    }
+}
+
+impl DisplaySolanaTxOverview {
+    fn from_display_type(value: &ParsedSolanaTx) -> Self {
         let display_type = convert_c_char(value.display_type.to_string());
         match value.display_type {
             SolanaTxDisplayType::Transfer => {
-                if let SolanaOverview::Transfer(overview) = &value.overview {
+                if let ProgramOverview::Transfer(overview) = &value.overview.kind {
                     return Self {
                         display_type,
                         //transfer
@@ -378,7 +570,7 @@ impl From<&ParsedSolanaTx> for DisplaySolanaTxOverview {
                 }
             }
             SolanaTxDisplayType::TokenTransfer => {
-                if let SolanaOverview::SplTokenTransfer(overview) = &value.overview {
+                if let ProgramOverview::SplTokenTransfer(overview) = &value.overview.kind {
                     return Self {
                         display_type,
                         spl_token_transfer: DisplaySolanaTxSplTokenTransferOverview {
@@ -387,6 +579,17 @@ impl From<&ParsedSolanaTx> for DisplaySolanaTxOverview {
                             authority: convert_c_char(overview.authority.to_string()),
                             decimals: overview.decimals,
                             amount: convert_c_char(overview.amount.to_string()),
+                            fee: overview
+                                .fee
+                                .clone()
+                                .map(convert_c_char)
+                                .unwrap_or(null_mut()),
+                            transfer_hook: overview
+                                .transfer_hook
+                                .clone()
+                                .map(convert_c_char)
+                                .unwrap_or(null_mut()),
+                            transfer_hook_verified: overview.transfer_hook_verified,
                             token_mint_account: convert_c_char(
                                 overview.token_mint_account.to_string(),
                             ),
@@ -399,7 +602,7 @@ impl From<&ParsedSolanaTx> for DisplaySolanaTxOverview {
                 }
             }
             SolanaTxDisplayType::Vote => {
-                if let SolanaOverview::Vote(overview) = &value.overview {
+                if let ProgramOverview::Vote(overview) = &value.overview.kind {
                     return Self {
                         display_type,
                         //vote
@@ -420,7 +623,7 @@ impl From<&ParsedSolanaTx> for DisplaySolanaTxOverview {
                 }
             }
             SolanaTxDisplayType::General => {
-                if let SolanaOverview::General(overview) = &value.overview {
+                if let ProgramOverview::General(overview) = &value.overview.kind {
                     return Self {
                         display_type,
                         //general
@@ -436,7 +639,7 @@ impl From<&ParsedSolanaTx> for DisplaySolanaTxOverview {
                 }
             }
             SolanaTxDisplayType::SquadsV4 => {
-                if let SolanaOverview::SquadsV4Proposal(overview) = &value.overview {
+                if let ProgramOverview::SquadsV4Proposal(overview) = &value.overview.kind {
                     let display_type = convert_c_char("squads_proposal".to_string());
                     let squads_proposal = VecFFI::from(
                         overview
@@ -456,7 +659,7 @@ impl From<&ParsedSolanaTx> for DisplaySolanaTxOverview {
                     };
                 }
 
-                if let SolanaOverview::SquadsV4MultisigCreate(overview) = &value.overview {
+                if let ProgramOverview::SquadsV4MultisigCreate(overview) = &value.overview.kind {
                     let squads_overview = DisplaySolanaTxOverviewSquadsV4MultisigCreate {
                         wallet_name: convert_c_char(overview.wallet_name.to_string()),
                         wallet_desc: convert_c_char(overview.wallet_desc.to_string()),
@@ -495,7 +698,7 @@ impl From<&ParsedSolanaTx> for DisplaySolanaTxOverview {
             }
 
             SolanaTxDisplayType::JupiterV6 => {
-                if let SolanaOverview::JupiterV6SwapOverview(overview) = &value.overview {
+                if let ProgramOverview::JupiterV6SwapOverview(overview) = &value.overview.kind {
                     let display_type = convert_c_char("jupiterv6_swap".to_string());
                     return Self {
                         display_type,
@@ -549,7 +752,7 @@ impl From<&ParsedSolanaTx> for DisplaySolanaTxOverview {
             }
 
             SolanaTxDisplayType::Unknown => {
-                if let SolanaOverview::Instructions(overview) = &value.overview {
+                if let ProgramOverview::Instructions(overview) = &value.overview.kind {
                     let display_overview_instructions =
                         DisplaySolanaTxOverviewUnknownInstructions {
                             overview_accounts: VecFFI::from(
@@ -573,6 +776,10 @@ impl From<&ParsedSolanaTx> for DisplaySolanaTxOverview {
                                         );
                                         Instruction {
                                             accounts: accounts.c_ptr(),
+                                            from_lookup_table: VecFFI::from(
+                                                v.from_lookup_table.clone(),
+                                            )
+                                            .c_ptr(),
                                             data: convert_c_char(v.data.to_string()),
                                             program_address: convert_c_char(
                                                 v.program_address.to_string(),
@@ -595,15 +802,88 @@ impl From<&ParsedSolanaTx> for DisplaySolanaTxOverview {
     }
 }
 
+#[repr(C)]
+pub struct DisplaySolanaSignInMessage {
+    domain: PtrString,
+    address: PtrString,
+    statement: PtrString,
+    uri: PtrString,
+    version: PtrString,
+    chain_id: PtrString,
+    nonce: PtrString,
+    issued_at: PtrString,
+    expiration_time: PtrString,
+    not_before: PtrString,
+    request_id: PtrString,
+    resources: PtrString,
+}
+
+impl From<SignInWithSolana> for DisplaySolanaSignInMessage {
+    fn from(value: SignInWithSolana) -> Self {
+        Self {
+            domain: convert_c_char(value.domain),
+            address: convert_c_char(value.address),
+            statement: value.statement.map(convert_c_char).unwrap_or(null_mut()),
+            uri: value.uri.map(convert_c_char).unwrap_or(null_mut()),
+            version: value.version.map(convert_c_char).unwrap_or(null_mut()),
+            chain_id: value.chain_id.map(convert_c_char).unwrap_or(null_mut()),
+            nonce: value.nonce.map(convert_c_char).unwrap_or(null_mut()),
+            issued_at: value.issued_at.map(convert_c_char).unwrap_or(null_mut()),
+            expiration_time: value
+                .expiration_time
+                .map(convert_c_char)
+                .unwrap_or(null_mut()),
+            not_before: value.not_before.map(convert_c_char).unwrap_or(null_mut()),
+            request_id: value.request_id.map(convert_c_char).unwrap_or(null_mut()),
+            resources: if value.resources.is_empty() {
+                null_mut()
+            } else {
+                convert_c_char(value.resources.join("\n"))
+            },
+        }
+    }
+}
+
+impl_c_ptr!(DisplaySolanaSignInMessage);
+
+impl Free for DisplaySolanaSignInMessage {
+    fn free(&self) {
+        free_str_ptr!(self.domain);
+        free_str_ptr!(self.address);
+        free_str_ptr!(self.statement);
+        free_str_ptr!(self.uri);
+        free_str_ptr!(self.version);
+        free_str_ptr!(self.chain_id);
+        free_str_ptr!(self.nonce);
+        free_str_ptr!(self.issued_at);
+        free_str_ptr!(self.expiration_time);
+        free_str_ptr!(self.not_before);
+        free_str_ptr!(self.request_id);
+        free_str_ptr!(self.resources);
+    }
+}
+
 #[repr(C)]
 pub struct DisplaySolanaMessage {
     raw_message: PtrString,
     utf8_message: PtrString,
     from: PtrString,
+    // off-chain message header, null for raw messages
+    application_domain: PtrString,
+    message_format: PtrString,
+    // null unless the message is a sign-in request
+    sign_in: PtrT<DisplaySolanaSignInMessage>,
 }
 
 impl From<SolanaMessage> for DisplaySolanaMessage {
     fn from(message: SolanaMessage) -> Self {
+        let (application_domain, message_format) = match message.offchain {
+            Some(offchain) => (
+                convert_c_char(offchain.application_domain),
+                convert_c_char(offchain.format.to_string()),
+            ),
+            None => (null_mut(), null_mut()),
+        };
         Self {
             raw_message: convert_c_char(message.raw_message),
             utf8_message: if message.utf8_message.is_empty() {
@@ -612,6 +892,12 @@ impl From<SolanaMessage> for DisplaySolanaMessage {
                 convert_c_char(message.utf8_message)
             },
             from: convert_c_char(message.from),
+            application_domain,
+            message_format,
+            sign_in: message
+                .sign_in
+                .map(|v| DisplaySolanaSignInMessage::from(v).c_ptr())
+                .unwrap_or(null_mut()),
         }
     }
 }
@@ -623,6 +909,14 @@ impl Free for DisplaySolanaMessage {
         free_str_ptr!(self.raw_message);
         free_str_ptr!(self.utf8_message);
         free_str_ptr!(self.from);
+        free_str_ptr!(self.application_domain);
+        free_str_ptr!(self.message_format);
+        if !self.sign_in.is_null() {
+            unsafe {
+                let x = Box::from_raw(self.sign_in);
+                x.free();
+            }
+        }
     }
 }
 
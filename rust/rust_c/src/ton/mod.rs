@@ -13,7 +13,8 @@ use alloc::{
     string::{String, ToString},
     vec::Vec,
 };
-use app_ton::{mnemonic::ton_mnemonic_validate, ton_compare_address_and_public_key};
+use app_ton::{mnemonic::ton_mnemonic_validate, ton_compare_address_and_public_key, WalletVersion};
+use core::str::FromStr;
 
 use crate::{extract_ptr_with_type, impl_c_ptr};
 use cty::c_char;
@@ -23,7 +24,10 @@ use structs::{DisplayTonProof, DisplayTonTransaction};
 use {
     hex,
     ur_registry::{
-        ton::{ton_sign_request::TonSignRequest, ton_signature::TonSignature},
+        ton::{
+            ton_sign_request::{DataType, TonSignRequest},
+            ton_signature::TonSignature,
+        },
         traits::RegistryItem,
     },
 };
@@ -76,11 +80,19 @@ pub extern "C" fn ton_check_transaction(
     let pk = recover_c_char(public_key);
     match hex::decode(pk) {
         Ok(pk) => {
-            if ton_compare_address_and_public_key(pk, ton_tx.get_address()) {
-                TransactionCheckResult::new().c_ptr()
-            } else {
-                TransactionCheckResult::from(RustCError::MasterFingerprintMismatch).c_ptr()
+            if !ton_compare_address_and_public_key(pk.clone(), ton_tx.get_address()) {
+                return TransactionCheckResult::from(RustCError::MasterFingerprintMismatch).c_ptr();
+            }
+            if let DataType::Transaction = ton_tx.get_data_type() {
+                if let Err(e) = app_ton::transaction::check_wallet_version(
+                    &ton_tx.get_sign_data(),
+                    pk,
+                    ton_tx.get_address(),
+                ) {
+                    return TransactionCheckResult::from(e).c_ptr();
+                }
             }
+            TransactionCheckResult::new().c_ptr()
         }
         Err(e) => TransactionCheckResult::from(RustCError::InvalidHex(e.to_string())).c_ptr(),
     }
@@ -257,3 +269,25 @@ pub extern "C" fn ton_get_address(public_key: PtrString) -> *mut SimpleResponse<
         }
     }
 }
+
+#[no_mangle]
+pub extern "C" fn ton_get_address_by_version(
+    public_key: PtrString,
+    wallet_version: PtrString,
+) -> *mut SimpleResponse<c_char> {
+    let pk = recover_c_char(public_key);
+    let wallet_version = recover_c_char(wallet_version);
+    let version = match WalletVersion::from_str(&wallet_version) {
+        Ok(version) => version,
+        Err(e) => {
+            return SimpleResponse::from(RustCError::InvalidData(e.to_string())).simple_c_ptr()
+        }
+    };
+    match hex::decode(pk) {
+        Ok(pk) => match app_ton::ton_public_key_to_address_by_version(pk, version) {
+            Ok(address) => SimpleResponse::success(convert_c_char(address)).simple_c_ptr(),
+            Err(e) => SimpleResponse::from(e).simple_c_ptr(),
+        },
+        Err(e) => SimpleResponse::from(RustCError::InvalidHex(e.to_string())).simple_c_ptr(),
+    }
+}
@@ -1,13 +1,15 @@
 use core::ptr::null_mut;
 
 use crate::common::{
+    ffi::VecFFI,
     free::Free,
     structs::TransactionParseResult,
     types::{PtrString, PtrT},
     utils::convert_c_char,
 };
-use crate::{check_and_free_ptr, free_str_ptr, impl_c_ptr, make_free_method};
-use app_ton::structs::{TonProof, TonTransaction};
+use crate::{check_and_free_ptr, free_str_ptr, free_vec, impl_c_ptr, make_free_method};
+use alloc::vec::Vec;
+use app_ton::structs::{TonAction, TonProof, TonTransaction};
 
 #[repr(C)]
 pub struct DisplayTonTransaction {
@@ -18,10 +20,58 @@ pub struct DisplayTonTransaction {
     data_view: PtrString,
     raw_data: PtrString,
     contract_data: PtrString,
+    wallet_version: PtrString,
+    actions: PtrT<VecFFI<DisplayTonAction>>,
 }
 
 impl_c_ptr!(DisplayTonTransaction);
 
+#[repr(C)]
+pub struct DisplayTonAction {
+    amount: PtrString,
+    action: PtrString,
+    to: PtrString,
+    comment: PtrString,
+    data_view: PtrString,
+    contract_data: PtrString,
+}
+
+impl From<&TonAction> for DisplayTonAction {
+    fn from(action: &TonAction) -> Self {
+        DisplayTonAction {
+            amount: convert_c_char(action.amount.clone()),
+            action: convert_c_char(action.action.clone()),
+            to: convert_c_char(action.to.clone()),
+            comment: action
+                .comment
+                .clone()
+                .map(convert_c_char)
+                .unwrap_or(null_mut()),
+            data_view: action
+                .data_view
+                .clone()
+                .map(convert_c_char)
+                .unwrap_or(null_mut()),
+            contract_data: action
+                .contract_data
+                .clone()
+                .map(convert_c_char)
+                .unwrap_or(null_mut()),
+        }
+    }
+}
+
+impl Free for DisplayTonAction {
+    fn free(&self) {
+        free_str_ptr!(self.amount);
+        free_str_ptr!(self.action);
+        free_str_ptr!(self.to);
+        free_str_ptr!(self.comment);
+        free_str_ptr!(self.data_view);
+        free_str_ptr!(self.contract_data);
+    }
+}
+
 impl From<&TonTransaction> for DisplayTonTransaction {
     fn from(tx: &TonTransaction) -> Self {
         DisplayTonTransaction {
@@ -40,6 +90,14 @@ impl From<&TonTransaction> for DisplayTonTransaction {
                 .clone()
                 .map(convert_c_char)
                 .unwrap_or(null_mut()),
+            wallet_version: convert_c_char(tx.wallet_version.clone()),
+            actions: VecFFI::from(
+                tx.actions
+                    .iter()
+                    .map(DisplayTonAction::from)
+                    .collect::<Vec<DisplayTonAction>>(),
+            )
+            .c_ptr(),
         }
     }
 }
@@ -53,6 +111,8 @@ impl Free for DisplayTonTransaction {
         free_str_ptr!(self.data_view);
         free_str_ptr!(self.raw_data);
         free_str_ptr!(self.contract_data);
+        free_str_ptr!(self.wallet_version);
+        free_vec!(self.actions);
     }
 }
 
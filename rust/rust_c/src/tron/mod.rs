@@ -1,15 +1,21 @@
+pub mod request;
 pub mod structs;
 
 use crate::common::errors::RustCError;
 use crate::common::keystone;
 use crate::common::structs::{SimpleResponse, TransactionCheckResult, TransactionParseResult};
 use crate::common::types::{PtrBytes, PtrString, PtrT, PtrUR};
-use crate::common::ur::{QRCodeType, UREncodeResult};
+use crate::common::ur::{QRCodeType, UREncodeResult, FRAGMENT_MAX_LENGTH_DEFAULT};
 use crate::common::utils::{convert_c_char, recover_c_char};
+use crate::extract_ptr_with_type;
 use alloc::boxed::Box;
 use alloc::slice;
+use alloc::string::ToString;
 use cty::c_char;
+use request::{build_sign_result, TronSignRequest};
 use structs::DisplayTron;
+use ur_registry::bytes::Bytes;
+use ur_registry::traits::RegistryItem;
 
 #[no_mangle]
 pub extern "C" fn tron_check_keystone(
@@ -77,6 +83,98 @@ pub extern "C" fn tron_sign_keystone(
     )
 }
 
+fn build_raw_tx_context(
+    ptr: PtrUR,
+    master_fingerprint: PtrBytes,
+    length: u32,
+    x_pub: PtrString,
+) -> Result<(TronSignRequest, app_utils::keystone::ParseContext), RustCError> {
+    if length != 4 {
+        return Err(RustCError::InvalidMasterFingerprint);
+    }
+    let bytes = extract_ptr_with_type!(ptr, Bytes);
+    let request = TronSignRequest::from_bytes(&bytes.get_bytes())?;
+    let context = keystone::build_parse_context(master_fingerprint, x_pub)
+        .map_err(|e| RustCError::InvalidData(e.to_string()))?;
+    Ok((request, context))
+}
+
+#[no_mangle]
+pub extern "C" fn tron_check_raw_tx(
+    ptr: PtrUR,
+    master_fingerprint: PtrBytes,
+    length: u32,
+    x_pub: PtrString,
+) -> PtrT<TransactionCheckResult> {
+    match build_raw_tx_context(ptr, master_fingerprint, length, x_pub) {
+        Ok((request, context)) => {
+            match app_tron::check_raw_data(&request.raw_data, request.hd_path, request.xfp, context)
+            {
+                Ok(_) => TransactionCheckResult::new().c_ptr(),
+                Err(e) => TransactionCheckResult::from(e).c_ptr(),
+            }
+        }
+        Err(e) => TransactionCheckResult::from(e).c_ptr(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn tron_parse_raw_tx(
+    ptr: PtrUR,
+    master_fingerprint: PtrBytes,
+    length: u32,
+    x_pub: PtrString,
+) -> PtrT<TransactionParseResult<DisplayTron>> {
+    match build_raw_tx_context(ptr, master_fingerprint, length, x_pub) {
+        Ok((request, context)) => {
+            match app_tron::parse_raw_data(&request.raw_data, request.hd_path, request.xfp, context)
+            {
+                Ok(res) => {
+                    TransactionParseResult::success(Box::into_raw(Box::new(DisplayTron::from(res))))
+                        .c_ptr()
+                }
+                Err(e) => TransactionParseResult::from(e).c_ptr(),
+            }
+        }
+        Err(e) => TransactionParseResult::from(e).c_ptr(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn tron_sign_raw_tx(
+    ptr: PtrUR,
+    master_fingerprint: PtrBytes,
+    length: u32,
+    x_pub: PtrString,
+    seed: PtrBytes,
+    seed_len: u32,
+) -> PtrT<UREncodeResult> {
+    let seed = unsafe { slice::from_raw_parts(seed, seed_len as usize) };
+    let (request, context) = match build_raw_tx_context(ptr, master_fingerprint, length, x_pub) {
+        Ok(v) => v,
+        Err(e) => return UREncodeResult::from(e).c_ptr(),
+    };
+    let result = app_tron::sign_raw_data(
+        &request.raw_data,
+        request.hd_path,
+        request.xfp,
+        context,
+        seed,
+    );
+    match result.map(|(signed_tx, tx_id)| Bytes::new(build_sign_result(signed_tx, tx_id))) {
+        Ok(bytes) => match bytes.try_into() {
+            Ok(data) => UREncodeResult::encode(
+                data,
+                Bytes::get_registry_type().get_type(),
+                FRAGMENT_MAX_LENGTH_DEFAULT,
+            )
+            .c_ptr(),
+            Err(e) => UREncodeResult::from(e).c_ptr(),
+        },
+        Err(e) => UREncodeResult::from(e).c_ptr(),
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn tron_get_address(
     hd_path: PtrString,
@@ -0,0 +1,77 @@
+use crate::common::errors::RustCError;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde_json::{json, Value};
+
+// the sign request of third party wallets is carried by a bytes UR:
+// {"type": "tron-sign-request", "rawData": "<hex>", "derivationPath": "m/44'/195'/0'/0/0", "xfp": "<hex>"}
+pub const TRON_SIGN_REQUEST_TYPE: &str = "tron-sign-request";
+pub const TRON_SIGN_RESULT_TYPE: &str = "tron-sign-result";
+
+pub struct TronSignRequest {
+    pub raw_data: Vec<u8>,
+    pub hd_path: String,
+    pub xfp: String,
+}
+
+impl TronSignRequest {
+    pub fn is_sign_request(value: &Value) -> bool {
+        value.pointer("/type").and_then(Value::as_str) == Some(TRON_SIGN_REQUEST_TYPE)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RustCError> {
+        let value: Value = serde_json::from_slice(bytes)
+            .map_err(|e| RustCError::InvalidData(format!("invalid tron sign request, {}", e)))?;
+        if !Self::is_sign_request(&value) {
+            return Err(RustCError::InvalidData(
+                "invalid tron sign request type".to_string(),
+            ));
+        }
+        let field = |name: &str| {
+            value
+                .pointer(&format!("/{}", name))
+                .and_then(Value::as_str)
+                .map(|v| v.to_string())
+                .ok_or(RustCError::InvalidData(format!("missing field {}", name)))
+        };
+        let raw_data = hex::decode(field("rawData")?)
+            .map_err(|_e| RustCError::InvalidData("invalid rawData".to_string()))?;
+        Ok(Self {
+            raw_data,
+            hd_path: field("derivationPath")?,
+            xfp: field("xfp")?,
+        })
+    }
+}
+
+pub fn build_sign_result(signed_tx: String, tx_id: String) -> Vec<u8> {
+    json!({
+        "type": TRON_SIGN_RESULT_TYPE,
+        "signedTx": signed_tx,
+        "txId": tx_id,
+    })
+    .to_string()
+    .into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate std;
+
+    #[test]
+    fn test_parse_sign_request() {
+        let request = r#"{"type":"tron-sign-request","rawData":"0a02ea05","derivationPath":"m/44'/195'/0'/0/0","xfp":"73c5da0a"}"#;
+        let request = TronSignRequest::from_bytes(request.as_bytes()).unwrap();
+        assert_eq!(Vec::from([0x0a, 0x02, 0xea, 0x05]), request.raw_data);
+        assert_eq!("m/44'/195'/0'/0/0", request.hd_path);
+        assert_eq!("73c5da0a", request.xfp);
+
+        let request = r#"{"type":"tron-sign-request","rawData":"0a02ea05"}"#;
+        assert!(TronSignRequest::from_bytes(request.as_bytes()).is_err());
+        let request = r#"{"TransactionType":"Payment"}"#;
+        assert!(TronSignRequest::from_bytes(request.as_bytes()).is_err());
+    }
+}
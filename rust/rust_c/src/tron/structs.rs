@@ -1,11 +1,14 @@
 use alloc::boxed::Box;
-use app_tron::{DetailTx, OverviewTx, ParsedTx};
+use alloc::vec::Vec;
+use app_tron::{DetailTx, OverviewTx, ParsedTx, TronVote};
+use core::ptr::null_mut;
 
+use crate::common::ffi::VecFFI;
 use crate::common::free::Free;
 use crate::common::structs::TransactionParseResult;
 use crate::common::types::{PtrString, PtrT};
 use crate::common::utils::convert_c_char;
-use crate::{check_and_free_ptr, free_str_ptr, impl_c_ptr, make_free_method};
+use crate::{check_and_free_ptr, free_str_ptr, free_vec, impl_c_ptr, make_free_method};
 
 #[repr(C)]
 pub struct DisplayTron {
@@ -20,6 +23,7 @@ pub struct DisplayTronOverview {
     from: PtrString,
     to: PtrString,
     network: PtrString,
+    resource: PtrString,
 }
 
 impl_c_ptr!(DisplayTronOverview);
@@ -33,10 +37,36 @@ pub struct DisplayTronDetail {
     network: PtrString,
     token: PtrString,
     contract_address: PtrString,
+    resource: PtrString,
+    lock_period: PtrString,
+    votes: PtrT<VecFFI<DisplayTronVote>>,
+    fee_limit: PtrString,
 }
 
 impl_c_ptr!(DisplayTronDetail);
 
+#[repr(C)]
+pub struct DisplayTronVote {
+    address: PtrString,
+    count: PtrString,
+}
+
+impl From<TronVote> for DisplayTronVote {
+    fn from(value: TronVote) -> Self {
+        Self {
+            address: convert_c_char(value.address),
+            count: convert_c_char(value.count),
+        }
+    }
+}
+
+impl Free for DisplayTronVote {
+    fn free(&self) {
+        free_str_ptr!(self.address);
+        free_str_ptr!(self.count);
+    }
+}
+
 impl From<OverviewTx> for DisplayTronOverview {
     fn from(value: OverviewTx) -> Self {
         Self {
@@ -45,6 +75,7 @@ impl From<OverviewTx> for DisplayTronOverview {
             from: convert_c_char(value.from),
             to: convert_c_char(value.to),
             network: convert_c_char(value.network),
+            resource: value.resource.map(convert_c_char).unwrap_or(null_mut()),
         }
     }
 }
@@ -59,6 +90,17 @@ impl From<DetailTx> for DisplayTronDetail {
             network: convert_c_char(value.network),
             token: convert_c_char(value.token),
             contract_address: convert_c_char(value.contract_address),
+            resource: value.resource.map(convert_c_char).unwrap_or(null_mut()),
+            lock_period: value.lock_period.map(convert_c_char).unwrap_or(null_mut()),
+            votes: VecFFI::from(
+                value
+                    .votes
+                    .into_iter()
+                    .map(DisplayTronVote::from)
+                    .collect::<Vec<DisplayTronVote>>(),
+            )
+            .c_ptr(),
+            fee_limit: value.fee_limit.map(convert_c_char).unwrap_or(null_mut()),
         }
     }
 }
@@ -78,6 +120,7 @@ impl Free for DisplayTronOverview {
         free_str_ptr!(self.value);
         free_str_ptr!(self.method);
         free_str_ptr!(self.network);
+        free_str_ptr!(self.resource);
     }
 }
 
@@ -90,6 +133,10 @@ impl Free for DisplayTronDetail {
         free_str_ptr!(self.network);
         free_str_ptr!(self.token);
         free_str_ptr!(self.contract_address);
+        free_str_ptr!(self.resource);
+        free_str_ptr!(self.lock_period);
+        free_vec!(self.votes);
+        free_str_ptr!(self.fee_limit);
     }
 }
 
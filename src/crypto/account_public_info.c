@@ -1447,6 +1447,7 @@ void appendWalletItemToJson(MultiSigWalletItem_t *item, void *root)
     cJSON_AddNumberToObject(walletItem, "network", item->network);
     cJSON_AddStringToObject(walletItem, "wallet_config", item->walletConfig);
     cJSON_AddStringToObject(walletItem, "format", item->format);
+    cJSON_AddStringToObject(walletItem, "policy_hmac", item->policyHmac);
     cJSON_AddItemToArray((cJSON*)root, walletItem);
 }
 
@@ -1586,6 +1587,10 @@ int32_t MultiSigWalletGet(uint8_t accountIndex, const char *password, MultiSigWa
             multiSigWalletItem->format = MULTI_SIG_MALLOC(strlen(strCache) + 1);
             strcpy(multiSigWalletItem->format, strCache);
 
+            GetStringValue(wallet, "policy_hmac", strCache, MULTI_SIG_STR_CACHE_LENGTH);
+            multiSigWalletItem->policyHmac = MULTI_SIG_MALLOC(strlen(strCache) + 1);
+            strcpy(multiSigWalletItem->policyHmac, strCache);
+
             manager->insertNode(multiSigWalletItem);
         }
         MULTI_SIG_FREE(strCache);
@@ -13,6 +13,7 @@
 #define MAX_FORMAT_LENGTH 12
 #define MAX_VERIFY_CODE_LENGTH 12
 #define MAX_WALLET_CONFIG_TEXT_LENGTH 2048
+#define MAX_POLICY_HMAC_LENGTH 65
 
 #define ASSERT_WALLET_MANAGER_EXIST assert(g_multisigWalletManager != NULL);
 
@@ -136,7 +137,7 @@ static void DestoryMultisigWalletManager(MultiSigWalletManager_t *manager)
     manager = NULL;
 }
 
-MultiSigWalletItem_t *AddMultisigWalletToCurrentAccount(MultiSigWallet *wallet, const char *password)
+static MultiSigWalletItem_t *AddWalletItemToCurrentAccount(MultiSigWallet *wallet, const char *policyHmac, const char *password)
 {
     ASSERT_WALLET_MANAGER_EXIST
     MultiSigWalletManager_t *manager = g_multisigWalletManager;
@@ -160,11 +161,29 @@ MultiSigWalletItem_t *AddMultisigWalletToCurrentAccount(MultiSigWallet *wallet,
     walletItem->walletConfig = MULTI_SIG_MALLOC(MAX_WALLET_CONFIG_TEXT_LENGTH);
     strcpy_s(walletItem->walletConfig, MAX_WALLET_CONFIG_TEXT_LENGTH, wallet->config_text);
 
+    walletItem->policyHmac = MULTI_SIG_MALLOC(MAX_POLICY_HMAC_LENGTH);
+    strcpy_s(walletItem->policyHmac, MAX_POLICY_HMAC_LENGTH, policyHmac);
+
     manager->insertNode(walletItem);
     manager->saveToFlash(password);
     return walletItem;
 }
 
+MultiSigWalletItem_t *AddMultisigWalletToCurrentAccount(MultiSigWallet *wallet, const char *password)
+{
+    return AddWalletItemToCurrentAccount(wallet, "", password);
+}
+
+MultiSigWalletItem_t *AddWalletPolicyToCurrentAccount(MultiSigWallet *wallet, const char *policyHmac, const char *password)
+{
+    return AddWalletItemToCurrentAccount(wallet, policyHmac, password);
+}
+
+bool IsWalletPolicyItem(const MultiSigWalletItem_t *item)
+{
+    return item != NULL && item->policyHmac != NULL && strlen(item->policyHmac) > 0;
+}
+
 static void createMultiSigWalletList()
 {
     ASSERT_WALLET_MANAGER_EXIST
@@ -214,6 +233,7 @@ static void modifyNode(char *verifyCode, MultiSigWalletItem_t *newItem)
             MULTI_SIG_FREE(temp->value->verifyCode);
             MULTI_SIG_FREE(temp->value->name);
             MULTI_SIG_FREE(temp->value->walletConfig);
+            MULTI_SIG_FREE(temp->value->policyHmac);
             MULTI_SIG_FREE(temp->value);
             temp->value = newItem;
             return;
@@ -251,6 +271,7 @@ static int deleteNode(char *verifyCode)
         MULTI_SIG_FREE(temp->value->verifyCode);
         MULTI_SIG_FREE(temp->value->name);
         MULTI_SIG_FREE(temp->value->walletConfig);
+        MULTI_SIG_FREE(temp->value->policyHmac);
         MULTI_SIG_FREE(temp->value);
         MULTI_SIG_FREE(temp);
         list->length--;
@@ -271,6 +292,7 @@ static int deleteNode(char *verifyCode)
     MULTI_SIG_FREE(temp->value->verifyCode);
     MULTI_SIG_FREE(temp->value->name);
     MULTI_SIG_FREE(temp->value->walletConfig);
+    MULTI_SIG_FREE(temp->value->policyHmac);
     MULTI_SIG_FREE(temp->value);
     MULTI_SIG_FREE(temp);
     list->length--;
@@ -308,6 +330,7 @@ static void destroyMultiSigWalletList()
         MULTI_SIG_FREE(current->value->verifyCode);
         MULTI_SIG_FREE(current->value->name);
         MULTI_SIG_FREE(current->value->walletConfig);
+        MULTI_SIG_FREE(current->value->policyHmac);
         MULTI_SIG_FREE(current->value);
         MULTI_SIG_FREE(current);
         current = next;
@@ -15,6 +15,7 @@
 #define FORMAT_P2WSH_P2SH_MID "P2WSH-P2SH"
 #define FORMAT_P2SH_P2WSH_MID "P2SH-P2WSH"
 #define FORMAT_P2SH "P2SH"
+#define FORMAT_POLICY "POLICY"
 
 typedef struct MultiSigWalletItem {
     int order;
@@ -23,6 +24,7 @@ typedef struct MultiSigWalletItem {
     char *format;
     int network;
     char *walletConfig;
+    char *policyHmac;
 } MultiSigWalletItem_t;
 
 typedef struct MultiSigWalletList MultiSigWalletList_t;
@@ -45,6 +47,8 @@ typedef struct MultiSigWalletManager {
 MultiSigWalletManager_t* initMultiSigWalletManager();
 int32_t LoadCurrentAccountMultisigWallet(const char* password);
 MultiSigWalletItem_t *AddMultisigWalletToCurrentAccount(MultiSigWallet *wallet, const char *password);
+MultiSigWalletItem_t *AddWalletPolicyToCurrentAccount(MultiSigWallet *wallet, const char *policyHmac, const char *password);
+bool IsWalletPolicyItem(const MultiSigWalletItem_t *item);
 MultiSigWalletItem_t *GetMultisigWalletByVerifyCode(const char* verifyCode);
 MultiSigWalletManager_t* GetMultisigWalletManager();
 int GetCurrentAccountMultisigWalletNum(void);
@@ -895,7 +895,8 @@ lv_obj_t *GuiWidgetTabViewChild(lv_obj_t *parent, cJSON *json)
     lv_obj_set_style_text_color(tab_btns, WHITE_COLOR, LV_PART_ITEMS | LV_STATE_CHECKED);
     lv_obj_set_style_text_color(tab_btns, WHITE_COLOR, LV_PART_MAIN);
     lv_obj_set_style_border_side(tab_btns, LV_BORDER_SIDE_BOTTOM, LV_PART_ITEMS | LV_STATE_CHECKED);
-    lv_obj_set_width(tab_btns, 200);
+    // 100 px for every tab, two tabs at least
+    lv_obj_set_width(tab_btns, LV_MAX(200, 100 * (g_analyzeTabview.tabviewIndex + 1)));
 
     item = cJSON_GetObjectItem(json, "font");
     if (item != NULL) {
@@ -1077,7 +1078,8 @@ void GuiAnalyzeViewInit(lv_obj_t *parent)
     static lv_point_t points[2] = {{0, 0}, {408, 0}};
     lv_obj_t *line = (lv_obj_t *)GuiCreateLine(g_imgCont, points, 2);
     lv_obj_align(line, LV_ALIGN_TOP_LEFT, 0, 64);
-    uint16_t tabWidth = 0;
+    // every tab slot is taken
+    uint16_t tabWidth = 408;
     for (int i = 0; i < GUI_ANALYZE_TABVIEW_CNT; i++) {
         if (g_analyzeTabview.obj[i] == NULL) {
             if (i <= 1) {
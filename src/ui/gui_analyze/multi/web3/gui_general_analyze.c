@@ -7,6 +7,7 @@ static GetLabelDataLenFunc GuiEthTextLenFuncGet(char *type);
 static GetLabelDataLenFunc GuiXrpTextLenFuncGet(char *type);
 static GetLabelDataLenFunc GuiStellarTextLenFuncGet(char *type);
 static GetLabelDataLenFunc GuiArTextLenFuncGet(char *type);
+static GetLabelDataLenFunc GuiTrxTextLenFuncGet(char *type);
 static GetTableDataFunc GuiEthTableFuncGet(char *type);
 static GetTableDataFunc GuiAdaTabelFuncGet(char *type);
 static GetLabelDataFunc GuiTrxTextFuncGet(char *type);
@@ -21,6 +22,7 @@ static GetLabelDataFunc GuiStellarTextFuncGet(char *type);
 static GetLabelDataFunc GuiSolMessageTextFuncGet(char *type);
 static GetLabelDataFunc GuiEthTypedDataTextFuncGet(char *type);
 static GetLabelDataFunc GuiEthPersonalMessageTextFuncGet(char *type);
+static GetLabelDataFunc GuiEthAuthorizationTextFuncGet(char *type);
 static GetLabelDataFunc GuiEthTextFuncGet(char *type);
 static GetContSizeFunc GetEthObjPos(char *type);
 static GetContSizeFunc GetCosmosObjPos(char *type);
@@ -29,6 +31,7 @@ static GetListLenFunc GetCosmosListLen(char *type);
 static GetContSizeFunc GetAdaContainerSize(char *type);
 static GetContSizeFunc GetCosmosContainerSize(char *type);
 static GetContSizeFunc GetEthContainerSize(char *type);
+static GetContSizeFunc GetTrxContainerSize(char *type);
 
 
 GetContSizeFunc GetOtherChainContainerSize(char *type, GuiRemapViewType remapIndex)
@@ -37,6 +40,8 @@ GetContSizeFunc GetOtherChainContainerSize(char *type, GuiRemapViewType remapInd
     case REMAPVIEW_ETH:
     case REMAPVIEW_ETH_TYPEDDATA:
         return GetEthContainerSize(type);
+    case REMAPVIEW_TRX:
+        return GetTrxContainerSize(type);
     case REMAPVIEW_COSMOS:
         return GetCosmosContainerSize(type);
     case REMAPVIEW_ADA:
@@ -54,6 +59,7 @@ GetContSizeFunc GetOtherChainPos(char *type, GuiRemapViewType remapIndex)
     switch (remapIndex) {
     case REMAPVIEW_ETH:
     case REMAPVIEW_ETH_TYPEDDATA:
+    case REMAPVIEW_ETH_AUTHORIZATION:
         return GetEthObjPos(type);
     case REMAPVIEW_COSMOS:
         return GetCosmosObjPos(type);
@@ -69,6 +75,12 @@ GetCustomContainerFunc GetOtherChainCustomFunc(char *funcName)
         return GuiShowSolTxOverview;
     } else if (!strcmp(funcName, "GuiShowSolTxDetail")) {
         return GuiShowSolTxDetail;
+    } else if (!strcmp(funcName, "GuiShowSolTxBalanceChanges")) {
+        return GuiShowSolTxBalanceChanges;
+    } else if (!strcmp(funcName, "GuiShowSolSignInMessage")) {
+        return GuiShowSolSignInMessage;
+    } else if (!strcmp(funcName, "GuiShowSolOffchainMessage")) {
+        return GuiShowSolOffchainMessage;
     } else if (!strcmp(funcName, "GuiShowArweaveTxDetail")) {
         return GuiShowArweaveTxDetail;
     } else if (!strcmp(funcName, "GetCatalystRewardsNotice")) {
@@ -139,12 +151,28 @@ GetObjStateFunc GuiOtherChainStateFuncGet(char *type)
         return GetEthContractDataNotExist;
     } else if (!strcmp(type, "GetEthInputDataExist")) {
         return GetEthInputDataExist;
+    } else if (!strcmp(type, "GetEthAccessListExist")) {
+        return GetEthAccessListExist;
+    } else if (!strcmp(type, "GetEthAuthorizationListExist")) {
+        return GetEthAuthorizationListExist;
+    } else if (!strcmp(type, "GetEthAuthorizationListWarningExist")) {
+        return GetEthAuthorizationListWarningExist;
+    } else if (!strcmp(type, "GetEthAuthorizationWarningExist")) {
+        return GetEthAuthorizationWarningExist;
     } else if (!strcmp(type, "EthInputExistContractNot")) {
         return EthInputExistContractNot;
     } else if (!strcmp(type, "GetTrxContractExist")) {
         return GetTrxContractExist;
     } else if (!strcmp(type, "GetTrxTokenExist")) {
         return GetTrxTokenExist;
+    } else if (!strcmp(type, "GetTrxResourceExist")) {
+        return GetTrxResourceExist;
+    } else if (!strcmp(type, "GetTrxLockPeriodExist")) {
+        return GetTrxLockPeriodExist;
+    } else if (!strcmp(type, "GetTrxFeeLimitExist")) {
+        return GetTrxFeeLimitExist;
+    } else if (!strcmp(type, "GetTrxVotesExist")) {
+        return GetTrxVotesExist;
     } else if (!strcmp(type, "GetCosmosChannelExist")) {
         return GetCosmosChannelExist;
     } else if (!strcmp(type, "GetCosmosOldValidatorExist")) {
@@ -210,6 +238,8 @@ GetLabelDataFunc GuiOtherChainTextFuncGet(char *type, GuiRemapViewType remapInde
         return GuiEthPersonalMessageTextFuncGet(type);
     case REMAPVIEW_ETH_TYPEDDATA:
         return GuiEthTypedDataTextFuncGet(type);
+    case REMAPVIEW_ETH_AUTHORIZATION:
+        return GuiEthAuthorizationTextFuncGet(type);
     case REMAPVIEW_TRX:
         return GuiTrxTextFuncGet(type);
     case REMAPVIEW_COSMOS:
@@ -260,6 +290,8 @@ GetLabelDataLenFunc GuiOtherChainTextLenFuncGet(char *type, GuiRemapViewType rem
         return GuiArTextLenFuncGet(type);
     case REMAPVIEW_STELLAR:
         return GuiStellarTextLenFuncGet(type);
+    case REMAPVIEW_TRX:
+        return GuiTrxTextLenFuncGet(type);
     default:
         return NULL;
     }
@@ -350,6 +382,10 @@ static GetLabelDataLenFunc GuiEthTextLenFuncGet(char *type)
         return GetEthTypedDataMessageLen;
     } else if (!strcmp(type, "GetEthInputDataLen")) {
         return GetEthInputDataLen;
+    } else if (!strcmp(type, "GetEthAccessListLen")) {
+        return GetEthAccessListLen;
+    } else if (!strcmp(type, "GetEthAuthorizationListLen")) {
+        return GetEthAuthorizationListLen;
     }
     return NULL;
 }
@@ -422,6 +458,30 @@ static GetLabelDataFunc GuiTrxTextFuncGet(char *type)
         return GetTrxContract;
     } else if (!strcmp(type, "GetTrxToken")) {
         return GetTrxToken;
+    } else if (!strcmp(type, "GetTrxResource")) {
+        return GetTrxResource;
+    } else if (!strcmp(type, "GetTrxLockPeriod")) {
+        return GetTrxLockPeriod;
+    } else if (!strcmp(type, "GetTrxFeeLimit")) {
+        return GetTrxFeeLimit;
+    } else if (!strcmp(type, "GetTrxVotes")) {
+        return GetTrxVotes;
+    }
+    return NULL;
+}
+
+static GetLabelDataLenFunc GuiTrxTextLenFuncGet(char *type)
+{
+    if (!strcmp(type, "GetTrxVotesLen")) {
+        return GetTrxVotesLen;
+    }
+    return NULL;
+}
+
+static GetContSizeFunc GetTrxContainerSize(char *type)
+{
+    if (!strcmp(type, "GetTrxVotesSize")) {
+        return GetTrxVotesSize;
     }
     return NULL;
 }
@@ -562,10 +622,28 @@ static GetLabelDataFunc GuiEthPersonalMessageTextFuncGet(char *type)
     return NULL;
 }
 
+static GetLabelDataFunc GuiEthAuthorizationTextFuncGet(char *type)
+{
+    if (!strcmp(type, "GetEthAuthorizationWarning")) {
+        return GetEthAuthorizationWarning;
+    } else if (!strcmp(type, "GetEthAuthorizationAuthority")) {
+        return GetEthAuthorizationAuthority;
+    } else if (!strcmp(type, "GetEthAuthorizationDelegate")) {
+        return GetEthAuthorizationDelegate;
+    } else if (!strcmp(type, "GetEthAuthorizationChainId")) {
+        return GetEthAuthorizationChainId;
+    } else if (!strcmp(type, "GetEthAuthorizationNonce")) {
+        return GetEthAuthorizationNonce;
+    }
+    return NULL;
+}
+
 
 static GetLabelDataFunc GuiEthTypedDataTextFuncGet(char *type)
 {
-    if (!strcmp(type, "GetEthTypedDataDomianName")) {
+    if (!strcmp(type, "GetEthPermitWarning")) {
+        return GetEthPermitWarning;
+    } else if (!strcmp(type, "GetEthTypedDataDomianName")) {
         return GetEthTypedDataDomianName;
     } else if (!strcmp(type, "GetEthTypedDataDomianVersion")) {
         return GetEthTypedDataDomianVersion;
@@ -635,6 +713,12 @@ static GetLabelDataFunc GuiEthTextFuncGet(char *type)
         return GetEthInputData;
     } else if (!strcmp(type, "GetEthNonce")) {
         return GetEthNonce;
+    } else if (!strcmp(type, "GetEthAccessList")) {
+        return GetEthAccessList;
+    } else if (!strcmp(type, "GetEthAuthorizationList")) {
+        return GetEthAuthorizationList;
+    } else if (!strcmp(type, "GetEthAuthorizationListWarning")) {
+        return GetEthAuthorizationListWarning;
     }
 
     return NULL;
@@ -646,6 +730,8 @@ static GetContSizeFunc GetEthObjPos(char *type)
         return GetEthToLabelPos;
     } else if (!strcmp(type, "GetEthTypeDomainPos")) {
         return GetEthTypeDomainPos;
+    } else if (!strcmp(type, "GetEthAuthorizationPos")) {
+        return GetEthAuthorizationPos;
     }
     return NULL;
 }
@@ -692,6 +778,10 @@ static GetContSizeFunc GetEthContainerSize(char *type)
         return GetEthContractDataSize;
     } else if (!strcmp(type, "GetEthTypeDomainSize")) {
         return GetEthTypeDomainSize;
+    } else if (!strcmp(type, "GetEthAccessListSize")) {
+        return GetEthAccessListSize;
+    } else if (!strcmp(type, "GetEthAuthorizationListSize")) {
+        return GetEthAuthorizationListSize;
     }
     return NULL;
 }
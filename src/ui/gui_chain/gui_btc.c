@@ -9,6 +9,7 @@
 #include "secret_cache.h"
 #include "screen_manager.h"
 #include "account_manager.h"
+#include "device_setting.h"
 #include "gui_chain_components.h"
 #include "gui_home_widgets.h"
 #include "gui_transaction_detail_widgets.h"
@@ -125,7 +126,13 @@ static UREncodeResult *GuiGetSignPsbtBytesCodeData(void)
         uint8_t seed[64];
         int len = GetMnemonicType() == MNEMONIC_TYPE_BIP39 ? sizeof(seed) : GetCurrentAccountEntropyLen();
         GetAccountSeed(GetCurrentAccountIndex(), seed, SecretCacheGetPassword());
-        MultisigSignResult *result = btc_sign_multisig_psbt_bytes(g_psbtBytes, g_psbtBytesLen, seed, len, mfp, sizeof(mfp));
+        MultiSigWalletItem_t *item = GetCurrentWalletIndex() != SINGLE_WALLET ? GetDefaultMultisigWallet() : NULL;
+        MultisigSignResult *result = NULL;
+        if (IsWalletPolicyItem(item)) {
+            result = btc_sign_multisig_psbt_bytes_with_wallet_policy(g_psbtBytes, g_psbtBytesLen, seed, len, mfp, sizeof(mfp), item->walletConfig, item->policyHmac, GetEnableBlindSigning());
+        } else {
+            result = btc_sign_multisig_psbt_bytes(g_psbtBytes, g_psbtBytesLen, seed, len, mfp, sizeof(mfp), GetEnableBlindSigning());
+        }
         encodeResult = result->ur_result;
         GuiMultisigTransactionSignatureSetSignStatus(result->sign_status, result->is_completed, result->psbt_hex, result->psbt_len);
         free_MultisigSignResult(result);
@@ -197,18 +204,24 @@ static UREncodeResult *GetBtcSignDataDynamic(bool unLimit)
             GetAccountSeed(GetCurrentAccountIndex(), seed, SecretCacheGetPassword());
 #ifdef BTC_ONLY
             if (GuiGetCurrentTransactionType() == TRANSACTION_TYPE_BTC_MULTISIG) {
-                MultisigSignResult *result = btc_sign_multisig_psbt(data, seed, len, mfp, sizeof(mfp));
+                MultiSigWalletItem_t *item = GetCurrentWalletIndex() != SINGLE_WALLET ? GetDefaultMultisigWallet() : NULL;
+                MultisigSignResult *result = NULL;
+                if (IsWalletPolicyItem(item)) {
+                    result = btc_sign_multisig_psbt_with_wallet_policy(data, seed, len, mfp, sizeof(mfp), item->walletConfig, item->policyHmac, GetEnableBlindSigning());
+                } else {
+                    result = btc_sign_multisig_psbt(data, seed, len, mfp, sizeof(mfp), GetEnableBlindSigning());
+                }
                 encodeResult = result->ur_result;
                 GuiMultisigTransactionSignatureSetSignStatus(result->sign_status, result->is_completed, result->psbt_hex, result->psbt_len);
                 free_MultisigSignResult(result);
             } else {
-                encodeResult = btc_sign_psbt(data, seed, len, mfp, sizeof(mfp));
+                encodeResult = btc_sign_psbt(data, seed, len, mfp, sizeof(mfp), GetEnableBlindSigning());
             }
 #else
             if (unLimit) {
-                encodeResult = btc_sign_psbt_unlimited(data, seed, len, mfp, sizeof(mfp));
+                encodeResult = btc_sign_psbt_unlimited(data, seed, len, mfp, sizeof(mfp), GetEnableBlindSigning());
             } else {
-                encodeResult = btc_sign_psbt(data, seed, len, mfp, sizeof(mfp));
+                encodeResult = btc_sign_psbt(data, seed, len, mfp, sizeof(mfp), GetEnableBlindSigning());
             }
 #endif
         }
@@ -236,6 +249,11 @@ static UREncodeResult *GetBtcSignDataDynamic(bool unLimit)
         int len = GetMnemonicType() == MNEMONIC_TYPE_BIP39 ? sizeof(seed) : GetCurrentAccountEntropyLen();
         GetAccountSeed(GetCurrentAccountIndex(), seed, SecretCacheGetPassword());
         encodeResult = sign_seed_signer_message(data, seed, len);
+    } else if (urType == Bip322Message) {
+        uint8_t seed[64];
+        int len = GetMnemonicType() == MNEMONIC_TYPE_BIP39 ? sizeof(seed) : GetCurrentAccountEntropyLen();
+        GetAccountSeed(GetCurrentAccountIndex(), seed, SecretCacheGetPassword());
+        encodeResult = btc_sign_bip322_msg(data, seed, len, mfp, sizeof(mfp));
     }
     CHECK_CHAIN_PRINT(encodeResult);
     ClearSecretCache();
@@ -284,18 +302,24 @@ static void *GuiGetParsedPsbtStrData(void)
     GetMasterFingerPrint(mfp);
 
     char *wallet_config = NULL;
+    bool isWalletPolicy = false;
     if (GetCurrentWalletIndex() != SINGLE_WALLET) {
         MultiSigWalletItem_t *item = GetDefaultMultisigWallet();
         if (item != NULL) {
             wallet_config = SRAM_MALLOC(MAX_WALLET_CONFIG_LEN);
             memset_s(wallet_config, MAX_WALLET_CONFIG_LEN, '\0', MAX_WALLET_CONFIG_LEN);
             strncpy_s(wallet_config, MAX_WALLET_CONFIG_LEN, item->walletConfig, strnlen_s(item->walletConfig, MAX_WALLET_CONFIG_LEN));
+            isWalletPolicy = IsWalletPolicyItem(item);
         }
     }
-    g_parseResult = btc_parse_psbt_bytes(g_psbtBytes, g_psbtBytesLen, mfp, sizeof(mfp), public_keys, wallet_config);
+    if (isWalletPolicy) {
+        g_parseResult = btc_parse_psbt_bytes_with_wallet_policy(g_psbtBytes, g_psbtBytesLen, mfp, sizeof(mfp), wallet_config);
+    } else {
+        g_parseResult = btc_parse_psbt_bytes(g_psbtBytes, g_psbtBytesLen, mfp, sizeof(mfp), public_keys, wallet_config);
+    }
     CHECK_CHAIN_RETURN(g_parseResult);
     GuiSetCurrentTransactionNeedSign(g_parseResult->data->overview->need_sign);
-    if (IsMultiSigTx(g_parseResult->data)) {
+    if (IsMultiSigTx(g_parseResult->data) || isWalletPolicy) {
         GuiSetCurrentTransactionType(TRANSACTION_TYPE_BTC_MULTISIG);
     }
     SRAM_FREE(public_keys);
@@ -387,16 +411,26 @@ void *GuiGetParsedQrData(void)
         if (urType == CryptoPSBT) {
 #ifdef BTC_ONLY
             char *wallet_config = NULL;
+            bool isWalletPolicy = false;
             if (GetCurrentWalletIndex() != SINGLE_WALLET) {
                 MultiSigWalletItem_t *item = GetDefaultMultisigWallet();
                 if (item != NULL) {
                     wallet_config = SRAM_MALLOC(MAX_WALLET_CONFIG_LEN);
                     memset_s(wallet_config, MAX_WALLET_CONFIG_LEN, '\0', MAX_WALLET_CONFIG_LEN);
                     strncpy_s(wallet_config, MAX_WALLET_CONFIG_LEN, item->walletConfig, strnlen_s(item->walletConfig, MAX_WALLET_CONFIG_LEN));
+                    isWalletPolicy = IsWalletPolicyItem(item);
                 }
             }
-            g_parseResult = btc_parse_psbt(crypto, mfp, sizeof(mfp), public_keys, wallet_config);
+            if (isWalletPolicy) {
+                g_parseResult = btc_parse_psbt_with_wallet_policy(crypto, mfp, sizeof(mfp), wallet_config);
+            } else {
+                g_parseResult = btc_parse_psbt(crypto, mfp, sizeof(mfp), public_keys, wallet_config);
+            }
             GuiSetCurrentTransactionNeedSign(g_parseResult->data->overview->need_sign);
+            if (isWalletPolicy) {
+                // policy transactions are signed through the multisig path which checks the policy hmac
+                GuiSetCurrentTransactionType(TRANSACTION_TYPE_BTC_MULTISIG);
+            }
             SRAM_FREE(wallet_config);
 #else
             g_parseResult = btc_parse_psbt(crypto, mfp, sizeof(mfp), public_keys, NULL);
@@ -428,6 +462,10 @@ void *GuiGetParsedQrData(void)
             g_parseMsgResult = parse_seed_signer_message(crypto, public_keys);
             CHECK_CHAIN_RETURN(g_parseMsgResult);
             return g_parseMsgResult;
+        } else if (urType == Bip322Message) {
+            g_parseMsgResult = btc_parse_bip322_msg(crypto, mfp, sizeof(mfp));
+            CHECK_CHAIN_RETURN(g_parseMsgResult);
+            return g_parseMsgResult;
         }
     } while (0);
     return g_parseResult;
@@ -476,6 +514,7 @@ PtrT_TransactionCheckResult GuiGetPsbtStrCheckResult(void)
 
     char *verify_code = NULL;
     char *wallet_config = NULL;
+    bool isWalletPolicy = false;
     if (GetCurrentWalletIndex() != SINGLE_WALLET) {
         MultiSigWalletItem_t *item = GetDefaultMultisigWallet();
         if (item != NULL) {
@@ -485,11 +524,16 @@ PtrT_TransactionCheckResult GuiGetPsbtStrCheckResult(void)
             wallet_config = SRAM_MALLOC(MAX_WALLET_CONFIG_LEN);
             memset_s(wallet_config, MAX_WALLET_CONFIG_LEN, '\0', MAX_WALLET_CONFIG_LEN);
             strncpy_s(wallet_config, MAX_WALLET_CONFIG_LEN, item->walletConfig, strnlen_s(item->walletConfig, MAX_WALLET_CONFIG_LEN));
+            isWalletPolicy = IsWalletPolicyItem(item);
         }
     }
     printf("wallet_config = %s\n", wallet_config);
 
-    result = btc_check_psbt_bytes(g_psbtBytes, g_psbtBytesLen, mfp, sizeof(mfp), public_keys, verify_code, wallet_config);
+    if (isWalletPolicy) {
+        result = btc_check_psbt_bytes_with_wallet_policy(g_psbtBytes, g_psbtBytesLen, mfp, sizeof(mfp), wallet_config, GetEnableBlindSigning());
+    } else {
+        result = btc_check_psbt_bytes(g_psbtBytes, g_psbtBytesLen, mfp, sizeof(mfp), public_keys, verify_code, wallet_config, GetEnableBlindSigning());
+    }
     SRAM_FREE(public_keys);
     SRAM_FREE(verify_code);
     SRAM_FREE(wallet_config);
@@ -581,6 +625,7 @@ PtrT_TransactionCheckResult GuiGetPsbtCheckResult(void)
 #ifdef BTC_ONLY
         char *verify_code = NULL;
         char *wallet_config = NULL;
+        bool isWalletPolicy = false;
         if (GetCurrentWalletIndex() != SINGLE_WALLET) {
             MultiSigWalletItem_t *item = GetDefaultMultisigWallet();
             if (item != NULL) {
@@ -590,13 +635,18 @@ PtrT_TransactionCheckResult GuiGetPsbtCheckResult(void)
                 wallet_config = SRAM_MALLOC(MAX_WALLET_CONFIG_LEN);
                 memset_s(wallet_config, MAX_WALLET_CONFIG_LEN, '\0', MAX_WALLET_CONFIG_LEN);
                 strncpy_s(wallet_config, MAX_WALLET_CONFIG_LEN, item->walletConfig, strnlen_s(item->walletConfig, MAX_WALLET_CONFIG_LEN));
+                isWalletPolicy = IsWalletPolicyItem(item);
             }
         }
-        result = btc_check_psbt(crypto, mfp, sizeof(mfp), public_keys, verify_code, wallet_config);
+        if (isWalletPolicy) {
+            result = btc_check_psbt_with_wallet_policy(crypto, mfp, sizeof(mfp), wallet_config, GetEnableBlindSigning());
+        } else {
+            result = btc_check_psbt(crypto, mfp, sizeof(mfp), public_keys, verify_code, wallet_config, GetEnableBlindSigning());
+        }
         SRAM_FREE(verify_code);
         SRAM_FREE(wallet_config);
 #else
-        result = btc_check_psbt(crypto, mfp, sizeof(mfp), public_keys, NULL, NULL);
+        result = btc_check_psbt(crypto, mfp, sizeof(mfp), public_keys, NULL, NULL, GetEnableBlindSigning());
 #endif
         SRAM_FREE(public_keys);
     }
@@ -614,6 +664,8 @@ PtrT_TransactionCheckResult GuiGetPsbtCheckResult(void)
         result = btc_check_msg(crypto, mfp, sizeof(mfp));
     } else if (urType == SeedSignerMessage) {
         result = tx_check_pass();
+    } else if (urType == Bip322Message) {
+        result = btc_check_bip322_msg(crypto, mfp, sizeof(mfp));
     }
     return result;
 }
@@ -890,6 +942,34 @@ static lv_obj_t *CreateAvalancheNoticeView(lv_obj_t *parent, lv_obj_t *lastView)
     return noticeContainer;
 }
 
+// findings that have to be seen before signing, the content is one entry per line
+static lv_obj_t *CreateOverviewWarningView(lv_obj_t *parent, const char *title, const char *content, lv_obj_t *lastView)
+{
+    lv_obj_t *warningContainer = GuiCreateContainerWithParent(parent, 408, 0);
+    if (lastView == NULL) {
+        lv_obj_align(warningContainer, LV_ALIGN_DEFAULT, 0, 0);
+    } else {
+        lv_obj_align_to(warningContainer, lastView, LV_ALIGN_OUT_BOTTOM_MID, 0, 16);
+    }
+    SetContainerDefaultStyle(warningContainer);
+
+    lv_obj_t *img = GuiCreateImg(warningContainer, &imgNotice);
+    lv_obj_align(img, LV_ALIGN_DEFAULT, 24, 24);
+
+    lv_obj_t *label = GuiCreateIllustrateLabel(warningContainer, title);
+    lv_obj_set_style_text_color(label, ORANGE_COLOR, LV_PART_MAIN);
+    lv_obj_align_to(label, img, LV_ALIGN_OUT_RIGHT_MID, 8, 0);
+
+    label = GuiCreateIllustrateLabel(warningContainer, content);
+    lv_obj_set_width(label, 360);
+    lv_label_set_long_mode(label, LV_LABEL_LONG_WRAP);
+    lv_obj_align(label, LV_ALIGN_DEFAULT, 24, 68);
+    lv_obj_update_layout(label);
+    lv_obj_set_height(warningContainer, lv_obj_get_y2(label) + 16);
+
+    return warningContainer;
+}
+
 static lv_obj_t *CreateOverviewAmountView(lv_obj_t *parent, DisplayTxOverview *overviewData, lv_obj_t *lastView)
 {
     lv_obj_t *amountContainer = GuiCreateContainerWithParent(parent, 408, 144);
@@ -1131,6 +1211,20 @@ static lv_obj_t *CreateDetailAmountView(lv_obj_t *parent, DisplayTxDetail *detai
     return amountContainer;
 }
 
+// notes like inscriptions or sighash warnings are shown under the input or output they belong to
+static lv_obj_t *CreateDetailNoteLabel(lv_obj_t *parent, const char *title, const char *content, lv_obj_t *lastView)
+{
+    lv_obj_t *noteLabel = lv_label_create(parent);
+    lv_obj_set_width(noteLabel, 332);
+    lv_label_set_long_mode(noteLabel, LV_LABEL_LONG_WRAP);
+    lv_label_set_text_fmt(noteLabel, "%s:\n%s", title, content);
+    lv_obj_set_style_text_font(noteLabel, g_defIllustrateFont, LV_PART_MAIN);
+    lv_obj_set_style_text_color(noteLabel, lv_color_hex(0xf5870a), LV_PART_MAIN);
+    lv_obj_align_to(noteLabel, lastView, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 4);
+    lv_obj_update_layout(noteLabel);
+    return noteLabel;
+}
+
 static lv_obj_t *CreateDetailFromView(lv_obj_t *parent, DisplayTxDetail *detailData, lv_obj_t *lastView)
 {
     bool showChange = true;
@@ -1214,6 +1308,15 @@ static lv_obj_t *CreateDetailFromView(lv_obj_t *parent, DisplayTxDetail *detailD
         lv_obj_update_layout(pathLabel);
 
         int pathLabelBottom = lv_obj_get_y2(pathLabel);
+        lv_obj_t *noteView = pathLabel;
+        if (from->data[i].inscriptions != NULL) {
+            noteView = CreateDetailNoteLabel(formInnerContainer, "Inscriptions", from->data[i].inscriptions, noteView);
+            pathLabelBottom = lv_obj_get_y2(noteView);
+        }
+        if (from->data[i].sighash_warning != NULL) {
+            noteView = CreateDetailNoteLabel(formInnerContainer, "Sighash", from->data[i].sighash_warning, noteView);
+            pathLabelBottom = lv_obj_get_y2(noteView);
+        }
 
         lv_obj_set_height(formInnerContainer, pathLabelBottom);
 
@@ -1293,6 +1396,10 @@ static lv_obj_t *CreateDetailToView(lv_obj_t *parent, DisplayTxDetail *detailDat
         lv_obj_update_layout(addressLabel);
 
         int bottom = lv_obj_get_y2(addressLabel);
+        if (to->data[i].inscriptions != NULL) {
+            lv_obj_t *inscriptionLabel = CreateDetailNoteLabel(toInnerContainer, "Inscriptions", to->data[i].inscriptions, addressLabel);
+            bottom = lv_obj_get_y2(inscriptionLabel);
+        }
 
         lv_obj_set_height(toInnerContainer, bottom);
         lv_obj_align_to(toInnerContainer, lastView, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 8);
@@ -1306,6 +1413,43 @@ static lv_obj_t *CreateDetailToView(lv_obj_t *parent, DisplayTxDetail *detailDat
     return toContainer;
 }
 
+static lv_obj_t *CreateDetailOrdinalsView(lv_obj_t *parent, DisplayTxDetail *detailData, lv_obj_t *lastView)
+{
+    lv_obj_t *container = GuiCreateContainerWithParent(parent, 408, 0);
+    SetContainerDefaultStyle(container);
+    lv_obj_align_to(container, lastView, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 16);
+
+    lastView = NULL;
+    if (detailData->inscriptions_to_fee != NULL) {
+        lv_obj_t *label = lv_label_create(container);
+        lv_label_set_text(label, "Inscriptions Spent As Fee");
+        lv_obj_align(label, LV_ALIGN_DEFAULT, 24, 16);
+        SetTitleLabelStyle(label);
+        lastView = CreateDetailNoteLabel(container, "Inscriptions", detailData->inscriptions_to_fee, label);
+    }
+    if (detailData->runes != NULL) {
+        lv_obj_t *label = lv_label_create(container);
+        lv_label_set_text(label, "Runes");
+        if (lastView == NULL) {
+            lv_obj_align(label, LV_ALIGN_DEFAULT, 24, 16);
+        } else {
+            lv_obj_align_to(label, lastView, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 16);
+        }
+        SetTitleLabelStyle(label);
+        lv_obj_t *runesLabel = lv_label_create(container);
+        lv_obj_set_width(runesLabel, 360);
+        lv_label_set_long_mode(runesLabel, LV_LABEL_LONG_WRAP);
+        lv_label_set_text(runesLabel, detailData->runes);
+        SetContentLableStyle(runesLabel);
+        lv_obj_align_to(runesLabel, label, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 4);
+        lv_obj_update_layout(runesLabel);
+        lastView = runesLabel;
+    }
+    lv_obj_set_height(container, lv_obj_get_y2(lastView) + 16);
+
+    return container;
+}
+
 void GuiBtcTxOverview(lv_obj_t *parent, void *totalData)
 {
     DisplayTx *txData = (DisplayTx *)totalData;
@@ -1324,6 +1468,15 @@ void GuiBtcTxOverview(lv_obj_t *parent, void *totalData)
     if (IsAvalancheTx(txData)) {
         lastView = CreateAvalancheNoticeView(parent, lastView);
     }
+    if (overviewData->inscriptions_to_fee != NULL) {
+        lastView = CreateOverviewWarningView(parent, "Inscriptions Spent As Fee", overviewData->inscriptions_to_fee, lastView);
+    }
+    if (overviewData->sighash_warnings != NULL) {
+        lastView = CreateOverviewWarningView(parent, "Sighash", overviewData->sighash_warnings, lastView);
+    }
+    if (overviewData->policy_warnings != NULL) {
+        lastView = CreateOverviewWarningView(parent, "Policy", overviewData->policy_warnings, lastView);
+    }
     lastView = CreateOverviewAmountView(parent, overviewData, lastView);
     lastView = CreateNetworkView(parent, overviewData->network, lastView);
     lastView = CreateOverviewFromView(parent, overviewData, lastView);
@@ -1347,7 +1500,10 @@ void GuiBtcTxDetail(lv_obj_t *parent, void *totalData)
     lastView = CreateNetworkView(parent, detailData->network, lastView);
     lastView = CreateDetailAmountView(parent, detailData, lastView);
     lastView = CreateDetailFromView(parent, detailData, lastView);
-    CreateDetailToView(parent, detailData, lastView);
+    lastView = CreateDetailToView(parent, detailData, lastView);
+    if (detailData->inscriptions_to_fee != NULL || detailData->runes != NULL) {
+        CreateDetailOrdinalsView(parent, detailData, lastView);
+    }
 }
 
 void GuiBtcMsg(lv_obj_t *parent, void *totalData)
@@ -106,6 +106,7 @@ typedef enum {
     REMAPVIEW_ETH,
     REMAPVIEW_ETH_PERSONAL_MESSAGE,
     REMAPVIEW_ETH_TYPEDDATA,
+    REMAPVIEW_ETH_AUTHORIZATION,
     REMAPVIEW_ETH_BATCH_TX,
     REMAPVIEW_TRX,
     REMAPVIEW_COSMOS,
@@ -651,6 +651,9 @@ void GuiSetEthUrData(URParseResult *urResult, URParseMultiResult *urMultiResult,
         case EthTypedData:                                                                                                        \
             free_TransactionParseResult_DisplayETHTypedData((PtrT_TransactionParseResult_DisplayETHTypedData)result);             \
             break;                                                                                                                \
+        case EthAuthorization:                                                                                                    \
+            free_TransactionParseResult_DisplayETHAuthorization((PtrT_TransactionParseResult_DisplayETHAuthorization)result);     \
+            break;                                                                                                                \
         default:                                                                                                                  \
             break;                                                                                                                \
         }                                                                                                                         \
@@ -748,10 +751,15 @@ UREncodeResult *GuiGetEthSignUrDataUnlimited(void)
     return GetEthSignDataDynamic(true);
 }
 
-static void UpdatePermitFlag(const char *primaryType)
+static void UpdatePermitFlag(DisplayETHTypedData *typedData)
 {
+    const char *primaryType = typedData->primary_type;
     printf("primaryType: %s\n", primaryType);
-    if (!strcmp("Permit", primaryType) || !strcmp("PermitSingle", primaryType) || !strcmp("PermitBatch", primaryType)) {
+    if (typedData->risk_summary != NULL) {
+        // seaport orders and permit2 transfers hand out tokens as well
+        g_isPermit = true;
+        g_isPermitSingle = !strcmp("Permit", primaryType);
+    } else if (!strcmp("Permit", primaryType) || !strcmp("PermitSingle", primaryType) || !strcmp("PermitBatch", primaryType)) {
         g_isPermit = true;
         if (!strcmp("Permit", primaryType)) {
             g_isPermitSingle = true;
@@ -787,7 +795,7 @@ void *GuiGetEthTypeData(void)
         cJSON_Delete(json);
         CHECK_CHAIN_BREAK(parseResult);
         g_parseResult = (void *)parseResult;
-        UpdatePermitFlag(parseResult->data->primary_type);
+        UpdatePermitFlag(parseResult->data);
     } while (0);
     free_TransactionCheckResult(result);
     free_ptr_string(rootPath);
@@ -1032,6 +1040,87 @@ void GetMessageRaw(void *indata, void *param, uint32_t maxLen)
     }
 }
 
+void *GuiGetEthAuthorizationData(void)
+{
+    CHECK_FREE_PARSE_RESULT(g_parseResult);
+    uint8_t mfp[4];
+    void *data = g_isMulti ? g_urMultiResult->data : g_urResult->data;
+    char *rootPath = eth_get_root_path(data);
+    char *ethXpub = GetCurrentAccountPublicKey(GetEthPublickeyIndex(rootPath));
+    GetMasterFingerPrint(mfp);
+    TransactionCheckResult *result = NULL;
+    do {
+        result = eth_check(data, mfp, sizeof(mfp));
+        CHECK_CHAIN_BREAK(result);
+        PtrT_TransactionParseResult_DisplayETHAuthorization parseResult = eth_parse_authorization(data, ethXpub);
+        CHECK_CHAIN_BREAK(parseResult);
+        g_parseResult = (void *)parseResult;
+    } while (0);
+    free_TransactionCheckResult(result);
+    free_ptr_string(rootPath);
+    return g_parseResult;
+}
+
+static void GetAuthorizationWarning(DisplayETHAuthorization *authorization, char *text, uint32_t maxLen)
+{
+    if (authorization->is_any_chain) {
+        snprintf_s(text, maxLen, "This authorization delegates your account to %s on every chain, it can be replayed on any network.", authorization->delegate);
+    } else if (authorization->is_chain_mismatch) {
+        snprintf_s(text, maxLen, "This authorization is bound to chain ID %s, which is not the chain of this request.", authorization->chain_id);
+    }
+}
+
+bool GetEthAuthorizationWarningExist(void *indata, void *param)
+{
+    DisplayETHAuthorization *authorization = (DisplayETHAuthorization *)param;
+    return authorization->is_any_chain || authorization->is_chain_mismatch;
+}
+
+void GetEthAuthorizationWarning(void *indata, void *param, uint32_t maxLen)
+{
+    GetAuthorizationWarning((DisplayETHAuthorization *)param, (char *)indata, maxLen);
+}
+
+void GetEthAuthorizationPos(uint16_t *x, uint16_t *y, void *param)
+{
+    *x = 36;
+    *y = 24 + GetEthAuthorizationWarningExist(NULL, param) * (152 + 16);
+}
+
+void GetEthAuthorizationAuthority(void *indata, void *param, uint32_t maxLen)
+{
+    DisplayETHAuthorization *authorization = (DisplayETHAuthorization *)param;
+    if (authorization->authority != NULL) {
+        strcpy_s((char *)indata, maxLen, authorization->authority);
+    }
+}
+
+void GetEthAuthorizationDelegate(void *indata, void *param, uint32_t maxLen)
+{
+    DisplayETHAuthorization *authorization = (DisplayETHAuthorization *)param;
+    if (authorization->is_revocation) {
+        strcpy_s((char *)indata, maxLen, "Revoke the existing delegation");
+    } else {
+        strcpy_s((char *)indata, maxLen, authorization->delegate);
+    }
+}
+
+void GetEthAuthorizationChainId(void *indata, void *param, uint32_t maxLen)
+{
+    DisplayETHAuthorization *authorization = (DisplayETHAuthorization *)param;
+    if (authorization->is_any_chain) {
+        snprintf_s((char *)indata, maxLen, "%s (All Chains)", authorization->chain_id);
+    } else {
+        strcpy_s((char *)indata, maxLen, authorization->chain_id);
+    }
+}
+
+void GetEthAuthorizationNonce(void *indata, void *param, uint32_t maxLen)
+{
+    DisplayETHAuthorization *authorization = (DisplayETHAuthorization *)param;
+    strcpy_s((char *)indata, maxLen, authorization->nonce);
+}
+
 static uint8_t GetEthPublickeyIndex(char* rootPath)
 {
     if (strcmp(rootPath, "44'/60'/0'") == 0) return XPUB_TYPE_ETH_BIP44_STANDARD;
@@ -1122,6 +1211,16 @@ PtrT_TransactionCheckResult GuiGetEthCheckResult(void)
 void GetEthTransType(void *indata, void *param, uint32_t maxLen)
 {
     DisplayETH *eth = (DisplayETH *)param;
+    // access list transactions are priced like legacy ones
+    if (strcmp(eth->tx_type, "AccessList") == 0) {
+        strcpy_s((char *)indata, maxLen, "Legacy");
+        return;
+    }
+    // set code and blob transactions are priced like fee market ones
+    if (strcmp(eth->tx_type, "SetCode") == 0 || strcmp(eth->tx_type, "Blob") == 0) {
+        strcpy_s((char *)indata, maxLen, "FeeMarket");
+        return;
+    }
     strcpy_s((char *)indata, maxLen, eth->tx_type);
 }
 
@@ -1316,6 +1415,124 @@ bool EthInputExistContractNot(void *indata, void *param)
     return !g_contractDataExist && strlen(eth->detail->input) > 0;
 }
 
+bool GetEthAccessListExist(void *indata, void *param)
+{
+    DisplayETH *eth = (DisplayETH *)param;
+    return eth->detail->access_list != NULL && eth->detail->access_list->size > 0;
+}
+
+void GetEthAccessList(void *indata, void *param, uint32_t maxLen)
+{
+    DisplayETH *eth = (DisplayETH *)param;
+    memset_s(indata, maxLen, 0, maxLen);
+    for (uint32_t i = 0; i < eth->detail->access_list->size; i++) {
+        DisplayETHAccessListItem *item = &eth->detail->access_list->data[i];
+        if (i != 0) {
+            strcat_s((char *)indata, maxLen, "\n");
+        }
+        strcat_s((char *)indata, maxLen, item->address);
+        for (uint32_t j = 0; j < item->storage_keys->size; j++) {
+            strcat_s((char *)indata, maxLen, "\n");
+            strcat_s((char *)indata, maxLen, item->storage_keys->data[j]);
+        }
+    }
+}
+
+int GetEthAccessListLen(void *param)
+{
+    DisplayETH *eth = (DisplayETH *)param;
+    int len = 0;
+    for (uint32_t i = 0; i < eth->detail->access_list->size; i++) {
+        DisplayETHAccessListItem *item = &eth->detail->access_list->data[i];
+        len += strlen(item->address) + 1;
+        for (uint32_t j = 0; j < item->storage_keys->size; j++) {
+            len += strlen(item->storage_keys->data[j]) + 1;
+        }
+    }
+    return len;
+}
+
+void GetEthAccessListSize(uint16_t *width, uint16_t *height, void *param)
+{
+    DisplayETH *eth = (DisplayETH *)param;
+    *width = 408;
+    *height = PADDING;
+    *height += TEXT_LINE_HEIGHT; // "Access List"
+    *height += GAP;
+    for (uint32_t i = 0; i < eth->detail->access_list->size; i++) {
+        // addresses and storage keys wrap to two lines each
+        *height += 2 * TEXT_LINE_HEIGHT * (1 + eth->detail->access_list->data[i].storage_keys->size);
+    }
+    *height += PADDING;
+}
+
+bool GetEthAuthorizationListExist(void *indata, void *param)
+{
+    DisplayETH *eth = (DisplayETH *)param;
+    return eth->detail->authorization_list != NULL && eth->detail->authorization_list->size > 0;
+}
+
+bool GetEthAuthorizationListWarningExist(void *indata, void *param)
+{
+    DisplayETH *eth = (DisplayETH *)param;
+    if (!GetEthAuthorizationListExist(indata, param)) {
+        return false;
+    }
+    for (uint32_t i = 0; i < eth->detail->authorization_list->size; i++) {
+        if (GetEthAuthorizationWarningExist(NULL, &eth->detail->authorization_list->data[i])) {
+            return true;
+        }
+    }
+    return false;
+}
+
+void GetEthAuthorizationListWarning(void *indata, void *param, uint32_t maxLen)
+{
+    DisplayETH *eth = (DisplayETH *)param;
+    for (uint32_t i = 0; i < eth->detail->authorization_list->size; i++) {
+        DisplayETHAuthorization *authorization = &eth->detail->authorization_list->data[i];
+        if (GetEthAuthorizationWarningExist(NULL, authorization)) {
+            GetAuthorizationWarning(authorization, (char *)indata, maxLen);
+            return;
+        }
+    }
+}
+
+void GetEthAuthorizationList(void *indata, void *param, uint32_t maxLen)
+{
+    DisplayETH *eth = (DisplayETH *)param;
+    char line[BUFFER_SIZE_256];
+    memset_s(indata, maxLen, 0, maxLen);
+    for (uint32_t i = 0; i < eth->detail->authorization_list->size; i++) {
+        DisplayETHAuthorization *authorization = &eth->detail->authorization_list->data[i];
+        snprintf_s(line, sizeof(line), "%s#%d\nAuthority: %s\nDelegate: %s\nChain ID: %s%s\nNonce: %s",
+                   i == 0 ? "" : "\n\n", i + 1,
+                   authorization->authority != NULL ? authorization->authority : "Unknown",
+                   authorization->is_revocation ? "Revoke" : authorization->delegate,
+                   authorization->chain_id, authorization->is_any_chain ? " (All Chains)" : "",
+                   authorization->nonce);
+        strcat_s((char *)indata, maxLen, line);
+    }
+}
+
+int GetEthAuthorizationListLen(void *param)
+{
+    DisplayETH *eth = (DisplayETH *)param;
+    return eth->detail->authorization_list->size * BUFFER_SIZE_256;
+}
+
+void GetEthAuthorizationListSize(uint16_t *width, uint16_t *height, void *param)
+{
+    DisplayETH *eth = (DisplayETH *)param;
+    *width = 408;
+    *height = PADDING;
+    *height += TEXT_LINE_HEIGHT; // "Authorizations"
+    *height += GAP;
+    // index, two lines for each address, chain id, nonce and the gap between entries
+    *height += eth->detail->authorization_list->size * 8 * TEXT_LINE_HEIGHT;
+    *height += PADDING;
+}
+
 void GetEthToFromSize(uint16_t *width, uint16_t *height, void *param)
 {
     *width = 408;
@@ -1372,6 +1589,10 @@ void GetEthTransactionData(void *indata, void *param, uint32_t maxLen)
 void GetEthMethodName(void *indata, void *param, uint32_t maxLen)
 {
     Response_DisplayContractData *contractData = (Response_DisplayContractData *)g_contractData;
+    if (contractData->data->is_signature_inferred) {
+        snprintf_s((char *)indata, maxLen, "%s (Inferred)", contractData->data->method_name);
+        return;
+    }
     strcpy_s((char *)indata, maxLen, contractData->data->method_name);
 }
 
@@ -1385,6 +1606,8 @@ void GetEthContractName(void *indata, void *param, uint32_t maxLen)
     // add contract address string
     if (strlen(contractData->data->contract_name) > 0) {
         strcpy_s((char *)indata, maxLen, contractData->data->contract_name);
+    } else if (contractData->data->is_signature_inferred) {
+        snprintf_s((char *)indata, maxLen, "Selector Registry v%u", eth_get_selector_registry_version());
     } else {
         snprintf_s((char *)indata,  maxLen, "Unknown Contract Name");
     }
@@ -1466,11 +1689,16 @@ static void decodeEthContractData(void *parseResult)
         return;
     }
 
-    if (!GetEthContractFromInternal(contractAddress, result->data->detail->input)) {
-        char selectorId[9] = {0};
-        strncpy(selectorId, result->data->detail->input, 8);
-        GetEthContractFromExternal(contractAddress, selectorId, result->data->chain_id, result->data->detail->input);
+    if (GetEthContractFromInternal(contractAddress, result->data->detail->input)) {
+        return;
     }
+    char selectorId[9] = {0};
+    strncpy(selectorId, result->data->detail->input, 8);
+    if (GetEthContractFromExternal(contractAddress, selectorId, result->data->chain_id, result->data->detail->input)) {
+        return;
+    }
+    // no abi for this contract, fall back to the on-device selector registry
+    GetEthContractFromSelectorRegistry(result->data->detail->input);
 }
 
 static void FixRecipientAndValueWhenErc20Contract(const char *inputdata, uint8_t decimals)
@@ -1537,6 +1765,23 @@ bool GetEthPermitWarningExist(void *indata, void *param)
     return g_isPermit;
 }
 
+void GetEthPermitWarning(void *indata, void *param, uint32_t maxLen)
+{
+    DisplayETHTypedData *typedData = (DisplayETHTypedData *)param;
+    DisplayETHTypedDataSummary *summary = typedData->risk_summary;
+    if (summary == NULL) {
+        strcpy_s((char *)indata, maxLen, _("sign_eth_permit_warn"));
+    } else if (summary->warning != NULL) {
+        strcpy_s((char *)indata, maxLen, summary->warning);
+    } else if (summary->has_recipient_not_signer) {
+        snprintf_s((char *)indata, maxLen, "This %s pays out to addresses other than yours, check every recipient.", summary->kind);
+    } else if (summary->has_unlimited_amount) {
+        snprintf_s((char *)indata, maxLen, "This %s grants an unlimited amount of your tokens to %s.", summary->kind, summary->spender != NULL ? summary->spender : "the spender");
+    } else {
+        strcpy_s((char *)indata, maxLen, _("sign_eth_permit_warn"));
+    }
+}
+
 bool GetEthOperationWarningExist(void *indata, void *param)
 {
     return g_isOperation;
@@ -1675,6 +1920,18 @@ bool GetEthContractFromExternal(char *address, char *selectorId, uint64_t chainI
     return false;
 }
 
+bool GetEthContractFromSelectorRegistry(char *inputData)
+{
+    Response_DisplayContractData *contractData = eth_parse_contract_data_by_selector(inputData);
+    if (contractData->error_code == 0) {
+        g_contractDataExist = true;
+        g_contractData = contractData;
+        return true;
+    }
+    free_Response_DisplayContractData(contractData);
+    return false;
+}
+
 void FreeContractData(void)
 {
     if (g_contractData != NULL) {
@@ -40,6 +40,7 @@ bool GetEthContractFromInternal(char *address, char *inputData);
 bool GetEthTypeDataChainExist(void *indata, void *param);
 bool GetEthTypeDataVersionExist(void *indata, void *param);
 bool GetEthContractFromExternal(char *address, char *selectorId, uint64_t chainId, char *inputData);
+bool GetEthContractFromSelectorRegistry(char *inputData);
 void GetEthMethodName(void *indata, void *param, uint32_t maxLen);
 void GetEthContractName(void *indata, void *param, uint32_t maxLen);
 void GetEthTransactionData(void *indata, void *param, uint32_t maxLen);
@@ -52,8 +53,19 @@ void GetEthContractDataSize(uint16_t *width, uint16_t *height, void *param);
 void GetEthTypeDomainSize(uint16_t *width, uint16_t *height, void *param);
 void *GetEthContractData(uint8_t *row, uint8_t *col, void *param);
 bool GetEthInputDataExist(void *indata, void *param);
+bool GetEthAccessListExist(void *indata, void *param);
+void GetEthAccessList(void *indata, void *param, uint32_t maxLen);
+int GetEthAccessListLen(void *param);
+void GetEthAccessListSize(uint16_t *width, uint16_t *height, void *param);
+bool GetEthAuthorizationListExist(void *indata, void *param);
+bool GetEthAuthorizationListWarningExist(void *indata, void *param);
+void GetEthAuthorizationListWarning(void *indata, void *param, uint32_t maxLen);
+void GetEthAuthorizationList(void *indata, void *param, uint32_t maxLen);
+int GetEthAuthorizationListLen(void *param);
+void GetEthAuthorizationListSize(uint16_t *width, uint16_t *height, void *param);
 bool EthInputExistContractNot(void *indata, void *param);
 bool GetEthPermitWarningExist(void *indata, void *param);
+void GetEthPermitWarning(void *indata, void *param, uint32_t maxLen);
 bool GetEthPermitCantSign(void *indata, void *param);
 bool GetEthOperationWarningExist(void *indata, void *param);
 void *GuiGetEthPersonalMessage(void);
@@ -61,6 +73,14 @@ void GetEthPersonalMessageType(void *indata, void *param, uint32_t maxLen);
 void GetMessageFrom(void *indata, void *param, uint32_t maxLen);
 void GetMessageUtf8(void *indata, void *param, uint32_t maxLen);
 void GetMessageRaw(void *indata, void *param, uint32_t maxLen);
+void *GuiGetEthAuthorizationData(void);
+bool GetEthAuthorizationWarningExist(void *indata, void *param);
+void GetEthAuthorizationWarning(void *indata, void *param, uint32_t maxLen);
+void GetEthAuthorizationPos(uint16_t *x, uint16_t *y, void *param);
+void GetEthAuthorizationAuthority(void *indata, void *param, uint32_t maxLen);
+void GetEthAuthorizationDelegate(void *indata, void *param, uint32_t maxLen);
+void GetEthAuthorizationChainId(void *indata, void *param, uint32_t maxLen);
+void GetEthAuthorizationNonce(void *indata, void *param, uint32_t maxLen);
 void EthContractCheckRawDataCallback(void);
 
 void *GuiGetEthTypeData(void);
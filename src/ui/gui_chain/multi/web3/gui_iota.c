@@ -147,7 +147,7 @@ void GuiIotaTxOverview(lv_obj_t *parent, void *totalData)
 
     lv_obj_t *container = NULL;
     if (txData->amount != NULL) {
-        if (strcmp(txData->amount, "max") == 0) {
+        if (strcmp(txData->amount, "Max") == 0) {
             container = CreateNoticeView(parent, 408, 212, _("iota_max_amount_notice"));
             lv_obj_align(container, LV_ALIGN_TOP_LEFT, 0, 0);
         } else {
@@ -178,6 +178,16 @@ void GuiIotaTxOverview(lv_obj_t *parent, void *totalData)
         container = CreateSingleInfoTwoLineView(parent, "to", txData->to);
         GuiAlignToPrevObj(container, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 12);
     }
+
+    if (txData->staked_iota != NULL) {
+        container = CreateSingleInfoTwoLineView(parent, "Staked IOTA", txData->staked_iota);
+        GuiAlignToPrevObj(container, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 12);
+    }
+
+    if (txData->max_fee != NULL) {
+        container = CreateSingleInfoView(parent, "Max Fee", txData->max_fee);
+        GuiAlignToPrevObj(container, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 12);
+    }
 }
 
 void GuiIotaTxRawData(lv_obj_t *parent, void *totalData)
@@ -140,7 +140,11 @@ static void learn_more_click_event_handler(lv_event_t *e)
 void GetSolMessageType(void *indata, void *param, uint32_t maxLen)
 {
     DisplaySolanaMessage *message = (DisplaySolanaMessage *)param;
-    if (message->utf8_message) {
+    if (message->sign_in) {
+        strcpy_s((char *)indata, maxLen, "sign_in");
+    } else if (message->application_domain) {
+        strcpy_s((char *)indata, maxLen, "offchain");
+    } else if (message->utf8_message) {
         strcpy_s((char *)indata, maxLen, "utf8_message");
     } else {
         strcpy_s((char *)indata, maxLen, "raw_message");
@@ -203,6 +207,72 @@ static void SetContentLableStyle(lv_obj_t *label)
     lv_obj_set_style_text_color(label, WHITE_COLOR, LV_PART_MAIN);
 }
 
+static lv_obj_t *GuiShowSolLabelRow(lv_obj_t *container, lv_obj_t *lastView, const char *title, const char *content)
+{
+    if (content == NULL) {
+        return lastView;
+    }
+    lv_obj_t *label = GuiCreateTextLabel(container, title);
+    SetTitleLabelStyle(label);
+    if (lastView == NULL) {
+        lv_obj_align(label, LV_ALIGN_TOP_LEFT, 24, 0);
+    } else {
+        lv_obj_align_to(label, lastView, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 16);
+    }
+    lv_obj_t *valueLabel = GuiCreateIllustrateLabel(container, content);
+    lv_label_set_long_mode(valueLabel, LV_LABEL_LONG_WRAP);
+    lv_obj_set_width(valueLabel, 360);
+    lv_obj_set_style_text_color(valueLabel, WHITE_COLOR, LV_PART_MAIN);
+    lv_obj_align_to(valueLabel, label, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 8);
+    return valueLabel;
+}
+
+static lv_obj_t *GuiCreateSolMessageContainer(lv_obj_t *parent)
+{
+    lv_obj_set_size(parent, 408, 500);
+    lv_obj_add_flag(parent, LV_OBJ_FLAG_SCROLLABLE);
+    lv_obj_add_flag(parent, LV_OBJ_FLAG_CLICKABLE);
+    lv_obj_t *container = GuiCreateAutoHeightContainer(parent, 408, 16);
+    lv_obj_align(container, LV_ALIGN_DEFAULT, 0, 0);
+    return container;
+}
+
+void GuiShowSolSignInMessage(lv_obj_t *parent, void *totalData)
+{
+    DisplaySolanaMessage *message = (DisplaySolanaMessage *)totalData;
+    PtrT_DisplaySolanaSignInMessage signIn = message->sign_in;
+    lv_obj_t *container = GuiCreateSolMessageContainer(parent);
+    lv_obj_t *lastView = GuiShowSolLabelRow(container, NULL, "Domain", signIn->domain);
+    lastView = GuiShowSolLabelRow(container, lastView, "Address", signIn->address);
+    lastView = GuiShowSolLabelRow(container, lastView, "Statement", signIn->statement);
+    lastView = GuiShowSolLabelRow(container, lastView, "URI", signIn->uri);
+    lastView = GuiShowSolLabelRow(container, lastView, "Version", signIn->version);
+    lastView = GuiShowSolLabelRow(container, lastView, "Chain ID", signIn->chain_id);
+    lastView = GuiShowSolLabelRow(container, lastView, "Nonce", signIn->nonce);
+    lastView = GuiShowSolLabelRow(container, lastView, "Issued At", signIn->issued_at);
+    lastView = GuiShowSolLabelRow(container, lastView, "Expiration Time", signIn->expiration_time);
+    lastView = GuiShowSolLabelRow(container, lastView, "Not Before", signIn->not_before);
+    lastView = GuiShowSolLabelRow(container, lastView, "Request ID", signIn->request_id);
+    lastView = GuiShowSolLabelRow(container, lastView, "Resources", signIn->resources);
+    // a sign-in request may also come wrapped in an off-chain message
+    lastView = GuiShowSolLabelRow(container, lastView, "Application Domain", message->application_domain);
+    GuiShowSolLabelRow(container, lastView, "Format", message->message_format);
+}
+
+void GuiShowSolOffchainMessage(lv_obj_t *parent, void *totalData)
+{
+    DisplaySolanaMessage *message = (DisplaySolanaMessage *)totalData;
+    lv_obj_t *container = GuiCreateSolMessageContainer(parent);
+    lv_obj_t *lastView = GuiShowSolLabelRow(container, NULL, "From", message->from);
+    lastView = GuiShowSolLabelRow(container, lastView, "Application Domain", message->application_domain);
+    lastView = GuiShowSolLabelRow(container, lastView, "Format", message->message_format);
+    if (message->utf8_message) {
+        GuiShowSolLabelRow(container, lastView, "Message", message->utf8_message);
+    } else {
+        GuiShowSolLabelRow(container, lastView, "Raw Message", message->raw_message);
+    }
+}
+
 static void SetVotesOnOrderLableStyle(lv_obj_t *label)
 {
     lv_obj_set_style_text_font(label, g_defLittleTitleFont, LV_PART_MAIN);
@@ -790,6 +860,31 @@ static void GuiShowSplTokenTransferOverview(lv_obj_t *parent, PtrT_DisplaySolana
     destLearnMoredata.title = "Destination Account";
     destLearnMoredata.content = _("solana_ata_desc");
     lv_obj_add_event_cb(destInfoSIcon, learn_more_click_event_handler, LV_EVENT_CLICKED, &destLearnMoredata);
+
+    lv_obj_t *lastView = destValuelabel;
+    if (splTokenTransfer->fee != NULL) {
+        lv_obj_t * feeLabel = lv_label_create(container);
+        lv_label_set_text(feeLabel, "Transfer Fee");
+        SetTitleLabelStyle(feeLabel);
+        lv_obj_align_to(feeLabel, lastView, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 16);
+        lv_obj_t * feeValueLabel = lv_label_create(container);
+        lv_label_set_text(feeValueLabel, splTokenTransfer->fee);
+        SetContentLableStyle(feeValueLabel);
+        lv_obj_align_to(feeValueLabel, feeLabel, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 8);
+        lastView = feeValueLabel;
+    }
+    if (splTokenTransfer->transfer_hook != NULL) {
+        lv_obj_t * hookLabel = lv_label_create(container);
+        lv_label_set_text(hookLabel, splTokenTransfer->transfer_hook_verified ? "Transfer Hook" : "Transfer Hook (Unverified)");
+        SetTitleLabelStyle(hookLabel);
+        lv_obj_align_to(hookLabel, lastView, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 16);
+        lv_obj_t * hookValueLabel = lv_label_create(container);
+        lv_label_set_text(hookValueLabel, splTokenTransfer->transfer_hook);
+        lv_label_set_long_mode(hookValueLabel, LV_LABEL_LONG_WRAP);
+        lv_obj_set_width(hookValueLabel, 306);
+        SetContentLableStyle(hookValueLabel);
+        lv_obj_align_to(hookValueLabel, hookLabel, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 8);
+    }
 }
 
 static void GuiShowSolTxSquadsProposalOverview(lv_obj_t *parent, PtrT_DisplaySolanaTxOverview overviewData)
@@ -1353,6 +1448,16 @@ static void GuiShowSolTxInstructionsOverview(lv_obj_t *parent, PtrT_DisplaySolan
                     }
                 }
                 lv_obj_add_event_cb(info_icon, SolanaAddressLearnMore, LV_EVENT_CLICKED, NULL);
+            } else if (overview_instructions->data[i].from_lookup_table->data[j]) {
+                // resolved from the lookup tables the software wallet sent along with the transaction
+                lv_label_set_text(account_label, accounts->data[j]);
+                lv_obj_t *info_icon = GuiCreateImg(account_cont, &imgInfoSmall);
+                lv_obj_set_style_pad_right(info_icon, 0, LV_PART_MAIN);
+                lv_obj_add_flag(info_icon, LV_OBJ_FLAG_CLICKABLE);
+                static SolanaLearnMoreData_t lookupTableLearnMoreData;
+                lookupTableLearnMoreData.title = "Address Lookup Table";
+                lookupTableLearnMoreData.content = "This account was loaded from an address lookup table provided by the software wallet and can't be verified on the device.";
+                lv_obj_add_event_cb(info_icon, learn_more_click_event_handler, LV_EVENT_CLICKED, &lookupTableLearnMoreData);
             } else {
                 lv_label_set_text(account_label, accounts->data[j]);
             }
@@ -1399,6 +1504,43 @@ static void GuiShowSolTxInstructionsOverview(lv_obj_t *parent, PtrT_DisplaySolan
     }
 }
 
+static void GuiShowSolTxNetworkFeeOverview(lv_obj_t *parent, PtrT_DisplaySolanaTxOverviewNetworkFee networkFee)
+{
+    if (networkFee == NULL) {
+        return;
+    }
+    // the overview cards are placed by hand, so the fee card goes below the lowest of them
+    lv_obj_update_layout(parent);
+    lv_coord_t yOffset = 0;
+    for (uint32_t i = 0; i < lv_obj_get_child_cnt(parent); i++) {
+        lv_obj_t *child = lv_obj_get_child(parent, i);
+        yOffset = LV_MAX(yOffset, lv_obj_get_y(child) + lv_obj_get_height(child) + 16);
+    }
+
+    lv_obj_t *container = GuiCreateContainerWithParent(parent, 408, 100);
+    lv_obj_align(container, LV_ALIGN_TOP_LEFT, 0, yOffset);
+    SetContainerDefaultStyle(container);
+
+    lv_obj_t *label = lv_label_create(container);
+    lv_label_set_text(label, "Priority Fee");
+    lv_obj_align(label, LV_ALIGN_TOP_LEFT, 24, 16);
+    SetTitleLabelStyle(label);
+
+    label = lv_label_create(container);
+    lv_label_set_text(label, networkFee->priority_fee);
+    lv_obj_align(label, LV_ALIGN_TOP_LEFT, 151, 16);
+    SetContentLableStyle(label);
+
+    label = lv_label_create(container);
+    lv_label_set_text(label, "Network Fee");
+    lv_obj_align(label, LV_ALIGN_TOP_LEFT, 24, 54);
+    SetTitleLabelStyle(label);
+
+    label = lv_label_create(container);
+    lv_label_set_text(label, networkFee->total_fee);
+    lv_obj_align(label, LV_ALIGN_TOP_LEFT, 151, 54);
+    SetContentLableStyle(label);
+}
 
 void GuiShowSolTxOverview(lv_obj_t *parent, void *totalData)
 {
@@ -1425,6 +1567,59 @@ void GuiShowSolTxOverview(lv_obj_t *parent, void *totalData)
     } else {
         GuiShowSolTxInstructionsOverview(parent, overviewData);
     }
+    GuiShowSolTxNetworkFeeOverview(parent, overviewData->network_fee);
+}
+
+void GuiShowSolTxBalanceChanges(lv_obj_t *parent, void *totalData)
+{
+    lv_obj_set_size(parent, 408, 444);
+    lv_obj_add_flag(parent, LV_OBJ_FLAG_SCROLLABLE);
+    lv_obj_add_flag(parent, LV_OBJ_FLAG_CLICKABLE);
+    DisplaySolanaTx *txData = (DisplaySolanaTx*)totalData;
+    PtrT_VecFFI_DisplaySolanaTxBalanceChange changes = txData->balance_changes->changes;
+    PtrT_VecFFI_DisplaySolanaTxUnknownEffect unknownEffects = txData->balance_changes->unknown_effects;
+
+    int containerYOffset = 0;
+    for (int i = 0; i < changes->size; i++) {
+        lv_obj_t *container = GuiCreateAutoHeightContainer(parent, 408, 16);
+        lv_obj_align(container, LV_ALIGN_DEFAULT, 0, containerYOffset);
+
+        lv_obj_t *label = GuiCreateTextLabel(container, changes->data[i].amount);
+        lv_obj_set_style_text_font(label, &openSansEnLittleTitle, LV_PART_MAIN);
+        lv_obj_set_style_text_color(label, lv_color_hex(0xF5870A), LV_PART_MAIN);
+        lv_obj_align(label, LV_ALIGN_TOP_LEFT, 24, 0);
+
+        lv_obj_t *lastView = GuiShowSolLabelRow(container, label, "Account", changes->data[i].account);
+        lastView = GuiShowSolLabelRow(container, lastView, "Token", changes->data[i].token);
+        lastView = GuiShowSolLabelRow(container, lastView, "Mint", changes->data[i].mint);
+        if (changes->data[i].unknown_effect) {
+            label = GuiCreateIllustrateLabel(container, "The account is also used by an instruction whose effect can't be previewed.");
+            lv_obj_set_width(label, 360);
+            lv_obj_set_style_text_color(label, lv_color_hex(0xF55831), LV_PART_MAIN);
+            lv_obj_align_to(label, lastView, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 16);
+        }
+        lv_obj_update_layout(container);
+        containerYOffset += lv_obj_get_height(container) + 16;
+    }
+
+    if (unknownEffects->size == 0) {
+        return;
+    }
+    lv_obj_t *container = GuiCreateAutoHeightContainer(parent, 408, 16);
+    lv_obj_align(container, LV_ALIGN_DEFAULT, 0, containerYOffset);
+    lv_obj_t *label = GuiCreateTextLabel(container, "Not Previewed");
+    lv_obj_set_style_text_color(label, lv_color_hex(0xF55831), LV_PART_MAIN);
+    lv_obj_align(label, LV_ALIGN_TOP_LEFT, 24, 0);
+    lv_obj_t *lastView = label;
+    for (int i = 0; i < unknownEffects->size; i++) {
+        char instruction[BUFFER_SIZE_128] = {0};
+        snprintf_s(instruction, BUFFER_SIZE_128, "%s %s", unknownEffects->data[i].program, unknownEffects->data[i].method);
+        label = GuiCreateIllustrateLabel(container, instruction);
+        lv_obj_set_width(label, 360);
+        lv_obj_set_style_text_color(label, WHITE_COLOR, LV_PART_MAIN);
+        lv_obj_align_to(label, lastView, LV_ALIGN_OUT_BOTTOM_LEFT, 0, 8);
+        lastView = label;
+    }
 }
 
 void GuiShowSolTxDetail(lv_obj_t *parent, void *totalData)
@@ -12,9 +12,12 @@ void GetSolMessageType(void *indata, void *param, uint32_t maxLen);
 void GetSolMessageFrom(void *indata, void *param, uint32_t maxLen);
 void GetSolMessageUtf8(void *indata, void *param, uint32_t maxLen);
 void GetSolMessageRaw(void *indata, void *param, uint32_t maxLen);
+void GuiShowSolSignInMessage(lv_obj_t *parent, void *totalData);
+void GuiShowSolOffchainMessage(lv_obj_t *parent, void *totalData);
 
 void FreeSolMemory(void);
 
 void GuiShowSolTxOverview(lv_obj_t *parent, void *g_totalData);
 void GuiShowSolTxDetail(lv_obj_t *parent, void *g_totalData);
+void GuiShowSolTxBalanceChanges(lv_obj_t *parent, void *g_totalData);
 #endif
\ No newline at end of file
@@ -218,14 +218,18 @@ static lv_obj_t *CreateOverviewDestinationView(lv_obj_t *parent, DisplayTonTrans
     } else {
         xPub = GetCurrentAccountPublicKey(XPUB_TYPE_TON_BIP39);
     }
-    SimpleResponse_c_char *from = ton_get_address(xPub);
+    // show the address of the wallet contract the signing message was built for
+    SimpleResponse_c_char *from = ton_get_address_by_version(xPub, data->wallet_version);
 
     lv_obj_t *label = GuiCreateIllustrateLabel(container, _("From"));
     lv_obj_align(label, LV_ALIGN_TOP_LEFT, 24, 16);
     lv_obj_set_style_text_opa(label, LV_OPA_64, LV_PART_MAIN);
 
     char fromAddress[128] = {0};
-    snprintf_s(fromAddress, 128, "%.22s\n%s", from->data, &from->data[22]);
+    if (from->error_code == 0) {
+        snprintf_s(fromAddress, 128, "%.22s\n%s", from->data, &from->data[22]);
+    }
+    free_simple_response_c_char(from);
 
     label = GuiCreateIllustrateLabel(container, fromAddress);
     lv_obj_align(label, LV_ALIGN_TOP_LEFT, 24, 54);
@@ -7,17 +7,20 @@
 #include "secret_cache.h"
 #include "screen_manager.h"
 #include "account_manager.h"
+#include "gui_constants.h"
 
 static bool g_isMulti = false;
 static URParseResult *g_urResult = NULL;
 static URParseMultiResult *g_urMultiResult = NULL;
 static void *g_parseResult = NULL;
+static ViewType g_viewType = ViewTypeUnKnown;
 
 void GuiSetTrxUrData(URParseResult *urResult, URParseMultiResult *urMultiResult, bool multi)
 {
     g_urResult = urResult;
     g_urMultiResult = urMultiResult;
     g_isMulti = multi;
+    g_viewType = g_isMulti ? g_urMultiResult->t : g_urResult->t;
 }
 
 #define CHECK_FREE_PARSE_RESULT(result)                                                              \
@@ -36,7 +39,12 @@ void *GuiGetTrxData(void)
     char *trxXpub = GetCurrentAccountPublicKey(XPUB_TYPE_TRX);
     GetMasterFingerPrint(mfp);
     do {
-        PtrT_TransactionParseResult_DisplayTron parseResult = tron_parse_keystone(data, urType, mfp, sizeof(mfp), trxXpub);
+        PtrT_TransactionParseResult_DisplayTron parseResult = NULL;
+        if (g_viewType == TronRawTx) {
+            parseResult = tron_parse_raw_tx(data, mfp, sizeof(mfp), trxXpub);
+        } else {
+            parseResult = tron_parse_keystone(data, urType, mfp, sizeof(mfp), trxXpub);
+        }
         CHECK_CHAIN_BREAK(parseResult);
         g_parseResult = (void *)parseResult;
     } while (0);
@@ -51,6 +59,9 @@ PtrT_TransactionCheckResult GuiGetTrxCheckResult(void)
     QRCodeType urType = g_isMulti ? g_urMultiResult->ur_type : g_urResult->ur_type;
     char *trxXpub = GetCurrentAccountPublicKey(XPUB_TYPE_TRX);
     GetMasterFingerPrint(mfp);
+    if (g_viewType == TronRawTx) {
+        return tron_check_raw_tx(data, mfp, sizeof(mfp), trxXpub);
+    }
     return tron_check_keystone(data, urType, mfp, sizeof(mfp), trxXpub);
 }
 void FreeTrxMemory(void)
@@ -108,6 +119,84 @@ void GetTrxToken(void *indata, void *param, uint32_t maxLen)
     strcpy_s((char *)indata,  maxLen, trx->detail->token);
 }
 
+bool GetTrxResourceExist(void *indata, void *param)
+{
+    DisplayTron *trx = (DisplayTron *)param;
+    return trx->detail->resource != NULL;
+}
+
+void GetTrxResource(void *indata, void *param, uint32_t maxLen)
+{
+    DisplayTron *trx = (DisplayTron *)param;
+    strcpy_s((char *)indata, maxLen, trx->detail->resource);
+}
+
+bool GetTrxLockPeriodExist(void *indata, void *param)
+{
+    DisplayTron *trx = (DisplayTron *)param;
+    return trx->detail->lock_period != NULL;
+}
+
+void GetTrxLockPeriod(void *indata, void *param, uint32_t maxLen)
+{
+    DisplayTron *trx = (DisplayTron *)param;
+    snprintf_s((char *)indata, maxLen, "%s Blocks", trx->detail->lock_period);
+}
+
+bool GetTrxFeeLimitExist(void *indata, void *param)
+{
+    DisplayTron *trx = (DisplayTron *)param;
+    return trx->detail->fee_limit != NULL;
+}
+
+void GetTrxFeeLimit(void *indata, void *param, uint32_t maxLen)
+{
+    DisplayTron *trx = (DisplayTron *)param;
+    strcpy_s((char *)indata, maxLen, trx->detail->fee_limit);
+}
+
+bool GetTrxVotesExist(void *indata, void *param)
+{
+    DisplayTron *trx = (DisplayTron *)param;
+    return trx->detail->votes->size > 0;
+}
+
+void GetTrxVotes(void *indata, void *param, uint32_t maxLen)
+{
+    DisplayTron *trx = (DisplayTron *)param;
+    char *text = (char *)indata;
+    char line[BUFFER_SIZE_128];
+    text[0] = '\0';
+    for (uint32_t i = 0; i < trx->detail->votes->size; i++) {
+        DisplayTronVote *vote = &trx->detail->votes->data[i];
+        snprintf_s(line, sizeof(line), "%s%s\n%s Votes", i == 0 ? "" : "\n", vote->address, vote->count);
+        strcat_s(text, maxLen, line);
+    }
+}
+
+int GetTrxVotesLen(void *param)
+{
+    DisplayTron *trx = (DisplayTron *)param;
+    int len = 0;
+    for (uint32_t i = 0; i < trx->detail->votes->size; i++) {
+        DisplayTronVote *vote = &trx->detail->votes->data[i];
+        len += strlen(vote->address) + strlen(vote->count) + 8;
+    }
+    return len + 1;
+}
+
+void GetTrxVotesSize(uint16_t *width, uint16_t *height, void *param)
+{
+    DisplayTron *trx = (DisplayTron *)param;
+    *width = 408;
+    *height = PADDING;
+    *height += TEXT_LINE_HEIGHT; // "Votes"
+    *height += GAP;
+    // the witness address may wrap, the count takes one more line
+    *height += 3 * TEXT_LINE_HEIGHT * trx->detail->votes->size;
+    *height += PADDING;
+}
+
 UREncodeResult *GuiGetTrxSignQrCodeData(void)
 {
     bool enable = IsPreviousLockScreenEnable();
@@ -122,7 +211,11 @@ UREncodeResult *GuiGetTrxSignQrCodeData(void)
         GetAccountSeed(GetCurrentAccountIndex(), seed, SecretCacheGetPassword());
         char *xPub = GetCurrentAccountPublicKey(XPUB_TYPE_TRX);
         int len = GetMnemonicType() == MNEMONIC_TYPE_BIP39 ? sizeof(seed) : GetCurrentAccountEntropyLen();
-        encodeResult = tron_sign_keystone(data, urType, mfp, sizeof(mfp), xPub, SOFTWARE_VERSION, seed, len);
+        if (g_viewType == TronRawTx) {
+            encodeResult = tron_sign_raw_tx(data, mfp, sizeof(mfp), xPub, seed, len);
+        } else {
+            encodeResult = tron_sign_keystone(data, urType, mfp, sizeof(mfp), xPub, SOFTWARE_VERSION, seed, len);
+        }
         ClearSecretCache();
         CHECK_CHAIN_BREAK(encodeResult);
     } while (0);
@@ -12,4 +12,14 @@ bool GetTrxContractExist(void *indata, void *param);
 void GetTrxContract(void *indata, void *param, uint32_t maxLen);
 bool GetTrxTokenExist(void *indata, void *param);
 void GetTrxToken(void *indata, void *param, uint32_t maxLen);
+bool GetTrxResourceExist(void *indata, void *param);
+void GetTrxResource(void *indata, void *param, uint32_t maxLen);
+bool GetTrxLockPeriodExist(void *indata, void *param);
+void GetTrxLockPeriod(void *indata, void *param, uint32_t maxLen);
+bool GetTrxFeeLimitExist(void *indata, void *param);
+void GetTrxFeeLimit(void *indata, void *param, uint32_t maxLen);
+bool GetTrxVotesExist(void *indata, void *param);
+void GetTrxVotes(void *indata, void *param, uint32_t maxLen);
+int GetTrxVotesLen(void *param);
+void GetTrxVotesSize(uint16_t *width, uint16_t *height, void *param);
 UREncodeResult *GuiGetTrxSignQrCodeData(void);
@@ -3,6 +3,7 @@
 #include "gui_views.h"
 #include "gui_export_pubkey_widgets.h"
 #include "gui_animating_qrcode.h"
+#include "gui_lock_widgets.h"
 
 int32_t GuiExportPubkeyViewEventProcess(void *self, uint16_t usEvent, void *param, uint16_t usLen)
 {
@@ -27,6 +28,24 @@ int32_t GuiExportPubkeyViewEventProcess(void *self, uint16_t usEvent, void *para
     case SIG_BACKGROUND_UR_UPDATE:
         GuiAnimatingQRCodeUpdate((char*)param, usLen);
         break;
+#ifdef BTC_ONLY
+    case SIG_VERIFY_PASSWORD_PASS:
+        if (param != NULL) {
+            uint16_t sig = *(uint16_t *)param;
+            if (sig == SIG_LOCK_VIEW_SCREEN_GO_HOME_PASS) {
+                GuiLockScreenToHome();
+                return SUCCESS_CODE;
+            }
+        }
+        GuiExportPubkeyVerifyPasswordSuccess();
+        break;
+    case SIG_VERIFY_PASSWORD_FAIL:
+        if (param == NULL) {
+            return ERR_GUI_ERROR;
+        }
+        GuiExportPubkeyPasswordErrorCount(param);
+        break;
+#endif
     default:
         return ERR_GUI_UNHANDLED;
     }
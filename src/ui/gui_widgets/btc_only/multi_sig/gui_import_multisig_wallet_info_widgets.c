@@ -140,14 +140,27 @@ void GuiImportMultisigWalletInfoVerifyPasswordSuccess(void)
     GetAccountSeed(GetCurrentAccountIndex(), seed, SecretCacheGetPassword());
     uint8_t mfp[4] = {0};
     GetMasterFingerPrint(mfp);
-    Response_MultiSigWallet *response = parse_and_verify_multisig_config(seed, len, g_wallet->config_text, mfp, 4);
-    if (response->error_code != 0) {
-        printf("errorMessage: %s\r\n", response->error_message);
-        g_noticeWindow = GuiCreateErrorCodeWindow(ERR_MULTISIG_WALLET_CONFIG_INVALID, &g_noticeWindow, GuiCloseWarnningDialog);
-        free_MultiSigWallet(response->data);
-        return;
+    MultiSigWalletItem_t *wallet = NULL;
+    if (strcmp(g_wallet->format, FORMAT_POLICY) == 0) {
+        SimpleResponse_c_char *response = btc_register_wallet_policy(seed, len, g_wallet->config_text, mfp, 4);
+        if (response->error_code != 0) {
+            printf("errorMessage: %s\r\n", response->error_message);
+            g_noticeWindow = GuiCreateErrorCodeWindow(ERR_MULTISIG_WALLET_CONFIG_INVALID, &g_noticeWindow, GuiCloseWarnningDialog);
+            free_simple_response_c_char(response);
+            return;
+        }
+        wallet = AddWalletPolicyToCurrentAccount(g_wallet, response->data, SecretCacheGetPassword());
+        free_simple_response_c_char(response);
+    } else {
+        Response_MultiSigWallet *response = parse_and_verify_multisig_config(seed, len, g_wallet->config_text, mfp, 4);
+        if (response->error_code != 0) {
+            printf("errorMessage: %s\r\n", response->error_message);
+            g_noticeWindow = GuiCreateErrorCodeWindow(ERR_MULTISIG_WALLET_CONFIG_INVALID, &g_noticeWindow, GuiCloseWarnningDialog);
+            free_MultiSigWallet(response->data);
+            return;
+        }
+        wallet = AddMultisigWalletToCurrentAccount(g_wallet, SecretCacheGetPassword());
     }
-    MultiSigWalletItem_t *wallet = AddMultisigWalletToCurrentAccount(g_wallet, SecretCacheGetPassword());
     if (wallet == NULL) {
         printf("multi sigwallet not found\n");
         return;
@@ -182,7 +195,18 @@ static uint32_t prepareWalletBySDCard(char *walletConfig)
 {
     uint8_t mfp[4];
     GetMasterFingerPrint(mfp);
+    // BIP-129 descriptor records, only unencrypted ones since there is no token input
+    if (strncmp(walletConfig, "BSMS 1.0", strlen("BSMS 1.0")) == 0) {
+        return processResult(import_multi_sig_wallet_by_bsms(walletConfig, "00", mfp, 4));
+    }
     Ptr_Response_MultiSigWallet result = import_multi_sig_wallet_by_file(walletConfig, mfp, 4);
+    if (result->error_code != 0) {
+        // BIP-388 wallet policies are imported from the same file picker
+        Ptr_Response_MultiSigWallet policyResult = import_wallet_policy_by_file(walletConfig, mfp, 4);
+        if (policyResult->error_code == 0) {
+            return processResult(policyResult);
+        }
+    }
     return processResult(result);
 }
 
@@ -64,7 +64,19 @@ static void GuiWriteSDCardHandler(lv_event_t *e)
     GUI_DEL_OBJ(g_noticeWindow);
 
     char *filename = lv_event_get_user_data(e);
-    int ret = FileWrite(filename, g_multisigWalletItem->walletConfig, strnlen(g_multisigWalletItem->walletConfig, MAX_WALLET_CONTENT_LEN - 1));
+    int ret = -1;
+    if (IsWalletPolicyItem(g_multisigWalletItem)) {
+        ret = FileWrite(filename, g_multisigWalletItem->walletConfig, strnlen(g_multisigWalletItem->walletConfig, MAX_WALLET_CONTENT_LEN - 1));
+    } else {
+        // wallets imported from Caravan, Specter or a descriptor are written as a Coldcard file
+        uint8_t mfp[4];
+        GetMasterFingerPrint(mfp);
+        SimpleResponse_c_char *result = export_multi_sig_wallet_by_file(mfp, sizeof(mfp), g_multisigWalletItem->walletConfig, Coldcard);
+        if (result->error_code == 0) {
+            ret = FileWrite(filename, result->data, strnlen(result->data, MAX_WALLET_CONTENT_LEN - 1));
+        }
+        free_simple_response_c_char(result);
+    }
     if (ret == 0) {
         GuiShowSDCardExportSuccess();
     } else {
@@ -107,7 +119,9 @@ static void GuiShowSDCardExport()
     g_filename = SRAM_MALLOC(MAX_WALLET_NAME_LEN);
     uint8_t mfp[4];
     GetMasterFingerPrint(mfp);
-    Ptr_Response_MultiSigWallet result = import_multi_sig_wallet_by_file(g_multisigWalletItem->walletConfig, mfp, 4);
+    Ptr_Response_MultiSigWallet result = IsWalletPolicyItem(g_multisigWalletItem) ?
+                                         import_wallet_policy_by_file(g_multisigWalletItem->walletConfig, mfp, 4) :
+                                         import_multi_sig_wallet_by_file(g_multisigWalletItem->walletConfig, mfp, 4);
     if (result->error_code == 0) {
         snprintf_s(g_filename, MAX_WALLET_NAME_LEN, "%s_%s_%d.txt", g_multisigWalletItem->name, result->data->policy, GetCurrentStampTime());
         free_MultiSigWallet(result->data);
@@ -183,6 +197,9 @@ static UREncodeResult *GuiGenerateUR()
 {
     uint8_t mfp[4];
     GetMasterFingerPrint(mfp);
+    if (IsWalletPolicyItem(g_multisigWalletItem)) {
+        return btc_export_wallet_policy_by_ur(g_multisigWalletItem->walletConfig);
+    }
     return export_multi_sig_wallet_by_ur(mfp, 4, g_multisigWalletItem->walletConfig);
 }
 
@@ -290,7 +307,12 @@ static void ModelGenerateAddress(char *address, uint32_t maxLen)
 {
     uint8_t mfp[4];
     GetMasterFingerPrint(mfp);
-    SimpleResponse_c_char *result = generate_address_for_multisig_wallet_config(g_multisigWalletItem->walletConfig, 0, 0, mfp, 4);
+    SimpleResponse_c_char *result = NULL;
+    if (IsWalletPolicyItem(g_multisigWalletItem)) {
+        result = btc_generate_address_for_wallet_policy(g_multisigWalletItem->walletConfig, false, 0);
+    } else {
+        result = generate_address_for_multisig_wallet_config(g_multisigWalletItem->walletConfig, 0, 0, mfp, 4);
+    }
     if (result->error_code != 0) {
         printf("errorMessage: %s\r\n", result->error_message);
         GuiCloseCurrentWorkingView();
@@ -303,7 +325,12 @@ void ModelGenerateMultiSigAddress(char *address, uint32_t maxLen, char *walletCo
 {
     uint8_t mfp[4];
     GetMasterFingerPrint(mfp);
-    SimpleResponse_c_char *result = generate_address_for_multisig_wallet_config(walletConfig, 0, index, mfp, 4);
+    SimpleResponse_c_char *result = NULL;
+    if (IsWalletPolicyItem(GetDefaultMultisigWallet())) {
+        result = btc_generate_address_for_wallet_policy(walletConfig, false, index);
+    } else {
+        result = generate_address_for_multisig_wallet_config(walletConfig, 0, index, mfp, 4);
+    }
     if (result->error_code != 0) {
         printf("errorMessage: %s\r\n", result->error_message);
         GuiCloseCurrentWorkingView();
@@ -13,8 +13,12 @@
 #include "gui_animating_qrcode.h"
 #include "keystore.h"
 #include "gui_home_widgets.h"
+#include "gui_keyboard_hintbox.h"
+#include "account_manager.h"
+#include "secret_cache.h"
 static lv_obj_t *g_noticeWindow = NULL;
 static char *g_xpubConfigName = NULL;
+static KeyboardWidget_t *g_keyboardWidget = NULL;
 #endif
 
 typedef enum {
@@ -153,10 +157,48 @@ static void GuiWriteToMicroCardHandler(lv_event_t *e)
     int ret = FileWrite(g_xpubConfigName, xPubBuff, len);
     if (ret) {
         g_noticeWindow =  GuiCreateErrorCodeWindow(ERR_EXPORT_FILE_TO_MICRO_CARD_FAILED, &g_noticeWindow, NULL);
+    } else if (g_isMultisig) {
+        // the BIP-129 key record next to it is signed by the exported key
+        g_keyboardWidget = GuiCreateKeyboardWidget(g_pageWidget->contentZone);
+        SetKeyboardWidgetSelf(g_keyboardWidget, &g_keyboardWidget);
+        static uint16_t sig = SIG_MULTISIG_WALLET_IMPORT_VERIFY_PASSWORD;
+        SetKeyboardWidgetSig(g_keyboardWidget, &sig);
     }
     EXT_FREE(xPubBuff);
 }
 
+void GuiExportPubkeyVerifyPasswordSuccess(void)
+{
+    uint8_t seed[64] = {0};
+    int len = (GetMnemonicType() == MNEMONIC_TYPE_BIP39) ? sizeof(seed) : GetCurrentAccountEntropyLen();
+    GetAccountSeed(GetCurrentAccountIndex(), seed, SecretCacheGetPassword());
+    ClearSecretCache();
+    GuiDeleteKeyboardWidget(g_keyboardWidget);
+
+    // unencrypted ("00" token), there is no way to enter the token of the coordinator
+    SimpleResponse_c_char *result = export_bsms_key_record(seed, len, g_btcMultisigPathList[GetPathType()].path, "00", GetWalletName());
+    memset_s(seed, sizeof(seed), 0, sizeof(seed));
+    int ret = -1;
+    if (result->error_code == 0) {
+        char fileName[BUFFER_SIZE_64] = {0};
+        uint8_t mfp[4];
+        GetMasterFingerPrint(mfp);
+        snprintf_s(fileName, sizeof(fileName), "%s-%02X%02X%02X%02X.bsms", GetWalletName(), mfp[0], mfp[1], mfp[2], mfp[3]);
+        ret = FileWrite(fileName, result->data, strnlen_s(result->data, SIMPLERESPONSE_C_CHAR_MAX_LEN));
+    } else {
+        printf("errorMessage: %s\r\n", result->error_message);
+    }
+    free_simple_response_c_char(result);
+    if (ret) {
+        g_noticeWindow = GuiCreateErrorCodeWindow(ERR_EXPORT_FILE_TO_MICRO_CARD_FAILED, &g_noticeWindow, NULL);
+    }
+}
+
+void GuiExportPubkeyPasswordErrorCount(void *param)
+{
+    GuiShowErrorNumber(g_keyboardWidget, (PasswordVerifyResult_t *)param);
+}
+
 static void GuiExportXpubToMicroCard(void)
 {
     g_noticeWindow = GuiCreateConfirmHintBox(&imgSdCardL, _("wallet_profile_export_to_sdcard_title"), _("about_info_export_file_name"), g_xpubConfigName, _("got_it"), ORANGE_COLOR);
@@ -274,6 +316,9 @@ void GuiExportPubkeyDeInit(void)
     GuiAnimatingQRCodeDestroyTimer();
     GUI_DEL_OBJ(g_noticeWindow)
     SRAM_FREE(g_xpubConfigName);
+    if (g_keyboardWidget != NULL) {
+        GuiDeleteKeyboardWidget(g_keyboardWidget);
+    }
 #endif
     if (g_pageWidget != NULL) {
         DestroyPageWidget(g_pageWidget);
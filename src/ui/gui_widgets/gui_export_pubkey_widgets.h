@@ -8,5 +8,7 @@ void GuiExportPubkeyInit(uint8_t chain);
 void GuiExportPubkeyDeInit(void);
 void OpenExportViewHandler(lv_event_t *e);
 void OpenExportMultisigViewHandler(lv_event_t *e);
+void GuiExportPubkeyVerifyPasswordSuccess(void);
+void GuiExportPubkeyPasswordErrorCount(void *param);
 
 #endif
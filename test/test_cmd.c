@@ -1931,7 +1931,7 @@ static void RustTestSignPSBT(int argc, char *argv[])
     GetAccountSeed(GetCurrentAccountIndex(), seed, SecretCacheGetPassword());
     uint8_t mfp[4] = {0};
     GetMasterFingerPrint(mfp);
-    UREncodeResult *result = btc_sign_psbt(crypto_psbt, seed, len, mfp, sizeof(mfp));
+    UREncodeResult *result = btc_sign_psbt(crypto_psbt, seed, len, mfp, sizeof(mfp), false);
     printf("is multi part: %d\r\n", result->is_multi_part);
     printf("data, %s\r\n", result->data);
 }